@@ -0,0 +1,83 @@
+//! Shared vs worker-local request metrics under contention:
+//! `cargo bench --bench metric_batching`.
+//!
+//! Each iteration has every thread record a batch of requests (counter,
+//! status code and latency), either straight into the shared metrics or into
+//! a `WorkerMetrics` that is flushed at the end of the batch.
+
+use std::sync::{Arc, Barrier};
+use std::thread;
+use std::time::Duration;
+
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+use rust_loadtest::metric_batch::WorkerMetrics;
+use rust_loadtest::metrics::{REQUEST_DURATION_SECONDS, REQUEST_STATUS_CODES, REQUEST_TOTAL};
+
+const REQUESTS_PER_THREAD: usize = 10_000;
+const LABELS: [&str; 5] = ["local", "", "", "bench-node", "bench-run"];
+
+fn shared(threads: usize) {
+    run_threads(threads, || {
+        for _ in 0..REQUESTS_PER_THREAD {
+            REQUEST_TOTAL.with_label_values(&LABELS).inc();
+            REQUEST_STATUS_CODES
+                .with_label_values(&["200", LABELS[0], LABELS[1], LABELS[2], LABELS[3], LABELS[4]])
+                .inc();
+            REQUEST_DURATION_SECONDS
+                .with_label_values(&LABELS)
+                .observe(0.012);
+        }
+    });
+}
+
+fn batched(threads: usize) {
+    run_threads(threads, || {
+        let [region, zone, tenant, node_id, run_id] = LABELS;
+        let mut metrics = WorkerMetrics::with_interval(
+            region,
+            zone,
+            tenant,
+            node_id,
+            run_id,
+            Duration::from_millis(250),
+        );
+        for _ in 0..REQUESTS_PER_THREAD {
+            metrics.request();
+            metrics.status("200");
+            metrics.duration(0.012);
+            metrics.maybe_flush();
+        }
+    });
+}
+
+fn run_threads(threads: usize, work: fn()) {
+    let barrier = Arc::new(Barrier::new(threads));
+    let handles: Vec<_> = (0..threads)
+        .map(|_| {
+            let barrier = Arc::clone(&barrier);
+            thread::spawn(move || {
+                barrier.wait();
+                work();
+            })
+        })
+        .collect();
+    for handle in handles {
+        handle.join().unwrap();
+    }
+}
+
+fn metric_batching(c: &mut Criterion) {
+    let mut group = c.benchmark_group("request_metrics");
+    for threads in [1, 4, 16] {
+        group.bench_with_input(BenchmarkId::new("shared", threads), &threads, |b, &t| {
+            b.iter(|| shared(t))
+        });
+        group.bench_with_input(BenchmarkId::new("batched", threads), &threads, |b, &t| {
+            b.iter(|| batched(t))
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, metric_batching);
+criterion_main!(benches);
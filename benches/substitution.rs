@@ -0,0 +1,42 @@
+//! Variable substitution cost per request: `cargo bench --bench substitution`.
+//!
+//! Compares the per-request `substitute_variables` path with rendering
+//! templates parsed once per plan, for a static value and for one with
+//! variables.
+
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use rust_loadtest::scenario::ScenarioContext;
+use rust_loadtest::substitution::Template;
+
+const STATIC_BODY: &str = r#"{"query": "shoes", "page": 1, "sort": "price"}"#;
+const DYNAMIC_BODY: &str =
+    r#"{"cart": "${cart_id}", "user": "${user_id}", "items": [{"sku": "$sku", "qty": 2}]}"#;
+
+fn context() -> ScenarioContext {
+    let mut ctx = ScenarioContext::new();
+    for i in 0..20 {
+        ctx.set_variable(format!("var_{}", i), format!("value-{}", i));
+    }
+    ctx.set_variable("cart_id".to_string(), "cart-8f2c".to_string());
+    ctx.set_variable("user_id".to_string(), "user-1234".to_string());
+    ctx.set_variable("sku".to_string(), "SKU-42".to_string());
+    ctx
+}
+
+fn substitution(c: &mut Criterion) {
+    let ctx = context();
+    for (name, body) in [("static", STATIC_BODY), ("dynamic", DYNAMIC_BODY)] {
+        let mut group = c.benchmark_group(name);
+        group.bench_function("substitute_variables", |b| {
+            b.iter(|| ctx.substitute_variables(black_box(body)))
+        });
+        let template = Template::parse(body);
+        group.bench_function("template_render", |b| {
+            b.iter(|| black_box(&template).render(&ctx).len())
+        });
+        group.finish();
+    }
+}
+
+criterion_group!(benches, substitution);
+criterion_main!(benches);
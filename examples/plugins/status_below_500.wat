@@ -0,0 +1,71 @@
+;; Minimal assertion plugin (plugin ABI version 1).
+;;
+;; Passes when the response status code is below 500. Load it with:
+;;
+;;   cargo build --release --features wasm-plugins
+;;   PLUGIN_PATHS=examples/plugins/status_below_500.wat ./target/release/rust_loadtest
+;;
+;; and reference it from a scenario step:
+;;
+;;   assertions:
+;;     - type: plugin
+;;       plugin: status_below_500
+;;
+;; Plugins are usually written in Rust/TinyGo/AssemblyScript and compiled to
+;; wasm32; this one is hand-written WAT so it needs no toolchain.
+(module
+  (memory (export "memory") 1)
+
+  ;; Canned JSON outcomes returned to the host.
+  (data (i32.const 0) "{\"passed\":true}")
+  (data (i32.const 32) "{\"passed\":false,\"message\":\"status code >= 500\"}")
+
+  ;; Host input is written at a fixed offset after the canned outcomes.
+  (global $input_base i32 (i32.const 1024))
+
+  (func (export "rlt_abi_version") (result i32)
+    i32.const 1)
+
+  ;; CAP_ASSERTION
+  (func (export "rlt_capabilities") (result i32)
+    i32.const 2)
+
+  ;; Single-buffer allocator: every call reuses the same region, growing
+  ;; memory when the input does not fit. The host copies its input in right
+  ;; after allocating and the plugin never keeps it across calls.
+  (func (export "rlt_alloc") (param $len i32) (result i32)
+    (local $needed i32)
+    ;; pages needed = ceil((input_base + len) / 64KiB)
+    global.get $input_base
+    local.get $len
+    i32.add
+    i32.const 65535
+    i32.add
+    i32.const 16
+    i32.shr_u
+    memory.size
+    i32.sub
+    local.tee $needed
+    i32.const 0
+    i32.gt_s
+    if
+      local.get $needed
+      memory.grow
+      drop
+    end
+    global.get $input_base)
+
+  ;; Returns (ptr << 32) | len of the JSON outcome. The JSON input at
+  ;; ($ptr, $len) is ignored: only the status code matters here.
+  (func (export "rlt_assert")
+    (param $status i32) (param $elapsed_ms i64) (param $ptr i32) (param $len i32)
+    (result i64)
+    local.get $status
+    i32.const 500
+    i32.lt_u
+    if (result i64)
+      i64.const 15              ;; ptr 0, len 15
+    else
+      i64.const 0x200000002f    ;; ptr 32, len 47
+    end)
+)
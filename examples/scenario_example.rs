@@ -8,7 +8,7 @@
 use rust_loadtest::executor::{ScenarioExecutor, SessionStore};
 use rust_loadtest::scenario::{
     Assertion, Extractor, RequestConfig, Scenario, ScenarioContext, Step, ThinkTime,
-    VariableExtraction,
+    VariableExtraction, VariableScope,
 };
 use std::collections::HashMap;
 use std::time::Duration;
@@ -97,11 +97,25 @@ fn create_shopping_scenario() -> Scenario {
                     body: None,
                     body_size: None,
                     headers: HashMap::new(),
+                    protobuf: None,
+                    host: None,
                 },
                 extractions: vec![],
                 assertions: vec![Assertion::StatusCode(200)],
                 cache: None,
                 think_time: Some(ThinkTime::Fixed(Duration::from_millis(500))),
+                tags: HashMap::new(),
+                paginate: None,
+                max_concurrent: None,
+                slo: None,
+                shared: vec![],
+                rendezvous: None,
+                conditional: false,
+                expected_status: Vec::new(),
+                patterns: Default::default(),
+                templates: Default::default(),
+                idempotency_key: None,
+                tcp_check: None,
             },
             // Step 2: Browse products and extract first product ID
             Step {
@@ -112,6 +126,8 @@ fn create_shopping_scenario() -> Scenario {
                     body: None,
                     body_size: None,
                     headers: HashMap::new(),
+                    protobuf: None,
+                    host: None,
                 },
                 extractions: vec![
                     // ⭐ Extract first product ID from JSON response
@@ -119,6 +135,8 @@ fn create_shopping_scenario() -> Scenario {
                     VariableExtraction {
                         name: "product_id".to_string(),
                         extractor: Extractor::JsonPath("$.products[0].id".to_string()),
+                        transforms: Vec::new(),
+                        scope: VariableScope::Iteration,
                     },
                 ],
                 assertions: vec![
@@ -127,6 +145,18 @@ fn create_shopping_scenario() -> Scenario {
                 ],
                 cache: None,
                 think_time: Some(ThinkTime::Fixed(Duration::from_secs(2))),
+                tags: HashMap::new(),
+                paginate: None,
+                max_concurrent: None,
+                slo: None,
+                shared: vec![],
+                rendezvous: None,
+                conditional: false,
+                expected_status: Vec::new(),
+                patterns: Default::default(),
+                templates: Default::default(),
+                idempotency_key: None,
+                tcp_check: None,
             },
             // Step 3: View product details using extracted product_id
             Step {
@@ -138,6 +168,8 @@ fn create_shopping_scenario() -> Scenario {
                     body: None,
                     body_size: None,
                     headers: HashMap::new(),
+                    protobuf: None,
+                    host: None,
                 },
                 extractions: vec![],
                 assertions: vec![
@@ -146,6 +178,18 @@ fn create_shopping_scenario() -> Scenario {
                 ],
                 cache: None,
                 think_time: Some(ThinkTime::Fixed(Duration::from_secs(3))),
+                tags: HashMap::new(),
+                paginate: None,
+                max_concurrent: None,
+                slo: None,
+                shared: vec![],
+                rendezvous: None,
+                conditional: false,
+                expected_status: Vec::new(),
+                patterns: Default::default(),
+                templates: Default::default(),
+                idempotency_key: None,
+                tcp_check: None,
             },
             // Step 4: Register user
             Step {
@@ -167,17 +211,33 @@ fn create_shopping_scenario() -> Scenario {
                         headers.insert("Content-Type".to_string(), "application/json".to_string());
                         headers
                     },
+                    protobuf: None,
+                    host: None,
                 },
                 extractions: vec![
                     // Extract auth token from response
                     VariableExtraction {
                         name: "auth_token".to_string(),
                         extractor: Extractor::JsonPath("$.token".to_string()),
+                        transforms: Vec::new(),
+                        scope: VariableScope::Iteration,
                     },
                 ],
                 assertions: vec![Assertion::StatusCode(201)],
                 cache: None,
                 think_time: Some(ThinkTime::Fixed(Duration::from_secs(1))),
+                tags: HashMap::new(),
+                paginate: None,
+                max_concurrent: None,
+                slo: None,
+                shared: vec![],
+                rendezvous: None,
+                conditional: false,
+                expected_status: Vec::new(),
+                patterns: Default::default(),
+                templates: Default::default(),
+                idempotency_key: None,
+                tcp_check: None,
             },
             // Step 5: Add item to cart (using auth token)
             Step {
@@ -202,14 +262,30 @@ fn create_shopping_scenario() -> Scenario {
                         );
                         headers
                     },
+                    protobuf: None,
+                    host: None,
                 },
                 extractions: vec![VariableExtraction {
                     name: "cart_id".to_string(),
                     extractor: Extractor::JsonPath("$.cart.id".to_string()),
+                    transforms: Vec::new(),
+                    scope: VariableScope::Iteration,
                 }],
                 assertions: vec![Assertion::StatusCode(201)],
                 cache: None,
                 think_time: Some(ThinkTime::Fixed(Duration::from_secs(2))),
+                tags: HashMap::new(),
+                paginate: None,
+                max_concurrent: None,
+                slo: None,
+                shared: vec![],
+                rendezvous: None,
+                conditional: false,
+                expected_status: Vec::new(),
+                patterns: Default::default(),
+                templates: Default::default(),
+                idempotency_key: None,
+                tcp_check: None,
             },
             // Step 6: View cart
             Step {
@@ -227,6 +303,8 @@ fn create_shopping_scenario() -> Scenario {
                         );
                         headers
                     },
+                    protobuf: None,
+                    host: None,
                 },
                 extractions: vec![],
                 assertions: vec![
@@ -235,7 +313,31 @@ fn create_shopping_scenario() -> Scenario {
                 ],
                 cache: None,
                 think_time: Some(ThinkTime::Fixed(Duration::from_secs(5))),
+                tags: HashMap::new(),
+                paginate: None,
+                max_concurrent: None,
+                slo: None,
+                shared: vec![],
+                rendezvous: None,
+                conditional: false,
+                expected_status: Vec::new(),
+                patterns: Default::default(),
+                templates: Default::default(),
+                idempotency_key: None,
+                tcp_check: None,
             },
         ],
+        network_profile: None,
+        tags: HashMap::new(),
+        max_concurrent: None,
+        depends_on: Vec::new(),
+        iterations: None,
+        retry: None,
+        assertion_sample_rate: 1.0,
+        client: None,
+        headers: Default::default(),
+        optional: false,
+        data_source: None,
+        rps: None,
     }
 }
@@ -0,0 +1,152 @@
+//! Abort thresholds for capacity discovery.
+//!
+//! The Exponential load model keeps doubling its rate, so something has to
+//! decide when the target has had enough. Once per second the health updater
+//! passes the last second's error rate and mean latency to an
+//! [`AbortMonitor`]; when either stays above the model's threshold for
+//! [`ABORT_WINDOW`], the test is stopped and the rate it had reached — the
+//! knee — is logged.
+
+use tokio::time::{Duration, Instant};
+
+/// How long a threshold must stay breached before the test is stopped, so a
+/// single slow second does not end the run.
+pub const ABORT_WINDOW: Duration = Duration::from_secs(5);
+
+/// Limits past which a growing load test stops. Unset limits never trip.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct AbortThreshold {
+    /// Percent of requests failing over the last second.
+    pub error_rate_percent: Option<f64>,
+    /// Mean request latency over the last second.
+    pub latency: Option<Duration>,
+}
+
+impl AbortThreshold {
+    /// True when at least one limit is set.
+    pub fn is_set(&self) -> bool {
+        self.error_rate_percent.is_some() || self.latency.is_some()
+    }
+
+    /// Describes the first limit the sample exceeds, if any.
+    fn breach(&self, error_rate_percent: f64, mean_latency_ms: Option<f64>) -> Option<String> {
+        if let Some(max) = self.error_rate_percent {
+            if error_rate_percent > max {
+                return Some(format!(
+                    "error rate {:.1}% above {}%",
+                    error_rate_percent, max
+                ));
+            }
+        }
+        match (self.latency, mean_latency_ms) {
+            (Some(max), Some(mean_ms)) if mean_ms > max.as_secs_f64() * 1000.0 => Some(format!(
+                "mean latency {:.0}ms above {}ms",
+                mean_ms,
+                max.as_millis()
+            )),
+            _ => None,
+        }
+    }
+}
+
+/// Tracks how long an [`AbortThreshold`] has been breached.
+#[derive(Debug, Default)]
+pub struct AbortMonitor {
+    breached_since: Option<Instant>,
+    tripped: bool,
+}
+
+impl AbortMonitor {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Records one second's sample. Returns the reason once, when a limit
+    /// has been exceeded for the whole [`ABORT_WINDOW`]; `mean_latency_ms`
+    /// is `None` when no request completed.
+    pub fn observe(
+        &mut self,
+        threshold: &AbortThreshold,
+        error_rate_percent: f64,
+        mean_latency_ms: Option<f64>,
+        now: Instant,
+    ) -> Option<String> {
+        let Some(reason) = threshold.breach(error_rate_percent, mean_latency_ms) else {
+            self.breached_since = None;
+            return None;
+        };
+        let since = *self.breached_since.get_or_insert(now);
+        if self.tripped || now.duration_since(since) < ABORT_WINDOW {
+            return None;
+        }
+        self.tripped = true;
+        Some(reason)
+    }
+
+    /// Clears the monitor for a new test.
+    pub fn reset(&mut self) {
+        self.breached_since = None;
+        self.tripped = false;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const THRESHOLD: AbortThreshold = AbortThreshold {
+        error_rate_percent: Some(5.0),
+        latency: Some(Duration::from_millis(500)),
+    };
+
+    #[test]
+    fn trips_once_after_sustained_breach() {
+        let mut monitor = AbortMonitor::new();
+        let t0 = Instant::now();
+        let at = |s: u64| t0 + Duration::from_secs(s);
+
+        assert_eq!(monitor.observe(&THRESHOLD, 10.0, Some(20.0), at(0)), None);
+        assert_eq!(monitor.observe(&THRESHOLD, 10.0, Some(20.0), at(4)), None);
+        assert_eq!(
+            monitor
+                .observe(&THRESHOLD, 10.0, Some(20.0), at(5))
+                .as_deref(),
+            Some("error rate 10.0% above 5%")
+        );
+        assert_eq!(monitor.observe(&THRESHOLD, 10.0, Some(20.0), at(6)), None);
+
+        monitor.reset();
+        assert_eq!(monitor.observe(&THRESHOLD, 0.0, Some(900.0), at(10)), None);
+        assert_eq!(
+            monitor
+                .observe(&THRESHOLD, 0.0, Some(900.0), at(15))
+                .as_deref(),
+            Some("mean latency 900ms above 500ms")
+        );
+    }
+
+    #[test]
+    fn recovery_restarts_the_window() {
+        let mut monitor = AbortMonitor::new();
+        let t0 = Instant::now();
+        let at = |s: u64| t0 + Duration::from_secs(s);
+
+        assert_eq!(monitor.observe(&THRESHOLD, 10.0, None, at(0)), None);
+        assert_eq!(monitor.observe(&THRESHOLD, 1.0, None, at(3)), None);
+        assert_eq!(monitor.observe(&THRESHOLD, 10.0, None, at(4)), None);
+        assert_eq!(monitor.observe(&THRESHOLD, 10.0, None, at(8)), None);
+        assert!(monitor.observe(&THRESHOLD, 10.0, None, at(9)).is_some());
+    }
+
+    #[test]
+    fn unset_threshold_never_trips() {
+        let mut monitor = AbortMonitor::new();
+        let threshold = AbortThreshold::default();
+        assert!(!threshold.is_set());
+        let t0 = Instant::now();
+        for s in 0..10 {
+            let now = t0 + Duration::from_secs(s);
+            assert_eq!(monitor.observe(&threshold, 100.0, Some(1e6), now), None);
+        }
+    }
+}
@@ -0,0 +1,270 @@
+//! Address parsing shared by `RESOLVE_TARGET_ADDR`, `METRICS_ADDR` and
+//! `CLUSTER_HEALTH_ADDR`.
+//!
+//! IPv6 literals contain colons, so these values can't be split on `:`. The
+//! port is always the part after the last colon, and an IPv6 address may be
+//! written in brackets as in URLs: `[::1]:8080`. Listen addresses require
+//! the brackets, since `::1:8080` is itself a valid IPv6 address; in a
+//! `RESOLVE_TARGET_ADDR` entry the port is mandatory, so they're optional.
+
+use std::net::{IpAddr, Ipv4Addr, SocketAddr};
+
+/// Format of one `RESOLVE_TARGET_ADDR` entry, for error messages.
+pub const RESOLVE_FORMAT: &str = "hostname:ip:port";
+
+/// One `RESOLVE_TARGET_ADDR` entry: connect to `addr` for `host`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ResolveOverride {
+    pub host: String,
+    pub addr: SocketAddr,
+}
+
+/// Splits `raw` at its last colon into the address and the port.
+fn split_port(raw: &str) -> Result<(&str, u16), String> {
+    let (host, port) = raw
+        .rsplit_once(':')
+        .ok_or_else(|| format!("'{}' has no port", raw))?;
+    let port = port
+        .trim()
+        .parse()
+        .map_err(|_| format!("invalid port '{}' in '{}'", port.trim(), raw))?;
+    Ok((host.trim(), port))
+}
+
+/// Parses an IP address, with or without IPv6 brackets.
+pub fn parse_ip(raw: &str) -> Result<IpAddr, String> {
+    let raw = raw.trim();
+    let bracketed = raw.strip_prefix('[').and_then(|r| r.strip_suffix(']'));
+    match bracketed {
+        Some(inner) => match inner.parse() {
+            Ok(IpAddr::V6(ip)) => Ok(IpAddr::V6(ip)),
+            _ => Err(format!("'{}' is not an IPv6 address", raw)),
+        },
+        None => raw
+            .parse()
+            .map_err(|_| format!("'{}' is not an IP address", raw)),
+    }
+}
+
+/// Parses `ip:port` or `[ipv6]:port`.
+pub fn parse_socket_addr(raw: &str) -> Result<SocketAddr, String> {
+    let raw = raw.trim();
+    let (ip, port) = split_port(raw)?;
+    if ip.contains(':') && !ip.starts_with('[') {
+        return Err(format!(
+            "'{}' is ambiguous: write IPv6 addresses in brackets, e.g. '[::1]:8080'",
+            raw
+        ));
+    }
+    Ok(SocketAddr::new(parse_ip(ip)?, port))
+}
+
+/// Parses `host:port`, where the host is a hostname, an IPv4 address or an
+/// IPv6 address in brackets; the host is returned without brackets.
+pub fn parse_host_port(raw: &str) -> Result<(String, u16), String> {
+    let raw = raw.trim();
+    let (host, port) = split_port(raw)?;
+    if host.is_empty() {
+        return Err(format!("'{}' has no host", raw));
+    }
+    if host.starts_with('[') {
+        return Ok((parse_ip(host)?.to_string(), port));
+    }
+    if host.contains(':') {
+        return Err(format!(
+            "'{}' is ambiguous: write IPv6 addresses in brackets, e.g. '[::1]:8080'",
+            raw
+        ));
+    }
+    Ok((host.to_string(), port))
+}
+
+/// Parses a listen address: `ip:port`, `[ipv6]:port`, `:port` or a bare
+/// port, the last two on all IPv4 interfaces.
+pub fn parse_listen_addr(raw: &str) -> Result<SocketAddr, String> {
+    let raw = raw.trim();
+    if let Ok(port) = raw.trim_start_matches(':').parse::<u16>() {
+        return Ok(SocketAddr::new(IpAddr::V4(Ipv4Addr::UNSPECIFIED), port));
+    }
+    parse_socket_addr(raw)
+}
+
+/// Parses one `hostname:ip:port` entry; the IP may be IPv6, bracketed or not.
+pub fn parse_resolve_override(raw: &str) -> Result<ResolveOverride, String> {
+    let raw = raw.trim();
+    let (host, addr) = raw
+        .split_once(':')
+        .ok_or_else(|| format!("'{}' is not in the format '{}'", raw, RESOLVE_FORMAT))?;
+    let host = host.trim();
+    if host.is_empty() || host.starts_with('[') {
+        return Err(format!(
+            "'{}' has no hostname; the format is '{}'",
+            raw, RESOLVE_FORMAT
+        ));
+    }
+    let (ip, port) =
+        split_port(addr).map_err(|e| format!("{}; the format is '{}'", e, RESOLVE_FORMAT))?;
+    if ip.is_empty() {
+        return Err(format!(
+            "'{}' has no IP address; the format is '{}'",
+            raw, RESOLVE_FORMAT
+        ));
+    }
+    Ok(ResolveOverride {
+        host: host.to_string(),
+        addr: SocketAddr::new(parse_ip(ip)?, port),
+    })
+}
+
+/// Parses a comma-separated list of `hostname:ip:port` entries. A hostname
+/// may appear more than once to resolve to several addresses.
+pub fn parse_resolve_overrides(raw: &str) -> Result<Vec<ResolveOverride>, String> {
+    let overrides = raw
+        .split(',')
+        .map(str::trim)
+        .filter(|entry| !entry.is_empty())
+        .map(parse_resolve_override)
+        .collect::<Result<Vec<_>, _>>()?;
+    if overrides.is_empty() {
+        return Err(format!("no '{}' entries", RESOLVE_FORMAT));
+    }
+    Ok(overrides)
+}
+
+/// The addresses `overrides` resolve `host` to, case-insensitively, in the
+/// order given; empty when `host` isn't overridden.
+pub fn resolved_addrs(overrides: &[ResolveOverride], host: &str) -> Vec<SocketAddr> {
+    overrides
+        .iter()
+        .filter(|o| o.host.eq_ignore_ascii_case(host))
+        .map(|o| o.addr)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn addr(s: &str) -> SocketAddr {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn parses_ips_with_and_without_brackets() {
+        assert_eq!(parse_ip("10.0.0.1").unwrap(), addr("10.0.0.1:0").ip());
+        assert_eq!(parse_ip("::1").unwrap(), addr("[::1]:0").ip());
+        assert_eq!(
+            parse_ip(" [2001:db8::1] ").unwrap(),
+            addr("[2001:db8::1]:0").ip()
+        );
+        assert!(parse_ip("[10.0.0.1]").is_err());
+        assert!(parse_ip("[::1").is_err());
+        assert!(parse_ip("example.com").is_err());
+    }
+
+    #[test]
+    fn parses_socket_addrs() {
+        assert_eq!(
+            parse_socket_addr("10.0.0.1:80").unwrap(),
+            addr("10.0.0.1:80")
+        );
+        assert_eq!(parse_socket_addr("[::1]:8080").unwrap(), addr("[::1]:8080"));
+        assert_eq!(
+            parse_socket_addr(" [2001:db8::1]:443 ").unwrap(),
+            addr("[2001:db8::1]:443")
+        );
+        let err = parse_socket_addr("::1:8080").unwrap_err();
+        assert!(err.contains("brackets"), "{}", err);
+        assert!(parse_socket_addr("[::1]").is_err());
+        assert!(parse_socket_addr("10.0.0.1:99999").is_err());
+        assert!(parse_socket_addr("localhost:80").is_err());
+    }
+
+    #[test]
+    fn parses_host_ports() {
+        assert_eq!(
+            parse_host_port("db.internal:5432").unwrap(),
+            ("db.internal".to_string(), 5432)
+        );
+        assert_eq!(
+            parse_host_port("[2001:db8::1]:443").unwrap(),
+            ("2001:db8::1".to_string(), 443)
+        );
+        assert!(parse_host_port("2001:db8::1:443").is_err());
+        assert!(parse_host_port("db.internal").is_err());
+        assert!(parse_host_port(":5432").is_err());
+    }
+
+    #[test]
+    fn parses_listen_addrs() {
+        assert_eq!(parse_listen_addr("8080").unwrap(), addr("0.0.0.0:8080"));
+        assert_eq!(parse_listen_addr(":9090").unwrap(), addr("0.0.0.0:9090"));
+        assert_eq!(parse_listen_addr("[::]:8080").unwrap(), addr("[::]:8080"));
+        assert_eq!(
+            parse_listen_addr("127.0.0.1:8080").unwrap(),
+            addr("127.0.0.1:8080")
+        );
+        assert!(parse_listen_addr("").is_err());
+    }
+
+    #[test]
+    fn parses_resolve_overrides() {
+        let parsed = |s: &str| parse_resolve_override(s).map(|o| (o.host, o.addr));
+        assert_eq!(
+            parsed("api.example.com:10.0.0.1:443").unwrap(),
+            ("api.example.com".to_string(), addr("10.0.0.1:443"))
+        );
+        assert_eq!(
+            parsed("api.example.com:[2001:db8::1]:443").unwrap().1,
+            addr("[2001:db8::1]:443")
+        );
+        // The port is mandatory, so unbracketed IPv6 is unambiguous.
+        assert_eq!(
+            parsed("localhost:::1:8080").unwrap(),
+            ("localhost".to_string(), addr("[::1]:8080"))
+        );
+        assert_eq!(
+            parsed(" api : 10.0.0.1 : 443 ").unwrap(),
+            ("api".to_string(), addr("10.0.0.1:443"))
+        );
+    }
+
+    #[test]
+    fn rejects_malformed_resolve_overrides() {
+        for (raw, expected) in [
+            ("api.example.com", "format"),
+            (":10.0.0.1:443", "no hostname"),
+            ("[::1]:443", "no hostname"),
+            ("api:443", "no port"),
+            ("api::443", "no IP address"),
+            ("api:10.0.0.1:", "invalid port"),
+            ("api:10.0.0.1:https", "invalid port"),
+            ("api:10.0.0.256:443", "not an IP address"),
+            ("api:backend.internal:443", "not an IP address"),
+        ] {
+            let err = parse_resolve_override(raw).unwrap_err();
+            assert!(err.contains(expected), "{}: {}", raw, err);
+        }
+    }
+
+    #[test]
+    fn parses_multiple_resolve_overrides() {
+        let overrides = parse_resolve_overrides(
+            "api.example.com:10.0.0.1:443, auth.example.com:[2001:db8::2]:443,API.example.com:10.0.0.2:443,",
+        )
+        .unwrap();
+        assert_eq!(overrides.len(), 3);
+        assert_eq!(
+            resolved_addrs(&overrides, "api.example.com"),
+            vec![addr("10.0.0.1:443"), addr("10.0.0.2:443")]
+        );
+        assert_eq!(
+            resolved_addrs(&overrides, "auth.example.com"),
+            vec![addr("[2001:db8::2]:443")]
+        );
+        assert!(resolved_addrs(&overrides, "other.example.com").is_empty());
+
+        assert!(parse_resolve_overrides(" , ").is_err());
+        assert!(parse_resolve_overrides("api:10.0.0.1:443,broken").is_err());
+    }
+}
@@ -0,0 +1,219 @@
+//! Apdex scoring against per-step response time SLOs.
+//!
+//! A step with an `slo` classifies every response it gets: within
+//! `satisfiedMs` is *satisfied*, within `toleratingMs` *tolerating*, and
+//! anything slower — or any failed step — *frustrated*. The Apdex score is
+//!
+//! ```text
+//! (satisfied + tolerating / 2) / total
+//! ```
+//!
+//! from 0 (everyone frustrated) to 1 (everyone satisfied). Scores are kept per
+//! step and per scenario (over all of its SLO steps), exported as the
+//! `apdex_score` gauge and printed in the final report.
+
+use std::collections::BTreeMap;
+use std::sync::Mutex;
+
+use crate::scenario::StepSlo;
+
+/// Classified response counts.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ApdexCounts {
+    pub satisfied: u64,
+    pub tolerating: u64,
+    pub frustrated: u64,
+}
+
+impl ApdexCounts {
+    /// Counts one response.
+    pub fn record(&mut self, slo: &StepSlo, response_time_ms: u64, success: bool) {
+        if !success || response_time_ms > slo.tolerating_ms {
+            self.frustrated += 1;
+        } else if response_time_ms > slo.satisfied_ms {
+            self.tolerating += 1;
+        } else {
+            self.satisfied += 1;
+        }
+    }
+
+    pub fn total(&self) -> u64 {
+        self.satisfied + self.tolerating + self.frustrated
+    }
+
+    /// Apdex score, or `None` before any response was counted.
+    pub fn score(&self) -> Option<f64> {
+        let total = self.total();
+        (total > 0).then(|| (self.satisfied as f64 + self.tolerating as f64 / 2.0) / total as f64)
+    }
+
+    fn add(&mut self, other: &ApdexCounts) {
+        self.satisfied += other.satisfied;
+        self.tolerating += other.tolerating;
+        self.frustrated += other.frustrated;
+    }
+}
+
+/// Apdex counts per (scenario, step).
+#[derive(Default)]
+pub struct ApdexTracker {
+    counts: Mutex<BTreeMap<(String, String), ApdexCounts>>,
+}
+
+impl ApdexTracker {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Counts one response and returns the updated step and scenario scores.
+    pub fn record(
+        &self,
+        scenario: &str,
+        step: &str,
+        slo: &StepSlo,
+        response_time_ms: u64,
+        success: bool,
+    ) -> (f64, f64) {
+        let mut counts = self.counts.lock().unwrap_or_else(|e| e.into_inner());
+        let entry = counts
+            .entry((scenario.to_string(), step.to_string()))
+            .or_default();
+        entry.record(slo, response_time_ms, success);
+        let step_score = entry.score().unwrap_or(1.0);
+        let scenario_score = scenario_counts(&counts, scenario).score().unwrap_or(1.0);
+        (step_score, scenario_score)
+    }
+
+    /// Counts per step, ordered by scenario then step.
+    pub fn step_counts(&self) -> Vec<(String, String, ApdexCounts)> {
+        self.counts
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .iter()
+            .map(|((scenario, step), counts)| (scenario.clone(), step.clone(), *counts))
+            .collect()
+    }
+
+    /// Counts per scenario, summed over its SLO steps.
+    pub fn scenario_counts(&self) -> Vec<(String, ApdexCounts)> {
+        let counts = self.counts.lock().unwrap_or_else(|e| e.into_inner());
+        let mut scenarios: BTreeMap<String, ApdexCounts> = BTreeMap::new();
+        for ((scenario, _), step) in counts.iter() {
+            scenarios.entry(scenario.clone()).or_default().add(step);
+        }
+        scenarios.into_iter().collect()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.counts
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .is_empty()
+    }
+
+    pub fn reset(&self) {
+        self.counts
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .clear();
+    }
+}
+
+fn scenario_counts(
+    counts: &BTreeMap<(String, String), ApdexCounts>,
+    scenario: &str,
+) -> ApdexCounts {
+    let mut total = ApdexCounts::default();
+    for (_, step) in counts.iter().filter(|((s, _), _)| s == scenario) {
+        total.add(step);
+    }
+    total
+}
+
+/// Formats scenario and step scores as a table.
+pub fn format_apdex_table(tracker: &ApdexTracker) -> String {
+    let mut output = String::new();
+    output.push_str(&format!(
+        "\n{:<40} {:>8} {:>10} {:>10} {:>10}\n",
+        "Scenario / Step", "Apdex", "Satisfied", "Tolerating", "Frustrated"
+    ));
+    output.push_str(&"-".repeat(82));
+    output.push('\n');
+
+    let steps = tracker.step_counts();
+    for (scenario, counts) in tracker.scenario_counts() {
+        output.push_str(&row(&scenario, &counts));
+        for (_, step, counts) in steps.iter().filter(|(s, _, _)| *s == scenario) {
+            output.push_str(&row(&format!("  {}", step), counts));
+        }
+    }
+    output
+}
+
+fn row(label: &str, counts: &ApdexCounts) -> String {
+    format!(
+        "{:<40} {:>8.2} {:>10} {:>10} {:>10}\n",
+        label,
+        counts.score().unwrap_or(1.0),
+        counts.satisfied,
+        counts.tolerating,
+        counts.frustrated
+    )
+}
+
+lazy_static::lazy_static! {
+    /// Process-wide Apdex counts, reported at the end of the test.
+    pub static ref GLOBAL_APDEX: ApdexTracker = ApdexTracker::new();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn classifies_responses() {
+        let slo = StepSlo::new(100);
+        assert_eq!(slo.tolerating_ms, 400);
+
+        let mut counts = ApdexCounts::default();
+        assert_eq!(counts.score(), None);
+        counts.record(&slo, 100, true);
+        counts.record(&slo, 250, true);
+        counts.record(&slo, 401, true);
+        counts.record(&slo, 10, false);
+        assert_eq!(
+            counts,
+            ApdexCounts {
+                satisfied: 1,
+                tolerating: 1,
+                frustrated: 2
+            }
+        );
+        assert_eq!(counts.score(), Some(0.375));
+    }
+
+    #[test]
+    fn scores_steps_and_scenarios() {
+        let tracker = ApdexTracker::new();
+        let slo = StepSlo::new(100);
+        assert_eq!(
+            tracker.record("Checkout", "Cart", &slo, 50, true),
+            (1.0, 1.0)
+        );
+        let (step, scenario) = tracker.record("Checkout", "Pay", &slo, 500, true);
+        assert_eq!(step, 0.0);
+        assert_eq!(scenario, 0.5);
+        tracker.record("Browse", "List", &slo, 200, true);
+
+        let scenarios = tracker.scenario_counts();
+        assert_eq!(scenarios[0].0, "Browse");
+        assert_eq!(scenarios[1].1.total(), 2);
+
+        let table = format_apdex_table(&tracker);
+        assert!(table.contains("Checkout"));
+        assert!(table.contains("  Pay"));
+
+        tracker.reset();
+        assert!(tracker.is_empty());
+    }
+}
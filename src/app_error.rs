@@ -0,0 +1,140 @@
+//! Top-level error type of the binary and its exit-code contract.
+//!
+//! CI pipelines branch on the process exit code, so every way a run can end
+//! maps to a fixed, documented code:
+//!
+//! | Code | Kind | Meaning |
+//! |------|------|---------|
+//! | 0  | `success` | The run completed (and, in smoke mode, every scenario passed) |
+//! | 2  | `config_error` | Invalid configuration, plan, plugin or TLS material; nothing was sent; also a failed `migrate`, `import` or `validate` |
+//! | 3  | `threshold_failed` | The target answered but the run failed its checks (smoke assertions, `scenarioThresholds`, `percentile` assertions) |
+//! | 4  | `target_unreachable` | The target failed the startup pre-check, or requests were sent but it never produced a response |
+//! | 10 | `internal` | A failure inside the load generator itself |
+//!
+//! With `LOG_FORMAT=json` the final `report = "exit"` event carries the code,
+//! its kind and this mapping, so a pipeline can read the outcome from logs.
+
+use std::process::ExitCode;
+
+use thiserror::Error;
+
+/// Exit code when the run completed successfully.
+pub const EXIT_SUCCESS: u8 = 0;
+
+/// Every exit code the binary uses, with its kind.
+pub const EXIT_CODES: [(&str, u8); 5] = [
+    ("success", EXIT_SUCCESS),
+    ("config_error", 2),
+    ("threshold_failed", 3),
+    ("target_unreachable", 4),
+    ("internal", 10),
+];
+
+/// Why the binary exits unsuccessfully.
+#[derive(Error, Debug)]
+pub enum AppError {
+    #[error("Configuration error: {0}")]
+    Config(String),
+
+    #[error("Threshold failed: {0}")]
+    ThresholdFailed(String),
+
+    #[error("Target unreachable: {0}")]
+    TargetUnreachable(String),
+
+    #[error("Internal error: {0}")]
+    Internal(String),
+}
+
+impl AppError {
+    /// Kind label, as listed in [`EXIT_CODES`].
+    pub fn kind(&self) -> &'static str {
+        match self {
+            AppError::Config(_) => "config_error",
+            AppError::ThresholdFailed(_) => "threshold_failed",
+            AppError::TargetUnreachable(_) => "target_unreachable",
+            AppError::Internal(_) => "internal",
+        }
+    }
+
+    /// Documented process exit code for this error.
+    pub fn exit_code(&self) -> u8 {
+        exit_code_for(self.kind())
+    }
+
+    /// The error a child process exiting with `code` reported, by the same
+    /// mapping.
+    pub fn from_exit_code(code: Option<i32>, message: String) -> AppError {
+        match code {
+            Some(2) => AppError::Config(message),
+            Some(3) => AppError::ThresholdFailed(message),
+            Some(4) => AppError::TargetUnreachable(message),
+            _ => AppError::Internal(message),
+        }
+    }
+
+    /// Fails with `TargetUnreachable` when requests were sent but none of
+    /// them got an HTTP response.
+    pub fn check_reachable(sent: u64, responses: u64) -> Result<(), AppError> {
+        if sent > 0 && responses == 0 {
+            Err(AppError::TargetUnreachable(format!(
+                "none of {} request(s) received a response",
+                sent
+            )))
+        } else {
+            Ok(())
+        }
+    }
+}
+
+impl From<AppError> for ExitCode {
+    fn from(error: AppError) -> Self {
+        ExitCode::from(error.exit_code())
+    }
+}
+
+fn exit_code_for(kind: &str) -> u8 {
+    EXIT_CODES
+        .iter()
+        .find(|(k, _)| *k == kind)
+        .map_or(10, |(_, code)| *code)
+}
+
+/// The exit-code mapping as a JSON object, e.g. `{"success":0,"config_error":2,...}`.
+pub fn exit_codes_json() -> String {
+    let map: serde_json::Map<String, serde_json::Value> = EXIT_CODES
+        .iter()
+        .map(|(kind, code)| (kind.to_string(), (*code).into()))
+        .collect();
+    serde_json::Value::Object(map).to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn maps_errors_to_documented_codes() {
+        assert_eq!(AppError::Config("x".into()).exit_code(), 2);
+        assert_eq!(AppError::ThresholdFailed("x".into()).exit_code(), 3);
+        assert_eq!(AppError::TargetUnreachable("x".into()).exit_code(), 4);
+        assert_eq!(AppError::Internal("x".into()).exit_code(), 10);
+        let mapping: serde_json::Value = serde_json::from_str(&exit_codes_json()).unwrap();
+        assert_eq!(mapping["success"], 0);
+        assert_eq!(mapping["target_unreachable"], 4);
+        let err = AppError::from_exit_code(Some(3), "runner 1".into());
+        assert_eq!(err.exit_code(), 3);
+        assert_eq!(
+            AppError::from_exit_code(None, "killed".into()).exit_code(),
+            10
+        );
+    }
+
+    #[test]
+    fn checks_reachability() {
+        assert!(AppError::check_reachable(0, 0).is_ok());
+        assert!(AppError::check_reachable(10, 1).is_ok());
+        let err = AppError::check_reachable(10, 0).unwrap_err();
+        assert_eq!(err.kind(), "target_unreachable");
+    }
+}
@@ -3,9 +3,16 @@
 //! This module provides functionality to validate HTTP responses against
 //! assertions defined in scenarios.
 
+use crate::golden::{self, GoldenError};
+use crate::http_cache::CacheRatio;
+use crate::patterns::CompiledPatterns;
+use crate::plugin::{self, AssertionInput};
+use crate::redaction::GLOBAL_REDACTOR;
 use crate::scenario::Assertion;
-use regex::Regex;
+use crate::xml_path;
 use serde_json::Value;
+use serde_json_path::JsonPath;
+use sha2::{Digest, Sha256};
 #[cfg(test)]
 use std::time::Duration;
 use thiserror::Error;
@@ -42,6 +49,9 @@ pub enum AssertionError {
     #[error("JSONPath assertion failed: {0}")]
     JsonPathFailed(String),
 
+    #[error("XPath assertion failed: {0}")]
+    XmlPathFailed(String),
+
     #[error("Body does not contain expected substring: {0}")]
     BodyNotContains(String),
 
@@ -51,11 +61,84 @@ pub enum AssertionError {
     #[error("Header '{0}' not found in response")]
     HeaderNotFound(String),
 
+    #[error("Header '{header}' is '{actual}', expected {expected}")]
+    HeaderValueMismatch {
+        header: String,
+        expected: String,
+        actual: String,
+    },
+
+    #[error("Body SHA-256 mismatch: expected {expected}, got {actual}")]
+    BodySha256Mismatch { expected: String, actual: String },
+
+    #[error("Body size {actual} bytes is outside {range}")]
+    BodySizeOutOfRange { actual: u64, range: String },
+
+    #[error("Expected 304 Not Modified, got {0}")]
+    NotModifiedExpected(u16),
+
+    #[error("Cache hit ratio {actual:.3} is below {min} over {samples} responses")]
+    CacheHitRatioTooLow { actual: f64, min: f64, samples: u64 },
+
     #[error("Regex compilation failed: {0}")]
     RegexError(#[from] regex::Error),
 
     #[error("Invalid JSON: {0}")]
     InvalidJson(String),
+
+    #[error("Golden file assertion failed: {0}")]
+    GoldenMismatch(#[from] GoldenError),
+
+    #[error("Plugin assertion '{name}' failed: {message}")]
+    PluginFailed { name: String, message: String },
+}
+
+/// SHA-256 and size of a raw response body.
+#[derive(Debug, Clone, PartialEq)]
+pub struct BodyDigest {
+    /// Lowercase hex SHA-256
+    pub sha256: String,
+    pub size: u64,
+}
+
+impl BodyDigest {
+    pub fn of(body: &[u8]) -> Self {
+        let mut hasher = BodyHasher::new();
+        hasher.update(body);
+        hasher.finish()
+    }
+}
+
+/// Computes a [`BodyDigest`] chunk by chunk, so large bodies never have to
+/// be held in memory.
+#[derive(Default)]
+pub struct BodyHasher {
+    hasher: Sha256,
+    size: u64,
+}
+
+impl BodyHasher {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn update(&mut self, chunk: &[u8]) {
+        self.hasher.update(chunk);
+        self.size += chunk.len() as u64;
+    }
+
+    pub fn finish(self) -> BodyDigest {
+        let sha256 = self
+            .hasher
+            .finalize()
+            .iter()
+            .map(|b| format!("{:02x}", b))
+            .collect();
+        BodyDigest {
+            sha256,
+            size: self.size,
+        }
+    }
 }
 
 /// Run all assertions against a response.
@@ -75,47 +158,95 @@ pub fn run_assertions(
     response_time_ms: u64,
     response_body: &str,
     response_headers: &reqwest::header::HeaderMap,
+) -> Vec<AssertionResult> {
+    let digest = assertions
+        .iter()
+        .any(Assertion::needs_digest)
+        .then(|| BodyDigest::of(response_body.as_bytes()));
+    run_assertions_with_digest(
+        assertions,
+        &CompiledPatterns::default(),
+        status_code,
+        response_time_ms,
+        response_body,
+        response_headers,
+        digest.as_ref(),
+        None,
+    )
+}
+
+/// Like [`run_assertions`], with checksum and size assertions checked
+/// against `digest` (the raw body as received) rather than `response_body`,
+/// which may be empty when nothing else reads the body, and `cacheHitRatio`
+/// checked against the step's running `cache_ratio`. JSONPath and regex
+/// assertions use the step's precompiled `patterns`.
+#[allow(clippy::too_many_arguments)]
+pub fn run_assertions_with_digest(
+    assertions: &[Assertion],
+    patterns: &CompiledPatterns,
+    status_code: u16,
+    response_time_ms: u64,
+    response_body: &str,
+    response_headers: &reqwest::header::HeaderMap,
+    digest: Option<&BodyDigest>,
+    cache_ratio: Option<CacheRatio>,
 ) -> Vec<AssertionResult> {
     let mut results = Vec::new();
 
     for assertion in assertions {
         debug!(assertion = ?assertion, "Running assertion");
 
-        let result = match run_single_assertion(
+        let outcome = match (assertion, digest) {
+            (Assertion::CacheHitRatio { min, min_samples }, _) => {
+                assert_cache_hit_ratio(*min, *min_samples, cache_ratio)
+            }
+            (_, Some(digest)) if assertion.needs_digest() => assert_digest(assertion, digest),
+            _ => run_single_assertion(
+                assertion,
+                patterns,
+                status_code,
+                response_time_ms,
+                response_body,
+                response_headers,
+            ),
+        };
+        let actual = format_actual_value(
             assertion,
             status_code,
             response_time_ms,
             response_body,
             response_headers,
-        ) {
+            digest,
+            cache_ratio,
+        );
+        let result = match outcome {
             Ok(()) => {
                 debug!(assertion = ?assertion, "Assertion passed");
                 AssertionResult {
                     assertion: assertion.clone(),
                     passed: true,
-                    actual: format_actual_value(
-                        assertion,
-                        status_code,
-                        response_time_ms,
-                        response_body,
-                    ),
+                    actual,
                     expected: format_expected_value(assertion),
                     error_message: None,
                 }
             }
             Err(e) => {
-                warn!(assertion = ?assertion, error = %e, "Assertion failed");
+                // Failures are logged and reported, so response data in
+                // them is masked first (see `crate::redaction`).
+                let message = GLOBAL_REDACTOR.text(&e.to_string()).into_owned();
+                let actual = match assertion {
+                    Assertion::BodyContains(_) | Assertion::BodyMatches(_) => {
+                        body_excerpt(&GLOBAL_REDACTOR.body(response_body))
+                    }
+                    _ => GLOBAL_REDACTOR.text(&actual).into_owned(),
+                };
+                warn!(assertion = ?assertion, error = %message, "Assertion failed");
                 AssertionResult {
                     assertion: assertion.clone(),
                     passed: false,
-                    actual: format_actual_value(
-                        assertion,
-                        status_code,
-                        response_time_ms,
-                        response_body,
-                    ),
+                    actual,
                     expected: format_expected_value(assertion),
-                    error_message: Some(e.to_string()),
+                    error_message: Some(message),
                 }
             }
         };
@@ -129,6 +260,7 @@ pub fn run_assertions(
 /// Run a single assertion.
 fn run_single_assertion(
     assertion: &Assertion,
+    patterns: &CompiledPatterns,
     status_code: u16,
     response_time_ms: u64,
     response_body: &str,
@@ -159,7 +291,14 @@ fn run_single_assertion(
         }
 
         Assertion::JsonPath { path, expected } => {
-            assert_json_path(response_body, path, expected.as_deref())
+            let json_path = patterns.json_path(path).map_err(|e| {
+                AssertionError::JsonPathFailed(format!("Invalid JSONPath '{}': {}", path, e))
+            })?;
+            assert_json_path(response_body, &json_path, path, expected.as_deref())
+        }
+
+        Assertion::XmlPath { path, expected } => {
+            assert_xml_path(response_body, path, expected.as_deref())
         }
 
         Assertion::BodyContains(substring) => {
@@ -171,7 +310,7 @@ fn run_single_assertion(
         }
 
         Assertion::BodyMatches(pattern) => {
-            let re = Regex::new(pattern)?;
+            let re = patterns.regex(pattern)?;
             if re.is_match(response_body) {
                 Ok(())
             } else {
@@ -186,26 +325,186 @@ fn run_single_assertion(
                 Err(AssertionError::HeaderNotFound(header_name.clone()))
             }
         }
+
+        Assertion::HeaderEquals { header, expected } => {
+            assert_header_value(response_headers, header, &format!("'{}'", expected), |v| {
+                v == expected.as_str()
+            })
+        }
+
+        Assertion::HeaderMatches { header, pattern } => {
+            let re = patterns.regex(pattern)?;
+            assert_header_value(response_headers, header, &format!("/{}/", pattern), |v| {
+                re.is_match(v)
+            })
+        }
+
+        Assertion::BodySha256 { .. } | Assertion::BodySizeBytes { .. } => {
+            assert_digest(assertion, &BodyDigest::of(response_body.as_bytes()))
+        }
+
+        Assertion::NotModified => {
+            if status_code == 304 {
+                Ok(())
+            } else {
+                Err(AssertionError::NotModifiedExpected(status_code))
+            }
+        }
+
+        Assertion::CacheHitRatio { min, min_samples } => {
+            assert_cache_hit_ratio(*min, *min_samples, None)
+        }
+
+        Assertion::MatchesGolden {
+            path,
+            ignore_fields,
+        } => golden::check(path, ignore_fields, response_body).map_err(AssertionError::from),
+
+        Assertion::Plugin { name, config } => assert_plugin(
+            name,
+            config.as_deref(),
+            status_code,
+            response_time_ms,
+            response_body,
+            response_headers,
+        ),
+    }
+}
+
+/// Check that some value of `header` satisfies `check`. Header names are
+/// case-insensitive; repeated headers pass if any one value does.
+fn assert_header_value(
+    headers: &reqwest::header::HeaderMap,
+    header: &str,
+    expected: &str,
+    check: impl Fn(&str) -> bool,
+) -> Result<(), AssertionError> {
+    let values = header_values(headers, header);
+    if values.is_empty() {
+        return Err(AssertionError::HeaderNotFound(header.to_string()));
+    }
+    if values.iter().any(|v| check(v)) {
+        Ok(())
+    } else {
+        Err(AssertionError::HeaderValueMismatch {
+            header: header.to_string(),
+            expected: expected.to_string(),
+            actual: values.join(", "),
+        })
+    }
+}
+
+/// All values of `header`, lossily decoded.
+fn header_values(headers: &reqwest::header::HeaderMap, header: &str) -> Vec<String> {
+    headers
+        .get_all(header.to_ascii_lowercase().as_str())
+        .iter()
+        .map(|v| String::from_utf8_lossy(v.as_bytes()).into_owned())
+        .collect()
+}
+
+/// Check a checksum or size assertion against the body's digest.
+fn assert_digest(assertion: &Assertion, digest: &BodyDigest) -> Result<(), AssertionError> {
+    match assertion {
+        Assertion::BodySha256 { expected } if !expected.eq_ignore_ascii_case(&digest.sha256) => {
+            Err(AssertionError::BodySha256Mismatch {
+                expected: expected.to_ascii_lowercase(),
+                actual: digest.sha256.clone(),
+            })
+        }
+        Assertion::BodySizeBytes { min, max }
+            if min.is_some_and(|min| digest.size < min)
+                || max.is_some_and(|max| digest.size > max) =>
+        {
+            Err(AssertionError::BodySizeOutOfRange {
+                actual: digest.size,
+                range: format_size_range(*min, *max),
+            })
+        }
+        _ => Ok(()),
+    }
+}
+
+/// Check the step's running cache-hit ratio; passes until `min_samples`
+/// responses have been seen.
+fn assert_cache_hit_ratio(
+    min: f64,
+    min_samples: u64,
+    ratio: Option<CacheRatio>,
+) -> Result<(), AssertionError> {
+    let ratio = ratio.unwrap_or_default();
+    if ratio.total < min_samples || ratio.ratio() >= min {
+        Ok(())
+    } else {
+        Err(AssertionError::CacheHitRatioTooLow {
+            actual: ratio.ratio(),
+            min,
+            samples: ratio.total,
+        })
+    }
+}
+
+fn format_size_range(min: Option<u64>, max: Option<u64>) -> String {
+    match (min, max) {
+        (Some(min), Some(max)) => format!("{}..={} bytes", min, max),
+        (Some(min), None) => format!(">= {} bytes", min),
+        (None, Some(max)) => format!("<= {} bytes", max),
+        (None, None) => "any size".to_string(),
+    }
+}
+
+/// Run an assertion implemented by a registered plugin.
+fn assert_plugin(
+    name: &str,
+    config: Option<&str>,
+    status_code: u16,
+    response_time_ms: u64,
+    response_body: &str,
+    response_headers: &reqwest::header::HeaderMap,
+) -> Result<(), AssertionError> {
+    let plugin_failed = |message: String| AssertionError::PluginFailed {
+        name: name.to_string(),
+        message,
+    };
+
+    let plugin = plugin::global_assertion(name)
+        .ok_or_else(|| plugin_failed("plugin is not registered".to_string()))?;
+
+    let headers = response_headers
+        .iter()
+        .filter_map(|(k, v)| v.to_str().ok().map(|v| (k.to_string(), v.to_string())))
+        .collect();
+
+    let input = AssertionInput {
+        body: response_body.to_string(),
+        headers,
+        config: config.map(str::to_string),
+    };
+
+    let outcome = plugin
+        .check(status_code, response_time_ms, &input)
+        .map_err(|e| plugin_failed(e.to_string()))?;
+
+    if outcome.passed {
+        Ok(())
+    } else {
+        Err(plugin_failed(outcome.message.unwrap_or_else(|| {
+            "assertion rejected response".to_string()
+        })))
     }
 }
 
 /// Assert JSONPath condition.
 fn assert_json_path(
     json_body: &str,
+    json_path: &JsonPath,
     path: &str,
     expected: Option<&str>,
 ) -> Result<(), AssertionError> {
-    use serde_json_path::JsonPath;
-
     // Parse JSON
     let json: Value =
         serde_json::from_str(json_body).map_err(|e| AssertionError::InvalidJson(e.to_string()))?;
 
-    // Parse JSONPath
-    let json_path = JsonPath::parse(path).map_err(|e| {
-        AssertionError::JsonPathFailed(format!("Invalid JSONPath '{}': {}", path, e))
-    })?;
-
     // Query
     let node_list = json_path.query(&json);
 
@@ -241,12 +540,40 @@ fn assert_json_path(
     }
 }
 
+/// Assert XPath condition: at least one match, and if `expected` is given,
+/// the first match equals it.
+fn assert_xml_path(
+    xml_body: &str,
+    path: &str,
+    expected: Option<&str>,
+) -> Result<(), AssertionError> {
+    let matches = xml_path::select(xml_body, path)
+        .map_err(|e| AssertionError::XmlPathFailed(e.to_string()))?;
+
+    match (matches.first(), expected) {
+        (None, _) => Err(AssertionError::XmlPathFailed(format!(
+            "XPath '{}' did not match",
+            path
+        ))),
+        (Some(actual), Some(expected_value)) if actual != expected_value => {
+            Err(AssertionError::XmlPathFailed(format!(
+                "XPath '{}' value mismatch: expected '{}', got '{}'",
+                path, expected_value, actual
+            )))
+        }
+        _ => Ok(()),
+    }
+}
+
 /// Format actual value for display.
 fn format_actual_value(
     assertion: &Assertion,
     status_code: u16,
     response_time_ms: u64,
     response_body: &str,
+    response_headers: &reqwest::header::HeaderMap,
+    digest: Option<&BodyDigest>,
+    cache_ratio: Option<CacheRatio>,
 ) -> String {
     match assertion {
         Assertion::StatusCode(_) => status_code.to_string(),
@@ -254,21 +581,43 @@ fn format_actual_value(
         Assertion::JsonPath { path, .. } => {
             format!("JSONPath: {}", path)
         }
-        Assertion::BodyContains(_) => {
-            if response_body.len() > 100 {
-                format!("{}...", &response_body[..100])
-            } else {
-                response_body.to_string()
+        Assertion::XmlPath { path, .. } => format!("XPath: {}", path),
+        Assertion::BodyContains(_) | Assertion::BodyMatches(_) => body_excerpt(response_body),
+        Assertion::HeaderExists(header) => format!("header '{}'", header),
+        Assertion::HeaderEquals { header, .. } | Assertion::HeaderMatches { header, .. } => {
+            match header_values(response_headers, header).as_slice() {
+                [] => format!("header '{}' missing", header),
+                values => values.join(", "),
             }
         }
-        Assertion::BodyMatches(_) => {
-            if response_body.len() > 100 {
-                format!("{}...", &response_body[..100])
-            } else {
-                response_body.to_string()
-            }
+        Assertion::BodySha256 { .. } => match digest {
+            Some(digest) => digest.sha256.clone(),
+            None => BodyDigest::of(response_body.as_bytes()).sha256,
+        },
+        Assertion::BodySizeBytes { .. } => format!(
+            "{} bytes",
+            digest.map_or(response_body.len() as u64, |d| d.size)
+        ),
+        Assertion::NotModified => status_code.to_string(),
+        Assertion::CacheHitRatio { .. } => {
+            let ratio = cache_ratio.unwrap_or_default();
+            format!("{}/{} cached", ratio.hits, ratio.total)
         }
-        Assertion::HeaderExists(header) => format!("header '{}'", header),
+        Assertion::MatchesGolden { path, .. } => format!("golden '{}'", path),
+        Assertion::Plugin { name, .. } => format!("plugin '{}'", name),
+    }
+}
+
+/// The first 100 bytes of a body, cut at a character boundary.
+fn body_excerpt(body: &str) -> String {
+    let mut end = body.len().min(100);
+    while !body.is_char_boundary(end) {
+        end -= 1;
+    }
+    if end < body.len() {
+        format!("{}...", &body[..end])
+    } else {
+        body.to_string()
     }
 }
 
@@ -284,9 +633,32 @@ fn format_expected_value(assertion: &Assertion) -> String {
                 format!("{} exists", path)
             }
         }
+        Assertion::XmlPath { path, expected } => match expected {
+            Some(exp) => format!("{} = {}", path, exp),
+            None => format!("{} exists", path),
+        },
         Assertion::BodyContains(substring) => format!("contains '{}'", substring),
         Assertion::BodyMatches(pattern) => format!("matches /{}/", pattern),
         Assertion::HeaderExists(header) => format!("header '{}' exists", header),
+        Assertion::HeaderEquals { header, expected } => format!("{}: {}", header, expected),
+        Assertion::HeaderMatches { header, pattern } => format!("{} matches /{}/", header, pattern),
+        Assertion::BodySha256 { expected } => format!("sha256 {}", expected.to_ascii_lowercase()),
+        Assertion::BodySizeBytes { min, max } => format_size_range(*min, *max),
+        Assertion::NotModified => "304".to_string(),
+        Assertion::CacheHitRatio { min, min_samples } => {
+            format!(">= {} cached after {} responses", min, min_samples)
+        }
+        Assertion::MatchesGolden {
+            path,
+            ignore_fields,
+        } => match ignore_fields.as_slice() {
+            [] => format!("matches '{}'", path),
+            fields => format!("matches '{}' ignoring {}", path, fields.join(", ")),
+        },
+        Assertion::Plugin { name, config } => match config {
+            Some(cfg) => format!("plugin '{}' passes ({})", name, cfg),
+            None => format!("plugin '{}' passes", name),
+        },
     }
 }
 
@@ -298,28 +670,56 @@ mod tests {
     #[test]
     fn test_status_code_assertion_pass() {
         let assertion = Assertion::StatusCode(200);
-        let result = run_single_assertion(&assertion, 200, 100, "", &HeaderMap::new());
+        let result = run_single_assertion(
+            &assertion,
+            &CompiledPatterns::default(),
+            200,
+            100,
+            "",
+            &HeaderMap::new(),
+        );
         assert!(result.is_ok());
     }
 
     #[test]
     fn test_status_code_assertion_fail() {
         let assertion = Assertion::StatusCode(200);
-        let result = run_single_assertion(&assertion, 404, 100, "", &HeaderMap::new());
+        let result = run_single_assertion(
+            &assertion,
+            &CompiledPatterns::default(),
+            404,
+            100,
+            "",
+            &HeaderMap::new(),
+        );
         assert!(result.is_err());
     }
 
     #[test]
     fn test_response_time_assertion_pass() {
         let assertion = Assertion::ResponseTime(Duration::from_millis(500));
-        let result = run_single_assertion(&assertion, 200, 300, "", &HeaderMap::new());
+        let result = run_single_assertion(
+            &assertion,
+            &CompiledPatterns::default(),
+            200,
+            300,
+            "",
+            &HeaderMap::new(),
+        );
         assert!(result.is_ok());
     }
 
     #[test]
     fn test_response_time_assertion_fail() {
         let assertion = Assertion::ResponseTime(Duration::from_millis(500));
-        let result = run_single_assertion(&assertion, 200, 700, "", &HeaderMap::new());
+        let result = run_single_assertion(
+            &assertion,
+            &CompiledPatterns::default(),
+            200,
+            700,
+            "",
+            &HeaderMap::new(),
+        );
         assert!(result.is_err());
     }
 
@@ -330,7 +730,14 @@ mod tests {
             path: "$.user.id".to_string(),
             expected: None,
         };
-        let result = run_single_assertion(&assertion, 200, 100, json, &HeaderMap::new());
+        let result = run_single_assertion(
+            &assertion,
+            &CompiledPatterns::default(),
+            200,
+            100,
+            json,
+            &HeaderMap::new(),
+        );
         assert!(result.is_ok());
     }
 
@@ -341,7 +748,14 @@ mod tests {
             path: "$.status".to_string(),
             expected: Some("ok".to_string()),
         };
-        let result = run_single_assertion(&assertion, 200, 100, json, &HeaderMap::new());
+        let result = run_single_assertion(
+            &assertion,
+            &CompiledPatterns::default(),
+            200,
+            100,
+            json,
+            &HeaderMap::new(),
+        );
         assert!(result.is_ok());
     }
 
@@ -352,15 +766,75 @@ mod tests {
             path: "$.status".to_string(),
             expected: Some("ok".to_string()),
         };
-        let result = run_single_assertion(&assertion, 200, 100, json, &HeaderMap::new());
+        let result = run_single_assertion(
+            &assertion,
+            &CompiledPatterns::default(),
+            200,
+            100,
+            json,
+            &HeaderMap::new(),
+        );
         assert!(result.is_err());
     }
 
+    #[test]
+    fn test_xml_path_assertions() {
+        let xml = r#"<orders><order id="1"><status>shipped</status></order></orders>"#;
+        let assertion = Assertion::XmlPath {
+            path: "//order[@id='1']/status".to_string(),
+            expected: Some("shipped".to_string()),
+        };
+        assert!(run_single_assertion(
+            &assertion,
+            &CompiledPatterns::default(),
+            200,
+            100,
+            xml,
+            &HeaderMap::new()
+        )
+        .is_ok());
+
+        let assertion = Assertion::XmlPath {
+            path: "//order/status".to_string(),
+            expected: Some("pending".to_string()),
+        };
+        assert!(run_single_assertion(
+            &assertion,
+            &CompiledPatterns::default(),
+            200,
+            100,
+            xml,
+            &HeaderMap::new()
+        )
+        .is_err());
+
+        let assertion = Assertion::XmlPath {
+            path: "//order[@id='2']".to_string(),
+            expected: None,
+        };
+        assert!(run_single_assertion(
+            &assertion,
+            &CompiledPatterns::default(),
+            200,
+            100,
+            xml,
+            &HeaderMap::new()
+        )
+        .is_err());
+    }
+
     #[test]
     fn test_body_contains_pass() {
         let body = "Hello, world!";
         let assertion = Assertion::BodyContains("world".to_string());
-        let result = run_single_assertion(&assertion, 200, 100, body, &HeaderMap::new());
+        let result = run_single_assertion(
+            &assertion,
+            &CompiledPatterns::default(),
+            200,
+            100,
+            body,
+            &HeaderMap::new(),
+        );
         assert!(result.is_ok());
     }
 
@@ -368,7 +842,14 @@ mod tests {
     fn test_body_contains_fail() {
         let body = "Hello, world!";
         let assertion = Assertion::BodyContains("missing".to_string());
-        let result = run_single_assertion(&assertion, 200, 100, body, &HeaderMap::new());
+        let result = run_single_assertion(
+            &assertion,
+            &CompiledPatterns::default(),
+            200,
+            100,
+            body,
+            &HeaderMap::new(),
+        );
         assert!(result.is_err());
     }
 
@@ -376,7 +857,14 @@ mod tests {
     fn test_body_matches_regex_pass() {
         let body = "Order #12345 confirmed";
         let assertion = Assertion::BodyMatches(r"Order #\d+".to_string());
-        let result = run_single_assertion(&assertion, 200, 100, body, &HeaderMap::new());
+        let result = run_single_assertion(
+            &assertion,
+            &CompiledPatterns::default(),
+            200,
+            100,
+            body,
+            &HeaderMap::new(),
+        );
         assert!(result.is_ok());
     }
 
@@ -384,10 +872,54 @@ mod tests {
     fn test_body_matches_regex_fail() {
         let body = "No order here";
         let assertion = Assertion::BodyMatches(r"Order #\d+".to_string());
-        let result = run_single_assertion(&assertion, 200, 100, body, &HeaderMap::new());
+        let result = run_single_assertion(
+            &assertion,
+            &CompiledPatterns::default(),
+            200,
+            100,
+            body,
+            &HeaderMap::new(),
+        );
         assert!(result.is_err());
     }
 
+    #[test]
+    fn test_header_equals_and_matches() {
+        let mut headers = HeaderMap::new();
+        headers.insert("cache-control", "public, max-age=300".parse().unwrap());
+        headers.append("vary", "Accept-Encoding".parse().unwrap());
+        headers.append("vary", "Origin".parse().unwrap());
+
+        let equals = |header: &str, expected: &str| Assertion::HeaderEquals {
+            header: header.to_string(),
+            expected: expected.to_string(),
+        };
+        let matches = |header: &str, pattern: &str| Assertion::HeaderMatches {
+            header: header.to_string(),
+            pattern: pattern.to_string(),
+        };
+        let check = |a: &Assertion| {
+            run_single_assertion(a, &CompiledPatterns::default(), 200, 10, "", &headers)
+        };
+
+        assert!(check(&equals("Cache-Control", "public, max-age=300")).is_ok());
+        assert!(check(&equals("Vary", "Origin")).is_ok());
+        assert!(check(&matches("CACHE-CONTROL", r"max-age=\d+")).is_ok());
+        assert!(matches!(
+            check(&equals("cache-control", "no-store")),
+            Err(AssertionError::HeaderValueMismatch { .. })
+        ));
+        assert!(matches!(
+            check(&matches("Access-Control-Allow-Origin", ".*")),
+            Err(AssertionError::HeaderNotFound(_))
+        ));
+        assert!(check(&matches("vary", "(")).is_err());
+
+        let results = run_assertions(&[equals("vary", "Cookie")], 200, 10, "", &headers);
+        assert_eq!(results[0].actual, "Accept-Encoding, Origin");
+        assert_eq!(results[0].expected, "vary: Cookie");
+    }
+
     #[test]
     fn test_run_multiple_assertions() {
         let json = r#"{"status": "ok", "count": 5}"#;
@@ -423,4 +955,142 @@ mod tests {
         assert!(!results[1].passed); // StatusCode 404
         assert!(results[2].passed); // BodyContains
     }
+
+    #[test]
+    fn test_plugin_assertion_unregistered_fails() {
+        let assertion = Assertion::Plugin {
+            name: "not_registered".to_string(),
+            config: None,
+        };
+        let result = run_single_assertion(
+            &assertion,
+            &CompiledPatterns::default(),
+            200,
+            100,
+            "",
+            &HeaderMap::new(),
+        );
+        assert!(matches!(result, Err(AssertionError::PluginFailed { .. })));
+    }
+
+    #[test]
+    fn test_body_sha256_assertion() {
+        // sha256("hello")
+        let hello = "2CF24DBA5FB0A30E26E83B2AC5B9E29E1B161E5C1FA7425E73043362938B9824";
+        let assertion = Assertion::BodySha256 {
+            expected: hello.to_string(),
+        };
+        assert!(run_single_assertion(
+            &assertion,
+            &CompiledPatterns::default(),
+            200,
+            100,
+            "hello",
+            &HeaderMap::new()
+        )
+        .is_ok());
+        assert!(matches!(
+            run_single_assertion(
+                &assertion,
+                &CompiledPatterns::default(),
+                200,
+                100,
+                "hello!",
+                &HeaderMap::new()
+            ),
+            Err(AssertionError::BodySha256Mismatch { .. })
+        ));
+
+        // Binary bodies are checked against the digest of the raw bytes.
+        let mut hasher = BodyHasher::new();
+        hasher.update(b"hel");
+        hasher.update(b"lo");
+        let digest = hasher.finish();
+        assert_eq!(digest.size, 5);
+        let results = run_assertions_with_digest(
+            &[assertion],
+            &CompiledPatterns::default(),
+            200,
+            100,
+            "",
+            &HeaderMap::new(),
+            Some(&digest),
+            None,
+        );
+        assert!(results[0].passed);
+        assert_eq!(results[0].actual, hello.to_ascii_lowercase());
+    }
+
+    #[test]
+    fn test_body_size_assertion() {
+        let digest = BodyDigest::of(&[0u8; 1024]);
+        let check = |min, max| {
+            run_assertions_with_digest(
+                &[Assertion::BodySizeBytes { min, max }],
+                &CompiledPatterns::default(),
+                200,
+                100,
+                "",
+                &HeaderMap::new(),
+                Some(&digest),
+                None,
+            )
+            .remove(0)
+        };
+        assert!(check(Some(1024), Some(1024)).passed);
+        assert!(check(Some(1), None).passed);
+        let too_small = check(None, Some(1000));
+        assert!(!too_small.passed);
+        assert_eq!(too_small.actual, "1024 bytes");
+        assert_eq!(too_small.expected, "<= 1000 bytes");
+        assert!(!check(Some(2048), None).passed);
+    }
+
+    #[test]
+    fn test_cache_assertions() {
+        assert!(run_single_assertion(
+            &Assertion::NotModified,
+            &CompiledPatterns::default(),
+            304,
+            10,
+            "",
+            &HeaderMap::new()
+        )
+        .is_ok());
+        assert!(matches!(
+            run_single_assertion(
+                &Assertion::NotModified,
+                &CompiledPatterns::default(),
+                200,
+                10,
+                "",
+                &HeaderMap::new()
+            ),
+            Err(AssertionError::NotModifiedExpected(200))
+        ));
+
+        let assertion = Assertion::CacheHitRatio {
+            min: 0.9,
+            min_samples: 10,
+        };
+        let check = |hits, total| {
+            run_assertions_with_digest(
+                std::slice::from_ref(&assertion),
+                &CompiledPatterns::default(),
+                200,
+                10,
+                "",
+                &HeaderMap::new(),
+                None,
+                Some(CacheRatio { hits, total }),
+            )
+            .remove(0)
+        };
+        // Too few samples to judge yet.
+        assert!(check(0, 9).passed);
+        assert!(check(9, 10).passed);
+        let low = check(8, 10);
+        assert!(!low.passed);
+        assert_eq!(low.actual, "8/10 cached");
+    }
 }
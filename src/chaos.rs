@@ -0,0 +1,256 @@
+//! Client-side fault injection for resilience testing (`CHAOS_*`).
+//!
+//! Lets a load test also exercise how the target copes with misbehaving
+//! clients. Nothing is injected unless `CHAOS_ENABLED=true` **and** at least
+//! one fault has a non-zero percentage:
+//!
+//! | Variable | Fault |
+//! |----------|-------|
+//! | `CHAOS_DROP_PERCENT` | Request is dropped before it is sent |
+//! | `CHAOS_CORRUPT_HEADERS_PERCENT` | `Content-Type`/`Accept` replaced with garbage |
+//! | `CHAOS_OVERSIZED_BODY_PERCENT` | Random body of `CHAOS_OVERSIZED_BODY_BYTES` attached |
+//! | `CHAOS_ABORT_RESPONSE_PERCENT` | Connection closed after the first body chunk |
+//!
+//! Every injected fault increments `chaos_faults_injected_total{fault}`.
+
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::RwLock;
+
+use rand::distributions::Alphanumeric;
+use rand::Rng;
+use reqwest::header::{ACCEPT, CONTENT_TYPE};
+use reqwest::RequestBuilder;
+
+use crate::metrics::CHAOS_FAULTS_INJECTED_TOTAL;
+
+/// Default size of an oversized body: 10 MiB.
+pub const DEFAULT_OVERSIZED_BODY_BYTES: usize = 10 * 1024 * 1024;
+
+/// Fast-path switch so disabled chaos costs one relaxed atomic load.
+static ENABLED: AtomicBool = AtomicBool::new(false);
+
+/// Active configuration, set by [`init`].
+static ACTIVE: RwLock<Option<ChaosConfig>> = RwLock::new(None);
+
+/// Kinds of injected faults, used as the `fault` metric label.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Fault {
+    Drop,
+    CorruptHeaders,
+    OversizedBody,
+    AbortResponse,
+}
+
+impl Fault {
+    pub fn label(&self) -> &'static str {
+        match self {
+            Fault::Drop => "drop",
+            Fault::CorruptHeaders => "corrupt_headers",
+            Fault::OversizedBody => "oversized_body",
+            Fault::AbortResponse => "abort_response",
+        }
+    }
+}
+
+/// Fault injection settings. Percentages are 0-100 per request.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ChaosConfig {
+    pub enabled: bool,
+    pub drop_percent: f64,
+    pub corrupt_headers_percent: f64,
+    pub oversized_body_percent: f64,
+    pub oversized_body_bytes: usize,
+    pub abort_response_percent: f64,
+}
+
+impl Default for ChaosConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            drop_percent: 0.0,
+            corrupt_headers_percent: 0.0,
+            oversized_body_percent: 0.0,
+            oversized_body_bytes: DEFAULT_OVERSIZED_BODY_BYTES,
+            abort_response_percent: 0.0,
+        }
+    }
+}
+
+impl ChaosConfig {
+    /// Environment variable name and value of each fault percentage.
+    pub fn percentages(&self) -> [(&'static str, f64); 4] {
+        [
+            ("CHAOS_DROP_PERCENT", self.drop_percent),
+            (
+                "CHAOS_CORRUPT_HEADERS_PERCENT",
+                self.corrupt_headers_percent,
+            ),
+            ("CHAOS_OVERSIZED_BODY_PERCENT", self.oversized_body_percent),
+            ("CHAOS_ABORT_RESPONSE_PERCENT", self.abort_response_percent),
+        ]
+    }
+
+    /// True when enabled and at least one fault can fire.
+    pub fn is_active(&self) -> bool {
+        self.enabled && self.percentages().iter().any(|(_, p)| *p > 0.0)
+    }
+
+    /// Decides which faults to inject into one request. A dropped request
+    /// gets no other faults.
+    pub fn roll<R: Rng + ?Sized>(&self, rng: &mut R) -> InjectedFaults {
+        if !self.is_active() {
+            return InjectedFaults::default();
+        }
+        let mut hit = |percent: f64| percent > 0.0 && rng.gen::<f64>() * 100.0 < percent;
+        if hit(self.drop_percent) {
+            return InjectedFaults {
+                drop: true,
+                ..Default::default()
+            };
+        }
+        InjectedFaults {
+            drop: false,
+            corrupt_headers: hit(self.corrupt_headers_percent),
+            oversized_body: hit(self.oversized_body_percent).then_some(self.oversized_body_bytes),
+            abort_response: hit(self.abort_response_percent),
+        }
+    }
+}
+
+/// Faults selected for a single request.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct InjectedFaults {
+    pub drop: bool,
+    pub corrupt_headers: bool,
+    /// Size of the oversized body to send, if selected.
+    pub oversized_body: Option<usize>,
+    pub abort_response: bool,
+}
+
+impl InjectedFaults {
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+
+    fn faults(&self) -> impl Iterator<Item = Fault> + '_ {
+        [
+            (self.drop, Fault::Drop),
+            (self.corrupt_headers, Fault::CorruptHeaders),
+            (self.oversized_body.is_some(), Fault::OversizedBody),
+            (self.abort_response, Fault::AbortResponse),
+        ]
+        .into_iter()
+        .filter_map(|(selected, fault)| selected.then_some(fault))
+    }
+
+    /// Applies header and body faults to a request about to be sent.
+    pub fn apply(&self, mut builder: RequestBuilder) -> RequestBuilder {
+        if self.corrupt_headers {
+            builder = builder
+                .header(CONTENT_TYPE, format!("chaos/{}", random_token(24)))
+                .header(ACCEPT, random_token(48));
+        }
+        if let Some(size) = self.oversized_body {
+            let body: Vec<u8> =
+                crate::seed::with_rng(|rng| (0..size).map(|_| rng.sample(Alphanumeric)).collect());
+            builder = builder.body(body);
+        }
+        builder
+    }
+}
+
+fn random_token(len: usize) -> String {
+    crate::seed::with_rng(|rng| {
+        (0..len)
+            .map(|_| char::from(rng.sample(Alphanumeric)))
+            .collect()
+    })
+}
+
+/// Installs the chaos configuration for all workers.
+pub fn init(config: ChaosConfig) {
+    let active = config.is_active();
+    *ACTIVE.write().unwrap_or_else(|e| e.into_inner()) = active.then_some(config);
+    ENABLED.store(active, Ordering::Relaxed);
+}
+
+/// Returns true if any fault injection is active.
+pub fn is_enabled() -> bool {
+    ENABLED.load(Ordering::Relaxed)
+}
+
+/// Picks faults for the next request under the global configuration and
+/// counts them in `chaos_faults_injected_total`.
+pub fn next_request_faults() -> InjectedFaults {
+    if !is_enabled() {
+        return InjectedFaults::default();
+    }
+    let config = *ACTIVE.read().unwrap_or_else(|e| e.into_inner());
+    let Some(config) = config else {
+        return InjectedFaults::default();
+    };
+    let faults = crate::seed::with_rng(|rng| config.roll(rng));
+    for fault in faults.faults() {
+        CHAOS_FAULTS_INJECTED_TOTAL
+            .with_label_values(&[fault.label()])
+            .inc();
+    }
+    faults
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn disabled_or_zero_percent_injects_nothing() {
+        let mut rng = rand::thread_rng();
+        let off = ChaosConfig {
+            drop_percent: 100.0,
+            ..Default::default()
+        };
+        assert!(!off.is_active());
+        assert!(off.roll(&mut rng).is_empty());
+
+        let zero = ChaosConfig {
+            enabled: true,
+            ..Default::default()
+        };
+        assert!(!zero.is_active());
+        assert!(zero.roll(&mut rng).is_empty());
+    }
+
+    #[test]
+    fn drop_excludes_other_faults() {
+        let config = ChaosConfig {
+            enabled: true,
+            drop_percent: 100.0,
+            corrupt_headers_percent: 100.0,
+            abort_response_percent: 100.0,
+            ..Default::default()
+        };
+        let faults = config.roll(&mut rand::thread_rng());
+        assert!(faults.drop);
+        assert_eq!(faults.faults().collect::<Vec<_>>(), vec![Fault::Drop]);
+    }
+
+    #[test]
+    fn faults_fire_at_roughly_configured_rate() {
+        let config = ChaosConfig {
+            enabled: true,
+            corrupt_headers_percent: 25.0,
+            oversized_body_percent: 100.0,
+            oversized_body_bytes: 1024,
+            ..Default::default()
+        };
+        let mut rng = rand::thread_rng();
+        let mut corrupted = 0;
+        for _ in 0..10_000 {
+            let faults = config.roll(&mut rng);
+            assert_eq!(faults.oversized_body, Some(1024));
+            assert!(!faults.drop && !faults.abort_response);
+            corrupted += faults.corrupt_headers as u32;
+        }
+        assert!((2000..3000).contains(&corrupted), "{}", corrupted);
+    }
+}
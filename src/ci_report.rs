@@ -0,0 +1,421 @@
+//! Commit status and pull request comment on GitHub or GitLab (`CI_REPORT`).
+//!
+//! At test end the outcome is posted as a commit status on the tested commit
+//! and, when the run belongs to a pull/merge request, a markdown summary
+//! (thresholds, latency percentiles, report link) is added as a comment.
+//! Everything comes from the variables the CI system already sets:
+//!
+//! - `CI_REPORT=github`: `GITHUB_TOKEN`, `GITHUB_REPOSITORY`, `GITHUB_SHA`,
+//!   `GITHUB_API_URL`; the PR number from `GITHUB_REF`
+//!   (`refs/pull/<n>/merge`) or `CI_PR_NUMBER`.
+//! - `CI_REPORT=gitlab`: `GITLAB_TOKEN`, `CI_API_V4_URL`, `CI_PROJECT_ID`,
+//!   `CI_COMMIT_SHA`, `CI_MERGE_REQUEST_IID`.
+//!
+//! `CI_STATUS_CONTEXT` names the status (default `load-test`).
+
+use std::env;
+use std::time::Duration;
+
+use serde_json::json;
+use thiserror::Error;
+
+use crate::run_summary::RunSummary;
+
+const CI_TIMEOUT: Duration = Duration::from_secs(15);
+
+/// Longest status description the forges accept.
+const MAX_DESCRIPTION: usize = 140;
+
+#[derive(Error, Debug, PartialEq)]
+pub enum CiReportError {
+    #[error("invalid CI_REPORT '{0}': expected github or gitlab")]
+    UnknownForge(String),
+
+    #[error("CI_REPORT={forge} requires {var}")]
+    MissingVar {
+        forge: &'static str,
+        var: &'static str,
+    },
+
+    #[error("{action} failed: {message}")]
+    Request {
+        action: &'static str,
+        message: String,
+    },
+}
+
+/// Where results are posted.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Forge {
+    GitHub {
+        api_url: String,
+        token: String,
+        repository: String,
+        sha: String,
+        pull_request: Option<u64>,
+    },
+    GitLab {
+        api_url: String,
+        token: String,
+        project_id: String,
+        sha: String,
+        merge_request: Option<u64>,
+    },
+}
+
+/// Posts threshold results to the forge.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CiReporter {
+    pub forge: Forge,
+    pub context: String,
+}
+
+/// The PR number in a `refs/pull/<n>/merge` ref.
+fn pull_request_from_ref(git_ref: &str) -> Option<u64> {
+    git_ref
+        .strip_prefix("refs/pull/")?
+        .split('/')
+        .next()?
+        .parse()
+        .ok()
+}
+
+impl CiReporter {
+    /// Reads `CI_REPORT` and the forge's variables; `None` when unset.
+    pub fn from_env() -> Result<Option<Self>, CiReportError> {
+        let var = |name: &str| env::var(name).ok().filter(|v| !v.trim().is_empty());
+        let Some(mode) = var("CI_REPORT") else {
+            return Ok(None);
+        };
+        let required = |forge: &'static str, name: &'static str| {
+            var(name).ok_or(CiReportError::MissingVar { forge, var: name })
+        };
+
+        let forge = match mode.trim().to_lowercase().as_str() {
+            "github" => Forge::GitHub {
+                api_url: var("GITHUB_API_URL")
+                    .unwrap_or_else(|| "https://api.github.com".to_string())
+                    .trim_end_matches('/')
+                    .to_string(),
+                token: required("github", "GITHUB_TOKEN")?,
+                repository: required("github", "GITHUB_REPOSITORY")?,
+                sha: required("github", "GITHUB_SHA")?,
+                pull_request: var("CI_PR_NUMBER")
+                    .and_then(|n| n.trim().parse().ok())
+                    .or_else(|| var("GITHUB_REF").as_deref().and_then(pull_request_from_ref)),
+            },
+            "gitlab" => Forge::GitLab {
+                api_url: required("gitlab", "CI_API_V4_URL")?
+                    .trim_end_matches('/')
+                    .to_string(),
+                token: required("gitlab", "GITLAB_TOKEN")?,
+                project_id: required("gitlab", "CI_PROJECT_ID")?,
+                sha: required("gitlab", "CI_COMMIT_SHA")?,
+                merge_request: var("CI_MERGE_REQUEST_IID").and_then(|n| n.trim().parse().ok()),
+            },
+            _ => return Err(CiReportError::UnknownForge(mode)),
+        };
+        Ok(Some(Self {
+            forge,
+            context: var("CI_STATUS_CONTEXT").unwrap_or_else(|| "load-test".to_string()),
+        }))
+    }
+
+    /// Posts the commit status, then the comment if there is a PR/MR.
+    pub async fn report(
+        &self,
+        summary: &RunSummary,
+        report_url: Option<&str>,
+    ) -> Result<(), CiReportError> {
+        let client = reqwest::Client::builder()
+            .timeout(CI_TIMEOUT)
+            .user_agent(concat!("rust_loadtest/", env!("CARGO_PKG_VERSION")))
+            .build()
+            .map_err(|e| CiReportError::Request {
+                action: "client",
+                message: e.to_string(),
+            })?;
+        let description = status_description(summary);
+
+        let (status, comment) = match &self.forge {
+            Forge::GitHub {
+                api_url,
+                token,
+                repository,
+                sha,
+                pull_request,
+            } => {
+                let status = client
+                    .post(format!("{}/repos/{}/statuses/{}", api_url, repository, sha))
+                    .bearer_auth(token)
+                    .header("Accept", "application/vnd.github+json")
+                    .json(&json!({
+                        "state": if summary.passed { "success" } else { "failure" },
+                        "context": self.context,
+                        "description": description,
+                        "target_url": report_url,
+                    }));
+                let comment = pull_request.map(|number| {
+                    client
+                        .post(format!(
+                            "{}/repos/{}/issues/{}/comments",
+                            api_url, repository, number
+                        ))
+                        .bearer_auth(token)
+                        .header("Accept", "application/vnd.github+json")
+                        .json(&json!({ "body": markdown(summary, report_url) }))
+                });
+                (status, comment)
+            }
+            Forge::GitLab {
+                api_url,
+                token,
+                project_id,
+                sha,
+                merge_request,
+            } => {
+                let project = encode_component(project_id);
+                let mut query = vec![
+                    ("state", if summary.passed { "success" } else { "failed" }),
+                    ("name", self.context.as_str()),
+                    ("description", description.as_str()),
+                ];
+                if let Some(url) = report_url {
+                    query.push(("target_url", url));
+                }
+                let status = client
+                    .post(format!("{}/projects/{}/statuses/{}", api_url, project, sha))
+                    .header("PRIVATE-TOKEN", token)
+                    .query(&query);
+                let comment = merge_request.map(|iid| {
+                    client
+                        .post(format!(
+                            "{}/projects/{}/merge_requests/{}/notes",
+                            api_url, project, iid
+                        ))
+                        .header("PRIVATE-TOKEN", token)
+                        .json(&json!({ "body": markdown(summary, report_url) }))
+                });
+                (status, comment)
+            }
+        };
+
+        send(status, "commit status").await?;
+        if let Some(comment) = comment {
+            send(comment, "pull request comment").await?;
+        }
+        Ok(())
+    }
+}
+
+async fn send(request: reqwest::RequestBuilder, action: &'static str) -> Result<(), CiReportError> {
+    let response = request.send().await.map_err(|e| CiReportError::Request {
+        action,
+        message: e.to_string(),
+    })?;
+    let status = response.status();
+    if !status.is_success() {
+        let body = response.text().await.unwrap_or_default();
+        return Err(CiReportError::Request {
+            action,
+            message: format!(
+                "HTTP {}: {}",
+                status,
+                body.chars().take(300).collect::<String>()
+            ),
+        });
+    }
+    Ok(())
+}
+
+/// Percent-encodes a path component (GitLab project paths like `group/app`).
+fn encode_component(value: &str) -> String {
+    value
+        .bytes()
+        .map(|b| match b {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => {
+                (b as char).to_string()
+            }
+            _ => format!("%{:02X}", b),
+        })
+        .collect()
+}
+
+/// One-line status description, within the forges' length limit.
+pub fn status_description(summary: &RunSummary) -> String {
+    let failed = summary.thresholds.iter().filter(|t| !t.passed).count();
+    let mut description = if summary.passed {
+        format!("Passed: {} requests", summary.requests)
+    } else if failed > 0 {
+        format!(
+            "Failed: {} of {} scenario thresholds",
+            failed,
+            summary.thresholds.len()
+        )
+    } else {
+        format!("Failed: {}", summary.outcome)
+    };
+    if let Some(latency) = &summary.latency {
+        description.push_str(&format!(
+            ", p95 {:.1} ms, p99 {:.1} ms",
+            latency.p95_ms, latency.p99_ms
+        ));
+    }
+    if description.chars().count() > MAX_DESCRIPTION {
+        description = description.chars().take(MAX_DESCRIPTION - 1).collect();
+        description.push('…');
+    }
+    description
+}
+
+/// The pull request comment: verdict, thresholds and latency tables.
+pub fn markdown(summary: &RunSummary, report_url: Option<&str>) -> String {
+    let mut md = if summary.passed {
+        format!("### ✅ Load test `{}` passed\n\n", summary.run_id)
+    } else {
+        format!(
+            "### ❌ Load test `{}` failed (`{}`)\n\n",
+            summary.run_id, summary.outcome
+        )
+    };
+    if !summary.message.is_empty() {
+        md.push_str(&format!("{}\n\n", summary.message));
+    }
+    md.push_str(&format!(
+        "{} requests, {} responses on `{}`.\n\n",
+        summary.requests, summary.responses, summary.node_id
+    ));
+
+    if !summary.thresholds.is_empty() {
+        md.push_str("| Scenario | Required | Success rate | Result |\n|---|---|---:|---|\n");
+        for threshold in &summary.thresholds {
+            let rate = threshold
+                .success_rate
+                .map_or_else(|| "–".to_string(), |r| format!("{:.3}%", r));
+            md.push_str(&format!(
+                "| {} | {} | {} | {} |\n",
+                cell(&threshold.scenario),
+                cell(&threshold.required),
+                rate,
+                if threshold.passed { "✅" } else { "❌" }
+            ));
+        }
+        md.push('\n');
+    }
+
+    let overall = summary.latency.iter().map(|l| ("all requests", l));
+    let scenarios = summary
+        .scenarios
+        .iter()
+        .map(|row| (row.label.as_str(), &row.latency));
+    let rows: Vec<_> = overall.chain(scenarios).collect();
+    if !rows.is_empty() {
+        md.push_str(
+            "| Latency | Count | p50 (ms) | p95 (ms) | p99 (ms) | Max (ms) |\n\
+             |---|---:|---:|---:|---:|---:|\n",
+        );
+        for (label, latency) in rows {
+            md.push_str(&format!(
+                "| {} | {} | {:.1} | {:.1} | {:.1} | {:.1} |\n",
+                cell(label),
+                latency.count,
+                latency.p50_ms,
+                latency.p95_ms,
+                latency.p99_ms,
+                latency.max_ms
+            ));
+        }
+        md.push('\n');
+    }
+
+    if let Some(url) = report_url {
+        md.push_str(&format!("[Full report]({})\n", url));
+    }
+    md
+}
+
+/// Escapes a markdown table cell.
+fn cell(text: &str) -> String {
+    text.replace('|', "\\|").replace('\n', " ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::run_summary::{LabelledLatency, LatencySummary, ThresholdSummary};
+
+    fn summary(passed: bool) -> RunSummary {
+        let latency = LatencySummary {
+            count: 100,
+            mean_ms: 12.0,
+            p50_ms: 10.0,
+            p90_ms: 20.0,
+            p95_ms: 30.0,
+            p99_ms: 40.0,
+            max_ms: 50.0,
+        };
+        RunSummary {
+            run_id: "pr-7".to_string(),
+            node_id: "ci".to_string(),
+            finished_at: 0,
+            passed,
+            outcome: if passed {
+                "success"
+            } else {
+                "threshold_failed"
+            }
+            .to_string(),
+            exit_code: if passed { 0 } else { 3 },
+            message: String::new(),
+            requests: 100,
+            responses: 100,
+            latency: Some(latency.clone()),
+            scenarios: vec![LabelledLatency {
+                label: "a|b".to_string(),
+                latency,
+            }],
+            steps: Vec::new(),
+            thresholds: vec![ThresholdSummary {
+                scenario: "checkout".to_string(),
+                required: "successRate > 99.5%".to_string(),
+                success_rate: Some(98.0),
+                passed,
+            }],
+            assertions: Vec::new(),
+            seed: None,
+        }
+    }
+
+    #[test]
+    fn reads_pull_request_from_ref() {
+        assert_eq!(pull_request_from_ref("refs/pull/42/merge"), Some(42));
+        assert_eq!(pull_request_from_ref("refs/heads/main"), None);
+        assert_eq!(encode_component("group/app"), "group%2Fapp");
+    }
+
+    #[test]
+    fn describes_status() {
+        assert_eq!(
+            status_description(&summary(true)),
+            "Passed: 100 requests, p95 30.0 ms, p99 40.0 ms"
+        );
+        assert_eq!(
+            status_description(&summary(false)),
+            "Failed: 1 of 1 scenario thresholds, p95 30.0 ms, p99 40.0 ms"
+        );
+        let mut long = summary(false);
+        long.thresholds.clear();
+        long.outcome = "x".repeat(200);
+        assert_eq!(status_description(&long).chars().count(), MAX_DESCRIPTION);
+    }
+
+    #[test]
+    fn renders_markdown_comment() {
+        let md = markdown(&summary(false), Some("https://r/report.html"));
+        assert!(md.starts_with("### ❌ Load test `pr-7` failed (`threshold_failed`)"));
+        assert!(md.contains("| checkout | successRate > 99.5% | 98.000% | ❌ |"));
+        assert!(md.contains("| all requests | 100 | 10.0 | 30.0 | 40.0 | 50.0 |"));
+        assert!(md.contains("| a\\|b | 100 |"));
+        assert!(md.ends_with("[Full report](https://r/report.html)\n"));
+    }
+}
@@ -1,13 +1,21 @@
 use reqwest::header::{HeaderMap, HeaderName, HeaderValue};
-use std::fs::File;
-use std::io::Read;
-use std::net::SocketAddr;
+use std::net::{IpAddr, SocketAddr};
 use std::str::FromStr;
+use std::sync::Arc;
+use std::time::Duration;
+use tracing::{debug, info, warn};
 
+use crate::addr::{self, ResolveOverride};
 use crate::connection_pool::PoolConfig;
-use crate::utils::parse_headers_with_escapes;
+use crate::dns_refresh::{self, RefreshingResolver};
+use crate::dns_round_robin::ResolvedTarget;
+use crate::header_layers::HeaderLayer;
+use crate::ip_family::{FamilyResolver, IpFamily};
+use crate::sni::{SniOverride, SniResolver};
+use crate::tls;
 
 /// Configuration for building the HTTP client.
+#[derive(Clone)]
 pub struct ClientConfig {
     pub skip_tls_verify: bool,
     pub resolve_target_addr: Option<String>,
@@ -17,6 +25,65 @@ pub struct ClientConfig {
     pub pool_config: Option<PoolConfig>,
     /// Enable per-request cookie jar (required for scenario session isolation).
     pub cookie_store: bool,
+    /// Source address to bind outgoing connections to (one entry of `LOCAL_ADDRS`).
+    pub local_address: Option<IpAddr>,
+    /// Pin a hostname to one of its resolved addresses (`DNS_ROUND_ROBIN`).
+    pub pinned_target: Option<(String, SocketAddr)>,
+    /// Restrict DNS results to one address family (`IP_FAMILY`).
+    pub ip_family: IpFamily,
+    /// Cache TLS sessions for resumption (`TLS_SESSION_RESUMPTION`).
+    pub tls_session_resumption: bool,
+    /// Present a server name other than the connect host (`TLS_SERVER_NAME`).
+    pub sni_override: Option<SniOverride>,
+    /// Send every request through this proxy URL.
+    pub proxy: Option<String>,
+    /// Per-request timeout, overriding the connection pool's.
+    pub timeout: Option<Duration>,
+}
+
+/// Client settings a scenario overrides for its own workers (the YAML
+/// scenario `client:` block), layered over the node's [`ClientConfig`].
+///
+/// Lets one plan mix trust domains, e.g. an mTLS admin API next to the
+/// public edge: every worker running the scenario builds its client with
+/// these applied.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ClientOverrides {
+    pub skip_tls_verify: Option<bool>,
+    pub client_cert_path: Option<String>,
+    pub client_key_path: Option<String>,
+    /// Default headers added to (and replacing same-named) node headers.
+    pub headers: Vec<(String, String)>,
+    pub proxy: Option<String>,
+    pub timeout: Option<Duration>,
+}
+
+impl ClientOverrides {
+    /// Applies the overrides to `config`.
+    pub fn apply(&self, config: &mut ClientConfig) {
+        if let Some(skip) = self.skip_tls_verify {
+            config.skip_tls_verify = skip;
+        }
+        if self.client_cert_path.is_some() {
+            config.client_cert_path = self.client_cert_path.clone();
+            config.client_key_path = self.client_key_path.clone();
+        }
+        if !self.headers.is_empty() {
+            let mut headers: Vec<String> = config.custom_headers.iter().cloned().collect();
+            headers.extend(
+                self.headers
+                    .iter()
+                    .map(|(name, value)| format!("{}:{}", name, value.replace(',', "\\,"))),
+            );
+            config.custom_headers = Some(headers.join(","));
+        }
+        if self.proxy.is_some() {
+            config.proxy = self.proxy.clone();
+        }
+        if self.timeout.is_some() {
+            config.timeout = self.timeout;
+        }
+    }
 }
 
 /// Result of building the client, includes parsed headers for logging.
@@ -36,47 +103,86 @@ pub fn build_client(
         if !resolve_str.is_empty() {
             client_builder = configure_dns_override(client_builder, resolve_str)?;
         } else {
-            println!("RESOLVE_TARGET_ADDR is set but empty, no DNS override will be applied.");
+            warn!("RESOLVE_TARGET_ADDR is set but empty, no DNS override will be applied");
         }
     }
 
-    // mTLS Configuration
-    client_builder = configure_mtls(
-        client_builder,
-        config.client_cert_path.as_deref(),
-        config.client_key_path.as_deref(),
-    )?;
+    // Per-address pinning (DNS_ROUND_ROBIN)
+    if let Some((ref host, addr)) = config.pinned_target {
+        client_builder = client_builder.resolve(host, addr);
+        debug!(host = %host, addr = %addr, "Pinning target host to resolved address");
+    }
+
+    // Periodically re-resolved cache (DNS_REFRESH_INTERVAL), honouring
+    // TLS_SERVER_NAME and IP_FAMILY; otherwise the server name override,
+    // or address family selection alone
+    if dns_refresh::enabled() {
+        client_builder = client_builder.dns_resolver(Arc::new(RefreshingResolver::new(
+            config.sni_override.clone(),
+            config.ip_family,
+        )));
+        debug!("Resolving through the re-resolved DNS cache");
+    } else if let Some(ref sni) = config.sni_override {
+        client_builder =
+            client_builder.dns_resolver(Arc::new(SniResolver::new(sni.clone(), config.ip_family)));
+        debug!(
+            server_name = %sni.server_name,
+            connect_host = %sni.connect_host,
+            "Presenting TLS server name for connect host"
+        );
+    } else if config.ip_family != IpFamily::Auto {
+        client_builder =
+            client_builder.dns_resolver(Arc::new(FamilyResolver::new(config.ip_family)));
+        debug!(ip_family = %config.ip_family, "Restricting connections to one address family");
+    }
+
+    // TLS Configuration (verification, mTLS, session resumption)
+    if config.skip_tls_verify {
+        warn!("Skipping TLS certificate verification");
+    }
+    client_builder = client_builder.use_preconfigured_tls(tls::client_tls_config(config)?);
 
     // Custom Headers Configuration
     let parsed_headers = configure_custom_headers(config.custom_headers.as_deref())?;
     if !parsed_headers.is_empty() {
         client_builder = client_builder.default_headers(parsed_headers.clone());
-        println!("Successfully configured custom default headers.");
+        info!(
+            header_count = parsed_headers.len(),
+            "Configured custom default headers"
+        );
     }
 
     // Connection Pool Configuration
     let pool_config = config.pool_config.clone().unwrap_or_default();
     client_builder = pool_config.apply_to_builder(client_builder);
-    println!(
-        "Connection pool configured: max_idle_per_host={}, idle_timeout={:?}",
-        pool_config.max_idle_per_host, pool_config.idle_timeout
+    info!(
+        max_idle_per_host = pool_config.max_idle_per_host,
+        idle_timeout_secs = pool_config.idle_timeout.as_secs_f64(),
+        "Connection pool configured"
     );
 
+    if let Some(timeout) = config.timeout {
+        client_builder = client_builder.timeout(timeout);
+    }
+
+    // Proxy
+    if let Some(ref proxy) = config.proxy {
+        client_builder = client_builder.proxy(reqwest::Proxy::all(proxy)?);
+        debug!(proxy = %proxy, "Sending requests through proxy");
+    }
+
     // Cookie store for session isolation (scenario workers)
     if config.cookie_store {
         client_builder = client_builder.cookie_store(true);
     }
 
-    // Build client with TLS settings
-    let client = if config.skip_tls_verify {
-        println!("WARNING: Skipping TLS certificate verification.");
-        client_builder
-            .danger_accept_invalid_certs(true)
-            .danger_accept_invalid_hostnames(true)
-            .build()?
-    } else {
-        client_builder.build()?
-    };
+    // Source address binding (LOCAL_ADDRS)
+    if let Some(addr) = config.local_address {
+        client_builder = client_builder.local_address(addr);
+        debug!(local_address = %addr, "Binding outgoing connections to local address");
+    }
+
+    let client = client_builder.build()?;
 
     Ok(ClientBuildResult {
         client,
@@ -84,218 +190,146 @@ pub fn build_client(
     })
 }
 
-fn configure_dns_override(
-    mut client_builder: reqwest::ClientBuilder,
-    resolve_str: &str,
-) -> Result<reqwest::ClientBuilder, Box<dyn std::error::Error + Send + Sync>> {
-    println!(
-        "Attempting to apply DNS override from RESOLVE_TARGET_ADDR: {}",
-        resolve_str
-    );
+/// HTTP clients bound to each configured source address (`LOCAL_ADDRS`)
+/// and, with `DNS_ROUND_ROBIN`, pinned to each resolved target address.
+///
+/// Each client keeps its own connection pool, so spreading workers across
+/// them spreads connections across source IPs: every address gets its own
+/// ephemeral port range, and the target sees traffic from many clients.
+/// Worker `i` uses source address `i % len`, matching
+/// [`crate::config::Config::local_addr_for_worker`]; target addresses are
+/// handed out round-robin within each source address.
+#[derive(Clone)]
+pub struct SourceAddrClients {
+    clients: Vec<reqwest::Client>,
+    target_addrs: Vec<Option<SocketAddr>>,
+}
 
-    let parts: Vec<&str> = resolve_str.split(':').collect();
-    if parts.len() != 3 {
-        return Err(format!(
-            "RESOLVE_TARGET_ADDR environment variable ('{}') is not in the expected format 'hostname:ip:port'",
-            resolve_str
-        ).into());
+impl SourceAddrClients {
+    /// Builds one client per combination of address in `local_addrs` and
+    /// resolved `target` address. With neither, every worker shares
+    /// `default_client`.
+    pub fn build(
+        config: &ClientConfig,
+        local_addrs: &[IpAddr],
+        target: Option<&ResolvedTarget>,
+        default_client: reqwest::Client,
+    ) -> Result<Self, Box<dyn std::error::Error + Send + Sync>> {
+        let target_addrs = target.map(|t| t.addrs.as_slice()).unwrap_or_default();
+        if local_addrs.is_empty() && target_addrs.is_empty() {
+            return Ok(Self::single(default_client));
+        }
+        let locals = local_addrs.len().max(1);
+        let targets = target_addrs.len().max(1);
+        let mut clients = Vec::with_capacity(locals * targets);
+        let mut pinned = Vec::with_capacity(locals * targets);
+        // Index k uses local address k % locals, so worker i keeps the
+        // source address that `local_addr_for_worker(i)` reports.
+        for k in 0..locals * targets {
+            let mut bound = config.clone();
+            bound.local_address = local_addrs.get(k % locals).copied();
+            let addr = target_addrs.get(k / locals).copied();
+            bound.pinned_target = target.zip(addr).map(|(t, a)| (t.host.clone(), a));
+            clients.push(build_client(&bound)?.client);
+            pinned.push(addr);
+        }
+        if !local_addrs.is_empty() {
+            info!(
+                local_addrs = ?local_addrs,
+                "Outgoing connections spread across local addresses"
+            );
+        }
+        Ok(Self {
+            clients,
+            target_addrs: pinned,
+        })
     }
 
-    let hostname_to_override = parts[0].trim();
-    let ip_to_resolve_to = parts[1].trim();
-    let port_to_connect_to_str = parts[2].trim();
-
-    if hostname_to_override.is_empty() {
-        return Err(
-            "RESOLVE_TARGET_ADDR: hostname part cannot be empty. Format: 'hostname:ip:port'".into(),
-        );
-    }
-    if ip_to_resolve_to.is_empty() {
-        return Err(
-            "RESOLVE_TARGET_ADDR: IP address part cannot be empty. Format: 'hostname:ip:port'"
-                .into(),
-        );
-    }
-    if port_to_connect_to_str.is_empty() {
-        return Err(
-            "RESOLVE_TARGET_ADDR: port part cannot be empty. Format: 'hostname:ip:port'".into(),
-        );
+    /// All workers share `client`.
+    pub fn single(client: reqwest::Client) -> Self {
+        Self {
+            clients: vec![client],
+            target_addrs: vec![None],
+        }
     }
 
-    let port_to_connect_to: u16 = port_to_connect_to_str.parse().map_err(|e| {
-        format!(
-            "Failed to parse port '{}' in RESOLVE_TARGET_ADDR: {}. Must be a valid u16. Format: 'hostname:ip:port'",
-            port_to_connect_to_str, e
-        )
-    })?;
+    /// Client for worker `task_id`.
+    pub fn for_worker(&self, task_id: usize) -> reqwest::Client {
+        self.clients[task_id % self.clients.len()].clone()
+    }
 
-    let socket_addr_str = format!("{}:{}", ip_to_resolve_to, port_to_connect_to);
-    let socket_addr: SocketAddr = socket_addr_str.parse().map_err(|e| {
-        format!(
-            "Failed to parse IP/Port '{}' into SocketAddr for RESOLVE_TARGET_ADDR: {}. Ensure IP and port are valid. Format: 'hostname:ip:port'",
-            socket_addr_str, e
-        )
-    })?;
+    /// Target address worker `task_id`'s client is pinned to, if any.
+    pub fn target_addr_for_worker(&self, task_id: usize) -> Option<SocketAddr> {
+        self.target_addrs[task_id % self.target_addrs.len()]
+    }
 
-    client_builder = client_builder.resolve(hostname_to_override, socket_addr);
-    println!(
-        "Successfully configured DNS override: '{}' will resolve to {}",
-        hostname_to_override, socket_addr
-    );
+    pub fn len(&self) -> usize {
+        self.clients.len()
+    }
 
-    Ok(client_builder)
+    pub fn is_empty(&self) -> bool {
+        self.clients.is_empty()
+    }
 }
 
-fn configure_mtls(
+fn configure_dns_override(
     mut client_builder: reqwest::ClientBuilder,
-    cert_path: Option<&str>,
-    key_path: Option<&str>,
+    resolve_str: &str,
 ) -> Result<reqwest::ClientBuilder, Box<dyn std::error::Error + Send + Sync>> {
-    match (cert_path, key_path) {
-        (Some(cert_path), Some(key_path)) => {
-            println!("Attempting to load mTLS certificate from: {}", cert_path);
-            println!("Attempting to load mTLS private key from: {}", key_path);
-
-            let mut cert_file = File::open(cert_path).map_err(|e| {
-                format!(
-                    "Failed to open client certificate file '{}': {}",
-                    cert_path, e
-                )
-            })?;
-            let mut cert_pem_buf = Vec::new();
-            cert_file.read_to_end(&mut cert_pem_buf).map_err(|e| {
-                format!(
-                    "Failed to read client certificate file '{}': {}",
-                    cert_path, e
-                )
-            })?;
-
-            let mut key_file = File::open(key_path)
-                .map_err(|e| format!("Failed to open client key file '{}': {}", key_path, e))?;
-            let mut key_pem_buf = Vec::new();
-            key_file
-                .read_to_end(&mut key_pem_buf)
-                .map_err(|e| format!("Failed to read client key file '{}': {}", key_path, e))?;
-
-            // Validate certificate PEM
-            let mut cert_pem_cursor = std::io::Cursor::new(cert_pem_buf.as_slice());
-            let certs_result: Vec<_> = rustls_pemfile::certs(&mut cert_pem_cursor).collect();
-            if certs_result.is_empty() {
-                return Err(format!("No PEM certificates found in {}", cert_path).into());
-            }
-            for cert in certs_result {
-                if let Err(e) = cert {
-                    return Err(format!(
-                        "Failed to parse PEM certificates from '{}': {}",
-                        cert_path, e
-                    )
-                    .into());
-                }
-            }
-
-            // Validate private key PEM (must be PKCS#8)
-            let mut key_pem_cursor = std::io::Cursor::new(key_pem_buf.as_slice());
-            let keys_result: Vec<_> =
-                rustls_pemfile::pkcs8_private_keys(&mut key_pem_cursor).collect();
-            if keys_result.is_empty() {
-                return Err(format!(
-                    "No PKCS#8 private keys found in '{}'. Ensure the file contains a valid PEM-encoded PKCS#8 private key.",
-                    key_path
-                ).into());
-            }
-            for key in keys_result {
-                if let Err(e) = key {
-                    return Err(format!(
-                        "Failed to parse private key from '{}' as PKCS#8: {}. Please ensure the key is PEM-encoded and in PKCS#8 format.",
-                        key_path, e
-                    ).into());
-                }
-            }
-
-            // Combine certificate PEM and key PEM into one buffer
-            let mut combined_pem_buf = Vec::new();
-            combined_pem_buf.extend_from_slice(&cert_pem_buf);
-            if !cert_pem_buf.ends_with(b"\n") && !key_pem_buf.starts_with(b"\n") {
-                combined_pem_buf.push(b'\n');
-            }
-            combined_pem_buf.extend_from_slice(&key_pem_buf);
-
-            let identity = reqwest::Identity::from_pem(&combined_pem_buf)
-                .map_err(|e| format!(
-                    "Failed to create reqwest::Identity from PEM (cert+key): {}. Ensure the key is PKCS#8 and the certificate is valid.",
-                    e
-                ))?;
+    debug!(
+        resolve_target_addr = %resolve_str,
+        "Applying DNS override from RESOLVE_TARGET_ADDR"
+    );
 
-            client_builder = client_builder.identity(identity);
-            println!("Successfully configured mTLS with client certificate and key.");
-        }
-        (Some(_), None) => {
-            return Err("CLIENT_CERT_PATH is set, but CLIENT_KEY_PATH is missing for mTLS.".into());
-        }
-        (None, Some(_)) => {
-            return Err("CLIENT_KEY_PATH is set, but CLIENT_CERT_PATH is missing for mTLS.".into());
-        }
-        (None, None) => {
-            // No mTLS configured
+    let overrides = parse_resolve_target_addr(resolve_str)?;
+    // URL hosts are lowercase, so entries match whatever their case.
+    let mut hosts: Vec<String> = Vec::new();
+    for o in &overrides {
+        let host = o.host.to_ascii_lowercase();
+        if !hosts.contains(&host) {
+            hosts.push(host);
         }
     }
+    for host in hosts {
+        let addrs = addr::resolved_addrs(&overrides, &host);
+        client_builder = client_builder.resolve_to_addrs(&host, &addrs);
+        info!(
+            hostname = %host,
+            addresses = ?addrs,
+            "Configured DNS override"
+        );
+    }
 
     Ok(client_builder)
 }
 
+/// Parses `RESOLVE_TARGET_ADDR`: comma-separated `hostname:ip:port` entries,
+/// the IP optionally an IPv6 literal in brackets. A hostname listed more
+/// than once resolves to all of its addresses.
+pub fn parse_resolve_target_addr(resolve_str: &str) -> Result<Vec<ResolveOverride>, String> {
+    addr::parse_resolve_overrides(resolve_str)
+        .map_err(|e| format!("RESOLVE_TARGET_ADDR ('{}'): {}", resolve_str, e))
+}
+
 fn configure_custom_headers(
     custom_headers_str: Option<&str>,
 ) -> Result<HeaderMap, Box<dyn std::error::Error + Send + Sync>> {
+    // The client has no scenario context, so values are sent verbatim; the
+    // scenario executor renders the same layer per request instead.
+    let layer = HeaderLayer::from_custom_headers(custom_headers_str)?;
     let mut parsed_headers = HeaderMap::new();
-
-    let headers_str = match custom_headers_str {
-        Some(s) if !s.is_empty() => s,
-        _ => return Ok(parsed_headers),
-    };
-
-    println!("Attempting to parse CUSTOM_HEADERS: {}", headers_str);
-
-    let header_pairs = parse_headers_with_escapes(headers_str);
-
-    for header_pair_str in header_pairs {
-        let header_pair_str_trimmed = header_pair_str.trim();
-        if header_pair_str_trimmed.is_empty() {
-            continue;
-        }
-
-        let parts: Vec<&str> = header_pair_str_trimmed.splitn(2, ':').collect();
-        if parts.len() != 2 {
-            return Err(format!(
-                "Invalid header format in CUSTOM_HEADERS: '{}'. Expected 'Name:Value'.",
-                header_pair_str_trimmed
-            )
-            .into());
-        }
-
-        let name_str = parts[0].trim();
-        let value_str = parts[1].trim();
-
-        if name_str.is_empty() {
-            return Err(format!(
-                "Invalid header format: Header name cannot be empty in '{}'.",
-                header_pair_str_trimmed
-            )
-            .into());
-        }
-
-        let unescaped_value = value_str.replace("\\,", ",");
-
-        let header_name = HeaderName::from_str(name_str)
-            .map_err(|e| format!("Invalid header name: {}. Name: '{}'", e, name_str))?;
-        let header_value = HeaderValue::from_str(&unescaped_value).map_err(|e| {
+    for (name, value) in &layer.headers {
+        let header_name = HeaderName::from_str(name)
+            .map_err(|e| format!("Invalid header name: {}. Name: '{}'", e, name))?;
+        let header_value = HeaderValue::from_str(value.source()).map_err(|e| {
             format!(
                 "Invalid header value for '{}': {}. Value: '{}'",
-                name_str, e, unescaped_value
+                name,
+                e,
+                value.source()
             )
         })?;
-
         parsed_headers.insert(header_name, header_value);
     }
-
     Ok(parsed_headers)
 }
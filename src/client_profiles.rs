@@ -0,0 +1,130 @@
+//! Weighted client profiles for a realistic device mix.
+//!
+//! A plan's top-level `clients:` list describes the kinds of client hitting
+//! the target — a mobile app, a desktop browser, a partner integration —
+//! each with a `weight`. Every virtual user draws one profile when it
+//! starts and keeps it for the whole run, so the traffic mix follows the
+//! weights the way a real device distribution would.
+//!
+//! A profile contributes a header layer (its `userAgent`, `acceptLanguage`
+//! and `headers`), sitting above the global layer and below the scenario's
+//! own headers (see [`crate::header_layers`]), and a `thinkTimeMultiplier`
+//! that stretches or shortens every step's think time. Step metrics are
+//! repeated in the `*_by_client` series with a `client_profile` label.
+
+use std::sync::{Arc, RwLock};
+
+use rand::Rng;
+
+use crate::header_layers::HeaderLayer;
+
+/// One kind of client, as configured under `clients:`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ClientProfile {
+    pub name: String,
+    pub weight: f64,
+    /// Headers sent by this client, including `User-Agent` and
+    /// `Accept-Language` when set.
+    pub headers: HeaderLayer,
+    /// Factor applied to every step's think time.
+    pub think_time_multiplier: f64,
+}
+
+/// The configured profiles, drawn from in proportion to their weights.
+#[derive(Debug, Clone, Default)]
+pub struct ClientProfiles {
+    profiles: Vec<Arc<ClientProfile>>,
+    cumulative: Vec<f64>,
+}
+
+impl ClientProfiles {
+    /// Profiles with a non-positive weight are never drawn.
+    pub fn new(profiles: Vec<ClientProfile>) -> Self {
+        let mut total = 0.0;
+        let mut kept = Vec::with_capacity(profiles.len());
+        let mut cumulative = Vec::with_capacity(profiles.len());
+        for profile in profiles.into_iter().filter(|p| p.weight > 0.0) {
+            total += profile.weight;
+            cumulative.push(total);
+            kept.push(Arc::new(profile));
+        }
+        Self {
+            profiles: kept,
+            cumulative,
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.profiles.is_empty()
+    }
+
+    pub fn profiles(&self) -> &[Arc<ClientProfile>] {
+        &self.profiles
+    }
+
+    /// Profile at position `point` in `[0, total weight)`.
+    fn pick(&self, point: f64) -> Option<Arc<ClientProfile>> {
+        let idx = self
+            .cumulative
+            .partition_point(|&c| c <= point)
+            .min(self.profiles.len().checked_sub(1)?);
+        Some(self.profiles[idx].clone())
+    }
+
+    /// Draws a profile in proportion to the weights; `None` when no
+    /// profiles are configured.
+    pub fn sample(&self) -> Option<Arc<ClientProfile>> {
+        let total = *self.cumulative.last()?;
+        self.pick(crate::seed::with_rng(|rng| rng.gen_range(0.0..total)))
+    }
+}
+
+lazy_static::lazy_static! {
+    static ref CLIENT_PROFILES: RwLock<Arc<ClientProfiles>> = RwLock::new(Arc::default());
+}
+
+/// Sets the profiles drawn by workers started afterwards.
+pub fn set_client_profiles(profiles: ClientProfiles) {
+    *CLIENT_PROFILES.write().unwrap() = Arc::new(profiles);
+}
+
+/// The current profiles.
+pub fn client_profiles() -> Arc<ClientProfiles> {
+    CLIENT_PROFILES.read().unwrap().clone()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn profile(name: &str, weight: f64) -> ClientProfile {
+        ClientProfile {
+            name: name.to_string(),
+            weight,
+            headers: HeaderLayer::new([("User-Agent", name)]),
+            think_time_multiplier: 1.0,
+        }
+    }
+
+    #[test]
+    fn picks_by_cumulative_weight() {
+        let profiles = ClientProfiles::new(vec![
+            profile("mobile", 60.0),
+            profile("never", 0.0),
+            profile("desktop", 40.0),
+        ]);
+        assert_eq!(profiles.profiles().len(), 2);
+        assert_eq!(profiles.pick(0.0).unwrap().name, "mobile");
+        assert_eq!(profiles.pick(59.9).unwrap().name, "mobile");
+        assert_eq!(profiles.pick(60.0).unwrap().name, "desktop");
+        assert_eq!(profiles.pick(99.9).unwrap().name, "desktop");
+    }
+
+    #[test]
+    fn empty_profiles_draw_nothing() {
+        let profiles = ClientProfiles::new(vec![profile("off", 0.0)]);
+        assert!(profiles.is_empty());
+        assert_eq!(profiles.sample(), None);
+        assert_eq!(ClientProfiles::default().sample(), None);
+    }
+}
@@ -0,0 +1,171 @@
+//! Per-scenario and per-step concurrency limits (`maxConcurrent`).
+//!
+//! A scenario or step with `maxConcurrent: N` may run in at most N workers at
+//! once, however large the worker pool is. Workers over the limit wait for a
+//! free slot, so a fragile dependency behind one step (e.g. checkout) sees a
+//! bounded number of in-flight calls while the rest of the test runs at full
+//! load.
+//!
+//! Limits are process-wide semaphores keyed by scenario and step name.
+//! Saturation is exposed as `concurrency_limit`, `concurrency_in_use` and
+//! `concurrency_waiting`: `in_use / limit` near 1 with a non-zero `waiting`
+//! means the limit is what paces that flow.
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+use prometheus::Gauge;
+use tokio::sync::{OwnedSemaphorePermit, Semaphore};
+
+use crate::metrics::{CONCURRENCY_IN_USE, CONCURRENCY_LIMIT, CONCURRENCY_WAITING};
+
+/// (scenario, step) a limit applies to; `None` for a whole scenario.
+type Key = (String, Option<String>);
+
+lazy_static::lazy_static! {
+    static ref LIMITS: Mutex<HashMap<Key, (usize, Arc<Semaphore>)>> =
+        Mutex::new(HashMap::new());
+}
+
+/// Semaphore for `key`, replaced if the configured limit changed.
+fn semaphore(key: Key, limit: usize) -> Arc<Semaphore> {
+    let mut limits = LIMITS.lock().unwrap_or_else(|e| e.into_inner());
+    let entry = limits
+        .entry(key)
+        .or_insert_with(|| (limit, Arc::new(Semaphore::new(limit))));
+    if entry.0 != limit {
+        *entry = (limit, Arc::new(Semaphore::new(limit)));
+    }
+    entry.1.clone()
+}
+
+/// Drops all limits, e.g. before a new test plan starts.
+pub fn clear() {
+    LIMITS.lock().unwrap_or_else(|e| e.into_inner()).clear();
+}
+
+/// A held slot; released (and `concurrency_in_use` decremented) on drop.
+pub struct ConcurrencyPermit {
+    _permit: OwnedSemaphorePermit,
+    labels: [String; 4],
+}
+
+impl Drop for ConcurrencyPermit {
+    fn drop(&mut self) {
+        let labels: Vec<&str> = self.labels.iter().map(String::as_str).collect();
+        CONCURRENCY_IN_USE.with_label_values(&labels).dec();
+    }
+}
+
+/// Counts a worker in `concurrency_waiting` until dropped, so a wait that is
+/// cancelled (e.g. an aborted worker) doesn't leave it counted.
+struct Waiting(Gauge);
+
+impl Waiting {
+    fn start(gauge: Gauge) -> Self {
+        gauge.inc();
+        Self(gauge)
+    }
+}
+
+impl Drop for Waiting {
+    fn drop(&mut self) {
+        self.0.dec();
+    }
+}
+
+/// Waits for a slot under `limit` for `scenario` (and `step`, when given).
+pub async fn acquire(
+    scenario: &str,
+    step: Option<&str>,
+    limit: usize,
+    node_id: &str,
+    run_id: &str,
+) -> ConcurrencyPermit {
+    let key = (scenario.to_string(), step.map(str::to_string));
+    let labels = [
+        scenario.to_string(),
+        step.unwrap_or_default().to_string(),
+        node_id.to_string(),
+        run_id.to_string(),
+    ];
+    let label_refs: Vec<&str> = labels.iter().map(String::as_str).collect();
+    CONCURRENCY_LIMIT
+        .with_label_values(&label_refs)
+        .set(limit as f64);
+
+    let waiting = Waiting::start(CONCURRENCY_WAITING.with_label_values(&label_refs));
+    let permit = semaphore(key, limit)
+        .acquire_owned()
+        .await
+        .expect("concurrency semaphores are never closed");
+    drop(waiting);
+    CONCURRENCY_IN_USE.with_label_values(&label_refs).inc();
+
+    ConcurrencyPermit {
+        _permit: permit,
+        labels,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    #[tokio::test]
+    async fn limits_concurrent_holders() {
+        let first = acquire("limit-test", Some("pay"), 1, "node", "run").await;
+        let second = tokio::time::timeout(
+            Duration::from_millis(50),
+            acquire("limit-test", Some("pay"), 1, "node", "run"),
+        )
+        .await;
+        assert!(second.is_err(), "second holder should wait for the slot");
+
+        // Other steps of the same scenario have their own slots.
+        let _other = acquire("limit-test", Some("browse"), 1, "node", "run").await;
+
+        drop(first);
+        let second = tokio::time::timeout(
+            Duration::from_millis(50),
+            acquire("limit-test", Some("pay"), 1, "node", "run"),
+        )
+        .await;
+        assert!(second.is_ok());
+    }
+
+    #[tokio::test]
+    async fn cancelled_wait_is_not_left_waiting() {
+        let waiting = CONCURRENCY_WAITING.with_label_values(&["cancel-test", "", "node", "run"]);
+        let _held = acquire("cancel-test", None, 1, "node", "run").await;
+        let second = tokio::time::timeout(
+            Duration::from_millis(50),
+            acquire("cancel-test", None, 1, "node", "run"),
+        )
+        .await;
+        assert!(second.is_err());
+        assert_eq!(waiting.get(), 0.0);
+    }
+
+    #[tokio::test]
+    async fn slash_in_names_does_not_share_slots() {
+        let _step = acquire("slash-test", Some("a/b"), 1, "node", "run").await;
+        let other = tokio::time::timeout(
+            Duration::from_millis(50),
+            acquire("slash-test/a", Some("b"), 1, "node", "run"),
+        )
+        .await;
+        assert!(other.is_ok());
+    }
+
+    #[test]
+    fn changed_limit_replaces_semaphore() {
+        let key = || ("resize-test".to_string(), None);
+        let a = semaphore(key(), 2);
+        let b = semaphore(key(), 2);
+        assert!(Arc::ptr_eq(&a, &b));
+        let c = semaphore(key(), 5);
+        assert_eq!(c.available_permits(), 5);
+    }
+}
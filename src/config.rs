@@ -1,12 +1,30 @@
 use std::env;
+use std::net::IpAddr;
+use std::path::PathBuf;
+use std::time::SystemTime;
 use thiserror::Error;
 use tokio::time::Duration;
 use tracing::{info, warn};
 
+use crate::abort_threshold::AbortThreshold;
+use crate::chaos::{ChaosConfig, DEFAULT_OVERSIZED_BODY_BYTES};
 use crate::client::ClientConfig;
 use crate::config_merge::ConfigMerger;
-use crate::load_models::LoadModel;
-use crate::utils::parse_duration_string;
+use crate::in_flight_limit::BackpressurePolicy;
+use crate::ip_family::IpFamily;
+use crate::load_models::{self, LoadModel};
+use crate::rate_expression::RateExpression;
+use crate::redaction::GLOBAL_REDACTOR;
+use crate::response_body::DEFAULT_MAX_BODY_BUFFER;
+use crate::safety::{SafetyPolicy, CONFIRM_VAR};
+use crate::service_discovery;
+use crate::smoke::RunMode;
+use crate::sni::{self, SniOverride};
+use crate::start_barrier;
+use crate::tls::DEFAULT_CLIENT_CERT_RELOAD_INTERVAL;
+use crate::traffic_calendar::TrafficCalendar;
+use crate::utils::{parse_body_size, parse_duration_string};
+use crate::worker::WorkerPacing;
 use crate::yaml_config::{YamlConfig, YamlConfigError};
 
 /// Configuration errors with descriptive messages.
@@ -45,8 +63,12 @@ pub struct ClusterConfig {
     pub node_id: String,
 
     /// Geographic region tag attached to all emitted metrics.
-    /// Defaults to `"local"`.
+    /// Defaults to `CLUSTER_REGION`, then `REGION`, then `"local"`.
     pub region: String,
+
+    /// Availability zone within the region, attached alongside `region`.
+    /// Defaults to `CLUSTER_ZONE`, then `ZONE`, then `"local"`.
+    pub zone: String,
 }
 
 impl ClusterConfig {
@@ -55,8 +77,17 @@ impl ClusterConfig {
         let node_id = std::env::var("CLUSTER_NODE_ID").unwrap_or_else(|_| {
             std::env::var("HOSTNAME").unwrap_or_else(|_| "unknown-node".to_string())
         });
-        let region = std::env::var("CLUSTER_REGION").unwrap_or_else(|_| "local".to_string());
-        Self { node_id, region }
+        let label = |names: [&str; 2]| {
+            names
+                .iter()
+                .find_map(|name| std::env::var(name).ok().filter(|v| !v.is_empty()))
+                .unwrap_or_else(|| "local".to_string())
+        };
+        Self {
+            node_id,
+            region: label(["CLUSTER_REGION", "REGION"]),
+            zone: label(["CLUSTER_ZONE", "ZONE"]),
+        }
     }
 
     /// Create a cluster config for testing purposes.
@@ -65,6 +96,7 @@ impl ClusterConfig {
         Self {
             node_id: "test-node".to_string(),
             region: "local".to_string(),
+            zone: "local".to_string(),
         }
     }
 }
@@ -90,10 +122,101 @@ pub struct Config {
     pub percentile_sampling_rate: u8, // 1-100: percentage of requests to record (Issue #70)
     pub max_histogram_labels: usize,
     pub histogram_rotation_interval: Duration, // 0 = disabled
+    pub hdr_log_path: Option<PathBuf>, // HdrHistogram interval log, written at each rotation
     pub memory_warning_threshold_percent: f64,
     pub memory_critical_threshold_percent: f64,
     pub auto_disable_percentiles_on_warning: bool,
 
+    // Debug sampling: fraction of requests (0.0-1.0) whose full request and
+    // response are logged, capped at `debug_sample_max_per_sec`.
+    pub debug_sample_rate: f64,
+    pub debug_sample_max_per_sec: u32,
+
+    // Worker start-up ramp and pacing jitter: workers start spread over
+    // `worker_ramp`, and start offsets and pacing intervals vary randomly by
+    // up to ±`worker_jitter_percent` of a cycle.
+    pub worker_ramp: Duration,
+    pub worker_jitter_percent: f64,
+
+    // Source addresses for outgoing connections (LOCAL_ADDRS), assigned to
+    // workers round-robin. Empty = let the OS choose.
+    pub local_addrs: Vec<IpAddr>,
+
+    // Client-side fault injection (CHAOS_*). Inactive unless CHAOS_ENABLED=true.
+    pub chaos: ChaosConfig,
+
+    // Run identifier (RUN_ID, else YAML `metadata.run_id`); generated at
+    // startup when unset. RUN_ID_HEADER=true sends it as `X-Load-Test-Run`.
+    pub run_id: Option<String>,
+    pub run_id_header: bool,
+
+    // Target guardrails (TARGET_ALLOWLIST, TARGET_DENYLIST, PROTECTED_TARGETS).
+    pub safety: SafetyPolicy,
+
+    // Hard caps overriding the load model; 0 = no cap.
+    pub max_total_requests: u64,
+    pub hard_max_rps: f64,
+
+    // Cap on requests in flight across all workers (MAX_IN_FLIGHT); 0 = no cap.
+    pub max_in_flight: usize,
+    // What workers do while that cap is saturated (BACKPRESSURE).
+    pub backpressure: BackpressurePolicy,
+
+    // Wall-clock instant all nodes start generating load at (START_AT, else
+    // YAML `config.startAt`); None starts as soon as the node is ready.
+    pub start_at: Option<SystemTime>,
+
+    // Resolve the target host once and spread workers across every address
+    // it returns (DNS_ROUND_ROBIN), with per-IP request metrics.
+    pub dns_round_robin: bool,
+
+    // Write missing `matchesGolden` files from the first response
+    // (GOLDEN_RECORD) instead of failing the assertion.
+    pub golden_record: bool,
+
+    // How often a `consul://` / `k8s://` target is re-resolved
+    // (DISCOVERY_REFRESH); zero resolves it once at start.
+    pub discovery_refresh: Duration,
+
+    // How often resolved hostnames are looked up again, retiring pooled
+    // connections when the addresses change (DNS_REFRESH_INTERVAL); zero
+    // leaves resolution to the connection pool.
+    pub dns_refresh_interval: Duration,
+
+    // Address family for outgoing connections (IP_FAMILY): auto, v4 or v6.
+    pub ip_family: IpFamily,
+
+    // Run mode (MODE): load (default) or smoke, which runs each scenario once.
+    pub mode: RunMode,
+
+    // Directory of weighted request templates (TEMPLATE_DIR); when set,
+    // single-URL workers draw each request from the mix instead of TARGET_URL.
+    pub template_dir: Option<PathBuf>,
+
+    // Cache TLS sessions for resumption (TLS_SESSION_RESUMPTION); disabling
+    // forces a full handshake on every new connection.
+    pub tls_session_resumption: bool,
+
+    // How often the mTLS certificate files are checked for renewal
+    // (CLIENT_CERT_RELOAD_INTERVAL); zero disables reloading.
+    pub client_cert_reload_interval: Duration,
+
+    // TLS server name presented instead of the target URL's host
+    // (TLS_SERVER_NAME); the URL's host becomes the connect address.
+    pub sni_override: Option<SniOverride>,
+
+    // Connections opened with one request each before the test clock starts
+    // (WARMUP_CONNECTIONS); 0 skips the warm-up.
+    pub warmup_connections: usize,
+
+    // Check DNS, TCP, TLS and one HEAD request against the target before
+    // any load is sent (TARGET_PRECHECK), failing fast when it's unreachable.
+    pub target_precheck: bool,
+
+    // Most response body bytes kept in memory per request (MAX_BODY_BUFFER);
+    // bodies nothing reads are streamed and discarded regardless.
+    pub max_body_buffer: usize,
+
     // Cluster configuration (Issue #45)
     pub cluster: ClusterConfig,
 
@@ -123,7 +246,168 @@ where
     }
 }
 
+/// Helper to parse a duration environment variable (`30s`, `5m`, ...) with a default value.
+fn env_duration_or(name: &str, default: Duration) -> Result<Duration, ConfigError> {
+    match env::var(name) {
+        Ok(val) => parse_duration_string(&val).map_err(|e| ConfigError::InvalidDuration {
+            var: name.into(),
+            message: e,
+        }),
+        Err(_) => Ok(default),
+    }
+}
+
+/// Helper to parse a byte size environment variable (`512KB`, `10MB`, ...) with a default value.
+fn env_size_or(name: &str, default: usize) -> Result<usize, ConfigError> {
+    match env::var(name) {
+        Ok(val) => parse_body_size(&val).map_err(|message| ConfigError::InvalidValue {
+            var: name.into(),
+            message,
+        }),
+        Err(_) => Ok(default),
+    }
+}
+
+/// Helper to parse `ALIGN_TO_CLOCK` and `CYCLE_START` (UTC `HH:MM[:SS]`,
+/// default midnight) into the time of day Ramp/DailyTraffic cycles start at.
+fn env_clock_start() -> Result<Option<Duration>, ConfigError> {
+    if !env_bool("ALIGN_TO_CLOCK", false) {
+        return Ok(None);
+    }
+    match env::var("CYCLE_START") {
+        Ok(value) => load_models::parse_time_of_day(&value)
+            .map(Some)
+            .map_err(|message| ConfigError::InvalidValue {
+                var: "CYCLE_START".into(),
+                message,
+            }),
+        Err(_) => Ok(Some(Duration::ZERO)),
+    }
+}
+
+/// Helper to load the DailyTraffic calendar named by `DAILY_CALENDAR_FILE`.
+fn env_calendar() -> Result<Option<TrafficCalendar>, ConfigError> {
+    match env::var("DAILY_CALENDAR_FILE") {
+        Ok(path) if !path.trim().is_empty() => TrafficCalendar::from_file(path.trim())
+            .map(Some)
+            .map_err(|e| ConfigError::InvalidValue {
+                var: "DAILY_CALENDAR_FILE".into(),
+                message: e.to_string(),
+            }),
+        _ => Ok(None),
+    }
+}
+
+/// Helper to parse the Exponential model's `ABORT_ERROR_RATE` (percent) and
+/// `ABORT_LATENCY_MS`, each falling back to `default`.
+fn env_abort_threshold(default: AbortThreshold) -> Result<AbortThreshold, ConfigError> {
+    let error_rate_percent = match env::var("ABORT_ERROR_RATE") {
+        Ok(_) => Some(env_parse_or::<f64>("ABORT_ERROR_RATE", 0.0)?),
+        Err(_) => default.error_rate_percent,
+    };
+    let latency = match env::var("ABORT_LATENCY_MS") {
+        Ok(_) => Some(Duration::from_millis(env_parse_or("ABORT_LATENCY_MS", 0)?)),
+        Err(_) => default.latency,
+    };
+    Ok(AbortThreshold {
+        error_rate_percent,
+        latency,
+    })
+}
+
+/// Helper to parse `START_AT`, falling back to the YAML `config.startAt`.
+fn env_start_at(yaml: Option<&str>) -> Result<Option<SystemTime>, ConfigError> {
+    let value = env::var("START_AT")
+        .ok()
+        .or_else(|| yaml.map(str::to_string));
+    match value.filter(|v| !v.trim().is_empty()) {
+        Some(value) => start_barrier::parse_start_at(&value)
+            .map(Some)
+            .map_err(|message| ConfigError::InvalidValue {
+                var: "START_AT".into(),
+                message,
+            }),
+        None => Ok(None),
+    }
+}
+
+/// Applies `TLS_SERVER_NAME` to the target URL (see [`crate::sni`]).
+fn apply_tls_server_name(
+    target_url: String,
+    server_name: Option<String>,
+) -> Result<(String, Option<SniOverride>), ConfigError> {
+    match server_name.filter(|name| !name.trim().is_empty()) {
+        Some(name) => {
+            let (url, sni) =
+                sni::apply(&target_url, &name).map_err(|message| ConfigError::InvalidValue {
+                    var: "TLS_SERVER_NAME".into(),
+                    message,
+                })?;
+            Ok((url, Some(sni)))
+        }
+        None => Ok((target_url, None)),
+    }
+}
+
+/// Helper to parse `LOCAL_ADDRS`: a comma-separated list of IP addresses.
+fn env_local_addrs() -> Result<Vec<IpAddr>, ConfigError> {
+    let Ok(raw) = env::var("LOCAL_ADDRS") else {
+        return Ok(Vec::new());
+    };
+    raw.split(',')
+        .map(str::trim)
+        .filter(|s| !s.is_empty())
+        .map(|s| {
+            s.parse::<IpAddr>().map_err(|e| ConfigError::InvalidValue {
+                var: "LOCAL_ADDRS".into(),
+                message: format!("'{}': {}", s, e),
+            })
+        })
+        .collect()
+}
+
+/// Helper to parse the `CHAOS_*` fault injection variables.
+fn env_chaos() -> Result<ChaosConfig, ConfigError> {
+    Ok(ChaosConfig {
+        enabled: env_bool("CHAOS_ENABLED", false),
+        drop_percent: env_parse_or("CHAOS_DROP_PERCENT", 0.0)?,
+        corrupt_headers_percent: env_parse_or("CHAOS_CORRUPT_HEADERS_PERCENT", 0.0)?,
+        oversized_body_percent: env_parse_or("CHAOS_OVERSIZED_BODY_PERCENT", 0.0)?,
+        oversized_body_bytes: env_parse_or(
+            "CHAOS_OVERSIZED_BODY_BYTES",
+            DEFAULT_OVERSIZED_BODY_BYTES,
+        )?,
+        abort_response_percent: env_parse_or("CHAOS_ABORT_RESPONSE_PERCENT", 0.0)?,
+    })
+}
+
 /// Helper to parse a boolean environment variable.
+/// Helper to parse the target allow/deny/protected lists.
+fn env_safety() -> Result<SafetyPolicy, ConfigError> {
+    let list = |var: &str| {
+        SafetyPolicy::parse_list(&env::var(var).unwrap_or_default()).map_err(|e| {
+            ConfigError::InvalidValue {
+                var: var.into(),
+                message: e.to_string(),
+            }
+        })
+    };
+    Ok(SafetyPolicy {
+        allow: list("TARGET_ALLOWLIST")?,
+        deny: list("TARGET_DENYLIST")?,
+        protected: list("PROTECTED_TARGETS")?,
+        confirmed: env_bool(CONFIRM_VAR, false),
+    })
+}
+
+/// Reads `RUN_ID`, ignoring blank values.
+fn env_run_id() -> Option<String> {
+    env::var("RUN_ID")
+        .ok()
+        .map(|id| id.trim().to_string())
+        .filter(|id| !id.is_empty())
+}
+
 fn env_bool(name: &str, default: bool) -> bool {
     env::var(name)
         .unwrap_or_else(|_| default.to_string())
@@ -209,6 +493,24 @@ impl Config {
         let resolve_target_addr = env::var("RESOLVE_TARGET_ADDR")
             .ok()
             .or_else(|| yaml_config.config.resolve_target_addr.clone());
+        let template_dir = env::var("TEMPLATE_DIR")
+            .ok()
+            .or_else(|| yaml_config.config.template_dir.clone())
+            .map(PathBuf::from);
+        let (target_url, sni_override) = apply_tls_server_name(
+            target_url,
+            env::var("TLS_SERVER_NAME")
+                .ok()
+                .or_else(|| yaml_config.config.tls_server_name.clone()),
+        )?;
+        let tls_session_resumption = env_bool("TLS_SESSION_RESUMPTION", true);
+        let client_cert_reload_interval = env_duration_or(
+            "CLIENT_CERT_RELOAD_INTERVAL",
+            DEFAULT_CLIENT_CERT_RELOAD_INTERVAL,
+        )?;
+        let max_body_buffer = env_size_or("MAX_BODY_BUFFER", DEFAULT_MAX_BODY_BUFFER)?;
+        let warmup_connections: usize = env_parse_or("WARMUP_CONNECTIONS", 0)?;
+        let target_precheck = env_bool("TARGET_PRECHECK", true);
         let client_cert_path = env::var("CLIENT_CERT_PATH").ok();
         let client_key_path = env::var("CLIENT_KEY_PATH").ok();
 
@@ -217,6 +519,28 @@ impl Config {
         let percentile_sampling_rate: u8 = env_parse_or("PERCENTILE_SAMPLING_RATE", 100u8)?;
         let max_histogram_labels: usize = env_parse_or("MAX_HISTOGRAM_LABELS", 100)?;
 
+        let debug_sample_rate: f64 = env_parse_or("DEBUG_SAMPLE_RATE", 0.0)?;
+        let debug_sample_max_per_sec: u32 = env_parse_or("DEBUG_SAMPLE_MAX_PER_SEC", 1)?;
+        let worker_ramp = env_duration_or("WORKER_RAMP", Duration::ZERO)?;
+        let worker_jitter_percent: f64 = env_parse_or("WORKER_JITTER_PERCENT", 10.0)?;
+        let local_addrs = env_local_addrs()?;
+        let chaos = env_chaos()?;
+        let safety = env_safety()?;
+        let max_total_requests: u64 = env_parse_or("MAX_TOTAL_REQUESTS", 0)?;
+        let hard_max_rps: f64 = env_parse_or("HARD_MAX_RPS", 0.0)?;
+        let max_in_flight: usize = env_parse_or("MAX_IN_FLIGHT", 0)?;
+        let backpressure: BackpressurePolicy =
+            env_parse_or("BACKPRESSURE", BackpressurePolicy::Queue)?;
+        let start_at = env_start_at(yaml_config.config.start_at.as_deref())?;
+        let dns_round_robin = env_bool("DNS_ROUND_ROBIN", false);
+        let discovery_refresh = env_duration_or("DISCOVERY_REFRESH", Duration::ZERO)?;
+        let dns_refresh_interval = env_duration_or("DNS_REFRESH_INTERVAL", Duration::ZERO)?;
+        let golden_record = env_bool("GOLDEN_RECORD", false);
+        let ip_family: IpFamily = env_parse_or("IP_FAMILY", IpFamily::Auto)?;
+        let mode: RunMode = env_parse_or("MODE", RunMode::Load)?;
+        let run_id = env_run_id().or_else(|| yaml_config.metadata.run_id.clone());
+        let run_id_header = env_bool("RUN_ID_HEADER", false);
+
         // Histogram rotation interval (0 = disabled)
         let histogram_rotation_interval =
             if let Ok(interval_str) = env::var("HISTOGRAM_ROTATION_INTERVAL") {
@@ -227,6 +551,7 @@ impl Config {
             } else {
                 Duration::from_secs(0) // Disabled by default
             };
+        let hdr_log_path = env::var("HDR_LOG_PATH").ok().map(PathBuf::from);
 
         // Auto-OOM protection settings (Issue #72)
         let memory_warning_threshold_percent: f64 =
@@ -263,9 +588,37 @@ impl Config {
             percentile_sampling_rate,
             max_histogram_labels,
             histogram_rotation_interval,
+            hdr_log_path,
             memory_warning_threshold_percent,
             memory_critical_threshold_percent,
             auto_disable_percentiles_on_warning,
+            debug_sample_rate,
+            debug_sample_max_per_sec,
+            worker_ramp,
+            worker_jitter_percent,
+            local_addrs,
+            chaos,
+            run_id,
+            run_id_header,
+            safety,
+            max_total_requests,
+            hard_max_rps,
+            max_in_flight,
+            backpressure,
+            start_at,
+            dns_round_robin,
+            discovery_refresh,
+            dns_refresh_interval,
+            golden_record,
+            ip_family,
+            mode,
+            template_dir,
+            tls_session_resumption,
+            client_cert_reload_interval,
+            sni_override,
+            max_body_buffer,
+            warmup_connections,
+            target_precheck,
             cluster: ClusterConfig::from_env(),
             pool_max_idle_per_host,
             pool_idle_timeout_secs,
@@ -316,11 +669,51 @@ impl Config {
         let resolve_target_addr = env::var("RESOLVE_TARGET_ADDR")
             .ok()
             .or_else(|| yaml_config.config.resolve_target_addr.clone());
+        let template_dir = env::var("TEMPLATE_DIR")
+            .ok()
+            .or_else(|| yaml_config.config.template_dir.clone())
+            .map(PathBuf::from);
+        let (target_url, sni_override) = apply_tls_server_name(
+            target_url,
+            env::var("TLS_SERVER_NAME")
+                .ok()
+                .or_else(|| yaml_config.config.tls_server_name.clone()),
+        )?;
+        let tls_session_resumption = env_bool("TLS_SESSION_RESUMPTION", true);
+        let client_cert_reload_interval = env_duration_or(
+            "CLIENT_CERT_RELOAD_INTERVAL",
+            DEFAULT_CLIENT_CERT_RELOAD_INTERVAL,
+        )?;
+        let max_body_buffer = env_size_or("MAX_BODY_BUFFER", DEFAULT_MAX_BODY_BUFFER)?;
+        let warmup_connections: usize = env_parse_or("WARMUP_CONNECTIONS", 0)?;
+        let target_precheck = env_bool("TARGET_PRECHECK", true);
         let client_cert_path = env::var("CLIENT_CERT_PATH").ok();
         let client_key_path = env::var("CLIENT_KEY_PATH").ok();
         let percentile_tracking_enabled = env_bool("PERCENTILE_TRACKING_ENABLED", true);
         let percentile_sampling_rate: u8 = env_parse_or("PERCENTILE_SAMPLING_RATE", 100u8)?;
         let max_histogram_labels: usize = env_parse_or("MAX_HISTOGRAM_LABELS", 100)?;
+
+        let debug_sample_rate: f64 = env_parse_or("DEBUG_SAMPLE_RATE", 0.0)?;
+        let debug_sample_max_per_sec: u32 = env_parse_or("DEBUG_SAMPLE_MAX_PER_SEC", 1)?;
+        let worker_ramp = env_duration_or("WORKER_RAMP", Duration::ZERO)?;
+        let worker_jitter_percent: f64 = env_parse_or("WORKER_JITTER_PERCENT", 10.0)?;
+        let local_addrs = env_local_addrs()?;
+        let chaos = env_chaos()?;
+        let safety = env_safety()?;
+        let max_total_requests: u64 = env_parse_or("MAX_TOTAL_REQUESTS", 0)?;
+        let hard_max_rps: f64 = env_parse_or("HARD_MAX_RPS", 0.0)?;
+        let max_in_flight: usize = env_parse_or("MAX_IN_FLIGHT", 0)?;
+        let backpressure: BackpressurePolicy =
+            env_parse_or("BACKPRESSURE", BackpressurePolicy::Queue)?;
+        let start_at = env_start_at(yaml_config.config.start_at.as_deref())?;
+        let dns_round_robin = env_bool("DNS_ROUND_ROBIN", false);
+        let discovery_refresh = env_duration_or("DISCOVERY_REFRESH", Duration::ZERO)?;
+        let dns_refresh_interval = env_duration_or("DNS_REFRESH_INTERVAL", Duration::ZERO)?;
+        let golden_record = env_bool("GOLDEN_RECORD", false);
+        let ip_family: IpFamily = env_parse_or("IP_FAMILY", IpFamily::Auto)?;
+        let mode: RunMode = env_parse_or("MODE", RunMode::Load)?;
+        let run_id = env_run_id().or_else(|| yaml_config.metadata.run_id.clone());
+        let run_id_header = env_bool("RUN_ID_HEADER", false);
         let histogram_rotation_interval =
             if let Ok(interval_str) = env::var("HISTOGRAM_ROTATION_INTERVAL") {
                 parse_duration_string(&interval_str).map_err(|e| ConfigError::InvalidDuration {
@@ -330,6 +723,7 @@ impl Config {
             } else {
                 Duration::from_secs(0)
             };
+        let hdr_log_path = env::var("HDR_LOG_PATH").ok().map(PathBuf::from);
         let memory_warning_threshold_percent: f64 =
             env_parse_or("MEMORY_WARNING_THRESHOLD_PERCENT", 80.0)?;
         let memory_critical_threshold_percent: f64 =
@@ -364,9 +758,37 @@ impl Config {
             percentile_sampling_rate,
             max_histogram_labels,
             histogram_rotation_interval,
+            hdr_log_path,
             memory_warning_threshold_percent,
             memory_critical_threshold_percent,
             auto_disable_percentiles_on_warning,
+            debug_sample_rate,
+            debug_sample_max_per_sec,
+            worker_ramp,
+            worker_jitter_percent,
+            local_addrs,
+            chaos,
+            run_id,
+            run_id_header,
+            safety,
+            max_total_requests,
+            hard_max_rps,
+            max_in_flight,
+            backpressure,
+            start_at,
+            dns_round_robin,
+            discovery_refresh,
+            dns_refresh_interval,
+            golden_record,
+            ip_family,
+            mode,
+            template_dir,
+            tls_session_resumption,
+            client_cert_reload_interval,
+            sni_override,
+            max_body_buffer,
+            warmup_connections,
+            target_precheck,
             cluster: ClusterConfig::from_env(),
             pool_max_idle_per_host,
             pool_idle_timeout_secs,
@@ -403,8 +825,9 @@ impl Config {
                 min_rps,
                 max_rps,
                 ramp_duration,
+                clock_start,
             } => {
-                // MIN_RPS, MAX_RPS, RAMP_DURATION can override YAML values
+                // MIN_RPS, MAX_RPS, RAMP_DURATION, ALIGN_TO_CLOCK can override YAML values
                 let final_min =
                     ConfigMerger::merge_rps(Some(min_rps), "MIN_RPS").unwrap_or(min_rps);
                 let final_max =
@@ -415,6 +838,7 @@ impl Config {
                     min_rps: final_min,
                     max_rps: final_max,
                     ramp_duration: final_duration,
+                    clock_start: env_clock_start()?.or(clock_start),
                 })
             }
             LoadModel::DailyTraffic {
@@ -427,8 +851,11 @@ impl Config {
                 mid_decline_ratio,
                 mid_sustain_ratio,
                 evening_decline_ratio,
+                clock_start,
+                calendar,
             } => {
-                // DAILY_MIN_RPS, DAILY_MID_RPS, DAILY_MAX_RPS can override YAML
+                // DAILY_MIN_RPS, DAILY_MID_RPS, DAILY_MAX_RPS, ALIGN_TO_CLOCK,
+                // DAILY_CALENDAR_FILE can override YAML
                 let final_min =
                     ConfigMerger::merge_rps(Some(min_rps), "DAILY_MIN_RPS").unwrap_or(min_rps);
                 let final_mid =
@@ -447,9 +874,32 @@ impl Config {
                     mid_decline_ratio,
                     mid_sustain_ratio,
                     evening_decline_ratio,
+                    clock_start: env_clock_start()?.or(clock_start),
+                    calendar: env_calendar()?.or(calendar),
+                })
+            }
+            LoadModel::Exponential {
+                start_rps,
+                doubling_period,
+                max_rps,
+                abort,
+            } => {
+                // START_RPS, DOUBLING_PERIOD, MAX_RPS and the abort thresholds
+                // can override YAML values
+                Ok(LoadModel::Exponential {
+                    start_rps: ConfigMerger::merge_rps(Some(start_rps), "START_RPS")
+                        .unwrap_or(start_rps),
+                    doubling_period: ConfigMerger::merge_timeout(
+                        Some(doubling_period),
+                        "DOUBLING_PERIOD",
+                    ),
+                    max_rps: ConfigMerger::merge_rps(max_rps, "MAX_RPS"),
+                    abort: env_abort_threshold(abort)?,
                 })
             }
             LoadModel::Concurrent => Ok(LoadModel::Concurrent),
+            // Component parameters and expressions have no env overrides.
+            model @ (LoadModel::Composite { .. } | LoadModel::Expression { .. }) => Ok(model),
         }
     }
 
@@ -487,6 +937,17 @@ impl Config {
         let skip_tls_verify = env_bool("SKIP_TLS_VERIFY", false);
 
         let resolve_target_addr = env::var("RESOLVE_TARGET_ADDR").ok();
+        let template_dir = env::var("TEMPLATE_DIR").ok().map(PathBuf::from);
+        let (target_url, sni_override) =
+            apply_tls_server_name(target_url, env::var("TLS_SERVER_NAME").ok())?;
+        let tls_session_resumption = env_bool("TLS_SESSION_RESUMPTION", true);
+        let client_cert_reload_interval = env_duration_or(
+            "CLIENT_CERT_RELOAD_INTERVAL",
+            DEFAULT_CLIENT_CERT_RELOAD_INTERVAL,
+        )?;
+        let max_body_buffer = env_size_or("MAX_BODY_BUFFER", DEFAULT_MAX_BODY_BUFFER)?;
+        let warmup_connections: usize = env_parse_or("WARMUP_CONNECTIONS", 0)?;
+        let target_precheck = env_bool("TARGET_PRECHECK", true);
         let client_cert_path = env::var("CLIENT_CERT_PATH").ok();
         let client_key_path = env::var("CLIENT_KEY_PATH").ok();
         let custom_headers = env::var("CUSTOM_HEADERS").ok();
@@ -496,6 +957,28 @@ impl Config {
         let percentile_sampling_rate: u8 = env_parse_or("PERCENTILE_SAMPLING_RATE", 100u8)?;
         let max_histogram_labels: usize = env_parse_or("MAX_HISTOGRAM_LABELS", 100)?;
 
+        let debug_sample_rate: f64 = env_parse_or("DEBUG_SAMPLE_RATE", 0.0)?;
+        let debug_sample_max_per_sec: u32 = env_parse_or("DEBUG_SAMPLE_MAX_PER_SEC", 1)?;
+        let worker_ramp = env_duration_or("WORKER_RAMP", Duration::ZERO)?;
+        let worker_jitter_percent: f64 = env_parse_or("WORKER_JITTER_PERCENT", 10.0)?;
+        let local_addrs = env_local_addrs()?;
+        let chaos = env_chaos()?;
+        let safety = env_safety()?;
+        let max_total_requests: u64 = env_parse_or("MAX_TOTAL_REQUESTS", 0)?;
+        let hard_max_rps: f64 = env_parse_or("HARD_MAX_RPS", 0.0)?;
+        let max_in_flight: usize = env_parse_or("MAX_IN_FLIGHT", 0)?;
+        let backpressure: BackpressurePolicy =
+            env_parse_or("BACKPRESSURE", BackpressurePolicy::Queue)?;
+        let start_at = env_start_at(None)?;
+        let dns_round_robin = env_bool("DNS_ROUND_ROBIN", false);
+        let discovery_refresh = env_duration_or("DISCOVERY_REFRESH", Duration::ZERO)?;
+        let dns_refresh_interval = env_duration_or("DNS_REFRESH_INTERVAL", Duration::ZERO)?;
+        let golden_record = env_bool("GOLDEN_RECORD", false);
+        let ip_family: IpFamily = env_parse_or("IP_FAMILY", IpFamily::Auto)?;
+        let mode: RunMode = env_parse_or("MODE", RunMode::Load)?;
+        let run_id = env_run_id();
+        let run_id_header = env_bool("RUN_ID_HEADER", false);
+
         // Histogram rotation interval (0 = disabled)
         let histogram_rotation_interval =
             if let Ok(interval_str) = env::var("HISTOGRAM_ROTATION_INTERVAL") {
@@ -506,6 +989,7 @@ impl Config {
             } else {
                 Duration::from_secs(0) // Disabled by default
             };
+        let hdr_log_path = env::var("HDR_LOG_PATH").ok().map(PathBuf::from);
 
         // Auto-OOM protection settings (Issue #72)
         let memory_warning_threshold_percent: f64 =
@@ -532,9 +1016,37 @@ impl Config {
             percentile_sampling_rate,
             max_histogram_labels,
             histogram_rotation_interval,
+            hdr_log_path,
             memory_warning_threshold_percent,
             memory_critical_threshold_percent,
             auto_disable_percentiles_on_warning,
+            debug_sample_rate,
+            debug_sample_max_per_sec,
+            worker_ramp,
+            worker_jitter_percent,
+            local_addrs,
+            chaos,
+            run_id,
+            run_id_header,
+            safety,
+            max_total_requests,
+            hard_max_rps,
+            max_in_flight,
+            backpressure,
+            start_at,
+            dns_round_robin,
+            discovery_refresh,
+            dns_refresh_interval,
+            golden_record,
+            ip_family,
+            mode,
+            template_dir,
+            tls_session_resumption,
+            client_cert_reload_interval,
+            sni_override,
+            max_body_buffer,
+            warmup_connections,
+            target_precheck,
             cluster: ClusterConfig::from_env(),
             pool_max_idle_per_host: None,
             pool_idle_timeout_secs: None,
@@ -596,6 +1108,7 @@ impl Config {
                     min_rps,
                     max_rps,
                     ramp_duration,
+                    clock_start: env_clock_start()?,
                 })
             }
             "DailyTraffic" => {
@@ -670,12 +1183,62 @@ impl Config {
                     mid_decline_ratio,
                     mid_sustain_ratio,
                     evening_decline_ratio,
+                    clock_start: env_clock_start()?,
+                    calendar: env_calendar()?,
+                })
+            }
+            "Expression" => {
+                let source = env_required("RPS_EXPRESSION").map_err(|_| {
+                    ConfigError::MissingLoadModelParams {
+                        model: "Expression".into(),
+                        required: "RPS_EXPRESSION".into(),
+                    }
+                })?;
+                let expression =
+                    RateExpression::parse(&source).map_err(|e| ConfigError::InvalidValue {
+                        var: "RPS_EXPRESSION".into(),
+                        message: e.to_string(),
+                    })?;
+                Ok(LoadModel::Expression { expression })
+            }
+            "Exponential" => {
+                let start_rps: f64 = env_required("START_RPS")
+                    .map_err(|_| ConfigError::MissingLoadModelParams {
+                        model: "Exponential".into(),
+                        required: "START_RPS".into(),
+                    })?
+                    .parse()
+                    .map_err(|e: std::num::ParseFloatError| ConfigError::InvalidValue {
+                        var: "START_RPS".into(),
+                        message: e.to_string(),
+                    })?;
+                let doubling_period_str = env_required("DOUBLING_PERIOD").map_err(|_| {
+                    ConfigError::MissingLoadModelParams {
+                        model: "Exponential".into(),
+                        required: "DOUBLING_PERIOD".into(),
+                    }
+                })?;
+                let doubling_period = parse_duration_string(&doubling_period_str).map_err(|e| {
+                    ConfigError::InvalidDuration {
+                        var: "DOUBLING_PERIOD".into(),
+                        message: e,
+                    }
+                })?;
+                let max_rps = match env::var("MAX_RPS") {
+                    Ok(_) => Some(env_parse_or::<f64>("MAX_RPS", 0.0)?),
+                    Err(_) => None,
+                };
+                Ok(LoadModel::Exponential {
+                    start_rps,
+                    doubling_period,
+                    max_rps,
+                    abort: env_abort_threshold(AbortThreshold::default())?,
                 })
             }
             _ => Err(ConfigError::InvalidValue {
                 var: "LOAD_MODEL_TYPE".into(),
                 message: format!(
-                    "Unknown load model '{}'. Valid options: Concurrent, Rps, RampRps, DailyTraffic",
+                    "Unknown load model '{}'. Valid options: Concurrent, Rps, RampRps, DailyTraffic, Expression, Exponential",
                     model_type
                 ),
             }),
@@ -685,9 +1248,14 @@ impl Config {
     /// Validates the configuration for consistency and correctness.
     fn validate(&self) -> Result<(), ConfigError> {
         // Validate URL format
-        if !self.target_url.starts_with("http://") && !self.target_url.starts_with("https://") {
+        if service_discovery::is_discovery_url(&self.target_url) {
+            service_discovery::ServiceRef::parse(&self.target_url)
+                .map_err(|e| ConfigError::InvalidUrl(e.to_string()))?;
+        } else if !self.target_url.starts_with("http://")
+            && !self.target_url.starts_with("https://")
+        {
             return Err(ConfigError::InvalidUrl(
-                "TARGET_URL must start with http:// or https://".into(),
+                "TARGET_URL must start with http://, https://, consul:// or k8s://".into(),
             ));
         }
 
@@ -715,6 +1283,42 @@ impl Config {
             });
         }
 
+        if !(0.0..=1.0).contains(&self.debug_sample_rate) {
+            return Err(ConfigError::InvalidValue {
+                var: "DEBUG_SAMPLE_RATE".into(),
+                message: format!(
+                    "Must be between 0.0 and 1.0 (got {})",
+                    self.debug_sample_rate
+                ),
+            });
+        }
+
+        if !(0.0..=100.0).contains(&self.worker_jitter_percent) {
+            return Err(ConfigError::InvalidValue {
+                var: "WORKER_JITTER_PERCENT".into(),
+                message: format!(
+                    "Must be between 0 and 100 (got {})",
+                    self.worker_jitter_percent
+                ),
+            });
+        }
+
+        for (var, percent) in self.chaos.percentages() {
+            if !(0.0..=100.0).contains(&percent) {
+                return Err(ConfigError::InvalidValue {
+                    var: var.into(),
+                    message: format!("Must be between 0 and 100 (got {})", percent),
+                });
+            }
+        }
+
+        if !self.hard_max_rps.is_finite() || self.hard_max_rps < 0.0 {
+            return Err(ConfigError::InvalidValue {
+                var: "HARD_MAX_RPS".into(),
+                message: format!("Must be 0 (no cap) or positive (got {})", self.hard_max_rps),
+            });
+        }
+
         Ok(())
     }
 
@@ -738,9 +1342,37 @@ impl Config {
             percentile_sampling_rate: 100,
             max_histogram_labels: 100,
             histogram_rotation_interval: Duration::from_secs(0),
+            hdr_log_path: None,
             memory_warning_threshold_percent: 80.0,
             memory_critical_threshold_percent: 90.0,
             auto_disable_percentiles_on_warning: true,
+            debug_sample_rate: 0.0,
+            debug_sample_max_per_sec: 1,
+            worker_ramp: Duration::ZERO,
+            worker_jitter_percent: 0.0,
+            local_addrs: Vec::new(),
+            chaos: ChaosConfig::default(),
+            run_id: None,
+            run_id_header: false,
+            safety: SafetyPolicy::default(),
+            max_total_requests: 0,
+            hard_max_rps: 0.0,
+            max_in_flight: 0,
+            backpressure: BackpressurePolicy::Queue,
+            start_at: None,
+            dns_round_robin: false,
+            discovery_refresh: Duration::ZERO,
+            dns_refresh_interval: Duration::ZERO,
+            golden_record: false,
+            ip_family: IpFamily::Auto,
+            mode: RunMode::Load,
+            template_dir: None,
+            tls_session_resumption: true,
+            client_cert_reload_interval: DEFAULT_CLIENT_CERT_RELOAD_INTERVAL,
+            sni_override: None,
+            max_body_buffer: DEFAULT_MAX_BODY_BUFFER,
+            warmup_connections: 0,
+            target_precheck: true,
             cluster: ClusterConfig::for_testing(),
             pool_max_idle_per_host: None,
             pool_idle_timeout_secs: None,
@@ -748,6 +1380,23 @@ impl Config {
         }
     }
 
+    /// Start-up ramp and pacing jitter for this config's workers.
+    pub fn worker_pacing(&self) -> WorkerPacing {
+        WorkerPacing {
+            ramp: self.worker_ramp,
+            jitter_percent: self.worker_jitter_percent,
+        }
+    }
+
+    /// Source address for worker `task_id`: `LOCAL_ADDRS` round-robin.
+    pub fn local_addr_for_worker(&self, task_id: usize) -> Option<IpAddr> {
+        if self.local_addrs.is_empty() {
+            None
+        } else {
+            Some(self.local_addrs[task_id % self.local_addrs.len()])
+        }
+    }
+
     /// Creates a ClientConfig from this Config.
     pub fn to_client_config(&self) -> ClientConfig {
         let mut pool = crate::connection_pool::PoolConfig::from_env();
@@ -765,6 +1414,13 @@ impl Config {
             custom_headers: self.custom_headers.clone(),
             pool_config: Some(pool),
             cookie_store: false,
+            local_address: None,
+            pinned_target: None,
+            ip_family: self.ip_family,
+            tls_session_resumption: self.tls_session_resumption,
+            sni_override: self.sni_override.clone(),
+            proxy: None,
+            timeout: None,
         }
     }
 
@@ -784,12 +1440,14 @@ impl Config {
             custom_headers_count = custom_headers_count,
             percentile_tracking = self.percentile_tracking_enabled,
             region = %self.cluster.region,
+            zone = %self.cluster.zone,
             node_id = %self.cluster.node_id,
             "Starting load test"
         );
 
         info!(
             region = %self.cluster.region,
+            zone = %self.cluster.zone,
             node_id = %self.cluster.node_id,
             "Standalone node"
         );
@@ -829,18 +1487,41 @@ impl Config {
                     interval_str
                 );
             }
+
+            if let Some(path) = &self.hdr_log_path {
+                info!(
+                    hdr_log_path = %path.display(),
+                    "HDR interval log enabled - each histogram interval is appended to the log"
+                );
+            }
         }
 
         if !parsed_headers.is_empty() {
             for (name, value) in parsed_headers.iter() {
+                let value = value.to_str().unwrap_or("<non-ASCII or sensitive value>");
                 info!(
                     header_name = %name,
-                    header_value = %value.to_str().unwrap_or("<non-ASCII or sensitive value>"),
+                    header_value = %GLOBAL_REDACTOR.header(name.as_str(), value),
                     "Custom header configured"
                 );
             }
         }
 
+        if let (Some(cert), Some(key)) = (&self.client_cert_path, &self.client_key_path) {
+            info!(
+                client_cert = %GLOBAL_REDACTOR.path(cert),
+                client_key = %GLOBAL_REDACTOR.path(key),
+                "mTLS client certificate configured"
+            );
+        }
+
+        if let Some(payload) = &self.json_payload {
+            info!(
+                json_payload = %GLOBAL_REDACTOR.body(payload),
+                "JSON payload configured"
+            );
+        }
+
         // Auto-OOM protection status (Issue #72)
         if self.auto_disable_percentiles_on_warning {
             info!(
@@ -856,6 +1537,152 @@ impl Config {
                 "Auto-OOM protection monitoring only (Issue #72) - will log warnings but NOT take automatic actions"
             );
         }
+
+        if self.debug_sample_rate > 0.0 {
+            info!(
+                debug_sample_rate = self.debug_sample_rate,
+                debug_sample_max_per_sec = self.debug_sample_max_per_sec,
+                "Debug sampling ENABLED - full request/response details logged with target 'rust_loadtest::debug_sample'"
+            );
+        }
+
+        if !self.local_addrs.is_empty() {
+            info!(
+                local_addrs = ?self.local_addrs,
+                "Outgoing connections bound to local addresses (round-robin per worker)"
+            );
+        }
+
+        if !self.worker_ramp.is_zero() || self.worker_jitter_percent > 0.0 {
+            info!(
+                worker_ramp_secs = self.worker_ramp.as_secs_f64(),
+                worker_jitter_percent = self.worker_jitter_percent,
+                "Worker ramp-in and pacing jitter"
+            );
+        }
+
+        if let Some(run_id) = &self.run_id {
+            info!(
+                run_id = %run_id,
+                header = self.run_id_header,
+                "Run ID set"
+            );
+        }
+
+        if self.max_total_requests > 0 || self.hard_max_rps > 0.0 {
+            info!(
+                max_total_requests = self.max_total_requests,
+                hard_max_rps = self.hard_max_rps,
+                "Hard request caps active (0 = no cap)"
+            );
+        }
+
+        if self.max_in_flight > 0 {
+            info!(
+                max_in_flight = self.max_in_flight,
+                backpressure = %self.backpressure,
+                "In-flight request cap active"
+            );
+        }
+
+        if let Some(start_at) = self.start_at {
+            let unix_ms = start_at
+                .duration_since(std::time::UNIX_EPOCH)
+                .map_or(0, |d| d.as_millis());
+            info!(
+                start_at_unix_ms = unix_ms as u64,
+                "Load starts at the synchronized START_AT instant"
+            );
+        }
+
+        if self.ip_family != IpFamily::Auto {
+            info!(ip_family = %self.ip_family, "Outgoing connections restricted to one address family");
+        }
+
+        if self.dns_round_robin {
+            info!("DNS round-robin enabled: workers spread across all resolved target IPs");
+        }
+
+        if !self.dns_refresh_interval.is_zero() {
+            info!(
+                refresh_secs = self.dns_refresh_interval.as_secs_f64(),
+                "DNS re-resolution enabled: pooled connections retired when addresses change"
+            );
+        }
+
+        if service_discovery::is_discovery_url(&self.target_url) {
+            info!(
+                target_url = %self.target_url,
+                refresh_secs = self.discovery_refresh.as_secs_f64(),
+                "Target instances found through service discovery"
+            );
+        }
+
+        if self.golden_record {
+            info!("Golden files missing on disk are recorded from the first response");
+        }
+
+        if let Some(dir) = &self.template_dir {
+            info!(template_dir = %dir.display(), "Single-URL requests drawn from request templates");
+        }
+
+        if self.client_cert_path.is_some() && !self.client_cert_reload_interval.is_zero() {
+            info!(
+                interval_secs = self.client_cert_reload_interval.as_secs_f64(),
+                "mTLS client certificate reloaded when its files change"
+            );
+        }
+
+        if let Some(sni) = &self.sni_override {
+            info!(
+                server_name = %sni.server_name,
+                connect_host = %sni.connect_host,
+                "TLS server name override: connecting to one host while presenting another"
+            );
+        }
+
+        if self.max_body_buffer != DEFAULT_MAX_BODY_BUFFER {
+            info!(
+                max_body_buffer = self.max_body_buffer,
+                "Response bodies buffered up to MAX_BODY_BUFFER bytes"
+            );
+        }
+
+        if self.warmup_connections > 0 {
+            info!(
+                warmup_connections = self.warmup_connections,
+                "Connections warmed up before the test starts"
+            );
+        }
+
+        if !self.tls_session_resumption {
+            info!(
+                "TLS session resumption disabled: every new connection performs a full handshake"
+            );
+        }
+
+        if !self.safety.is_empty() {
+            info!(
+                allowlist = self.safety.allow.len(),
+                denylist = self.safety.deny.len(),
+                protected = self.safety.protected.len(),
+                confirmed = self.safety.confirmed,
+                "Target safety guard active"
+            );
+        }
+
+        if self.chaos.is_active() {
+            warn!(
+                drop_percent = self.chaos.drop_percent,
+                corrupt_headers_percent = self.chaos.corrupt_headers_percent,
+                oversized_body_percent = self.chaos.oversized_body_percent,
+                oversized_body_bytes = self.chaos.oversized_body_bytes,
+                abort_response_percent = self.chaos.abort_response_percent,
+                "Chaos fault injection ENABLED - some requests will be deliberately broken"
+            );
+        } else if !self.chaos.enabled && self.chaos.percentages().iter().any(|(_, p)| *p > 0.0) {
+            warn!("CHAOS_*_PERCENT set without CHAOS_ENABLED=true - fault injection is off");
+        }
     }
 }
 
@@ -871,6 +1698,10 @@ mod tests {
     // Helper to clear all load-test-related env vars before each test
     fn clear_env_vars() {
         let vars = [
+            "CLUSTER_REGION",
+            "CLUSTER_ZONE",
+            "REGION",
+            "ZONE",
             "TARGET_URL",
             "REQUEST_TYPE",
             "SEND_JSON",
@@ -896,6 +1727,50 @@ mod tests {
             "CLIENT_CERT_PATH",
             "CLIENT_KEY_PATH",
             "CUSTOM_HEADERS",
+            "DEBUG_SAMPLE_RATE",
+            "DEBUG_SAMPLE_MAX_PER_SEC",
+            "WORKER_RAMP",
+            "WORKER_JITTER_PERCENT",
+            "LOCAL_ADDRS",
+            "CHAOS_ENABLED",
+            "CHAOS_DROP_PERCENT",
+            "CHAOS_CORRUPT_HEADERS_PERCENT",
+            "CHAOS_OVERSIZED_BODY_PERCENT",
+            "CHAOS_OVERSIZED_BODY_BYTES",
+            "CHAOS_ABORT_RESPONSE_PERCENT",
+            "RUN_ID",
+            "RUN_ID_HEADER",
+            "TARGET_ALLOWLIST",
+            "TARGET_DENYLIST",
+            "PROTECTED_TARGETS",
+            "I_UNDERSTAND_THIS_SENDS_LOAD",
+            "MAX_TOTAL_REQUESTS",
+            "HARD_MAX_RPS",
+            "MAX_IN_FLIGHT",
+            "BACKPRESSURE",
+            "START_AT",
+            "ALIGN_TO_CLOCK",
+            "CYCLE_START",
+            "DAILY_CALENDAR_FILE",
+            "RPS_EXPRESSION",
+            "START_RPS",
+            "DOUBLING_PERIOD",
+            "ABORT_ERROR_RATE",
+            "ABORT_LATENCY_MS",
+            "DNS_ROUND_ROBIN",
+            "DNS_REFRESH_INTERVAL",
+            "DISCOVERY_REFRESH",
+            "GOLDEN_RECORD",
+            "IP_FAMILY",
+            "MODE",
+            "TEMPLATE_DIR",
+            "HDR_LOG_PATH",
+            "TLS_SESSION_RESUMPTION",
+            "CLIENT_CERT_RELOAD_INTERVAL",
+            "TLS_SERVER_NAME",
+            "MAX_BODY_BUFFER",
+            "WARMUP_CONNECTIONS",
+            "TARGET_PRECHECK",
         ];
         for var in vars {
             env::remove_var(var);
@@ -979,6 +1854,7 @@ mod tests {
                 min_rps,
                 max_rps,
                 ramp_duration,
+                ..
             } => {
                 assert!((min_rps - 10.0).abs() < 0.001);
                 assert!((max_rps - 1000.0).abs() < 0.001);
@@ -1045,6 +1921,44 @@ mod tests {
         clear_env_vars();
     }
 
+    #[test]
+    fn exponential_model_parsed() {
+        let _lock = ENV_MUTEX.lock().unwrap_or_else(|e| e.into_inner());
+        clear_env_vars();
+
+        env::set_var("TARGET_URL", "https://example.com");
+        env::set_var("LOAD_MODEL_TYPE", "Exponential");
+        env::set_var("START_RPS", "10");
+        env::set_var("DOUBLING_PERIOD", "2m");
+        env::set_var("ABORT_ERROR_RATE", "5");
+        env::set_var("ABORT_LATENCY_MS", "1500");
+
+        let config = Config::from_env().unwrap();
+        match config.load_model {
+            LoadModel::Exponential {
+                start_rps,
+                doubling_period,
+                max_rps,
+                abort,
+            } => {
+                assert!((start_rps - 10.0).abs() < 0.001);
+                assert_eq!(doubling_period, Duration::from_secs(120));
+                assert_eq!(max_rps, None);
+                assert_eq!(abort.error_rate_percent, Some(5.0));
+                assert_eq!(abort.latency, Some(Duration::from_millis(1500)));
+            }
+            other => panic!("expected Exponential, got {:?}", other),
+        }
+
+        env::remove_var("DOUBLING_PERIOD");
+        assert!(matches!(
+            Config::from_env(),
+            Err(ConfigError::MissingLoadModelParams { .. })
+        ));
+
+        clear_env_vars();
+    }
+
     #[test]
     fn custom_request_type() {
         let _lock = ENV_MUTEX.lock().unwrap_or_else(|e| e.into_inner());
@@ -1295,6 +2209,459 @@ mod tests {
         clear_env_vars();
     }
 
+    #[test]
+    fn debug_sample_rate_parsed_and_validated() {
+        let _lock = ENV_MUTEX.lock().unwrap_or_else(|e| e.into_inner());
+        clear_env_vars();
+
+        env::set_var("TARGET_URL", "https://example.com");
+        let config = Config::from_env().unwrap();
+        assert_eq!(config.debug_sample_rate, 0.0);
+        assert_eq!(config.debug_sample_max_per_sec, 1);
+
+        env::set_var("DEBUG_SAMPLE_RATE", "0.01");
+        env::set_var("DEBUG_SAMPLE_MAX_PER_SEC", "5");
+        let config = Config::from_env().unwrap();
+        assert_eq!(config.debug_sample_rate, 0.01);
+        assert_eq!(config.debug_sample_max_per_sec, 5);
+
+        env::set_var("DEBUG_SAMPLE_RATE", "1.5");
+        let err = Config::from_env().unwrap_err();
+        assert!(
+            matches!(err, ConfigError::InvalidValue { ref var, .. } if var == "DEBUG_SAMPLE_RATE"),
+            "expected InvalidValue for DEBUG_SAMPLE_RATE, got {:?}",
+            err
+        );
+        clear_env_vars();
+    }
+
+    #[test]
+    fn local_addrs_parsed_and_assigned_round_robin() {
+        let _lock = ENV_MUTEX.lock().unwrap_or_else(|e| e.into_inner());
+        clear_env_vars();
+
+        env::set_var("TARGET_URL", "https://example.com");
+        let config = Config::from_env().unwrap();
+        assert!(config.local_addrs.is_empty());
+        assert_eq!(config.local_addr_for_worker(3), None);
+
+        env::set_var("LOCAL_ADDRS", "10.0.0.5, 10.0.0.6,::1");
+        let config = Config::from_env().unwrap();
+        assert_eq!(config.local_addrs.len(), 3);
+        assert_eq!(
+            config.local_addr_for_worker(4),
+            Some("10.0.0.6".parse().unwrap())
+        );
+
+        env::set_var("LOCAL_ADDRS", "10.0.0.5,not-an-ip");
+        assert!(matches!(
+            Config::from_env().unwrap_err(),
+            ConfigError::InvalidValue { ref var, .. } if var == "LOCAL_ADDRS"
+        ));
+        clear_env_vars();
+    }
+
+    #[test]
+    fn chaos_requires_opt_in_and_valid_percentages() {
+        let _lock = ENV_MUTEX.lock().unwrap_or_else(|e| e.into_inner());
+        clear_env_vars();
+
+        env::set_var("TARGET_URL", "https://example.com");
+        env::set_var("CHAOS_DROP_PERCENT", "5");
+        let config = Config::from_env().unwrap();
+        assert_eq!(config.chaos.drop_percent, 5.0);
+        assert!(!config.chaos.is_active());
+
+        env::set_var("CHAOS_ENABLED", "true");
+        env::set_var("CHAOS_OVERSIZED_BODY_BYTES", "2048");
+        let config = Config::from_env().unwrap();
+        assert!(config.chaos.is_active());
+        assert_eq!(config.chaos.oversized_body_bytes, 2048);
+
+        env::set_var("CHAOS_ABORT_RESPONSE_PERCENT", "150");
+        assert!(matches!(
+            Config::from_env().unwrap_err(),
+            ConfigError::InvalidValue { ref var, .. } if var == "CHAOS_ABORT_RESPONSE_PERCENT"
+        ));
+        clear_env_vars();
+    }
+
+    #[test]
+    fn safety_lists_from_env() {
+        let _lock = ENV_MUTEX.lock().unwrap_or_else(|e| e.into_inner());
+        clear_env_vars();
+
+        env::set_var("TARGET_URL", "https://example.com");
+        let config = Config::from_env().unwrap();
+        assert!(config.safety.is_empty());
+
+        env::set_var("PROTECTED_TARGETS", "*.prod.example.com, 10.0.0.0/8");
+        env::set_var("I_UNDERSTAND_THIS_SENDS_LOAD", "true");
+        let config = Config::from_env().unwrap();
+        assert_eq!(config.safety.protected.len(), 2);
+        assert!(config.safety.confirmed);
+
+        env::set_var("TARGET_DENYLIST", "10.0.0.0/40");
+        assert!(matches!(
+            Config::from_env().unwrap_err(),
+            ConfigError::InvalidValue { ref var, .. } if var == "TARGET_DENYLIST"
+        ));
+        clear_env_vars();
+    }
+
+    #[test]
+    fn start_at_parsed_and_validated() {
+        let _lock = ENV_MUTEX.lock().unwrap_or_else(|e| e.into_inner());
+        clear_env_vars();
+
+        env::set_var("TARGET_URL", "https://example.com");
+        assert_eq!(Config::from_env().unwrap().start_at, None);
+
+        env::set_var("START_AT", "2026-10-16T14:00:00Z");
+        let start_at = Config::from_env().unwrap().start_at.unwrap();
+        assert_eq!(
+            start_at,
+            std::time::UNIX_EPOCH + Duration::from_secs(1_792_159_200)
+        );
+
+        env::set_var("START_AT", "soon");
+        assert!(matches!(
+            Config::from_env().unwrap_err(),
+            ConfigError::InvalidValue { ref var, .. } if var == "START_AT"
+        ));
+        clear_env_vars();
+    }
+
+    #[test]
+    fn hard_caps_parsed_and_validated() {
+        let _lock = ENV_MUTEX.lock().unwrap_or_else(|e| e.into_inner());
+        clear_env_vars();
+
+        env::set_var("TARGET_URL", "https://example.com");
+        let config = Config::from_env().unwrap();
+        assert_eq!(config.max_total_requests, 0);
+        assert_eq!(config.hard_max_rps, 0.0);
+
+        env::set_var("MAX_TOTAL_REQUESTS", "100000");
+        env::set_var("HARD_MAX_RPS", "2500");
+        let config = Config::from_env().unwrap();
+        assert_eq!(config.max_total_requests, 100_000);
+        assert_eq!(config.hard_max_rps, 2500.0);
+
+        env::set_var("MAX_IN_FLIGHT", "64");
+        env::set_var("BACKPRESSURE", "shed");
+        let config = Config::from_env().unwrap();
+        assert_eq!(config.max_in_flight, 64);
+        assert_eq!(config.backpressure, BackpressurePolicy::Shed);
+
+        env::set_var("HARD_MAX_RPS", "-1");
+        assert!(matches!(
+            Config::from_env().unwrap_err(),
+            ConfigError::InvalidValue { ref var, .. } if var == "HARD_MAX_RPS"
+        ));
+        clear_env_vars();
+    }
+
+    #[test]
+    fn dns_round_robin_from_env() {
+        let _lock = ENV_MUTEX.lock().unwrap_or_else(|e| e.into_inner());
+        clear_env_vars();
+
+        env::set_var("TARGET_URL", "https://example.com");
+        assert!(!Config::from_env().unwrap().dns_round_robin);
+
+        env::set_var("DNS_ROUND_ROBIN", "true");
+        assert!(Config::from_env().unwrap().dns_round_robin);
+        clear_env_vars();
+    }
+
+    #[test]
+    fn ip_family_from_env() {
+        let _lock = ENV_MUTEX.lock().unwrap_or_else(|e| e.into_inner());
+        clear_env_vars();
+
+        env::set_var("TARGET_URL", "https://example.com");
+        assert_eq!(Config::from_env().unwrap().ip_family, IpFamily::Auto);
+
+        env::set_var("IP_FAMILY", "v6");
+        assert_eq!(Config::from_env().unwrap().ip_family, IpFamily::V6);
+
+        env::set_var("IP_FAMILY", "v5");
+        assert!(matches!(
+            Config::from_env().unwrap_err(),
+            ConfigError::InvalidValue { ref var, .. } if var == "IP_FAMILY"
+        ));
+        clear_env_vars();
+    }
+
+    #[test]
+    fn mode_from_env() {
+        let _lock = ENV_MUTEX.lock().unwrap_or_else(|e| e.into_inner());
+        clear_env_vars();
+
+        env::set_var("TARGET_URL", "https://example.com");
+        assert_eq!(Config::from_env().unwrap().mode, RunMode::Load);
+
+        env::set_var("MODE", "smoke");
+        assert_eq!(Config::from_env().unwrap().mode, RunMode::Smoke);
+
+        env::set_var("MODE", "soak");
+        assert!(matches!(
+            Config::from_env().unwrap_err(),
+            ConfigError::InvalidValue { ref var, .. } if var == "MODE"
+        ));
+        clear_env_vars();
+    }
+
+    #[test]
+    fn template_dir_from_env() {
+        let _lock = ENV_MUTEX.lock().unwrap_or_else(|e| e.into_inner());
+        clear_env_vars();
+
+        env::set_var("TARGET_URL", "https://example.com");
+        assert_eq!(Config::from_env().unwrap().template_dir, None);
+
+        env::set_var("TEMPLATE_DIR", "/etc/loadtest/templates");
+        assert_eq!(
+            Config::from_env().unwrap().template_dir,
+            Some(PathBuf::from("/etc/loadtest/templates"))
+        );
+        clear_env_vars();
+    }
+
+    #[test]
+    fn hdr_log_path_from_env() {
+        let _lock = ENV_MUTEX.lock().unwrap_or_else(|e| e.into_inner());
+        clear_env_vars();
+
+        env::set_var("TARGET_URL", "https://example.com");
+        assert_eq!(Config::from_env().unwrap().hdr_log_path, None);
+
+        env::set_var("HDR_LOG_PATH", "/var/log/loadtest/latency.hlog");
+        assert_eq!(
+            Config::from_env().unwrap().hdr_log_path,
+            Some(PathBuf::from("/var/log/loadtest/latency.hlog"))
+        );
+        clear_env_vars();
+    }
+
+    #[test]
+    fn cluster_region_and_zone_from_env() {
+        let _lock = ENV_MUTEX.lock().unwrap_or_else(|e| e.into_inner());
+        clear_env_vars();
+
+        let cluster = ClusterConfig::from_env();
+        assert_eq!(cluster.region, "local");
+        assert_eq!(cluster.zone, "local");
+
+        env::set_var("REGION", "us-east-1");
+        env::set_var("ZONE", "us-east-1b");
+        let cluster = ClusterConfig::from_env();
+        assert_eq!(cluster.region, "us-east-1");
+        assert_eq!(cluster.zone, "us-east-1b");
+
+        env::set_var("CLUSTER_REGION", "eu-west-1");
+        assert_eq!(ClusterConfig::from_env().region, "eu-west-1");
+        clear_env_vars();
+    }
+
+    #[test]
+    fn tls_session_resumption_from_env() {
+        let _lock = ENV_MUTEX.lock().unwrap_or_else(|e| e.into_inner());
+        clear_env_vars();
+
+        env::set_var("TARGET_URL", "https://example.com");
+        let config = Config::from_env().unwrap();
+        assert!(config.tls_session_resumption);
+        assert!(config.to_client_config().tls_session_resumption);
+
+        env::set_var("TLS_SESSION_RESUMPTION", "false");
+        assert!(!Config::from_env().unwrap().tls_session_resumption);
+        clear_env_vars();
+    }
+
+    #[cfg(feature = "service-discovery")]
+    #[test]
+    fn discovery_target_from_env() {
+        let _lock = ENV_MUTEX.lock().unwrap_or_else(|e| e.into_inner());
+        clear_env_vars();
+
+        env::set_var("TARGET_URL", "consul://checkout/api");
+        let config = Config::from_env().unwrap();
+        assert_eq!(config.target_url, "consul://checkout/api");
+        assert_eq!(config.discovery_refresh, Duration::ZERO);
+
+        env::set_var("DISCOVERY_REFRESH", "30s");
+        assert_eq!(
+            Config::from_env().unwrap().discovery_refresh,
+            Duration::from_secs(30)
+        );
+
+        env::set_var("TARGET_URL", "k8s://checkout.:http");
+        assert!(Config::from_env().is_err());
+        clear_env_vars();
+    }
+
+    #[test]
+    fn dns_refresh_interval_from_env() {
+        let _lock = ENV_MUTEX.lock().unwrap_or_else(|e| e.into_inner());
+        clear_env_vars();
+
+        env::set_var("TARGET_URL", "https://example.com");
+        assert_eq!(
+            Config::from_env().unwrap().dns_refresh_interval,
+            Duration::ZERO
+        );
+
+        env::set_var("DNS_REFRESH_INTERVAL", "30s");
+        assert_eq!(
+            Config::from_env().unwrap().dns_refresh_interval,
+            Duration::from_secs(30)
+        );
+
+        env::set_var("DNS_REFRESH_INTERVAL", "often");
+        assert!(Config::from_env().is_err());
+        clear_env_vars();
+    }
+
+    #[test]
+    fn client_cert_reload_interval_from_env() {
+        let _lock = ENV_MUTEX.lock().unwrap_or_else(|e| e.into_inner());
+        clear_env_vars();
+
+        env::set_var("TARGET_URL", "https://example.com");
+        assert_eq!(
+            Config::from_env().unwrap().client_cert_reload_interval,
+            Duration::from_secs(30)
+        );
+
+        env::set_var("CLIENT_CERT_RELOAD_INTERVAL", "5m");
+        assert_eq!(
+            Config::from_env().unwrap().client_cert_reload_interval,
+            Duration::from_secs(300)
+        );
+
+        env::set_var("CLIENT_CERT_RELOAD_INTERVAL", "soon");
+        assert!(Config::from_env().is_err());
+        clear_env_vars();
+    }
+
+    #[test]
+    fn max_body_buffer_from_env() {
+        let _lock = ENV_MUTEX.lock().unwrap_or_else(|e| e.into_inner());
+        clear_env_vars();
+
+        env::set_var("TARGET_URL", "https://example.com");
+        assert_eq!(
+            Config::from_env().unwrap().max_body_buffer,
+            DEFAULT_MAX_BODY_BUFFER
+        );
+
+        env::set_var("MAX_BODY_BUFFER", "512KB");
+        assert_eq!(Config::from_env().unwrap().max_body_buffer, 512 * 1024);
+
+        env::set_var("MAX_BODY_BUFFER", "lots");
+        assert!(Config::from_env().is_err());
+        clear_env_vars();
+    }
+
+    #[test]
+    fn warmup_connections_from_env() {
+        let _lock = ENV_MUTEX.lock().unwrap_or_else(|e| e.into_inner());
+        clear_env_vars();
+
+        env::set_var("TARGET_URL", "https://example.com");
+        assert_eq!(Config::from_env().unwrap().warmup_connections, 0);
+
+        env::set_var("WARMUP_CONNECTIONS", "50");
+        assert_eq!(Config::from_env().unwrap().warmup_connections, 50);
+
+        env::set_var("WARMUP_CONNECTIONS", "-1");
+        assert!(Config::from_env().is_err());
+        clear_env_vars();
+    }
+
+    #[test]
+    fn target_precheck_from_env() {
+        let _lock = ENV_MUTEX.lock().unwrap_or_else(|e| e.into_inner());
+        clear_env_vars();
+
+        env::set_var("TARGET_URL", "https://example.com");
+        assert!(Config::from_env().unwrap().target_precheck);
+
+        env::set_var("TARGET_PRECHECK", "false");
+        assert!(!Config::from_env().unwrap().target_precheck);
+        clear_env_vars();
+    }
+
+    #[test]
+    fn tls_server_name_rewrites_target_url() {
+        let _lock = ENV_MUTEX.lock().unwrap_or_else(|e| e.into_inner());
+        clear_env_vars();
+
+        env::set_var("TARGET_URL", "https://203.0.113.5/health");
+        env::set_var("TLS_SERVER_NAME", "www.example.com");
+        let config = Config::from_env().unwrap();
+        assert_eq!(config.target_url, "https://www.example.com/health");
+        let sni = config.to_client_config().sni_override.unwrap();
+        assert_eq!(sni.server_name, "www.example.com");
+        assert_eq!(sni.connect_host, "203.0.113.5");
+
+        env::set_var("TLS_SERVER_NAME", "10.0.0.1");
+        assert!(Config::from_env().is_err());
+        clear_env_vars();
+    }
+
+    #[test]
+    fn run_id_from_env() {
+        let _lock = ENV_MUTEX.lock().unwrap_or_else(|e| e.into_inner());
+        clear_env_vars();
+
+        env::set_var("TARGET_URL", "https://example.com");
+        env::set_var("RUN_ID", "  ");
+        let config = Config::from_env().unwrap();
+        assert_eq!(config.run_id, None);
+        assert!(!config.run_id_header);
+
+        env::set_var("RUN_ID", "nightly-42");
+        env::set_var("RUN_ID_HEADER", "true");
+        let config = Config::from_env().unwrap();
+        assert_eq!(config.run_id.as_deref(), Some("nightly-42"));
+        assert!(config.run_id_header);
+        clear_env_vars();
+    }
+
+    #[test]
+    fn worker_ramp_and_jitter_parsed_and_validated() {
+        let _lock = ENV_MUTEX.lock().unwrap_or_else(|e| e.into_inner());
+        clear_env_vars();
+
+        env::set_var("TARGET_URL", "https://example.com");
+        let config = Config::from_env().unwrap();
+        assert_eq!(config.worker_ramp, Duration::ZERO);
+        assert_eq!(config.worker_jitter_percent, 10.0);
+
+        env::set_var("WORKER_RAMP", "30s");
+        env::set_var("WORKER_JITTER_PERCENT", "0");
+        let pacing = Config::from_env().unwrap().worker_pacing();
+        assert_eq!(pacing.ramp, Duration::from_secs(30));
+        assert_eq!(pacing.jitter_percent, 0.0);
+
+        env::set_var("WORKER_RAMP", "soon");
+        assert!(matches!(
+            Config::from_env().unwrap_err(),
+            ConfigError::InvalidDuration { ref var, .. } if var == "WORKER_RAMP"
+        ));
+
+        env::set_var("WORKER_RAMP", "30s");
+        env::set_var("WORKER_JITTER_PERCENT", "150");
+        assert!(matches!(
+            Config::from_env().unwrap_err(),
+            ConfigError::InvalidValue { ref var, .. } if var == "WORKER_JITTER_PERCENT"
+        ));
+        clear_env_vars();
+    }
+
     #[test]
     fn for_testing_creates_valid_config() {
         let config = Config::for_testing();
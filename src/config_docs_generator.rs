@@ -110,7 +110,7 @@ impl ConfigDocsGenerator {
                         "timeout": {
                             "description": "Request timeout (e.g., '30s', '1m')",
                             "oneOf": [
-                                {"type": "string", "pattern": "^[0-9]+(s|m|h)$"},
+                                {"type": "string", "pattern": crate::utils::DURATION_PATTERN},
                                 {"type": "integer", "minimum": 1}
                             ],
                             "default": "30s"
@@ -124,7 +124,7 @@ impl ConfigDocsGenerator {
                         "duration": {
                             "description": "Test duration (e.g., '5m', '1h')",
                             "oneOf": [
-                                {"type": "string", "pattern": "^[0-9]+(s|m|h)$"},
+                                {"type": "string", "pattern": crate::utils::DURATION_PATTERN},
                                 {"type": "integer", "minimum": 1}
                             ]
                         },
@@ -177,7 +177,7 @@ impl ConfigDocsGenerator {
                                 "rampDuration": {
                                     "description": "Ramp duration (e.g., '5m')",
                                     "oneOf": [
-                                        {"type": "string", "pattern": "^[0-9]+(s|m|h)$"},
+                                        {"type": "string", "pattern": crate::utils::DURATION_PATTERN},
                                         {"type": "integer", "minimum": 1}
                                     ]
                                 }
@@ -210,12 +210,31 @@ impl ConfigDocsGenerator {
                                 "minItems": 1,
                                 "items": {
                                     "type": "object",
-                                    "required": ["request"],
+                                    "anyOf": [
+                                        {"required": ["request"]},
+                                        {"required": ["tcpCheck"]},
+                                        {"required": ["ping"]}
+                                    ],
                                     "properties": {
                                         "name": {
                                             "type": "string",
                                             "description": "Step name"
                                         },
+                                        "tcpCheck": {
+                                            "description": "Open a TCP connection instead of sending a request (alias: ping)",
+                                            "oneOf": [
+                                                {"type": "string", "description": "host:port"},
+                                                {
+                                                    "type": "object",
+                                                    "properties": {
+                                                        "address": {"type": "string"},
+                                                        "tls": {"type": "boolean"},
+                                                        "serverName": {"type": "string"},
+                                                        "timeout": {"type": "string", "pattern": crate::utils::DURATION_PATTERN}
+                                                    }
+                                                }
+                                            ]
+                                        },
                                         "request": {
                                             "type": "object",
                                             "required": ["method", "path"],
@@ -237,13 +256,23 @@ impl ConfigDocsGenerator {
                                                     "type": "object",
                                                     "description": "Custom request headers",
                                                     "additionalProperties": {"type": "string"}
+                                                },
+                                                "protobuf": {
+                                                    "type": "object",
+                                                    "description": "Encode body / decode response as protobuf",
+                                                    "properties": {
+                                                        "descriptorSet": {"type": "string"},
+                                                        "requestMessage": {"type": "string"},
+                                                        "responseMessage": {"type": "string"}
+                                                    },
+                                                    "required": ["descriptorSet"]
                                                 }
                                             }
                                         },
                                         "thinkTime": {
                                             "description": "Think time after step",
                                             "oneOf": [
-                                                {"type": "string", "pattern": "^[0-9]+(s|m|h)$"},
+                                                {"type": "string", "pattern": crate::utils::DURATION_PATTERN},
                                                 {"type": "integer", "minimum": 0},
                                                 {
                                                     "type": "object",
@@ -270,6 +299,7 @@ impl ConfigDocsGenerator {
                                                 "properties": {
                                                     "name": {"type": "string"},
                                                     "jsonPath": {"type": "string"},
+                                                    "xmlPath": {"type": "string"},
                                                     "regex": {"type": "string"}
                                                 }
                                             }
@@ -304,7 +334,9 @@ impl ConfigDocsGenerator {
                                 "properties": {
                                     "timeout": {"type": "string"},
                                     "retryCount": {"type": "integer"},
-                                    "retryDelay": {"type": "string"}
+                                    "retryDelay": {"type": "string"},
+                                    "honorRetryAfter": {"type": "boolean"},
+                                    "maxRetryAfter": {"type": "string"}
                                 }
                             }
                         }
@@ -360,6 +360,7 @@ fn load_and_validate_config(file_path: &Path) -> Result<YamlConfig, String> {
     config
         .validate()
         .map_err(|e| format!("Validation failed: {}", e))?;
+    crate::lint::log_warnings(&config);
 
     Ok(config)
 }
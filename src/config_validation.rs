@@ -290,6 +290,24 @@ impl LoadModelValidator {
 
         Ok(())
     }
+
+    pub fn validate_exponential(start_rps: f64, max_rps: Option<f64>) -> ValidationResult<()> {
+        RangeValidator::validate_positive_f64(start_rps, "load.startRps")?;
+
+        if let Some(max_rps) = max_rps {
+            if max_rps <= start_rps {
+                return Err(ValidationError::FieldError {
+                    field: "load".to_string(),
+                    message: format!(
+                        "maxRps ({}) must be greater than startRps ({})",
+                        max_rps, start_rps
+                    ),
+                });
+            }
+        }
+
+        Ok(())
+    }
 }
 
 /// Configuration schema definition and JSON Schema export.
@@ -341,14 +359,14 @@ impl ConfigSchema {
                         "duration": {
                             "oneOf": [
                                 { "type": "integer", "minimum": 1 },
-                                { "type": "string", "pattern": "^\\d+[smhd]$" }
+                                { "type": "string", "pattern": crate::utils::DURATION_PATTERN }
                             ],
                             "description": "Test duration (e.g., '5m', '2h', 300)"
                         },
                         "timeout": {
                             "oneOf": [
                                 { "type": "integer", "minimum": 1 },
-                                { "type": "string", "pattern": "^\\d+[smhd]$" }
+                                { "type": "string", "pattern": crate::utils::DURATION_PATTERN }
                             ],
                             "default": 30,
                             "description": "Request timeout"
@@ -406,7 +424,11 @@ impl ConfigSchema {
                                 "minItems": 1,
                                 "items": {
                                     "type": "object",
-                                    "required": ["request"],
+                                    "anyOf": [
+                                        { "required": ["request"] },
+                                        { "required": ["tcpCheck"] },
+                                        { "required": ["ping"] }
+                                    ],
                                     "properties": {
                                         "name": { "type": "string" },
                                         "request": {
@@ -511,6 +533,14 @@ mod tests {
         assert!(LoadModelValidator::validate_daily_traffic(10.0, 10.0, 100.0).is_err());
     }
 
+    #[test]
+    fn test_load_model_validator_exponential() {
+        assert!(LoadModelValidator::validate_exponential(10.0, None).is_ok());
+        assert!(LoadModelValidator::validate_exponential(10.0, Some(1000.0)).is_ok());
+        assert!(LoadModelValidator::validate_exponential(0.0, None).is_err());
+        assert!(LoadModelValidator::validate_exponential(10.0, Some(10.0)).is_err());
+    }
+
     #[test]
     fn test_validation_context() {
         let mut ctx = ValidationContext::new();
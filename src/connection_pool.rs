@@ -3,12 +3,22 @@
 //! This module provides connection pool statistics tracking and configuration.
 //! Since reqwest doesn't expose internal pool metrics, we track connection
 //! behavior patterns and configuration to provide insights into pool utilization.
-
+//!
+//! On Linux the real connection counts are also sampled from the kernel (see
+//! [`spawn_connection_sampler`]): open outbound TCP connections in total and
+//! per remote address, how many of them are idle, and how many new
+//! connections are being opened — which makes connection churn visible in
+//! no-keepalive or HTTP/1 tests.
+
+use std::collections::{HashMap, HashSet};
+use std::net::SocketAddr;
+use std::sync::atomic::{AtomicI64, Ordering};
 use std::sync::{Arc, Mutex};
 use std::time::{Duration, Instant};
-use tracing::debug;
+use tracing::{debug, info};
 
 use crate::metrics::{
+    CONNECTIONS_IDLE, CONNECTIONS_OPEN, CONNECTIONS_OPENED_TOTAL, CONNECTIONS_OPEN_BY_HOST,
     CONNECTION_POOL_LIKELY_NEW, CONNECTION_POOL_LIKELY_REUSED, CONNECTION_POOL_REQUESTS_TOTAL,
     CONNECTION_POOL_REUSE_RATE,
 };
@@ -280,10 +290,220 @@ lazy_static::lazy_static! {
     pub static ref GLOBAL_POOL_STATS: PoolStatsTracker = PoolStatsTracker::default();
 }
 
+/// Requests currently waiting for a response. Open connections beyond this
+/// are sitting idle in the pool.
+static IN_FLIGHT: AtomicI64 = AtomicI64::new(0);
+
+/// Marks one request as in flight until dropped.
+pub struct InFlightGuard(());
+
+impl Drop for InFlightGuard {
+    fn drop(&mut self) {
+        IN_FLIGHT.fetch_sub(1, Ordering::Relaxed);
+    }
+}
+
+/// Counts a request as in flight for as long as the guard lives.
+pub fn track_in_flight() -> InFlightGuard {
+    IN_FLIGHT.fetch_add(1, Ordering::Relaxed);
+    InFlightGuard(())
+}
+
+/// Requests currently in flight.
+pub fn in_flight() -> i64 {
+    IN_FLIGHT.load(Ordering::Relaxed)
+}
+
+/// TCP socket state, as far as connection counting cares.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SocketState {
+    Listen,
+    Established,
+    Other,
+}
+
+/// One row of the kernel's TCP socket table.
+#[derive(Debug, Clone)]
+pub struct SocketEntry {
+    pub local: SocketAddr,
+    pub remote: SocketAddr,
+    pub state: SocketState,
+    pub inode: u64,
+}
+
+/// Established outbound connections owned by this process, per remote
+/// address. Sockets accepted by our own listeners (health/metrics server)
+/// are excluded.
+pub fn outbound_by_host(entries: &[SocketEntry], owned: &HashSet<u64>) -> HashMap<String, u64> {
+    let listen_ports: HashSet<u16> = entries
+        .iter()
+        .filter(|e| e.state == SocketState::Listen && owned.contains(&e.inode))
+        .map(|e| e.local.port())
+        .collect();
+    let mut hosts = HashMap::new();
+    for entry in entries {
+        if entry.state == SocketState::Established
+            && owned.contains(&entry.inode)
+            && !listen_ports.contains(&entry.local.port())
+        {
+            *hosts.entry(entry.remote.to_string()).or_insert(0) += 1;
+        }
+    }
+    hosts
+}
+
+/// Reads `ActiveOpens` (outbound connections opened) from `/proc/net/snmp`.
+pub fn parse_active_opens(snmp: &str) -> Option<u64> {
+    let mut tcp_lines = snmp.lines().filter(|line| line.starts_with("Tcp:"));
+    let header = tcp_lines.next()?;
+    let values = tcp_lines.next()?;
+    let index = header.split_whitespace().position(|f| f == "ActiveOpens")?;
+    values.split_whitespace().nth(index)?.parse().ok()
+}
+
+/// Reads this process's socket table and owned socket inodes.
+#[cfg(target_os = "linux")]
+fn read_sockets() -> Option<(Vec<SocketEntry>, HashSet<u64>)> {
+    use procfs::net::TcpState;
+    use procfs::process::{FDTarget, Process};
+
+    let me = Process::myself().ok()?;
+    let owned: HashSet<u64> = me
+        .fd()
+        .ok()?
+        .filter_map(|fd| match fd.ok()?.target {
+            FDTarget::Socket(inode) => Some(inode),
+            _ => None,
+        })
+        .collect();
+    let mut rows = procfs::net::tcp().unwrap_or_default();
+    rows.extend(procfs::net::tcp6().unwrap_or_default());
+    let entries = rows
+        .into_iter()
+        .map(|row| SocketEntry {
+            local: row.local_address,
+            remote: row.remote_address,
+            state: match row.state {
+                TcpState::Listen => SocketState::Listen,
+                TcpState::Established => SocketState::Established,
+                _ => SocketState::Other,
+            },
+            inode: row.inode,
+        })
+        .collect();
+    Some((entries, owned))
+}
+
+#[cfg(not(target_os = "linux"))]
+fn read_sockets() -> Option<(Vec<SocketEntry>, HashSet<u64>)> {
+    None
+}
+
+/// Periodically updates the `connections_*` gauges from the kernel.
+///
+/// `connections_opened_total` follows the network namespace's `ActiveOpens`
+/// counter, so it also catches connections shorter than the sampling
+/// interval; in a container that namespace is effectively this process.
+/// Does nothing on platforms without `/proc`.
+pub fn spawn_connection_sampler(interval: Duration) -> tokio::task::JoinHandle<()> {
+    tokio::spawn(async move {
+        if read_sockets().is_none() {
+            info!("Connection gauges unavailable on this platform (requires /proc)");
+            return;
+        }
+        let mut ticker = tokio::time::interval(interval);
+        let mut last_active_opens = None;
+        loop {
+            ticker.tick().await;
+            if let Some((entries, owned)) = read_sockets() {
+                let hosts = outbound_by_host(&entries, &owned);
+                let open: u64 = hosts.values().sum();
+                CONNECTIONS_OPEN.set(open as f64);
+                CONNECTIONS_IDLE.set((open as i64 - in_flight()).max(0) as f64);
+                CONNECTIONS_OPEN_BY_HOST.reset();
+                for (host, count) in &hosts {
+                    CONNECTIONS_OPEN_BY_HOST
+                        .with_label_values(&[host])
+                        .set(*count as f64);
+                }
+            }
+            let active_opens = std::fs::read_to_string("/proc/net/snmp")
+                .ok()
+                .and_then(|snmp| parse_active_opens(&snmp));
+            if let (Some(prev), Some(now)) = (last_active_opens, active_opens) {
+                CONNECTIONS_OPENED_TOTAL.inc_by(now.saturating_sub(prev));
+            }
+            last_active_opens = active_opens.or(last_active_opens);
+        }
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn counts_outbound_connections_per_host() {
+        let addr = |s: &str| s.parse::<SocketAddr>().unwrap();
+        let entry = |local, remote, state, inode| SocketEntry {
+            local: addr(local),
+            remote: addr(remote),
+            state,
+            inode,
+        };
+        let entries = vec![
+            entry("0.0.0.0:8080", "0.0.0.0:0", SocketState::Listen, 1),
+            // Scraper connected to our metrics server
+            entry(
+                "10.0.0.2:8080",
+                "10.0.0.9:51000",
+                SocketState::Established,
+                2,
+            ),
+            entry(
+                "10.0.0.2:40001",
+                "10.0.0.5:443",
+                SocketState::Established,
+                3,
+            ),
+            entry(
+                "10.0.0.2:40002",
+                "10.0.0.5:443",
+                SocketState::Established,
+                4,
+            ),
+            entry(
+                "10.0.0.2:40003",
+                "10.0.0.6:443",
+                SocketState::Established,
+                5,
+            ),
+            entry("10.0.0.2:40004", "10.0.0.6:443", SocketState::Other, 6),
+            // Another process's connection
+            entry(
+                "10.0.0.2:40005",
+                "10.0.0.7:443",
+                SocketState::Established,
+                99,
+            ),
+        ];
+        let owned: HashSet<u64> = (1..=6).collect();
+
+        let hosts = outbound_by_host(&entries, &owned);
+        assert_eq!(hosts.len(), 2);
+        assert_eq!(hosts["10.0.0.5:443"], 2);
+        assert_eq!(hosts["10.0.0.6:443"], 1);
+    }
+
+    #[test]
+    fn parses_active_opens() {
+        let snmp = "Ip: Forwarding DefaultTTL\nIp: 1 64\n\
+                    Tcp: RtoAlgorithm RtoMin RtoMax MaxConn ActiveOpens PassiveOpens\n\
+                    Tcp: 1 200 120000 -1 4242 17\n";
+        assert_eq!(parse_active_opens(snmp), Some(4242));
+        assert_eq!(parse_active_opens("Ip: 1\n"), None);
+    }
+
     #[test]
     fn test_pool_config_defaults() {
         let config = PoolConfig::default();
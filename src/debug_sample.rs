@@ -0,0 +1,220 @@
+//! Request/response debug sampling (`DEBUG_SAMPLE_RATE`).
+//!
+//! Logs the full request (method, URL, headers, body) and response (status,
+//! headers, body) for a small random fraction of requests, capped at
+//! `DEBUG_SAMPLE_MAX_PER_SEC` samples per second across all workers. This
+//! makes it possible to check what is actually on the wire during a
+//! high-RPS run without drowning in output.
+//!
+//! Samples are emitted as `tracing` events at INFO level under this module's
+//! target (`rust_loadtest::debug_sample`). Headers and bodies pass through
+//! [`crate::redaction`] and bodies are truncated to [`MAX_LOGGED_BODY_BYTES`].
+
+use std::sync::atomic::{AtomicU32, AtomicU64, Ordering};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use rand::Rng;
+use reqwest::header::HeaderMap;
+use tracing::info;
+
+use crate::redaction::GLOBAL_REDACTOR;
+
+/// Bodies longer than this are truncated in sample output.
+pub const MAX_LOGGED_BODY_BYTES: usize = 4096;
+
+/// Sampling probability (0.0–1.0) stored as f64 bits. 0 = disabled.
+static SAMPLE_RATE_BITS: AtomicU64 = AtomicU64::new(0);
+
+/// Maximum samples per wall-clock second.
+static MAX_PER_SEC: AtomicU32 = AtomicU32::new(1);
+
+/// Unix second of the current rate-limit window.
+static WINDOW_SECS: AtomicU64 = AtomicU64::new(0);
+
+/// Samples taken in the current window.
+static WINDOW_COUNT: AtomicU32 = AtomicU32::new(0);
+
+/// Enable debug sampling. `rate` is the fraction of requests sampled
+/// (0.0 disables), `max_per_sec` caps the number of samples per second.
+pub fn init(rate: f64, max_per_sec: u32) {
+    SAMPLE_RATE_BITS.store(rate.clamp(0.0, 1.0).to_bits(), Ordering::Relaxed);
+    MAX_PER_SEC.store(max_per_sec, Ordering::Relaxed);
+    WINDOW_SECS.store(0, Ordering::Relaxed);
+    WINDOW_COUNT.store(0, Ordering::Relaxed);
+}
+
+/// Returns true if debug sampling is enabled at all.
+pub fn is_enabled() -> bool {
+    SAMPLE_RATE_BITS.load(Ordering::Relaxed) != 0
+}
+
+/// Returns true if the current request should be logged.
+///
+/// Cheap when sampling is disabled: a single relaxed atomic load.
+pub fn should_sample() -> bool {
+    let rate = f64::from_bits(SAMPLE_RATE_BITS.load(Ordering::Relaxed));
+    if rate <= 0.0 {
+        return false;
+    }
+    if rate < 1.0 && crate::seed::with_rng(|rng| rng.gen::<f64>()) >= rate {
+        return false;
+    }
+    take_rate_limit_slot()
+}
+
+/// Claims one slot in the current one-second window.
+fn take_rate_limit_slot() -> bool {
+    let max = MAX_PER_SEC.load(Ordering::Relaxed);
+    if max == 0 {
+        return true;
+    }
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0);
+    let window = WINDOW_SECS.load(Ordering::Relaxed);
+    if window != now
+        && WINDOW_SECS
+            .compare_exchange(window, now, Ordering::Relaxed, Ordering::Relaxed)
+            .is_ok()
+    {
+        WINDOW_COUNT.store(0, Ordering::Relaxed);
+    }
+    WINDOW_COUNT.fetch_add(1, Ordering::Relaxed) < max
+}
+
+/// Render headers as `name: value` lines with sensitive values masked.
+pub fn format_headers(headers: &HeaderMap) -> String {
+    headers
+        .iter()
+        .map(|(name, value)| {
+            let value = value.to_str().unwrap_or("<binary>");
+            format!("{}: {}", name, GLOBAL_REDACTOR.header(name.as_str(), value))
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Render a body for logging: sensitive fields are masked, then the result
+/// is truncated to [`MAX_LOGGED_BODY_BYTES`].
+///
+/// `total_len` is the full body size, which may exceed `body.len()` when
+/// only a prefix was captured from a streamed response.
+pub fn format_body(body: &[u8], total_len: usize) -> String {
+    let text = String::from_utf8_lossy(body);
+    let masked = GLOBAL_REDACTOR.body(&text);
+    let mut end = masked.len().min(MAX_LOGGED_BODY_BYTES);
+    while !masked.is_char_boundary(end) {
+        end -= 1;
+    }
+    let omitted = total_len.saturating_sub(body.len()) + (masked.len() - end);
+    if omitted > 0 {
+        format!("{}... [{} bytes truncated]", &masked[..end], omitted)
+    } else {
+        masked.into_owned()
+    }
+}
+
+/// Append a streamed response chunk to a sample buffer, keeping at most
+/// [`MAX_LOGGED_BODY_BYTES`].
+pub fn capture_chunk(buffer: &mut Vec<u8>, chunk: &[u8]) {
+    let room = MAX_LOGGED_BODY_BYTES.saturating_sub(buffer.len());
+    buffer.extend_from_slice(&chunk[..chunk.len().min(room)]);
+}
+
+/// Log a sampled outgoing request.
+pub fn log_request(source: &str, request: &reqwest::Request) {
+    let body = request
+        .body()
+        .and_then(|b| b.as_bytes())
+        .map(|b| format_body(b, b.len()))
+        .unwrap_or_default();
+    info!(
+        source = %source,
+        method = %request.method(),
+        url = %request.url(),
+        headers = %format_headers(request.headers()),
+        body = %body,
+        "Sampled request"
+    );
+}
+
+/// Log the response to a sampled request. `body` may be a captured prefix
+/// of a `body_len`-byte body.
+pub fn log_response(
+    source: &str,
+    status: u16,
+    headers: &HeaderMap,
+    body: &[u8],
+    body_len: usize,
+    response_time_ms: u64,
+) {
+    info!(
+        source = %source,
+        status,
+        response_time_ms,
+        headers = %format_headers(headers),
+        body_len,
+        body = %format_body(body, body_len),
+        "Sampled response"
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serial_test::serial;
+
+    #[test]
+    #[serial]
+    fn disabled_by_default() {
+        init(0.0, 1);
+        assert!(!is_enabled());
+        assert!(!should_sample());
+    }
+
+    #[test]
+    #[serial]
+    fn rate_limit_caps_samples_per_second() {
+        init(1.0, 3);
+        let taken = (0..100).filter(|_| should_sample()).count();
+        // A window boundary may fall inside the loop, allowing one extra batch.
+        assert!((3..=6).contains(&taken), "took {}", taken);
+        init(0.0, 1);
+    }
+
+    #[test]
+    #[serial]
+    fn unlimited_when_max_is_zero() {
+        init(1.0, 0);
+        assert_eq!((0..50).filter(|_| should_sample()).count(), 50);
+        init(0.0, 1);
+    }
+
+    #[test]
+    fn redacts_credentials() {
+        let mut headers = HeaderMap::new();
+        headers.insert("authorization", "Bearer abc.def".parse().unwrap());
+        headers.insert("content-type", "application/json".parse().unwrap());
+        let rendered = format_headers(&headers);
+        assert!(rendered.contains("authorization: Bearer [REDACTED]"));
+        assert!(rendered.contains("content-type: application/json"));
+
+        let body = br#"{"user":"bob","password":"hunter2"}"#;
+        assert!(!format_body(body, body.len()).contains("hunter2"));
+    }
+
+    #[test]
+    fn truncates_long_bodies() {
+        let body = vec![b'a'; MAX_LOGGED_BODY_BYTES + 10];
+        let rendered = format_body(&body, body.len());
+        assert!(rendered.ends_with("[10 bytes truncated]"));
+        assert_eq!(format_body(b"short", 5), "short");
+
+        let mut buffer = Vec::new();
+        capture_chunk(&mut buffer, &body);
+        capture_chunk(&mut buffer, b"more");
+        assert_eq!(buffer.len(), MAX_LOGGED_BODY_BYTES);
+        assert!(format_body(&buffer, body.len() + 4).ends_with("[14 bytes truncated]"));
+    }
+}
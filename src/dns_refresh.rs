@@ -0,0 +1,269 @@
+//! Periodic DNS re-resolution for long tests (`DNS_REFRESH_INTERVAL`).
+//!
+//! Pooled connections stay open to whatever address the target resolved to
+//! when they were established, so when a target fails over to a new IP in
+//! the middle of a soak test the generator keeps hammering the old one.
+//!
+//! With `DNS_REFRESH_INTERVAL` set, clients resolve through
+//! [`RefreshingResolver`], which serves hostnames from a shared cache, and a
+//! background task re-resolves every cached hostname once per interval (so
+//! the interval acts as the cache's TTL). When a hostname's address set
+//! changes, the change is logged and counted in
+//! `dns_resolution_changes_total{host}`, and the next requests — one per
+//! worker, about one per pooled connection — are sent with
+//! `Connection: close`, so their replacements connect to the new addresses.
+//!
+//! `RESOLVE_TARGET_ADDR` and `DNS_ROUND_ROBIN` pin addresses and bypass the
+//! resolver; `TLS_SERVER_NAME` and `IP_FAMILY` are honoured as usual.
+
+use std::collections::{BTreeSet, HashMap};
+use std::net::SocketAddr;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::Mutex;
+use std::time::Duration;
+
+use reqwest::dns::{Addrs, Name, Resolve, Resolving};
+use reqwest::header::CONNECTION;
+use reqwest::RequestBuilder;
+use tracing::{info, warn};
+
+use crate::ip_family::IpFamily;
+use crate::metrics::DNS_RESOLUTION_CHANGES_TOTAL;
+use crate::sni::SniOverride;
+
+type CacheKey = (String, IpFamily);
+
+/// Resolved addresses by hostname and address family.
+#[derive(Debug, Default)]
+pub struct DnsCache {
+    entries: HashMap<CacheKey, Vec<SocketAddr>>,
+}
+
+impl DnsCache {
+    pub fn get(&self, host: &str, family: IpFamily) -> Option<Vec<SocketAddr>> {
+        self.entries.get(&(host.to_string(), family)).cloned()
+    }
+
+    /// Stores `addrs` for `host`; returns true if a different address set
+    /// was cached before. Order doesn't count as a change.
+    pub fn update(&mut self, host: &str, family: IpFamily, addrs: Vec<SocketAddr>) -> bool {
+        let previous = self
+            .entries
+            .insert((host.to_string(), family), addrs.clone());
+        previous.is_some_and(|previous| {
+            previous.iter().collect::<BTreeSet<_>>() != addrs.iter().collect::<BTreeSet<_>>()
+        })
+    }
+
+    pub fn keys(&self) -> Vec<CacheKey> {
+        self.entries.keys().cloned().collect()
+    }
+}
+
+/// Countdown of requests to send with `Connection: close` after a change.
+#[derive(Debug, Default)]
+pub struct ConnectionRetirer {
+    remaining: AtomicUsize,
+}
+
+impl ConnectionRetirer {
+    pub const fn new() -> Self {
+        Self {
+            remaining: AtomicUsize::new(0),
+        }
+    }
+
+    /// Starts retiring `connections` connections.
+    pub fn retire(&self, connections: usize) {
+        self.remaining.store(connections, Ordering::Relaxed);
+    }
+
+    /// Returns true if this request should close its connection.
+    pub fn take(&self) -> bool {
+        self.remaining
+            .fetch_update(Ordering::Relaxed, Ordering::Relaxed, |n| n.checked_sub(1))
+            .is_ok()
+    }
+}
+
+static ACTIVE: AtomicBool = AtomicBool::new(false);
+static WORKERS: AtomicUsize = AtomicUsize::new(1);
+static RETIRER: ConnectionRetirer = ConnectionRetirer::new();
+
+lazy_static::lazy_static! {
+    static ref CACHE: Mutex<DnsCache> = Mutex::new(DnsCache::default());
+}
+
+/// Enables re-resolution for clients built afterwards; `workers` is how
+/// many connections a change retires.
+pub fn init(enabled: bool, workers: usize) {
+    ACTIVE.store(enabled, Ordering::Relaxed);
+    WORKERS.store(workers.max(1), Ordering::Relaxed);
+}
+
+/// Returns true if clients should resolve through [`RefreshingResolver`].
+pub fn enabled() -> bool {
+    ACTIVE.load(Ordering::Relaxed)
+}
+
+async fn lookup(host: &str, family: IpFamily) -> Result<Vec<SocketAddr>, String> {
+    let addrs: Vec<SocketAddr> = tokio::net::lookup_host((host, 0))
+        .await
+        .map_err(|e| e.to_string())?
+        .filter(|addr| family.allows(addr.ip()))
+        .collect();
+    if addrs.is_empty() {
+        return Err(format!("'{}' has no {} addresses", host, family));
+    }
+    Ok(addrs)
+}
+
+/// DNS resolver serving hostnames from the shared re-resolved cache.
+pub struct RefreshingResolver {
+    sni: Option<SniOverride>,
+    family: IpFamily,
+}
+
+impl RefreshingResolver {
+    pub fn new(sni: Option<SniOverride>, family: IpFamily) -> Self {
+        Self { sni, family }
+    }
+}
+
+impl Resolve for RefreshingResolver {
+    fn resolve(&self, name: Name) -> Resolving {
+        let host = match &self.sni {
+            Some(sni) if name.as_str().eq_ignore_ascii_case(&sni.server_name) => {
+                sni.connect_host.clone()
+            }
+            _ => name.as_str().to_string(),
+        };
+        let family = self.family;
+        Box::pin(async move {
+            let cached = CACHE
+                .lock()
+                .unwrap_or_else(|e| e.into_inner())
+                .get(&host, family);
+            let addrs = match cached {
+                Some(addrs) => addrs,
+                None => {
+                    let addrs = lookup(&host, family).await?;
+                    CACHE.lock().unwrap_or_else(|e| e.into_inner()).update(
+                        &host,
+                        family,
+                        addrs.clone(),
+                    );
+                    addrs
+                }
+            };
+            let addrs: Addrs = Box::new(addrs.into_iter());
+            Ok(addrs)
+        })
+    }
+}
+
+/// Re-resolves every cached hostname; returns how many changed. Failed
+/// lookups keep the previous addresses.
+pub async fn refresh_all() -> usize {
+    let keys = CACHE.lock().unwrap_or_else(|e| e.into_inner()).keys();
+    let mut changed = 0;
+    for (host, family) in keys {
+        let addrs = match lookup(&host, family).await {
+            Ok(addrs) => addrs,
+            Err(e) => {
+                warn!(host = %host, error = %e, "DNS re-resolution failed, keeping cached addresses");
+                continue;
+            }
+        };
+        let mut cache = CACHE.lock().unwrap_or_else(|e| e.into_inner());
+        let previous = cache.get(&host, family).unwrap_or_default();
+        if cache.update(&host, family, addrs.clone()) {
+            changed += 1;
+            DNS_RESOLUTION_CHANGES_TOTAL
+                .with_label_values(&[host.as_str()])
+                .inc();
+            RETIRER.retire(WORKERS.load(Ordering::Relaxed));
+            info!(
+                host = %host,
+                previous = ?previous,
+                current = ?addrs,
+                "DNS resolution changed - retiring pooled connections"
+            );
+        }
+    }
+    changed
+}
+
+/// Spawn the background task that re-resolves cached hostnames every
+/// `interval`.
+pub fn spawn_dns_refresh(interval: Duration) -> tokio::task::JoinHandle<()> {
+    tokio::spawn(async move {
+        let mut ticker = tokio::time::interval(interval);
+        ticker.tick().await; // Skip the first immediate tick
+        loop {
+            ticker.tick().await;
+            refresh_all().await;
+        }
+    })
+}
+
+/// Adds `Connection: close` to the request while connections are being
+/// retired after a resolution change.
+pub fn apply(builder: RequestBuilder) -> RequestBuilder {
+    if RETIRER.take() {
+        builder.header(CONNECTION, "close")
+    } else {
+        builder
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::str::FromStr;
+
+    fn addr(s: &str) -> SocketAddr {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn detects_changed_address_sets() {
+        let mut cache = DnsCache::default();
+        let a = addr("10.0.0.1:0");
+        let b = addr("10.0.0.2:0");
+        assert!(!cache.update("api.example.com", IpFamily::Auto, vec![a, b]));
+        assert!(!cache.update("api.example.com", IpFamily::Auto, vec![b, a]));
+        assert!(cache.update("api.example.com", IpFamily::Auto, vec![b]));
+        assert!(!cache.update("api.example.com", IpFamily::V4, vec![a]));
+        assert_eq!(cache.get("api.example.com", IpFamily::Auto), Some(vec![b]));
+        assert_eq!(cache.keys().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn resolves_through_the_cache() {
+        let resolver = RefreshingResolver::new(None, IpFamily::V4);
+        let addrs: Vec<SocketAddr> = resolver
+            .resolve(Name::from_str("localhost").unwrap())
+            .await
+            .unwrap()
+            .collect();
+        assert!(!addrs.is_empty() && addrs.iter().all(|a| a.is_ipv4()));
+        assert_eq!(
+            CACHE.lock().unwrap().get("localhost", IpFamily::V4),
+            Some(addrs)
+        );
+    }
+
+    #[test]
+    fn retires_the_requested_number_of_connections() {
+        let retirer = ConnectionRetirer::new();
+        assert!(!retirer.take());
+        retirer.retire(2);
+        assert!(retirer.take() && retirer.take());
+        assert!(!retirer.take());
+
+        let client = reqwest::Client::new();
+        let request = apply(client.get("http://localhost/")).build().unwrap();
+        assert!(request.headers().get(CONNECTION).is_none());
+    }
+}
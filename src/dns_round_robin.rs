@@ -0,0 +1,145 @@
+//! Spreading connections across every address a target resolves to
+//! (`DNS_ROUND_ROBIN`).
+//!
+//! By default the HTTP client connects to whichever address the resolver
+//! returns first, so one unhealthy backend behind DNS load balancing can hide
+//! among healthy ones — or take all the traffic. With `DNS_ROUND_ROBIN=true`
+//! the target host is resolved once at test start, one client is pinned to
+//! each address, and workers are assigned to them round-robin
+//! (see [`crate::client::SourceAddrClients`]).
+//!
+//! Requests are then also counted in `requests_by_target_ip_total` and
+//! `request_duration_by_target_ip_seconds`, labelled with the address the
+//! request was sent to, so a single bad backend stands out.
+
+use std::net::{IpAddr, SocketAddr};
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use reqwest::Url;
+use tracing::{info, warn};
+
+use crate::config::Config;
+use crate::metrics::{REQUESTS_BY_TARGET_IP_TOTAL, REQUEST_DURATION_BY_TARGET_IP_SECONDS};
+use crate::service_discovery;
+
+/// Whether per-IP metrics are recorded.
+static ENABLED: AtomicBool = AtomicBool::new(false);
+
+/// Enables or disables per-IP metrics for the current test.
+pub fn init(enabled: bool) {
+    ENABLED.store(enabled, Ordering::Relaxed);
+}
+
+pub fn is_enabled() -> bool {
+    ENABLED.load(Ordering::Relaxed)
+}
+
+/// A target hostname and every address it resolved to.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ResolvedTarget {
+    pub host: String,
+    pub addrs: Vec<SocketAddr>,
+}
+
+/// Resolves the host of `url` to all of its addresses.
+///
+/// Returns `None` when the URL has no host, the host is already an IP
+/// literal, or resolution fails or returns nothing.
+pub async fn resolve(url: &str) -> Option<ResolvedTarget> {
+    let parsed = Url::parse(url).ok()?;
+    let host = parsed.host_str()?;
+    if host
+        .trim_matches(|c| c == '[' || c == ']')
+        .parse::<IpAddr>()
+        .is_ok()
+    {
+        return None;
+    }
+    let port = parsed.port_or_known_default().unwrap_or(80);
+    let mut addrs: Vec<SocketAddr> = tokio::net::lookup_host((host, port)).await.ok()?.collect();
+    addrs.sort();
+    addrs.dedup();
+    if addrs.is_empty() {
+        return None;
+    }
+    info!(
+        host = %host,
+        addrs = ?addrs,
+        "DNS_ROUND_ROBIN: spreading connections across resolved addresses"
+    );
+    Some(ResolvedTarget {
+        host: host.to_string(),
+        addrs,
+    })
+}
+
+/// Resolves `config.target_url` when `DNS_ROUND_ROBIN` is enabled.
+///
+/// An explicit `RESOLVE_TARGET_ADDR` override wins: the target is already
+/// pinned to one address, so nothing is resolved.
+pub async fn resolve_target(config: &Config) -> Option<ResolvedTarget> {
+    if !config.dns_round_robin {
+        return None;
+    }
+    if config
+        .resolve_target_addr
+        .as_deref()
+        .is_some_and(|s| !s.is_empty())
+    {
+        warn!("DNS_ROUND_ROBIN ignored: RESOLVE_TARGET_ADDR already pins the target address");
+        return None;
+    }
+    if service_discovery::is_discovery_url(&config.target_url) {
+        warn!("DNS_ROUND_ROBIN ignored: the target's instances come from service discovery");
+        return None;
+    }
+    if config.sni_override.is_some() {
+        warn!("DNS_ROUND_ROBIN ignored: TLS_SERVER_NAME already maps the target host");
+        return None;
+    }
+    let target = resolve(&config.target_url).await.and_then(|mut t| {
+        t.addrs.retain(|addr| config.ip_family.allows(addr.ip()));
+        (!t.addrs.is_empty()).then_some(t)
+    });
+    if target.is_none() {
+        warn!(
+            target_url = %config.target_url,
+            "DNS_ROUND_ROBIN: target host is an IP or did not resolve; using default connections"
+        );
+    }
+    target
+}
+
+/// Records one request against the address it was sent to.
+///
+/// `status` is the status-code label (`"200"`, `"error"`, ...). Does nothing
+/// when `DNS_ROUND_ROBIN` is off or the address is unknown.
+pub fn record(ip: Option<IpAddr>, status: &str, latency_secs: f64, node_id: &str, run_id: &str) {
+    let Some(ip) = ip.filter(|_| is_enabled()) else {
+        return;
+    };
+    let ip = ip.to_string();
+    REQUESTS_BY_TARGET_IP_TOTAL
+        .with_label_values(&[&ip, status, node_id, run_id])
+        .inc();
+    REQUEST_DURATION_BY_TARGET_IP_SECONDS
+        .with_label_values(&[&ip, node_id, run_id])
+        .observe(latency_secs);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn resolves_hostnames_but_not_ip_literals() {
+        assert_eq!(resolve("http://127.0.0.1:8080/").await, None);
+        assert_eq!(resolve("http://[::1]/").await, None);
+        assert_eq!(resolve("not a url").await, None);
+
+        let target = resolve("http://localhost:8080/health").await.unwrap();
+        assert_eq!(target.host, "localhost");
+        assert!(!target.addrs.is_empty());
+        assert!(target.addrs.iter().all(|a| a.port() == 8080));
+    }
+}
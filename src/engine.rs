@@ -0,0 +1,149 @@
+//! Request engine selection (`ENGINE`).
+//!
+//! The default engine sends every request through reqwest, with the full
+//! feature set. `ENGINE=raw` selects the raw HTTP/1.1 engine
+//! ([`crate::raw_engine`], built with the `raw-engine` feature) for
+//! wrk-style throughput benchmarks: it writes pre-encoded requests, pipelined,
+//! over persistent plain-HTTP connections and parses only the status line and
+//! the headers that frame the body. Anything it cannot honour is rejected at
+//! startup by [`Engine::check`] rather than silently ignored.
+
+use thiserror::Error;
+
+use crate::config::Config;
+use crate::smoke::RunMode;
+
+/// Request methods the raw engine can send.
+pub const RAW_METHODS: [&str; 7] = ["GET", "POST", "PUT", "PATCH", "DELETE", "HEAD", "OPTIONS"];
+
+#[derive(Error, Debug, PartialEq)]
+pub enum EngineError {
+    #[error("ENGINE must be 'reqwest' or 'raw' (got '{0}')")]
+    Unknown(String),
+
+    #[error("ENGINE=raw needs a binary built with the raw-engine feature (cargo build --features raw-engine)")]
+    NotCompiled,
+
+    #[error("ENGINE=raw does not support: {}. Unset these or use the default engine", .0.join(", "))]
+    Unsupported(Vec<&'static str>),
+}
+
+/// Which engine sends the single-URL workers' requests.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Engine {
+    #[default]
+    Reqwest,
+    Raw,
+}
+
+impl Engine {
+    /// Reads `ENGINE`; unset means the default engine.
+    pub fn from_env() -> Result<Self, EngineError> {
+        match std::env::var("ENGINE") {
+            Ok(value) => Self::parse(&value),
+            Err(_) => Ok(Engine::Reqwest),
+        }
+    }
+
+    pub fn parse(value: &str) -> Result<Self, EngineError> {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "" | "reqwest" => Ok(Engine::Reqwest),
+            "raw" if cfg!(feature = "raw-engine") => Ok(Engine::Raw),
+            "raw" => Err(EngineError::NotCompiled),
+            other => Err(EngineError::Unknown(other.to_string())),
+        }
+    }
+
+    /// Fails when `config` uses features this engine lacks. `ephemeral` nodes
+    /// take their workers from `POST /config`, which the raw engine doesn't run.
+    pub fn check(&self, config: &Config, ephemeral: bool) -> Result<(), EngineError> {
+        if *self == Engine::Reqwest {
+            return Ok(());
+        }
+        let unsupported = raw_unsupported(config, ephemeral);
+        if unsupported.is_empty() {
+            Ok(())
+        } else {
+            Err(EngineError::Unsupported(unsupported))
+        }
+    }
+}
+
+/// Settings in `config` the raw engine cannot honour.
+fn raw_unsupported(config: &Config, ephemeral: bool) -> Vec<&'static str> {
+    let checks = [
+        (
+            !config.target_url.starts_with("http://"),
+            "non-http:// TARGET_URL (no TLS)",
+        ),
+        (
+            !RAW_METHODS.contains(&config.request_type.as_str()),
+            "this REQUEST_TYPE",
+        ),
+        (ephemeral, "EPHEMERAL (POST /config workers)"),
+        (config.mode == RunMode::Smoke, "MODE=smoke"),
+        (config.template_dir.is_some(), "TEMPLATE_DIR"),
+        (config.custom_headers.is_some(), "CUSTOM_HEADERS"),
+        (
+            config.client_cert_path.is_some() || config.client_key_path.is_some(),
+            "CLIENT_CERT_PATH/CLIENT_KEY_PATH",
+        ),
+        (config.resolve_target_addr.is_some(), "RESOLVE_TARGET_ADDR"),
+        (config.sni_override.is_some(), "TLS_SERVER_NAME"),
+        (config.dns_round_robin, "DNS_ROUND_ROBIN"),
+        (
+            !config.dns_refresh_interval.is_zero(),
+            "DNS_REFRESH_INTERVAL",
+        ),
+        (!config.local_addrs.is_empty(), "LOCAL_ADDRS"),
+        (config.chaos.enabled, "CHAOS_ENABLED"),
+        (config.debug_sample_rate > 0.0, "DEBUG_SAMPLE_RATE"),
+        (config.run_id_header, "RUN_ID_HEADER"),
+        (config.warmup_connections > 0, "WARMUP_CONNECTIONS"),
+    ];
+    checks
+        .into_iter()
+        .filter_map(|(used, name)| used.then_some(name))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_engine() {
+        assert_eq!(Engine::parse(""), Ok(Engine::Reqwest));
+        assert_eq!(Engine::parse("Reqwest"), Ok(Engine::Reqwest));
+        assert_eq!(
+            Engine::parse("curl"),
+            Err(EngineError::Unknown("curl".to_string()))
+        );
+        if cfg!(feature = "raw-engine") {
+            assert_eq!(Engine::parse("raw"), Ok(Engine::Raw));
+        } else {
+            assert_eq!(Engine::parse("raw"), Err(EngineError::NotCompiled));
+        }
+    }
+
+    #[test]
+    fn raw_engine_rejects_unsupported_settings() {
+        let mut config = Config::for_testing();
+        config.target_url = "http://localhost:8080/".to_string();
+        assert_eq!(Engine::Raw.check(&config, false), Ok(()));
+
+        config.target_url = "https://localhost:8443/".to_string();
+        config.custom_headers = Some("X-Key: 1".to_string());
+        let err = Engine::Raw.check(&config, true).unwrap_err();
+        assert_eq!(
+            err,
+            EngineError::Unsupported(vec![
+                "non-http:// TARGET_URL (no TLS)",
+                "EPHEMERAL (POST /config workers)",
+                "CUSTOM_HEADERS",
+            ])
+        );
+        assert!(err.to_string().contains("CUSTOM_HEADERS"));
+        assert_eq!(Engine::Reqwest.check(&config, true), Ok(()));
+    }
+}
@@ -24,6 +24,10 @@ pub enum ErrorCategory {
     /// TLS/SSL certificate errors
     TlsError,
 
+    /// Local resource exhaustion on the generator: out of file descriptors
+    /// (EMFILE/ENFILE) or ephemeral ports (EADDRINUSE/EADDRNOTAVAIL)
+    ResourceExhausted,
+
     /// Other/unknown errors
     OtherError,
 }
@@ -53,6 +57,12 @@ impl ErrorCategory {
     /// # Returns
     /// The appropriate error category
     pub fn from_reqwest_error(error: &reqwest::Error) -> Self {
+        // Checked first: these surface as connect errors, but the fix is on
+        // the generator host (ulimit, port range), not the target.
+        if is_resource_exhaustion(error) {
+            return ErrorCategory::ResourceExhausted;
+        }
+
         if error.is_timeout() {
             ErrorCategory::TimeoutError
         } else if error.is_connect() {
@@ -97,6 +107,7 @@ impl ErrorCategory {
             ErrorCategory::NetworkError => "network_error",
             ErrorCategory::TimeoutError => "timeout_error",
             ErrorCategory::TlsError => "tls_error",
+            ErrorCategory::ResourceExhausted => "resource_exhausted",
             ErrorCategory::OtherError => "other_error",
         }
     }
@@ -109,6 +120,9 @@ impl ErrorCategory {
             ErrorCategory::NetworkError => "Network/Connection Errors",
             ErrorCategory::TimeoutError => "Request Timeout Errors",
             ErrorCategory::TlsError => "TLS/SSL Certificate Errors",
+            ErrorCategory::ResourceExhausted => {
+                "Local Resource Exhaustion (file descriptors / ephemeral ports)"
+            }
             ErrorCategory::OtherError => "Other/Unknown Errors",
         }
     }
@@ -121,11 +135,53 @@ impl ErrorCategory {
             ErrorCategory::NetworkError,
             ErrorCategory::TimeoutError,
             ErrorCategory::TlsError,
+            ErrorCategory::ResourceExhausted,
             ErrorCategory::OtherError,
         ]
     }
 }
 
+/// OS error codes for file descriptor and ephemeral port exhaustion.
+#[cfg(target_os = "linux")]
+const EXHAUSTION_OS_ERRORS: &[i32] = &[
+    24, // EMFILE: per-process open file limit
+    23, // ENFILE: system-wide open file limit
+    98, // EADDRINUSE
+    99, // EADDRNOTAVAIL: no free local port for this destination
+];
+#[cfg(not(target_os = "linux"))]
+const EXHAUSTION_OS_ERRORS: &[i32] = &[
+    24, // EMFILE
+    23, // ENFILE
+    48, // EADDRINUSE
+    49, // EADDRNOTAVAIL
+];
+
+/// Returns true if `error` (or any error in its source chain) is a local
+/// file descriptor or ephemeral port exhaustion error.
+pub fn is_resource_exhaustion(error: &(dyn std::error::Error + 'static)) -> bool {
+    let mut current: Option<&(dyn std::error::Error + 'static)> = Some(error);
+    while let Some(err) = current {
+        if let Some(io_err) = err.downcast_ref::<std::io::Error>() {
+            if io_err
+                .raw_os_error()
+                .is_some_and(|code| EXHAUSTION_OS_ERRORS.contains(&code))
+            {
+                return true;
+            }
+        }
+        let msg = err.to_string().to_lowercase();
+        if msg.contains("too many open files")
+            || msg.contains("address already in use")
+            || msg.contains("cannot assign requested address")
+        {
+            return true;
+        }
+        current = err.source();
+    }
+    false
+}
+
 impl fmt::Display for ErrorCategory {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(f, "{}", self.description())
@@ -330,11 +386,36 @@ mod tests {
     #[test]
     fn test_all_categories() {
         let categories = ErrorCategory::all();
-        assert_eq!(categories.len(), 6);
+        assert_eq!(categories.len(), 7);
         assert!(categories.contains(&ErrorCategory::ClientError));
         assert!(categories.contains(&ErrorCategory::ServerError));
     }
 
+    #[test]
+    fn test_resource_exhaustion_detected_in_source_chain() {
+        #[derive(Debug)]
+        struct Wrapper(std::io::Error);
+        impl fmt::Display for Wrapper {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                write!(f, "error trying to connect")
+            }
+        }
+        impl std::error::Error for Wrapper {
+            fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+                Some(&self.0)
+            }
+        }
+
+        let emfile = Wrapper(std::io::Error::from_raw_os_error(24));
+        assert!(is_resource_exhaustion(&emfile));
+
+        let refused = Wrapper(std::io::Error::new(
+            std::io::ErrorKind::ConnectionRefused,
+            "connection refused",
+        ));
+        assert!(!is_resource_exhaustion(&refused));
+    }
+
     #[test]
     fn test_categorize_status_code_names() {
         assert_eq!(categorize_status_code(200), "OK");
@@ -4,17 +4,51 @@
 //! It handles sequential step execution, context management, variable substitution,
 //! and metrics tracking.
 
+use crate::apdex::GLOBAL_APDEX;
 use crate::assertions;
-use crate::connection_pool::GLOBAL_POOL_STATS;
+use crate::chaos;
+use crate::client_profiles::ClientProfile;
+use crate::concurrency_limit;
+use crate::connection_pool::{self, GLOBAL_POOL_STATS};
+use crate::debug_sample;
+use crate::dns_refresh;
+use crate::errors::is_resource_exhaustion;
 use crate::extractor;
+use crate::failure_signatures::GLOBAL_FAILURE_SIGNATURES;
+use crate::fd_limits;
+use crate::header_layers::{self, HeaderLayer};
+use crate::http_cache::{self, CacheResult, Validators};
+use crate::in_flight_limit;
+use crate::ip_family;
+use crate::latency_budget::{JourneyTiming, GLOBAL_LATENCY_BUDGET};
+use crate::memory_guard::is_percentile_tracking_active;
 use crate::metrics::{
-    CONCURRENT_SCENARIOS, SCENARIO_ASSERTIONS_TOTAL, SCENARIO_DURATION_SECONDS,
-    SCENARIO_EXECUTIONS_TOTAL, SCENARIO_STEPS_TOTAL, SCENARIO_STEP_DURATION_SECONDS,
-    SCENARIO_STEP_STATUS_CODES,
+    tag_label_values, APDEX_SCORE, ASSERTION_SAMPLING_TOTAL, CONCURRENT_SCENARIOS, METRIC_TAG_KEYS,
+    RESPONSE_BODIES_TRUNCATED_TOTAL, RETRY_AFTER_HONORED_TOTAL, SCENARIO_ASSERTIONS_TOTAL,
+    SCENARIO_DURATION_SECONDS, SCENARIO_EXECUTIONS_TOTAL, SCENARIO_RESPONSE_BYTES_TOTAL,
+    SCENARIO_STEPS_BY_CLIENT_TOTAL, SCENARIO_STEPS_BY_TAG_TOTAL, SCENARIO_STEPS_TOTAL,
+    SCENARIO_STEP_DURATION_BY_CLIENT_SECONDS, SCENARIO_STEP_DURATION_BY_TAG_SECONDS,
+    SCENARIO_STEP_DURATION_SECONDS, SCENARIO_STEP_PAGES, SCENARIO_STEP_STATUS_CODES,
+    SESSION_CACHE_HITS_TOTAL, SESSION_CACHE_MISSES_TOTAL, STEP_RETRIES_TOTAL,
 };
-use crate::scenario::{Scenario, ScenarioContext, Step};
+use crate::middleware::{MiddlewareChain, RequestContext, ResponseInfo};
+use crate::percentiles::GLOBAL_TAG_PERCENTILES;
+use crate::plugin::{self, ExecutorPlugin, PluginRequest};
+use crate::protobuf::{self, PROTOBUF_CONTENT_TYPE};
+use crate::rate_budget;
+use crate::rate_limit;
+use crate::redaction::GLOBAL_REDACTOR;
+use crate::rendezvous;
+use crate::response_body::{self, BodyNeeds};
+use crate::retry;
+use crate::run_id;
+use crate::scenario::{Assertion, Scenario, ScenarioContext, Step};
+use crate::shared_state::{self, StoreError};
+use crate::tcp_check::{self, TcpCheck};
 use rand::Rng;
+use std::borrow::Cow;
 use std::collections::HashMap;
+use std::sync::Arc;
 use std::time::Instant;
 use tokio::time::sleep;
 use tracing::{debug, error, info, warn};
@@ -25,14 +59,156 @@ pub struct SessionEntry {
     pub expires_at: Instant,
 }
 
-/// Per-worker session store: step name → cached result.
+/// What a session entry holds.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SessionSlot {
+    /// Variables extracted by a step with `cache:`.
+    StepCache,
+    /// `ETag` / `Last-Modified` of a conditional step's last response.
+    Validators,
+}
+
+/// Identity of a session entry: which virtual user, scenario and step it
+/// belongs to, plus the step's rendered `cache.key` (or, for validators, the
+/// request URL). Two entries share a key only when all of these match, so
+/// identical steps run by different virtual users or in different scenarios
+/// never see each other's entries, however the stores are pooled.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct SessionKey {
+    /// The owning virtual user; `None` for `cache.shared` entries, which
+    /// every virtual user reads.
+    pub vu: Option<usize>,
+    pub scenario: String,
+    pub step: String,
+    pub key: Option<String>,
+    pub slot: SessionSlot,
+}
+
+impl SessionKey {
+    /// Key of a step's cached variables; `key` is the rendered `cache.key`.
+    pub fn step_cache(vu: Option<usize>, scenario: &str, step: &str, key: Option<String>) -> Self {
+        Self {
+            vu,
+            scenario: scenario.to_string(),
+            step: step.to_string(),
+            key,
+            slot: SessionSlot::StepCache,
+        }
+    }
+}
+
+/// A virtual user's session store.
 ///
 /// Lives for the lifetime of the worker (outside the scenario iteration loop)
 /// so extracted variables survive across iterations until their TTL expires.
-pub type SessionStore = HashMap<String, SessionEntry>;
+/// Keys carry the store's virtual user, so entries stay apart even if stores
+/// are merged or shared.
+#[derive(Default)]
+pub struct SessionStore {
+    vu: usize,
+    entries: HashMap<SessionKey, SessionEntry>,
+}
+
+impl SessionStore {
+    /// A store for virtual user 0, e.g. for a single smoke run.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// A store for virtual user `vu`.
+    pub fn for_vu(vu: usize) -> Self {
+        Self {
+            vu,
+            entries: HashMap::new(),
+        }
+    }
+
+    pub fn vu(&self) -> usize {
+        self.vu
+    }
+
+    pub fn get(&self, key: &SessionKey) -> Option<&SessionEntry> {
+        self.entries.get(key)
+    }
+
+    pub fn insert(&mut self, key: SessionKey, entry: SessionEntry) {
+        debug_assert_eq!(key.vu, Some(self.vu), "session key of another virtual user");
+        self.entries.insert(key, entry);
+    }
+
+    pub fn remove(&mut self, key: &SessionKey) -> Option<SessionEntry> {
+        self.entries.remove(key)
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+}
+
+/// Entry for a step with `cache.shared`, guarded by an async lock so only one
+/// worker refreshes it at a time.
+type SharedEntry = Arc<tokio::sync::Mutex<Option<SessionEntry>>>;
+
+lazy_static::lazy_static! {
+    /// Process-wide cache for steps with `cache.shared`, shared by all workers.
+    static ref SHARED_SESSION: std::sync::Mutex<HashMap<SessionKey, SharedEntry>> =
+        std::sync::Mutex::new(HashMap::new());
+}
+
+fn shared_entry(key: &SessionKey) -> SharedEntry {
+    debug_assert_eq!(key.vu, None, "shared session key with a virtual user");
+    SHARED_SESSION
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .entry(key.clone())
+        .or_default()
+        .clone()
+}
+
+/// Drops every shared cache entry, e.g. when a new test starts.
+pub fn clear_shared_session() {
+    SHARED_SESSION
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .clear();
+}
+
+/// Applies the step's shared store reads (`reads`) or writes, binding each
+/// value read to its variable.
+async fn apply_shared(
+    step: &Step,
+    context: &mut ScenarioContext,
+    reads: bool,
+) -> Result<(), StoreError> {
+    for action in step.shared.iter().filter(|a| a.op.is_read() == reads) {
+        let op = action.op.map_strings(|s| context.substitute_variables(s));
+        if let Some(value) = shared_state::apply(&op).await? {
+            context.set_variable(action.variable().to_string(), value);
+        }
+    }
+    Ok(())
+}
+
+/// Sets the next row of the data source plugin `name` as variables.
+fn load_data_row(name: &str, context: &mut ScenarioContext) -> Result<(), String> {
+    let source = plugin::global_data_source(name)
+        .ok_or_else(|| format!("data source plugin '{}' is not registered", name))?;
+    let row = source
+        .next_row()
+        .map_err(|e| e.to_string())?
+        .ok_or_else(|| format!("data source plugin '{}' is exhausted", name))?;
+    for (variable, value) in row {
+        context.set_variable(variable, value);
+    }
+    Ok(())
+}
 
 /// Result of executing a single step.
-#[derive(Debug)]
+#[derive(Debug, Default)]
 pub struct StepResult {
     /// Name of the step that was executed
     pub step_name: String,
@@ -57,6 +233,51 @@ pub struct StepResult {
 
     /// True when the step result was served from the session cache (no HTTP request made).
     pub cache_hit: bool,
+
+    /// Next page URL or cursor, for steps with `paginate` whose response had one.
+    pub next_page: Option<String>,
+
+    /// Description of each failed assertion (expected vs. actual).
+    pub assertion_failures: Vec<String>,
+
+    /// Delay requested by a 429/503 response's `Retry-After` header.
+    pub retry_after: Option<std::time::Duration>,
+
+    /// Failed attempts retried (per the scenario's `retry`) before this one.
+    pub retries: u32,
+}
+
+impl StepResult {
+    /// A failed step that got no response.
+    pub fn failed(step: &Step, err: impl std::fmt::Display) -> Self {
+        Self {
+            step_name: step.name.clone(),
+            error: Some(err.to_string()),
+            ..Self::default()
+        }
+    }
+}
+
+/// Adds a failed attempt to the failure triage report.
+fn record_failure_signature(scenario: &str, step: &StepResult) {
+    let message = step
+        .assertion_failures
+        .first()
+        .or(step.error.as_ref())
+        .map_or("step failed", String::as_str);
+    GLOBAL_FAILURE_SIGNATURES.record(scenario, &step.step_name, step.status_code, message);
+}
+
+/// Describes each failed assertion for [`StepResult::assertion_failures`].
+fn assertion_failure_messages(results: &[assertions::AssertionResult]) -> Vec<String> {
+    results
+        .iter()
+        .filter(|r| !r.passed)
+        .map(|r| match &r.error_message {
+            Some(message) => message.clone(),
+            None => format!("expected {}, got {}", r.expected, r.actual),
+        })
+        .collect()
 }
 
 /// Result of executing an entire scenario.
@@ -114,6 +335,16 @@ pub struct ScenarioExecutor {
 
     /// Run identifier attached to all metrics (Issue #106).
     run_id: String,
+
+    /// Request transformers / response observers applied to every HTTP step.
+    middleware: MiddlewareChain,
+
+    /// Headers sent with every step, below scenario, step and auth headers.
+    global_headers: Arc<HeaderLayer>,
+
+    /// The virtual user's client profile: headers above the global layer,
+    /// a think-time multiplier and the `client_profile` metric label.
+    client_profile: Option<Arc<ClientProfile>>,
 }
 
 impl ScenarioExecutor {
@@ -131,9 +362,42 @@ impl ScenarioExecutor {
             client,
             node_id,
             run_id,
+            middleware: plugin::global_middleware(),
+            global_headers: header_layers::global_headers(),
+            client_profile: None,
         }
     }
 
+    /// Replaces the middleware chain (by default, the middleware registered
+    /// on the global plugin registry when the executor was created).
+    pub fn with_middleware(mut self, middleware: MiddlewareChain) -> Self {
+        self.middleware = middleware;
+        self
+    }
+
+    /// Replaces the global header layer (by default, the one set with
+    /// [`header_layers::set_global_headers`] when the executor was created).
+    pub fn with_global_headers(mut self, headers: HeaderLayer) -> Self {
+        self.global_headers = Arc::new(headers);
+        self
+    }
+
+    /// Runs steps as the given client profile (see [`crate::client_profiles`]).
+    pub fn with_client_profile(mut self, profile: Option<Arc<ClientProfile>>) -> Self {
+        self.client_profile = profile;
+        self
+    }
+
+    /// The client profile's header layer, empty without a profile.
+    fn client_headers(&self) -> &HeaderLayer {
+        static NO_HEADERS: HeaderLayer = HeaderLayer {
+            headers: Vec::new(),
+        };
+        self.client_profile
+            .as_ref()
+            .map_or(&NO_HEADERS, |profile| &profile.headers)
+    }
+
     /// Execute a scenario with the given context.
     ///
     /// Steps are executed sequentially. If any step fails, execution stops
@@ -151,14 +415,54 @@ impl ScenarioExecutor {
         context: &mut ScenarioContext,
         session: &mut SessionStore,
     ) -> ScenarioResult {
+        // Time spent waiting for a maxConcurrent slot is not scenario time.
+        let _scenario_permit = match scenario.max_concurrent {
+            Some(limit) => Some(
+                concurrency_limit::acquire(
+                    &scenario.name,
+                    None,
+                    limit,
+                    &self.node_id,
+                    &self.run_id,
+                )
+                .await,
+            ),
+            None => None,
+        };
+
+        // dataSource: without a row the iteration has nothing to run with.
+        if let Some(source) = &scenario.data_source {
+            if let Err(e) = load_data_row(source, context) {
+                error!(scenario = %scenario.name, error = %e, "Scenario has no data row");
+                SCENARIO_EXECUTIONS_TOTAL
+                    .with_label_values(&[&scenario.name, "failed", &self.node_id, &self.run_id])
+                    .inc();
+                return ScenarioResult {
+                    scenario_name: scenario.name.clone(),
+                    success: false,
+                    steps: Vec::new(),
+                    total_time_ms: 0,
+                    steps_completed: 0,
+                    failed_at_step: Some(0),
+                };
+            }
+        }
+
         let scenario_start = Instant::now();
         let mut step_results = Vec::new();
         let mut all_success = true;
         let mut failed_at_step = None;
+        let mut think_ms = 0;
 
         // Track concurrent scenario execution
         CONCURRENT_SCENARIOS.inc();
 
+        // assertionSampleRate: decide once per iteration whether assertions run
+        context.set_validating(
+            scenario.assertion_sample_rate >= 1.0
+                || crate::seed::with_rng(|rng| rng.gen::<f64>()) < scenario.assertion_sample_rate,
+        );
+
         info!(
             scenario = %scenario.name,
             steps = scenario.steps.len(),
@@ -173,9 +477,62 @@ impl ScenarioExecutor {
                 "Executing step"
             );
 
-            let step_result = self
-                .execute_step(&scenario.name, step, context, session)
-                .await;
+            let mut step_result = match apply_shared(step, context, true).await {
+                Ok(()) => {
+                    // Waiting at a rendezvous happens before the step's timer starts.
+                    if let Some(point) = &step.rendezvous {
+                        rendezvous::wait(point, &self.node_id, &self.run_id).await;
+                    }
+                    self.execute_step_with_retries(scenario, step, context, session)
+                        .await
+                }
+                // Shared store reads failed; no request is sent.
+                Err(e) => StepResult::failed(step, e),
+            };
+
+            if !METRIC_TAG_KEYS.is_empty() {
+                self.record_tagged_step(scenario, step, &step_result);
+            }
+            self.record_apdex(scenario, step, &step_result);
+            self.record_client_step(scenario, step, &step_result);
+
+            // Follow next-page links; each page is recorded as its own step result
+            if let Some(paginate) = &step.paginate {
+                let mut pages = 1;
+                while step_result.success && pages < paginate.max_pages {
+                    let Some(next) = step_result.next_page.take() else {
+                        break;
+                    };
+                    debug!(
+                        scenario = %scenario.name,
+                        step = %step.name,
+                        page = pages + 1,
+                        next = %next,
+                        "Fetching next page"
+                    );
+                    let page_step = paginate.page_step(step, &next);
+                    step_results.push(step_result);
+                    step_result = self
+                        .execute_step(scenario, &page_step, context, session)
+                        .await;
+                    if !METRIC_TAG_KEYS.is_empty() {
+                        self.record_tagged_step(scenario, step, &step_result);
+                    }
+                    self.record_apdex(scenario, step, &step_result);
+                    self.record_client_step(scenario, step, &step_result);
+                    pages += 1;
+                }
+                SCENARIO_STEP_PAGES
+                    .with_label_values(&[&scenario.name, &step.name, &self.node_id, &self.run_id])
+                    .observe(pages as f64);
+            }
+
+            if step_result.success {
+                if let Err(e) = apply_shared(step, context, false).await {
+                    step_result.success = false;
+                    step_result.error = Some(e.to_string());
+                }
+            }
 
             let success = step_result.success;
             step_results.push(step_result);
@@ -196,7 +553,10 @@ impl ScenarioExecutor {
 
             // Apply think time if configured (simulates user delay between actions)
             if let Some(ref think_time) = step.think_time {
-                let delay = think_time.calculate_delay();
+                let mut delay = think_time.calculate_delay();
+                if let Some(profile) = &self.client_profile {
+                    delay = delay.mul_f64(profile.think_time_multiplier);
+                }
                 debug!(
                     scenario = %scenario.name,
                     step = %step.name,
@@ -204,6 +564,7 @@ impl ScenarioExecutor {
                     think_time_type = ?think_time,
                     "Applying think time"
                 );
+                think_ms += delay.as_millis() as u64;
                 sleep(delay).await;
             }
         }
@@ -220,6 +581,26 @@ impl ScenarioExecutor {
             failed_at_step,
         };
 
+        // Step timings for the latency budget report
+        if is_percentile_tracking_active() {
+            GLOBAL_LATENCY_BUDGET.record(&JourneyTiming {
+                scenario: &scenario.name,
+                total_ms: total_time_ms,
+                think_ms,
+                steps: result
+                    .steps
+                    .iter()
+                    .map(|step| (step.step_name.as_str(), step.response_time_ms))
+                    .collect(),
+            });
+        }
+
+        // Failed steps for the failure triage report; retried attempts are
+        // recorded as they fail.
+        for step in result.steps.iter().filter(|step| !step.success) {
+            record_failure_signature(&scenario.name, step);
+        }
+
         // Record scenario metrics
         CONCURRENT_SCENARIOS.dec();
         SCENARIO_DURATION_SECONDS
@@ -251,44 +632,276 @@ impl ScenarioExecutor {
         result
     }
 
+    /// Execute a step, retrying per the scenario's retry policy. Only the
+    /// last attempt is returned, with the number of retries before it.
+    async fn execute_step_with_retries(
+        &self,
+        scenario: &Scenario,
+        step: &Step,
+        context: &mut ScenarioContext,
+        session: &mut SessionStore,
+    ) -> StepResult {
+        // One key per execution of the step, resent by its retries.
+        if step.idempotency_key.is_some() {
+            context.set_variable(
+                retry::IDEMPOTENCY_KEY_VARIABLE.to_string(),
+                shared_state::uuid_v4(),
+            );
+        }
+        let mut result = self.execute_step(scenario, step, context, session).await;
+        let Some(policy) = &scenario.retry else {
+            return result;
+        };
+        for attempt in 1..=policy.count {
+            if !retry::is_retryable(&result) || rate_budget::is_exhausted() {
+                break;
+            }
+            let labels: [&str; 4] = [&scenario.name, &step.name, &self.node_id, &self.run_id];
+            let (delay, honored) = retry::backoff(policy, &result);
+            if honored {
+                RETRY_AFTER_HONORED_TOTAL.with_label_values(&labels).inc();
+            }
+            STEP_RETRIES_TOTAL.with_label_values(&labels).inc();
+            debug!(
+                scenario = %scenario.name,
+                step = %step.name,
+                attempt,
+                status = ?result.status_code,
+                delay_ms = delay.as_millis() as u64,
+                retry_after = honored,
+                "Retrying step"
+            );
+            if !METRIC_TAG_KEYS.is_empty() {
+                self.record_tagged_step(scenario, step, &result);
+            }
+            self.record_apdex(scenario, step, &result);
+            self.record_client_step(scenario, step, &result);
+            record_failure_signature(&scenario.name, &result);
+            sleep(delay).await;
+            result = self.execute_step(scenario, step, context, session).await;
+            result.retries = attempt;
+        }
+        result
+    }
+
+    /// Classify the step's response against its `slo` and update Apdex scores.
+    fn record_apdex(&self, scenario: &Scenario, step: &Step, result: &StepResult) {
+        let Some(slo) = step.slo.as_ref().filter(|_| !result.cache_hit) else {
+            return;
+        };
+        let (step_score, scenario_score) = GLOBAL_APDEX.record(
+            &scenario.name,
+            &step.name,
+            slo,
+            result.response_time_ms,
+            result.success,
+        );
+        APDEX_SCORE
+            .with_label_values(&[&scenario.name, &step.name, &self.node_id, &self.run_id])
+            .set(step_score);
+        APDEX_SCORE
+            .with_label_values(&[&scenario.name, "", &self.node_id, &self.run_id])
+            .set(scenario_score);
+    }
+
+    /// Record step metrics labeled with the virtual user's client profile.
+    fn record_client_step(&self, scenario: &Scenario, step: &Step, result: &StepResult) {
+        let Some(profile) = &self.client_profile else {
+            return;
+        };
+        let status = if result.success { "success" } else { "failed" };
+        SCENARIO_STEPS_BY_CLIENT_TOTAL
+            .with_label_values(&[
+                &scenario.name,
+                &step.name,
+                &profile.name,
+                status,
+                &self.node_id,
+                &self.run_id,
+            ])
+            .inc();
+        if !result.cache_hit {
+            SCENARIO_STEP_DURATION_BY_CLIENT_SECONDS
+                .with_label_values(&[
+                    &scenario.name,
+                    &step.name,
+                    &profile.name,
+                    &self.node_id,
+                    &self.run_id,
+                ])
+                .observe(result.response_time_ms as f64 / 1000.0);
+        }
+    }
+
+    /// Record step metrics labeled with the tags selected by `METRIC_TAG_KEYS`.
+    fn record_tagged_step(&self, scenario: &Scenario, step: &Step, result: &StepResult) {
+        let tags = scenario.step_tags(step);
+        let tag_values = tag_label_values(&tags);
+        let status = if result.success { "success" } else { "failed" };
+
+        let mut labels = vec![
+            scenario.name.as_str(),
+            step.name.as_str(),
+            status,
+            self.node_id.as_str(),
+            self.run_id.as_str(),
+        ];
+        labels.extend(&tag_values);
+        SCENARIO_STEPS_BY_TAG_TOTAL.with_label_values(&labels).inc();
+
+        if result.cache_hit {
+            return;
+        }
+        let mut labels = vec![
+            scenario.name.as_str(),
+            step.name.as_str(),
+            self.node_id.as_str(),
+            self.run_id.as_str(),
+        ];
+        labels.extend(&tag_values);
+        SCENARIO_STEP_DURATION_BY_TAG_SECONDS
+            .with_label_values(&labels)
+            .observe(result.response_time_ms as f64 / 1000.0);
+
+        if is_percentile_tracking_active() {
+            for key in METRIC_TAG_KEYS.iter() {
+                if let Some(value) = tags.get(key) {
+                    GLOBAL_TAG_PERCENTILES
+                        .record(&format!("{}={}", key, value), result.response_time_ms);
+                }
+            }
+        }
+    }
+
+    /// Count whether this response's assertions ran or were sampled out.
+    fn record_assertion_sampling(&self, scenario_name: &str, step: &Step, validate: bool) {
+        if step.assertions.is_empty() {
+            return;
+        }
+        let sampled = if validate { "true" } else { "false" };
+        ASSERTION_SAMPLING_TOTAL
+            .with_label_values(&[
+                scenario_name,
+                &step.name,
+                sampled,
+                &self.node_id,
+                &self.run_id,
+            ])
+            .inc();
+    }
+
     /// Execute a single step.
     async fn execute_step(
         &self,
-        scenario_name: &str,
+        scenario: &Scenario,
         step: &Step,
         context: &mut ScenarioContext,
         session: &mut SessionStore,
     ) -> StepResult {
+        let scenario_name = scenario.name.as_str();
+        let network = scenario.network_profile.as_ref();
         // ── Session cache check ────────────────────────────────────────────
-        if step.cache.is_some() {
-            if let Some(entry) = session.get(&step.name) {
-                if entry.expires_at > Instant::now() {
-                    for (name, value) in &entry.variables {
-                        context.set_variable(name.clone(), value.clone());
+        let cache_key = step.cache.as_ref().map(|cache| {
+            SessionKey::step_cache(
+                (!cache.shared).then(|| session.vu()),
+                scenario_name,
+                &step.name,
+                cache.render_key(context),
+            )
+        });
+        let mut shared_guard = None;
+        if let (Some(cache), Some(key)) = (&step.cache, &cache_key) {
+            let labels: [&str; 4] = [scenario_name, &step.name, &self.node_id, &self.run_id];
+            let now = Instant::now();
+            let cached = if cache.shared {
+                // Single flight: hold the entry's lock until this step has
+                // refreshed it, so other workers wait for its result instead
+                // of all sending the request when the entry expires.
+                let guard = shared_entry(key).lock_owned().await;
+                let fresh = guard
+                    .as_ref()
+                    .filter(|entry| entry.expires_at > now)
+                    .map(|entry| entry.variables.clone());
+                if fresh.is_none() {
+                    shared_guard = Some(guard);
+                }
+                fresh
+            } else {
+                match session.get(key) {
+                    Some(entry) if entry.expires_at > now => Some(entry.variables.clone()),
+                    Some(_) => {
+                        // Entry expired — evict it so we make a fresh request
+                        session.remove(key);
+                        None
                     }
-                    debug!(step = %step.name, "Session cache hit — skipping HTTP request");
-                    return StepResult {
-                        step_name: step.name.clone(),
-                        success: true,
-                        status_code: None,
-                        response_time_ms: 0,
-                        error: None,
-                        assertions_passed: 0,
-                        assertions_failed: 0,
-                        cache_hit: true,
-                    };
+                    None => None,
                 }
-                // Entry expired — evict it so we make a fresh request
-                session.remove(&step.name);
+            };
+            if let Some(variables) = cached {
+                for (name, value) in variables {
+                    context.set_variable(name, value);
+                }
+                SESSION_CACHE_HITS_TOTAL.with_label_values(&labels).inc();
+                debug!(step = %step.name, "Session cache hit — skipping HTTP request");
+                return StepResult {
+                    step_name: step.name.clone(),
+                    success: true,
+                    cache_hit: true,
+                    ..Default::default()
+                };
             }
+            SESSION_CACHE_MISSES_TOTAL.with_label_values(&labels).inc();
         }
 
+        // Held until the step returns; waiting happens before the timer starts.
+        let _step_permit = match step.max_concurrent {
+            Some(limit) => Some(
+                concurrency_limit::acquire(
+                    scenario_name,
+                    Some(&step.name),
+                    limit,
+                    &self.node_id,
+                    &self.run_id,
+                )
+                .await,
+            ),
+            None => None,
+        };
+
+        // Emulated client latency and upload time are spent before the timer
+        // starts, so response times keep measuring the target.
+        if let Some(profile) = network {
+            let body_len = match (&step.request.body, step.request.body_size) {
+                (Some(body), _) => body.len(),
+                (None, Some(size)) => size,
+                (None, None) => 0,
+            };
+            profile.delay_request(body_len).await;
+        }
+
+        // Hard caps (MAX_TOTAL_REQUESTS / HARD_MAX_RPS) override the load model.
+        // The budget comes before the slot so none is held while it waits.
+        if !rate_budget::acquire().await {
+            return StepResult::failed(step, "MAX_TOTAL_REQUESTS reached");
+        }
+
+        // Held until the response is handled (MAX_IN_FLIGHT); like the
+        // permits, waiting for a slot isn't response time.
+        let _in_flight_slot = in_flight_limit::acquire().await;
+
         let step_start = Instant::now();
 
+        if let Some(check) = &step.tcp_check {
+            return self
+                .execute_tcp_check_step(scenario, step, check, context, step_start)
+                .await;
+        }
+
         // Build the full URL with variable substitution
-        let path = context.substitute_variables(&step.request.path);
+        let templates = step.templates();
+        let path = templates.path.render(context);
         let url = if path.starts_with("http://") || path.starts_with("https://") {
-            path
+            path.into_owned()
         } else {
             let base = self.base_url.trim_end_matches('/');
             let p = path.trim_start_matches('/');
@@ -312,48 +925,143 @@ impl ScenarioExecutor {
             "HEAD" => self.client.head(&url),
             "OPTIONS" => self.client.request(reqwest::Method::OPTIONS, &url),
             method => {
+                if let Some(plugin) = plugin::global_executor(method) {
+                    return self
+                        .execute_plugin_step(scenario, step, context, &url, plugin, step_start)
+                        .await;
+                }
                 error!(step = %step.name, method = %method, "Unsupported HTTP method");
-                return StepResult {
-                    step_name: step.name.clone(),
-                    success: false,
-                    status_code: None,
-                    response_time_ms: 0,
-                    error: Some(format!("Unsupported HTTP method: {}", method)),
-                    assertions_passed: 0,
-                    assertions_failed: 0,
-                    cache_hit: false,
-                };
+                return StepResult::failed(step, format!("Unsupported HTTP method: {}", method));
             }
         };
 
-        // Add headers with variable substitution
-        for (key, value) in &step.request.headers {
-            let substituted_value = context.substitute_variables(value);
-            request_builder = request_builder.header(key, substituted_value);
+        // Add global, client, scenario, step and auth headers, highest layer winning
+        for (key, value) in header_layers::resolve(
+            &self.global_headers,
+            self.client_headers(),
+            &scenario.headers,
+            &templates.headers,
+            context,
+        ) {
+            request_builder = request_builder.header(key, value.as_ref());
+        }
+        if let Some(host) = &templates.host {
+            request_builder =
+                request_builder.header(reqwest::header::HOST, host.render(context).as_ref());
+        }
+        request_builder = dns_refresh::apply(run_id::apply(request_builder, &self.run_id));
+        if let Some(header) = &step.idempotency_key {
+            if let Some(key) = context.get_variable(retry::IDEMPOTENCY_KEY_VARIABLE) {
+                request_builder = request_builder.header(header.as_str(), key.as_str());
+            }
+        }
+
+        // Revalidate with the validators of this step's last response
+        let validators = step
+            .conditional
+            .then(|| Validators::load(session, scenario_name, &step.name, &url))
+            .flatten();
+        if let Some(validators) = &validators {
+            request_builder = validators.apply(request_builder);
         }
 
         // Add body: inline string (with variable substitution) or synthetic generated body
-        if let Some(body) = &step.request.body {
-            let substituted_body = context.substitute_variables(body);
-            request_builder = request_builder.body(substituted_body);
+        let proto = step.request.protobuf.as_ref();
+        if let Some(body) = &templates.body {
+            let substituted_body = body.render(context);
+            match proto.filter(|pb| pb.request_message.is_some()) {
+                Some(pb) => match protobuf::encode_json(pb, &substituted_body) {
+                    Ok(encoded) => {
+                        if !step
+                            .request
+                            .headers
+                            .keys()
+                            .any(|k| k.eq_ignore_ascii_case("content-type"))
+                        {
+                            request_builder = request_builder
+                                .header(reqwest::header::CONTENT_TYPE, PROTOBUF_CONTENT_TYPE);
+                        }
+                        request_builder = request_builder.body(encoded);
+                    }
+                    Err(e) => {
+                        error!(step = %step.name, error = %e, "Failed to encode protobuf body");
+                        return StepResult::failed(step, e);
+                    }
+                },
+                None => request_builder = request_builder.body(substituted_body.into_owned()),
+            }
         } else if let Some(size) = step.request.body_size {
-            let synthetic: Vec<u8> = rand::thread_rng()
-                .sample_iter(&rand::distributions::Alphanumeric)
-                .take(size)
-                .collect();
+            let synthetic: Vec<u8> = crate::seed::with_rng(|rng| {
+                (0..size)
+                    .map(|_| rng.sample(rand::distributions::Alphanumeric))
+                    .collect()
+            });
             request_builder = request_builder.body(synthetic);
         }
 
-        // Execute the request
-        let response_result = request_builder.send().await;
+        // Chaos fault injection (CHAOS_*)
+        let faults = chaos::next_request_faults();
+        if faults.drop {
+            debug!(step = %step.name, "Chaos: request dropped before sending");
+            return StepResult::failed(step, "Chaos: request dropped before sending");
+        }
+        request_builder = faults.apply(request_builder);
+
+        // Execute the request. It is built first when middleware must see it
+        // or it is debug-sampled, so the final request can be logged.
+        let middleware_ctx = RequestContext {
+            scenario: scenario_name,
+            step: &step.name,
+        };
+        let _in_flight = connection_pool::track_in_flight();
+        let sampled = debug_sample::should_sample();
+        let response_result = if sampled || !self.middleware.is_empty() {
+            match request_builder.build() {
+                Ok(mut request) => {
+                    if let Err(e) = self.middleware.apply(&mut request, &middleware_ctx) {
+                        error!(step = %step.name, error = %e, "Middleware rejected request");
+                        return StepResult::failed(step, e);
+                    }
+                    if sampled {
+                        debug_sample::log_request(
+                            &format!("{}/{}", scenario_name, step.name),
+                            &request,
+                        );
+                    }
+                    self.client.execute(request).await
+                }
+                Err(e) => Err(e),
+            }
+        } else {
+            request_builder.send().await
+        };
 
         let response_time_ms = step_start.elapsed().as_millis() as u64;
         GLOBAL_POOL_STATS.record_request(response_time_ms);
 
+        if !self.middleware.is_empty() {
+            let empty = reqwest::header::HeaderMap::new();
+            let error = response_result.as_ref().err().map(|e| e.to_string());
+            self.middleware.observe(
+                &ResponseInfo {
+                    status: response_result.as_ref().ok().map(|r| r.status().as_u16()),
+                    headers: response_result.as_ref().map_or(&empty, |r| r.headers()),
+                    response_time_ms,
+                    error: error.as_deref(),
+                },
+                &middleware_ctx,
+            );
+        }
+
         match response_result {
             Ok(response) => {
                 let status = response.status();
                 let headers = response.headers().clone();
+                ip_family::record(
+                    response.remote_addr().map(|addr| addr.ip()),
+                    &self.node_id,
+                    &self.run_id,
+                );
 
                 debug!(
                     step = %step.name,
@@ -362,11 +1070,111 @@ impl ScenarioExecutor {
                     "Received response"
                 );
 
+                rate_limit::observe(
+                    &format!("{}/{}", scenario_name, step.name),
+                    status.as_u16(),
+                    &headers,
+                    &self.node_id,
+                    &self.run_id,
+                );
+
+                // Classify cache behaviour and keep fresh validators
+                let tracks_cache = step.conditional
+                    || step
+                        .assertions
+                        .iter()
+                        .any(|a| matches!(a, Assertion::CacheHitRatio { .. }));
+                let cache_ratio = tracks_cache.then(|| {
+                    let result = CacheResult::classify(status.as_u16(), &headers);
+                    if step.conditional
+                        && (status.is_success() || result == CacheResult::NotModified)
+                    {
+                        if let Some(fresh) = Validators::from_headers(&headers) {
+                            fresh.store(session, scenario_name, &step.name, &url);
+                        }
+                    }
+                    http_cache::record(
+                        scenario_name,
+                        &step.name,
+                        result,
+                        &self.node_id,
+                        &self.run_id,
+                    )
+                });
+
                 // Get response body for extraction and assertions
-                let body_result = response.text().await;
+                let mut body_digest = None;
+                let body_result = if faults.abort_response {
+                    // Chaos: drop the response after the first chunk, closing the connection
+                    let mut response = response;
+                    let _ = response.chunk().await;
+                    Err("Chaos: connection aborted mid-response".to_string())
+                } else if let Some(pb) = proto.filter(|pb| pb.response_message.is_some()) {
+                    // Decode protobuf responses to JSON for extraction and assertions
+                    match network {
+                        Some(profile) => profile.read_bytes(response).await,
+                        None => response.bytes().await.map(|b| b.to_vec()),
+                    }
+                    .map_err(|e| e.to_string())
+                    .and_then(|bytes| {
+                        protobuf::decode_to_json(pb, &bytes).map_err(|e| e.to_string())
+                    })
+                } else {
+                    // Stream the body; it is only kept as text if something
+                    // reads it, and only up to MAX_BODY_BUFFER.
+                    let validate = context.is_validating();
+                    let needs = BodyNeeds {
+                        text: sampled
+                            || !step.extractions.is_empty()
+                            || step.paginate.is_some()
+                            || (validate && step.assertions.iter().any(Assertion::needs_body_text)),
+                        digest: validate && step.assertions.iter().any(Assertion::needs_digest),
+                    };
+                    response_body::read(response, network, needs)
+                        .await
+                        .map(|read| {
+                            let labels: [&str; 4] =
+                                [scenario_name, &step.name, &self.node_id, &self.run_id];
+                            SCENARIO_RESPONSE_BYTES_TOTAL
+                                .with_label_values(&labels)
+                                .inc_by(read.size);
+                            if read.truncated {
+                                RESPONSE_BODIES_TRUNCATED_TOTAL
+                                    .with_label_values(&labels)
+                                    .inc();
+                                warn!(
+                                    step = %step.name,
+                                    size = read.size,
+                                    limit = response_body::max_body_buffer(),
+                                    "Response body truncated at MAX_BODY_BUFFER"
+                                );
+                            }
+                            body_digest = read.digest;
+                            read.text
+                        })
+                        .map_err(|e| e.to_string())
+                };
 
+                let mut next_page = None;
                 let body_result_data = match body_result {
                     Ok(body) => {
+                        if let Some(paginate) = &step.paginate {
+                            next_page = extractor::extract_json_path(&body, &paginate.next_field)
+                                .ok()
+                                .filter(|next| !next.is_empty() && next != "null");
+                        }
+
+                        if sampled {
+                            debug_sample::log_response(
+                                &format!("{}/{}", scenario_name, step.name),
+                                status.as_u16(),
+                                &headers,
+                                body.as_bytes(),
+                                body.len(),
+                                response_time_ms,
+                            );
+                        }
+
                         // Extract variables from response (#27 - IMPLEMENTED)
                         let extracted_count = if !step.extractions.is_empty() {
                             debug!(
@@ -375,8 +1183,12 @@ impl ScenarioExecutor {
                                 "Extracting variables from response"
                             );
 
-                            let extracted =
-                                extractor::extract_variables(&step.extractions, &body, &headers);
+                            let extracted = extractor::extract_variables_with_patterns(
+                                &step.extractions,
+                                step.patterns(),
+                                &body,
+                                &headers,
+                            );
 
                             let count = extracted.len();
 
@@ -393,27 +1205,36 @@ impl ScenarioExecutor {
                                 debug!(
                                     step = %step.name,
                                     variable = %name,
-                                    value = %value,
+                                    value = %GLOBAL_REDACTOR.value(name, value),
                                     "Stored extracted variable"
                                 );
-                                context.set_variable(name.clone(), value.clone());
+                                context.set_scoped_variable(
+                                    name.clone(),
+                                    value.clone(),
+                                    step.variable_scope(name),
+                                );
                             }
 
                             // Cache the extracted variables for future iterations
-                            if let (Some(cache_cfg), Some(vars)) = (&step.cache, for_session) {
+                            if let (Some(cache_cfg), Some(key), Some(vars)) =
+                                (&step.cache, &cache_key, for_session)
+                            {
                                 let expires_at = Instant::now() + cache_cfg.ttl;
                                 debug!(
                                     step = %step.name,
                                     ttl_secs = cache_cfg.ttl.as_secs(),
                                     "Caching step result in session store"
                                 );
-                                session.insert(
-                                    step.name.clone(),
-                                    SessionEntry {
-                                        variables: vars,
-                                        expires_at,
-                                    },
-                                );
+                                let entry = SessionEntry {
+                                    variables: vars,
+                                    expires_at,
+                                };
+                                match shared_guard.as_mut() {
+                                    Some(guard) => **guard = Some(entry),
+                                    None => {
+                                        session.insert(key.clone(), entry);
+                                    }
+                                }
                             }
 
                             count
@@ -422,7 +1243,10 @@ impl ScenarioExecutor {
                         };
 
                         // Run assertions on response (#30 - IMPLEMENTED)
-                        let (assertions_passed, assertions_failed) = if !step.assertions.is_empty()
+                        let validate = context.is_validating();
+                        self.record_assertion_sampling(scenario_name, step, validate);
+                        let (assertions_passed, assertions_failed, assertion_failures) = if validate
+                            && !step.assertions.is_empty()
                         {
                             debug!(
                                 step = %step.name,
@@ -430,12 +1254,31 @@ impl ScenarioExecutor {
                                 "Running assertions on response"
                             );
 
-                            let assertion_results = assertions::run_assertions(
-                                &step.assertions,
+                            // notModified only applies once validators were sent
+                            let is_not_modified =
+                                |a: &Assertion| matches!(a, Assertion::NotModified);
+                            let step_assertions: Cow<[Assertion]> = if validators.is_none()
+                                && step.assertions.iter().any(is_not_modified)
+                            {
+                                Cow::Owned(
+                                    step.assertions
+                                        .iter()
+                                        .filter(|a| !is_not_modified(a))
+                                        .cloned()
+                                        .collect(),
+                                )
+                            } else {
+                                Cow::Borrowed(&step.assertions)
+                            };
+                            let assertion_results = assertions::run_assertions_with_digest(
+                                &step_assertions,
+                                step.patterns(),
                                 status.as_u16(),
                                 response_time_ms,
                                 &body,
                                 &headers,
+                                body_digest.as_ref(),
+                                cache_ratio,
                             );
 
                             let passed = assertion_results.iter().filter(|r| r.passed).count();
@@ -471,18 +1314,29 @@ impl ScenarioExecutor {
                                     .inc();
                             }
 
-                            (passed, failed)
+                            (
+                                passed,
+                                failed,
+                                assertion_failure_messages(&assertion_results),
+                            )
                         } else {
-                            (0, 0)
+                            (0, 0, Vec::new())
                         };
 
-                        // Step succeeds if HTTP status is success/redirect AND all assertions pass
-                        let http_success = status.is_success() || status.is_redirection();
+                        // Step succeeds if the HTTP status is expected (by
+                        // default any success/redirect) AND all assertions pass
+                        let http_success = step.status_ok(status.as_u16());
                         let all_assertions_pass = assertions_failed == 0;
                         let success = http_success && all_assertions_pass;
 
                         let error_msg = if !success {
-                            if !http_success {
+                            if !http_success && !step.expected_status.is_empty() {
+                                Some(format!(
+                                    "HTTP {} (expected {:?})",
+                                    status.as_u16(),
+                                    step.expected_status
+                                ))
+                            } else if !http_success {
                                 Some(format!("HTTP {}", status.as_u16()))
                             } else if !all_assertions_pass {
                                 Some(format!("{} assertion(s) failed", assertions_failed))
@@ -498,6 +1352,7 @@ impl ScenarioExecutor {
                             extracted_count,
                             assertions_passed,
                             assertions_failed,
+                            assertion_failures,
                             error_msg,
                         )
                     }
@@ -512,13 +1367,20 @@ impl ScenarioExecutor {
                             0,
                             0,
                             0,
+                            Vec::new(),
                             Some(format!("Failed to read response body: {}", e)),
                         )
                     }
                 };
 
-                let (success, _extracted_count, assertions_passed, assertions_failed, error_msg) =
-                    body_result_data;
+                let (
+                    success,
+                    _extracted_count,
+                    assertions_passed,
+                    assertions_failed,
+                    assertion_failures,
+                    error_msg,
+                ) = body_result_data;
 
                 // Record step metrics
                 let response_time_secs = response_time_ms as f64 / 1000.0;
@@ -565,10 +1427,16 @@ impl ScenarioExecutor {
                     error: error_msg,
                     assertions_passed,
                     assertions_failed,
-                    cache_hit: false,
+                    next_page,
+                    assertion_failures,
+                    retry_after: retry::retry_after(status.as_u16(), &headers),
+                    ..Default::default()
                 }
             }
             Err(e) => {
+                if is_resource_exhaustion(&e) {
+                    fd_limits::log_exhaustion_hint(&e);
+                }
                 error!(
                     step = %step.name,
                     error = %e,
@@ -588,24 +1456,335 @@ impl ScenarioExecutor {
                     .inc();
 
                 StepResult {
-                    step_name: step.name.clone(),
-                    success: false,
-                    status_code: None,
                     response_time_ms,
-                    error: Some(e.to_string()),
-                    assertions_passed: 0,
-                    assertions_failed: 0,
-                    cache_hit: false,
+                    ..StepResult::failed(step, e)
                 }
             }
         }
     }
+
+    /// Execute a step through an executor plugin (custom protocol).
+    ///
+    /// The plugin response is treated like an HTTP response: extractions and
+    /// assertions run against its body and headers, and the status code must
+    /// be one the step accepts (see [`Step::status_ok`]).
+    async fn execute_plugin_step(
+        &self,
+        scenario: &Scenario,
+        step: &Step,
+        context: &mut ScenarioContext,
+        url: &str,
+        plugin: Arc<dyn ExecutorPlugin>,
+        step_start: Instant,
+    ) -> StepResult {
+        let scenario_name = scenario.name.as_str();
+        let request = PluginRequest {
+            method: step.request.method.to_uppercase(),
+            url: url.to_string(),
+            headers: header_layers::resolve(
+                &self.global_headers,
+                self.client_headers(),
+                &scenario.headers,
+                &step.templates().headers,
+                context,
+            )
+            .into_iter()
+            .map(|(k, v)| (k.to_string(), v.into_owned()))
+            .collect(),
+            body: step
+                .templates()
+                .body
+                .as_ref()
+                .map(|b| b.render(context).into_owned()),
+        };
+
+        debug!(
+            step = %step.name,
+            plugin = %plugin.name(),
+            url = %url,
+            "Executing step via plugin"
+        );
+
+        let outcome = tokio::task::spawn_blocking(move || plugin.execute(&request)).await;
+        let response_time_ms = step_start.elapsed().as_millis() as u64;
+
+        let response = match outcome {
+            Ok(Ok(response)) => response,
+            Ok(Err(e)) => return self.plugin_step_failed(scenario_name, step, response_time_ms, e),
+            Err(e) => return self.plugin_step_failed(scenario_name, step, response_time_ms, e),
+        };
+
+        let mut headers = reqwest::header::HeaderMap::new();
+        for (name, value) in &response.headers {
+            if let (Ok(name), Ok(value)) = (
+                reqwest::header::HeaderName::from_bytes(name.as_bytes()),
+                reqwest::header::HeaderValue::from_str(value),
+            ) {
+                headers.insert(name, value);
+            }
+        }
+
+        let extracted = extractor::extract_variables_with_patterns(
+            &step.extractions,
+            step.patterns(),
+            &response.body,
+            &headers,
+        );
+        for (name, value) in extracted {
+            let scope = step.variable_scope(&name);
+            context.set_scoped_variable(name, value, scope);
+        }
+
+        let validate = context.is_validating();
+        self.record_assertion_sampling(scenario_name, step, validate);
+        let assertion_results = if validate {
+            let digest = step
+                .assertions
+                .iter()
+                .any(Assertion::needs_digest)
+                .then(|| assertions::BodyDigest::of(response.body.as_bytes()));
+            assertions::run_assertions_with_digest(
+                &step.assertions,
+                step.patterns(),
+                response.status,
+                response_time_ms,
+                &response.body,
+                &headers,
+                digest.as_ref(),
+                None,
+            )
+        } else {
+            Vec::new()
+        };
+        let assertions_passed = assertion_results.iter().filter(|r| r.passed).count();
+        let assertions_failed = assertion_results.len() - assertions_passed;
+        for result in &assertion_results {
+            let result_label = if result.passed { "passed" } else { "failed" };
+            SCENARIO_ASSERTIONS_TOTAL
+                .with_label_values(&[
+                    scenario_name,
+                    &step.name,
+                    result_label,
+                    &self.node_id,
+                    &self.run_id,
+                ])
+                .inc();
+        }
+
+        let status_ok = step.status_ok(response.status);
+        let success = status_ok && assertions_failed == 0;
+        let error = if !status_ok {
+            Some(format!("Status {}", response.status))
+        } else if assertions_failed > 0 {
+            Some(format!("{} assertion(s) failed", assertions_failed))
+        } else {
+            None
+        };
+
+        SCENARIO_STEP_DURATION_SECONDS
+            .with_label_values(&[scenario_name, &step.name, &self.node_id, &self.run_id])
+            .observe(response_time_ms as f64 / 1000.0);
+        SCENARIO_STEP_STATUS_CODES
+            .with_label_values(&[
+                scenario_name,
+                &step.name,
+                &response.status.to_string(),
+                &self.node_id,
+                &self.run_id,
+            ])
+            .inc();
+        SCENARIO_STEPS_TOTAL
+            .with_label_values(&[
+                scenario_name,
+                &step.name,
+                if success { "success" } else { "failed" },
+                &self.node_id,
+                &self.run_id,
+            ])
+            .inc();
+
+        StepResult {
+            step_name: step.name.clone(),
+            success,
+            status_code: Some(response.status),
+            response_time_ms,
+            error,
+            assertions_passed,
+            assertions_failed,
+            assertion_failures: assertion_failure_messages(&assertion_results),
+            ..Default::default()
+        }
+    }
+
+    /// Runs a `tcpCheck` step: connect (and handshake) instead of a request.
+    /// Only `responseTime` assertions apply, to the time the check took.
+    async fn execute_tcp_check_step(
+        &self,
+        scenario: &Scenario,
+        step: &Step,
+        check: &TcpCheck,
+        context: &ScenarioContext,
+        step_start: Instant,
+    ) -> StepResult {
+        let scenario_name = scenario.name.as_str();
+        let base = reqwest::Url::parse(&self.base_url).ok();
+        let host = base
+            .as_ref()
+            .and_then(|url| url.host_str())
+            .unwrap_or_default()
+            .trim_matches(|c| c == '[' || c == ']')
+            .to_string();
+        let port = base
+            .as_ref()
+            .and_then(|url| url.port_or_known_default())
+            .unwrap_or(80);
+
+        debug!(
+            step = %step.name,
+            target = ?check.target,
+            tls = check.tls,
+            "Running TCP check"
+        );
+
+        let outcome = match tcp_check::settings() {
+            Ok(settings) => tcp_check::run(check, &host, port, &settings)
+                .await
+                .map_err(|e| e.to_string()),
+            Err(e) => Err(e.to_string()),
+        };
+        let response_time_ms = step_start.elapsed().as_millis() as u64;
+        if let Err(e) = outcome {
+            warn!(step = %step.name, error = %e, "TCP check failed");
+            return self.failed_step(scenario_name, step, response_time_ms, e);
+        }
+
+        let validate = context.is_validating();
+        self.record_assertion_sampling(scenario_name, step, validate);
+        let assertion_results = if validate {
+            assertions::run_assertions_with_digest(
+                &step.assertions,
+                step.patterns(),
+                0,
+                response_time_ms,
+                "",
+                &reqwest::header::HeaderMap::new(),
+                None,
+                None,
+            )
+        } else {
+            Vec::new()
+        };
+        let assertions_passed = assertion_results.iter().filter(|r| r.passed).count();
+        let assertions_failed = assertion_results.len() - assertions_passed;
+        for result in &assertion_results {
+            let result_label = if result.passed { "passed" } else { "failed" };
+            SCENARIO_ASSERTIONS_TOTAL
+                .with_label_values(&[
+                    scenario_name,
+                    &step.name,
+                    result_label,
+                    &self.node_id,
+                    &self.run_id,
+                ])
+                .inc();
+        }
+        let success = assertions_failed == 0;
+
+        SCENARIO_STEP_DURATION_SECONDS
+            .with_label_values(&[scenario_name, &step.name, &self.node_id, &self.run_id])
+            .observe(response_time_ms as f64 / 1000.0);
+        SCENARIO_STEPS_TOTAL
+            .with_label_values(&[
+                scenario_name,
+                &step.name,
+                if success { "success" } else { "failed" },
+                &self.node_id,
+                &self.run_id,
+            ])
+            .inc();
+
+        StepResult {
+            step_name: step.name.clone(),
+            success,
+            response_time_ms,
+            error: (!success).then(|| format!("{} assertion(s) failed", assertions_failed)),
+            assertions_passed,
+            assertions_failed,
+            assertion_failures: assertion_failure_messages(&assertion_results),
+            ..Default::default()
+        }
+    }
+
+    fn plugin_step_failed(
+        &self,
+        scenario_name: &str,
+        step: &Step,
+        response_time_ms: u64,
+        err: impl std::fmt::Display,
+    ) -> StepResult {
+        error!(step = %step.name, error = %err, "Plugin execution failed");
+        self.failed_step(scenario_name, step, response_time_ms, err)
+    }
+
+    /// Counts a step that got no response as failed.
+    fn failed_step(
+        &self,
+        scenario_name: &str,
+        step: &Step,
+        response_time_ms: u64,
+        err: impl std::fmt::Display,
+    ) -> StepResult {
+        SCENARIO_STEPS_TOTAL
+            .with_label_values(&[
+                scenario_name,
+                &step.name,
+                "failed",
+                &self.node_id,
+                &self.run_id,
+            ])
+            .inc();
+        StepResult {
+            response_time_ms,
+            ..StepResult::failed(step, err)
+        }
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_session_keys_isolate_vus_scenarios_and_keys() {
+        let key = SessionKey::step_cache(Some(1), "Checkout", "Login", None);
+        let variants = [
+            key.clone(),
+            SessionKey::step_cache(Some(2), "Checkout", "Login", None),
+            SessionKey::step_cache(None, "Checkout", "Login", None),
+            SessionKey::step_cache(Some(1), "Browse", "Login", None),
+            SessionKey::step_cache(Some(1), "Checkout", "Refresh", None),
+            SessionKey::step_cache(Some(1), "Checkout", "Login", Some("alice".to_string())),
+            SessionKey {
+                slot: SessionSlot::Validators,
+                ..key.clone()
+            },
+        ];
+        let distinct: std::collections::HashSet<&SessionKey> = variants.iter().collect();
+        assert_eq!(distinct.len(), variants.len());
+
+        let entry = || SessionEntry {
+            variables: HashMap::from([("token".to_string(), "t".to_string())]),
+            expires_at: Instant::now(),
+        };
+        let mut session = SessionStore::for_vu(1);
+        session.insert(key.clone(), entry());
+        assert!(session.get(&key).is_some());
+        assert!(session.get(&variants[1]).is_none());
+        assert_eq!(session.vu(), 1);
+        assert!(session.remove(&key).is_some());
+        assert!(session.is_empty());
+    }
+
     #[test]
     fn test_scenario_result_success() {
         let result = ScenarioResult {
@@ -645,10 +1824,8 @@ mod tests {
             success: true,
             status_code: Some(200),
             response_time_ms: 150,
-            error: None,
             assertions_passed: 2,
-            assertions_failed: 0,
-            cache_hit: false,
+            ..Default::default()
         };
 
         assert!(result.success);
@@ -669,6 +1846,60 @@ mod tests {
         assert_eq!(executor.base_url, "https://example.com");
     }
 
+    #[tokio::test]
+    async fn test_tcp_check_steps_connect_without_a_request() {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let closed = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let closed_addr = closed.local_addr().unwrap();
+        drop(closed);
+
+        let yaml = format!(
+            r#"
+version: "1.0"
+config:
+  baseUrl: "http://{addr}"
+  duration: "1m"
+load:
+  model: "concurrent"
+scenarios:
+  - name: "Probes"
+    steps:
+      - name: "Base"
+        tcpCheck: {{}}
+      - name: "Closed"
+        ping: "{closed_addr}"
+"#
+        );
+        let scenarios = crate::yaml_config::YamlConfig::from_str(&yaml)
+            .unwrap()
+            .to_scenarios()
+            .unwrap();
+        let executor = ScenarioExecutor::new(
+            format!("http://{}", addr),
+            reqwest::Client::new(),
+            "test-node".to_string(),
+            "run-0".to_string(),
+        );
+        let result = executor
+            .execute(
+                &scenarios[0],
+                &mut ScenarioContext::new(),
+                &mut SessionStore::new(),
+            )
+            .await;
+
+        assert!(result.steps[0].success, "{:?}", result.steps[0].error);
+        assert_eq!(result.steps[0].status_code, None);
+        assert!(!result.steps[1].success);
+        assert!(result.steps[1]
+            .error
+            .as_deref()
+            .unwrap()
+            .starts_with("TCP connect to"));
+        assert_eq!(result.failed_at_step, Some(1));
+    }
+
     // Integration tests with actual HTTP calls would go here
     // For now, keeping tests simple to avoid external dependencies
 }
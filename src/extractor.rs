@@ -1,11 +1,16 @@
 //! Variable extraction from HTTP responses.
 //!
 //! This module provides functionality to extract values from HTTP responses
-//! using various methods: JSONPath, Regex, HTTP headers, and cookies.
+//! using various methods: JSONPath, XPath (XML), Regex, HTTP headers, and cookies.
+//! Extracted values can then pass through a pipeline of [`Transform`]s.
 
+use crate::patterns::CompiledPatterns;
+use crate::redaction::GLOBAL_REDACTOR;
 use crate::scenario::{Extractor, VariableExtraction};
+use crate::xml_path;
 use regex::Regex;
 use serde_json::Value;
+use serde_json_path::JsonPath;
 use std::collections::HashMap;
 use thiserror::Error;
 use tracing::{debug, warn};
@@ -19,6 +24,9 @@ pub enum ExtractionError {
     #[error("Invalid JSON response: {0}")]
     InvalidJson(String),
 
+    #[error("XPath query failed: {0}")]
+    XmlPathError(String),
+
     #[error("Regex compilation failed: {0}")]
     RegexError(#[from] regex::Error),
 
@@ -34,10 +42,109 @@ pub enum ExtractionError {
     #[error("Cookie '{0}' not found in response")]
     CookieNotFound(String),
 
+    #[error("Unknown transform '{0}'")]
+    UnknownTransform(String),
+
     #[error("Extraction failed: {0}")]
     Other(String),
 }
 
+/// Post-processing step applied to an extracted value.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Transform {
+    ToUpper,
+    ToLower,
+    Trim,
+    /// Percent-encode everything except RFC 3986 unreserved characters
+    UrlEncode,
+    /// Escape for use inside a JSON string literal (no surrounding quotes)
+    JsonEscape,
+    /// Characters `start..end`, clamped to the value's length
+    Substring {
+        start: usize,
+        end: usize,
+    },
+}
+
+impl Transform {
+    /// Applies this transform to `value`.
+    pub fn apply(&self, value: &str) -> String {
+        match self {
+            Transform::ToUpper => value.to_uppercase(),
+            Transform::ToLower => value.to_lowercase(),
+            Transform::Trim => value.trim().to_string(),
+            Transform::UrlEncode => url_encode(value),
+            Transform::JsonEscape => {
+                let quoted = serde_json::to_string(value).unwrap_or_default();
+                quoted[1..quoted.len() - 1].to_string()
+            }
+            Transform::Substring { start, end } => value
+                .chars()
+                .skip(*start)
+                .take(end.saturating_sub(*start))
+                .collect(),
+        }
+    }
+}
+
+/// Parses a transform pipeline such as `"toUpper | urlEncode | substring(0,8)"`.
+/// A leading `|` is allowed.
+///
+/// # Example
+/// ```
+/// use rust_loadtest::extractor::{apply_transforms, parse_transforms};
+///
+/// let pipeline = parse_transforms("| substring(0,3) | toUpper").unwrap();
+/// assert_eq!(apply_transforms("abcdef", &pipeline), "ABC");
+/// ```
+pub fn parse_transforms(spec: &str) -> Result<Vec<Transform>, ExtractionError> {
+    spec.split('|')
+        .map(str::trim)
+        .filter(|name| !name.is_empty())
+        .map(|name| match name {
+            "toUpper" => Ok(Transform::ToUpper),
+            "toLower" => Ok(Transform::ToLower),
+            "trim" => Ok(Transform::Trim),
+            "urlEncode" => Ok(Transform::UrlEncode),
+            "jsonEscape" => Ok(Transform::JsonEscape),
+            _ => {
+                parse_substring(name).ok_or_else(|| ExtractionError::UnknownTransform(name.into()))
+            }
+        })
+        .collect()
+}
+
+/// Parses `substring(start,end)`.
+fn parse_substring(name: &str) -> Option<Transform> {
+    let args = name.strip_prefix("substring(")?.strip_suffix(')')?;
+    let (start, end) = args.split_once(',')?;
+    let start = start.trim().parse().ok()?;
+    let end = end.trim().parse().ok()?;
+    (start <= end).then_some(Transform::Substring { start, end })
+}
+
+/// Runs `value` through `transforms` in order.
+pub fn apply_transforms(value: &str, transforms: &[Transform]) -> String {
+    transforms
+        .iter()
+        .fold(value.to_string(), |value, transform| {
+            transform.apply(&value)
+        })
+}
+
+fn url_encode(value: &str) -> String {
+    let mut encoded = String::with_capacity(value.len());
+    for byte in value.bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => {
+                encoded.push(byte as char)
+            }
+            _ => encoded.push_str(&format!("%{:02X}", byte)),
+        }
+    }
+    encoded
+}
+
 /// Extract variables from an HTTP response.
 ///
 /// # Arguments
@@ -51,6 +158,22 @@ pub fn extract_variables(
     extractions: &[VariableExtraction],
     response_body: &str,
     response_headers: &reqwest::header::HeaderMap,
+) -> HashMap<String, String> {
+    extract_variables_with_patterns(
+        extractions,
+        &CompiledPatterns::default(),
+        response_body,
+        response_headers,
+    )
+}
+
+/// Like [`extract_variables`], using the step's precompiled JSONPath
+/// expressions and regexes.
+pub fn extract_variables_with_patterns(
+    extractions: &[VariableExtraction],
+    patterns: &CompiledPatterns,
+    response_body: &str,
+    response_headers: &reqwest::header::HeaderMap,
 ) -> HashMap<String, String> {
     let mut variables = HashMap::new();
 
@@ -61,11 +184,17 @@ pub fn extract_variables(
             "Attempting variable extraction"
         );
 
-        match extract_value(&extraction.extractor, response_body, response_headers) {
+        match extract_value(
+            &extraction.extractor,
+            patterns,
+            response_body,
+            response_headers,
+        ) {
             Ok(value) => {
+                let value = apply_transforms(&value, &extraction.transforms);
                 debug!(
                     variable_name = %extraction.name,
-                    value = %value,
+                    value = %GLOBAL_REDACTOR.value(&extraction.name, &value),
                     "Successfully extracted variable"
                 );
                 variables.insert(extraction.name.clone(), value);
@@ -87,17 +216,62 @@ pub fn extract_variables(
 /// Extract a single value using the specified extractor.
 fn extract_value(
     extractor: &Extractor,
+    patterns: &CompiledPatterns,
     response_body: &str,
     response_headers: &reqwest::header::HeaderMap,
 ) -> Result<String, ExtractionError> {
     match extractor {
-        Extractor::JsonPath(path) => extract_json_path(response_body, path),
-        Extractor::Regex { pattern, group } => extract_regex(response_body, pattern, group),
+        Extractor::JsonPath(path) => {
+            let result = patterns
+                .json_path(path)
+                .map_err(|e| ExtractionError::JsonPathError(format!("Invalid JSONPath: {}", e)))
+                .and_then(|compiled| query_json_path(response_body, &compiled, path));
+            result.map_err(|e| match e {
+                // Point at xmlPath when the JSON parse failed on an XML response
+                ExtractionError::InvalidJson(_) if is_xml_response(response_headers) => {
+                    ExtractionError::InvalidJson(format!(
+                        "{} (response Content-Type is XML; use an xmlPath extractor)",
+                        e
+                    ))
+                }
+                e => e,
+            })
+        }
+        Extractor::XmlPath(path) => extract_xml_path(response_body, path),
+        Extractor::Regex { pattern, group } => {
+            capture_regex(response_body, &*patterns.regex(pattern)?, group)
+        }
         Extractor::Header(header_name) => extract_header(response_headers, header_name),
         Extractor::Cookie(cookie_name) => extract_cookie(response_headers, cookie_name),
     }
 }
 
+/// Returns true if the response declares an XML `Content-Type`.
+fn is_xml_response(response_headers: &reqwest::header::HeaderMap) -> bool {
+    response_headers
+        .get(reqwest::header::CONTENT_TYPE)
+        .and_then(|v| v.to_str().ok())
+        .is_some_and(xml_path::is_xml_content_type)
+}
+
+/// Extract the first value matched by an XPath query on an XML body.
+///
+/// # Example
+/// ```
+/// use rust_loadtest::extractor::extract_xml_path;
+///
+/// let xml = r#"<response><user id="42"><name>Alice</name></user></response>"#;
+/// assert_eq!(extract_xml_path(xml, "//user/@id").unwrap(), "42");
+/// assert_eq!(extract_xml_path(xml, "/response/user/name").unwrap(), "Alice");
+/// ```
+pub fn extract_xml_path(xml_body: &str, path: &str) -> Result<String, ExtractionError> {
+    xml_path::select(xml_body, path)
+        .map_err(|e| ExtractionError::XmlPathError(e.to_string()))?
+        .into_iter()
+        .next()
+        .ok_or_else(|| ExtractionError::XmlPathError(format!("No match for XPath: {}", path)))
+}
+
 /// Extract value using JSONPath query.
 ///
 /// # Example
@@ -109,16 +283,21 @@ fn extract_value(
 /// assert_eq!(result, "123");
 /// ```
 pub fn extract_json_path(json_body: &str, path: &str) -> Result<String, ExtractionError> {
+    let json_path = JsonPath::parse(path)
+        .map_err(|e| ExtractionError::JsonPathError(format!("Invalid JSONPath: {}", e)))?;
+    query_json_path(json_body, &json_path, path)
+}
+
+/// Query `json_body` with the compiled `json_path` (whose source is `path`).
+fn query_json_path(
+    json_body: &str,
+    json_path: &JsonPath,
+    path: &str,
+) -> Result<String, ExtractionError> {
     // Parse JSON
     let json: Value =
         serde_json::from_str(json_body).map_err(|e| ExtractionError::InvalidJson(e.to_string()))?;
 
-    // Use serde_json_path to query
-    use serde_json_path::JsonPath;
-
-    let json_path = JsonPath::parse(path)
-        .map_err(|e| ExtractionError::JsonPathError(format!("Invalid JSONPath: {}", e)))?;
-
     let node_list = json_path.query(&json);
 
     // Get first match
@@ -154,8 +333,11 @@ pub fn extract_json_path(json_body: &str, path: &str) -> Result<String, Extracti
 /// assert_eq!(result, "123");
 /// ```
 pub fn extract_regex(text: &str, pattern: &str, group: &str) -> Result<String, ExtractionError> {
-    let re = Regex::new(pattern)?;
+    capture_regex(text, &Regex::new(pattern)?, group)
+}
 
+/// Capture the named `group` of the compiled regex `re` in `text`.
+fn capture_regex(text: &str, re: &Regex, group: &str) -> Result<String, ExtractionError> {
     if let Some(captures) = re.captures(text) {
         if let Some(matched) = captures.name(group) {
             Ok(matched.as_str().to_string())
@@ -231,6 +413,7 @@ pub fn extract_cookie(
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::scenario::VariableScope;
     use reqwest::header::{HeaderMap, HeaderValue, CONTENT_TYPE, SET_COOKIE};
 
     #[test]
@@ -293,6 +476,21 @@ mod tests {
         assert!(result.is_err());
     }
 
+    #[test]
+    fn test_extract_xml_path() {
+        let xml = r#"<soap:Envelope xmlns:soap="http://schemas.xmlsoap.org/soap/envelope/">
+            <soap:Body><GetPriceResponse><Price currency="USD">9.99</Price></GetPriceResponse></soap:Body>
+        </soap:Envelope>"#;
+
+        assert_eq!(extract_xml_path(xml, "//Price").unwrap(), "9.99");
+        assert_eq!(
+            extract_xml_path(xml, "/Envelope/Body/GetPriceResponse/Price/@currency").unwrap(),
+            "USD"
+        );
+        assert!(extract_xml_path(xml, "//Missing").is_err());
+        assert!(extract_xml_path("<broken", "//a").is_err());
+    }
+
     #[test]
     fn test_extract_regex_named_group() {
         let html = r#"<div id="user-123">Alice</div>"#;
@@ -394,10 +592,14 @@ mod tests {
             VariableExtraction {
                 name: "user_id".to_string(),
                 extractor: Extractor::JsonPath("$.user.id".to_string()),
+                transforms: Vec::new(),
+                scope: VariableScope::Iteration,
             },
             VariableExtraction {
                 name: "user_name".to_string(),
                 extractor: Extractor::JsonPath("$.user.name".to_string()),
+                transforms: Vec::new(),
+                scope: VariableScope::Iteration,
             },
         ];
 
@@ -410,16 +612,61 @@ mod tests {
         assert_eq!(result.get("user_name"), Some(&"Alice".to_string()));
     }
 
+    #[test]
+    fn test_transform_pipeline() {
+        let pipeline = parse_transforms("trim | toUpper | substring(0,8)").unwrap();
+        assert_eq!(
+            pipeline,
+            vec![
+                Transform::Trim,
+                Transform::ToUpper,
+                Transform::Substring { start: 0, end: 8 }
+            ]
+        );
+        assert_eq!(apply_transforms("  abcdef-1234  ", &pipeline), "ABCDEF-1");
+        assert_eq!(
+            Transform::UrlEncode.apply("a b&c=é/~"),
+            "a%20b%26c%3D%C3%A9%2F~"
+        );
+        assert_eq!(
+            Transform::JsonEscape.apply("say \"hi\"\n"),
+            "say \\\"hi\\\"\\n"
+        );
+        assert_eq!(
+            Transform::Substring { start: 2, end: 99 }.apply("héllo"),
+            "llo"
+        );
+
+        assert!(parse_transforms("reverse").is_err());
+        assert!(parse_transforms("substring(5,2)").is_err());
+        assert!(parse_transforms("").unwrap().is_empty());
+
+        let extractions = vec![VariableExtraction {
+            name: "token".to_string(),
+            extractor: Extractor::Header("x-token".to_string()),
+            transforms: parse_transforms("| toLower | urlEncode").unwrap(),
+            scope: VariableScope::Iteration,
+        }];
+        let mut headers = HeaderMap::new();
+        headers.insert("x-token", "AB CD".parse().unwrap());
+        let result = extract_variables(&extractions, "", &headers);
+        assert_eq!(result.get("token"), Some(&"ab%20cd".to_string()));
+    }
+
     #[test]
     fn test_extract_variables_partial_failure() {
         let extractions = vec![
             VariableExtraction {
                 name: "user_id".to_string(),
                 extractor: Extractor::JsonPath("$.user.id".to_string()),
+                transforms: Vec::new(),
+                scope: VariableScope::Iteration,
             },
             VariableExtraction {
                 name: "missing".to_string(),
                 extractor: Extractor::JsonPath("$.does.not.exist".to_string()),
+                transforms: Vec::new(),
+                scope: VariableScope::Iteration,
             },
         ];
 
@@ -0,0 +1,301 @@
+//! Failure triage: step failures grouped by signature.
+//!
+//! A failing target tends to fail in a handful of ways thousands of times
+//! over. Every failed step attempt is counted under its signature — scenario,
+//! step, status code and error message with the variable parts (numbers,
+//! ids) masked — and the final report lists the most frequent signatures with
+//! their counts and when each was first and last seen, so the distinct
+//! failure modes can be read off one table instead of the logs.
+
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Number of signatures shown in the final report.
+pub const TOP_SIGNATURES: usize = 10;
+
+/// Longest normalized message kept; longer ones are cut off.
+const MAX_MESSAGE_CHARS: usize = 200;
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+struct SignatureKey {
+    scenario: String,
+    step: String,
+    status: Option<u16>,
+    message: String,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct SignatureCounts {
+    count: u64,
+    first_seen: u64,
+    last_seen: u64,
+}
+
+/// One row of the failure triage report.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FailureSignature {
+    pub scenario: String,
+    pub step: String,
+    /// `None` when no response was received.
+    pub status: Option<u16>,
+    /// The normalized error message.
+    pub message: String,
+    pub count: u64,
+    /// Unix seconds.
+    pub first_seen: u64,
+    /// Unix seconds.
+    pub last_seen: u64,
+}
+
+/// Step failures counted by signature over the run.
+#[derive(Default)]
+pub struct FailureSignatureTracker {
+    signatures: Mutex<HashMap<SignatureKey, SignatureCounts>>,
+}
+
+impl FailureSignatureTracker {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Counts one failed step attempt, seen now.
+    pub fn record(&self, scenario: &str, step: &str, status: Option<u16>, message: &str) {
+        self.record_at(scenario, step, status, message, now_secs());
+    }
+
+    fn record_at(&self, scenario: &str, step: &str, status: Option<u16>, message: &str, at: u64) {
+        let key = SignatureKey {
+            scenario: scenario.to_string(),
+            step: step.to_string(),
+            status,
+            message: normalize_message(message),
+        };
+        let mut signatures = self.signatures.lock().unwrap_or_else(|e| e.into_inner());
+        let counts = signatures.entry(key).or_insert(SignatureCounts {
+            count: 0,
+            first_seen: at,
+            last_seen: at,
+        });
+        counts.count += 1;
+        counts.first_seen = counts.first_seen.min(at);
+        counts.last_seen = counts.last_seen.max(at);
+    }
+
+    /// The `n` most frequent signatures, most frequent first (ties: first
+    /// seen first).
+    pub fn top(&self, n: usize) -> Vec<FailureSignature> {
+        let signatures = self.signatures.lock().unwrap_or_else(|e| e.into_inner());
+        let mut rows: Vec<FailureSignature> = signatures
+            .iter()
+            .map(|(key, counts)| FailureSignature {
+                scenario: key.scenario.clone(),
+                step: key.step.clone(),
+                status: key.status,
+                message: key.message.clone(),
+                count: counts.count,
+                first_seen: counts.first_seen,
+                last_seen: counts.last_seen,
+            })
+            .collect();
+        rows.sort_by(|a, b| {
+            b.count
+                .cmp(&a.count)
+                .then(a.first_seen.cmp(&b.first_seen))
+                .then_with(|| {
+                    (&a.scenario, &a.step, &a.message).cmp(&(&b.scenario, &b.step, &b.message))
+                })
+        });
+        rows.truncate(n);
+        rows
+    }
+
+    /// Number of distinct signatures.
+    pub fn len(&self) -> usize {
+        self.signatures
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn reset(&self) {
+        self.signatures
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .clear();
+    }
+}
+
+/// Masks the parts of an error message that vary between otherwise
+/// identical failures: ids (UUIDs, long hex strings) become `<id>` and every
+/// other run of digits becomes `<n>`. Whitespace is collapsed and the result
+/// is cut to 200 characters.
+pub fn normalize_message(message: &str) -> String {
+    let mut output = String::with_capacity(message.len());
+    let mut rest = message.trim();
+    while let Some(c) = rest.chars().next() {
+        if c.is_ascii_alphanumeric() {
+            let end = rest
+                .find(|c: char| !(c.is_ascii_alphanumeric() || c == '-'))
+                .unwrap_or(rest.len());
+            let word = &rest[..end];
+            if is_id(word) {
+                output.push_str("<id>");
+            } else {
+                mask_digits(word, &mut output);
+            }
+            rest = &rest[end..];
+        } else if c.is_whitespace() {
+            output.push(' ');
+            rest = rest.trim_start();
+        } else {
+            output.push(c);
+            rest = &rest[c.len_utf8()..];
+        }
+    }
+    match output.char_indices().nth(MAX_MESSAGE_CHARS) {
+        Some((cut, _)) => format!("{}...", &output[..cut]),
+        None => output,
+    }
+}
+
+/// UUIDs and hex strings of 16+ digits with at least one decimal digit.
+fn is_id(word: &str) -> bool {
+    let hex: String = word.chars().filter(|&c| c != '-').collect();
+    hex.len() >= 16
+        && hex.chars().all(|c| c.is_ascii_hexdigit())
+        && hex.chars().any(|c| c.is_ascii_digit())
+}
+
+fn mask_digits(word: &str, output: &mut String) {
+    let mut in_digits = false;
+    for c in word.chars() {
+        if c.is_ascii_digit() {
+            if !in_digits {
+                output.push_str("<n>");
+            }
+            in_digits = true;
+        } else {
+            output.push(c);
+            in_digits = false;
+        }
+    }
+}
+
+fn now_secs() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs()
+}
+
+/// Unix seconds as `YYYY-MM-DDTHH:MM:SSZ`.
+pub fn format_timestamp(secs: u64) -> String {
+    chrono::DateTime::from_timestamp(secs as i64, 0)
+        .unwrap_or_default()
+        .format("%Y-%m-%dT%H:%M:%SZ")
+        .to_string()
+}
+
+/// Formats the signatures as a table.
+pub fn format_failure_signature_table(rows: &[FailureSignature]) -> String {
+    let mut output = String::new();
+    output.push_str(&format!(
+        "\n{:>8} {:<40} {:>6} {:<20} {:<20} {}\n",
+        "Count", "Scenario / Step", "Status", "First Seen", "Last Seen", "Error"
+    ));
+    output.push_str(&"-".repeat(120));
+    output.push('\n');
+
+    for row in rows {
+        let status = row
+            .status
+            .map_or_else(|| "-".to_string(), |status| status.to_string());
+        output.push_str(&format!(
+            "{:>8} {:<40} {:>6} {:<20} {:<20} {}\n",
+            row.count,
+            format!("{} / {}", row.scenario, row.step),
+            status,
+            format_timestamp(row.first_seen),
+            format_timestamp(row.last_seen),
+            row.message
+        ));
+    }
+    output
+}
+
+lazy_static::lazy_static! {
+    /// Process-wide failure signatures, reported at the end of the test.
+    pub static ref GLOBAL_FAILURE_SIGNATURES: FailureSignatureTracker =
+        FailureSignatureTracker::new();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn masks_variable_parts() {
+        assert_eq!(
+            normalize_message("Response time 1234ms exceeds 500ms"),
+            "Response time <n>ms exceeds <n>ms"
+        );
+        assert_eq!(
+            normalize_message("order 550e8400-e29b-41d4-a716-446655440000 not found"),
+            "order <id> not found"
+        );
+        assert_eq!(
+            normalize_message("error sending request for url (http://api:8080/users/42)"),
+            "error sending request for url (http://api:<n>/users/<n>)"
+        );
+        assert_eq!(
+            normalize_message("  connection\n  reset "),
+            "connection reset"
+        );
+        assert_eq!(normalize_message(&"x".repeat(300)).chars().count(), 203);
+    }
+
+    #[test]
+    fn groups_failures_by_signature() {
+        let tracker = FailureSignatureTracker::new();
+        tracker.record_at("Checkout", "Pay", Some(503), "HTTP 503", 100);
+        tracker.record_at("Checkout", "Pay", Some(503), "HTTP 503", 160);
+        tracker.record_at("Checkout", "Pay", Some(503), "HTTP 503", 130);
+        tracker.record_at("Checkout", "Pay", None, "timed out after 30000ms", 120);
+        tracker.record_at("Checkout", "Pay", None, "timed out after 30001ms", 140);
+        tracker.record_at("Checkout", "Login", Some(401), "HTTP 401", 110);
+
+        assert_eq!(tracker.len(), 3);
+        let top = tracker.top(2);
+        assert_eq!(top.len(), 2);
+        assert_eq!(top[0].status, Some(503));
+        assert_eq!(top[0].count, 3);
+        assert_eq!((top[0].first_seen, top[0].last_seen), (100, 160));
+        assert_eq!(top[1].message, "timed out after <n>ms");
+        assert_eq!(top[1].count, 2);
+
+        tracker.reset();
+        assert!(tracker.is_empty());
+    }
+
+    #[test]
+    fn formats_table() {
+        let rows = vec![FailureSignature {
+            scenario: "Checkout".to_string(),
+            step: "Pay".to_string(),
+            status: None,
+            message: "connection refused".to_string(),
+            count: 7,
+            first_seen: 0,
+            last_seen: 60,
+        }];
+        let table = format_failure_signature_table(&rows);
+        assert!(table.contains("Checkout / Pay"));
+        assert!(table.contains("1970-01-01T00:01:00Z"));
+        assert!(table.contains("connection refused"));
+    }
+}
@@ -0,0 +1,220 @@
+//! Startup checks for file descriptor and ephemeral port limits.
+//!
+//! Connection-heavy tests (high concurrency, keep-alive disabled, short
+//! pool idle timeouts) open one socket per in-flight request. When the
+//! process runs out of descriptors (`ulimit -n`) or the host runs out of
+//! ephemeral ports, requests fail with errors that look like target
+//! failures. These checks warn before the test starts, and failures at
+//! runtime are reported as `resource_exhausted` (see
+//! [`crate::errors::ErrorCategory::ResourceExhausted`]).
+//!
+//! Set `RAISE_FD_LIMIT=true` to raise the soft descriptor limit up to the
+//! hard limit when it is too low for the configured concurrency.
+
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use tracing::{info, warn};
+
+/// Descriptors reserved for things other than request sockets: stdio,
+/// metrics and control servers, log files, DNS, config watchers.
+const RESERVED_FDS: u64 = 64;
+
+/// Sockets budgeted per worker. A worker has at most one request in
+/// flight, but a connection being replaced can briefly overlap its successor.
+const FDS_PER_WORKER: u64 = 2;
+
+/// Set once the runtime exhaustion hint has been logged.
+static EXHAUSTION_HINT_LOGGED: AtomicBool = AtomicBool::new(false);
+
+/// Soft and hard `RLIMIT_NOFILE` values.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FdLimits {
+    pub soft: u64,
+    pub hard: u64,
+}
+
+/// Descriptors needed to run `concurrency` workers without hitting the limit.
+pub fn required_fds(concurrency: usize) -> u64 {
+    concurrency as u64 * FDS_PER_WORKER + RESERVED_FDS
+}
+
+/// Returns the current descriptor limits, or `None` where unsupported.
+#[cfg(unix)]
+pub fn current_limits() -> Option<FdLimits> {
+    let mut rlim = libc::rlimit {
+        rlim_cur: 0,
+        rlim_max: 0,
+    };
+    // SAFETY: getrlimit only writes into the struct we pass.
+    if unsafe { libc::getrlimit(libc::RLIMIT_NOFILE, &mut rlim) } != 0 {
+        return None;
+    }
+    // rlim_t is narrower than u64 on some targets.
+    #[allow(clippy::unnecessary_cast)]
+    Some(FdLimits {
+        soft: rlim.rlim_cur as u64,
+        hard: rlim.rlim_max as u64,
+    })
+}
+
+#[cfg(not(unix))]
+pub fn current_limits() -> Option<FdLimits> {
+    None
+}
+
+/// Raises the soft descriptor limit to `target`, capped at the hard limit.
+/// Returns the limits in effect afterwards.
+#[cfg(unix)]
+pub fn raise_soft_limit(target: u64) -> std::io::Result<FdLimits> {
+    let limits = current_limits().ok_or_else(std::io::Error::last_os_error)?;
+    let new_soft = target.min(limits.hard);
+    if new_soft <= limits.soft {
+        return Ok(limits);
+    }
+    let rlim = libc::rlimit {
+        rlim_cur: new_soft as libc::rlim_t,
+        rlim_max: limits.hard as libc::rlim_t,
+    };
+    // SAFETY: setrlimit only reads the struct we pass.
+    if unsafe { libc::setrlimit(libc::RLIMIT_NOFILE, &rlim) } != 0 {
+        return Err(std::io::Error::last_os_error());
+    }
+    Ok(FdLimits {
+        soft: new_soft,
+        hard: limits.hard,
+    })
+}
+
+#[cfg(not(unix))]
+pub fn raise_soft_limit(_target: u64) -> std::io::Result<FdLimits> {
+    Err(std::io::Error::new(
+        std::io::ErrorKind::Unsupported,
+        "descriptor limits are not supported on this platform",
+    ))
+}
+
+/// Size of the ephemeral port range (`net.ipv4.ip_local_port_range`).
+#[cfg(target_os = "linux")]
+pub fn ephemeral_port_count() -> Option<u64> {
+    let range = std::fs::read_to_string("/proc/sys/net/ipv4/ip_local_port_range").ok()?;
+    parse_port_range(&range)
+}
+
+#[cfg(not(target_os = "linux"))]
+pub fn ephemeral_port_count() -> Option<u64> {
+    None
+}
+
+/// Parses `"32768\t60999"` into the number of ports in the range.
+fn parse_port_range(range: &str) -> Option<u64> {
+    let mut parts = range.split_whitespace().map(|p| p.parse::<u64>().ok());
+    let (low, high) = (parts.next()??, parts.next()??);
+    high.checked_sub(low).map(|n| n + 1)
+}
+
+/// Returns true when `RAISE_FD_LIMIT=true`.
+pub fn raise_requested() -> bool {
+    std::env::var("RAISE_FD_LIMIT")
+        .map(|v| v.eq_ignore_ascii_case("true"))
+        .unwrap_or(false)
+}
+
+/// Checks descriptor and ephemeral port limits against `concurrency`,
+/// optionally raising the soft descriptor limit, and warns about anything
+/// that is likely to cause `resource_exhausted` errors during the test.
+pub fn preflight(concurrency: usize, raise: bool) {
+    let required = required_fds(concurrency);
+
+    if let Some(mut limits) = current_limits() {
+        if limits.soft < required && raise {
+            match raise_soft_limit(required) {
+                Ok(raised) => {
+                    info!(
+                        previous_soft_limit = limits.soft,
+                        soft_limit = raised.soft,
+                        hard_limit = raised.hard,
+                        "Raised open file descriptor soft limit"
+                    );
+                    limits = raised;
+                }
+                Err(e) => warn!(error = %e, "Failed to raise open file descriptor limit"),
+            }
+        }
+
+        if limits.soft < required {
+            warn!(
+                soft_limit = limits.soft,
+                hard_limit = limits.hard,
+                required,
+                concurrency,
+                "Open file descriptor limit is low for the configured concurrency; \
+                 requests may fail with 'Too many open files'. Raise `ulimit -n` \
+                 or set RAISE_FD_LIMIT=true"
+            );
+        } else {
+            info!(
+                soft_limit = limits.soft,
+                hard_limit = limits.hard,
+                required,
+                "Open file descriptor limit OK"
+            );
+        }
+    }
+
+    if let Some(ports) = ephemeral_port_count() {
+        if concurrency as u64 > ports / 2 {
+            warn!(
+                ephemeral_ports = ports,
+                concurrency,
+                "Concurrency is high relative to the ephemeral port range; without \
+                 keep-alive, connections in TIME_WAIT can exhaust local ports. Widen \
+                 net.ipv4.ip_local_port_range or keep connections pooled"
+            );
+        }
+    }
+}
+
+/// Logs a one-time hint when a request fails with a resource exhaustion
+/// error, so the cause is visible without reading every failure.
+pub fn log_exhaustion_hint(error: &dyn std::fmt::Display) {
+    if EXHAUSTION_HINT_LOGGED.swap(true, Ordering::Relaxed) {
+        return;
+    }
+    let limits = current_limits();
+    warn!(
+        error = %error,
+        soft_limit = limits.map(|l| l.soft),
+        hard_limit = limits.map(|l| l.hard),
+        "Load generator ran out of file descriptors or ephemeral ports — this is \
+         a limit on this host, not a target failure. Raise `ulimit -n` \
+         (or set RAISE_FD_LIMIT=true), reduce concurrency, or enable keep-alive"
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn required_fds_scales_with_concurrency() {
+        assert_eq!(required_fds(0), RESERVED_FDS);
+        assert_eq!(required_fds(1000), 2000 + RESERVED_FDS);
+    }
+
+    #[test]
+    fn parses_port_range() {
+        assert_eq!(parse_port_range("32768\t60999\n"), Some(28232));
+        assert_eq!(parse_port_range("1024 1024"), Some(1));
+        assert_eq!(parse_port_range("garbage"), None);
+        assert_eq!(parse_port_range("5000 4000"), None);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn reads_current_limits() {
+        let limits = current_limits().expect("getrlimit works on unix");
+        assert!(limits.soft <= limits.hard);
+        // Raising to the current soft limit is a no-op.
+        assert_eq!(raise_soft_limit(limits.soft).unwrap(), limits);
+    }
+}
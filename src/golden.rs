@@ -0,0 +1,305 @@
+//! Golden file assertions (`matchesGolden`).
+//!
+//! Latency percentiles say nothing about whether the target still answers
+//! correctly under load. A `matchesGolden` assertion compares the JSON body
+//! of every response to a stored golden file and fails on the first
+//! difference, reported with its location (`$.items[2].price`), so caches
+//! serving stale data or fallbacks returning partial results show up as
+//! assertion failures.
+//!
+//! Fields that legitimately change between responses — timestamps, request
+//! ids — are listed in `ignoreFields` and removed before comparing. A name
+//! (`updatedAt`) removes that key at any depth; a dotted path from the root
+//! (`order.items.*.id`) removes one location, with `*` matching any key or
+//! array element. Object key order never matters.
+//!
+//! With `GOLDEN_RECORD=true`, a golden file that does not exist yet is
+//! written from the first response (normalized, pretty-printed), so a first
+//! run against a known-good build records the goldens and later runs check
+//! against them. Existing goldens are never overwritten; delete a file to
+//! re-record it. Golden files are read once and cached for the run.
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+
+use serde_json::Value;
+use thiserror::Error;
+use tracing::info;
+
+/// Whether missing golden files are recorded.
+static RECORD: AtomicBool = AtomicBool::new(false);
+
+lazy_static::lazy_static! {
+    /// Golden files loaded (or recorded) so far, by path.
+    static ref GOLDENS: Mutex<HashMap<PathBuf, Arc<Value>>> = Mutex::new(HashMap::new());
+}
+
+#[derive(Error, Debug)]
+pub enum GoldenError {
+    #[error("golden file '{path}' does not exist (run with GOLDEN_RECORD=true to record it)")]
+    Missing { path: String },
+
+    #[error("failed to read golden file '{path}': {message}")]
+    Read { path: String, message: String },
+
+    #[error("failed to record golden file '{path}': {message}")]
+    Write { path: String, message: String },
+
+    #[error("response is not JSON: {0}")]
+    InvalidResponse(String),
+
+    #[error("response differs from '{path}' at {location}: expected {expected}, got {actual}")]
+    Mismatch {
+        path: String,
+        location: String,
+        expected: String,
+        actual: String,
+    },
+}
+
+/// Enables or disables recording of missing golden files (`GOLDEN_RECORD`).
+pub fn init(record: bool) {
+    RECORD.store(record, Ordering::Relaxed);
+}
+
+/// Checks `body` against the golden file at `path`, ignoring
+/// `ignore_fields`.
+pub fn check(path: &str, ignore_fields: &[String], body: &str) -> Result<(), GoldenError> {
+    let mut actual: Value =
+        serde_json::from_str(body).map_err(|e| GoldenError::InvalidResponse(e.to_string()))?;
+    normalize(&mut actual, ignore_fields);
+    let golden = load_or_record(path, &actual)?;
+    match first_difference(&golden, &actual, "$") {
+        None => Ok(()),
+        Some((location, expected, actual)) => Err(GoldenError::Mismatch {
+            path: path.to_string(),
+            location,
+            expected,
+            actual,
+        }),
+    }
+}
+
+/// The cached golden for `path`, loading it from disk or, when recording,
+/// writing `actual` as the new golden.
+fn load_or_record(path: &str, actual: &Value) -> Result<Arc<Value>, GoldenError> {
+    let key = PathBuf::from(path);
+    let mut goldens = GOLDENS.lock().unwrap_or_else(|e| e.into_inner());
+    if let Some(golden) = goldens.get(&key) {
+        return Ok(golden.clone());
+    }
+    let golden = if key.exists() {
+        let content = std::fs::read_to_string(&key).map_err(|e| GoldenError::Read {
+            path: path.to_string(),
+            message: e.to_string(),
+        })?;
+        serde_json::from_str(&content).map_err(|e| GoldenError::Read {
+            path: path.to_string(),
+            message: e.to_string(),
+        })?
+    } else if RECORD.load(Ordering::Relaxed) {
+        record(&key, actual).map_err(|e| GoldenError::Write {
+            path: path.to_string(),
+            message: e.to_string(),
+        })?;
+        info!(path = %path, "Recorded golden file");
+        actual.clone()
+    } else {
+        return Err(GoldenError::Missing {
+            path: path.to_string(),
+        });
+    };
+    let golden = Arc::new(golden);
+    goldens.insert(key, golden.clone());
+    Ok(golden)
+}
+
+fn record(path: &Path, value: &Value) -> std::io::Result<()> {
+    if let Some(dir) = path.parent().filter(|d| !d.as_os_str().is_empty()) {
+        std::fs::create_dir_all(dir)?;
+    }
+    let mut content = serde_json::to_string_pretty(value)?;
+    content.push('\n');
+    std::fs::write(path, content)
+}
+
+/// Removes `ignore_fields` from `value` (see the module docs for the syntax).
+pub fn normalize(value: &mut Value, ignore_fields: &[String]) {
+    for field in ignore_fields {
+        let segments: Vec<&str> = field.split('.').collect();
+        match segments.as_slice() {
+            [name] => remove_everywhere(value, name),
+            _ => remove_path(value, &segments),
+        }
+    }
+}
+
+fn remove_everywhere(value: &mut Value, name: &str) {
+    match value {
+        Value::Object(map) => {
+            map.remove(name);
+            map.values_mut().for_each(|v| remove_everywhere(v, name));
+        }
+        Value::Array(items) => items.iter_mut().for_each(|v| remove_everywhere(v, name)),
+        _ => {}
+    }
+}
+
+fn remove_path(value: &mut Value, segments: &[&str]) {
+    let [segment, rest @ ..] = segments else {
+        return;
+    };
+    if rest.is_empty() {
+        match value {
+            Value::Object(map) if *segment == "*" => map.clear(),
+            Value::Object(map) => {
+                map.remove(*segment);
+            }
+            Value::Array(items) if *segment == "*" => items.clear(),
+            Value::Array(items) => {
+                if let Some(i) = segment.parse::<usize>().ok().filter(|&i| i < items.len()) {
+                    items.remove(i);
+                }
+            }
+            _ => {}
+        }
+        return;
+    }
+    match value {
+        Value::Object(map) if *segment == "*" => {
+            map.values_mut().for_each(|v| remove_path(v, rest));
+        }
+        Value::Object(map) => {
+            if let Some(child) = map.get_mut(*segment) {
+                remove_path(child, rest);
+            }
+        }
+        Value::Array(items) if *segment == "*" => {
+            items.iter_mut().for_each(|v| remove_path(v, rest));
+        }
+        Value::Array(items) => {
+            if let Some(child) = segment.parse::<usize>().ok().and_then(|i| items.get_mut(i)) {
+                remove_path(child, rest);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// The first place `actual` differs from `expected`: its location and both
+/// values (compact JSON, `<missing>` for absent keys and elements).
+fn first_difference(
+    expected: &Value,
+    actual: &Value,
+    location: &str,
+) -> Option<(String, String, String)> {
+    const MISSING: &str = "<missing>";
+    match (expected, actual) {
+        (Value::Object(e), Value::Object(a)) => {
+            let mut keys: Vec<&String> = e.keys().chain(a.keys()).collect();
+            keys.sort();
+            keys.dedup();
+            keys.into_iter().find_map(|key| {
+                let child = format!("{}.{}", location, key);
+                match (e.get(key), a.get(key)) {
+                    (Some(ev), Some(av)) => first_difference(ev, av, &child),
+                    (Some(ev), None) => Some((child, ev.to_string(), MISSING.to_string())),
+                    (None, Some(av)) => Some((child, MISSING.to_string(), av.to_string())),
+                    (None, None) => None,
+                }
+            })
+        }
+        (Value::Array(e), Value::Array(a)) => (0..e.len().max(a.len())).find_map(|i| {
+            let child = format!("{}[{}]", location, i);
+            match (e.get(i), a.get(i)) {
+                (Some(ev), Some(av)) => first_difference(ev, av, &child),
+                (Some(ev), None) => Some((child, ev.to_string(), MISSING.to_string())),
+                (None, Some(av)) => Some((child, MISSING.to_string(), av.to_string())),
+                (None, None) => None,
+            }
+        }),
+        _ if expected == actual => None,
+        _ => Some((
+            location.to_string(),
+            expected.to_string(),
+            actual.to_string(),
+        )),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn ignore(fields: &[&str]) -> Vec<String> {
+        fields.iter().map(|f| f.to_string()).collect()
+    }
+
+    #[test]
+    fn normalize_removes_names_and_paths() {
+        let mut value = json!({
+            "id": 1,
+            "updatedAt": "now",
+            "order": {"updatedAt": "now", "items": [{"id": 7, "sku": "A"}, {"id": 8, "sku": "B"}]}
+        });
+        normalize(&mut value, &ignore(&["updatedAt", "order.items.*.id"]));
+        assert_eq!(
+            value,
+            json!({"id": 1, "order": {"items": [{"sku": "A"}, {"sku": "B"}]}})
+        );
+    }
+
+    #[test]
+    fn reports_first_difference() {
+        let golden = json!({"items": [{"price": 10}, {"price": 20}], "total": 30});
+        assert_eq!(first_difference(&golden, &golden.clone(), "$"), None);
+
+        let actual = json!({"items": [{"price": 10}, {"price": 25}], "total": 35});
+        assert_eq!(
+            first_difference(&golden, &actual, "$"),
+            Some(("$.items[1].price".into(), "20".into(), "25".into()))
+        );
+
+        let missing = json!({"items": [{"price": 10}], "total": 30});
+        assert_eq!(
+            first_difference(&golden, &missing, "$"),
+            Some((
+                "$.items[1]".into(),
+                r#"{"price":20}"#.into(),
+                "<missing>".into()
+            ))
+        );
+    }
+
+    #[test]
+    fn records_then_checks() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("goldens/user.json");
+        let path = path.to_str().unwrap();
+        let fields = ignore(&["requestId"]);
+
+        init(false);
+        assert!(matches!(
+            check(path, &fields, r#"{"name":"ada"}"#),
+            Err(GoldenError::Missing { .. })
+        ));
+
+        init(true);
+        check(path, &fields, r#"{"name":"ada","requestId":"1"}"#).unwrap();
+        init(false);
+        assert!(!std::fs::read_to_string(path).unwrap().contains("requestId"));
+
+        check(path, &fields, r#"{"requestId":"2","name":"ada"}"#).unwrap();
+        assert!(matches!(
+            check(path, &fields, r#"{"name":"bob"}"#),
+            Err(GoldenError::Mismatch { location, .. }) if location == "$.name"
+        ));
+        assert!(matches!(
+            check(path, &fields, "not json"),
+            Err(GoldenError::InvalidResponse(_))
+        ));
+    }
+}
@@ -0,0 +1,209 @@
+//! HdrHistogram interval logs (`HDR_LOG_PATH`).
+//!
+//! Percentile tables flatten a run into one number per label; a latency
+//! heatmap needs the full distribution of every interval. With
+//! `HDR_LOG_PATH` set, each histogram rotation (`HISTOGRAM_ROTATION_INTERVAL`,
+//! or an early rotation by the memory guard) first appends the interval that
+//! just ended to an interval log, the `.hlog` format read by
+//! HistogramLogAnalyzer, `HistogramLogProcessor` and the other HdrHistogram
+//! plotting tools. The interval still open at the end of the test is written
+//! with the final report.
+//!
+//! Every interval is written as one untagged line for single requests, plus
+//! `scenario:<name>` and `step:<scenario>:<step>` tagged lines. Values are in
+//! microseconds; timestamps are seconds since the log's `BaseTime`.
+
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+use std::path::Path;
+use std::sync::Mutex;
+use std::time::{Duration, SystemTime};
+
+use hdrhistogram::serialization::interval_log::{IntervalLogWriterBuilder, Tag};
+use hdrhistogram::serialization::V2DeflateSerializer;
+use hdrhistogram::Histogram;
+use tracing::warn;
+
+use crate::percentiles::{
+    GLOBAL_REQUEST_PERCENTILES, GLOBAL_SCENARIO_PERCENTILES, GLOBAL_STEP_PERCENTILES,
+};
+
+/// One interval's histogram; `None` tags the single-request latencies.
+pub type TaggedHistogram = (Option<String>, Histogram<u64>);
+
+/// An interval log being appended to.
+pub struct HdrLog<W: Write> {
+    writer: W,
+    serializer: V2DeflateSerializer,
+    base_time: SystemTime,
+    interval_start: SystemTime,
+}
+
+impl HdrLog<BufWriter<File>> {
+    /// Creates (or truncates) the log at `path` and writes its header.
+    pub fn create(path: &Path) -> io::Result<Self> {
+        Self::new(BufWriter::new(File::create(path)?), SystemTime::now())
+    }
+}
+
+impl<W: Write> HdrLog<W> {
+    /// Writes the header; the first interval starts at `now`.
+    pub fn new(mut writer: W, now: SystemTime) -> io::Result<Self> {
+        let mut serializer = V2DeflateSerializer::new();
+        IntervalLogWriterBuilder::new()
+            .add_comment("Logged with rust_loadtest")
+            .add_comment("Latencies in microseconds")
+            .with_start_time(now)
+            .with_base_time(now)
+            .begin_log_with(&mut writer, &mut serializer)?;
+        writer.flush()?;
+        Ok(Self {
+            writer,
+            serializer,
+            base_time: now,
+            interval_start: now,
+        })
+    }
+
+    /// Appends the interval ending at `now`. Empty histograms are skipped,
+    /// but the next interval still starts at `now`.
+    pub fn write_interval(
+        &mut self,
+        histograms: &[TaggedHistogram],
+        now: SystemTime,
+    ) -> io::Result<()> {
+        let start = self
+            .interval_start
+            .duration_since(self.base_time)
+            .unwrap_or(Duration::ZERO);
+        let duration = now
+            .duration_since(self.interval_start)
+            .unwrap_or(Duration::ZERO);
+        self.interval_start = now;
+
+        // The builder writes nothing without comments or times, so each
+        // interval appends only its histogram lines.
+        let mut log = IntervalLogWriterBuilder::new()
+            .begin_log_with(&mut self.writer, &mut self.serializer)?;
+        for (tag, histogram) in histograms.iter().filter(|(_, h)| !h.is_empty()) {
+            let tag = tag.as_deref().map(sanitize_tag);
+            log.write_histogram(
+                histogram,
+                start,
+                duration,
+                tag.as_deref().and_then(Tag::new),
+            )
+            .map_err(|e| io::Error::other(e.to_string()))?;
+        }
+        self.writer.flush()
+    }
+
+    /// The underlying writer.
+    pub fn get_ref(&self) -> &W {
+        &self.writer
+    }
+}
+
+/// Tags can't contain commas, spaces or line breaks.
+fn sanitize_tag(tag: &str) -> String {
+    tag.chars()
+        .map(|c| match c {
+            ',' | ' ' | '\r' | '\n' => '_',
+            c => c,
+        })
+        .collect()
+}
+
+lazy_static::lazy_static! {
+    static ref HDR_LOG: Mutex<Option<HdrLog<BufWriter<File>>>> = Mutex::new(None);
+}
+
+/// Starts logging intervals to `path`, replacing any earlier log.
+pub fn open(path: &Path) -> io::Result<()> {
+    let log = HdrLog::create(path)?;
+    *HDR_LOG.lock().unwrap_or_else(|e| e.into_inner()) = Some(log);
+    Ok(())
+}
+
+/// The global trackers' histograms for the current interval.
+fn global_histograms() -> Vec<TaggedHistogram> {
+    let mut histograms: Vec<TaggedHistogram> = GLOBAL_REQUEST_PERCENTILES
+        .histogram()
+        .map(|h| (None, h))
+        .into_iter()
+        .collect();
+    for (label, histogram) in GLOBAL_SCENARIO_PERCENTILES.histograms() {
+        histograms.push((Some(format!("scenario:{}", label)), histogram));
+    }
+    for (label, histogram) in GLOBAL_STEP_PERCENTILES.histograms() {
+        histograms.push((Some(format!("step:{}", label)), histogram));
+    }
+    histograms
+}
+
+/// Appends the global trackers' current interval to the log; a no-op
+/// unless `HDR_LOG_PATH` is set. Called before each rotation clears them.
+pub fn write_global_interval() {
+    let mut guard = HDR_LOG.lock().unwrap_or_else(|e| e.into_inner());
+    let Some(log) = guard.as_mut() else {
+        return;
+    };
+    if let Err(e) = log.write_interval(&global_histograms(), SystemTime::now()) {
+        warn!(error = %e, "Failed to write HDR interval log");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use hdrhistogram::serialization::interval_log::{IntervalLogIterator, LogEntry};
+
+    fn histogram(values: &[u64]) -> Histogram<u64> {
+        let mut h = Histogram::new_with_bounds(1, 60_000_000, 3).unwrap();
+        for v in values {
+            h.record(*v).unwrap();
+        }
+        h
+    }
+
+    #[test]
+    fn writes_tagged_intervals_readable_as_hlog() {
+        let t0 = SystemTime::UNIX_EPOCH + Duration::from_secs(1_700_000_000);
+        let mut log = HdrLog::new(Vec::new(), t0).unwrap();
+        log.write_interval(
+            &[
+                (None, histogram(&[1_000, 2_000])),
+                (
+                    Some("step:checkout:Pay now".to_string()),
+                    histogram(&[5_000]),
+                ),
+                (Some("scenario:idle".to_string()), histogram(&[])),
+            ],
+            t0 + Duration::from_secs(10),
+        )
+        .unwrap();
+        log.write_interval(&[(None, histogram(&[3_000]))], t0 + Duration::from_secs(20))
+            .unwrap();
+
+        let text = String::from_utf8(log.get_ref().clone()).unwrap();
+        assert!(text.contains("#[BaseTime: 1700000000.000"));
+        assert!(text.contains("Tag=step:checkout:Pay_now,"));
+        assert!(!text.contains("scenario:idle"));
+
+        let intervals: Vec<_> = IntervalLogIterator::new(text.as_bytes())
+            .filter_map(|e| match e.unwrap() {
+                LogEntry::Interval(h) => Some(h),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(intervals.len(), 3);
+        assert_eq!(intervals[0].tag(), None);
+        assert_eq!(intervals[0].start_timestamp(), Duration::ZERO);
+        assert_eq!(intervals[0].duration(), Duration::from_secs(10));
+        assert_eq!(
+            intervals[1].tag().map(|t| t.as_str()),
+            Some("step:checkout:Pay_now")
+        );
+        assert_eq!(intervals[2].start_timestamp(), Duration::from_secs(10));
+    }
+}
@@ -0,0 +1,391 @@
+//! Layered request headers for scenario steps.
+//!
+//! A step's request headers come from five layers, lowest precedence first:
+//!
+//! 1. **global** — `CUSTOM_HEADERS` / `config.customHeaders`, for every scenario
+//! 2. **client** — the virtual user's client profile (see
+//!    [`crate::client_profiles`])
+//! 3. **scenario** — the scenario's `headers:`
+//! 4. **step** — the step request's `headers:`
+//! 5. **auth** — the scenario's `auth:` block (bearer token, basic credentials
+//!    or an API-key header)
+//!
+//! Every layer supports variable substitution (`${token}`), rendered per
+//! request; values without variables are constant and cost nothing to render.
+//! When the same header (case-insensitive) is set by more than one layer, the
+//! higher layer wins and [`conflicts`] reports it, so a plan that silently
+//! overrides, say, a global `Authorization` can be spotted at load time.
+//! Middleware ([`crate::middleware`]) runs after all layers.
+
+use std::borrow::Cow;
+use std::fmt;
+use std::str::FromStr;
+use std::sync::{Arc, RwLock};
+
+use base64::Engine as _;
+use reqwest::header::{HeaderName, HeaderValue};
+
+use crate::scenario::{Scenario, ScenarioContext};
+use crate::substitution::Template;
+use crate::utils::parse_headers_with_escapes;
+
+/// Where a header comes from, in ascending precedence.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum HeaderSource {
+    Global,
+    Client,
+    Scenario,
+    Step,
+    Auth,
+}
+
+impl HeaderSource {
+    pub fn label(&self) -> &'static str {
+        match self {
+            HeaderSource::Global => "global",
+            HeaderSource::Client => "client",
+            HeaderSource::Scenario => "scenario",
+            HeaderSource::Step => "step",
+            HeaderSource::Auth => "auth",
+        }
+    }
+}
+
+impl fmt::Display for HeaderSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.label())
+    }
+}
+
+/// Headers of one layer, parsed into templates.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct HeaderLayer {
+    pub headers: Vec<(String, Template)>,
+}
+
+impl HeaderLayer {
+    pub fn new<I, K, V>(headers: I) -> Self
+    where
+        I: IntoIterator<Item = (K, V)>,
+        K: Into<String>,
+        V: AsRef<str>,
+    {
+        let mut headers: Vec<(String, Template)> = headers
+            .into_iter()
+            .map(|(name, value)| (name.into(), Template::parse(value.as_ref())))
+            .collect();
+        headers.sort_by(|a, b| a.0.cmp(&b.0));
+        Self { headers }
+    }
+
+    /// Parses a `CUSTOM_HEADERS` string (`Name:value,Other:value`, `\,` for a
+    /// literal comma). Names, and values without variables, must be valid
+    /// HTTP header names and values.
+    pub fn from_custom_headers(custom_headers: Option<&str>) -> Result<Self, String> {
+        let mut headers = Vec::new();
+        for pair in parse_headers_with_escapes(custom_headers.unwrap_or_default()) {
+            let pair = pair.trim();
+            if pair.is_empty() {
+                continue;
+            }
+            let (name, value) = pair.split_once(':').ok_or_else(|| {
+                format!(
+                    "Invalid header format in CUSTOM_HEADERS: '{}'. Expected 'Name:Value'.",
+                    pair
+                )
+            })?;
+            let (name, value) = (name.trim(), value.trim());
+            if name.is_empty() {
+                return Err(format!(
+                    "Invalid header format: Header name cannot be empty in '{}'.",
+                    pair
+                ));
+            }
+            HeaderName::from_str(name)
+                .map_err(|e| format!("Invalid header name: {}. Name: '{}'", e, name))?;
+            if Template::parse(value).is_static() {
+                HeaderValue::from_str(value).map_err(|e| {
+                    format!(
+                        "Invalid header value for '{}': {}. Value: '{}'",
+                        name, e, value
+                    )
+                })?;
+            }
+            headers.push((name.to_string(), value.to_string()));
+        }
+        Ok(Self::new(headers))
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.headers.is_empty()
+    }
+
+    fn names(&self) -> impl Iterator<Item = &str> {
+        self.headers.iter().map(|(name, _)| name.as_str())
+    }
+}
+
+/// Credentials injected by a scenario's `auth:` block.
+#[derive(Debug, Clone, PartialEq)]
+pub enum AuthScheme {
+    /// `Authorization: Bearer <token>`
+    Bearer(Template),
+    /// `Authorization: Basic base64(<username>:<password>)`
+    Basic {
+        username: Template,
+        password: Template,
+    },
+    /// An API key in a header of its own, e.g. `X-Api-Key`.
+    ApiKey { header: String, value: Template },
+}
+
+impl AuthScheme {
+    /// The header this scheme sets.
+    pub fn header_name(&self) -> &str {
+        match self {
+            AuthScheme::Bearer(_) | AuthScheme::Basic { .. } => "Authorization",
+            AuthScheme::ApiKey { header, .. } => header,
+        }
+    }
+
+    fn render(&self, context: &ScenarioContext) -> String {
+        match self {
+            AuthScheme::Bearer(token) => format!("Bearer {}", token.render(context)),
+            AuthScheme::Basic { username, password } => {
+                let credentials =
+                    format!("{}:{}", username.render(context), password.render(context));
+                format!(
+                    "Basic {}",
+                    base64::engine::general_purpose::STANDARD.encode(credentials)
+                )
+            }
+            AuthScheme::ApiKey { value, .. } => value.render(context).into_owned(),
+        }
+    }
+}
+
+/// The layers a scenario adds on top of the global one.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ScenarioHeaders {
+    pub scenario: HeaderLayer,
+    pub auth: Option<AuthScheme>,
+}
+
+lazy_static::lazy_static! {
+    static ref GLOBAL_HEADERS: RwLock<Arc<HeaderLayer>> = RwLock::new(Arc::default());
+}
+
+/// Sets the global layer used by executors created afterwards.
+pub fn set_global_headers(layer: HeaderLayer) {
+    *GLOBAL_HEADERS.write().unwrap() = Arc::new(layer);
+}
+
+/// The current global layer.
+pub fn global_headers() -> Arc<HeaderLayer> {
+    GLOBAL_HEADERS.read().unwrap().clone()
+}
+
+/// The headers to send for a step: every layer rendered against `context`,
+/// each name taking its value from the highest layer that sets it.
+pub fn resolve<'a>(
+    global: &'a HeaderLayer,
+    client: &'a HeaderLayer,
+    scenario: &'a ScenarioHeaders,
+    step: &'a [(String, Template)],
+    context: &'a ScenarioContext,
+) -> Vec<(&'a str, Cow<'a, str>)> {
+    let mut resolved: Vec<(&str, Cow<str>)> = Vec::with_capacity(
+        global.headers.len()
+            + client.headers.len()
+            + scenario.scenario.headers.len()
+            + step.len()
+            + 1,
+    );
+    let mut set = |name: &'a str, value: Cow<'a, str>| match resolved
+        .iter_mut()
+        .find(|(existing, _)| existing.eq_ignore_ascii_case(name))
+    {
+        Some(entry) => *entry = (name, value),
+        None => resolved.push((name, value)),
+    };
+    for (name, value) in global
+        .headers
+        .iter()
+        .chain(&client.headers)
+        .chain(&scenario.scenario.headers)
+        .chain(step)
+    {
+        set(name, value.render(context));
+    }
+    if let Some(auth) = &scenario.auth {
+        set(auth.header_name(), Cow::Owned(auth.render(context)));
+    }
+    resolved
+}
+
+/// A header set by more than one layer.
+#[derive(Debug, Clone, PartialEq)]
+pub struct HeaderConflict {
+    pub scenario: String,
+    /// The step, when a step's own header is involved.
+    pub step: Option<String>,
+    pub header: String,
+    /// Layers setting the header, lowest first; the last one wins.
+    pub sources: Vec<HeaderSource>,
+}
+
+impl fmt::Display for HeaderConflict {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let sources: Vec<&str> = self.sources.iter().map(|s| s.label()).collect();
+        write!(
+            f,
+            "header '{}' in scenario '{}'",
+            self.header, self.scenario
+        )?;
+        if let Some(step) = &self.step {
+            write!(f, " step '{}'", step)?;
+        }
+        write!(
+            f,
+            " is set by {} layers; {} wins",
+            sources.join(", "),
+            sources.last().copied().unwrap_or_default()
+        )
+    }
+}
+
+/// Headers set by more than one layer in `scenarios`. Conflicts that don't
+/// involve a step's own headers are reported once per scenario. Client
+/// profiles are left out: overriding, say, the global `User-Agent` is what
+/// they are for.
+pub fn conflicts<'a>(
+    global: &HeaderLayer,
+    scenarios: impl IntoIterator<Item = &'a Scenario>,
+) -> Vec<HeaderConflict> {
+    let mut found = Vec::new();
+    for scenario in scenarios {
+        let layers = &scenario.headers;
+        let mut base: Vec<(String, Vec<HeaderSource>)> = Vec::new();
+        let add = |list: &mut Vec<(String, Vec<HeaderSource>)>, name: &str, source| match list
+            .iter_mut()
+            .find(|(existing, _)| existing.eq_ignore_ascii_case(name))
+        {
+            Some((_, sources)) if !sources.contains(&source) => sources.push(source),
+            Some(_) => {}
+            None => list.push((name.to_string(), vec![source])),
+        };
+        for name in global.names() {
+            add(&mut base, name, HeaderSource::Global);
+        }
+        for name in layers.scenario.names() {
+            add(&mut base, name, HeaderSource::Scenario);
+        }
+        if let Some(auth) = &layers.auth {
+            add(&mut base, auth.header_name(), HeaderSource::Auth);
+        }
+        for (name, sources) in &base {
+            if sources.len() > 1 {
+                found.push(HeaderConflict {
+                    scenario: scenario.name.clone(),
+                    step: None,
+                    header: name.clone(),
+                    sources: sources.clone(),
+                });
+            }
+        }
+
+        for step in &scenario.steps {
+            let mut step_names: Vec<&String> = step.request.headers.keys().collect();
+            step_names.sort();
+            for name in step_names {
+                let Some((header, sources)) = base
+                    .iter()
+                    .find(|(existing, _)| existing.eq_ignore_ascii_case(name))
+                else {
+                    continue;
+                };
+                let mut sources = sources.clone();
+                sources.push(HeaderSource::Step);
+                sources.sort();
+                found.push(HeaderConflict {
+                    scenario: scenario.name.clone(),
+                    step: Some(step.name.clone()),
+                    header: header.clone(),
+                    sources,
+                });
+            }
+        }
+    }
+    found
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn scenario_headers() -> ScenarioHeaders {
+        ScenarioHeaders {
+            scenario: HeaderLayer::new([("X-Tenant", "${tenant}"), ("Accept", "text/plain")]),
+            auth: Some(AuthScheme::Bearer(Template::parse("${token}"))),
+        }
+    }
+
+    #[test]
+    fn parses_custom_headers() {
+        let layer =
+            HeaderLayer::from_custom_headers(Some("X-A: 1, X-B:a\\,b,Authorization:Bearer x"))
+                .unwrap();
+        let names: Vec<&str> = layer.names().collect();
+        assert_eq!(names, vec!["Authorization", "X-A", "X-B"]);
+        assert_eq!(layer.headers[2].1.source(), "a,b");
+        assert!(HeaderLayer::from_custom_headers(Some("novalue")).is_err());
+        assert!(HeaderLayer::from_custom_headers(Some("Bad Name:1")).is_err());
+        assert!(HeaderLayer::from_custom_headers(Some("X-Id:${id}")).is_ok());
+        assert!(HeaderLayer::from_custom_headers(None).unwrap().is_empty());
+    }
+
+    #[test]
+    fn higher_layers_win_and_substitute() {
+        let global = HeaderLayer::new([
+            ("accept", "*/*"),
+            ("X-Global", "1"),
+            ("User-Agent", "loadtest"),
+        ]);
+        let client = HeaderLayer::new([("User-Agent", "Mobile/1.0"), ("X-Tenant", "none")]);
+        let scenario = scenario_headers();
+        let step = HeaderLayer::new([("X-Tenant", "fixed")]);
+        let mut context = ScenarioContext::new();
+        context.set_variable("tenant".to_string(), "acme".to_string());
+        context.set_variable("token".to_string(), "t0k".to_string());
+
+        let resolved = resolve(&global, &client, &scenario, &step.headers, &context);
+        let get = |name: &str| {
+            resolved
+                .iter()
+                .find(|(n, _)| n.eq_ignore_ascii_case(name))
+                .map(|(_, v)| v.to_string())
+        };
+        assert_eq!(resolved.len(), 5, "{:?}", resolved);
+        assert_eq!(get("Accept").as_deref(), Some("text/plain"));
+        assert_eq!(get("User-Agent").as_deref(), Some("Mobile/1.0"));
+        assert_eq!(get("X-Global").as_deref(), Some("1"));
+        assert_eq!(get("X-Tenant").as_deref(), Some("fixed"));
+        assert_eq!(get("Authorization").as_deref(), Some("Bearer t0k"));
+    }
+
+    #[test]
+    fn renders_basic_and_api_key_auth() {
+        let context = ScenarioContext::new();
+        let basic = AuthScheme::Basic {
+            username: Template::parse("user"),
+            password: Template::parse("pass"),
+        };
+        assert_eq!(basic.render(&context), "Basic dXNlcjpwYXNz");
+        let api_key = AuthScheme::ApiKey {
+            header: "X-Api-Key".to_string(),
+            value: Template::parse("k"),
+        };
+        assert_eq!(api_key.header_name(), "X-Api-Key");
+        assert_eq!(api_key.render(&context), "k");
+    }
+}
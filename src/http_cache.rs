@@ -0,0 +1,254 @@
+//! HTTP cache validation: conditional requests and cache-hit ratios.
+//!
+//! A step with `conditional: true` remembers the `ETag` and `Last-Modified`
+//! validators of its last full response, per worker and URL, and sends them
+//! back as `If-None-Match` / `If-Modified-Since` on the next iteration — the
+//! revalidation flow of a browser or CDN edge. Validators are replaced by
+//! every `2xx` response that carries them and kept on `304 Not Modified`.
+//!
+//! Responses of such steps (and of steps with a `cacheHitRatio` assertion)
+//! are classified as `not_modified` (304), `hit` (a cache status header such
+//! as `X-Cache: HIT` or `CF-Cache-Status: HIT`) or `miss`, and counted in
+//! `http_cache_responses_total{result}`. The running hit ratio per step —
+//! hits and 304s over all classified responses, across all workers — backs
+//! the `cacheHitRatio` assertion.
+
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use reqwest::header::{
+    HeaderMap, HeaderName, ETAG, IF_MODIFIED_SINCE, IF_NONE_MATCH, LAST_MODIFIED,
+};
+use reqwest::RequestBuilder;
+
+use crate::executor::{SessionEntry, SessionKey, SessionSlot, SessionStore};
+use crate::metrics::HTTP_CACHE_RESPONSES_TOTAL;
+
+/// Response headers CDNs and reverse proxies use to report a cache hit.
+const CACHE_STATUS_HEADERS: &[&str] = &[
+    "x-cache",
+    "x-cache-status",
+    "cf-cache-status",
+    "x-proxy-cache",
+    "cdn-cache",
+];
+
+/// Validators never expire on their own; they are replaced by the next full
+/// response.
+const VALIDATOR_LIFETIME: Duration = Duration::from_secs(365 * 24 * 3600);
+
+/// How a response was served.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CacheResult {
+    /// Served from a cache, per a cache status header.
+    Hit,
+    /// `304 Not Modified`: the validators sent are still current.
+    NotModified,
+    /// Served by the origin.
+    Miss,
+}
+
+impl CacheResult {
+    /// Classifies a response by its status and cache status headers.
+    pub fn classify(status: u16, headers: &HeaderMap) -> Self {
+        if status == 304 {
+            return CacheResult::NotModified;
+        }
+        let hit = CACHE_STATUS_HEADERS.iter().any(|name| {
+            headers
+                .get(*name)
+                .and_then(|v| v.to_str().ok())
+                .is_some_and(|v| v.to_ascii_uppercase().contains("HIT"))
+        });
+        if hit {
+            CacheResult::Hit
+        } else {
+            CacheResult::Miss
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            CacheResult::Hit => "hit",
+            CacheResult::NotModified => "not_modified",
+            CacheResult::Miss => "miss",
+        }
+    }
+}
+
+/// Cache hits (including 304s) out of all classified responses for a step.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct CacheRatio {
+    pub hits: u64,
+    pub total: u64,
+}
+
+impl CacheRatio {
+    pub fn ratio(&self) -> f64 {
+        if self.total == 0 {
+            0.0
+        } else {
+            self.hits as f64 / self.total as f64
+        }
+    }
+}
+
+lazy_static::lazy_static! {
+    /// "scenario/step" → running hit ratio.
+    static ref RATIOS: Mutex<HashMap<String, CacheRatio>> = Mutex::new(HashMap::new());
+}
+
+/// Counts a classified response for `scenario`/`step` and returns the
+/// step's running ratio, including this response.
+pub fn record(
+    scenario: &str,
+    step: &str,
+    result: CacheResult,
+    node_id: &str,
+    run_id: &str,
+) -> CacheRatio {
+    HTTP_CACHE_RESPONSES_TOTAL
+        .with_label_values(&[scenario, step, result.label(), node_id, run_id])
+        .inc();
+    let mut ratios = RATIOS.lock().unwrap_or_else(|e| e.into_inner());
+    let ratio = ratios.entry(format!("{}/{}", scenario, step)).or_default();
+    ratio.total += 1;
+    if result != CacheResult::Miss {
+        ratio.hits += 1;
+    }
+    *ratio
+}
+
+/// Resets all hit ratios, e.g. before a new test plan starts.
+pub fn clear() {
+    RATIOS.lock().unwrap_or_else(|e| e.into_inner()).clear();
+}
+
+/// `ETag` / `Last-Modified` validators of a response.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Validators {
+    pub etag: Option<String>,
+    pub last_modified: Option<String>,
+}
+
+impl Validators {
+    /// Validators in `headers`, if any.
+    pub fn from_headers(headers: &HeaderMap) -> Option<Self> {
+        let value = |name: HeaderName| {
+            headers
+                .get(name)
+                .and_then(|v| v.to_str().ok())
+                .map(str::to_string)
+        };
+        let validators = Self {
+            etag: value(ETAG),
+            last_modified: value(LAST_MODIFIED),
+        };
+        (validators != Self::default()).then_some(validators)
+    }
+
+    /// Adds the matching conditional request headers.
+    pub fn apply(&self, mut request: RequestBuilder) -> RequestBuilder {
+        if let Some(etag) = &self.etag {
+            request = request.header(IF_NONE_MATCH, etag);
+        }
+        if let Some(last_modified) = &self.last_modified {
+            request = request.header(IF_MODIFIED_SINCE, last_modified);
+        }
+        request
+    }
+
+    /// Validators stored for `step` and `url` in a worker's session store.
+    pub fn load(session: &SessionStore, scenario: &str, step: &str, url: &str) -> Option<Self> {
+        let entry = session.get(&session_key(session, scenario, step, url))?;
+        Some(Self {
+            etag: entry.variables.get("etag").cloned(),
+            last_modified: entry.variables.get("last_modified").cloned(),
+        })
+    }
+
+    /// Stores these validators for `step` and `url`.
+    pub fn store(&self, session: &mut SessionStore, scenario: &str, step: &str, url: &str) {
+        let variables = [("etag", &self.etag), ("last_modified", &self.last_modified)]
+            .into_iter()
+            .filter_map(|(name, value)| Some((name.to_string(), value.clone()?)))
+            .collect();
+        session.insert(
+            session_key(session, scenario, step, url),
+            SessionEntry {
+                variables,
+                expires_at: Instant::now() + VALIDATOR_LIFETIME,
+            },
+        );
+    }
+}
+
+/// Session store key for a step's validators, one entry per URL.
+fn session_key(session: &SessionStore, scenario: &str, step: &str, url: &str) -> SessionKey {
+    SessionKey {
+        vu: Some(session.vu()),
+        scenario: scenario.to_string(),
+        step: step.to_string(),
+        key: Some(url.to_string()),
+        slot: SessionSlot::Validators,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn headers(pairs: &[(&'static str, &'static str)]) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        for (name, value) in pairs {
+            headers.insert(*name, value.parse().unwrap());
+        }
+        headers
+    }
+
+    #[test]
+    fn classifies_responses() {
+        let none = HeaderMap::new();
+        assert_eq!(CacheResult::classify(304, &none), CacheResult::NotModified);
+        assert_eq!(CacheResult::classify(200, &none), CacheResult::Miss);
+        let hit = headers(&[("x-cache", "Hit from cloudfront")]);
+        assert_eq!(CacheResult::classify(200, &hit), CacheResult::Hit);
+        let miss = headers(&[("cf-cache-status", "MISS")]);
+        assert_eq!(CacheResult::classify(200, &miss), CacheResult::Miss);
+
+        record("ratio-test", "step", CacheResult::Miss, "node", "run");
+        let ratio = record(
+            "ratio-test",
+            "step",
+            CacheResult::NotModified,
+            "node",
+            "run",
+        );
+        assert_eq!(ratio, CacheRatio { hits: 1, total: 2 });
+        assert_eq!(ratio.ratio(), 0.5);
+    }
+
+    #[test]
+    fn validators_round_trip_through_session() {
+        assert_eq!(Validators::from_headers(&HeaderMap::new()), None);
+        let validators = Validators::from_headers(&headers(&[("etag", "\"v1\"")])).unwrap();
+        assert_eq!(validators.etag.as_deref(), Some("\"v1\""));
+        assert_eq!(validators.last_modified, None);
+
+        let mut session = SessionStore::new();
+        validators.store(&mut session, "Feed", "Get", "http://example.com/a");
+        assert_eq!(
+            Validators::load(&session, "Feed", "Get", "http://example.com/a"),
+            Some(validators)
+        );
+        assert_eq!(
+            Validators::load(&session, "Feed", "Get", "http://example.com/b"),
+            None
+        );
+        assert_eq!(
+            Validators::load(&session, "Other", "Get", "http://example.com/a"),
+            None
+        );
+    }
+}
@@ -0,0 +1,566 @@
+//! `curl` command import for quick single-request steps.
+//!
+//! Parses a `curl ...` command line — as copied from browser dev tools or API
+//! docs — into a request. Supported options:
+//! - `-X/--request` → method
+//! - `-H/--header` → headers
+//! - `-d/--data/--data-raw/--data-binary/--data-ascii/--data-urlencode` → body
+//!   (multiple values are joined with `&`; implies `POST`)
+//! - `--json` → JSON body with `Content-Type`/`Accept` headers (implies `POST`)
+//! - `-G/--get` → data is appended to the query string instead
+//! - `-I/--head` → `HEAD`
+//! - `-u/--user` → `Authorization: Basic ...`
+//! - `-A/--user-agent`, `-e/--referer`, `-b/--cookie` → headers
+//!
+//! Output-only and transport options (`-s`, `-v`, `-L`, `--compressed`,
+//! `-o`, `--max-time`, ...) are ignored; anything else is reported as a
+//! warning.
+
+use super::{build_config, split_url, ImportError, ImportResult};
+use crate::scenario::RequestConfig;
+use crate::yaml_config::{YamlRequest, YamlScenario, YamlScenarioConfig, YamlStep};
+use base64::Engine;
+use std::collections::HashMap;
+
+/// A request parsed from a curl command.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CurlRequest {
+    pub method: String,
+
+    /// Absolute URL as given on the command line.
+    pub url: String,
+
+    /// Headers in command-line order.
+    pub headers: Vec<(String, String)>,
+
+    pub body: Option<String>,
+
+    /// Options that were ignored or could not be represented.
+    pub warnings: Vec<String>,
+}
+
+impl CurlRequest {
+    /// Convert to a [`RequestConfig`] using the absolute URL as the path.
+    pub fn to_request_config(&self) -> RequestConfig {
+        RequestConfig {
+            method: self.method.clone(),
+            path: self.url.clone(),
+            body: self.body.clone(),
+            body_size: None,
+            headers: self.headers.iter().cloned().collect(),
+            protobuf: None,
+            host: None,
+        }
+    }
+
+    /// Convert to a [`YamlRequest`] with the given path.
+    pub fn to_yaml_request(&self, path: String) -> YamlRequest {
+        YamlRequest {
+            method: self.method.clone(),
+            path,
+            query_params: None,
+            headers: if self.headers.is_empty() {
+                None
+            } else {
+                Some(self.headers.iter().cloned().collect())
+            },
+            body: self.body.clone(),
+            body_size: None,
+            protobuf: None,
+            host: None,
+        }
+    }
+}
+
+/// Options that take no value and do not affect the request.
+const IGNORED_FLAGS: &[&str] = &[
+    "-s",
+    "--silent",
+    "-S",
+    "--show-error",
+    "-sS",
+    "-sL",
+    "-sSL",
+    "-v",
+    "--verbose",
+    "-i",
+    "--include",
+    "-L",
+    "--location",
+    "--compressed",
+    "-k",
+    "--insecure",
+    "-f",
+    "--fail",
+    "-#",
+    "--progress-bar",
+    "-N",
+    "--no-buffer",
+    "--http1.1",
+    "--http2",
+];
+
+/// Options that take a value and do not affect the request.
+const IGNORED_WITH_VALUE: &[&str] = &[
+    "-o",
+    "--output",
+    "-m",
+    "--max-time",
+    "--connect-timeout",
+    "-w",
+    "--write-out",
+    "--retry",
+];
+
+/// Parse a curl command line into a request.
+pub fn parse_curl(command: &str) -> Result<CurlRequest, ImportError> {
+    let tokens = tokenize(command)?;
+    let mut args = tokens.into_iter();
+
+    if args.next().as_deref() != Some("curl") {
+        return Err(ImportError::InvalidFormat(
+            "command must start with 'curl'".to_string(),
+        ));
+    }
+
+    let mut method: Option<String> = None;
+    let mut url: Option<String> = None;
+    let mut headers: Vec<(String, String)> = Vec::new();
+    let mut data: Vec<String> = Vec::new();
+    let mut json_body = false;
+    let mut get = false;
+    let mut head = false;
+    let mut warnings = Vec::new();
+
+    while let Some(arg) = args.next() {
+        // Support --opt=value as well as --opt value
+        let (flag, inline_value) = match arg.split_once('=') {
+            Some((f, v)) if f.starts_with("--") => (f.to_string(), Some(v.to_string())),
+            _ => (arg.clone(), None),
+        };
+        let mut value = |name: &str| -> Result<String, ImportError> {
+            inline_value
+                .clone()
+                .or_else(|| args.next())
+                .ok_or_else(|| ImportError::InvalidFormat(format!("{} requires a value", name)))
+        };
+
+        match flag.as_str() {
+            "-X" | "--request" => method = Some(value(&flag)?.to_uppercase()),
+            "-H" | "--header" => {
+                let raw = value(&flag)?;
+                match raw.split_once(':') {
+                    Some((name, val)) => {
+                        headers.push((name.trim().to_string(), val.trim().to_string()))
+                    }
+                    None => warnings.push(format!("malformed header '{}' skipped", raw)),
+                }
+            }
+            "-d" | "--data" | "--data-raw" | "--data-binary" | "--data-ascii"
+            | "--data-urlencode" => {
+                let d = value(&flag)?;
+                if d.starts_with('@') && flag != "--data-raw" {
+                    warnings.push(format!(
+                        "{} {}: file bodies are not supported; value used literally",
+                        flag, d
+                    ));
+                }
+                data.push(d);
+            }
+            "--json" => {
+                data.push(value(&flag)?);
+                json_body = true;
+            }
+            "-G" | "--get" => get = true,
+            "-I" | "--head" => head = true,
+            "-u" | "--user" => {
+                let credentials = value(&flag)?;
+                let encoded = base64::engine::general_purpose::STANDARD.encode(credentials);
+                headers.push(("Authorization".to_string(), format!("Basic {}", encoded)));
+            }
+            "-A" | "--user-agent" => headers.push(("User-Agent".to_string(), value(&flag)?)),
+            "-e" | "--referer" => headers.push(("Referer".to_string(), value(&flag)?)),
+            "-b" | "--cookie" => headers.push(("Cookie".to_string(), value(&flag)?)),
+            "--url" => url = Some(value(&flag)?),
+            f if IGNORED_FLAGS.contains(&f) => {
+                if f == "-k" || f == "--insecure" {
+                    warnings.push(
+                        "--insecure ignored; set skipTlsVerify in the global config instead"
+                            .to_string(),
+                    );
+                }
+            }
+            f if IGNORED_WITH_VALUE.contains(&f) => {
+                value(f)?;
+            }
+            f if f.starts_with('-') && f.len() > 1 => {
+                warnings.push(format!("unsupported option '{}' ignored", f));
+            }
+            _ => {
+                if url.is_some() {
+                    warnings.push(format!("extra URL '{}' ignored", arg));
+                } else {
+                    url = Some(arg);
+                }
+            }
+        }
+    }
+
+    let mut url = url.ok_or_else(|| ImportError::InvalidFormat("no URL given".to_string()))?;
+
+    let has_header = |headers: &[(String, String)], name: &str| {
+        headers.iter().any(|(h, _)| h.eq_ignore_ascii_case(name))
+    };
+
+    let mut body = None;
+    if !data.is_empty() {
+        let joined = data.join("&");
+        if get {
+            let separator = if url.contains('?') { '&' } else { '?' };
+            url = format!("{}{}{}", url, separator, joined);
+        } else {
+            if json_body {
+                if !has_header(&headers, "Content-Type") {
+                    headers.push(("Content-Type".to_string(), "application/json".to_string()));
+                }
+                if !has_header(&headers, "Accept") {
+                    headers.push(("Accept".to_string(), "application/json".to_string()));
+                }
+            } else if !has_header(&headers, "Content-Type") {
+                headers.push((
+                    "Content-Type".to_string(),
+                    "application/x-www-form-urlencoded".to_string(),
+                ));
+            }
+            body = Some(joined);
+        }
+    }
+
+    let method = method.unwrap_or_else(|| {
+        if head {
+            "HEAD".to_string()
+        } else if body.is_some() {
+            "POST".to_string()
+        } else {
+            "GET".to_string()
+        }
+    });
+
+    Ok(CurlRequest {
+        method,
+        url,
+        headers,
+        body,
+        warnings,
+    })
+}
+
+/// Import a curl command as a single-step scenario.
+pub fn import_curl(command: &str) -> Result<ImportResult, ImportError> {
+    let parsed = parse_curl(command)?;
+    let (origin, path) = split_url(&parsed.url);
+    if origin.is_empty() {
+        return Err(ImportError::InvalidFormat(format!(
+            "URL '{}' must be absolute (http:// or https://)",
+            parsed.url
+        )));
+    }
+
+    let name_path = path.split('?').next().unwrap_or(&path).to_string();
+    let step = YamlStep {
+        name: Some(format!("{} {}", parsed.method, name_path)),
+        request: parsed.to_yaml_request(path),
+        from_curl: None,
+        extract: vec![],
+        assertions: vec![],
+        cache: None,
+        think_time: None,
+        tags: HashMap::new(),
+        paginate: None,
+        max_concurrent: None,
+        slo: None,
+        shared: vec![],
+        rendezvous: None,
+        conditional: false,
+        idempotency_key: None,
+        tcp_check: None,
+        expected_status: vec![],
+    };
+
+    let scenario = YamlScenario {
+        name: "curl request".to_string(),
+        weight: 1.0,
+        steps: vec![step],
+        data_file: None,
+        data_source: None,
+        config: YamlScenarioConfig::default(),
+        network_profile: None,
+        tags: HashMap::new(),
+        max_concurrent: None,
+        depends_on: Vec::new(),
+        iterations: None,
+        client: None,
+        headers: Default::default(),
+        auth: None,
+        optional: false,
+        request_defaults: None,
+        rps: None,
+    };
+
+    Ok(ImportResult {
+        config: build_config(
+            "Imported curl command",
+            "Generated by rust_loadtest import curl",
+            &origin,
+            vec![scenario],
+        ),
+        warnings: parsed.warnings,
+    })
+}
+
+/// Split a command line into words, POSIX-shell style.
+///
+/// Handles single quotes, double quotes (with `\"`, `\\`, `\$` escapes),
+/// backslash escapes, and backslash-newline continuations. Windows `^`
+/// continuations from "Copy as cURL (cmd)" are not supported.
+fn tokenize(command: &str) -> Result<Vec<String>, ImportError> {
+    let mut tokens = Vec::new();
+    let mut current = String::new();
+    let mut in_token = false;
+    let mut chars = command.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '\'' => {
+                in_token = true;
+                loop {
+                    match chars.next() {
+                        Some('\'') => break,
+                        Some(ch) => current.push(ch),
+                        None => {
+                            return Err(ImportError::InvalidFormat(
+                                "unterminated single quote".to_string(),
+                            ))
+                        }
+                    }
+                }
+            }
+            '"' => {
+                in_token = true;
+                loop {
+                    match chars.next() {
+                        Some('"') => break,
+                        Some('\\') => match chars.next() {
+                            Some(ch @ ('"' | '\\' | '$' | '`')) => current.push(ch),
+                            Some('\n') => {}
+                            Some(ch) => {
+                                current.push('\\');
+                                current.push(ch);
+                            }
+                            None => break,
+                        },
+                        Some(ch) => current.push(ch),
+                        None => {
+                            return Err(ImportError::InvalidFormat(
+                                "unterminated double quote".to_string(),
+                            ))
+                        }
+                    }
+                }
+            }
+            '\\' => match chars.next() {
+                Some('\n') => {}
+                Some('\r') if chars.peek() == Some(&'\n') => {
+                    chars.next();
+                }
+                Some(ch) => {
+                    in_token = true;
+                    current.push(ch);
+                }
+                None => {}
+            },
+            c if c.is_whitespace() => {
+                if in_token {
+                    tokens.push(std::mem::take(&mut current));
+                    in_token = false;
+                }
+            }
+            c => {
+                in_token = true;
+                current.push(c);
+            }
+        }
+    }
+    if in_token {
+        tokens.push(current);
+    }
+    Ok(tokens)
+}
+
+/// Overlay the explicit fields of `overrides` onto a curl-derived request.
+///
+/// Used for YAML steps that combine `fromCurl` with a partial `request`:
+/// non-empty method/path and any body or host replace the curl values, headers and
+/// query params are merged with the explicit ones winning.
+pub(crate) fn merge_request(base: &CurlRequest, overrides: &YamlRequest) -> YamlRequest {
+    let mut merged = base.to_yaml_request(base.url.clone());
+    if !overrides.method.is_empty() {
+        merged.method = overrides.method.clone();
+    }
+    if !overrides.path.is_empty() {
+        merged.path = overrides.path.clone();
+    }
+    if let Some(extra) = &overrides.headers {
+        let headers = merged.headers.get_or_insert_with(HashMap::new);
+        for (name, value) in extra {
+            headers.retain(|h, _| !h.eq_ignore_ascii_case(name));
+            headers.insert(name.clone(), value.clone());
+        }
+    }
+    if overrides.query_params.is_some() {
+        merged.query_params = overrides.query_params.clone();
+    }
+    if overrides.body.is_some() || overrides.body_size.is_some() {
+        merged.body = overrides.body.clone();
+        merged.body_size = overrides.body_size.clone();
+    }
+    if overrides.protobuf.is_some() {
+        merged.protobuf = overrides.protobuf.clone();
+    }
+    if overrides.host.is_some() {
+        merged.host = overrides.host.clone();
+    }
+    merged
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_get_with_headers() {
+        let req = parse_curl(
+            "curl 'https://api.example.com/users?page=2' -H 'Accept: application/json' -H \"X-Trace: a b\"",
+        )
+        .unwrap();
+        assert_eq!(req.method, "GET");
+        assert_eq!(req.url, "https://api.example.com/users?page=2");
+        assert_eq!(
+            req.headers,
+            vec![
+                ("Accept".to_string(), "application/json".to_string()),
+                ("X-Trace".to_string(), "a b".to_string()),
+            ]
+        );
+        assert!(req.body.is_none());
+    }
+
+    #[test]
+    fn data_implies_post_and_form_content_type() {
+        let req = parse_curl("curl https://example.com/login -d user=bob -d pass=secret").unwrap();
+        assert_eq!(req.method, "POST");
+        assert_eq!(req.body.as_deref(), Some("user=bob&pass=secret"));
+        assert!(req.headers.contains(&(
+            "Content-Type".to_string(),
+            "application/x-www-form-urlencoded".to_string()
+        )));
+    }
+
+    #[test]
+    fn handles_multiline_dev_tools_copy() {
+        let cmd = "curl 'https://api.example.com/orders' \\\n  -X 'PUT' \\\n  -H 'content-type: application/json' \\\n  --data-raw '{\"id\":42,\"note\":\"it'\\''s here\"}' \\\n  --compressed";
+        let req = parse_curl(cmd).unwrap();
+        assert_eq!(req.method, "PUT");
+        assert_eq!(req.body.as_deref(), Some(r#"{"id":42,"note":"it's here"}"#));
+        assert_eq!(req.headers.len(), 1);
+        assert!(req.warnings.is_empty());
+    }
+
+    #[test]
+    fn json_flag_sets_headers() {
+        let req = parse_curl(r#"curl --json '{"a":1}' https://example.com/items"#).unwrap();
+        assert_eq!(req.method, "POST");
+        assert!(req
+            .headers
+            .contains(&("Content-Type".to_string(), "application/json".to_string())));
+        assert!(req
+            .headers
+            .contains(&("Accept".to_string(), "application/json".to_string())));
+    }
+
+    #[test]
+    fn get_flag_moves_data_to_query() {
+        let req = parse_curl("curl -G https://example.com/search?x=1 --data q=rust").unwrap();
+        assert_eq!(req.method, "GET");
+        assert_eq!(req.url, "https://example.com/search?x=1&q=rust");
+        assert!(req.body.is_none());
+    }
+
+    #[test]
+    fn basic_auth_and_long_options() {
+        let req =
+            parse_curl("curl --request=delete --user alice:pw https://example.com/x").unwrap();
+        assert_eq!(req.method, "DELETE");
+        assert!(req.headers.contains(&(
+            "Authorization".to_string(),
+            "Basic YWxpY2U6cHc=".to_string()
+        )));
+    }
+
+    #[test]
+    fn warns_on_unsupported_options() {
+        let req = parse_curl("curl -k --proxy-foo https://example.com").unwrap();
+        assert_eq!(req.warnings.len(), 2);
+    }
+
+    #[test]
+    fn rejects_non_curl_and_missing_url() {
+        assert!(parse_curl("wget https://example.com").is_err());
+        assert!(parse_curl("curl -H 'A: b'").is_err());
+        assert!(parse_curl("curl 'https://example.com").is_err());
+    }
+
+    #[test]
+    fn to_request_config_uses_absolute_url() {
+        let config = parse_curl("curl -X POST https://example.com/a -d x=1")
+            .unwrap()
+            .to_request_config();
+        assert_eq!(config.method, "POST");
+        assert_eq!(config.path, "https://example.com/a");
+        assert_eq!(config.body.as_deref(), Some("x=1"));
+    }
+
+    #[test]
+    fn import_builds_single_step_config() {
+        let result = import_curl("curl https://api.example.com/health").unwrap();
+        assert_eq!(result.config.config.base_url, "https://api.example.com");
+        let step = &result.config.scenarios[0].steps[0];
+        assert_eq!(step.request.path, "/health");
+        assert_eq!(step.name.as_deref(), Some("GET /health"));
+    }
+
+    #[test]
+    fn merge_prefers_explicit_fields() {
+        let base = parse_curl("curl https://example.com/a -H 'X-Token: old' -d body").unwrap();
+        let overrides = YamlRequest {
+            method: "PUT".to_string(),
+            path: String::new(),
+            query_params: None,
+            headers: Some(HashMap::from([(
+                "x-token".to_string(),
+                "${token}".to_string(),
+            )])),
+            body: None,
+            body_size: None,
+            protobuf: None,
+            host: None,
+        };
+        let merged = merge_request(&base, &overrides);
+        assert_eq!(merged.method, "PUT");
+        assert_eq!(merged.path, "https://example.com/a");
+        assert_eq!(merged.body.as_deref(), Some("body"));
+        let headers = merged.headers.unwrap();
+        assert_eq!(headers["x-token"], "${token}");
+        assert!(!headers.contains_key("X-Token"));
+    }
+}
@@ -0,0 +1,427 @@
+//! HAR (HTTP Archive) import for record-and-replay.
+//!
+//! Converts a browser-exported `.har` file into a single scenario: one step
+//! per recorded request, in recording order, with headers and bodies copied
+//! over and think times derived from the gaps between requests. Static assets
+//! (scripts, stylesheets, images, fonts) are skipped by default so the
+//! scenario exercises the API rather than the CDN.
+
+use super::{build_config, is_managed_header, split_url, ImportError, ImportResult};
+use crate::yaml_config::{
+    YamlAssertion, YamlDuration, YamlRequest, YamlScenario, YamlScenarioConfig, YamlStep,
+    YamlThinkTime,
+};
+use chrono::DateTime;
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::path::Path;
+use std::time::Duration;
+
+/// Options controlling HAR conversion.
+#[derive(Debug, Clone)]
+pub struct HarImportOptions {
+    /// Keep requests for static assets (JS, CSS, images, fonts).
+    pub include_static_assets: bool,
+
+    /// Gaps shorter than this are not turned into think times.
+    /// Think times are rounded to whole seconds.
+    pub min_think_time: Duration,
+
+    /// Name of the generated scenario.
+    pub scenario_name: String,
+}
+
+impl Default for HarImportOptions {
+    fn default() -> Self {
+        Self {
+            include_static_assets: false,
+            min_think_time: Duration::from_secs(1),
+            scenario_name: "Recorded user journey".to_string(),
+        }
+    }
+}
+
+#[derive(Deserialize)]
+struct Har {
+    log: HarLog,
+}
+
+#[derive(Deserialize)]
+struct HarLog {
+    #[serde(default)]
+    entries: Vec<HarEntry>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct HarEntry {
+    started_date_time: String,
+    #[serde(default)]
+    time: f64,
+    request: HarRequest,
+    response: Option<HarResponse>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct HarRequest {
+    method: String,
+    url: String,
+    #[serde(default)]
+    headers: Vec<HarHeader>,
+    post_data: Option<HarPostData>,
+}
+
+#[derive(Deserialize)]
+struct HarHeader {
+    name: String,
+    value: String,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct HarPostData {
+    text: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct HarResponse {
+    #[serde(default)]
+    status: u16,
+    content: Option<HarContent>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct HarContent {
+    mime_type: Option<String>,
+}
+
+/// Import a HAR file from disk.
+pub fn import_har_file(
+    path: impl AsRef<Path>,
+    options: &HarImportOptions,
+) -> Result<ImportResult, ImportError> {
+    let content = std::fs::read_to_string(path)?;
+    import_har(&content, options)
+}
+
+/// Import HAR JSON content.
+pub fn import_har(json: &str, options: &HarImportOptions) -> Result<ImportResult, ImportError> {
+    let har: Har = serde_json::from_str(json)?;
+    let mut warnings = Vec::new();
+
+    let entries: Vec<&HarEntry> = har
+        .log
+        .entries
+        .iter()
+        .filter(|e| options.include_static_assets || !is_static_asset(e))
+        .collect();
+
+    let skipped = har.log.entries.len() - entries.len();
+    if skipped > 0 {
+        warnings.push(format!(
+            "Skipped {} static asset request(s); use --include-assets to keep them",
+            skipped
+        ));
+    }
+
+    if entries.is_empty() {
+        return Err(ImportError::InvalidFormat(
+            "HAR file contains no replayable requests".to_string(),
+        ));
+    }
+
+    // The first request's origin becomes the base URL; requests to other
+    // origins keep their absolute URL.
+    let (base_url, _) = split_url(&entries[0].request.url);
+
+    let mut steps = Vec::with_capacity(entries.len());
+    for (idx, entry) in entries.iter().enumerate() {
+        let (origin, path) = split_url(&entry.request.url);
+        let path = if origin == base_url {
+            path
+        } else {
+            entry.request.url.clone()
+        };
+
+        let headers: HashMap<String, String> = entry
+            .request
+            .headers
+            .iter()
+            .filter(|h| !is_managed_header(&h.name))
+            .map(|h| (h.name.clone(), h.value.clone()))
+            .collect();
+
+        let think_time = entries
+            .get(idx + 1)
+            .and_then(|next| gap_between(entry, next, &mut warnings))
+            .filter(|gap| *gap >= options.min_think_time)
+            .map(|gap| {
+                YamlThinkTime::Fixed(YamlDuration::Seconds(gap.as_secs_f64().round() as u64))
+            });
+
+        let assertions = match entry.response.as_ref().map(|r| r.status) {
+            Some(status) if status > 0 => vec![YamlAssertion::StatusCode { expected: status }],
+            _ => vec![],
+        };
+
+        let name_path = path.split('?').next().unwrap_or(&path).to_string();
+        steps.push(YamlStep {
+            name: Some(format!(
+                "{} {}",
+                entry.request.method.to_uppercase(),
+                name_path
+            )),
+            request: YamlRequest {
+                method: entry.request.method.to_uppercase(),
+                path,
+                query_params: None,
+                headers: if headers.is_empty() {
+                    None
+                } else {
+                    Some(headers)
+                },
+                body: entry
+                    .request
+                    .post_data
+                    .as_ref()
+                    .and_then(|p| p.text.clone())
+                    .filter(|t| !t.is_empty()),
+                body_size: None,
+                protobuf: None,
+                host: None,
+            },
+            extract: vec![],
+            assertions,
+            cache: None,
+            think_time,
+            from_curl: None,
+            tags: HashMap::new(),
+            paginate: None,
+            max_concurrent: None,
+            slo: None,
+            shared: vec![],
+            rendezvous: None,
+            conditional: false,
+            idempotency_key: None,
+            tcp_check: None,
+            expected_status: vec![],
+        });
+    }
+
+    let scenario = YamlScenario {
+        name: options.scenario_name.clone(),
+        weight: 1.0,
+        steps,
+        data_file: None,
+        data_source: None,
+        config: YamlScenarioConfig::default(),
+        network_profile: None,
+        tags: HashMap::new(),
+        max_concurrent: None,
+        depends_on: Vec::new(),
+        iterations: None,
+        client: None,
+        headers: Default::default(),
+        auth: None,
+        optional: false,
+        request_defaults: None,
+        rps: None,
+    };
+
+    Ok(ImportResult {
+        config: build_config(
+            "Imported HAR recording",
+            "Generated by rust_loadtest import har",
+            &base_url,
+            vec![scenario],
+        ),
+        warnings,
+    })
+}
+
+/// Idle time between the end of `entry` and the start of `next`.
+fn gap_between(entry: &HarEntry, next: &HarEntry, warnings: &mut Vec<String>) -> Option<Duration> {
+    let parse = |ts: &str, warnings: &mut Vec<String>| match DateTime::parse_from_rfc3339(ts) {
+        Ok(dt) => Some(dt),
+        Err(e) => {
+            warnings.push(format!("Unparseable startedDateTime '{}': {}", ts, e));
+            None
+        }
+    };
+    let start = parse(&entry.started_date_time, warnings)?;
+    let next_start = parse(&next.started_date_time, warnings)?;
+    let gap_ms = (next_start - start).num_milliseconds() as f64 - entry.time;
+    if gap_ms > 0.0 {
+        Some(Duration::from_millis(gap_ms as u64))
+    } else {
+        None
+    }
+}
+
+/// Returns true for requests fetching static assets.
+fn is_static_asset(entry: &HarEntry) -> bool {
+    const EXTENSIONS: &[&str] = &[
+        ".js", ".mjs", ".css", ".png", ".jpg", ".jpeg", ".gif", ".svg", ".ico", ".webp", ".avif",
+        ".woff", ".woff2", ".ttf", ".otf", ".eot", ".map",
+    ];
+
+    let path = entry
+        .request
+        .url
+        .split(['?', '#'])
+        .next()
+        .unwrap_or("")
+        .to_ascii_lowercase();
+    if EXTENSIONS.iter().any(|ext| path.ends_with(ext)) {
+        return true;
+    }
+
+    let mime = entry
+        .response
+        .as_ref()
+        .and_then(|r| r.content.as_ref())
+        .and_then(|c| c.mime_type.as_deref())
+        .unwrap_or("")
+        .to_ascii_lowercase();
+    mime.starts_with("image/")
+        || mime.starts_with("font/")
+        || mime.starts_with("text/css")
+        || mime.contains("javascript")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const HAR: &str = r#"{
+      "log": {
+        "version": "1.2",
+        "entries": [
+          {
+            "startedDateTime": "2024-01-01T10:00:00.000Z",
+            "time": 120,
+            "request": {
+              "method": "GET",
+              "url": "https://shop.example.com/",
+              "headers": [
+                {"name": ":authority", "value": "shop.example.com"},
+                {"name": "Accept", "value": "text/html"},
+                {"name": "Cookie", "value": "session=abc"}
+              ]
+            },
+            "response": {"status": 200, "content": {"mimeType": "text/html"}}
+          },
+          {
+            "startedDateTime": "2024-01-01T10:00:00.200Z",
+            "time": 30,
+            "request": {"method": "GET", "url": "https://cdn.example.com/app.js?v=3", "headers": []},
+            "response": {"status": 200, "content": {"mimeType": "application/javascript"}}
+          },
+          {
+            "startedDateTime": "2024-01-01T10:00:03.120Z",
+            "time": 80,
+            "request": {
+              "method": "post",
+              "url": "https://shop.example.com/api/cart?src=web",
+              "headers": [{"name": "Content-Type", "value": "application/json"}],
+              "postData": {"mimeType": "application/json", "text": "{\"sku\":\"42\"}"}
+            },
+            "response": {"status": 201, "content": {"mimeType": "application/json"}}
+          },
+          {
+            "startedDateTime": "2024-01-01T10:00:03.300Z",
+            "time": 50,
+            "request": {"method": "GET", "url": "https://auth.example.com/me", "headers": []},
+            "response": {"status": 200}
+          }
+        ]
+      }
+    }"#;
+
+    #[test]
+    fn converts_entries_in_order_and_skips_assets() {
+        let result = import_har(HAR, &HarImportOptions::default()).unwrap();
+        let scenario = &result.config.scenarios[0];
+
+        assert_eq!(result.config.config.base_url, "https://shop.example.com");
+        assert_eq!(scenario.steps.len(), 3);
+        assert_eq!(scenario.steps[0].request.path, "/");
+        assert_eq!(scenario.steps[1].request.method, "POST");
+        assert_eq!(scenario.steps[1].request.path, "/api/cart?src=web");
+        assert_eq!(scenario.steps[1].name.as_deref(), Some("POST /api/cart"));
+        assert_eq!(
+            scenario.steps[1].request.body.as_deref(),
+            Some(r#"{"sku":"42"}"#)
+        );
+        // Different origin keeps the absolute URL
+        assert_eq!(
+            scenario.steps[2].request.path,
+            "https://auth.example.com/me"
+        );
+        assert!(result.warnings.iter().any(|w| w.contains("1 static asset")));
+    }
+
+    #[test]
+    fn filters_managed_headers() {
+        let result = import_har(HAR, &HarImportOptions::default()).unwrap();
+        let headers = result.config.scenarios[0].steps[0]
+            .request
+            .headers
+            .clone()
+            .unwrap();
+        assert_eq!(headers.len(), 1);
+        assert_eq!(headers["Accept"], "text/html");
+    }
+
+    #[test]
+    fn derives_think_time_from_gaps() {
+        let result = import_har(HAR, &HarImportOptions::default()).unwrap();
+        let steps = &result.config.scenarios[0].steps;
+
+        // 10:00:00.000 + 120ms → 10:00:03.120 = 3s gap
+        match &steps[0].think_time {
+            Some(YamlThinkTime::Fixed(YamlDuration::Seconds(s))) => assert_eq!(*s, 3),
+            other => panic!("unexpected think time: {:?}", other),
+        }
+        // 100ms gap is below the 1s minimum
+        assert!(steps[1].think_time.is_none());
+        assert!(steps[2].think_time.is_none());
+    }
+
+    #[test]
+    fn records_status_assertions() {
+        let result = import_har(HAR, &HarImportOptions::default()).unwrap();
+        match &result.config.scenarios[0].steps[1].assertions[0] {
+            YamlAssertion::StatusCode { expected } => assert_eq!(*expected, 201),
+            other => panic!("unexpected assertion: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn include_static_assets_keeps_everything() {
+        let options = HarImportOptions {
+            include_static_assets: true,
+            ..Default::default()
+        };
+        let result = import_har(HAR, &options).unwrap();
+        assert_eq!(result.config.scenarios[0].steps.len(), 4);
+        assert!(result.warnings.is_empty());
+    }
+
+    #[test]
+    fn output_round_trips_through_yaml_config() {
+        let result = import_har(HAR, &HarImportOptions::default()).unwrap();
+        let yaml = result.to_yaml().unwrap();
+        let parsed = crate::yaml_config::YamlConfig::from_str(&yaml).unwrap();
+        let scenarios = parsed.to_scenarios().unwrap();
+        assert_eq!(scenarios[0].steps.len(), 3);
+    }
+
+    #[test]
+    fn empty_har_is_rejected() {
+        let result = import_har(r#"{"log": {"entries": []}}"#, &HarImportOptions::default());
+        assert!(matches!(result, Err(ImportError::InvalidFormat(_))));
+    }
+}
@@ -0,0 +1,707 @@
+//! JMeter test plan (`.jmx`) import — best-effort subset.
+//!
+//! Supported elements:
+//! - `ThreadGroup` → scenario (thread count becomes `workers`, the largest
+//!   group wins)
+//! - `HTTPSamplerProxy` → step (protocol/domain/port/path/method, raw body or
+//!   form arguments)
+//! - `HeaderManager` → headers (plan/group-level managers apply to every
+//!   sampler below them)
+//! - `ResponseAssertion` on response code / body text → assertions
+//! - `JSONPostProcessor`, `RegexExtractor` → extractors
+//! - `ConstantTimer`, `UniformRandomTimer` → think times
+//! - `Arguments` (user-defined variables) → inlined values
+//!
+//! JMeter's `${var}` syntax matches ours, so variable references carry over
+//! unchanged. Disabled elements are skipped; every other element is reported
+//! as a warning.
+
+use super::{build_config, is_managed_header, split_url, ImportError, ImportResult};
+use crate::yaml_config::{
+    YamlAssertion, YamlDuration, YamlExtractor, YamlRequest, YamlScenario, YamlScenarioConfig,
+    YamlStep, YamlThinkTime,
+};
+use roxmltree::{Document, Node};
+use std::collections::HashMap;
+use std::path::Path;
+
+/// Elements that carry no load-relevant behaviour and are skipped silently.
+const IGNORED_ELEMENTS: &[&str] = &[
+    "ResultCollector",
+    "ViewResultsFullVisualizer",
+    "SummaryReport",
+    "StatVisualizer",
+    "CookieManager",
+    "CacheManager",
+    "ConfigTestElement",
+    "DNSCacheManager",
+    "BackendListener",
+];
+
+/// Import a JMeter test plan from disk.
+pub fn import_jmx_file(path: impl AsRef<Path>) -> Result<ImportResult, ImportError> {
+    let content = std::fs::read_to_string(path)?;
+    import_jmx(&content)
+}
+
+/// Import JMeter test plan XML.
+pub fn import_jmx(xml: &str) -> Result<ImportResult, ImportError> {
+    let doc = Document::parse(xml)
+        .map_err(|e| ImportError::InvalidFormat(format!("Invalid JMX XML: {}", e)))?;
+
+    let root = doc.root_element();
+    if root.tag_name().name() != "jmeterTestPlan" {
+        return Err(ImportError::InvalidFormat(
+            "Root element is not <jmeterTestPlan>".to_string(),
+        ));
+    }
+
+    let mut importer = JmxImporter::default();
+    if let Some(tree) = child_elements(root).find(|n| n.tag_name().name() == "hashTree") {
+        importer.walk(tree, &Scope::default());
+    }
+
+    if importer.scenarios.is_empty() {
+        return Err(ImportError::InvalidFormat(
+            "Test plan contains no thread groups with HTTP samplers".to_string(),
+        ));
+    }
+
+    let mut config = build_config(
+        importer
+            .plan_name
+            .as_deref()
+            .unwrap_or("Imported JMeter plan"),
+        "Generated by rust_loadtest import jmeter",
+        importer.base_url.as_deref().unwrap_or("http://localhost"),
+        importer.scenarios,
+    );
+    if importer.max_threads > 0 {
+        config.config.workers = importer.max_threads;
+    }
+
+    Ok(ImportResult {
+        config,
+        warnings: importer.warnings,
+    })
+}
+
+/// Settings inherited from enclosing elements.
+#[derive(Default, Clone)]
+struct Scope {
+    headers: HashMap<String, String>,
+    variables: HashMap<String, String>,
+    think_time: Option<YamlThinkTime>,
+}
+
+#[derive(Default)]
+struct JmxImporter {
+    plan_name: Option<String>,
+    base_url: Option<String>,
+    max_threads: usize,
+    scenarios: Vec<YamlScenario>,
+    current_steps: Option<Vec<YamlStep>>,
+    warnings: Vec<String>,
+}
+
+impl JmxImporter {
+    /// Walk a `<hashTree>`: each element is followed by a sibling
+    /// `<hashTree>` holding its children.
+    fn walk(&mut self, tree: Node, parent_scope: &Scope) {
+        let elements: Vec<Node> = child_elements(tree).collect();
+
+        // Config elements (headers, variables, timers) apply to every sibling
+        // and descendant, regardless of their position in the tree.
+        let mut scope = parent_scope.clone();
+        for node in &elements {
+            if !is_enabled(*node) {
+                continue;
+            }
+            match node.tag_name().name() {
+                "HeaderManager" => scope.headers.extend(headers_of(*node)),
+                "Arguments" => scope.variables.extend(arguments_of(*node)),
+                "ConstantTimer" | "UniformRandomTimer" => {
+                    scope.think_time = timer_of(*node, &mut self.warnings)
+                }
+                "TestPlan" => {
+                    self.plan_name = node.attribute("testname").map(str::to_string);
+                    if let Some(vars) = child_elements(*node)
+                        .find(|n| n.attribute("name") == Some("TestPlan.user_defined_variables"))
+                    {
+                        scope.variables.extend(arguments_of(vars));
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        let mut iter = elements.iter().peekable();
+        while let Some(node) = iter.next() {
+            let name = node.tag_name().name();
+            if name == "hashTree" {
+                continue;
+            }
+            let children = iter
+                .peek()
+                .filter(|n| n.tag_name().name() == "hashTree")
+                .copied()
+                .copied();
+
+            if !is_enabled(*node) {
+                continue;
+            }
+
+            match name {
+                "TestPlan" => {
+                    if let Some(children) = children {
+                        self.walk(children, &scope);
+                    }
+                }
+                "ThreadGroup" | "SetupThreadGroup" | "PostThreadGroup" => {
+                    self.thread_group(*node, children, &scope)
+                }
+                "HTTPSamplerProxy" => self.sampler(*node, children, &scope),
+                "HeaderManager" | "Arguments" | "ConstantTimer" | "UniformRandomTimer" => {}
+                "GenericController" | "TransactionController" | "LoopController" => {
+                    // Simple controllers just group samplers; flatten them.
+                    if let Some(children) = children {
+                        self.walk(children, &scope);
+                    }
+                }
+                other if IGNORED_ELEMENTS.contains(&other) => {}
+                other => self.warnings.push(format!(
+                    "Unsupported element <{}> '{}' skipped",
+                    other,
+                    node.attribute("testname").unwrap_or("")
+                )),
+            }
+        }
+    }
+
+    fn thread_group(&mut self, node: Node, children: Option<Node>, scope: &Scope) {
+        let name = node
+            .attribute("testname")
+            .unwrap_or("Thread Group")
+            .to_string();
+        if let Some(threads) = string_prop(node, "ThreadGroup.num_threads")
+            .and_then(|t| substitute(&t, &scope.variables).parse::<usize>().ok())
+        {
+            self.max_threads = self.max_threads.max(threads);
+        }
+
+        self.current_steps = Some(Vec::new());
+        if let Some(children) = children {
+            self.walk(children, scope);
+        }
+        let steps = self.current_steps.take().unwrap_or_default();
+        if steps.is_empty() {
+            self.warnings
+                .push(format!("Thread group '{}' has no HTTP samplers", name));
+            return;
+        }
+        self.scenarios.push(YamlScenario {
+            name,
+            weight: 1.0,
+            steps,
+            data_file: None,
+            data_source: None,
+            config: YamlScenarioConfig::default(),
+            network_profile: None,
+            tags: HashMap::new(),
+            max_concurrent: None,
+            depends_on: Vec::new(),
+            iterations: None,
+            client: None,
+            headers: Default::default(),
+            auth: None,
+            optional: false,
+            request_defaults: None,
+            rps: None,
+        });
+    }
+
+    fn sampler(&mut self, node: Node, children: Option<Node>, scope: &Scope) {
+        let name = node.attribute("testname").unwrap_or("HTTP Request");
+        if self.current_steps.is_none() {
+            self.warnings.push(format!(
+                "Sampler '{}' is outside a thread group; skipped",
+                name
+            ));
+            return;
+        }
+
+        let vars = &scope.variables;
+        let prop = |key: &str| {
+            string_prop(node, key)
+                .map(|v| substitute(&v, vars))
+                .filter(|v| !v.is_empty())
+        };
+
+        let method = prop("HTTPSampler.method")
+            .unwrap_or_else(|| "GET".to_string())
+            .to_uppercase();
+        let raw_path = prop("HTTPSampler.path").unwrap_or_else(|| "/".to_string());
+
+        // The first sampler's origin becomes the base URL; samplers hitting
+        // other origins keep an absolute URL.
+        let (origin, path) = match prop("HTTPSampler.domain") {
+            Some(domain) => {
+                let protocol = prop("HTTPSampler.protocol").unwrap_or_else(|| "http".to_string());
+                let origin = match prop("HTTPSampler.port") {
+                    Some(port) => format!("{}://{}:{}", protocol, domain, port),
+                    None => format!("{}://{}", protocol, domain),
+                };
+                let path = if raw_path.starts_with('/') {
+                    raw_path
+                } else {
+                    format!("/{}", raw_path)
+                };
+                (origin, path)
+            }
+            None => split_url(&raw_path),
+        };
+        if !origin.is_empty() && self.base_url.is_none() {
+            self.base_url = Some(origin.clone());
+        }
+        let mut url_path = if origin.is_empty() || self.base_url.as_deref() == Some(origin.as_str())
+        {
+            path
+        } else {
+            format!("{}{}", origin, path)
+        };
+
+        // Body: raw post body, or form-encoded arguments
+        let mut headers = scope.headers.clone();
+        let mut body = None;
+        let args: Vec<(String, String)> = descendants_named(node, "elementProp")
+            .filter(|n| n.attribute("elementType") == Some("HTTPArgument"))
+            .map(|n| {
+                (
+                    string_prop(n, "Argument.name").unwrap_or_default(),
+                    substitute(&string_prop(n, "Argument.value").unwrap_or_default(), vars),
+                )
+            })
+            .collect();
+        if bool_prop(node, "HTTPSampler.postBodyRaw") {
+            body = args.first().map(|(_, v)| v.clone());
+        } else if !args.is_empty() {
+            let encoded: Vec<String> = args.iter().map(|(k, v)| format!("{}={}", k, v)).collect();
+            if matches!(method.as_str(), "POST" | "PUT" | "PATCH") {
+                headers
+                    .entry("Content-Type".to_string())
+                    .or_insert_with(|| "application/x-www-form-urlencoded".to_string());
+                body = Some(encoded.join("&"));
+            } else {
+                let sep = if url_path.contains('?') { '&' } else { '?' };
+                url_path = format!("{}{}{}", url_path, sep, encoded.join("&"));
+            }
+        }
+
+        let mut assertions = Vec::new();
+        let mut extract = Vec::new();
+        let mut think_time = scope.think_time.clone();
+        if let Some(children) = children {
+            for child in child_elements(children).filter(|n| is_enabled(*n)) {
+                match child.tag_name().name() {
+                    "hashTree" => {}
+                    "HeaderManager" => headers.extend(headers_of(child)),
+                    "ResponseAssertion" => {
+                        assertions.extend(response_assertion(child, &mut self.warnings))
+                    }
+                    "JSONPostProcessor" => {
+                        let names = string_prop(child, "JSONPostProcessor.referenceNames")
+                            .unwrap_or_default();
+                        let paths = string_prop(child, "JSONPostProcessor.jsonPathExprs")
+                            .unwrap_or_default();
+                        for (name, path) in names.split(';').zip(paths.split(';')) {
+                            extract.push(YamlExtractor::JsonPath {
+                                name: name.trim().to_string(),
+                                json_path: path.trim().to_string(),
+                                transform: None,
+                                scope: None,
+                            });
+                        }
+                    }
+                    "RegexExtractor" => {
+                        if let (Some(name), Some(regex)) = (
+                            string_prop(child, "RegexExtractor.refname"),
+                            string_prop(child, "RegexExtractor.regex"),
+                        ) {
+                            extract.push(YamlExtractor::Regex {
+                                name,
+                                regex,
+                                transform: None,
+                                scope: None,
+                            });
+                        }
+                    }
+                    "ConstantTimer" | "UniformRandomTimer" => {
+                        think_time = timer_of(child, &mut self.warnings)
+                    }
+                    other => self.warnings.push(format!(
+                        "Sampler '{}': unsupported child <{}> skipped",
+                        name, other
+                    )),
+                }
+            }
+        }
+
+        headers.retain(|k, _| !is_managed_header(k));
+        if url_path.contains("${__") || body.as_deref().is_some_and(|b| b.contains("${__")) {
+            self.warnings.push(format!(
+                "Sampler '{}': JMeter functions (${{__...}}) are not supported",
+                name
+            ));
+        }
+
+        if let Some(steps) = self.current_steps.as_mut() {
+            steps.push(YamlStep {
+                name: Some(name.to_string()),
+                request: YamlRequest {
+                    method,
+                    path: url_path,
+                    query_params: None,
+                    headers: if headers.is_empty() {
+                        None
+                    } else {
+                        Some(headers)
+                    },
+                    body,
+                    body_size: None,
+                    protobuf: None,
+                    host: None,
+                },
+                extract,
+                assertions,
+                cache: None,
+                think_time,
+                from_curl: None,
+                tags: HashMap::new(),
+                paginate: None,
+                max_concurrent: None,
+                slo: None,
+                shared: vec![],
+                rendezvous: None,
+                conditional: false,
+                idempotency_key: None,
+                tcp_check: None,
+                expected_status: vec![],
+            });
+        }
+    }
+}
+
+fn child_elements<'a, 'input>(node: Node<'a, 'input>) -> impl Iterator<Item = Node<'a, 'input>> {
+    node.children().filter(|n| n.is_element())
+}
+
+fn descendants_named<'a, 'input>(
+    node: Node<'a, 'input>,
+    tag: &'static str,
+) -> impl Iterator<Item = Node<'a, 'input>> {
+    node.descendants()
+        .filter(move |n| n.is_element() && n.tag_name().name() == tag)
+}
+
+fn is_enabled(node: Node) -> bool {
+    node.attribute("enabled") != Some("false")
+}
+
+/// Value of a direct `<stringProp name="key">` child.
+fn string_prop(node: Node, key: &str) -> Option<String> {
+    child_elements(node)
+        .find(|n| {
+            matches!(n.tag_name().name(), "stringProp" | "intProp" | "longProp")
+                && n.attribute("name") == Some(key)
+        })
+        .map(|n| n.text().unwrap_or("").to_string())
+}
+
+fn bool_prop(node: Node, key: &str) -> bool {
+    child_elements(node)
+        .find(|n| n.tag_name().name() == "boolProp" && n.attribute("name") == Some(key))
+        .and_then(|n| n.text())
+        == Some("true")
+}
+
+fn headers_of(node: Node) -> Vec<(String, String)> {
+    descendants_named(node, "elementProp")
+        .filter(|n| n.attribute("elementType") == Some("Header"))
+        .filter_map(|n| {
+            Some((
+                string_prop(n, "Header.name")?,
+                string_prop(n, "Header.value")?,
+            ))
+        })
+        .collect()
+}
+
+fn arguments_of(node: Node) -> Vec<(String, String)> {
+    descendants_named(node, "elementProp")
+        .filter(|n| n.attribute("elementType") == Some("Argument"))
+        .filter_map(|n| {
+            Some((
+                string_prop(n, "Argument.name")?,
+                string_prop(n, "Argument.value").unwrap_or_default(),
+            ))
+        })
+        .collect()
+}
+
+/// Inline user-defined variables; unknown `${var}` references are kept as
+/// runtime variables.
+fn substitute(input: &str, vars: &HashMap<String, String>) -> String {
+    let mut out = input.to_string();
+    for (k, v) in vars {
+        out = out.replace(&format!("${{{}}}", k), v);
+    }
+    out
+}
+
+fn ms_to_duration(ms: u64) -> YamlDuration {
+    YamlDuration::Seconds((ms as f64 / 1000.0).round() as u64)
+}
+
+fn timer_of(node: Node, warnings: &mut Vec<String>) -> Option<YamlThinkTime> {
+    let delay: u64 = string_prop(node, "ConstantTimer.delay")
+        .and_then(|d| d.trim().parse().ok())
+        .unwrap_or(0);
+    match node.tag_name().name() {
+        "UniformRandomTimer" => {
+            let range: u64 = string_prop(node, "RandomTimer.range")
+                .and_then(|r| r.trim().parse::<f64>().ok())
+                .map(|r| r as u64)
+                .unwrap_or(0);
+            Some(YamlThinkTime::Random {
+                min: ms_to_duration(delay),
+                max: ms_to_duration(delay + range),
+            })
+        }
+        _ if delay == 0 => None,
+        _ => {
+            if !delay.is_multiple_of(1000) {
+                warnings.push(format!("Timer delay {}ms rounded to whole seconds", delay));
+            }
+            Some(YamlThinkTime::Fixed(ms_to_duration(delay)))
+        }
+    }
+}
+
+/// Convert a `ResponseAssertion` testing the response code or body text.
+fn response_assertion(node: Node, warnings: &mut Vec<String>) -> Vec<YamlAssertion> {
+    let field = string_prop(node, "Assertion.test_field").unwrap_or_default();
+    // Bit flags: 1 = matches, 2 = contains, 4 = NOT, 8 = equals, 16 = substring
+    let test_type: u32 = string_prop(node, "Assertion.test_type")
+        .and_then(|t| t.parse().ok())
+        .unwrap_or(16);
+    let values: Vec<String> = descendants_named(node, "collectionProp")
+        .filter(|n| n.attribute("name") == Some("Asserion.test_strings"))
+        .flat_map(|n| child_elements(n).filter_map(|s| s.text().map(str::to_string)))
+        .collect();
+
+    if test_type & 4 != 0 {
+        warnings.push(format!(
+            "Negated assertion '{}' skipped",
+            node.attribute("testname").unwrap_or("")
+        ));
+        return vec![];
+    }
+
+    match field.as_str() {
+        "Assertion.response_code" => values
+            .iter()
+            .filter_map(|v| v.trim().parse().ok())
+            .map(|expected| YamlAssertion::StatusCode { expected })
+            .collect(),
+        "Assertion.response_data" | "" => values
+            .into_iter()
+            .map(|text| {
+                // "Matches" and "Contains" are regex tests in JMeter
+                if test_type & (1 | 2) != 0 {
+                    YamlAssertion::BodyMatches { regex: text }
+                } else {
+                    YamlAssertion::BodyContains { text }
+                }
+            })
+            .collect(),
+        other => {
+            warnings.push(format!("Response assertion on field '{}' skipped", other));
+            vec![]
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const JMX: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<jmeterTestPlan version="1.2" properties="5.0" jmeter="5.6">
+  <hashTree>
+    <TestPlan guiclass="TestPlanGui" testclass="TestPlan" testname="Shop Plan" enabled="true">
+      <elementProp name="TestPlan.user_defined_variables" elementType="Arguments">
+        <collectionProp name="Arguments.arguments">
+          <elementProp name="host" elementType="Argument">
+            <stringProp name="Argument.name">host</stringProp>
+            <stringProp name="Argument.value">shop.example.com</stringProp>
+          </elementProp>
+        </collectionProp>
+      </elementProp>
+    </TestPlan>
+    <hashTree>
+      <HeaderManager testname="Defaults" enabled="true">
+        <collectionProp name="HeaderManager.headers">
+          <elementProp name="" elementType="Header">
+            <stringProp name="Header.name">Accept</stringProp>
+            <stringProp name="Header.value">application/json</stringProp>
+          </elementProp>
+        </collectionProp>
+      </HeaderManager>
+      <hashTree/>
+      <ThreadGroup testname="Shoppers" enabled="true">
+        <stringProp name="ThreadGroup.num_threads">25</stringProp>
+      </ThreadGroup>
+      <hashTree>
+        <HTTPSamplerProxy testname="Login" enabled="true">
+          <boolProp name="HTTPSampler.postBodyRaw">true</boolProp>
+          <elementProp name="HTTPsampler.Arguments" elementType="Arguments">
+            <collectionProp name="Arguments.arguments">
+              <elementProp name="" elementType="HTTPArgument">
+                <stringProp name="Argument.value">{"user":"${username}"}</stringProp>
+              </elementProp>
+            </collectionProp>
+          </elementProp>
+          <stringProp name="HTTPSampler.domain">${host}</stringProp>
+          <stringProp name="HTTPSampler.protocol">https</stringProp>
+          <stringProp name="HTTPSampler.path">/api/login</stringProp>
+          <stringProp name="HTTPSampler.method">POST</stringProp>
+        </HTTPSamplerProxy>
+        <hashTree>
+          <ResponseAssertion testname="200" enabled="true">
+            <collectionProp name="Asserion.test_strings">
+              <stringProp name="49586">200</stringProp>
+            </collectionProp>
+            <stringProp name="Assertion.test_field">Assertion.response_code</stringProp>
+            <intProp name="Assertion.test_type">8</intProp>
+          </ResponseAssertion>
+          <hashTree/>
+          <JSONPostProcessor testname="token" enabled="true">
+            <stringProp name="JSONPostProcessor.referenceNames">token</stringProp>
+            <stringProp name="JSONPostProcessor.jsonPathExprs">$.token</stringProp>
+          </JSONPostProcessor>
+          <hashTree/>
+          <ConstantTimer testname="Think" enabled="true">
+            <stringProp name="ConstantTimer.delay">2000</stringProp>
+          </ConstantTimer>
+          <hashTree/>
+        </hashTree>
+        <HTTPSamplerProxy testname="Search" enabled="true">
+          <elementProp name="HTTPsampler.Arguments" elementType="Arguments">
+            <collectionProp name="Arguments.arguments">
+              <elementProp name="q" elementType="HTTPArgument">
+                <stringProp name="Argument.name">q</stringProp>
+                <stringProp name="Argument.value">shoes</stringProp>
+              </elementProp>
+            </collectionProp>
+          </elementProp>
+          <stringProp name="HTTPSampler.domain">${host}</stringProp>
+          <stringProp name="HTTPSampler.protocol">https</stringProp>
+          <stringProp name="HTTPSampler.path">/api/search</stringProp>
+          <stringProp name="HTTPSampler.method">GET</stringProp>
+        </HTTPSamplerProxy>
+        <hashTree/>
+        <HTTPSamplerProxy testname="Disabled" enabled="false">
+          <stringProp name="HTTPSampler.path">/nope</stringProp>
+        </HTTPSamplerProxy>
+        <hashTree/>
+        <CSVDataSet testname="users.csv" enabled="true"/>
+        <hashTree/>
+      </hashTree>
+      <ResultCollector testname="View Results Tree" enabled="true"/>
+      <hashTree/>
+    </hashTree>
+  </hashTree>
+</jmeterTestPlan>"#;
+
+    #[test]
+    fn converts_thread_group_and_samplers() {
+        let result = import_jmx(JMX).unwrap();
+        assert_eq!(result.config.metadata.name.as_deref(), Some("Shop Plan"));
+        assert_eq!(result.config.config.base_url, "https://shop.example.com");
+        assert_eq!(result.config.config.workers, 25);
+
+        let scenario = &result.config.scenarios[0];
+        assert_eq!(scenario.name, "Shoppers");
+        assert_eq!(scenario.steps.len(), 2);
+
+        let login = &scenario.steps[0];
+        assert_eq!(login.request.method, "POST");
+        assert_eq!(login.request.path, "/api/login");
+        assert_eq!(
+            login.request.body.as_deref(),
+            Some(r#"{"user":"${username}"}"#)
+        );
+        assert_eq!(
+            login.request.headers.as_ref().unwrap()["Accept"],
+            "application/json"
+        );
+    }
+
+    #[test]
+    fn converts_assertions_extractors_and_timers() {
+        let result = import_jmx(JMX).unwrap();
+        let login = &result.config.scenarios[0].steps[0];
+        assert!(matches!(
+            login.assertions[0],
+            YamlAssertion::StatusCode { expected: 200 }
+        ));
+        assert!(matches!(
+            &login.extract[0],
+            YamlExtractor::JsonPath {
+                name, json_path, ..
+            } if name == "token" && json_path == "$.token"
+        ));
+        assert!(matches!(
+            login.think_time,
+            Some(YamlThinkTime::Fixed(YamlDuration::Seconds(2)))
+        ));
+    }
+
+    #[test]
+    fn get_arguments_become_query_string() {
+        let result = import_jmx(JMX).unwrap();
+        let search = &result.config.scenarios[0].steps[1];
+        assert_eq!(search.request.path, "/api/search?q=shoes");
+        assert!(search.request.body.is_none());
+    }
+
+    #[test]
+    fn reports_unsupported_elements() {
+        let result = import_jmx(JMX).unwrap();
+        assert!(result
+            .warnings
+            .iter()
+            .any(|w| w.contains("CSVDataSet") && w.contains("users.csv")));
+        // Listeners are ignored silently
+        assert!(!result
+            .warnings
+            .iter()
+            .any(|w| w.contains("ResultCollector")));
+    }
+
+    #[test]
+    fn output_round_trips_through_yaml_config() {
+        let result = import_jmx(JMX).unwrap();
+        let yaml = result.to_yaml().unwrap();
+        let parsed = crate::yaml_config::YamlConfig::from_str(&yaml).unwrap();
+        assert_eq!(parsed.to_scenarios().unwrap()[0].steps.len(), 2);
+    }
+
+    #[test]
+    fn rejects_non_jmx_xml() {
+        assert!(matches!(
+            import_jmx("<root/>"),
+            Err(ImportError::InvalidFormat(_))
+        ));
+    }
+}
@@ -0,0 +1,345 @@
+//! k6 script import — best-effort subset.
+//!
+//! k6 scripts are JavaScript, so this is a line-oriented pattern match rather
+//! than a parser. Supported:
+//! - `export const options = { vus: N, duration: '30s' }` → workers/duration
+//! - `http.get/post/put/patch/del/head/options(url, [body], [params])` with a
+//!   string or template-literal URL → steps (`${VAR}` template placeholders
+//!   carry over as runtime variables)
+//! - `headers: { 'Name': 'value' }` inside request params → headers
+//! - `sleep(N)` → think time on the preceding step
+//! - `check(res, { ...: (r) => r.status === 200 })` → status assertion on the
+//!   preceding step
+//!
+//! Anything else that looks like logic (`http.batch`, loops, custom
+//! functions) is reported as a warning.
+
+use super::{build_config, split_url, ImportError, ImportResult};
+use crate::yaml_config::{
+    YamlAssertion, YamlDuration, YamlRequest, YamlScenario, YamlScenarioConfig, YamlStep,
+    YamlThinkTime,
+};
+use regex::Regex;
+use std::collections::HashMap;
+use std::path::Path;
+
+/// Import a k6 script from disk.
+pub fn import_k6_file(path: impl AsRef<Path>) -> Result<ImportResult, ImportError> {
+    let content = std::fs::read_to_string(path)?;
+    import_k6(&content)
+}
+
+/// Import k6 script source.
+pub fn import_k6(script: &str) -> Result<ImportResult, ImportError> {
+    let request = Regex::new(
+        r#"http\.(get|post|put|patch|del|head|options)\(\s*(?:'([^']*)'|"([^"]*)"|`([^`]*)`)\s*(?:,\s*(?:'([^']*)'|"([^"]*)"|`([^`]*)`|JSON\.stringify\((\{.*\})\)))?"#,
+    )
+    .expect("valid regex");
+    let header =
+        Regex::new(r#"['"]([A-Za-z0-9-]+)['"]\s*:\s*['"`]([^'"`]*)['"`]"#).expect("valid regex");
+    let sleep = Regex::new(r"^\s*sleep\(\s*([\d.]+)\s*\)").expect("valid regex");
+    let status_check = Regex::new(r"\.status\s*===?\s*(\d{3})").expect("valid regex");
+    let vus = Regex::new(r"\bvus\s*:\s*(\d+)").expect("valid regex");
+    let duration = Regex::new(r#"\bduration\s*:\s*['"]((?:\d+[smhd])+)['"]"#).expect("valid regex");
+    let unsupported = Regex::new(r"http\.batch|http\.request\(|\bfor\s*\(|\bwhile\s*\(|\bgroup\(")
+        .expect("valid regex");
+
+    let mut warnings = Vec::new();
+    let mut steps: Vec<YamlStep> = Vec::new();
+    let mut base_url: Option<String> = None;
+    let mut workers = None;
+    let mut test_duration = None;
+    let mut in_headers = false;
+
+    for (line_no, line) in script.lines().enumerate() {
+        let trimmed = line.trim();
+        if trimmed.starts_with("//") {
+            continue;
+        }
+
+        if let Some(caps) = vus.captures(trimmed) {
+            workers = caps[1].parse::<usize>().ok();
+        }
+        if let Some(caps) = duration.captures(trimmed) {
+            if steps.is_empty() {
+                test_duration = Some(caps[1].to_string());
+            }
+        }
+
+        if let Some(caps) = request.captures(trimmed) {
+            let method = match &caps[1] {
+                "del" => "DELETE".to_string(),
+                m => m.to_uppercase(),
+            };
+            let url = (2..=4)
+                .find_map(|i| caps.get(i))
+                .map(|m| m.as_str().to_string())
+                .unwrap_or_default();
+            let body = (5..=8)
+                .find_map(|i| caps.get(i))
+                .map(|m| m.as_str().to_string());
+
+            let (origin, path) = split_url(&url);
+            if !origin.is_empty() && base_url.is_none() {
+                base_url = Some(origin.clone());
+            }
+            let path = if origin.is_empty() || base_url.as_deref() == Some(origin.as_str()) {
+                path
+            } else {
+                url.clone()
+            };
+
+            let name_path = path.split('?').next().unwrap_or(&path).to_string();
+            let mut headers = HashMap::new();
+            if caps.get(8).is_some() {
+                headers.insert("Content-Type".to_string(), "application/json".to_string());
+            }
+            // Single-line params: http.post(url, body, { headers: { ... } })
+            if trimmed.contains("headers") {
+                for h in header.captures_iter(&trimmed[caps.get(0).unwrap().end()..]) {
+                    headers.insert(h[1].to_string(), h[2].to_string());
+                }
+            }
+            in_headers = trimmed.contains("headers") && !trimmed.contains('}');
+
+            steps.push(YamlStep {
+                name: Some(format!("{} {}", method, name_path)),
+                request: YamlRequest {
+                    method,
+                    path,
+                    query_params: None,
+                    headers: if headers.is_empty() {
+                        None
+                    } else {
+                        Some(headers)
+                    },
+                    body,
+                    body_size: None,
+                    protobuf: None,
+                    host: None,
+                },
+                extract: vec![],
+                assertions: vec![],
+                cache: None,
+                think_time: None,
+                from_curl: None,
+                tags: HashMap::new(),
+                paginate: None,
+                max_concurrent: None,
+                slo: None,
+                shared: vec![],
+                rendezvous: None,
+                conditional: false,
+                idempotency_key: None,
+                tcp_check: None,
+                expected_status: vec![],
+            });
+            continue;
+        }
+
+        // Multi-line params object following a request
+        if trimmed.starts_with("headers") {
+            in_headers = true;
+        }
+        if in_headers {
+            if let Some(step) = steps.last_mut() {
+                for h in header.captures_iter(trimmed) {
+                    step.request
+                        .headers
+                        .get_or_insert_with(HashMap::new)
+                        .insert(h[1].to_string(), h[2].to_string());
+                }
+            }
+            if trimmed.contains('}') {
+                in_headers = false;
+            }
+            continue;
+        }
+
+        if let Some(caps) = sleep.captures(trimmed) {
+            let secs: f64 = caps[1].parse().unwrap_or(0.0);
+            match steps.last_mut() {
+                Some(step) if secs >= 1.0 => {
+                    if secs.fract() != 0.0 {
+                        warnings.push(format!(
+                            "line {}: sleep({}) rounded to {}s",
+                            line_no + 1,
+                            secs,
+                            secs.round()
+                        ));
+                    }
+                    step.think_time = Some(YamlThinkTime::Fixed(YamlDuration::Seconds(
+                        secs.round() as u64,
+                    )));
+                }
+                Some(_) => warnings.push(format!(
+                    "line {}: sleep({}) shorter than 1s dropped",
+                    line_no + 1,
+                    secs
+                )),
+                None => {}
+            }
+            continue;
+        }
+
+        if let Some(caps) = status_check.captures(trimmed) {
+            if let Some(step) = steps.last_mut() {
+                step.assertions.push(YamlAssertion::StatusCode {
+                    expected: caps[1].parse().unwrap_or(200),
+                });
+            }
+            continue;
+        }
+
+        if unsupported.is_match(trimmed) {
+            warnings.push(format!(
+                "line {}: unsupported construct skipped: {}",
+                line_no + 1,
+                trimmed
+            ));
+        }
+    }
+
+    if steps.is_empty() {
+        return Err(ImportError::InvalidFormat(
+            "No http.* requests found in k6 script".to_string(),
+        ));
+    }
+
+    let scenario = YamlScenario {
+        name: "k6 default function".to_string(),
+        weight: 1.0,
+        steps,
+        data_file: None,
+        data_source: None,
+        config: YamlScenarioConfig::default(),
+        network_profile: None,
+        tags: HashMap::new(),
+        max_concurrent: None,
+        depends_on: Vec::new(),
+        iterations: None,
+        client: None,
+        headers: Default::default(),
+        auth: None,
+        optional: false,
+        request_defaults: None,
+        rps: None,
+    };
+
+    let mut config = build_config(
+        "Imported k6 script",
+        "Generated by rust_loadtest import k6",
+        base_url.as_deref().unwrap_or("http://localhost"),
+        vec![scenario],
+    );
+    if let Some(workers) = workers {
+        config.config.workers = workers;
+    }
+    if let Some(duration) = test_duration {
+        config.config.duration = YamlDuration::String(duration);
+    }
+
+    Ok(ImportResult { config, warnings })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SCRIPT: &str = r#"
+import http from 'k6/http';
+import { check, sleep } from 'k6';
+
+export const options = {
+  vus: 20,
+  duration: '5m',
+};
+
+const BASE = 'https://api.example.com';
+
+export default function () {
+  const res = http.get('https://api.example.com/products?page=1');
+  check(res, { 'status is 200': (r) => r.status === 200 });
+  sleep(2);
+
+  const login = http.post('https://api.example.com/login', JSON.stringify({"user":"bob"}), {
+    headers: {
+      'Content-Type': 'application/json',
+      'X-Client': 'k6',
+    },
+  });
+  check(login, { 'created': (r) => r.status == 201 });
+  sleep(0.5);
+
+  http.del(`https://api.example.com/cart/${__VU}`);
+  http.batch([['GET', 'https://api.example.com/a']]);
+}
+"#;
+
+    #[test]
+    fn reads_options() {
+        let result = import_k6(SCRIPT).unwrap();
+        assert_eq!(result.config.config.workers, 20);
+        assert!(matches!(
+            &result.config.config.duration,
+            YamlDuration::String(d) if d == "5m"
+        ));
+        assert_eq!(result.config.config.base_url, "https://api.example.com");
+    }
+
+    #[test]
+    fn reads_compound_durations() {
+        let script = SCRIPT.replace("duration: '5m'", "duration: '1h30m15s'");
+        let result = import_k6(&script).unwrap();
+        assert!(matches!(
+            &result.config.config.duration,
+            YamlDuration::String(d) if d == "1h30m15s"
+        ));
+    }
+
+    #[test]
+    fn converts_requests_checks_and_sleeps() {
+        let result = import_k6(SCRIPT).unwrap();
+        let steps = &result.config.scenarios[0].steps;
+        assert_eq!(steps.len(), 3);
+
+        assert_eq!(steps[0].request.method, "GET");
+        assert_eq!(steps[0].request.path, "/products?page=1");
+        assert!(matches!(
+            steps[0].assertions[0],
+            YamlAssertion::StatusCode { expected: 200 }
+        ));
+        assert!(matches!(
+            steps[0].think_time,
+            Some(YamlThinkTime::Fixed(YamlDuration::Seconds(2)))
+        ));
+
+        assert_eq!(steps[1].request.method, "POST");
+        assert_eq!(steps[1].request.body.as_deref(), Some(r#"{"user":"bob"}"#));
+        let headers = steps[1].request.headers.as_ref().unwrap();
+        assert_eq!(headers["X-Client"], "k6");
+        assert!(matches!(
+            steps[1].assertions[0],
+            YamlAssertion::StatusCode { expected: 201 }
+        ));
+        assert!(steps[1].think_time.is_none());
+
+        assert_eq!(steps[2].request.method, "DELETE");
+        assert_eq!(steps[2].request.path, "/cart/${__VU}");
+    }
+
+    #[test]
+    fn reports_unsupported_constructs() {
+        let result = import_k6(SCRIPT).unwrap();
+        assert!(result.warnings.iter().any(|w| w.contains("http.batch")));
+        assert!(result.warnings.iter().any(|w| w.contains("sleep(0.5)")));
+    }
+
+    #[test]
+    fn rejects_script_without_requests() {
+        assert!(matches!(
+            import_k6("export default function () {}"),
+            Err(ImportError::InvalidFormat(_))
+        ));
+    }
+}
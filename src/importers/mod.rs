@@ -0,0 +1,167 @@
+//! Importers that convert recorded traffic and other tools' test plans into
+//! YAML scenarios.
+//!
+//! Every importer produces an [`ImportResult`]: a [`YamlConfig`] that can be
+//! written out with [`ImportResult::to_yaml`], plus human-readable warnings for
+//! anything that could not be converted faithfully. Importers are best-effort —
+//! review the generated YAML before running it.
+//!
+//! Available from the command line as `rust_loadtest import <format> <file>`.
+
+pub mod curl;
+pub mod har;
+pub mod jmeter;
+pub mod k6;
+pub mod postman;
+
+use crate::yaml_config::{YamlConfig, YamlDuration, YamlGlobalConfig, YamlScenario};
+use thiserror::Error;
+
+/// Errors that can occur while importing a test plan.
+#[derive(Error, Debug)]
+pub enum ImportError {
+    #[error("Failed to read input file: {0}")]
+    Io(#[from] std::io::Error),
+
+    #[error("Failed to parse JSON input: {0}")]
+    Json(#[from] serde_json::Error),
+
+    #[error("Failed to serialize YAML output: {0}")]
+    Yaml(#[from] serde_yaml::Error),
+
+    #[error("Invalid input: {0}")]
+    InvalidFormat(String),
+}
+
+/// Output of an importer.
+#[derive(Debug, Clone)]
+pub struct ImportResult {
+    /// Generated configuration.
+    pub config: YamlConfig,
+
+    /// Elements that were skipped or only partially converted.
+    pub warnings: Vec<String>,
+}
+
+impl ImportResult {
+    /// Serialize the generated configuration to YAML.
+    pub fn to_yaml(&self) -> Result<String, ImportError> {
+        Ok(serde_yaml::to_string(&self.config)?)
+    }
+}
+
+/// Split an absolute URL into `(origin, path_and_query)`.
+///
+/// `https://api.example.com/v1/users?page=2` →
+/// `("https://api.example.com", "/v1/users?page=2")`.
+/// Input without a scheme is treated as a path.
+pub(crate) fn split_url(url: &str) -> (String, String) {
+    let Some(scheme_end) = url.find("://") else {
+        return (String::new(), url.to_string());
+    };
+    let rest = &url[scheme_end + 3..];
+    match rest.find(['/', '?']) {
+        Some(idx) => {
+            let origin = url[..scheme_end + 3 + idx].to_string();
+            let path = &rest[idx..];
+            if path.starts_with('?') {
+                (origin, format!("/{}", path))
+            } else {
+                (origin, path.to_string())
+            }
+        }
+        None => (url.to_string(), "/".to_string()),
+    }
+}
+
+/// Headers that the HTTP client manages itself and that should not be
+/// replayed verbatim from a recording (plus HTTP/2 pseudo-headers).
+pub(crate) fn is_managed_header(name: &str) -> bool {
+    const MANAGED: &[&str] = &[
+        "host",
+        "content-length",
+        "connection",
+        "keep-alive",
+        "transfer-encoding",
+        "accept-encoding",
+        "cookie",
+        "upgrade",
+        "te",
+    ];
+    name.starts_with(':') || MANAGED.contains(&name.to_ascii_lowercase().as_str())
+}
+
+/// Build a runnable config around imported scenarios.
+///
+/// Defaults to a short single-worker concurrent run so the imported plan can
+/// be smoke-tested before the load model is tuned.
+pub(crate) fn build_config(
+    name: &str,
+    description: &str,
+    base_url: &str,
+    scenarios: Vec<YamlScenario>,
+) -> YamlConfig {
+    let mut config = YamlConfig::default();
+    config.metadata.name = Some(name.to_string());
+    config.metadata.description = Some(description.to_string());
+    config.config = YamlGlobalConfig {
+        base_url: base_url.to_string(),
+        workers: 1,
+        duration: YamlDuration::String("1m".to_string()),
+        ..config.config
+    };
+    config.scenarios = scenarios;
+    config
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn split_url_with_path_and_query() {
+        let (origin, path) = split_url("https://api.example.com/v1/users?page=2");
+        assert_eq!(origin, "https://api.example.com");
+        assert_eq!(path, "/v1/users?page=2");
+    }
+
+    #[test]
+    fn split_url_without_path() {
+        assert_eq!(
+            split_url("http://localhost:8080"),
+            ("http://localhost:8080".to_string(), "/".to_string())
+        );
+        assert_eq!(
+            split_url("http://localhost:8080?q=1"),
+            ("http://localhost:8080".to_string(), "/?q=1".to_string())
+        );
+    }
+
+    #[test]
+    fn split_url_relative() {
+        assert_eq!(split_url("/health"), (String::new(), "/health".to_string()));
+    }
+
+    #[test]
+    fn managed_headers() {
+        assert!(is_managed_header("Host"));
+        assert!(is_managed_header(":authority"));
+        assert!(is_managed_header("Cookie"));
+        assert!(!is_managed_header("Authorization"));
+        assert!(!is_managed_header("Content-Type"));
+    }
+
+    #[test]
+    fn build_config_is_valid() {
+        let config = build_config("t", "d", "https://example.com", vec![]);
+        assert_eq!(config.config.workers, 1);
+        assert_eq!(config.config.base_url, "https://example.com");
+        let yaml = ImportResult {
+            config,
+            warnings: vec![],
+        }
+        .to_yaml()
+        .unwrap();
+        assert!(yaml.contains("baseUrl: https://example.com"));
+    }
+}
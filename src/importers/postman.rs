@@ -0,0 +1,683 @@
+//! Postman collection (v2.1) import.
+//!
+//! Mapping:
+//! - top-level folders → scenarios (nested folders are flattened into their
+//!   top-level folder); requests outside any folder form one scenario named
+//!   after the collection
+//! - requests → steps (method, URL, enabled headers, raw/urlencoded bodies,
+//!   bearer auth)
+//! - test scripts → assertions and extractors for the common `pm.*` idioms
+//!   (status checks, response time, headers, body text, `pm.*.set(...)` of
+//!   JSON fields); everything else is reported as a warning
+//! - collection and environment variables → substituted into the generated
+//!   steps; unknown `{{var}}` references become runtime `${var}` variables
+
+use super::{build_config, split_url, ImportError, ImportResult};
+use crate::yaml_config::{
+    YamlAssertion, YamlDuration, YamlExtractor, YamlRequest, YamlScenario, YamlScenarioConfig,
+    YamlStep,
+};
+use regex::Regex;
+use serde::Deserialize;
+use serde_json::Value;
+use std::collections::HashMap;
+use std::path::Path;
+
+#[derive(Deserialize)]
+struct Collection {
+    info: CollectionInfo,
+    #[serde(default)]
+    item: Vec<Item>,
+    #[serde(default)]
+    variable: Vec<KeyValue>,
+    auth: Option<Value>,
+}
+
+#[derive(Deserialize)]
+struct CollectionInfo {
+    name: String,
+}
+
+#[derive(Deserialize)]
+struct Item {
+    name: String,
+    /// Present on folders.
+    item: Option<Vec<Item>>,
+    /// Present on requests.
+    request: Option<PostmanRequest>,
+    #[serde(default)]
+    event: Vec<Event>,
+}
+
+#[derive(Deserialize)]
+struct PostmanRequest {
+    #[serde(default = "default_method")]
+    method: String,
+    url: Option<Value>,
+    #[serde(default)]
+    header: Vec<KeyValue>,
+    body: Option<Body>,
+    auth: Option<Value>,
+}
+
+fn default_method() -> String {
+    "GET".to_string()
+}
+
+#[derive(Deserialize)]
+struct KeyValue {
+    key: String,
+    #[serde(default)]
+    value: Value,
+    #[serde(default)]
+    disabled: bool,
+    /// Environment files use `enabled` instead of `disabled`.
+    enabled: Option<bool>,
+}
+
+impl KeyValue {
+    fn is_active(&self) -> bool {
+        !self.disabled && self.enabled.unwrap_or(true)
+    }
+
+    fn value_string(&self) -> String {
+        match &self.value {
+            Value::String(s) => s.clone(),
+            Value::Null => String::new(),
+            other => other.to_string(),
+        }
+    }
+}
+
+#[derive(Deserialize)]
+struct Body {
+    mode: Option<String>,
+    raw: Option<String>,
+    #[serde(default)]
+    urlencoded: Vec<KeyValue>,
+}
+
+#[derive(Deserialize)]
+struct Event {
+    listen: String,
+    script: Option<Script>,
+}
+
+#[derive(Deserialize)]
+struct Script {
+    #[serde(default)]
+    exec: ScriptLines,
+}
+
+#[derive(Deserialize, Default)]
+#[serde(untagged)]
+enum ScriptLines {
+    Lines(Vec<String>),
+    Single(String),
+    #[default]
+    Empty,
+}
+
+#[derive(Deserialize)]
+struct Environment {
+    #[serde(default)]
+    values: Vec<KeyValue>,
+}
+
+/// Import a Postman collection, optionally with an environment file.
+pub fn import_postman_file(
+    collection_path: impl AsRef<Path>,
+    environment_path: Option<&Path>,
+) -> Result<ImportResult, ImportError> {
+    let collection = std::fs::read_to_string(collection_path)?;
+    let environment = environment_path.map(std::fs::read_to_string).transpose()?;
+    import_postman(&collection, environment.as_deref())
+}
+
+/// Import Postman collection JSON (and optional environment JSON).
+pub fn import_postman(
+    collection_json: &str,
+    environment_json: Option<&str>,
+) -> Result<ImportResult, ImportError> {
+    let collection: Collection = serde_json::from_str(collection_json)?;
+
+    // Environment values override collection variables, as in Postman.
+    let mut variables: HashMap<String, String> = collection
+        .variable
+        .iter()
+        .filter(|v| v.is_active())
+        .map(|v| (v.key.clone(), v.value_string()))
+        .collect();
+    if let Some(env) = environment_json {
+        let env: Environment = serde_json::from_str(env)?;
+        for v in env.values.iter().filter(|v| v.is_active()) {
+            variables.insert(v.key.clone(), v.value_string());
+        }
+    }
+
+    let mut converter = Converter {
+        variables,
+        collection_auth: collection.auth.clone(),
+        warnings: Vec::new(),
+        base_url: None,
+    };
+
+    let mut scenarios = Vec::new();
+    let mut loose_steps = Vec::new();
+    for item in &collection.item {
+        match &item.item {
+            Some(children) => {
+                let mut steps = Vec::new();
+                converter.collect_steps(children, &mut steps);
+                if steps.is_empty() {
+                    converter
+                        .warnings
+                        .push(format!("Folder '{}' has no requests; skipped", item.name));
+                    continue;
+                }
+                scenarios.push(scenario(&item.name, steps));
+            }
+            None => converter.collect_steps(std::slice::from_ref(item), &mut loose_steps),
+        }
+    }
+    if !loose_steps.is_empty() {
+        scenarios.insert(0, scenario(&collection.info.name, loose_steps));
+    }
+
+    if scenarios.is_empty() {
+        return Err(ImportError::InvalidFormat(
+            "Postman collection contains no requests".to_string(),
+        ));
+    }
+
+    let base_url = converter
+        .base_url
+        .clone()
+        .unwrap_or_else(|| "http://localhost".to_string());
+
+    Ok(ImportResult {
+        config: build_config(
+            &collection.info.name,
+            "Generated by rust_loadtest import postman",
+            &base_url,
+            scenarios,
+        ),
+        warnings: converter.warnings,
+    })
+}
+
+fn scenario(name: &str, steps: Vec<YamlStep>) -> YamlScenario {
+    YamlScenario {
+        name: name.to_string(),
+        weight: 1.0,
+        steps,
+        data_file: None,
+        data_source: None,
+        config: YamlScenarioConfig::default(),
+        network_profile: None,
+        tags: HashMap::new(),
+        max_concurrent: None,
+        depends_on: Vec::new(),
+        iterations: None,
+        client: None,
+        headers: Default::default(),
+        auth: None,
+        optional: false,
+        request_defaults: None,
+        rps: None,
+    }
+}
+
+struct Converter {
+    variables: HashMap<String, String>,
+    collection_auth: Option<Value>,
+    warnings: Vec<String>,
+    base_url: Option<String>,
+}
+
+impl Converter {
+    /// Convert requests depth-first, flattening nested folders.
+    fn collect_steps(&mut self, items: &[Item], steps: &mut Vec<YamlStep>) {
+        for item in items {
+            if let Some(children) = &item.item {
+                self.collect_steps(children, steps);
+            } else if let Some(request) = &item.request {
+                steps.push(self.convert_request(item, request));
+            }
+        }
+    }
+
+    /// Replace `{{var}}` with known values, or `${var}` for runtime variables.
+    fn substitute(&mut self, input: &str, context: &str) -> String {
+        let re = Regex::new(r"\{\{\s*([^}\s]+)\s*\}\}").expect("valid regex");
+        let mut unresolved = Vec::new();
+        let out = re
+            .replace_all(input, |caps: &regex::Captures| {
+                let name = &caps[1];
+                match self.variables.get(name) {
+                    Some(v) => v.clone(),
+                    None => {
+                        unresolved.push(name.to_string());
+                        format!("${{{}}}", name)
+                    }
+                }
+            })
+            .into_owned();
+        for name in unresolved {
+            self.warnings.push(format!(
+                "{}: variable '{{{{{}}}}}' has no value; left as runtime variable ${{{}}}",
+                context, name, name
+            ));
+        }
+        out
+    }
+
+    fn convert_request(&mut self, item: &Item, request: &PostmanRequest) -> YamlStep {
+        let context = format!("Request '{}'", item.name);
+
+        let raw_url = match &request.url {
+            Some(Value::String(s)) => s.clone(),
+            Some(Value::Object(obj)) => obj
+                .get("raw")
+                .and_then(|v| v.as_str())
+                .unwrap_or_default()
+                .to_string(),
+            _ => String::new(),
+        };
+        let url = self.substitute(&raw_url, &context);
+        let (origin, path) = split_url(&url);
+        // The first absolute URL's origin becomes the base URL; requests to
+        // other origins keep their absolute URL.
+        if !origin.is_empty() && self.base_url.is_none() {
+            self.base_url = Some(origin.clone());
+        }
+        let path = if origin.is_empty() || self.base_url.as_deref() == Some(origin.as_str()) {
+            path
+        } else {
+            url.clone()
+        };
+
+        let mut headers = HashMap::new();
+        for h in request.header.iter().filter(|h| h.is_active()) {
+            let value = self.substitute(&h.value_string(), &context);
+            headers.insert(h.key.clone(), value);
+        }
+
+        let auth = request
+            .auth
+            .as_ref()
+            .or(self.collection_auth.as_ref())
+            .cloned();
+        if let Some(auth) = auth {
+            self.convert_auth(&auth, &context, &mut headers);
+        }
+
+        let body = match &request.body {
+            Some(body) => match body.mode.as_deref() {
+                Some("raw") => body.raw.clone().map(|b| self.substitute(&b, &context)),
+                Some("urlencoded") => {
+                    headers
+                        .entry("Content-Type".to_string())
+                        .or_insert_with(|| "application/x-www-form-urlencoded".to_string());
+                    let pairs: Vec<String> = body
+                        .urlencoded
+                        .iter()
+                        .filter(|kv| kv.is_active())
+                        .map(|kv| format!("{}={}", kv.key, kv.value_string()))
+                        .collect();
+                    Some(self.substitute(&pairs.join("&"), &context))
+                }
+                Some(mode) => {
+                    self.warnings
+                        .push(format!("{}: body mode '{}' not supported", context, mode));
+                    None
+                }
+                None => None,
+            },
+            None => None,
+        };
+
+        let mut assertions = Vec::new();
+        let mut extract = Vec::new();
+        for event in item.event.iter().filter(|e| e.listen == "test") {
+            let lines = match event.script.as_ref().map(|s| &s.exec) {
+                Some(ScriptLines::Lines(lines)) => lines.clone(),
+                Some(ScriptLines::Single(s)) => s.lines().map(str::to_string).collect(),
+                _ => Vec::new(),
+            };
+            self.convert_tests(&lines, &context, &mut assertions, &mut extract);
+        }
+        for event in item.event.iter().filter(|e| e.listen == "prerequest") {
+            if event.script.is_some() {
+                self.warnings.push(format!(
+                    "{}: pre-request scripts are not supported",
+                    context
+                ));
+            }
+        }
+
+        YamlStep {
+            name: Some(item.name.clone()),
+            request: YamlRequest {
+                method: request.method.to_uppercase(),
+                path,
+                query_params: None,
+                headers: if headers.is_empty() {
+                    None
+                } else {
+                    Some(headers)
+                },
+                body,
+                body_size: None,
+                protobuf: None,
+                host: None,
+            },
+            extract,
+            assertions,
+            cache: None,
+            think_time: None,
+            from_curl: None,
+            tags: HashMap::new(),
+            paginate: None,
+            max_concurrent: None,
+            slo: None,
+            shared: vec![],
+            rendezvous: None,
+            conditional: false,
+            idempotency_key: None,
+            tcp_check: None,
+            expected_status: vec![],
+        }
+    }
+
+    fn convert_auth(&mut self, auth: &Value, context: &str, headers: &mut HashMap<String, String>) {
+        let auth_type = auth.get("type").and_then(|t| t.as_str()).unwrap_or("");
+        match auth_type {
+            "noauth" | "" => {}
+            "bearer" => {
+                let token = auth
+                    .get("bearer")
+                    .and_then(|b| b.as_array())
+                    .and_then(|entries| {
+                        entries
+                            .iter()
+                            .find(|e| e.get("key").and_then(|k| k.as_str()) == Some("token"))
+                    })
+                    .and_then(|e| e.get("value"))
+                    .and_then(|v| v.as_str())
+                    .unwrap_or_default()
+                    .to_string();
+                let token = self.substitute(&token, context);
+                headers.insert("Authorization".to_string(), format!("Bearer {}", token));
+            }
+            other => self.warnings.push(format!(
+                "{}: auth type '{}' not supported; add the header manually",
+                context, other
+            )),
+        }
+    }
+
+    /// Translate common Postman test idioms into assertions/extractors.
+    fn convert_tests(
+        &mut self,
+        lines: &[String],
+        context: &str,
+        assertions: &mut Vec<YamlAssertion>,
+        extract: &mut Vec<YamlExtractor>,
+    ) {
+        let status = Regex::new(
+            r"pm\.response\.to\.have\.status\((\d{3})\)|pm\.expect\(pm\.response\.code\)\.to\.(?:eql|equal)\((\d{3})\)",
+        )
+        .expect("valid regex");
+        let response_time = Regex::new(
+            r"pm\.expect\(pm\.response\.responseTime\)\.to\.be\.(?:below|lessThan)\((\d+)\)",
+        )
+        .expect("valid regex");
+        let header = Regex::new(r#"pm\.response\.to\.have\.header\(\s*["']([^"']+)["']"#)
+            .expect("valid regex");
+        let body_include = Regex::new(
+            r#"pm\.expect\(pm\.response\.text\(\)\)\.to\.include\(\s*["']([^"']+)["']\s*\)"#,
+        )
+        .expect("valid regex");
+        let set_var = Regex::new(
+            r#"pm\.(?:environment|collectionVariables|globals|variables)\.set\(\s*["']([^"']+)["']\s*,\s*(?:pm\.response\.json\(\)|jsonData|responseJson)((?:\.[A-Za-z_$][\w$]*|\[\d+\])+)\s*\)"#,
+        )
+        .expect("valid regex");
+
+        let mut ignored = 0;
+        for line in lines {
+            let trimmed = line.trim();
+            if let Some(caps) = status.captures(trimmed) {
+                let code = caps.get(1).or(caps.get(2)).unwrap().as_str();
+                assertions.push(YamlAssertion::StatusCode {
+                    expected: code.parse().unwrap_or(200),
+                });
+            } else if let Some(caps) = response_time.captures(trimmed) {
+                let ms: u64 = caps[1].parse().unwrap_or(1000);
+                let secs = ms.div_ceil(1000).max(1);
+                if !ms.is_multiple_of(1000) {
+                    self.warnings.push(format!(
+                        "{}: response time limit {}ms rounded up to {}s",
+                        context, ms, secs
+                    ));
+                }
+                assertions.push(YamlAssertion::ResponseTime {
+                    max: YamlDuration::Seconds(secs),
+                });
+            } else if let Some(caps) = header.captures(trimmed) {
+                assertions.push(YamlAssertion::HeaderExists {
+                    header: caps[1].to_string(),
+                });
+            } else if let Some(caps) = body_include.captures(trimmed) {
+                assertions.push(YamlAssertion::BodyContains {
+                    text: caps[1].to_string(),
+                });
+            } else if let Some(caps) = set_var.captures(trimmed) {
+                extract.push(YamlExtractor::JsonPath {
+                    name: caps[1].to_string(),
+                    json_path: format!("${}", &caps[2]),
+                    transform: None,
+                    scope: None,
+                });
+            } else if is_script_noise(trimmed) {
+                continue;
+            } else {
+                ignored += 1;
+            }
+        }
+
+        if ignored > 0 {
+            self.warnings.push(format!(
+                "{}: {} test script line(s) could not be converted",
+                context, ignored
+            ));
+        }
+    }
+}
+
+/// Lines that carry no test logic on their own (test wrappers, braces,
+/// comments, `jsonData` declarations).
+fn is_script_noise(line: &str) -> bool {
+    line.is_empty()
+        || line.starts_with("//")
+        || line.starts_with("pm.test(")
+        || line.starts_with("});")
+        || line == "}"
+        || line == "})"
+        || line.contains("= pm.response.json()")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const COLLECTION: &str = r#"{
+      "info": {
+        "name": "Shop API",
+        "schema": "https://schema.getpostman.com/json/collection/v2.1.0/collection.json"
+      },
+      "variable": [{"key": "baseUrl", "value": "https://api.shop.test"}],
+      "item": [
+        {
+          "name": "Health",
+          "request": {"method": "GET", "url": "{{baseUrl}}/health"}
+        },
+        {
+          "name": "Checkout",
+          "item": [
+            {
+              "name": "Login",
+              "request": {
+                "method": "POST",
+                "header": [
+                  {"key": "Content-Type", "value": "application/json"},
+                  {"key": "X-Debug", "value": "1", "disabled": true}
+                ],
+                "body": {"mode": "raw", "raw": "{\"user\":\"{{username}}\"}"},
+                "url": {"raw": "{{baseUrl}}/login", "host": ["{{baseUrl}}"], "path": ["login"]}
+              },
+              "event": [{
+                "listen": "test",
+                "script": {"exec": [
+                  "pm.test(\"ok\", function () {",
+                  "    pm.response.to.have.status(200);",
+                  "});",
+                  "var jsonData = pm.response.json();",
+                  "pm.environment.set(\"token\", jsonData.data.token);",
+                  "pm.expect(pm.response.responseTime).to.be.below(1500);",
+                  "console.log('done');"
+                ]}
+              }]
+            },
+            {
+              "name": "Nested",
+              "item": [{
+                "name": "Cart",
+                "request": {
+                  "method": "get",
+                  "url": "{{baseUrl}}/cart",
+                  "auth": {"type": "bearer", "bearer": [{"key": "token", "value": "{{token}}", "type": "string"}]}
+                },
+                "event": [{
+                  "listen": "test",
+                  "script": {"exec": "pm.response.to.have.header(\"X-Cart-Id\");\npm.expect(pm.response.text()).to.include(\"items\");"}
+                }]
+              }]
+            }
+          ]
+        }
+      ]
+    }"#;
+
+    const ENVIRONMENT: &str = r#"{
+      "name": "staging",
+      "values": [
+        {"key": "baseUrl", "value": "https://staging.shop.test", "enabled": true},
+        {"key": "username", "value": "alice", "enabled": true}
+      ]
+    }"#;
+
+    #[test]
+    fn maps_folders_to_scenarios() {
+        let result = import_postman(COLLECTION, None).unwrap();
+        let scenarios = &result.config.scenarios;
+        assert_eq!(scenarios.len(), 2);
+        assert_eq!(scenarios[0].name, "Shop API");
+        assert_eq!(scenarios[0].steps.len(), 1);
+        assert_eq!(scenarios[1].name, "Checkout");
+        // Nested folder flattened into Checkout
+        assert_eq!(scenarios[1].steps.len(), 2);
+        assert_eq!(result.config.config.base_url, "https://api.shop.test");
+        assert_eq!(scenarios[1].steps[1].request.method, "GET");
+    }
+
+    #[test]
+    fn environment_overrides_collection_variables() {
+        let result = import_postman(COLLECTION, Some(ENVIRONMENT)).unwrap();
+        assert_eq!(result.config.config.base_url, "https://staging.shop.test");
+        let login = &result.config.scenarios[1].steps[0];
+        assert_eq!(login.request.path, "/login");
+        assert_eq!(login.request.body.as_deref(), Some(r#"{"user":"alice"}"#));
+    }
+
+    #[test]
+    fn unresolved_variables_become_runtime_variables() {
+        let result = import_postman(COLLECTION, None).unwrap();
+        let login = &result.config.scenarios[1].steps[0];
+        assert_eq!(
+            login.request.body.as_deref(),
+            Some(r#"{"user":"${username}"}"#)
+        );
+        assert!(result.warnings.iter().any(|w| w.contains("{{username}}")));
+
+        let cart = &result.config.scenarios[1].steps[1];
+        let headers = cart.request.headers.as_ref().unwrap();
+        assert_eq!(headers["Authorization"], "Bearer ${token}");
+    }
+
+    #[test]
+    fn disabled_headers_are_skipped() {
+        let result = import_postman(COLLECTION, None).unwrap();
+        let headers = result.config.scenarios[1].steps[0]
+            .request
+            .headers
+            .clone()
+            .unwrap();
+        assert!(headers.contains_key("Content-Type"));
+        assert!(!headers.contains_key("X-Debug"));
+    }
+
+    #[test]
+    fn converts_tests_to_assertions_and_extractors() {
+        let result = import_postman(COLLECTION, None).unwrap();
+        let login = &result.config.scenarios[1].steps[0];
+
+        assert!(matches!(
+            login.assertions[0],
+            YamlAssertion::StatusCode { expected: 200 }
+        ));
+        assert!(matches!(
+            login.assertions[1],
+            YamlAssertion::ResponseTime {
+                max: YamlDuration::Seconds(2)
+            }
+        ));
+        match &login.extract[0] {
+            YamlExtractor::JsonPath {
+                name, json_path, ..
+            } => {
+                assert_eq!(name, "token");
+                assert_eq!(json_path, "$.data.token");
+            }
+            other => panic!("unexpected extractor: {:?}", other),
+        }
+        assert!(result
+            .warnings
+            .iter()
+            .any(|w| w.contains("1 test script line(s)")));
+
+        let cart = &result.config.scenarios[1].steps[1];
+        assert!(matches!(
+            &cart.assertions[0],
+            YamlAssertion::HeaderExists { header } if header == "X-Cart-Id"
+        ));
+        assert!(matches!(
+            &cart.assertions[1],
+            YamlAssertion::BodyContains { text } if text == "items"
+        ));
+    }
+
+    #[test]
+    fn output_round_trips_through_yaml_config() {
+        let result = import_postman(COLLECTION, Some(ENVIRONMENT)).unwrap();
+        let yaml = result.to_yaml().unwrap();
+        let parsed = crate::yaml_config::YamlConfig::from_str(&yaml).unwrap();
+        assert_eq!(parsed.to_scenarios().unwrap().len(), 2);
+    }
+
+    #[test]
+    fn empty_collection_is_rejected() {
+        let result = import_postman(r#"{"info": {"name": "x"}, "item": []}"#, None);
+        assert!(matches!(result, Err(ImportError::InvalidFormat(_))));
+    }
+}
@@ -0,0 +1,238 @@
+//! Cap on requests in flight across all workers (`MAX_IN_FLIGHT`).
+//!
+//! The load model decides when requests start, not how many may be waiting
+//! for a response at once: when the target's latency spikes under an RPS
+//! model, outstanding requests pile up without bound, growing the
+//! generator's memory and burying a struggling target. With `MAX_IN_FLIGHT`
+//! set, every request takes a slot from one process-wide semaphore before it
+//! is sent and returns it once the response is done, whatever the worker
+//! count or load model.
+//!
+//! Saturation shows as `in_flight_waiting` (workers blocked right now),
+//! `in_flight_blocked_total` and `in_flight_wait_seconds_total`; the achieved
+//! rate then falls behind the target because the cap, not the model, paces
+//! the test. `0` (the default) means no cap.
+//!
+//! `BACKPRESSURE` chooses what happens while the cap is saturated:
+//!
+//! - `queue` (default): workers wait for a slot;
+//! - `shed`: single-URL requests and new scenario iterations are not sent
+//!   and are counted in `generator_dropped_total` instead, keeping open-model
+//!   tests honest about the load they failed to offer. Iterations already
+//!   running queue for their remaining steps, so journeys aren't cut short;
+//! - `degrade`: new iterations of scenarios marked `optional: true` are shed
+//!   as above; everything else queues.
+
+use std::fmt;
+use std::str::FromStr;
+use std::sync::atomic::{AtomicU8, Ordering};
+use std::sync::{Arc, RwLock};
+use std::time::Instant;
+
+use tokio::sync::{OwnedSemaphorePermit, Semaphore, TryAcquireError};
+
+use crate::metrics::{
+    GENERATOR_DROPPED_TOTAL, IN_FLIGHT_BLOCKED_TOTAL, IN_FLIGHT_LIMIT, IN_FLIGHT_WAITING,
+    IN_FLIGHT_WAIT_SECONDS_TOTAL,
+};
+
+lazy_static::lazy_static! {
+    static ref LIMIT: RwLock<Option<Arc<Semaphore>>> = RwLock::new(None);
+}
+
+static POLICY: AtomicU8 = AtomicU8::new(BackpressurePolicy::Queue as u8);
+
+/// What workers do while the in-flight cap is saturated (`BACKPRESSURE`).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum BackpressurePolicy {
+    /// Wait for a slot.
+    #[default]
+    Queue = 0,
+    /// Drop new requests and iterations.
+    Shed = 1,
+    /// Drop new iterations of optional scenarios; queue the rest.
+    Degrade = 2,
+}
+
+impl FromStr for BackpressurePolicy {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "" | "queue" => Ok(BackpressurePolicy::Queue),
+            "shed" => Ok(BackpressurePolicy::Shed),
+            "degrade" => Ok(BackpressurePolicy::Degrade),
+            other => Err(format!("expected queue, shed or degrade (got '{}')", other)),
+        }
+    }
+}
+
+impl fmt::Display for BackpressurePolicy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            BackpressurePolicy::Queue => "queue",
+            BackpressurePolicy::Shed => "shed",
+            BackpressurePolicy::Degrade => "degrade",
+        })
+    }
+}
+
+/// Installs the cap and policy for a new test; `0` removes the cap.
+/// Requests holding a slot of the previous cap release it to the old
+/// semaphore.
+pub fn init(max_in_flight: usize, policy: BackpressurePolicy) {
+    *LIMIT.write().unwrap_or_else(|e| e.into_inner()) =
+        (max_in_flight > 0).then(|| Arc::new(Semaphore::new(max_in_flight)));
+    POLICY.store(policy as u8, Ordering::Relaxed);
+    IN_FLIGHT_LIMIT.set(max_in_flight as f64);
+}
+
+fn semaphore() -> Option<Arc<Semaphore>> {
+    LIMIT.read().unwrap_or_else(|e| e.into_inner()).clone()
+}
+
+fn policy() -> BackpressurePolicy {
+    match POLICY.load(Ordering::Relaxed) {
+        1 => BackpressurePolicy::Shed,
+        2 => BackpressurePolicy::Degrade,
+        _ => BackpressurePolicy::Queue,
+    }
+}
+
+/// Returns true if the cap is set and has no free slot.
+pub fn is_saturated() -> bool {
+    semaphore().is_some_and(|s| s.available_permits() == 0)
+}
+
+/// Returns true if a new iteration of a scenario (`optional` or not) should
+/// be skipped under the policy; counts it in `generator_dropped_total`.
+pub fn shed_iteration(scenario: &str, optional: bool) -> bool {
+    let shed = match policy() {
+        BackpressurePolicy::Queue => false,
+        BackpressurePolicy::Shed => is_saturated(),
+        BackpressurePolicy::Degrade => optional && is_saturated(),
+    };
+    if shed {
+        GENERATOR_DROPPED_TOTAL.with_label_values(&[scenario]).inc();
+    }
+    shed
+}
+
+/// A slot under the cap, released on drop; empty when there is no cap.
+pub struct InFlightPermit {
+    _permit: Option<OwnedSemaphorePermit>,
+}
+
+/// Counts a blocked worker in `in_flight_waiting` and its wait in
+/// `in_flight_wait_seconds_total`, also when the wait is cancelled.
+struct Waiting(Instant);
+
+impl Waiting {
+    fn start() -> Self {
+        IN_FLIGHT_WAITING.inc();
+        Self(Instant::now())
+    }
+}
+
+impl Drop for Waiting {
+    fn drop(&mut self) {
+        IN_FLIGHT_WAITING.dec();
+        IN_FLIGHT_WAIT_SECONDS_TOTAL.inc_by(self.0.elapsed().as_secs_f64());
+    }
+}
+
+/// Waits for a slot under the cap.
+pub async fn acquire() -> InFlightPermit {
+    let Some(semaphore) = semaphore() else {
+        return InFlightPermit { _permit: None };
+    };
+    let permit = match semaphore.clone().try_acquire_owned() {
+        Ok(permit) => permit,
+        Err(_) => {
+            IN_FLIGHT_BLOCKED_TOTAL.inc();
+            let waiting = Waiting::start();
+            let permit = semaphore
+                .acquire_owned()
+                .await
+                .expect("the in-flight semaphore is never closed");
+            drop(waiting);
+            permit
+        }
+    };
+    InFlightPermit {
+        _permit: Some(permit),
+    }
+}
+
+/// A slot for a single-URL request: waits for one, or under
+/// `BACKPRESSURE=shed` returns `None` (counted in `generator_dropped_total`)
+/// when none is free.
+pub async fn admit_request() -> Option<InFlightPermit> {
+    if policy() != BackpressurePolicy::Shed {
+        return Some(acquire().await);
+    }
+    let slot = try_acquire();
+    if slot.is_none() {
+        GENERATOR_DROPPED_TOTAL.with_label_values(&[""]).inc();
+    }
+    slot
+}
+
+/// Takes a slot if one is free right now, e.g. for further requests of a
+/// pipelined batch that already holds one; `None` when the cap is reached.
+pub fn try_acquire() -> Option<InFlightPermit> {
+    let Some(semaphore) = semaphore() else {
+        return Some(InFlightPermit { _permit: None });
+    };
+    match semaphore.try_acquire_owned() {
+        Ok(permit) => Some(InFlightPermit {
+            _permit: Some(permit),
+        }),
+        Err(TryAcquireError::NoPermits | TryAcquireError::Closed) => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    #[tokio::test]
+    async fn caps_requests_in_flight() {
+        init(2, BackpressurePolicy::Queue);
+        let first = acquire().await;
+        let _second = acquire().await;
+        assert!(try_acquire().is_none());
+        let blocked = tokio::time::timeout(Duration::from_millis(50), acquire()).await;
+        assert!(blocked.is_err(), "third request should wait for a slot");
+
+        drop(first);
+        let third = tokio::time::timeout(Duration::from_millis(50), acquire()).await;
+        assert!(third.is_ok());
+
+        init(0, BackpressurePolicy::Queue);
+        assert!(try_acquire().is_some());
+
+        // Shedding only applies while the cap is saturated.
+        init(1, BackpressurePolicy::Degrade);
+        assert!(!shed_iteration("browse", true));
+        let _held = acquire().await;
+        assert!(shed_iteration("browse", true));
+        assert!(!shed_iteration("checkout", false));
+
+        init(1, BackpressurePolicy::Shed);
+        let _held = acquire().await;
+        assert!(shed_iteration("checkout", false));
+        assert!(admit_request().await.is_none());
+
+        init(0, BackpressurePolicy::Queue);
+    }
+
+    #[test]
+    fn parses_policy() {
+        assert_eq!("".parse(), Ok(BackpressurePolicy::Queue));
+        assert_eq!(" Shed ".parse(), Ok(BackpressurePolicy::Shed));
+        assert_eq!("degrade".parse(), Ok(BackpressurePolicy::Degrade));
+        assert!("drop".parse::<BackpressurePolicy>().is_err());
+    }
+}
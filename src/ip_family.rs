@@ -0,0 +1,142 @@
+//! Address-family selection for outgoing connections (`IP_FAMILY`).
+//!
+//! With `auto` (the default) the client races IPv6 and IPv4 addresses
+//! (happy eyeballs) and uses whichever connects first. `v4` or `v6` restricts
+//! DNS results to that family, so dual-stack targets can be tested one
+//! family at a time. Every request is counted in
+//! `requests_by_ip_family_total` by the family of the address it was sent to.
+
+use std::net::{IpAddr, SocketAddr};
+use std::str::FromStr;
+
+use reqwest::dns::{Addrs, Name, Resolve, Resolving};
+
+use crate::metrics::REQUESTS_BY_IP_FAMILY_TOTAL;
+
+/// Which address family outgoing connections may use.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum IpFamily {
+    #[default]
+    Auto,
+    V4,
+    V6,
+}
+
+impl IpFamily {
+    /// Returns true if connections to `ip` are allowed.
+    pub fn allows(&self, ip: IpAddr) -> bool {
+        match self {
+            IpFamily::Auto => true,
+            IpFamily::V4 => ip.is_ipv4(),
+            IpFamily::V6 => ip.is_ipv6(),
+        }
+    }
+}
+
+impl FromStr for IpFamily {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "" | "auto" => Ok(IpFamily::Auto),
+            "v4" | "ipv4" => Ok(IpFamily::V4),
+            "v6" | "ipv6" => Ok(IpFamily::V6),
+            other => Err(format!("expected v4, v6 or auto (got '{}')", other)),
+        }
+    }
+}
+
+impl std::fmt::Display for IpFamily {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            IpFamily::Auto => "auto",
+            IpFamily::V4 => "v4",
+            IpFamily::V6 => "v6",
+        })
+    }
+}
+
+/// Label for the family of `ip`: `v4` or `v6`.
+pub fn label(ip: IpAddr) -> &'static str {
+    if ip.is_ipv4() {
+        "v4"
+    } else {
+        "v6"
+    }
+}
+
+/// DNS resolver that drops addresses of the other family.
+pub struct FamilyResolver {
+    family: IpFamily,
+}
+
+impl FamilyResolver {
+    pub fn new(family: IpFamily) -> Self {
+        Self { family }
+    }
+}
+
+impl Resolve for FamilyResolver {
+    fn resolve(&self, name: Name) -> Resolving {
+        let family = self.family;
+        Box::pin(async move {
+            let host = name.as_str().to_string();
+            let addrs: Vec<SocketAddr> = tokio::net::lookup_host((host.as_str(), 0))
+                .await?
+                .filter(|addr| family.allows(addr.ip()))
+                .collect();
+            if addrs.is_empty() {
+                let err: Box<dyn std::error::Error + Send + Sync> =
+                    format!("'{}' has no {} addresses (IP_FAMILY)", host, family).into();
+                return Err(err);
+            }
+            let addrs: Addrs = Box::new(addrs.into_iter());
+            Ok(addrs)
+        })
+    }
+}
+
+/// Counts one request by the family of the address it was sent to.
+pub fn record(ip: Option<IpAddr>, node_id: &str, run_id: &str) {
+    if let Some(ip) = ip {
+        REQUESTS_BY_IP_FAMILY_TOTAL
+            .with_label_values(&[label(ip), node_id, run_id])
+            .inc();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_families() {
+        assert_eq!("auto".parse::<IpFamily>().unwrap(), IpFamily::Auto);
+        assert_eq!("V4".parse::<IpFamily>().unwrap(), IpFamily::V4);
+        assert_eq!("ipv6".parse::<IpFamily>().unwrap(), IpFamily::V6);
+        assert!("v5".parse::<IpFamily>().is_err());
+    }
+
+    #[test]
+    fn filters_by_family() {
+        let v4: IpAddr = "10.0.0.1".parse().unwrap();
+        let v6: IpAddr = "2001:db8::1".parse().unwrap();
+        assert!(IpFamily::Auto.allows(v4) && IpFamily::Auto.allows(v6));
+        assert!(IpFamily::V4.allows(v4) && !IpFamily::V4.allows(v6));
+        assert!(IpFamily::V6.allows(v6) && !IpFamily::V6.allows(v4));
+        assert_eq!(label(v4), "v4");
+        assert_eq!(label(v6), "v6");
+    }
+
+    #[tokio::test]
+    async fn resolver_keeps_only_requested_family() {
+        let resolver = FamilyResolver::new(IpFamily::V4);
+        let addrs: Vec<SocketAddr> = resolver
+            .resolve(Name::from_str("localhost").unwrap())
+            .await
+            .unwrap()
+            .collect();
+        assert!(!addrs.is_empty());
+        assert!(addrs.iter().all(|a| a.is_ipv4()));
+    }
+}
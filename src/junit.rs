@@ -0,0 +1,220 @@
+//! JUnit XML report of thresholds and assertions (`JUNIT_PATH`).
+//!
+//! Jenkins, GitLab and most CI systems display JUnit XML natively, so
+//! writing the run's checks in that shape makes load-test failures show up
+//! next to unit test failures. The report has three suites:
+//!
+//! - `load test`: one case for the run itself, failing with the exit code
+//!   kind when the run failed for any reason (unreachable target, ...);
+//! - `scenario thresholds`: one case per `scenarioThresholds` entry;
+//! - `scenario assertions`: one case per scenario with assertions, failing
+//!   when any of its assertions failed.
+
+use std::io;
+use std::path::Path;
+
+use crate::run_summary::RunSummary;
+
+struct Case {
+    name: String,
+    /// Failure message and details.
+    failure: Option<(String, String)>,
+}
+
+struct Suite {
+    name: &'static str,
+    cases: Vec<Case>,
+}
+
+impl Suite {
+    fn failures(&self) -> usize {
+        self.cases.iter().filter(|c| c.failure.is_some()).count()
+    }
+}
+
+fn suites(summary: &RunSummary) -> Vec<Suite> {
+    let run = Case {
+        name: format!("run {}", summary.run_id),
+        failure: (!summary.passed).then(|| (summary.outcome.clone(), summary.message.clone())),
+    };
+
+    let thresholds = summary
+        .thresholds
+        .iter()
+        .map(|threshold| Case {
+            name: format!("{}: {}", threshold.scenario, threshold.required),
+            failure: (!threshold.passed).then(|| {
+                let actual = threshold.success_rate.map_or_else(
+                    || "no executions".to_string(),
+                    |rate| format!("success rate {:.3}%", rate),
+                );
+                (
+                    format!("{}, required {}", actual, threshold.required),
+                    actual,
+                )
+            }),
+        })
+        .collect();
+
+    let assertions = summary
+        .assertions
+        .iter()
+        .map(|a| Case {
+            name: a.scenario.clone(),
+            failure: (a.failed > 0).then(|| {
+                (
+                    format!("{} of {} assertions failed", a.failed, a.passed + a.failed),
+                    format!("passed: {}\nfailed: {}", a.passed, a.failed),
+                )
+            }),
+        })
+        .collect();
+
+    vec![
+        Suite {
+            name: "load test",
+            cases: vec![run],
+        },
+        Suite {
+            name: "scenario thresholds",
+            cases: thresholds,
+        },
+        Suite {
+            name: "scenario assertions",
+            cases: assertions,
+        },
+    ]
+}
+
+/// The JUnit XML document for `summary`.
+pub fn render(summary: &RunSummary) -> String {
+    let suites = suites(summary);
+    let tests: usize = suites.iter().map(|s| s.cases.len()).sum();
+    let failures: usize = suites.iter().map(Suite::failures).sum();
+
+    let mut xml = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+    xml.push_str(&format!(
+        "<testsuites name=\"rust_loadtest\" tests=\"{}\" failures=\"{}\">\n",
+        tests, failures
+    ));
+    for suite in suites.iter().filter(|s| !s.cases.is_empty()) {
+        xml.push_str(&format!(
+            "  <testsuite name=\"{}\" tests=\"{}\" failures=\"{}\" errors=\"0\">\n",
+            suite.name,
+            suite.cases.len(),
+            suite.failures()
+        ));
+        for case in &suite.cases {
+            let open = format!(
+                "    <testcase classname=\"{}\" name=\"{}\"",
+                escape(suite.name),
+                escape(&case.name)
+            );
+            match &case.failure {
+                None => xml.push_str(&format!("{}/>\n", open)),
+                Some((message, details)) => xml.push_str(&format!(
+                    "{}>\n      <failure message=\"{}\">{}</failure>\n    </testcase>\n",
+                    open,
+                    escape(message),
+                    escape(details)
+                )),
+            }
+        }
+        xml.push_str("  </testsuite>\n");
+    }
+    xml.push_str("</testsuites>\n");
+    xml
+}
+
+/// Writes the report to `path`, creating parent directories.
+pub fn write(path: &Path, summary: &RunSummary) -> io::Result<()> {
+    if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+        std::fs::create_dir_all(parent)?;
+    }
+    std::fs::write(path, render(summary))
+}
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::run_summary::{AssertionSummary, ThresholdSummary};
+
+    fn summary() -> RunSummary {
+        RunSummary {
+            run_id: "r1".to_string(),
+            node_id: "n".to_string(),
+            finished_at: 0,
+            passed: false,
+            outcome: "threshold_failed".to_string(),
+            exit_code: 3,
+            message: "Threshold failed: checkout".to_string(),
+            requests: 10,
+            responses: 10,
+            latency: None,
+            scenarios: Vec::new(),
+            steps: Vec::new(),
+            thresholds: vec![
+                ThresholdSummary {
+                    scenario: "checkout".to_string(),
+                    required: "successRate > 99.5%".to_string(),
+                    success_rate: Some(90.0),
+                    passed: false,
+                },
+                ThresholdSummary {
+                    scenario: "browse".to_string(),
+                    required: "successRate >= 95%".to_string(),
+                    success_rate: Some(100.0),
+                    passed: true,
+                },
+            ],
+            assertions: vec![AssertionSummary {
+                scenario: "<login>".to_string(),
+                passed: 8,
+                failed: 2,
+            }],
+            seed: None,
+        }
+    }
+
+    #[test]
+    fn renders_cases_per_threshold_and_scenario() {
+        let xml = render(&summary());
+        assert!(xml.contains("<testsuites name=\"rust_loadtest\" tests=\"4\" failures=\"3\">"));
+        assert!(xml.contains(
+            "<testcase classname=\"scenario thresholds\" name=\"browse: successRate &gt;= 95%\"/>"
+        ));
+        assert!(xml.contains(
+            "<failure message=\"success rate 90.000%, required successRate &gt; 99.5%\">"
+        ));
+        assert!(xml.contains("name=\"&lt;login&gt;\""));
+        assert!(xml.contains("<failure message=\"2 of 10 assertions failed\">"));
+        assert!(xml.contains("<failure message=\"threshold_failed\">"));
+    }
+
+    #[test]
+    fn omits_empty_suites() {
+        let mut passed = summary();
+        passed.passed = true;
+        passed.thresholds.clear();
+        passed.assertions.clear();
+        let xml = render(&passed);
+        assert!(xml.contains("tests=\"1\" failures=\"0\""));
+        assert!(!xml.contains("scenario thresholds"));
+        assert!(xml.contains("<testcase classname=\"load test\" name=\"run r1\"/>"));
+    }
+
+    #[test]
+    fn writes_to_nested_path() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("reports/junit.xml");
+        write(&path, &summary()).unwrap();
+        assert!(std::fs::read_to_string(path).unwrap().starts_with("<?xml"));
+    }
+}
@@ -0,0 +1,305 @@
+//! Latency budget attribution: where a scenario's time goes.
+//!
+//! After each scenario iteration the executor reports a [`JourneyTiming`]:
+//! the iteration's wall time, the think time it slept and the time spent in
+//! each step (attempts and pages of one step summed). The tracker keeps, per
+//! scenario, the mean and p95 of the whole journey and of every step's
+//! contribution, and the final report shows each step's share of the mean
+//! journey with the dominant step marked. Whatever is left after steps and
+//! think time (rendezvous and concurrency waits, scheduling) is shown as
+//! `other`.
+
+use std::collections::BTreeMap;
+use std::sync::Mutex;
+
+use crate::percentiles::{PercentileStats, PercentileTracker};
+
+/// One scenario iteration's timings, as reported by the executor.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct JourneyTiming<'a> {
+    pub scenario: &'a str,
+    /// Wall time of the whole iteration.
+    pub total_ms: u64,
+    /// Think time slept between steps.
+    pub think_ms: u64,
+    /// Time per step, in execution order; a step may appear more than once
+    /// (retries, pages).
+    pub steps: Vec<(&'a str, u64)>,
+}
+
+struct StepBudget {
+    name: String,
+    sum_ms: u64,
+    latency: PercentileTracker,
+}
+
+#[derive(Default)]
+struct ScenarioBudget {
+    iterations: u64,
+    total_sum_ms: u64,
+    think_sum_ms: u64,
+    total: PercentileTracker,
+    /// In order of first execution.
+    steps: Vec<StepBudget>,
+}
+
+/// One row of the attribution report.
+#[derive(Debug, Clone, PartialEq)]
+pub struct BudgetLine {
+    pub label: String,
+    /// Mean contribution per iteration.
+    pub mean_ms: f64,
+    /// p95 over the iterations that ran it; `None` for derived rows.
+    pub p95_ms: Option<f64>,
+    /// Share of the scenario's mean duration (0.0–1.0).
+    pub share: f64,
+}
+
+/// Attribution for one scenario.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ScenarioBudgetReport {
+    pub scenario: String,
+    pub iterations: u64,
+    pub mean_ms: f64,
+    pub p95_ms: f64,
+    /// Steps in execution order, then `think time` and `other` when non-zero.
+    pub lines: Vec<BudgetLine>,
+    /// The step with the largest mean contribution.
+    pub dominant: Option<String>,
+}
+
+/// Per-scenario step timings, aggregated over the run.
+#[derive(Default)]
+pub struct LatencyBudgetTracker {
+    scenarios: Mutex<BTreeMap<String, ScenarioBudget>>,
+}
+
+impl LatencyBudgetTracker {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds one iteration.
+    pub fn record(&self, timing: &JourneyTiming) {
+        // A step's attempts and pages count once, summed, per iteration.
+        let mut per_step: Vec<(&str, u64)> = Vec::with_capacity(timing.steps.len());
+        for &(name, ms) in &timing.steps {
+            match per_step.iter_mut().find(|(n, _)| *n == name) {
+                Some((_, sum)) => *sum += ms,
+                None => per_step.push((name, ms)),
+            }
+        }
+
+        let mut scenarios = self.scenarios.lock().unwrap_or_else(|e| e.into_inner());
+        let budget = scenarios.entry(timing.scenario.to_string()).or_default();
+        budget.iterations += 1;
+        budget.total_sum_ms += timing.total_ms;
+        budget.think_sum_ms += timing.think_ms;
+        budget.total.record_ms(timing.total_ms);
+        for (name, ms) in per_step {
+            let index = match budget.steps.iter().position(|s| s.name == name) {
+                Some(index) => index,
+                None => {
+                    budget.steps.push(StepBudget {
+                        name: name.to_string(),
+                        sum_ms: 0,
+                        latency: PercentileTracker::new(),
+                    });
+                    budget.steps.len() - 1
+                }
+            };
+            let step = &mut budget.steps[index];
+            step.sum_ms += ms;
+            step.latency.record_ms(ms);
+        }
+    }
+
+    /// Attribution per scenario, ordered by name.
+    pub fn reports(&self) -> Vec<ScenarioBudgetReport> {
+        let scenarios = self.scenarios.lock().unwrap_or_else(|e| e.into_inner());
+        scenarios
+            .iter()
+            .filter(|(_, budget)| budget.iterations > 0)
+            .map(|(name, budget)| budget.report(name))
+            .collect()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.scenarios
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .is_empty()
+    }
+
+    pub fn reset(&self) {
+        self.scenarios
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .clear();
+    }
+}
+
+impl ScenarioBudget {
+    fn report(&self, name: &str) -> ScenarioBudgetReport {
+        let iterations = self.iterations as f64;
+        let mean_ms = self.total_sum_ms as f64 / iterations;
+        let share = |ms: f64| if mean_ms > 0.0 { ms / mean_ms } else { 0.0 };
+        let p95 = |stats: Option<PercentileStats>| stats.map_or(0.0, |s| s.p95 as f64 / 1000.0);
+
+        let mut lines: Vec<BudgetLine> = self
+            .steps
+            .iter()
+            .map(|step| {
+                let step_mean = step.sum_ms as f64 / iterations;
+                BudgetLine {
+                    label: step.name.clone(),
+                    mean_ms: step_mean,
+                    p95_ms: Some(p95(step.latency.stats())),
+                    share: share(step_mean),
+                }
+            })
+            .collect();
+        let dominant = lines
+            .iter()
+            .filter(|line| line.mean_ms > 0.0)
+            .max_by(|a, b| a.mean_ms.total_cmp(&b.mean_ms))
+            .map(|line| line.label.clone());
+
+        let think_mean = self.think_sum_ms as f64 / iterations;
+        let steps_mean: f64 = lines.iter().map(|line| line.mean_ms).sum();
+        let other_mean = (mean_ms - steps_mean - think_mean).max(0.0);
+        for (label, derived_mean) in [("think time", think_mean), ("other", other_mean)] {
+            if derived_mean >= 0.005 {
+                lines.push(BudgetLine {
+                    label: label.to_string(),
+                    mean_ms: derived_mean,
+                    p95_ms: None,
+                    share: share(derived_mean),
+                });
+            }
+        }
+
+        ScenarioBudgetReport {
+            scenario: name.to_string(),
+            iterations: self.iterations,
+            mean_ms,
+            p95_ms: p95(self.total.stats()),
+            lines,
+            dominant,
+        }
+    }
+}
+
+/// Formats the attribution of every scenario as a table.
+pub fn format_latency_budget_table(reports: &[ScenarioBudgetReport]) -> String {
+    let mut output = String::new();
+    output.push_str(&format!(
+        "\n{:<40} {:>10} {:>10} {:>10} {:>8}\n",
+        "Scenario / Step", "Count", "Mean (ms)", "P95 (ms)", "Share"
+    ));
+    output.push_str(&"-".repeat(82));
+    output.push('\n');
+
+    for report in reports {
+        output.push_str(&format!(
+            "{:<40} {:>10} {:>10.2} {:>10.2} {:>7.1}%\n",
+            report.scenario, report.iterations, report.mean_ms, report.p95_ms, 100.0
+        ));
+        for line in &report.lines {
+            let marker = if report.dominant.as_deref() == Some(line.label.as_str()) {
+                "  <- dominant"
+            } else {
+                ""
+            };
+            let p95 = line
+                .p95_ms
+                .map_or_else(|| "-".to_string(), |p95| format!("{:.2}", p95));
+            output.push_str(&format!(
+                "{:<40} {:>10} {:>10.2} {:>10} {:>7.1}%{}\n",
+                format!("  {}", line.label),
+                "",
+                line.mean_ms,
+                p95,
+                line.share * 100.0,
+                marker
+            ));
+        }
+    }
+    output
+}
+
+lazy_static::lazy_static! {
+    /// Process-wide step timings, reported at the end of the test.
+    pub static ref GLOBAL_LATENCY_BUDGET: LatencyBudgetTracker = LatencyBudgetTracker::new();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn journey<'a>(total_ms: u64, think_ms: u64, steps: &[(&'a str, u64)]) -> JourneyTiming<'a> {
+        JourneyTiming {
+            scenario: "Checkout",
+            total_ms,
+            think_ms,
+            steps: steps.to_vec(),
+        }
+    }
+
+    #[test]
+    fn attributes_time_to_steps() {
+        let tracker = LatencyBudgetTracker::new();
+        tracker.record(&journey(
+            1000,
+            200,
+            &[("Login", 100), ("Pay", 300), ("Pay", 300)],
+        ));
+        tracker.record(&journey(600, 200, &[("Login", 100), ("Pay", 200)]));
+
+        let reports = tracker.reports();
+        assert_eq!(reports.len(), 1);
+        let report = &reports[0];
+        assert_eq!(report.iterations, 2);
+        assert_eq!(report.mean_ms, 800.0);
+        assert_eq!(report.dominant.as_deref(), Some("Pay"));
+
+        let labels: Vec<&str> = report.lines.iter().map(|l| l.label.as_str()).collect();
+        assert_eq!(labels, vec!["Login", "Pay", "think time", "other"]);
+        // Pay: (600 + 200) / 2 iterations, retry attempts summed
+        assert_eq!(report.lines[1].mean_ms, 400.0);
+        assert_eq!(report.lines[1].share, 0.5);
+        assert!(report.lines[1].p95_ms.unwrap() >= 599.0);
+        // other = 800 - 50 - 400 - 200
+        assert_eq!(report.lines[3].mean_ms, 100.0);
+        assert_eq!(report.lines[3].p95_ms, None);
+    }
+
+    #[test]
+    fn steps_not_reached_lower_the_mean() {
+        let tracker = LatencyBudgetTracker::new();
+        tracker.record(&journey(100, 0, &[("Login", 100)]));
+        tracker.record(&journey(300, 0, &[("Login", 100), ("Pay", 200)]));
+
+        let report = &tracker.reports()[0];
+        assert_eq!(report.lines[1].label, "Pay");
+        assert_eq!(report.lines[1].mean_ms, 100.0);
+        assert_eq!(report.lines.len(), 2);
+    }
+
+    #[test]
+    fn formats_table_with_dominant_marker() {
+        let tracker = LatencyBudgetTracker::new();
+        tracker.record(&journey(500, 0, &[("Login", 100), ("Pay", 400)]));
+        let table = format_latency_budget_table(&tracker.reports());
+        assert!(table.contains("Checkout"));
+        let pay = table.lines().find(|l| l.contains("Pay")).unwrap();
+        assert!(pay.ends_with("<- dominant"), "{}", pay);
+        assert!(pay.contains("80.0%"), "{}", pay);
+        assert!(!table
+            .lines()
+            .any(|l| l.contains("Login") && l.contains("dominant")));
+
+        tracker.reset();
+        assert!(tracker.is_empty());
+    }
+}
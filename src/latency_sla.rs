@@ -0,0 +1,396 @@
+//! Windowed percentile SLAs (`type: percentile` assertions).
+//!
+//! ```yaml
+//! assertions:
+//!   - type: percentile
+//!     percentile: 95
+//!     window: "60s"
+//!     maxMs: 300
+//! ```
+//!
+//! A `responseTime` assertion judges one response; "p95 of this step over the
+//! last minute stays under 300ms" needs the step's recent latencies. Workers
+//! buffer step latencies in an [`SlaBatch`] and flush it with their other
+//! metrics into one histogram per second, and once per second the health
+//! updater calls [`evaluate`], which merges the buckets inside each SLA's
+//! window (the current second and the `window - 1` before it) and checks
+//! the percentile. A window with fewer than `minSamples` latencies is not
+//! judged.
+//!
+//! A breach is logged when it starts and counted in
+//! `latency_sla_breaches_total`; the final report lists every SLA, and a run
+//! with any breach fails with `threshold_failed`, like `scenarioThresholds`.
+
+use std::collections::VecDeque;
+use std::fmt;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
+
+use hdrhistogram::Histogram;
+use tokio::time::{Duration, Instant};
+
+/// Percentile of a step's latency that must stay under `max` over `window`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct LatencySla {
+    pub scenario: String,
+    pub step: String,
+    /// In `(0, 100]`, e.g. `95.0` or `99.9`.
+    pub percentile: f64,
+    /// Whole seconds, at least one.
+    pub window: Duration,
+    pub max_ms: u64,
+    /// Windows with fewer latencies are not judged.
+    pub min_samples: u64,
+}
+
+impl LatencySla {
+    /// `p95`, `p99.9`: the metric label for the percentile.
+    pub fn percentile_label(&self) -> String {
+        format!("p{}", self.percentile)
+    }
+}
+
+impl fmt::Display for LatencySla {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} of {}/{} over {}s < {}ms",
+            self.percentile_label(),
+            self.scenario,
+            self.step,
+            self.window.as_secs(),
+            self.max_ms
+        )
+    }
+}
+
+/// One second's evaluation of an SLA.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SlaSample {
+    pub sla: LatencySla,
+    /// The windowed percentile, `None` below `min_samples`.
+    pub value_ms: Option<u64>,
+    pub samples: u64,
+    pub breached: bool,
+    /// True on the first second of a breach.
+    pub breach_started: bool,
+}
+
+/// An SLA's outcome over the whole run.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SlaResult {
+    pub sla: LatencySla,
+    /// Seconds in which the window was judged.
+    pub evaluated_secs: u64,
+    /// Seconds in which the window breached the SLA.
+    pub breached_secs: u64,
+    /// Highest windowed percentile seen.
+    pub worst_ms: Option<u64>,
+    pub passed: bool,
+}
+
+impl fmt::Display for SlaResult {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let verdict = if self.passed { "PASS" } else { "FAIL" };
+        match self.worst_ms {
+            Some(worst) => write!(
+                f,
+                "{} {}: worst {}ms, breached {} of {} evaluated seconds",
+                verdict, self.sla, worst, self.breached_secs, self.evaluated_secs
+            ),
+            None => write!(f, "{} {}: never enough samples to judge", verdict, self.sla),
+        }
+    }
+}
+
+struct SlaState {
+    sla: LatencySla,
+    /// `(second since start, latencies in ms)`, oldest first.
+    buckets: VecDeque<(u64, Histogram<u64>)>,
+    breached: bool,
+    evaluated_secs: u64,
+    breached_secs: u64,
+    worst_ms: Option<u64>,
+}
+
+impl SlaState {
+    fn new(sla: LatencySla) -> Self {
+        Self {
+            sla,
+            buckets: VecDeque::new(),
+            breached: false,
+            evaluated_secs: 0,
+            breached_secs: 0,
+            worst_ms: None,
+        }
+    }
+
+    fn record(&mut self, second: u64, latency_ms: u64) {
+        // Workers flush at different times, so a second may arrive late.
+        let after = self.buckets.iter().rposition(|(s, _)| *s <= second);
+        let index = match after {
+            Some(i) if self.buckets[i].0 == second => i,
+            _ => {
+                let Ok(histogram) = Histogram::new(3) else {
+                    return;
+                };
+                let i = after.map_or(0, |i| i + 1);
+                self.buckets.insert(i, (second, histogram));
+                i
+            }
+        };
+        self.buckets[index].1.saturating_record(latency_ms);
+    }
+
+    fn evaluate(&mut self, second: u64) -> SlaSample {
+        let oldest = (second + 1).saturating_sub(self.sla.window.as_secs().max(1));
+        while self.buckets.front().is_some_and(|(s, _)| *s < oldest) {
+            self.buckets.pop_front();
+        }
+        let mut merged: Option<Histogram<u64>> = None;
+        for (_, bucket) in &self.buckets {
+            match merged.as_mut() {
+                Some(histogram) => {
+                    let _ = histogram.add(bucket);
+                }
+                None => merged = Some(bucket.clone()),
+            }
+        }
+        let samples = merged.as_ref().map_or(0, |h| h.len());
+        let value_ms = merged
+            .filter(|_| samples > 0 && samples >= self.sla.min_samples)
+            .map(|h| h.value_at_quantile(self.sla.percentile / 100.0));
+
+        let breached = value_ms.is_some_and(|value| value > self.sla.max_ms);
+        let breach_started = breached && !self.breached;
+        self.breached = breached;
+        if let Some(value) = value_ms {
+            self.evaluated_secs += 1;
+            self.worst_ms = Some(self.worst_ms.map_or(value, |worst| worst.max(value)));
+        }
+        if breached {
+            self.breached_secs += 1;
+        }
+        SlaSample {
+            sla: self.sla.clone(),
+            value_ms,
+            samples,
+            breached,
+            breach_started,
+        }
+    }
+
+    fn result(&self) -> SlaResult {
+        SlaResult {
+            sla: self.sla.clone(),
+            evaluated_secs: self.evaluated_secs,
+            breached_secs: self.breached_secs,
+            worst_ms: self.worst_ms,
+            passed: self.breached_secs == 0,
+        }
+    }
+}
+
+/// The SLAs of one test and their latency windows.
+pub struct SlaMonitor {
+    started: Instant,
+    states: Vec<SlaState>,
+}
+
+impl SlaMonitor {
+    pub fn new(slas: Vec<LatencySla>, started: Instant) -> Self {
+        Self {
+            started,
+            states: slas.into_iter().map(SlaState::new).collect(),
+        }
+    }
+
+    fn second(&self, now: Instant) -> u64 {
+        now.saturating_duration_since(self.started).as_secs()
+    }
+
+    /// Adds a step latency to the SLAs on that step.
+    pub fn record(&mut self, scenario: &str, step: &str, latency_ms: u64, now: Instant) {
+        let second = self.second(now);
+        for state in &mut self.states {
+            if state.sla.scenario == scenario && state.sla.step == step {
+                state.record(second, latency_ms);
+            }
+        }
+    }
+
+    /// Judges every SLA over its window ending at `now`.
+    pub fn evaluate(&mut self, now: Instant) -> Vec<SlaSample> {
+        let second = self.second(now);
+        self.states.iter_mut().map(|s| s.evaluate(second)).collect()
+    }
+
+    pub fn results(&self) -> Vec<SlaResult> {
+        self.states.iter().map(SlaState::result).collect()
+    }
+}
+
+static ACTIVE: AtomicBool = AtomicBool::new(false);
+
+lazy_static::lazy_static! {
+    static ref MONITOR: Mutex<Option<SlaMonitor>> = Mutex::new(None);
+}
+
+/// Starts judging `slas` for a new test, replacing any earlier test's. An
+/// empty list clears them.
+pub fn begin(slas: Vec<LatencySla>) {
+    let monitor = (!slas.is_empty()).then(|| SlaMonitor::new(slas, Instant::now()));
+    ACTIVE.store(monitor.is_some(), Ordering::Relaxed);
+    *MONITOR.lock().unwrap_or_else(|e| e.into_inner()) = monitor;
+}
+
+/// One worker's step latencies not yet added to the SLA windows, so the
+/// shared monitor is locked once per flush rather than once per step.
+#[derive(Default)]
+pub struct SlaBatch {
+    latencies: Vec<(String, String, u64, Instant)>,
+}
+
+impl SlaBatch {
+    /// Buffers a step latency; a no-op unless an SLA is active.
+    pub fn record(&mut self, scenario: &str, step: &str, latency_ms: u64) {
+        if ACTIVE.load(Ordering::Relaxed) {
+            self.latencies.push((
+                scenario.to_string(),
+                step.to_string(),
+                latency_ms,
+                Instant::now(),
+            ));
+        }
+    }
+
+    /// Adds the buffered latencies to the active SLAs.
+    pub fn flush(&mut self) {
+        if self.latencies.is_empty() {
+            return;
+        }
+        if let Some(monitor) = MONITOR.lock().unwrap_or_else(|e| e.into_inner()).as_mut() {
+            for (scenario, step, latency_ms, at) in &self.latencies {
+                monitor.record(scenario, step, *latency_ms, *at);
+            }
+        }
+        self.latencies.clear();
+    }
+}
+
+/// Judges every active SLA over its window; empty without SLAs.
+pub fn evaluate() -> Vec<SlaSample> {
+    MONITOR
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .as_mut()
+        .map(|monitor| monitor.evaluate(Instant::now()))
+        .unwrap_or_default()
+}
+
+/// Each active SLA's outcome so far.
+pub fn results() -> Vec<SlaResult> {
+    MONITOR
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .as_ref()
+        .map(SlaMonitor::results)
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sla() -> LatencySla {
+        LatencySla {
+            scenario: "checkout".to_string(),
+            step: "Pay".to_string(),
+            percentile: 95.0,
+            window: Duration::from_secs(10),
+            max_ms: 300,
+            min_samples: 20,
+        }
+    }
+
+    #[test]
+    fn breaches_when_the_window_percentile_exceeds_max() {
+        let t0 = Instant::now();
+        let at = |s: u64| t0 + Duration::from_secs(s);
+        let mut monitor = SlaMonitor::new(vec![sla()], t0);
+
+        for _ in 0..10 {
+            monitor.record("checkout", "Pay", 100, at(0));
+        }
+        monitor.record("checkout", "Other", 5000, at(0));
+        let sample = &monitor.evaluate(at(1))[0];
+        assert_eq!((sample.value_ms, sample.samples), (None, 10));
+        assert!(!sample.breached);
+
+        for _ in 0..10 {
+            monitor.record("checkout", "Pay", 900, at(1));
+        }
+        let sample = &monitor.evaluate(at(2))[0];
+        assert!(sample.value_ms.unwrap() > 300);
+        assert!(sample.breached && sample.breach_started);
+        assert!(!monitor.evaluate(at(3))[0].breach_started);
+
+        // The slow second ages out of the window.
+        for _ in 0..20 {
+            monitor.record("checkout", "Pay", 100, at(12));
+        }
+        let sample = &monitor.evaluate(at(12))[0];
+        assert_eq!(sample.samples, 20);
+        assert!(!sample.breached);
+
+        let result = &monitor.results()[0];
+        assert!(!result.passed);
+        assert_eq!(result.breached_secs, 2);
+        assert_eq!(result.evaluated_secs, 3);
+        assert!(result.worst_ms.unwrap() >= 900);
+    }
+
+    #[test]
+    fn window_covers_exactly_its_length_in_seconds() {
+        let t0 = Instant::now();
+        let at = |s: u64| t0 + Duration::from_secs(s);
+        let mut monitor = SlaMonitor::new(vec![sla()], t0);
+
+        // Flushed late, after second 3 has begun.
+        for s in [3, 2] {
+            for _ in 0..20 {
+                monitor.record("checkout", "Pay", 900, at(s));
+            }
+        }
+        // A 10s window ending in second 11 covers seconds 2 to 11.
+        assert_eq!(monitor.evaluate(at(11))[0].samples, 40);
+        assert_eq!(monitor.evaluate(at(12))[0].samples, 20);
+        assert_eq!(monitor.evaluate(at(13))[0].samples, 0);
+    }
+
+    #[test]
+    fn batches_reach_the_monitor_on_flush() {
+        let mut flagged = sla();
+        flagged.scenario = "batched".to_string();
+        begin(vec![flagged]);
+
+        let mut batch = SlaBatch::default();
+        for _ in 0..20 {
+            batch.record("batched", "Pay", 100);
+        }
+        assert_eq!(evaluate()[0].samples, 0);
+        batch.flush();
+        assert_eq!(evaluate()[0].samples, 20);
+        begin(Vec::new());
+    }
+
+    #[test]
+    fn displays_sla_and_result() {
+        let monitor = SlaMonitor::new(vec![sla()], Instant::now());
+        assert_eq!(sla().to_string(), "p95 of checkout/Pay over 10s < 300ms");
+        assert_eq!(
+            monitor.results()[0].to_string(),
+            "PASS p95 of checkout/Pay over 10s < 300ms: never enough samples to judge"
+        );
+    }
+}
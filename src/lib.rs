@@ -1,26 +1,102 @@
 #![recursion_limit = "256"]
 
+pub mod abort_threshold;
+pub mod addr;
+pub mod apdex;
+pub mod app_error;
+#[cfg(feature = "artifacts")]
+pub mod artifacts;
 pub mod assertions;
+pub mod chaos;
+pub mod ci_report;
 pub mod client;
+pub mod client_profiles;
+pub mod concurrency_limit;
 pub mod config;
+#[cfg(feature = "config-docs")]
 pub mod config_docs_generator;
+#[cfg(feature = "hot-reload")]
 pub mod config_hot_reload;
 pub mod config_merge;
 pub mod config_validation;
 pub mod config_version;
 pub mod connection_pool;
 pub mod data_source;
+pub mod debug_sample;
+pub mod dns_refresh;
+pub mod dns_round_robin;
+pub mod engine;
 pub mod errors;
 pub mod executor;
 pub mod extractor;
+pub mod failure_signatures;
+pub mod fd_limits;
+pub mod golden;
+pub mod hdr_log;
+pub mod header_layers;
+pub mod http_cache;
+#[cfg(feature = "importers")]
+pub mod importers;
+pub mod in_flight_limit;
+pub mod ip_family;
+pub mod junit;
+pub mod latency_budget;
+pub mod latency_sla;
+pub mod lint;
+pub mod littles_law;
+pub mod live_stream;
 pub mod load_models;
+pub mod logging;
 pub mod memory_guard;
+pub mod metric_batch;
 pub mod metrics;
+pub mod middleware;
 pub mod multi_scenario;
+pub mod network_profile;
+pub mod notify;
+pub mod openmetrics;
+pub mod patterns;
 pub mod percentiles;
+pub mod phases;
+pub mod plugin;
+pub mod precheck;
+pub mod protobuf;
+pub mod rate_budget;
+pub mod rate_expression;
+pub mod rate_limit;
+#[cfg(feature = "raw-engine")]
+pub mod raw_engine;
+pub mod redaction;
 pub mod registry;
+pub mod rendezvous;
+pub mod response_body;
+pub mod retry;
+pub mod rps_override;
+pub mod run_id;
+pub mod run_summary;
+pub mod runner;
+pub mod safety;
+pub mod saturation;
 pub mod scenario;
+pub mod scenario_slo;
+pub mod seed;
+pub mod service_discovery;
+pub mod shared_state;
+pub mod smoke;
+pub mod sni;
+pub mod start_barrier;
+pub mod substitution;
+#[cfg(feature = "supervisor")]
+pub mod supervisor;
+pub mod tcp_check;
+pub mod templates;
 pub mod throughput;
+pub mod tls;
+pub mod traffic_calendar;
 pub mod utils;
+pub mod warmup;
+#[cfg(feature = "web-ui")]
+pub mod web_ui;
 pub mod worker;
+pub mod xml_path;
 pub mod yaml_config;
@@ -0,0 +1,484 @@
+//! Lint pass over a parsed YAML plan.
+//!
+//! Validation rejects plans that cannot run; linting flags plans that run
+//! but probably not the way their author meant:
+//!
+//! - `thinkTime` of zero under a high request rate;
+//! - extracted variables that no `${...}` reference ever reads;
+//! - steps after a step whose assertions can never pass, since a scenario
+//!   stops at its first failed step;
+//! - duplicate step names in a scenario using the session cache, whose
+//!   default key is the step name;
+//! - scenario weights that look like percentages that don't add up, or that
+//!   give a scenario a vanishing share of the traffic.
+//!
+//! Warnings are printed by `rust_loadtest validate <file>` and logged when a
+//! plan is loaded.
+
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+use std::time::Duration;
+
+use tracing::warn;
+
+use crate::yaml_config::{
+    YamlAssertion, YamlConfig, YamlExtractor, YamlLoadModel, YamlStep, YamlThinkTime,
+};
+
+/// Peak RPS from which zero think time is flagged.
+const HIGH_RPS: f64 = 100.0;
+
+/// Scenarios getting less than this share of the total weight are flagged.
+const MIN_WEIGHT_SHARE: f64 = 0.001;
+
+/// A suspicious pattern found in a plan.
+#[derive(Debug, Clone, PartialEq)]
+pub struct LintWarning {
+    /// Where the pattern was found, e.g. `scenarios[0].steps[2]`.
+    pub path: String,
+    pub message: String,
+}
+
+impl fmt::Display for LintWarning {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}", self.path, self.message)
+    }
+}
+
+/// All warnings for `config`, in plan order.
+pub fn lint(config: &YamlConfig) -> Vec<LintWarning> {
+    let mut warnings = Vec::new();
+    let references = referenced_variables(config);
+    let peak_rps = peak_rps(&config.load);
+
+    for (s, scenario) in config.scenarios.iter().enumerate() {
+        let mut push = |path: String, message: String| warnings.push(LintWarning { path, message });
+        let mut names: HashMap<String, usize> = HashMap::new();
+        let mut failing_step: Option<String> = None;
+
+        for (i, step) in scenario.steps.iter().enumerate() {
+            let path = format!("scenarios[{}].steps[{}]", s, i);
+            let name = step
+                .name
+                .clone()
+                .unwrap_or_else(|| format!("Step {}", i + 1));
+
+            if let Some(failing) = &failing_step {
+                push(
+                    path.clone(),
+                    format!(
+                        "step '{}' is unreachable: step '{}' always fails its assertions",
+                        name, failing
+                    ),
+                );
+            }
+
+            if let (Some(rps), true) = (peak_rps, has_zero_think_time(step)) {
+                push(
+                    format!("{}.thinkTime", path),
+                    format!(
+                        "zero think time at up to {} RPS sends requests back to back; \
+                         remove it or model real user pauses",
+                        rps
+                    ),
+                );
+            }
+
+            for extractor in &step.extract {
+                let variable = extractor_name(extractor);
+                if !references.contains(variable) {
+                    push(
+                        format!("{}.extract", path),
+                        format!(
+                            "variable '{}' is extracted but never referenced as ${{{}}}",
+                            variable, variable
+                        ),
+                    );
+                }
+            }
+
+            if let Some(previous) = names.get(&name) {
+                let cached = step.cache.is_some() || scenario.steps[*previous].cache.is_some();
+                if cached {
+                    push(
+                        path.clone(),
+                        format!(
+                            "step name '{}' is also used by steps[{}]; both share one \
+                             session cache entry",
+                            name, previous
+                        ),
+                    );
+                }
+            } else {
+                names.insert(name.clone(), i);
+            }
+
+            if failing_step.is_none() {
+                if let Some(reason) = always_fails(step) {
+                    push(format!("{}.assertions", path), reason);
+                    failing_step = Some(name);
+                }
+            }
+        }
+    }
+
+    lint_weights(config, &mut warnings);
+    warnings
+}
+
+/// Logs each warning for a plan that is about to run.
+pub fn log_warnings(config: &YamlConfig) {
+    for warning in lint(config) {
+        warn!(path = %warning.path, "Scenario lint: {}", warning.message);
+    }
+}
+
+/// Highest target rate of the load model, if it is rate based.
+fn peak_rps(load: &YamlLoadModel) -> Option<f64> {
+    let peak = match load {
+        YamlLoadModel::Concurrent
+        | YamlLoadModel::Expression { .. }
+        | YamlLoadModel::Composite { .. } => return None,
+        YamlLoadModel::Rps { target } => *target,
+        YamlLoadModel::Ramp { min, max, .. } => min.max(*max),
+        YamlLoadModel::DailyTraffic { min, mid, max, .. } => min.max(*mid).max(*max),
+        YamlLoadModel::Exponential { max_rps, .. } => (*max_rps)?,
+    };
+    (peak >= HIGH_RPS).then_some(peak)
+}
+
+fn has_zero_think_time(step: &YamlStep) -> bool {
+    let zero = |duration: &crate::yaml_config::YamlDuration| {
+        duration.to_std_duration().ok() == Some(Duration::ZERO)
+    };
+    match &step.think_time {
+        Some(YamlThinkTime::Fixed(duration)) => zero(duration),
+        Some(YamlThinkTime::Random { max, .. }) => zero(max),
+        None => false,
+    }
+}
+
+fn extractor_name(extractor: &YamlExtractor) -> &str {
+    match extractor {
+        YamlExtractor::JsonPath { name, .. }
+        | YamlExtractor::XmlPath { name, .. }
+        | YamlExtractor::Regex { name, .. }
+        | YamlExtractor::Header { name, .. }
+        | YamlExtractor::Cookie { name, .. } => name,
+    }
+}
+
+/// Why the step's assertions can never all pass, if they can't.
+fn always_fails(step: &YamlStep) -> Option<String> {
+    let mut status_codes = HashSet::new();
+    for assertion in &step.assertions {
+        match assertion {
+            YamlAssertion::StatusCode { expected } => {
+                if !step.expected_status.is_empty() && !step.expected_status.contains(expected) {
+                    return Some(format!(
+                        "statusCode {} is not in expectedStatus {:?}, so the step always fails",
+                        expected, step.expected_status
+                    ));
+                }
+                status_codes.insert(*expected);
+            }
+            YamlAssertion::ResponseTime { max }
+                if max.to_std_duration().ok() == Some(Duration::ZERO) =>
+            {
+                return Some(
+                    "responseTime max of 0 can never pass, so the step always fails".to_string(),
+                );
+            }
+            YamlAssertion::BodySizeBytes {
+                min: Some(min),
+                max: Some(max),
+            } if min > max => {
+                return Some(format!(
+                    "bodySizeBytes min {} is above max {}, so the step always fails",
+                    min, max
+                ));
+            }
+            YamlAssertion::NotModified if !step.conditional => {
+                return Some(
+                    "notModified without conditional: true never gets a 304, so the step \
+                     always fails"
+                        .to_string(),
+                );
+            }
+            _ => {}
+        }
+    }
+    if status_codes.len() > 1 {
+        let mut codes: Vec<u16> = status_codes.into_iter().collect();
+        codes.sort_unstable();
+        return Some(format!(
+            "statusCode assertions expect {:?} at once, so the step always fails",
+            codes
+        ));
+    }
+    None
+}
+
+/// Every variable name read through `${name}` or `$name` anywhere in the plan.
+fn referenced_variables(config: &YamlConfig) -> HashSet<String> {
+    let mut names = HashSet::new();
+    // Every string field may hold a template, so walk them all.
+    if let Ok(value) = serde_json::to_value(config) {
+        collect_references(&value, &mut names);
+    }
+    names
+}
+
+fn collect_references(value: &serde_json::Value, names: &mut HashSet<String>) {
+    match value {
+        serde_json::Value::String(text) => {
+            let mut rest = text.as_str();
+            while let Some(at) = rest.find('$') {
+                rest = &rest[at + 1..];
+                let name = match rest.strip_prefix('{') {
+                    Some(braced) => match braced.find('}') {
+                        Some(end) => &braced[..end],
+                        None => break,
+                    },
+                    None => {
+                        let len = rest
+                            .bytes()
+                            .take_while(|b| b.is_ascii_alphanumeric() || *b == b'_')
+                            .count();
+                        &rest[..len]
+                    }
+                };
+                if !name.is_empty() {
+                    names.insert(name.to_string());
+                }
+            }
+        }
+        serde_json::Value::Array(items) => items
+            .iter()
+            .for_each(|item| collect_references(item, names)),
+        serde_json::Value::Object(fields) => fields
+            .values()
+            .for_each(|field| collect_references(field, names)),
+        _ => {}
+    }
+}
+
+fn lint_weights(config: &YamlConfig, warnings: &mut Vec<LintWarning>) {
+    if config.scenarios.len() < 2 {
+        return;
+    }
+    let total: f64 = config.scenarios.iter().map(|s| s.weight).sum();
+    if total <= 0.0 {
+        return;
+    }
+
+    // Weights written as percentages (or fractions) that miss the mark.
+    for (whole, unit) in [(100.0, "100"), (1.0, "1")] {
+        let off = (total - whole).abs();
+        if off > 1e-9 && off <= whole * 0.1 + 1e-9 {
+            warnings.push(LintWarning {
+                path: "scenarios".to_string(),
+                message: format!(
+                    "weights sum to {} instead of {}; traffic is split by relative weight, \
+                     so each scenario's share is its weight / {}",
+                    total, unit, total
+                ),
+            });
+            break;
+        }
+    }
+
+    for (s, scenario) in config.scenarios.iter().enumerate() {
+        let share = scenario.weight / total;
+        if share < MIN_WEIGHT_SHARE {
+            warnings.push(LintWarning {
+                path: format!("scenarios[{}].weight", s),
+                message: format!(
+                    "scenario '{}' gets {:.4}% of the traffic and may never run",
+                    scenario.name,
+                    share * 100.0
+                ),
+            });
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn plan(load: &str, scenarios: &str) -> YamlConfig {
+        let yaml = format!(
+            "version: \"1.0\"\nconfig:\n  baseUrl: \"https://example.com\"\n  \
+             duration: \"1m\"\nload:\n{}\nscenarios:\n{}",
+            load, scenarios
+        );
+        YamlConfig::from_str(&yaml).unwrap()
+    }
+
+    fn paths(config: &YamlConfig) -> Vec<String> {
+        lint(config).into_iter().map(|w| w.path).collect()
+    }
+
+    #[test]
+    fn clean_plan_has_no_warnings() {
+        let config = plan(
+            "  model: rps\n  target: 500",
+            r#"
+  - name: login
+    steps:
+      - name: token
+        request: { method: POST, path: /login }
+        extract:
+          - { type: jsonPath, name: token, jsonPath: "$.token" }
+        thinkTime: 1s
+      - request:
+          method: GET
+          path: /me
+          headers: { Authorization: "Bearer ${token}" }
+"#,
+        );
+        assert!(lint(&config).is_empty(), "{:?}", lint(&config));
+    }
+
+    #[test]
+    fn flags_zero_think_time_only_at_high_rps() {
+        let steps = r#"
+  - name: browse
+    steps:
+      - request: { method: GET, path: / }
+        thinkTime: 0s
+"#;
+        assert_eq!(
+            paths(&plan("  model: rps\n  target: 200", steps)),
+            vec!["scenarios[0].steps[0].thinkTime"]
+        );
+        assert!(lint(&plan("  model: rps\n  target: 10", steps)).is_empty());
+        assert!(lint(&plan("  model: concurrent", steps)).is_empty());
+    }
+
+    #[test]
+    fn flags_unreferenced_extractors() {
+        let config = plan(
+            "  model: concurrent",
+            r#"
+  - name: a
+    steps:
+      - request: { method: GET, path: /items }
+        extract:
+          - { type: jsonPath, name: used, jsonPath: "$.id" }
+          - { type: header, name: unused, header: X-Trace }
+  - name: b
+    steps:
+      - request: { method: GET, path: "/items/$used" }
+"#,
+        );
+        let warnings = lint(&config);
+        assert_eq!(warnings.len(), 1);
+        assert!(warnings[0].message.contains("'unused'"));
+    }
+
+    #[test]
+    fn flags_steps_after_an_always_failing_step() {
+        let config = plan(
+            "  model: concurrent",
+            r#"
+  - name: a
+    steps:
+      - name: create
+        request: { method: POST, path: /items }
+        expectedStatus: [201]
+        assertions:
+          - { type: statusCode, expected: 200 }
+      - name: read
+        request: { method: GET, path: /items }
+      - name: delete
+        request: { method: DELETE, path: /items }
+"#,
+        );
+        assert_eq!(
+            paths(&config),
+            vec![
+                "scenarios[0].steps[0].assertions",
+                "scenarios[0].steps[1]",
+                "scenarios[0].steps[2]"
+            ]
+        );
+    }
+
+    #[test]
+    fn detects_impossible_assertions() {
+        let step = |yaml: &str| -> YamlStep { serde_yaml::from_str(yaml).unwrap() };
+        assert!(always_fails(&step(
+            "request: {method: GET, path: /}\nassertions:\n  - {type: responseTime, max: 0}"
+        ))
+        .is_some());
+        assert!(always_fails(&step(
+            "request: {method: GET, path: /}\nassertions:\n  - {type: bodySizeBytes, min: 10, max: 5}"
+        ))
+        .is_some());
+        assert!(always_fails(&step(
+            "request: {method: GET, path: /}\nassertions:\n  - {type: notModified}"
+        ))
+        .is_some());
+        assert!(always_fails(&step(
+            "request: {method: GET, path: /}\nconditional: true\nassertions:\n  - {type: notModified}"
+        ))
+        .is_none());
+        assert!(always_fails(&step(
+            "request: {method: GET, path: /}\nassertions:\n  - {type: statusCode, expected: 200}\n  - {type: statusCode, expected: 204}"
+        ))
+        .is_some());
+    }
+
+    #[test]
+    fn flags_duplicate_names_with_session_cache() {
+        let scenarios = |cache: &str| {
+            format!(
+                r#"
+  - name: a
+    steps:
+      - name: fetch
+        request: {{ method: GET, path: /a }}
+{}
+      - name: fetch
+        request: {{ method: GET, path: /b }}
+"#,
+                cache
+            )
+        };
+        let cached = plan(
+            "  model: concurrent",
+            &scenarios("        cache: { ttl: 1m }"),
+        );
+        assert_eq!(paths(&cached), vec!["scenarios[0].steps[1]"]);
+        assert!(lint(&plan("  model: concurrent", &scenarios(""))).is_empty());
+    }
+
+    #[test]
+    fn flags_odd_weights() {
+        let weights = |a: f64, b: f64| {
+            plan(
+                "  model: concurrent",
+                &format!(
+                    r#"
+  - name: a
+    weight: {}
+    steps:
+      - request: {{ method: GET, path: /a }}
+  - name: b
+    weight: {}
+    steps:
+      - request: {{ method: GET, path: /b }}
+"#,
+                    a, b
+                ),
+            )
+        };
+        assert_eq!(paths(&weights(60.0, 35.0)), vec!["scenarios"]);
+        assert_eq!(paths(&weights(0.7, 0.2)), vec!["scenarios"]);
+        assert!(lint(&weights(60.0, 40.0)).is_empty());
+        assert!(lint(&weights(3.0, 1.0)).is_empty());
+        assert_eq!(paths(&weights(10000.0, 1.0)), vec!["scenarios[1].weight"]);
+    }
+}
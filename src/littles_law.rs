@@ -0,0 +1,268 @@
+//! Time-weighted concurrency and a Little's Law sanity check.
+//!
+//! A background sampler reads the in-flight request count
+//! ([`crate::connection_pool::in_flight`]) every 100 ms and integrates it
+//! over time, giving the average concurrency `L` since the test started
+//! (exported as `concurrency_average`). Little's Law says `L = λW`: the
+//! throughput `λ` times the mean latency `W`, both taken from
+//! `request_duration_seconds` over the same window. The final report compares
+//! the two; a large gap means the latencies and the concurrency the generator
+//! saw don't describe the same requests:
+//!
+//! - measured `L` above `λW`: requests sit in flight longer than their
+//!   recorded latency, usually generator-side queuing (connection pool waits,
+//!   an overloaded runtime delaying tasks);
+//! - measured `L` below `λW`: recorded latency includes time the request
+//!   wasn't in flight, or requests bypass the in-flight tracking.
+
+use std::fmt;
+use std::sync::Mutex;
+use std::time::Duration;
+
+use tokio::time::Instant;
+
+use crate::connection_pool::in_flight;
+use crate::metrics::{request_duration_totals, CONCURRENCY_AVERAGE};
+
+/// Relative gap between measured and implied concurrency still considered
+/// consistent.
+pub const TOLERANCE: f64 = 0.25;
+
+/// Below this average concurrency the comparison is dominated by sampling
+/// noise and always passes.
+const MIN_CONCURRENCY: f64 = 0.5;
+
+/// How often the in-flight count is sampled.
+pub const SAMPLE_INTERVAL: Duration = Duration::from_millis(100);
+
+/// Integral of the in-flight count over one window.
+#[derive(Debug, Clone)]
+struct Window {
+    start: Instant,
+    last: Instant,
+    last_in_flight: f64,
+    /// Request-seconds in flight.
+    area: f64,
+    /// `request_duration_seconds` (sum, count) when the window started.
+    base_duration: (f64, u64),
+}
+
+impl Window {
+    fn new(now: Instant, base_duration: (f64, u64)) -> Self {
+        Self {
+            start: now,
+            last: now,
+            last_in_flight: 0.0,
+            area: 0.0,
+            base_duration,
+        }
+    }
+
+    fn sample(&mut self, in_flight: f64, now: Instant) {
+        let dt = now.saturating_duration_since(self.last).as_secs_f64();
+        self.area += self.last_in_flight * dt;
+        self.last = now;
+        self.last_in_flight = in_flight;
+    }
+
+    fn elapsed_secs(&self) -> f64 {
+        self.last
+            .saturating_duration_since(self.start)
+            .as_secs_f64()
+    }
+
+    fn average(&self) -> Option<f64> {
+        let elapsed = self.elapsed_secs();
+        (elapsed > 0.0).then(|| self.area / elapsed)
+    }
+}
+
+/// Running average concurrency since the last [`reset`](Self::reset).
+#[derive(Default)]
+pub struct ConcurrencyTracker {
+    window: Mutex<Option<Window>>,
+}
+
+impl ConcurrencyTracker {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Starts a new window at `now`.
+    pub fn reset(&self, now: Instant) {
+        *self.window.lock().unwrap_or_else(|e| e.into_inner()) =
+            Some(Window::new(now, request_duration_totals()));
+    }
+
+    /// Records the in-flight count at `now` and returns the running average.
+    pub fn sample(&self, in_flight: i64, now: Instant) -> Option<f64> {
+        let mut window = self.window.lock().unwrap_or_else(|e| e.into_inner());
+        let window = window.get_or_insert_with(|| Window::new(now, request_duration_totals()));
+        window.sample(in_flight.max(0) as f64, now);
+        window.average()
+    }
+
+    /// Compares the window's average concurrency with λW; `None` before any
+    /// time has passed or any request completed.
+    pub fn check(&self) -> Option<LittlesLawCheck> {
+        let window = self.window.lock().unwrap_or_else(|e| e.into_inner());
+        let window = window.as_ref()?;
+        let (sum, count) = request_duration_totals();
+        LittlesLawCheck::new(
+            window.average()?,
+            window.elapsed_secs(),
+            count.saturating_sub(window.base_duration.1),
+            (sum - window.base_duration.0).max(0.0),
+        )
+    }
+}
+
+/// Measured versus implied concurrency over one window.
+#[derive(Debug, Clone, PartialEq)]
+pub struct LittlesLawCheck {
+    /// Time-weighted average of requests in flight (L).
+    pub measured_concurrency: f64,
+    /// Completed requests per second (λ).
+    pub throughput_rps: f64,
+    /// Mean recorded latency in seconds (W).
+    pub mean_latency_secs: f64,
+    /// λW.
+    pub implied_concurrency: f64,
+}
+
+impl LittlesLawCheck {
+    /// `completed` requests with `latency_sum_secs` total latency over
+    /// `elapsed_secs`.
+    pub fn new(
+        measured_concurrency: f64,
+        elapsed_secs: f64,
+        completed: u64,
+        latency_sum_secs: f64,
+    ) -> Option<Self> {
+        if elapsed_secs <= 0.0 || completed == 0 {
+            return None;
+        }
+        let throughput_rps = completed as f64 / elapsed_secs;
+        let mean_latency_secs = latency_sum_secs / completed as f64;
+        Some(Self {
+            measured_concurrency,
+            throughput_rps,
+            mean_latency_secs,
+            implied_concurrency: throughput_rps * mean_latency_secs,
+        })
+    }
+
+    /// `(measured - implied) / implied`; positive when more requests were in
+    /// flight than the latencies account for.
+    pub fn deviation(&self) -> f64 {
+        if self.implied_concurrency > 0.0 {
+            (self.measured_concurrency - self.implied_concurrency) / self.implied_concurrency
+        } else if self.measured_concurrency > 0.0 {
+            f64::INFINITY
+        } else {
+            0.0
+        }
+    }
+
+    pub fn is_consistent(&self) -> bool {
+        self.measured_concurrency.max(self.implied_concurrency) < MIN_CONCURRENCY
+            || self.deviation().abs() <= TOLERANCE
+    }
+
+    /// What a discrepancy most likely means; `None` when consistent.
+    pub fn hint(&self) -> Option<&'static str> {
+        if self.is_consistent() {
+            None
+        } else if self.deviation() > 0.0 {
+            Some(
+                "requests stay in flight longer than their recorded latency — \
+                 likely generator-side queuing (connection pool waits, overloaded runtime)",
+            )
+        } else {
+            Some(
+                "recorded latency covers time requests were not in flight, \
+                 or requests bypass in-flight tracking",
+            )
+        }
+    }
+}
+
+impl fmt::Display for LittlesLawCheck {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "measured L = {:.2}, λ = {:.2} req/s, W = {:.2} ms, λW = {:.2} ({:+.1}%)",
+            self.measured_concurrency,
+            self.throughput_rps,
+            self.mean_latency_secs * 1000.0,
+            self.implied_concurrency,
+            self.deviation() * 100.0
+        )
+    }
+}
+
+lazy_static::lazy_static! {
+    /// Process-wide concurrency window, reset when a test starts.
+    pub static ref GLOBAL_CONCURRENCY: ConcurrencyTracker = ConcurrencyTracker::new();
+}
+
+/// Samples the in-flight count every [`SAMPLE_INTERVAL`] into
+/// [`GLOBAL_CONCURRENCY`] and the `concurrency_average` gauge.
+pub fn spawn_concurrency_sampler() -> tokio::task::JoinHandle<()> {
+    tokio::spawn(async move {
+        let mut ticker = tokio::time::interval(SAMPLE_INTERVAL);
+        loop {
+            ticker.tick().await;
+            if let Some(average) = GLOBAL_CONCURRENCY.sample(in_flight(), Instant::now()) {
+                CONCURRENCY_AVERAGE.set(average);
+            }
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn integrates_in_flight_over_time() {
+        let start = Instant::now();
+        let mut window = Window::new(start, (0.0, 0));
+        assert_eq!(window.average(), None);
+        window.sample(4.0, start + Duration::from_secs(1));
+        // 4 in flight for 1 s, then 2 for 3 s
+        window.sample(2.0, start + Duration::from_secs(2));
+        window.sample(0.0, start + Duration::from_secs(5));
+        assert_eq!(window.area, 4.0 + 2.0 * 3.0);
+        assert_eq!(window.average(), Some(2.0));
+    }
+
+    #[test]
+    fn consistent_when_l_matches_lambda_w() {
+        // 200 req/s at 50 ms mean ≈ 10 in flight
+        let check = LittlesLawCheck::new(10.5, 10.0, 2000, 100.0).unwrap();
+        assert_eq!(check.throughput_rps, 200.0);
+        assert_eq!(check.implied_concurrency, 10.0);
+        assert!(check.is_consistent());
+        assert_eq!(check.hint(), None);
+        assert!(check.to_string().contains("+5.0%"), "{}", check);
+    }
+
+    #[test]
+    fn flags_generator_side_queuing() {
+        let check = LittlesLawCheck::new(20.0, 10.0, 2000, 100.0).unwrap();
+        assert_eq!(check.deviation(), 1.0);
+        assert!(!check.is_consistent());
+        assert!(check.hint().unwrap().contains("queuing"));
+
+        let low = LittlesLawCheck::new(4.0, 10.0, 2000, 100.0).unwrap();
+        assert!(low.hint().unwrap().contains("not in flight"));
+    }
+
+    #[test]
+    fn ignores_idle_runs() {
+        assert_eq!(LittlesLawCheck::new(0.0, 10.0, 0, 0.0), None);
+        let idle = LittlesLawCheck::new(0.2, 10.0, 10, 0.1).unwrap();
+        assert!(idle.is_consistent());
+    }
+}
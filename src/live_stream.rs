@@ -0,0 +1,238 @@
+//! Live test stats over WebSocket (`GET /live` on the control API).
+//!
+//! Once a second the node publishes a `tick` frame with its aggregate stats
+//! and the failures seen since the previous tick:
+//!
+//! ```json
+//! {"type":"tick","timestamp_ms":1700000000000,"run_id":"run-42","node_id":"node-a",
+//!  "node_state":"running","rps":198.0,"intended_rps":200.0,"error_rate_pct":1.5,
+//!  "mean_latency_ms":42.7,"workers":50,"failures":[{"timestamp_ms":1700000000000,
+//!  "scenario":"Checkout","step":"Pay","status":500,"error":"HTTP 500"}],"failures_dropped":0}
+//! ```
+//!
+//! Every connected client receives every frame; a client that falls behind
+//! skips frames instead of slowing the test down. At most
+//! [`MAX_FAILURES_PER_TICK`] failures are sent per tick and the remainder is
+//! counted in `failures_dropped`. Failures are only collected while a client
+//! is connected.
+
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use futures_util::{SinkExt, StreamExt};
+use hyper::header::{HeaderValue, CONNECTION, SEC_WEBSOCKET_ACCEPT, SEC_WEBSOCKET_KEY, UPGRADE};
+use hyper::{Body, Request, Response, StatusCode};
+use serde::Serialize;
+use tokio::sync::broadcast;
+use tokio_tungstenite::tungstenite::handshake::derive_accept_key;
+use tokio_tungstenite::tungstenite::protocol::Role;
+use tokio_tungstenite::tungstenite::Message;
+use tokio_tungstenite::WebSocketStream;
+use tracing::debug;
+
+use crate::redaction::GLOBAL_REDACTOR;
+
+/// Most failures included in one tick.
+pub const MAX_FAILURES_PER_TICK: usize = 20;
+
+/// Frames buffered per client before it starts skipping.
+const CLIENT_BUFFER: usize = 16;
+
+/// One failed request or step.
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct LiveFailure {
+    pub timestamp_ms: u64,
+    pub scenario: String,
+    pub step: String,
+    pub status: Option<u16>,
+    pub error: String,
+}
+
+/// Aggregate stats for one second of the test.
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct Tick {
+    pub run_id: String,
+    pub node_id: String,
+    pub node_state: String,
+    pub rps: f64,
+    pub intended_rps: Option<f64>,
+    pub error_rate_pct: f64,
+    /// Mean request latency over the last second; `None` with no responses.
+    pub mean_latency_ms: Option<f64>,
+    pub workers: u32,
+}
+
+#[derive(Serialize)]
+struct TickFrame<'a> {
+    #[serde(rename = "type")]
+    kind: &'static str,
+    timestamp_ms: u64,
+    #[serde(flatten)]
+    tick: &'a Tick,
+    failures: Vec<LiveFailure>,
+    failures_dropped: u64,
+}
+
+lazy_static::lazy_static! {
+    static ref FRAMES: broadcast::Sender<String> = broadcast::channel(CLIENT_BUFFER).0;
+    static ref PENDING: Mutex<Vec<LiveFailure>> = Mutex::new(Vec::new());
+}
+
+/// Failures not sent since the last tick because the tick was full.
+static DROPPED: AtomicU64 = AtomicU64::new(0);
+
+fn now_ms() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_millis() as u64)
+        .unwrap_or_default()
+}
+
+/// True while at least one client is connected.
+pub fn has_subscribers() -> bool {
+    FRAMES.receiver_count() > 0
+}
+
+/// Queues a failure for the next tick, with response data in `error`
+/// masked by [`GLOBAL_REDACTOR`]. A no-op without subscribers.
+pub fn record_failure(scenario: &str, step: &str, status: Option<u16>, error: &str) {
+    if !has_subscribers() {
+        return;
+    }
+    let mut pending = PENDING.lock().unwrap_or_else(|e| e.into_inner());
+    if pending.len() >= MAX_FAILURES_PER_TICK {
+        DROPPED.fetch_add(1, Ordering::Relaxed);
+        return;
+    }
+    pending.push(LiveFailure {
+        timestamp_ms: now_ms(),
+        scenario: scenario.to_string(),
+        step: step.to_string(),
+        status,
+        error: GLOBAL_REDACTOR.text(error).into_owned(),
+    });
+}
+
+/// Builds the JSON frame for `tick`, taking the queued failures.
+pub fn tick_frame(tick: &Tick) -> String {
+    let failures = std::mem::take(&mut *PENDING.lock().unwrap_or_else(|e| e.into_inner()));
+    let frame = TickFrame {
+        kind: "tick",
+        timestamp_ms: now_ms(),
+        tick,
+        failures,
+        failures_dropped: DROPPED.swap(0, Ordering::Relaxed),
+    };
+    serde_json::to_string(&frame).unwrap_or_else(|_| "{}".to_string())
+}
+
+/// Sends `tick` to every connected client.
+pub fn publish(tick: &Tick) {
+    if has_subscribers() {
+        let _ = FRAMES.send(tick_frame(tick));
+    }
+}
+
+/// Answers a WebSocket upgrade request and streams ticks to the client until
+/// it disconnects. Requests without a WebSocket handshake get `400`.
+pub fn handle_upgrade(mut req: Request<Body>) -> Response<Body> {
+    let is_upgrade = req
+        .headers()
+        .get(UPGRADE)
+        .and_then(|v| v.to_str().ok())
+        .is_some_and(|v| v.eq_ignore_ascii_case("websocket"));
+    let key = match req.headers().get(SEC_WEBSOCKET_KEY) {
+        Some(key) if is_upgrade => derive_accept_key(key.as_bytes()),
+        _ => {
+            return Response::builder()
+                .status(StatusCode::BAD_REQUEST)
+                .body(Body::from("expected a WebSocket upgrade"))
+                .unwrap()
+        }
+    };
+
+    let mut frames = FRAMES.subscribe();
+    tokio::spawn(async move {
+        let upgraded = match hyper::upgrade::on(&mut req).await {
+            Ok(upgraded) => upgraded,
+            Err(e) => {
+                debug!(error = %e, "Live stream upgrade failed");
+                return;
+            }
+        };
+        let mut socket = WebSocketStream::from_raw_socket(upgraded, Role::Server, None).await;
+        debug!("Live stream client connected");
+        loop {
+            tokio::select! {
+                frame = frames.recv() => match frame {
+                    Ok(text) => {
+                        if socket.send(Message::Text(text)).await.is_err() {
+                            break;
+                        }
+                    }
+                    Err(broadcast::error::RecvError::Lagged(skipped)) => {
+                        debug!(skipped, "Live stream client is lagging; frames skipped");
+                    }
+                    Err(broadcast::error::RecvError::Closed) => break,
+                },
+                incoming = socket.next() => match incoming {
+                    Some(Ok(Message::Close(_))) | Some(Err(_)) | None => break,
+                    // Pings are answered by the protocol layer; other client
+                    // messages are ignored.
+                    Some(Ok(_)) => {}
+                },
+            }
+        }
+        debug!("Live stream client disconnected");
+    });
+
+    Response::builder()
+        .status(StatusCode::SWITCHING_PROTOCOLS)
+        .header(CONNECTION, HeaderValue::from_static("Upgrade"))
+        .header(UPGRADE, HeaderValue::from_static("websocket"))
+        .header(SEC_WEBSOCKET_ACCEPT, key)
+        .body(Body::empty())
+        .unwrap()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tick() -> Tick {
+        Tick {
+            run_id: "run-1".to_string(),
+            node_id: "node-a".to_string(),
+            node_state: "running".to_string(),
+            rps: 10.0,
+            intended_rps: Some(12.0),
+            error_rate_pct: 0.0,
+            mean_latency_ms: Some(25.0),
+            workers: 4,
+        }
+    }
+
+    #[test]
+    fn failures_need_a_subscriber() {
+        record_failure("Checkout", "Pay", Some(500), "HTTP 500");
+        let frame: serde_json::Value = serde_json::from_str(&tick_frame(&tick())).unwrap();
+        assert_eq!(frame["failures"], serde_json::json!([]));
+
+        let _client = FRAMES.subscribe();
+        record_failure("Checkout", "Pay", Some(500), "HTTP 500");
+        let frame: serde_json::Value = serde_json::from_str(&tick_frame(&tick())).unwrap();
+        assert_eq!(frame["type"], "tick");
+        assert_eq!(frame["run_id"], "run-1");
+        assert_eq!(frame["intended_rps"], 12.0);
+        assert_eq!(frame["failures"][0]["step"], "Pay");
+        assert_eq!(frame["failures"][0]["status"], 500);
+        assert_eq!(frame["failures_dropped"], 0);
+    }
+
+    #[test]
+    fn rejects_plain_requests() {
+        let req = Request::builder().uri("/live").body(Body::empty()).unwrap();
+        assert_eq!(handle_upgrade(req).status(), StatusCode::BAD_REQUEST);
+    }
+}
@@ -1,5 +1,11 @@
+use serde::{Deserialize, Serialize};
+use std::time::{SystemTime, UNIX_EPOCH};
 use tokio::time::Duration;
 
+use crate::abort_threshold::AbortThreshold;
+use crate::rate_expression::RateExpression;
+use crate::traffic_calendar::TrafficCalendar;
+
 /// Represents different load generation models for the load test.
 #[derive(Debug, Clone)]
 pub enum LoadModel {
@@ -16,10 +22,14 @@ pub enum LoadModel {
     /// - First 1/3: Ramp from min_rps to max_rps
     /// - Middle 1/3: Sustain at max_rps
     /// - Last 1/3: Ramp down from max_rps to min_rps
+    ///
+    /// With `clock_start` set (`alignToClock`), the ramp repeats every
+    /// ramp_duration, anchored to that UTC time of day.
     RampRps {
         min_rps: f64,
         max_rps: f64,
         ramp_duration: Duration,
+        clock_start: Option<Duration>,
     },
 
     /// Complex daily traffic pattern simulation.
@@ -30,6 +40,11 @@ pub enum LoadModel {
     /// 4. Mid-day sustain (mid)
     /// 5. Evening decline (mid to min)
     /// 6. Night sustain (min)
+    ///
+    /// With `clock_start` set (`alignToClock`), cycles start at that UTC time
+    /// of day instead of at test start, so phases follow the wall clock. A
+    /// `calendar` scales the rate by the UTC day it falls on (weekends,
+    /// holidays).
     DailyTraffic {
         min_rps: f64,
         mid_rps: f64,
@@ -40,7 +55,71 @@ pub enum LoadModel {
         mid_decline_ratio: f64,
         mid_sustain_ratio: f64,
         evening_decline_ratio: f64,
+        clock_start: Option<Duration>,
+        calendar: Option<TrafficCalendar>,
     },
+
+    /// A base model with overlays applied in order, each adding its rate to
+    /// the result so far or multiplying it (e.g. a DailyTraffic baseline
+    /// plus a constant floor, times a slow growth ramp). Components are
+    /// evaluated at the same point in time and may be composites
+    /// themselves; the result is never negative.
+    Composite {
+        base: Box<LoadModel>,
+        overlays: Vec<LoadOverlay>,
+    },
+
+    /// Rate given by a math expression of elapsed time `t`
+    /// (see [`crate::rate_expression`]); negative or non-finite values
+    /// mean 0.
+    Expression { expression: RateExpression },
+
+    /// Rate doubling every `doubling_period` from `start_rps`, for finding
+    /// the knee of high-capacity systems faster than a linear ramp. Growth
+    /// stops at `max_rps` if set; the test stops once `abort` trips (see
+    /// [`crate::abort_threshold`]).
+    Exponential {
+        start_rps: f64,
+        doubling_period: Duration,
+        max_rps: Option<f64>,
+        abort: AbortThreshold,
+    },
+}
+
+/// How a Composite overlay combines with the rate so far.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum OverlayOp {
+    /// Adds the overlay's rate.
+    Add,
+    /// Multiplies by the overlay's rate, read as a factor.
+    Multiply,
+}
+
+/// One overlay of a Composite model.
+#[derive(Debug, Clone)]
+pub struct LoadOverlay {
+    pub op: OverlayOp,
+    pub model: LoadModel,
+}
+
+/// Parses a UTC time of day (`HH:MM` or `HH:MM:SS`) as the offset from
+/// midnight.
+pub fn parse_time_of_day(value: &str) -> Result<Duration, String> {
+    let value = value.trim();
+    let time = chrono::NaiveTime::parse_from_str(value, "%H:%M:%S")
+        .or_else(|_| chrono::NaiveTime::parse_from_str(value, "%H:%M"))
+        .map_err(|_| format!("expected a UTC time of day as HH:MM[:SS] (got '{}')", value))?;
+    Ok(Duration::from_secs(u64::from(
+        chrono::Timelike::num_seconds_from_midnight(&time),
+    )))
+}
+
+/// Seconds since the Unix epoch, as used for clock-aligned models.
+pub fn unix_now_secs() -> f64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0.0, |d| d.as_secs_f64())
 }
 
 impl LoadModel {
@@ -55,8 +134,61 @@ impl LoadModel {
     pub fn calculate_current_rps(
         &self,
         elapsed_total_secs: f64,
-        _overall_test_duration_secs: f64,
+        overall_test_duration_secs: f64,
     ) -> f64 {
+        let unix_secs = if self.follows_clock() {
+            unix_now_secs()
+        } else {
+            0.0
+        };
+        self.calculate_rps_at(elapsed_total_secs, overall_test_duration_secs, unix_secs)
+    }
+
+    /// True if the model, or any component of it, depends on the wall clock.
+    fn follows_clock(&self) -> bool {
+        match self {
+            LoadModel::Composite { base, overlays } => {
+                base.follows_clock() || overlays.iter().any(|o| o.model.follows_clock())
+            }
+            LoadModel::DailyTraffic {
+                calendar: Some(_), ..
+            } => true,
+            _ => self.clock_start().is_some(),
+        }
+    }
+
+    /// The UTC time of day cycles start at, for clock-aligned models.
+    pub fn clock_start(&self) -> Option<Duration> {
+        match self {
+            LoadModel::RampRps { clock_start, .. }
+            | LoadModel::DailyTraffic { clock_start, .. } => *clock_start,
+            _ => None,
+        }
+    }
+
+    /// Like [`calculate_current_rps`](Self::calculate_current_rps), at wall
+    /// clock time `unix_secs`; clock-aligned models take their position in
+    /// the cycle from it rather than from `elapsed_total_secs`, and calendars
+    /// the day.
+    pub fn calculate_rps_at(
+        &self,
+        elapsed_total_secs: f64,
+        overall_test_duration_secs: f64,
+        unix_secs: f64,
+    ) -> f64 {
+        let elapsed_total_secs = match self {
+            LoadModel::RampRps {
+                ramp_duration: cycle,
+                clock_start: Some(start),
+                ..
+            }
+            | LoadModel::DailyTraffic {
+                cycle_duration: cycle,
+                clock_start: Some(start),
+                ..
+            } => Self::clock_position(unix_secs, *start, cycle),
+            _ => elapsed_total_secs,
+        };
         match self {
             LoadModel::Concurrent => f64::MAX,
             LoadModel::Rps { target_rps } => *target_rps,
@@ -64,6 +196,7 @@ impl LoadModel {
                 min_rps,
                 max_rps,
                 ramp_duration,
+                ..
             } => Self::calculate_ramp_rps(*min_rps, *max_rps, ramp_duration, elapsed_total_secs),
             LoadModel::DailyTraffic {
                 min_rps,
@@ -75,21 +208,89 @@ impl LoadModel {
                 mid_decline_ratio,
                 mid_sustain_ratio,
                 evening_decline_ratio,
-            } => Self::calculate_daily_traffic_rps(
-                *min_rps,
-                *mid_rps,
-                *max_rps,
-                cycle_duration,
-                *morning_ramp_ratio,
-                *peak_sustain_ratio,
-                *mid_decline_ratio,
-                *mid_sustain_ratio,
-                *evening_decline_ratio,
-                elapsed_total_secs,
-            ),
+                calendar,
+                ..
+            } => {
+                let rps = Self::calculate_daily_traffic_rps(
+                    *min_rps,
+                    *mid_rps,
+                    *max_rps,
+                    cycle_duration,
+                    *morning_ramp_ratio,
+                    *peak_sustain_ratio,
+                    *mid_decline_ratio,
+                    *mid_sustain_ratio,
+                    *evening_decline_ratio,
+                    elapsed_total_secs,
+                );
+                match calendar {
+                    Some(calendar) => rps * calendar.multiplier(unix_secs),
+                    None => rps,
+                }
+            }
+            LoadModel::Composite { base, overlays } => {
+                let at = |model: &LoadModel| {
+                    model.calculate_rps_at(
+                        elapsed_total_secs,
+                        overall_test_duration_secs,
+                        unix_secs,
+                    )
+                };
+                let rps = overlays
+                    .iter()
+                    .fold(at(base), |rps, overlay| match overlay.op {
+                        OverlayOp::Add => rps + at(&overlay.model),
+                        OverlayOp::Multiply => rps * at(&overlay.model),
+                    });
+                rps.max(0.0)
+            }
+            LoadModel::Expression { expression } => {
+                let rps = expression.eval(elapsed_total_secs, overall_test_duration_secs);
+                if rps.is_finite() {
+                    rps.max(0.0)
+                } else {
+                    0.0
+                }
+            }
+            LoadModel::Exponential {
+                start_rps,
+                doubling_period,
+                max_rps,
+                ..
+            } => {
+                let period_secs = doubling_period.as_secs_f64();
+                let rps = if period_secs > 0.0 {
+                    start_rps * (elapsed_total_secs.max(0.0) / period_secs).exp2()
+                } else {
+                    *start_rps
+                };
+                match max_rps {
+                    Some(max) => rps.min(*max),
+                    None => rps,
+                }
+            }
         }
     }
 
+    /// Limits that stop the test, for models that grow until the target
+    /// gives out.
+    pub fn abort_threshold(&self) -> Option<&AbortThreshold> {
+        match self {
+            LoadModel::Exponential { abort, .. } if abort.is_set() => Some(abort),
+            _ => None,
+        }
+    }
+
+    /// Seconds into the current cycle of length `cycle` at `unix_secs`, with
+    /// cycles starting `start` after midnight UTC.
+    fn clock_position(unix_secs: f64, start: Duration, cycle: &Duration) -> f64 {
+        let cycle_secs = cycle.as_secs_f64();
+        if cycle_secs <= 0.0 {
+            return 0.0;
+        }
+        (unix_secs - start.as_secs_f64()).rem_euclid(cycle_secs)
+    }
+
     fn calculate_ramp_rps(
         min_rps: f64,
         max_rps: f64,
@@ -255,6 +456,7 @@ mod tests {
                 min_rps: min,
                 max_rps: max,
                 ramp_duration: Duration::from_secs(secs),
+                clock_start: None,
             }
         }
 
@@ -367,6 +569,8 @@ mod tests {
                 mid_decline_ratio: 0.2,
                 mid_sustain_ratio: 0.1,
                 evening_decline_ratio: 0.2,
+                clock_start: None,
+                calendar: None,
             }
         }
 
@@ -476,6 +680,8 @@ mod tests {
                 mid_decline_ratio: 0.2,
                 mid_sustain_ratio: 0.1,
                 evening_decline_ratio: 0.2,
+                clock_start: None,
+                calendar: None,
             };
             assert_approx(
                 model.calculate_current_rps(50.0, 100.0),
@@ -484,4 +690,257 @@ mod tests {
             );
         }
     }
+
+    // --- Clock alignment tests ---
+
+    mod clock_aligned {
+        use super::*;
+
+        const DAY: f64 = 86400.0;
+        const HOUR: f64 = 3600.0;
+
+        fn daily(clock_start: Option<Duration>) -> LoadModel {
+            LoadModel::DailyTraffic {
+                min_rps: 10.0,
+                mid_rps: 50.0,
+                max_rps: 100.0,
+                cycle_duration: Duration::from_secs(86400),
+                morning_ramp_ratio: 0.2,
+                peak_sustain_ratio: 0.1,
+                mid_decline_ratio: 0.2,
+                mid_sustain_ratio: 0.1,
+                evening_decline_ratio: 0.2,
+                clock_start,
+                calendar: None,
+            }
+        }
+
+        #[test]
+        fn parses_time_of_day() {
+            assert_eq!(parse_time_of_day("04:12"), Ok(Duration::from_secs(15120)));
+            assert_eq!(
+                parse_time_of_day(" 23:59:30 "),
+                Ok(Duration::from_secs(86370))
+            );
+            assert!(parse_time_of_day("24:00").is_err());
+            assert!(parse_time_of_day("9am").is_err());
+        }
+
+        #[test]
+        fn phase_follows_the_wall_clock_not_elapsed_time() {
+            // Cycles start at 04:12 UTC; the morning ramp is 0.2 of a day
+            // (4.8h), so the peak starts at 09:00 UTC.
+            let model = daily(Some(Duration::from_secs(15120)));
+            let nine_am = 200.0 * DAY + 9.0 * HOUR;
+            for elapsed in [0.0, 1234.0, 50000.0] {
+                assert_approx(model.calculate_rps_at(elapsed, DAY, nine_am), 100.0, "peak");
+            }
+            assert_approx(
+                model.calculate_rps_at(0.0, DAY, nine_am - 2.4 * HOUR),
+                55.0,
+                "morning ramp midpoint",
+            );
+            assert_approx(
+                model.calculate_rps_at(0.0, DAY, 200.0 * DAY + 3.0 * HOUR),
+                10.0,
+                "night before the cycle starts",
+            );
+        }
+
+        #[test]
+        fn unaligned_model_ignores_the_clock() {
+            let model = daily(None);
+            assert_eq!(model.clock_start(), None);
+            assert_approx(model.calculate_rps_at(0.0, DAY, 9.0 * HOUR), 10.0, "start");
+        }
+
+        #[test]
+        fn calendar_scales_the_day() {
+            let mut model = daily(None);
+            if let LoadModel::DailyTraffic { calendar, .. } = &mut model {
+                *calendar =
+                    Some(TrafficCalendar::from_yaml("weekdays:\n  saturday: 0.4\n").unwrap());
+            }
+            // 2026-11-23 is a Monday; elapsed 0 starts the morning ramp at min.
+            let monday = 1_795_392_000.0;
+            assert_approx(model.calculate_rps_at(0.0, DAY, monday), 10.0, "weekday");
+            assert_approx(
+                model.calculate_rps_at(0.0, DAY, monday + 5.0 * DAY),
+                4.0,
+                "saturday",
+            );
+        }
+
+        #[test]
+        fn ramp_repeats_every_duration() {
+            let model = LoadModel::RampRps {
+                min_rps: 10.0,
+                max_rps: 100.0,
+                ramp_duration: Duration::from_secs(3600),
+                clock_start: Some(Duration::ZERO),
+            };
+            assert_approx(
+                model.calculate_rps_at(0.0, HOUR, 7.0 * HOUR),
+                10.0,
+                "hour start",
+            );
+            assert_approx(
+                model.calculate_rps_at(0.0, HOUR, 7.0 * HOUR + 1800.0),
+                100.0,
+                "mid hour",
+            );
+        }
+    }
+
+    // --- Composite model tests ---
+
+    mod composite {
+        use super::*;
+
+        fn ramp(min: f64, max: f64, secs: u64) -> LoadModel {
+            LoadModel::RampRps {
+                min_rps: min,
+                max_rps: max,
+                ramp_duration: Duration::from_secs(secs),
+                clock_start: None,
+            }
+        }
+
+        #[test]
+        fn adds_and_multiplies_in_order() {
+            // (ramp 10..100 + 5) * 2
+            let model = LoadModel::Composite {
+                base: Box::new(ramp(10.0, 100.0, 90)),
+                overlays: vec![
+                    LoadOverlay {
+                        op: OverlayOp::Add,
+                        model: LoadModel::Rps { target_rps: 5.0 },
+                    },
+                    LoadOverlay {
+                        op: OverlayOp::Multiply,
+                        model: LoadModel::Rps { target_rps: 2.0 },
+                    },
+                ],
+            };
+            assert_approx(model.calculate_current_rps(0.0, 90.0), 30.0, "start");
+            assert_approx(model.calculate_current_rps(45.0, 90.0), 210.0, "peak");
+        }
+
+        #[test]
+        fn growth_trend_multiplier() {
+            // The first third of a 3x-long ramp grows linearly from 1.0 to
+            // 1.5 over the 300s test.
+            let model = LoadModel::Composite {
+                base: Box::new(LoadModel::Rps { target_rps: 100.0 }),
+                overlays: vec![LoadOverlay {
+                    op: OverlayOp::Multiply,
+                    model: ramp(1.0, 1.5, 900),
+                }],
+            };
+            assert_approx(model.calculate_current_rps(0.0, 300.0), 100.0, "start");
+            assert_approx(model.calculate_current_rps(150.0, 300.0), 125.0, "midway");
+            assert_approx(model.calculate_current_rps(300.0, 300.0), 150.0, "end");
+        }
+
+        #[test]
+        fn never_negative() {
+            let model = LoadModel::Composite {
+                base: Box::new(LoadModel::Rps { target_rps: 10.0 }),
+                overlays: vec![LoadOverlay {
+                    op: OverlayOp::Add,
+                    model: LoadModel::Rps { target_rps: -50.0 },
+                }],
+            };
+            assert_eq!(model.calculate_current_rps(0.0, 60.0), 0.0);
+        }
+    }
+
+    // --- Expression model tests ---
+
+    mod expression {
+        use super::*;
+
+        fn model(source: &str) -> LoadModel {
+            LoadModel::Expression {
+                expression: RateExpression::parse(source).unwrap(),
+            }
+        }
+
+        #[test]
+        fn evaluates_against_elapsed_time() {
+            let model = model("100 + (t > 1800 ? 200 : 0)");
+            assert_approx(model.calculate_current_rps(60.0, 3600.0), 100.0, "before");
+            assert_approx(model.calculate_current_rps(1900.0, 3600.0), 300.0, "after");
+        }
+
+        #[test]
+        fn clamps_negative_and_non_finite_rates() {
+            assert_eq!(model("50 - t").calculate_current_rps(100.0, 3600.0), 0.0);
+            assert_eq!(model("1 / (t - 5)").calculate_current_rps(5.0, 3600.0), 0.0);
+            assert_eq!(model("sqrt(0 - t)").calculate_current_rps(5.0, 3600.0), 0.0);
+        }
+    }
+
+    // --- Exponential model tests ---
+
+    mod exponential {
+        use super::*;
+
+        fn model(max_rps: Option<f64>) -> LoadModel {
+            LoadModel::Exponential {
+                start_rps: 10.0,
+                doubling_period: Duration::from_secs(120),
+                max_rps,
+                abort: AbortThreshold::default(),
+            }
+        }
+
+        #[test]
+        fn doubles_every_period() {
+            let model = model(None);
+            assert_approx(model.calculate_current_rps(0.0, 3600.0), 10.0, "start");
+            assert_approx(
+                model.calculate_current_rps(60.0, 3600.0),
+                10.0 * 2f64.sqrt(),
+                "half",
+            );
+            assert_approx(
+                model.calculate_current_rps(120.0, 3600.0),
+                20.0,
+                "one period",
+            );
+            assert_approx(
+                model.calculate_current_rps(600.0, 3600.0),
+                320.0,
+                "five periods",
+            );
+            assert!(model.abort_threshold().is_none());
+        }
+
+        #[test]
+        fn stops_growing_at_max_rps() {
+            let model = model(Some(100.0));
+            assert_approx(
+                model.calculate_current_rps(240.0, 3600.0),
+                40.0,
+                "below cap",
+            );
+            assert_approx(model.calculate_current_rps(3000.0, 3600.0), 100.0, "capped");
+        }
+
+        #[test]
+        fn zero_period_holds_start_rate() {
+            let model = LoadModel::Exponential {
+                start_rps: 5.0,
+                doubling_period: Duration::ZERO,
+                max_rps: None,
+                abort: AbortThreshold {
+                    error_rate_percent: Some(1.0),
+                    latency: None,
+                },
+            };
+            assert_approx(model.calculate_current_rps(500.0, 3600.0), 5.0, "flat");
+            assert!(model.abort_threshold().is_some());
+        }
+    }
 }
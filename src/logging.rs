@@ -0,0 +1,93 @@
+//! Logging setup.
+//!
+//! All runtime output goes through `tracing`. Filtering follows `RUST_LOG`
+//! (default `rust_loadtest=info`) and the output format follows `LOG_FORMAT`:
+//!
+//! - `text` (default): human-readable lines, multi-line end-of-test tables
+//! - `json`: one JSON object per line with event fields flattened to the top
+//!   level and the enclosing span's fields (e.g. `task_id`, `run_id`) under
+//!   `span`, for Kubernetes / Loki / Cloud Logging pipelines. End-of-test
+//!   reports are emitted as one structured event per row instead of tables.
+
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use tracing_subscriber::{fmt, EnvFilter};
+
+/// Filter used when `RUST_LOG` is unset or invalid.
+pub const DEFAULT_FILTER: &str = "rust_loadtest=info";
+
+/// Set once the subscriber is initialized in JSON mode.
+static JSON_OUTPUT: AtomicBool = AtomicBool::new(false);
+
+/// Log output format selected by `LOG_FORMAT`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LogFormat {
+    Text,
+    Json,
+}
+
+impl LogFormat {
+    /// Parse a `LOG_FORMAT` value. Unknown values fall back to text.
+    pub fn parse(value: &str) -> Self {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "json" => LogFormat::Json,
+            _ => LogFormat::Text,
+        }
+    }
+
+    /// Read `LOG_FORMAT` from the environment.
+    pub fn from_env() -> Self {
+        std::env::var("LOG_FORMAT")
+            .map(|v| Self::parse(&v))
+            .unwrap_or(LogFormat::Text)
+    }
+}
+
+/// Install the global tracing subscriber.
+///
+/// Safe to call more than once; later calls are ignored.
+pub fn init(format: LogFormat) {
+    let env_filter =
+        EnvFilter::try_from_default_env().unwrap_or_else(|_| EnvFilter::new(DEFAULT_FILTER));
+
+    let result = match format {
+        LogFormat::Json => fmt()
+            .with_env_filter(env_filter)
+            .with_target(true)
+            .with_thread_ids(true)
+            .json()
+            .flatten_event(true)
+            .with_current_span(true)
+            .with_span_list(false)
+            .try_init(),
+        LogFormat::Text => fmt()
+            .with_env_filter(env_filter)
+            .with_target(true)
+            .with_thread_ids(true)
+            .try_init(),
+    };
+
+    if result.is_ok() {
+        JSON_OUTPUT.store(format == LogFormat::Json, Ordering::Relaxed);
+    }
+}
+
+/// Returns true when logs are emitted as JSON, so callers can prefer
+/// structured per-row events over preformatted multi-line text.
+pub fn json_output() -> bool {
+    JSON_OUTPUT.load(Ordering::Relaxed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_log_format() {
+        assert_eq!(LogFormat::parse("json"), LogFormat::Json);
+        assert_eq!(LogFormat::parse(" JSON "), LogFormat::Json);
+        assert_eq!(LogFormat::parse("text"), LogFormat::Text);
+        assert_eq!(LogFormat::parse(""), LogFormat::Text);
+        assert_eq!(LogFormat::parse("pretty"), LogFormat::Text);
+    }
+}
@@ -6,61 +6,88 @@ static GLOBAL: MiMalloc = MiMalloc;
 use std::sync::{Arc, Mutex};
 use tokio::sync::{mpsc, watch};
 use tokio::time::{self, Duration};
-use tracing::{error, info};
-use tracing_subscriber::{fmt, EnvFilter};
+use tracing::{error, info, warn};
 
 use hyper::service::{make_service_fn, service_fn};
 use hyper::{Body, Method, Request, Response, Server, StatusCode};
 use std::convert::Infallible;
-
-use rust_loadtest::client::build_client;
+use std::path::Path;
+use std::process::ExitCode;
+
+use rust_loadtest::abort_threshold::AbortMonitor;
+use rust_loadtest::addr;
+use rust_loadtest::apdex::{format_apdex_table, GLOBAL_APDEX};
+use rust_loadtest::app_error::{self, AppError, EXIT_SUCCESS};
+#[cfg(feature = "artifacts")]
+use rust_loadtest::artifacts::{self, ArtifactStore};
+use rust_loadtest::ci_report::CiReporter;
+use rust_loadtest::client::{build_client, ClientConfig, SourceAddrClients};
+use rust_loadtest::client_profiles;
 use rust_loadtest::config::Config;
 use rust_loadtest::connection_pool::{PoolConfig, GLOBAL_POOL_STATS};
+use rust_loadtest::dns_refresh;
+use rust_loadtest::dns_round_robin;
+use rust_loadtest::engine::Engine;
+use rust_loadtest::executor::ScenarioExecutor;
+use rust_loadtest::failure_signatures::{
+    format_failure_signature_table, format_timestamp, GLOBAL_FAILURE_SIGNATURES, TOP_SIGNATURES,
+};
+use rust_loadtest::fd_limits;
+use rust_loadtest::golden;
+use rust_loadtest::hdr_log;
+use rust_loadtest::header_layers::{self, HeaderLayer};
+use rust_loadtest::junit;
+use rust_loadtest::latency_budget::{format_latency_budget_table, GLOBAL_LATENCY_BUDGET};
+use rust_loadtest::latency_sla;
+use rust_loadtest::littles_law::{self, GLOBAL_CONCURRENCY};
+use rust_loadtest::live_stream;
 use rust_loadtest::load_models::LoadModel;
+use rust_loadtest::logging::{self, LogFormat};
 use rust_loadtest::memory_guard::{
     init_percentile_tracking_flag, spawn_memory_guard, MemoryGuardConfig,
 };
 use rust_loadtest::metrics::CLUSTER_NODE_INFO;
 use rust_loadtest::metrics::{
-    gather_metrics_string, register_metrics, start_metrics_server, update_memory_metrics,
-    CONNECTION_POOL_IDLE_TIMEOUT_SECONDS, CONNECTION_POOL_MAX_IDLE,
-    PERCENTILE_SAMPLING_RATE_PERCENT, PROCESS_MEMORY_RSS_BYTES, REQUEST_ERRORS_BY_CATEGORY,
-    REQUEST_TOTAL, WORKERS_CONFIGURED_TOTAL,
+    gather_metrics_string, register_metrics, request_outcome_totals, start_metrics_server,
+    update_memory_metrics, MetricsServerConfig, ACHIEVED_RPS, CONNECTION_POOL_IDLE_TIMEOUT_SECONDS,
+    CONNECTION_POOL_MAX_IDLE, INTENDED_RPS, LATENCY_SLA_BREACHES_TOTAL,
+    LATENCY_SLA_PERCENTILE_SECONDS, PERCENTILE_SAMPLING_RATE_PERCENT, PROCESS_MEMORY_RSS_BYTES,
+    REQUEST_DURATION_SECONDS, REQUEST_ERRORS_BY_CATEGORY, REQUEST_TOTAL,
+    RPS_SHORTFALL_WARNINGS_TOTAL, WORKERS_CONFIGURED_TOTAL,
 };
-use rust_loadtest::multi_scenario::ScenarioSelector;
+use rust_loadtest::notify::WebhookNotifier;
 use rust_loadtest::percentiles::{
-    format_percentile_table, rotate_all_histograms, GLOBAL_REQUEST_PERCENTILES,
-    GLOBAL_SCENARIO_PERCENTILES, GLOBAL_STEP_PERCENTILES,
+    format_percentile_table, spawn_histogram_rotation, GLOBAL_REQUEST_PERCENTILES,
+    GLOBAL_ROLLING_REPORT, GLOBAL_SCENARIO_PERCENTILES, GLOBAL_STEP_PERCENTILES,
+    GLOBAL_TAG_PERCENTILES,
 };
+use rust_loadtest::precheck;
+use rust_loadtest::rate_limit;
+use rust_loadtest::rps_override;
+use rust_loadtest::run_id;
+use rust_loadtest::run_summary::RunSummary;
+use rust_loadtest::saturation::{SaturationConfig, SaturationDetector};
+use rust_loadtest::scenario::Scenario;
+use rust_loadtest::scenario_slo;
+use rust_loadtest::seed;
+use rust_loadtest::service_discovery;
+use rust_loadtest::smoke::{self, RunMode};
+use rust_loadtest::tcp_check;
+use rust_loadtest::templates::TemplateMix;
 use rust_loadtest::throughput::{format_throughput_table, GLOBAL_THROUGHPUT_TRACKER};
-use rust_loadtest::worker::{run_scenario_worker, run_worker, ScenarioWorkerConfig, WorkerConfig};
+use rust_loadtest::warmup;
+#[cfg(feature = "web-ui")]
+use rust_loadtest::web_ui;
+use rust_loadtest::worker::{run_worker, ScenarioWorkerConfig, WorkerConfig, WorkerPacing};
 use rust_loadtest::yaml_config::YamlConfig;
 
-/// Initializes the tracing subscriber for structured logging.
-fn init_tracing() {
-    let log_format = std::env::var("LOG_FORMAT").unwrap_or_default();
-
-    let env_filter =
-        EnvFilter::try_from_default_env().unwrap_or_else(|_| EnvFilter::new("rust_loadtest=info"));
-
-    if log_format == "json" {
-        fmt()
-            .with_env_filter(env_filter)
-            .with_target(true)
-            .with_thread_ids(true)
-            .json()
-            .init();
-    } else {
-        fmt()
-            .with_env_filter(env_filter)
-            .with_target(true)
-            .with_thread_ids(true)
-            .init();
-    }
-}
-
 /// Prints percentile latency statistics.
 fn print_percentile_report(enabled: bool, sampling_rate: u8) {
+    if logging::json_output() {
+        log_percentile_events(enabled, sampling_rate);
+        return;
+    }
+
     info!("\n{}", "=".repeat(120));
     info!("PERCENTILE LATENCY REPORT (Issue #33)");
     info!("{}", "=".repeat(120));
@@ -107,13 +134,370 @@ fn print_percentile_report(enabled: bool, sampling_rate: u8) {
         info!("{}", step_table);
     }
 
+    // Step percentiles grouped by tag (METRIC_TAG_KEYS)
+    let tag_stats = GLOBAL_TAG_PERCENTILES.all_stats();
+    if !tag_stats.is_empty() {
+        let tag_table = format_percentile_table("Step Latencies by Tag", &tag_stats);
+        info!("{}", tag_table);
+    }
+
+    // Intervals cleared by histogram rotation (Issue #67)
+    let interval_stats = GLOBAL_ROLLING_REPORT.request_stats_by_interval();
+    if !interval_stats.is_empty() {
+        info!(
+            "\n📊 Histograms were rotated {} time(s); the tables above cover only the \
+             current interval. Earlier intervals:",
+            GLOBAL_ROLLING_REPORT.rotations()
+        );
+        let interval_table =
+            format_percentile_table("Single Request Latencies by Interval", &interval_stats);
+        info!("{}", interval_table);
+    }
+
     info!("{}", "=".repeat(120));
     info!("END OF PERCENTILE REPORT");
     info!("{}\n", "=".repeat(120));
 }
 
+/// Emits percentile statistics as one structured event per label.
+fn log_percentile_events(enabled: bool, sampling_rate: u8) {
+    if !enabled {
+        info!(
+            report = "percentiles",
+            "Percentile tracking was disabled; no latency percentiles collected"
+        );
+        return;
+    }
+
+    let emit = |kind: &str, label: &str, stats: &rust_loadtest::percentiles::PercentileStats| {
+        info!(
+            report = "percentiles",
+            kind,
+            label,
+            sampling_rate_percent = sampling_rate,
+            count = stats.count,
+            min_ms = stats.min as f64 / 1000.0,
+            mean_ms = stats.mean / 1000.0,
+            p50_ms = stats.p50 as f64 / 1000.0,
+            p90_ms = stats.p90 as f64 / 1000.0,
+            p95_ms = stats.p95 as f64 / 1000.0,
+            p99_ms = stats.p99 as f64 / 1000.0,
+            p99_9_ms = stats.p99_9 as f64 / 1000.0,
+            max_ms = stats.max as f64 / 1000.0,
+            "Latency percentiles"
+        );
+    };
+
+    if let Some(stats) = GLOBAL_REQUEST_PERCENTILES.stats() {
+        emit("request", "all", &stats);
+    }
+    for (label, stats) in GLOBAL_SCENARIO_PERCENTILES.all_stats() {
+        emit("scenario", &label, &stats);
+    }
+    for (label, stats) in GLOBAL_STEP_PERCENTILES.all_stats() {
+        emit("step", &label, &stats);
+    }
+    for (label, stats) in GLOBAL_TAG_PERCENTILES.all_stats() {
+        emit("tag", &label, &stats);
+    }
+    for (label, stats) in GLOBAL_ROLLING_REPORT.request_stats_by_interval() {
+        emit("interval", &label, &stats);
+    }
+}
+
+/// Prints Apdex scores for steps with an `slo`.
+fn print_apdex_report() {
+    if GLOBAL_APDEX.is_empty() {
+        return;
+    }
+    if logging::json_output() {
+        for (scenario, counts) in GLOBAL_APDEX.scenario_counts() {
+            info!(
+                report = "apdex",
+                scenario = %scenario,
+                apdex = counts.score().unwrap_or(1.0),
+                satisfied = counts.satisfied,
+                tolerating = counts.tolerating,
+                frustrated = counts.frustrated,
+                "Scenario Apdex"
+            );
+        }
+        for (scenario, step, counts) in GLOBAL_APDEX.step_counts() {
+            info!(
+                report = "apdex",
+                scenario = %scenario,
+                step = %step,
+                apdex = counts.score().unwrap_or(1.0),
+                satisfied = counts.satisfied,
+                tolerating = counts.tolerating,
+                frustrated = counts.frustrated,
+                "Step Apdex"
+            );
+        }
+        return;
+    }
+
+    info!("\n{}", "=".repeat(120));
+    info!("APDEX REPORT");
+    info!("{}", "=".repeat(120));
+    info!("{}", format_apdex_table(&GLOBAL_APDEX));
+    info!("{}", "=".repeat(120));
+    info!("END OF APDEX REPORT");
+    info!("{}\n", "=".repeat(120));
+}
+
+/// Prints each scenario's duration broken down into per-step contributions.
+fn print_latency_budget_report() {
+    if GLOBAL_LATENCY_BUDGET.is_empty() {
+        return;
+    }
+    let reports = GLOBAL_LATENCY_BUDGET.reports();
+    if logging::json_output() {
+        for report in &reports {
+            info!(
+                report = "latency_budget",
+                scenario = %report.scenario,
+                iterations = report.iterations,
+                mean_ms = report.mean_ms,
+                p95_ms = report.p95_ms,
+                dominant_step = report.dominant.as_deref().unwrap_or(""),
+                "Scenario latency budget"
+            );
+            for line in &report.lines {
+                info!(
+                    report = "latency_budget",
+                    scenario = %report.scenario,
+                    step = %line.label,
+                    mean_ms = line.mean_ms,
+                    p95_ms = line.p95_ms,
+                    share = line.share,
+                    dominant = report.dominant.as_deref() == Some(line.label.as_str()),
+                    "Step latency budget"
+                );
+            }
+        }
+        return;
+    }
+
+    info!("\n{}", "=".repeat(120));
+    info!("LATENCY BUDGET REPORT");
+    info!("{}", "=".repeat(120));
+    info!("{}", format_latency_budget_table(&reports));
+    info!("{}", "=".repeat(120));
+    info!("END OF LATENCY BUDGET REPORT");
+    info!("{}\n", "=".repeat(120));
+}
+
+/// Prints the most frequent step failure signatures with their counts and
+/// when each was first and last seen.
+fn print_failure_signature_report() {
+    if GLOBAL_FAILURE_SIGNATURES.is_empty() {
+        return;
+    }
+    let rows = GLOBAL_FAILURE_SIGNATURES.top(TOP_SIGNATURES);
+    if logging::json_output() {
+        for row in &rows {
+            info!(
+                report = "failure_signatures",
+                scenario = %row.scenario,
+                step = %row.step,
+                status = row.status,
+                error = %row.message,
+                count = row.count,
+                first_seen = %format_timestamp(row.first_seen),
+                last_seen = %format_timestamp(row.last_seen),
+                "Failure signature"
+            );
+        }
+        return;
+    }
+
+    info!("\n{}", "=".repeat(120));
+    info!(
+        "FAILURE SIGNATURES (top {} of {})",
+        rows.len(),
+        GLOBAL_FAILURE_SIGNATURES.len()
+    );
+    info!("{}", "=".repeat(120));
+    info!("{}", format_failure_signature_table(&rows));
+    info!("{}", "=".repeat(120));
+    info!("END OF FAILURE SIGNATURES");
+    info!("{}\n", "=".repeat(120));
+}
+
+/// Prints the per-scenario success-rate thresholds, judged separately from
+/// the run's overall results. Fails when any scenario missed its threshold.
+fn print_scenario_slo_report() -> Result<(), AppError> {
+    let results = scenario_slo::evaluate();
+    if results.is_empty() {
+        return Ok(());
+    }
+    if logging::json_output() {
+        for result in &results {
+            info!(
+                report = "scenario_slo",
+                scenario = %result.scenario,
+                threshold = %result.threshold,
+                success_rate_percent = result.success_rate,
+                successes = result.successes,
+                failures = result.failures,
+                passed = result.passed,
+                "Scenario success-rate threshold"
+            );
+        }
+    } else {
+        info!("\n{}", "=".repeat(120));
+        info!("SCENARIO SLO REPORT");
+        info!("{}", "=".repeat(120));
+        for result in &results {
+            info!("{}", result);
+        }
+        info!("{}", "=".repeat(120));
+        info!("END OF SCENARIO SLO REPORT");
+        info!("{}\n", "=".repeat(120));
+    }
+
+    let failed: Vec<&str> = results
+        .iter()
+        .filter(|r| !r.passed)
+        .map(|r| r.scenario.as_str())
+        .collect();
+    if failed.is_empty() {
+        Ok(())
+    } else {
+        Err(AppError::ThresholdFailed(format!(
+            "scenario success-rate threshold missed: {}",
+            failed.join(", ")
+        )))
+    }
+}
+
+/// Prints each windowed percentile SLA's worst value and breached seconds.
+/// Fails when any SLA was breached during the run.
+fn print_latency_sla_report() -> Result<(), AppError> {
+    let results = latency_sla::results();
+    if results.is_empty() {
+        return Ok(());
+    }
+    if logging::json_output() {
+        for result in &results {
+            info!(
+                report = "latency_sla",
+                scenario = %result.sla.scenario,
+                step = %result.sla.step,
+                sla = %result.sla,
+                worst_ms = result.worst_ms,
+                breached_secs = result.breached_secs,
+                evaluated_secs = result.evaluated_secs,
+                passed = result.passed,
+                "Latency SLA"
+            );
+        }
+    } else {
+        info!("\n{}", "=".repeat(120));
+        info!("LATENCY SLA REPORT");
+        info!("{}", "=".repeat(120));
+        for result in &results {
+            info!("{}", result);
+        }
+        info!("{}", "=".repeat(120));
+        info!("END OF LATENCY SLA REPORT");
+        info!("{}\n", "=".repeat(120));
+    }
+
+    let failed: Vec<String> = results
+        .iter()
+        .filter(|r| !r.passed)
+        .map(|r| r.sla.to_string())
+        .collect();
+    if failed.is_empty() {
+        Ok(())
+    } else {
+        Err(AppError::ThresholdFailed(format!(
+            "latency SLA breached: {}",
+            failed.join(", ")
+        )))
+    }
+}
+
+/// Prints the average concurrency next to the λW it should equal, warning
+/// when they disagree.
+fn print_littles_law_report() {
+    let Some(check) = GLOBAL_CONCURRENCY.check() else {
+        return;
+    };
+    if logging::json_output() {
+        info!(
+            report = "littles_law",
+            measured_concurrency = check.measured_concurrency,
+            throughput_rps = check.throughput_rps,
+            mean_latency_ms = check.mean_latency_secs * 1000.0,
+            implied_concurrency = check.implied_concurrency,
+            deviation = check.deviation(),
+            consistent = check.is_consistent(),
+            "Little's Law check"
+        );
+    } else {
+        info!("\n## Little's Law Check (L = λW)\n");
+        info!("{}", check);
+    }
+    if let Some(hint) = check.hint() {
+        warn!(
+            measured_concurrency = check.measured_concurrency,
+            implied_concurrency = check.implied_concurrency,
+            "Average concurrency differs from throughput × latency by more than {:.0}%: {}",
+            littles_law::TOLERANCE * 100.0,
+            hint
+        );
+    }
+}
+
+/// Prints the connection warm-up that ran before the test, if any.
+fn print_warmup_report() {
+    let Some(stats) = warmup::last() else {
+        return;
+    };
+    if logging::json_output() {
+        info!(
+            report = "warmup",
+            connections = stats.connections,
+            succeeded = stats.succeeded,
+            failed = stats.failed,
+            elapsed_ms = stats.elapsed.as_millis() as u64,
+            min_ms = stats.min.map(|d| d.as_millis() as u64),
+            mean_ms = stats.mean.map(|d| d.as_millis() as u64),
+            max_ms = stats.max.map(|d| d.as_millis() as u64),
+            "Connection warm-up"
+        );
+    } else {
+        info!("\n## Connection Warm-up (not included in the statistics above)\n");
+        info!("{}", stats);
+    }
+}
+
 /// Prints per-scenario throughput statistics.
 fn print_throughput_report() {
+    if logging::json_output() {
+        for stats in GLOBAL_THROUGHPUT_TRACKER.all_stats() {
+            info!(
+                report = "throughput",
+                scenario = %stats.scenario_name,
+                count = stats.total_count,
+                duration_secs = stats.duration.as_secs_f64(),
+                rps = stats.rps,
+                avg_time_ms = stats.avg_time_ms,
+                "Scenario throughput"
+            );
+        }
+        info!(
+            report = "throughput",
+            total_rps = GLOBAL_THROUGHPUT_TRACKER.total_throughput(),
+            elapsed_secs = GLOBAL_THROUGHPUT_TRACKER.elapsed().as_secs_f64(),
+            "Total throughput"
+        );
+        return;
+    }
+
     info!("\n{}", "=".repeat(120));
     info!("PER-SCENARIO THROUGHPUT REPORT (Issue #35)");
     info!("{}", "=".repeat(120));
@@ -140,8 +524,55 @@ fn print_throughput_report() {
     info!("{}\n", "=".repeat(120));
 }
 
+/// Prints 429 counts, advertised budgets and effective rate limits for
+/// endpoints that pushed back.
+fn print_rate_limit_report() {
+    let endpoints = rate_limit::endpoints();
+    if endpoints.is_empty() {
+        return;
+    }
+    if logging::json_output() {
+        for (endpoint, stats) in &endpoints {
+            info!(
+                report = "rate_limit",
+                endpoint = %endpoint,
+                responses = stats.responses,
+                limited = stats.limited,
+                advertised_limit = ?stats.limit,
+                min_remaining = ?stats.min_remaining,
+                effective_rps = ?stats.effective_rps(),
+                "Endpoint rate limit"
+            );
+        }
+        return;
+    }
+
+    info!("\n{}", "=".repeat(120));
+    info!("RATE LIMIT REPORT");
+    info!("{}", "=".repeat(120));
+    info!("{}", rate_limit::format_rate_limit_table(&endpoints));
+    info!("Effective RPS: accepted responses per second from the first 429 onwards.");
+    info!("{}", "=".repeat(120));
+    info!("END OF RATE LIMIT REPORT");
+    info!("{}\n", "=".repeat(120));
+}
+
 /// Prints connection pool statistics.
 fn print_pool_report() {
+    if logging::json_output() {
+        let stats = GLOBAL_POOL_STATS.stats();
+        info!(
+            report = "connection_pool",
+            total_requests = stats.total_requests,
+            likely_new_connections = stats.likely_new_connections,
+            likely_reused_connections = stats.likely_reused_connections,
+            reuse_rate_percent = stats.reuse_rate(),
+            duration_secs = stats.duration().map(|d| d.as_secs_f64()),
+            "Connection pool statistics"
+        );
+        return;
+    }
+
     info!("\n{}", "=".repeat(120));
     info!("CONNECTION POOL STATISTICS (Issue #36)");
     info!("{}", "=".repeat(120));
@@ -187,10 +618,127 @@ fn print_pool_report() {
     info!("{}\n", "=".repeat(120));
 }
 
+/// Logs every header set at more than one layer (global, scenario, step,
+/// auth) so an unintended override is visible before load starts.
+fn warn_header_conflicts<'a>(scenarios: impl IntoIterator<Item = &'a Scenario>) {
+    for conflict in header_layers::conflicts(&header_layers::global_headers(), scenarios) {
+        warn!(%conflict, "Header set at multiple layers");
+    }
+}
+
+/// Runs `MODE=smoke`: every scenario in `plan` (or the single `TARGET_URL`
+/// request when there is no plan) once, logging every request and response,
+/// then prints the report. Returns true if every scenario passed.
+async fn run_smoke(
+    config: &Config,
+    client_config: &ClientConfig,
+    plan: Option<&YamlConfig>,
+) -> Result<(), AppError> {
+    if let Some(plan) = plan {
+        let variables = plan.resolve_variables().map_err(|e| {
+            AppError::Config(format!("failed to resolve variables in SMOKE_PLAN: {}", e))
+        })?;
+        rust_loadtest::shared_state::set_global_variables(variables);
+    }
+    let scenarios = match plan {
+        Some(plan) => plan.to_scenarios().map_err(|e| {
+            AppError::Config(format!("failed to build scenarios from SMOKE_PLAN: {}", e))
+        })?,
+        None => vec![smoke::single_request_scenario(
+            &config.target_url,
+            &config.request_type,
+            config.json_payload.clone().filter(|_| config.send_json),
+        )],
+    };
+
+    warn_header_conflicts(&scenarios);
+    config
+        .safety
+        .check_targets(&config.target_url, client_config, &scenarios, None)
+        .await
+        .map_err(|e| AppError::Config(format!("target refused by safety guard: {}", e)))?;
+
+    // Global headers come from the executor's header layer, not client defaults.
+    let mut smoke_client_config = client_config.clone();
+    smoke_client_config.custom_headers = None;
+    smoke_client_config.cookie_store = true;
+    let client = build_client(&smoke_client_config)
+        .map_err(|e| AppError::Config(format!("failed to build HTTP client: {}", e)))?
+        .client;
+
+    // Verbose output: log every request and response in full.
+    rust_loadtest::debug_sample::init(1.0, u32::MAX);
+
+    info!(
+        scenarios = scenarios.len(),
+        target_url = %config.target_url,
+        "Smoke mode: running each scenario once (load model ignored)"
+    );
+    let smoke_run_id = config.run_id.clone().unwrap_or_else(run_id::generate);
+    let executor = ScenarioExecutor::new(
+        service_discovery::rewrite(&config.target_url).into_owned(),
+        client,
+        config.cluster.node_id.clone(),
+        smoke_run_id.clone(),
+    );
+    // Scenarios with their own `client:` block run on a dedicated client.
+    let mut report = smoke::SmokeReport {
+        results: Vec::with_capacity(scenarios.len()),
+    };
+    for scenario in &scenarios {
+        let part = match &scenario.client {
+            Some(overrides) => {
+                let mut scenario_client_config = smoke_client_config.clone();
+                overrides.apply(&mut scenario_client_config);
+                let client = build_client(&scenario_client_config)
+                    .map_err(|e| {
+                        AppError::Config(format!(
+                            "failed to build HTTP client for scenario '{}': {}",
+                            scenario.name, e
+                        ))
+                    })?
+                    .client;
+                let scenario_executor = ScenarioExecutor::new(
+                    service_discovery::rewrite(&config.target_url).into_owned(),
+                    client,
+                    config.cluster.node_id.clone(),
+                    smoke_run_id.clone(),
+                );
+                smoke::run(&scenario_executor, std::slice::from_ref(scenario)).await
+            }
+            None => smoke::run(&executor, std::slice::from_ref(scenario)).await,
+        };
+        report.results.extend(part.results);
+    }
+    info!("{}", report.format());
+    if report.passed() {
+        Ok(())
+    } else if report.unreachable() {
+        Err(AppError::TargetUnreachable(format!(
+            "{} did not respond",
+            config.target_url
+        )))
+    } else {
+        let failed = report.results.iter().filter(|r| !r.success).count();
+        Err(AppError::ThresholdFailed(format!(
+            "{} of {} smoke scenario(s) failed",
+            failed,
+            report.results.len()
+        )))
+    }
+}
+
+/// Prints a subcommand failure, since subcommands run before tracing is set
+/// up, and returns it as a config error (exit code 2).
+fn subcommand_failed(message: String) -> AppError {
+    eprintln!("{}", message);
+    AppError::Config(message)
+}
+
 /// Reads current environment variables and writes an equivalent YAML config
 /// file.  Called when the binary is run as `rust-loadtest migrate [--output
-/// <path>]`.  Exits the process when done.
-fn run_migrate(args: &[String]) {
+/// <path>]`.
+fn run_migrate(args: &[String]) -> Result<(), AppError> {
     // Parse optional --output flag
     let output_path = args
         .windows(2)
@@ -205,8 +753,9 @@ fn run_migrate(args: &[String]) {
     let target_url = match env("TARGET_URL") {
         Some(u) => u,
         None => {
-            eprintln!("migrate: TARGET_URL is not set — nothing to migrate.");
-            std::process::exit(1);
+            return Err(subcommand_failed(
+                "migrate: TARGET_URL is not set — nothing to migrate.".to_string(),
+            ))
         }
     };
 
@@ -334,18 +883,154 @@ scenarios:
             );
         }
         Err(e) => {
-            eprintln!("migrate: failed to write '{}': {}", output_path, e);
-            std::process::exit(1);
+            return Err(subcommand_failed(format!(
+                "migrate: failed to write '{}': {}",
+                output_path, e
+            )))
         }
     }
-    std::process::exit(0);
+    Ok(())
+}
+
+/// `rust_loadtest import <format> <file> [--output <path>]`
+///
+/// Converts a recording or another tool's test plan into a YAML config.
+/// Writes to stdout unless `--output` is given; conversion warnings go to stderr.
+#[cfg(feature = "importers")]
+fn run_import(args: &[String]) -> Result<(), AppError> {
+    use rust_loadtest::importers::{curl, har, jmeter, k6, postman, ImportResult};
+
+    let (format, input) = match (args.first(), args.get(1)) {
+        (Some(f), Some(i)) => (f.as_str(), i.as_str()),
+        _ => return Err(import_usage()),
+    };
+    let has_flag = |flag: &str| args.iter().any(|a| a == flag);
+    let output_path = args
+        .windows(2)
+        .find(|w| w[0] == "--output" || w[0] == "-o")
+        .map(|w| w[1].clone());
+
+    let result: Result<ImportResult, _> = match format {
+        "har" => {
+            let options = har::HarImportOptions {
+                include_static_assets: has_flag("--include-assets"),
+                ..Default::default()
+            };
+            har::import_har_file(input, &options)
+        }
+        "postman" => {
+            let environment = args
+                .windows(2)
+                .find(|w| w[0] == "--environment" || w[0] == "-e")
+                .map(|w| std::path::PathBuf::from(&w[1]));
+            postman::import_postman_file(input, environment.as_deref())
+        }
+        "jmeter" => jmeter::import_jmx_file(input),
+        "k6" => k6::import_k6_file(input),
+        "curl" => {
+            // The "file" is the command itself; "-" reads it from stdin.
+            if input == "-" {
+                let mut command = String::new();
+                match std::io::Read::read_to_string(&mut std::io::stdin(), &mut command) {
+                    Ok(_) => curl::import_curl(&command),
+                    Err(e) => Err(e.into()),
+                }
+            } else {
+                curl::import_curl(input)
+            }
+        }
+        other => {
+            eprintln!("import: unknown format '{}'", other);
+            return Err(import_usage());
+        }
+    };
+
+    let result = result.map_err(|e| subcommand_failed(format!("import: {}", e)))?;
+
+    for warning in &result.warnings {
+        eprintln!("import: warning: {}", warning);
+    }
+
+    let yaml = result
+        .to_yaml()
+        .map_err(|e| subcommand_failed(format!("import: {}", e)))?;
+
+    match output_path {
+        Some(path) => {
+            std::fs::write(&path, &yaml).map_err(|e| {
+                subcommand_failed(format!("import: failed to write '{}': {}", path, e))
+            })?;
+            eprintln!("import: wrote YAML config to '{}'", path);
+        }
+        None => print!("{}", yaml),
+    }
+    Ok(())
+}
+
+/// `import` in a binary built without the `importers` feature.
+#[cfg(not(feature = "importers"))]
+fn run_import(_args: &[String]) -> Result<(), AppError> {
+    let error = subcommand_failed(
+        "import: this binary was built without the importers feature".to_string(),
+    );
+    eprintln!("  rebuild with: cargo build --release --features importers");
+    Err(error)
+}
+
+/// Prints `import` subcommand usage and returns it as a config error.
+#[cfg(feature = "importers")]
+fn import_usage() -> AppError {
+    eprintln!("usage: rust_loadtest import <format> <file> [--output <path>] [options]");
+    eprintln!();
+    eprintln!("formats:");
+    eprintln!("  har       Browser HTTP Archive export (--include-assets keeps JS/CSS/images)");
+    eprintln!("  postman   Postman collection v2.1 (--environment <file> applies an environment)");
+    eprintln!("  jmeter    JMeter .jmx plan (thread groups, HTTP samplers, common assertions)");
+    eprintln!("  k6        k6 script (http.* calls, check() status, sleep(), options)");
+    eprintln!("  curl      A quoted 'curl ...' command instead of a file (- reads stdin)");
+    AppError::Config("import: invalid arguments".to_string())
+}
+
+/// `validate <file.yaml> [--strict]`: reports validation errors and, with
+/// `--strict`, lint warnings as a config error (exit 2).
+fn run_validate(args: &[String]) -> Result<(), AppError> {
+    let Some(path) = args.iter().find(|a| !a.starts_with("--")) else {
+        return Err(subcommand_failed(
+            "usage: rust_loadtest validate <file.yaml> [--strict]".to_string(),
+        ));
+    };
+    let strict = args.iter().any(|a| a == "--strict");
+
+    let config = YamlConfig::from_file(path)
+        .map_err(|e| subcommand_failed(format!("validate: {}: {}", path, e)))?;
+    Config::from_yaml(&config)
+        .map_err(|e| subcommand_failed(format!("validate: {}: {}", path, e)))?;
+
+    let warnings = rust_loadtest::lint::lint(&config);
+    for warning in &warnings {
+        eprintln!("validate: warning: {}", warning);
+    }
+    eprintln!(
+        "validate: {} is valid ({} warning{})",
+        path,
+        warnings.len(),
+        if warnings.len() == 1 { "" } else { "s" }
+    );
+    if strict && !warnings.is_empty() {
+        return Err(subcommand_failed(format!(
+            "validate: {}: --strict and {} warning(s)",
+            path,
+            warnings.len()
+        )));
+    }
+    Ok(())
 }
 
 /// Prints helpful configuration documentation.
 fn print_config_help() {
     eprintln!("Required environment variables:");
     eprintln!(
-        "  TARGET_URL              - The URL to load test (must start with http:// or https://)"
+        "  TARGET_URL              - The URL to load test (http://, https://, or consul://service / k8s://service.namespace:port)"
     );
     eprintln!();
     eprintln!("Optional environment variables:");
@@ -358,9 +1043,11 @@ fn print_config_help() {
         "  NUM_CONCURRENT_TASKS    - Number of concurrent workers (default: 10, must be > 0)"
     );
     eprintln!("  TEST_DURATION           - Total test duration: 10m, 2h, 1d (default: 2h)");
+    eprintln!("  WORKER_RAMP             - Start workers gradually over this period, e.g. 30s (default: 0s)");
+    eprintln!("  WORKER_JITTER_PERCENT   - Random ±% jitter on worker start offsets and pacing (default: 10)");
     eprintln!();
     eprintln!("Load model configuration:");
-    eprintln!("  LOAD_MODEL_TYPE         - Concurrent, Rps, RampRps, or DailyTraffic (default: Concurrent)");
+    eprintln!("  LOAD_MODEL_TYPE         - Concurrent, Rps, RampRps, DailyTraffic, Expression or Exponential (default: Concurrent)");
     eprintln!("    Rps model requires:");
     eprintln!("      TARGET_RPS          - Target requests per second");
     eprintln!("    RampRps model requires:");
@@ -372,21 +1059,72 @@ fn print_config_help() {
     eprintln!("      DAILY_MID_RPS       - Medium (afternoon) RPS");
     eprintln!("      DAILY_MAX_RPS       - Maximum (peak) RPS");
     eprintln!("      DAILY_CYCLE_DURATION - Full cycle duration (e.g., 1d)");
+    eprintln!("      DAILY_CALENDAR_FILE - YAML file of weekday/date RPS multipliers (optional)");
+    eprintln!("    Expression model requires:");
+    eprintln!(
+        "      RPS_EXPRESSION      - Rate formula of elapsed seconds t, e.g. 100 + 50*sin(t/600)"
+    );
+    eprintln!("    Exponential model requires:");
+    eprintln!("      START_RPS           - Initial RPS");
+    eprintln!("      DOUBLING_PERIOD     - Time for the rate to double (e.g., 2m)");
+    eprintln!("      MAX_RPS             - Rate to stop growing at (optional)");
+    eprintln!("      ABORT_ERROR_RATE    - Stop the test above this error % (optional)");
+    eprintln!("      ABORT_LATENCY_MS    - Stop the test above this mean latency (optional)");
+    eprintln!("    RampRps and DailyTraffic:");
+    eprintln!(
+        "      ALIGN_TO_CLOCK      - Follow the time of day, not elapsed time (default: false)"
+    );
+    eprintln!(
+        "      CYCLE_START         - UTC time of day cycles start at, HH:MM (default: 00:00)"
+    );
     eprintln!();
     eprintln!("TLS/mTLS configuration:");
     eprintln!("  SKIP_TLS_VERIFY         - Skip TLS certificate verification (default: false)");
+    eprintln!("  TLS_SESSION_RESUMPTION  - Resume cached TLS sessions (default: true)");
     eprintln!("  CLIENT_CERT_PATH        - Path to client certificate for mTLS");
     eprintln!("  CLIENT_KEY_PATH         - Path to client key for mTLS");
+    eprintln!("  CLIENT_CERT_RELOAD_INTERVAL - Check cert/key files for renewal (default: 30s, 0 disables)");
     eprintln!("  Note: Both CLIENT_CERT_PATH and CLIENT_KEY_PATH must be set together");
     eprintln!();
     eprintln!("Advanced configuration:");
-    eprintln!("  RESOLVE_TARGET_ADDR     - DNS override: hostname:ip:port");
+    eprintln!("  RESOLVE_TARGET_ADDR     - DNS override: hostname:ip:port[,...] (IPv6 as [::1])");
+    eprintln!("  TLS_SERVER_NAME         - SNI/Host name to present; TARGET_URL host becomes the connect address");
     eprintln!("  CUSTOM_HEADERS          - Comma-separated headers (use \\, for literal commas)");
+    eprintln!("  DNS_REFRESH_INTERVAL    - Re-resolve hostnames this often, retiring connections on change (default: off)");
+    eprintln!("  TARGET_PRECHECK         - Check DNS/TCP/TLS/HEAD against the target before the test (default: true)");
+    eprintln!("  WARMUP_CONNECTIONS      - Connections opened with one request each before the test (default: 0)");
+    eprintln!("  MAX_BODY_BUFFER         - Most response body bytes kept per request, e.g. 512KB (default: 10MB)");
+    eprintln!("  TEMPLATE_DIR            - Directory of weighted request templates (.yaml/.json)");
+    eprintln!("  GOLDEN_RECORD           - Record missing matchesGolden files from the first response (default: false)");
+    eprintln!("  RPS_OVERRIDE_FILE       - File holding a target RPS that overrides the load model while set");
+    eprintln!("  SHARED_STORE_URL        - Use another node's shared store (POST /store) for scenario shared: actions");
     eprintln!("  METRIC_NAMESPACE        - Prometheus metric namespace (default: rust_loadtest)");
+    eprintln!(
+        "  METRIC_NAMESPACE_RUN_ID - Suffix the metric namespace with RUN_ID (default: false)"
+    );
+    eprintln!("  METRIC_LABELS           - Constant labels on every metric, e.g. env:staging,team:payments");
+    eprintln!("  METRICS_ADDR            - Metrics server bind address, host:port or port (default: 0.0.0.0:9090)");
+    eprintln!("  METRICS_TLS_CERT        - PEM certificate; with METRICS_TLS_KEY serves /metrics over HTTPS");
+    eprintln!("  METRICS_TLS_KEY         - PEM private key for METRICS_TLS_CERT");
+    eprintln!("  METRICS_BASIC_AUTH      - Require basic auth on /metrics: username:password");
+    eprintln!("  METRICS_BEARER_TOKEN    - Require Authorization: Bearer <token> on /metrics");
+    eprintln!("  RPS_DEVIATION_WARN_PERCENT - Warn when achieved RPS is this % below target (default: 10)");
+    eprintln!("  RPS_DEVIATION_WARN_SECS - ...for this many consecutive seconds (default: 10)");
+    eprintln!("  PLUGIN_PATHS            - Comma-separated WASM plugin files to load at startup");
+    eprintln!("                            (requires building with --features wasm-plugins)");
     eprintln!();
     eprintln!("Connection pool configuration:");
     eprintln!("  POOL_MAX_IDLE_PER_HOST  - Max idle connections per host (default: 32)");
     eprintln!("  POOL_IDLE_TIMEOUT_SECS  - Idle connection timeout in seconds (default: 30)");
+    eprintln!(
+        "  LOCAL_ADDRS             - Comma-separated source IPs; workers bind to them round-robin"
+    );
+    eprintln!(
+        "  DNS_ROUND_ROBIN         - Spread workers across all IPs the target resolves to (default: false)"
+    );
+    eprintln!("  DISCOVERY_REFRESH       - Re-resolve a consul:// or k8s:// TARGET_URL this often, e.g. 30s (default: once)");
+    eprintln!("  IP_FAMILY               - Address family for connections: v4, v6 or auto (default: auto)");
+    eprintln!("  RAISE_FD_LIMIT          - Raise the open file soft limit to fit NUM_CONCURRENT_TASKS (default: false)");
     eprintln!(
         "  TCP_NODELAY             - Disable Nagle's algorithm for lower latency (default: true)"
     );
@@ -396,9 +1134,10 @@ fn print_config_help() {
     eprintln!(
         "  CLUSTER_NODE_ID         - Stable node identity for metrics labels (default: $HOSTNAME)"
     );
-    eprintln!("  CLUSTER_REGION          - Geographic region label for metrics (default: local)");
+    eprintln!("  CLUSTER_REGION          - Geographic region label for metrics; REGION also accepted (default: local)");
+    eprintln!("  CLUSTER_ZONE            - Availability zone label for metrics; ZONE also accepted (default: local)");
     eprintln!(
-        "  CLUSTER_HEALTH_ADDR     - Health/config HTTP listen address (default: 0.0.0.0:8080)"
+        "  CLUSTER_HEALTH_ADDR     - Health/config HTTP listen address, [v6]:port or port (default: 0.0.0.0:8080)"
     );
     eprintln!("  API_AUTH_TOKEN          - Bearer token required on POST /config and POST /stop");
     eprintln!("                            (optional; when unset, endpoints are open)");
@@ -428,13 +1167,83 @@ fn print_config_help() {
     eprintln!("                            final totals.  (default: 60s)");
     eprintln!("    GET  /ready           - Returns {{\"ready\":true}} — no auth (Nomad/K8s probe)");
     eprintln!("    GET  /health          - Returns JSON with live node metrics");
+    eprintln!("    GET  /ui              - Web UI: live charts, pause/resume/stop, submit config");
     eprintln!("    POST /config          - Accepts a YAML config body to reconfigure workers");
     eprintln!("    POST /stop            - Stops all workers and transitions node to idle");
     eprintln!();
+    eprintln!("Smoke mode:");
+    eprintln!(
+        "  MODE                    - load (default) or smoke: run each scenario once and exit"
+    );
+    eprintln!("  SMOKE_PLAN              - YAML plan whose scenarios MODE=smoke runs (default: TARGET_URL)");
+    eprintln!();
     eprintln!("Logging configuration:");
     eprintln!("  RUST_LOG                - Log level: error, warn, info, debug, trace");
     eprintln!("                            Examples: RUST_LOG=info, RUST_LOG=rust_loadtest=debug");
     eprintln!("  LOG_FORMAT              - Output format: json or default (human-readable)");
+    eprintln!("  DEBUG_SAMPLE_RATE       - Fraction of requests (0.0-1.0) logged in full with");
+    eprintln!("                            auth headers redacted (default: 0 — disabled)");
+    eprintln!("  DEBUG_SAMPLE_MAX_PER_SEC - Max debug samples logged per second (default: 1)");
+    eprintln!(
+        "  REDACT_FIELDS           - Extra field-name regexes to mask in logs (comma-separated)"
+    );
+    eprintln!("  REDACT_JSON_PATHS       - JSONPaths to mask in captured bodies (';'-separated)");
+    eprintln!(
+        "  REDACT_PATTERNS         - Regexes masked in captured bodies and failures (';'-separated)"
+    );
+    eprintln!("  METRIC_TAG_KEYS         - Scenario/step tag keys exported as metric labels");
+    eprintln!(
+        "  METRICS_FLUSH_INTERVAL_MS - Worker-local request metric flush interval (default: 250)"
+    );
+    eprintln!("  ENGINE                  - reqwest (default) or raw: pipelined HTTP/1.1, http:// only (raw-engine feature)");
+    eprintln!("  RAW_PIPELINE_DEPTH      - Requests in flight per connection with ENGINE=raw (default: 1)");
+    eprintln!("  RUNNER_PROCESSES        - Split the load across N runner processes, or auto (default: off)");
+    eprintln!("  RUNNER_PIN_CORES        - Pin runner processes to disjoint cores on Linux (default: true)");
+    eprintln!("  RUN_ID                  - Run identifier for metrics and reports (default: run-<unix time>)");
+    eprintln!("  RUN_ID_HEADER           - Send the run ID as X-Load-Test-Run (default: false)");
+    eprintln!(
+        "  SEED                    - Seed all randomness (think times, jitter, scenario selection)"
+    );
+    eprintln!("                            for a reproducible run; recorded in the run summary");
+    eprintln!("  JUNIT_PATH              - Write thresholds and per-scenario assertions as JUnit XML at test end");
+    eprintln!("  ARTIFACT_URI            - Upload summary.json, report.html and results.jsonl at test end");
+    eprintln!("                            to s3://bucket/prefix/ or gs://bucket/prefix/ (under <run id>/)");
+    eprintln!("                            S3: AWS_ACCESS_KEY_ID, AWS_SECRET_ACCESS_KEY, AWS_SESSION_TOKEN,");
+    eprintln!("                            AWS_REGION, AWS_ENDPOINT_URL; GCS: GOOGLE_OAUTH_ACCESS_TOKEN or GCE metadata");
+    eprintln!("  NOTIFY_WEBHOOK_URL      - Post a pass/fail summary to this Slack or webhook URL at test end");
+    eprintln!("  NOTIFY_WEBHOOK_FORMAT   - slack (text only) or json (text + summary) (default: slack for hooks.slack.com)");
+    eprintln!("  NOTIFY_ON               - always or failure (default: always)");
+    eprintln!("  CI_REPORT               - github or gitlab: post a commit status and a PR/MR comment at test end");
+    eprintln!("                            GitHub: GITHUB_TOKEN, GITHUB_REPOSITORY, GITHUB_SHA, GITHUB_REF or CI_PR_NUMBER");
+    eprintln!("                            GitLab: GITLAB_TOKEN, CI_API_V4_URL, CI_PROJECT_ID, CI_COMMIT_SHA, CI_MERGE_REQUEST_IID");
+    eprintln!("  CI_STATUS_CONTEXT       - Commit status name (default: load-test)");
+    eprintln!();
+    eprintln!("Hard caps (override the load model):");
+    eprintln!("  MAX_TOTAL_REQUESTS      - Stop after this many requests (default: 0 — unlimited)");
+    eprintln!(
+        "  HARD_MAX_RPS            - Never exceed this many requests/sec (default: 0 — no cap)"
+    );
+    eprintln!(
+        "  MAX_IN_FLIGHT           - Never have more requests awaiting a response (default: 0 — no cap)"
+    );
+    eprintln!(
+        "  BACKPRESSURE            - When MAX_IN_FLIGHT is full: queue (default), shed or degrade"
+    );
+    eprintln!(
+        "  START_AT                - Start load at this instant (RFC 3339 or Unix ms) on every node"
+    );
+    eprintln!();
+    eprintln!("Target safety guard:");
+    eprintln!("  TARGET_ALLOWLIST        - Only these hosts/wildcards/CIDRs may be targeted");
+    eprintln!("  TARGET_DENYLIST         - These hosts/wildcards/CIDRs are always refused");
+    eprintln!("  PROTECTED_TARGETS       - Require I_UNDERSTAND_THIS_SENDS_LOAD=true");
+    eprintln!();
+    eprintln!("Chaos fault injection (off unless CHAOS_ENABLED=true):");
+    eprintln!("  CHAOS_DROP_PERCENT            - % of requests dropped before sending");
+    eprintln!("  CHAOS_CORRUPT_HEADERS_PERCENT - % of requests with garbage Content-Type/Accept");
+    eprintln!("  CHAOS_OVERSIZED_BODY_PERCENT  - % of requests sent with an oversized body");
+    eprintln!("  CHAOS_OVERSIZED_BODY_BYTES    - Oversized body size (default: 10485760)");
+    eprintln!("  CHAOS_ABORT_RESPONSE_PERCENT  - % of responses aborted mid-body");
 }
 
 /// Live per-node metrics exposed on the health endpoint.
@@ -485,6 +1294,7 @@ struct StandbyRunConfig {
     percentile_tracking_enabled: bool,
     percentile_sampling_rate: u8,
     region: String,
+    zone: String,
     node_id: String,
 }
 
@@ -503,6 +1313,8 @@ struct TestState {
     /// startup and reset on each POST /config from `metadata.run_id` or a new
     /// Unix-timestamp value.
     run_id: String,
+    /// Load model of the active run, used to publish the intended RPS.
+    load_model: LoadModel,
 }
 
 /// Returns the current Unix timestamp in seconds.
@@ -608,9 +1420,13 @@ fn spawn_completion_watcher(
                     percentile_tracking_enabled: sb.percentile_tracking_enabled,
                     percentile_sampling_rate: sb.percentile_sampling_rate,
                     region: sb.region.clone(),
+                    zone: sb.zone.clone(),
                     tenant: String::new(), // standby mode has no tenant
                     node_id: sb.node_id.clone(),
                     run_id: String::new(), // standby mode has no run_id
+                    pacing: WorkerPacing::default(),
+                    target_addr: None,
+                    templates: None,
                     stop_rx: new_stop_rx.clone(),
                 };
                 tokio::spawn(run_worker(client.clone(), wc, new_start))
@@ -628,6 +1444,9 @@ fn spawn_completion_watcher(
             if ts.generation == generation {
                 ts.node_state = "standby";
                 ts.yaml = None;
+                ts.load_model = LoadModel::Rps {
+                    target_rps: standby_rps,
+                };
                 true
             } else {
                 false
@@ -649,21 +1468,82 @@ struct WorkerPool {
     handles: Vec<tokio::task::JoinHandle<()>>,
 }
 
+/// Logs how the run ended and converts it to the process exit code. In JSON
+/// mode the `exit` report event also carries the full code mapping.
+fn finish(result: Result<(), AppError>) -> ExitCode {
+    let (code, kind, message) = match &result {
+        Ok(()) => (EXIT_SUCCESS, "success", String::new()),
+        Err(e) => (e.exit_code(), e.kind(), e.to_string()),
+    };
+    if let Err(e) = &result {
+        error!(error = %e, exit_code = code, "Exiting");
+    }
+    if logging::json_output() {
+        info!(
+            report = "exit",
+            exit_code = code,
+            kind,
+            message = %message,
+            exit_codes = %app_error::exit_codes_json(),
+            "Exit status"
+        );
+    }
+    ExitCode::from(code)
+}
+
 #[tokio::main]
-async fn main() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+async fn main() -> ExitCode {
+    finish(run().await)
+}
+
+async fn run() -> Result<(), AppError> {
     // ── Subcommand dispatch ────────────────────────────────────────────────────
     let args: Vec<String> = std::env::args().collect();
     if args.get(1).map(|s| s.as_str()) == Some("migrate") {
-        run_migrate(&args[2..]);
-        // run_migrate always exits; this is unreachable but satisfies the compiler.
-        return Ok(());
+        return run_migrate(&args[2..]);
+    }
+    if args.get(1).map(|s| s.as_str()) == Some("import") {
+        return run_import(&args[2..]);
+    }
+    if args.get(1).map(|s| s.as_str()) == Some("validate") {
+        return run_validate(&args[2..]);
+    }
+
+    // Initialize tracing subscriber (RUST_LOG filtering, LOG_FORMAT=json)
+    logging::init(LogFormat::from_env());
+
+    // RUNNER_PROCESSES=N: supervise N runner processes instead of running
+    // the load in this one.
+    #[cfg(feature = "supervisor")]
+    match rust_loadtest::supervisor::processes_from_env() {
+        Ok(Some(processes)) => return rust_loadtest::supervisor::run(processes).await,
+        Ok(None) => {}
+        Err(e) => return Err(AppError::Config(e)),
+    }
+    #[cfg(not(feature = "supervisor"))]
+    if std::env::var("RUNNER_PROCESSES").is_ok_and(|v| !matches!(v.trim(), "" | "0" | "1")) {
+        return Err(AppError::Config(
+            "RUNNER_PROCESSES requires building with --features supervisor".to_string(),
+        ));
     }
 
-    // Initialize tracing subscriber
-    init_tracing();
+    // SEED: reproducible think times, jitter, scenario selection, ...
+    let seed = seed::from_env().map_err(|e| AppError::Config(e.to_string()))?;
+    seed::init(seed);
+    if let Some(seed) = seed {
+        info!(seed, "Seeded randomness (SEED)");
+    }
 
     // Register Prometheus metrics
-    register_metrics()?;
+    register_metrics()
+        .map_err(|e| AppError::Internal(format!("failed to register metrics: {}", e)))?;
+
+    // Load custom protocol/assertion/data-source plugins (PLUGIN_PATHS).
+    match rust_loadtest::plugin::load_plugins_from_env() {
+        Ok(0) => {}
+        Ok(loaded) => info!(plugins = loaded, "Loaded plugins"),
+        Err(e) => return Err(AppError::Config(format!("failed to load plugins: {}", e))),
+    }
 
     // ── Ephemeral-node config ──────────────────────────────────────────────────
     // EPHEMERAL=true: node starts in "ready" state, skips startup workers, and
@@ -692,24 +1572,155 @@ async fn main() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
         std::env::set_var("TARGET_URL", "http://localhost");
     }
 
-    // Load configuration from environment variables
-    let config = match Config::from_env() {
-        Ok(c) => c,
-        Err(e) => {
-            error!(error = %e, "Configuration error");
-            eprintln!("Configuration error: {}\n", e);
-            print_config_help();
-            std::process::exit(1);
-        }
-    };
-
-    // Build HTTP client with TLS and header configuration
-    let client_config = config.to_client_config();
-    let client_result = build_client(&client_config)?;
-    let client = client_result.client;
+    // MODE=smoke can take its scenarios from a YAML plan (SMOKE_PLAN); its
+    // baseUrl is the target unless TARGET_URL overrides it.
+    let smoke_plan = match std::env::var("SMOKE_PLAN") {
+        Ok(path) => match YamlConfig::from_file(&path) {
+            Ok(plan) => {
+                rust_loadtest::lint::log_warnings(&plan);
+                Some(plan)
+            }
+            Err(e) => {
+                return Err(AppError::Config(format!(
+                    "failed to load SMOKE_PLAN {}: {}",
+                    path, e
+                )))
+            }
+        },
+        Err(_) => None,
+    };
+    if let Some(plan) = &smoke_plan {
+        if std::env::var("TARGET_URL").is_err() {
+            // Safety: single-threaded at this point — no other threads started yet.
+            #[allow(deprecated)]
+            std::env::set_var("TARGET_URL", &plan.config.base_url);
+        }
+    }
+
+    // Load configuration from environment variables
+    let config = match Config::from_env() {
+        Ok(c) => rust_loadtest::runner::split_config(c),
+        Err(e) => {
+            // The variable reference is for humans at a terminal; keep it out
+            // of machine-parsed JSON logs.
+            if !logging::json_output() {
+                print_config_help();
+            }
+            return Err(AppError::Config(e.to_string()));
+        }
+    };
+
+    // ENGINE=raw swaps reqwest for the raw HTTP/1.1 engine in the startup
+    // workers; settings it can't honour fail here rather than being ignored.
+    let engine = Engine::from_env()
+        .and_then(|engine| engine.check(&config, ephemeral).map(|_| engine))
+        .map_err(|e| AppError::Config(e.to_string()))?;
+
+    // Re-resolve hostnames periodically, retiring stale connections (DNS_REFRESH_INTERVAL)
+    dns_refresh::init(
+        !config.dns_refresh_interval.is_zero(),
+        config.num_concurrent_tasks,
+    );
+    if !config.dns_refresh_interval.is_zero() {
+        dns_refresh::spawn_dns_refresh(config.dns_refresh_interval);
+    }
+
+    // Build HTTP client with TLS and header configuration
+    let client_config = config.to_client_config();
+    let client_result = build_client(&client_config)
+        .map_err(|e| AppError::Config(format!("failed to build HTTP client: {}", e)))?;
+    let client = client_result.client;
+    tcp_check::init(&client_config, &config.safety);
+    // Scenario steps render the CUSTOM_HEADERS layer per request (with
+    // substitution); the single-URL workers send it as client defaults.
+    header_layers::set_global_headers(
+        HeaderLayer::from_custom_headers(config.custom_headers.as_deref())
+            .map_err(AppError::Config)?,
+    );
+    dns_round_robin::init(config.dns_round_robin);
+    golden::init(config.golden_record);
+    let resolved_target = if ephemeral {
+        None
+    } else {
+        dns_round_robin::resolve_target(&config).await
+    };
+    let worker_clients = SourceAddrClients::build(
+        &client_config,
+        &config.local_addrs,
+        resolved_target.as_ref(),
+        client.clone(),
+    )
+    .map_err(|e| AppError::Config(format!("failed to build worker clients: {}", e)))?;
+
+    // Print configuration summary
+    config.print_summary(&client_result.parsed_headers);
+
+    // Weighted request templates (TEMPLATE_DIR)
+    let templates = match config.template_dir.as_deref().map(TemplateMix::load_dir) {
+        Some(Ok(mix)) => Some(Arc::new(mix)),
+        Some(Err(e)) => {
+            return Err(AppError::Config(format!(
+                "failed to load TEMPLATE_DIR: {}",
+                e
+            )))
+        }
+        None => None,
+    };
+
+    // Refuse denied, non-allowed or unconfirmed protected targets before any load is sent.
+    // Ephemeral nodes get their real target from POST /config, checked by the watcher.
+    if !ephemeral {
+        if let Err(e) = config
+            .safety
+            .check_targets(
+                &config.target_url,
+                &client_config,
+                &[],
+                templates.as_deref(),
+            )
+            .await
+        {
+            return Err(AppError::Config(format!(
+                "target refused by safety guard: {}",
+                e
+            )));
+        }
+    }
+
+    // consul:// and k8s:// targets: find the instances before any load is sent.
+    if !ephemeral {
+        service_discovery::start(&config.target_url, config.discovery_refresh, &config.safety)
+            .await
+            .map_err(|e| AppError::Config(format!("service discovery failed: {}", e)))?;
+    }
+
+    // DNS, TCP, TLS and one HEAD request: fail fast on an unreachable target.
+    if config.target_precheck
+        && !ephemeral
+        && !service_discovery::is_discovery_url(&config.target_url)
+    {
+        let report = precheck::check(&config.target_url, &client_config, &client)
+            .await
+            .map_err(|e| AppError::TargetUnreachable(format!("pre-check failed: {}", e)))?;
+        info!(
+            url = %report.url,
+            addr = %report.addr,
+            dns_ms = report.dns.as_millis() as u64,
+            connect_ms = report.connect.as_millis() as u64,
+            tls_ms = report.tls.map(|d| d.as_millis() as u64),
+            status = report.status,
+            http_ms = report.http.as_millis() as u64,
+            "Target pre-check passed"
+        );
+    }
+
+    // MODE=smoke: run each scenario once and exit instead of generating load.
+    if config.mode == RunMode::Smoke {
+        return run_smoke(&config, &client_config, smoke_plan.as_ref()).await;
+    }
 
-    // Print configuration summary
-    config.print_summary(&client_result.parsed_headers);
+    // Check open file descriptor and ephemeral port limits against concurrency.
+    fd_limits::preflight(config.num_concurrent_tasks, fd_limits::raise_requested());
 
     // Startup standby config: fallback when a test YAML has no `standby:` block.
     // Nodes auto-revert to their startup state (typically TARGET_RPS=0) after a test ends.
@@ -723,22 +1734,51 @@ async fn main() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
         percentile_tracking_enabled: config.percentile_tracking_enabled,
         percentile_sampling_rate: config.percentile_sampling_rate,
         region: config.cluster.region.clone(),
+        zone: config.cluster.zone.clone(),
         node_id: config.cluster.node_id.clone(),
     });
 
     // Start the Prometheus metrics HTTP server
-    let metrics_port = 9090;
-    let registry_arc = Arc::new(Mutex::new(prometheus::default_registry().clone()));
+    let metrics_config = match MetricsServerConfig::from_env() {
+        Ok(c) => c,
+        Err(e) => {
+            return Err(AppError::Config(format!(
+                "metrics server configuration: {}",
+                e
+            )))
+        }
+    };
+    let registry_arc = Arc::new(Mutex::new(rust_loadtest::metrics::REGISTRY.clone()));
+
+    // Object store for the end-of-test artifacts (ARTIFACT_URI)
+    #[cfg(feature = "artifacts")]
+    let artifact_store = ArtifactStore::from_env()
+        .map_err(|e| AppError::Config(format!("artifact upload: {}", e)))?;
+    #[cfg(not(feature = "artifacts"))]
+    if std::env::var("ARTIFACT_URI").is_ok_and(|v| !v.trim().is_empty()) {
+        return Err(AppError::Config(
+            "ARTIFACT_URI requires building with --features artifacts".to_string(),
+        ));
+    }
+    let notifier = WebhookNotifier::from_env()
+        .map_err(|e| AppError::Config(format!("completion notification: {}", e)))?;
+    let ci_reporter =
+        CiReporter::from_env().map_err(|e| AppError::Config(format!("CI report: {}", e)))?;
 
     {
         let registry = registry_arc.clone();
+        let metrics_config = metrics_config.clone();
         tokio::spawn(async move {
-            start_metrics_server(metrics_port, registry).await;
+            if let Err(e) = start_metrics_server(metrics_config, registry).await {
+                error!(error = %e, "Metrics server failed");
+            }
         });
     }
 
     info!(
-        metrics_port = metrics_port,
+        metrics_addr = %metrics_config.addr,
+        tls = metrics_config.tls_enabled(),
+        auth = metrics_config.auth.is_some(),
         "Prometheus metrics server started"
     );
 
@@ -747,6 +1787,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
         .with_label_values(&[
             &config.cluster.node_id,
             &config.cluster.region,
+            &config.cluster.zone,
             "standalone",
         ])
         .set(1.0);
@@ -786,27 +1827,33 @@ async fn main() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
         } else {
             Some(startup_tenant.clone())
         },
-        run_id: format!("run-{}", unix_now()),
+        run_id: config.run_id.clone().unwrap_or_else(run_id::generate),
+        load_model: config.load_model.clone(),
     }));
 
     // ── Standalone health + config HTTP server ─────────────────────────────
     // GET  /ready   → {"ready":true}  (no auth — safe for Nomad health checks)
     // GET  /health  → JSON with node identity and live metrics
     //                 (requires Bearer token when HEALTH_AUTH_ENABLED=true)
+    // GET  /live    → WebSocket stream of per-second stats and recent failures
+    //                 (same auth as /health; `?token=` accepted)
+    // GET  /ui      → embedded web UI (no auth — the page sends the token)
+    // GET  /load-curve → target RPS curve of the active load model (same auth as /health)
     // POST /config  → accept YAML body, apply new config, restart workers
     // POST /stop    → stop active test workers
     {
         let health_addr =
             std::env::var("CLUSTER_HEALTH_ADDR").unwrap_or_else(|_| "0.0.0.0:8080".to_string());
-        let addr: std::net::SocketAddr = health_addr.parse().unwrap_or_else(|_| {
-            error!(addr = %health_addr, "Invalid CLUSTER_HEALTH_ADDR, using 0.0.0.0:8080");
-            "0.0.0.0:8080".parse().unwrap()
+        let addr = addr::parse_listen_addr(&health_addr).unwrap_or_else(|e| {
+            error!(addr = %health_addr, error = %e, "Invalid CLUSTER_HEALTH_ADDR, using 0.0.0.0:8080");
+            std::net::SocketAddr::from(([0, 0, 0, 0], 8080))
         });
 
         let node_id_for_http = config.cluster.node_id.clone();
         let node_name_for_http =
             std::env::var("NODE_NAME").unwrap_or_else(|_| config.cluster.node_id.clone());
         let region_for_http = config.cluster.region.clone();
+        let zone_for_http = config.cluster.zone.clone();
         let live_metrics_for_http = live_metrics.clone();
         let config_tx_for_http = config_tx.clone();
         let worker_pool_for_http = worker_pool.clone();
@@ -822,6 +1869,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
                 let node_id = node_id_for_http.clone();
                 let node_name = node_name_for_http.clone();
                 let region = region_for_http.clone();
+                let zone = zone_for_http.clone();
                 let lm = live_metrics_for_http.clone();
                 let tx = config_tx_for_http.clone();
                 let wp = worker_pool_for_http.clone();
@@ -834,6 +1882,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
                         let node_id = node_id.clone();
                         let node_name = node_name.clone();
                         let region = region.clone();
+                        let zone = zone.clone();
                         let lm = lm.clone();
                         let tx = tx.clone();
                         let wp = wp.clone();
@@ -877,6 +1926,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
                                         "node_id": node_id,
                                         "node_name": node_name,
                                         "region": region,
+                                        "zone": zone,
                                         "ephemeral": ephemeral,
                                         "tenant": current_tenant,
                                         "run_id": current_run_id,
@@ -903,6 +1953,78 @@ async fn main() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
                                             .unwrap(),
                                     )
                                 }
+                                (&Method::GET, "/live") => {
+                                    if health_auth_enabled {
+                                        if let Some(ref t) = token {
+                                            // Browsers cannot set headers on a
+                                            // WebSocket, so `?token=` also works.
+                                            let auth = req
+                                                .headers()
+                                                .get("authorization")
+                                                .and_then(|v| v.to_str().ok())
+                                                .unwrap_or("");
+                                            let query_token = req.uri().query().and_then(|q| {
+                                                q.split('&')
+                                                    .find_map(|kv| kv.strip_prefix("token="))
+                                            });
+                                            if auth != format!("Bearer {}", t)
+                                                && query_token != Some(t.as_str())
+                                            {
+                                                return Ok(Response::builder()
+                                                    .status(StatusCode::UNAUTHORIZED)
+                                                    .body(Body::from("unauthorized"))
+                                                    .unwrap());
+                                            }
+                                        }
+                                    }
+                                    Ok::<_, Infallible>(live_stream::handle_upgrade(req))
+                                }
+                                // Static page; it asks for the token itself
+                                // and sends it with every API call.
+                                #[cfg(feature = "web-ui")]
+                                (&Method::GET, "/ui") => Ok::<_, Infallible>(
+                                    Response::builder()
+                                        .status(StatusCode::OK)
+                                        .header("Content-Type", "text/html; charset=utf-8")
+                                        .body(Body::from(web_ui::INDEX_HTML))
+                                        .unwrap(),
+                                ),
+                                #[cfg(feature = "web-ui")]
+                                (&Method::GET, "/load-curve") => {
+                                    if health_auth_enabled {
+                                        if let Some(ref t) = token {
+                                            let auth = req
+                                                .headers()
+                                                .get("authorization")
+                                                .and_then(|v| v.to_str().ok())
+                                                .unwrap_or("");
+                                            if auth != format!("Bearer {}", t) {
+                                                return Ok(Response::builder()
+                                                    .status(StatusCode::UNAUTHORIZED)
+                                                    .body(Body::from("unauthorized"))
+                                                    .unwrap());
+                                            }
+                                        }
+                                    }
+                                    let curve = {
+                                        let st = ts.lock().unwrap();
+                                        web_ui::LoadCurve::sample(
+                                            &st.load_model,
+                                            st.duration.as_secs_f64(),
+                                            st.start.elapsed().as_secs_f64(),
+                                            rps_override::get(),
+                                        )
+                                    };
+                                    Ok::<_, Infallible>(
+                                        Response::builder()
+                                            .status(StatusCode::OK)
+                                            .header("Content-Type", "application/json")
+                                            .body(Body::from(
+                                                serde_json::to_string(&curve).unwrap_or_default(),
+                                            ))
+                                            .unwrap(),
+                                    )
+                                }
                                 (&Method::POST, "/config") => {
                                     if let Some(ref t) = token {
                                         let auth = req
@@ -948,6 +2070,59 @@ async fn main() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
                                         ),
                                     }
                                 }
+                                (&Method::POST, "/store") => {
+                                    if let Some(ref t) = token {
+                                        let auth = req
+                                            .headers()
+                                            .get("authorization")
+                                            .and_then(|v| v.to_str().ok())
+                                            .unwrap_or("");
+                                        if auth != format!("Bearer {}", t) {
+                                            return Ok(Response::builder()
+                                                .status(StatusCode::UNAUTHORIZED)
+                                                .body(Body::from("unauthorized"))
+                                                .unwrap());
+                                        }
+                                    }
+                                    let body_bytes = hyper::body::to_bytes(req.into_body())
+                                        .await
+                                        .unwrap_or_default();
+                                    let (status, body) =
+                                        rust_loadtest::shared_state::handle_request(&body_bytes);
+                                    Ok::<_, Infallible>(
+                                        Response::builder()
+                                            .status(status)
+                                            .header("Content-Type", "application/json")
+                                            .body(Body::from(body))
+                                            .unwrap(),
+                                    )
+                                }
+                                (&Method::POST, "/rps") => {
+                                    if let Some(ref t) = token {
+                                        let auth = req
+                                            .headers()
+                                            .get("authorization")
+                                            .and_then(|v| v.to_str().ok())
+                                            .unwrap_or("");
+                                        if auth != format!("Bearer {}", t) {
+                                            return Ok(Response::builder()
+                                                .status(StatusCode::UNAUTHORIZED)
+                                                .body(Body::from("unauthorized"))
+                                                .unwrap());
+                                        }
+                                    }
+                                    let body_bytes = hyper::body::to_bytes(req.into_body())
+                                        .await
+                                        .unwrap_or_default();
+                                    let (status, body) = rps_override::handle_request(&body_bytes);
+                                    Ok::<_, Infallible>(
+                                        Response::builder()
+                                            .status(status)
+                                            .header("Content-Type", "application/json")
+                                            .body(Body::from(body))
+                                            .unwrap(),
+                                    )
+                                }
                                 (&Method::POST, "/stop") => {
                                     if let Some(ref t) = token {
                                         let auth = req
@@ -1075,6 +2250,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
         let pool_for_watcher = worker_pool.clone();
         let client_for_watcher = client.clone();
         let region_for_watcher = config.cluster.region.clone();
+        let zone_for_watcher = config.cluster.zone.clone();
         let node_id_for_watcher = config.cluster.node_id.clone();
         let test_state_for_watcher = test_state.clone();
         let startup_standby_for_watcher = startup_standby.clone();
@@ -1083,7 +2259,10 @@ async fn main() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
             while let Some(yaml) = config_rx.recv().await {
                 let (yaml_cfg_parsed, new_cfg) = match serde_yaml::from_str::<YamlConfig>(&yaml) {
                     Ok(yaml_cfg) => match Config::from_yaml(&yaml_cfg) {
-                        Ok(c) => (yaml_cfg, c),
+                        Ok(c) => {
+                            rust_loadtest::lint::log_warnings(&yaml_cfg);
+                            (yaml_cfg, rust_loadtest::runner::split_config(c))
+                        }
                         Err(e) => {
                             error!(error = %e, "Config YAML failed validation");
                             continue;
@@ -1095,6 +2274,43 @@ async fn main() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
                     }
                 };
 
+                let new_templates = match new_cfg.template_dir.as_deref().map(TemplateMix::load_dir)
+                {
+                    Some(Ok(mix)) => Some(Arc::new(mix)),
+                    Some(Err(e)) => {
+                        error!(error = %e, "Failed to load TEMPLATE_DIR — sending TARGET_URL only");
+                        None
+                    }
+                    None => None,
+                };
+
+                // Target guardrails: every target the new test would send traffic to.
+                // Scenarios that fail to build aren't run, so there's nothing to check.
+                let scenarios = yaml_cfg_parsed.to_scenarios().unwrap_or_default();
+                if let Err(e) = new_cfg
+                    .safety
+                    .check_targets(
+                        &new_cfg.target_url,
+                        &new_cfg.to_client_config(),
+                        &scenarios,
+                        new_templates.as_deref(),
+                    )
+                    .await
+                {
+                    error!(error = %e, "Config rejected by target safety guard");
+                    continue;
+                }
+                if let Err(e) = service_discovery::start(
+                    &new_cfg.target_url,
+                    new_cfg.discovery_refresh,
+                    &new_cfg.safety,
+                )
+                .await
+                {
+                    error!(error = %e, "Config rejected: service discovery failed");
+                    continue;
+                }
+
                 // Extract optional standby config from the YAML `standby:` block.
                 let standby_cfg = yaml_cfg_parsed.standby.as_ref().map(|sb| StandbyRunConfig {
                     workers: sb.workers,
@@ -1106,6 +2322,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
                     percentile_tracking_enabled: new_cfg.percentile_tracking_enabled,
                     percentile_sampling_rate: new_cfg.percentile_sampling_rate,
                     region: region_for_watcher.clone(),
+                    zone: zone_for_watcher.clone(),
                     node_id: node_id_for_watcher.clone(),
                 });
 
@@ -1141,62 +2358,143 @@ async fn main() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
                     GLOBAL_POOL_STATS.set_threshold_ms(threshold_ms);
                 }
                 GLOBAL_POOL_STATS.reset();
-
+                rust_loadtest::rate_budget::init(new_cfg.max_total_requests, new_cfg.hard_max_rps);
+                rust_loadtest::in_flight_limit::init(new_cfg.max_in_flight, new_cfg.backpressure);
+                rust_loadtest::response_body::init(new_cfg.max_body_buffer);
+                rust_loadtest::executor::clear_shared_session();
+                rust_loadtest::http_cache::clear();
+                rust_loadtest::rate_limit::clear();
+                rust_loadtest::shared_state::GLOBAL_STORE.clear();
+                match yaml_cfg_parsed.resolve_variables() {
+                    Ok(variables) => rust_loadtest::shared_state::set_global_variables(variables),
+                    Err(e) => {
+                        error!(error = %e, "Failed to resolve variables — running without them");
+                        rust_loadtest::shared_state::set_global_variables(Default::default());
+                    }
+                }
+                match HeaderLayer::from_custom_headers(new_cfg.custom_headers.as_deref()) {
+                    Ok(layer) => header_layers::set_global_headers(layer),
+                    Err(e) => {
+                        error!(error = %e, "Invalid customHeaders — running without them");
+                        header_layers::set_global_headers(HeaderLayer::default());
+                    }
+                }
+                client_profiles::set_client_profiles(yaml_cfg_parsed.client_profiles());
+                rust_loadtest::concurrency_limit::clear();
+                rust_loadtest::rendezvous::clear();
+                GLOBAL_APDEX.reset();
+                GLOBAL_LATENCY_BUDGET.reset();
+                GLOBAL_FAILURE_SIGNATURES.reset();
+                GLOBAL_CONCURRENCY.reset(time::Instant::now());
+                match yaml_cfg_parsed.scenario_thresholds() {
+                    Ok(thresholds) => scenario_slo::begin(thresholds),
+                    Err(e) => {
+                        error!(error = %e, "Invalid scenarioThresholds — not evaluating them");
+                        scenario_slo::begin(Vec::new());
+                    }
+                }
+                match yaml_cfg_parsed.latency_slas() {
+                    Ok(slas) => latency_sla::begin(slas),
+                    Err(e) => {
+                        error!(error = %e, "Invalid percentile assertions — not evaluating them");
+                        latency_sla::begin(Vec::new());
+                    }
+                }
                 // Rebuild HTTP client in case TLS/pool config changed.
-                let new_client =
-                    match rust_loadtest::client::build_client(&new_cfg.to_client_config()) {
-                        Ok(r) => r.client,
-                        Err(e) => {
-                            error!(
-                                error = %e,
-                                "Failed to build HTTP client for new config — reusing existing"
-                            );
-                            client_for_watcher.clone()
-                        }
-                    };
+                let new_client_config = new_cfg.to_client_config();
+                tcp_check::init(&new_client_config, &new_cfg.safety);
+                let new_client = match rust_loadtest::client::build_client(&new_client_config) {
+                    Ok(r) => r.client,
+                    Err(e) => {
+                        error!(
+                            error = %e,
+                            "Failed to build HTTP client for new config — reusing existing"
+                        );
+                        client_for_watcher.clone()
+                    }
+                };
+                dns_round_robin::init(new_cfg.dns_round_robin);
+                golden::init(new_cfg.golden_record);
+                let new_resolved_target = dns_round_robin::resolve_target(&new_cfg).await;
+                let new_worker_clients = SourceAddrClients::build(
+                    &new_client_config,
+                    &new_cfg.local_addrs,
+                    new_resolved_target.as_ref(),
+                    new_client.clone(),
+                )
+                .unwrap_or_else(|e| {
+                    error!(
+                        error = %e,
+                        "Failed to build per-address clients — using the default client"
+                    );
+                    SourceAddrClients::single(new_client.clone())
+                });
 
                 let (new_stop_tx, new_stop_rx) = watch::channel(false);
-                let new_start = time::Instant::now();
+                // With START_AT, every node that received this config starts
+                // its workers at the same instant.
+                let new_start = match new_cfg.start_at {
+                    Some(start_at) => rust_loadtest::start_barrier::wait(start_at).await,
+                    None => time::Instant::now(),
+                };
                 let new_tenant = yaml_cfg_parsed.metadata.tenant.clone();
                 let new_run_id = yaml_cfg_parsed
                     .metadata
                     .run_id
                     .clone()
-                    .unwrap_or_else(|| format!("run-{}", unix_now()));
+                    .unwrap_or_else(run_id::generate);
 
                 // If the YAML contains scenarios, use scenario workers; otherwise
                 // fall back to the legacy single-URL worker.
+                let mut run_duration = new_cfg.test_duration;
                 let new_handles: Vec<_> = if !yaml_cfg_parsed.scenarios.is_empty() {
-                    match yaml_cfg_parsed.to_scenarios() {
-                        Ok(scenarios) => {
+                    match yaml_cfg_parsed.to_phases() {
+                        Ok(phases) => {
+                            run_duration = rust_loadtest::phases::total_duration(&phases);
+                            warn_header_conflicts(phases.iter().flat_map(|p| &p.scenarios));
                             info!(
-                                scenario_count = scenarios.len(),
+                                scenario_count = yaml_cfg_parsed.scenarios.len(),
+                                phase_count = phases.len(),
                                 workers = new_cfg.num_concurrent_tasks,
                                 "Spawning scenario workers"
                             );
-                            let selector = ScenarioSelector::new(scenarios);
-                            (0..new_cfg.num_concurrent_tasks)
-                                .map(|i| {
-                                    let sc = ScenarioWorkerConfig {
-                                        task_id: i,
-                                        base_url: new_cfg.target_url.clone(),
-                                        scenario: selector.select().clone(),
-                                        test_duration: new_cfg.test_duration,
-                                        load_model: new_cfg.load_model.clone(),
-                                        num_concurrent_tasks: new_cfg.num_concurrent_tasks,
-                                        percentile_tracking_enabled: new_cfg
-                                            .percentile_tracking_enabled,
-                                        percentile_sampling_rate: new_cfg.percentile_sampling_rate,
-                                        region: region_for_watcher.clone(),
-                                        tenant: new_tenant.clone().unwrap_or_default(),
-                                        node_id: node_id_for_watcher.clone(),
-                                        run_id: new_run_id.clone(),
-                                        skip_tls_verify: new_cfg.skip_tls_verify,
-                                        resolve_target_addr: new_cfg.resolve_target_addr.clone(),
-                                    };
-                                    tokio::spawn(run_scenario_worker(sc, new_start))
-                                })
-                                .collect()
+                            let cfg = new_cfg.clone();
+                            let clients = new_worker_clients.clone();
+                            let region = region_for_watcher.clone();
+                            let zone = zone_for_watcher.clone();
+                            let tenant = new_tenant.clone().unwrap_or_default();
+                            let node_id = node_id_for_watcher.clone();
+                            let run_id = new_run_id.clone();
+                            let make_worker = move |i: usize, scenario| ScenarioWorkerConfig {
+                                task_id: i,
+                                base_url: cfg.target_url.clone(),
+                                scenario,
+                                test_duration: cfg.test_duration,
+                                load_model: cfg.load_model.clone(),
+                                num_concurrent_tasks: cfg.num_concurrent_tasks,
+                                percentile_tracking_enabled: cfg.percentile_tracking_enabled,
+                                percentile_sampling_rate: cfg.percentile_sampling_rate,
+                                region: region.clone(),
+                                zone: zone.clone(),
+                                tenant: tenant.clone(),
+                                node_id: node_id.clone(),
+                                run_id: run_id.clone(),
+                                skip_tls_verify: cfg.skip_tls_verify,
+                                resolve_target_addr: cfg.resolve_target_addr.clone(),
+                                local_address: cfg.local_addr_for_worker(i),
+                                target_addr: clients.target_addr_for_worker(i),
+                                ip_family: cfg.ip_family,
+                                pacing: cfg.worker_pacing(),
+                                tls_session_resumption: cfg.tls_session_resumption,
+                                sni_override: cfg.sni_override.clone(),
+                                iteration_budget: None,
+                            };
+                            vec![tokio::spawn(rust_loadtest::phases::run(
+                                phases,
+                                new_cfg.num_concurrent_tasks,
+                                new_start,
+                                make_worker,
+                            ))]
                         }
                         Err(e) => {
                             error!(error = %e, "Failed to build scenarios — falling back to single-URL mode");
@@ -1215,12 +2513,20 @@ async fn main() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
                                             .percentile_tracking_enabled,
                                         percentile_sampling_rate: new_cfg.percentile_sampling_rate,
                                         region: region_for_watcher.clone(),
+                                        zone: zone_for_watcher.clone(),
                                         tenant: new_tenant.clone().unwrap_or_default(),
                                         node_id: node_id_for_watcher.clone(),
                                         run_id: new_run_id.clone(),
+                                        pacing: new_cfg.worker_pacing(),
+                                        target_addr: new_worker_clients.target_addr_for_worker(i),
+                                        templates: new_templates.clone(),
                                         stop_rx: new_stop_rx.clone(),
                                     };
-                                    tokio::spawn(run_worker(new_client.clone(), wc, new_start))
+                                    tokio::spawn(run_worker(
+                                        new_worker_clients.for_worker(i),
+                                        wc,
+                                        new_start,
+                                    ))
                                 })
                                 .collect()
                         }
@@ -1240,12 +2546,20 @@ async fn main() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
                                 percentile_tracking_enabled: new_cfg.percentile_tracking_enabled,
                                 percentile_sampling_rate: new_cfg.percentile_sampling_rate,
                                 region: region_for_watcher.clone(),
+                                zone: zone_for_watcher.clone(),
                                 tenant: new_tenant.clone().unwrap_or_default(),
                                 node_id: node_id_for_watcher.clone(),
                                 run_id: new_run_id.clone(),
+                                pacing: new_cfg.worker_pacing(),
+                                target_addr: new_worker_clients.target_addr_for_worker(i),
+                                templates: new_templates.clone(),
                                 stop_rx: new_stop_rx.clone(),
                             };
-                            tokio::spawn(run_worker(new_client.clone(), wc, new_start))
+                            tokio::spawn(run_worker(
+                                new_worker_clients.for_worker(i),
+                                wc,
+                                new_start,
+                            ))
                         })
                         .collect()
                 };
@@ -1260,13 +2574,14 @@ async fn main() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
                     let mut ts = test_state_for_watcher.lock().unwrap();
                     ts.start = new_start;
                     ts.started_at_unix = unix_now();
-                    ts.duration = new_cfg.test_duration;
+                    ts.duration = run_duration;
                     ts.yaml = Some(yaml.clone());
                     ts.node_state = "running";
                     ts.generation += 1;
                     ts.standby = standby_cfg;
                     ts.tenant = new_tenant.clone();
                     ts.run_id = new_run_id.clone();
+                    ts.load_model = new_cfg.load_model.clone();
                     ts.generation
                 };
                 spawn_completion_watcher(
@@ -1275,7 +2590,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
                     new_client.clone(),
                     startup_standby_for_watcher.clone(),
                     new_gen,
-                    new_cfg.test_duration,
+                    run_duration,
                     ephemeral_for_watcher,
                 );
 
@@ -1297,6 +2612,29 @@ async fn main() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
         info!("Percentile tracking initialized but DISABLED via config");
     }
 
+    // Request/response debug sampling (DEBUG_SAMPLE_RATE)
+    rust_loadtest::debug_sample::init(config.debug_sample_rate, config.debug_sample_max_per_sec);
+
+    // Client-side fault injection (CHAOS_*), off unless explicitly enabled
+    rust_loadtest::chaos::init(config.chaos);
+
+    // X-Load-Test-Run request header (RUN_ID_HEADER)
+    run_id::init(config.run_id_header);
+
+    // Hard request caps (MAX_TOTAL_REQUESTS / HARD_MAX_RPS)
+    rust_loadtest::rate_budget::init(config.max_total_requests, config.hard_max_rps);
+
+    // Cap on requests in flight across all workers (MAX_IN_FLIGHT, BACKPRESSURE)
+    rust_loadtest::in_flight_limit::init(config.max_in_flight, config.backpressure);
+
+    // Response body buffering limit (MAX_BODY_BUFFER)
+    rust_loadtest::response_body::init(config.max_body_buffer);
+
+    // Manual target-RPS throttle (RPS_OVERRIDE_FILE; POST /rps on the control API)
+    if let Ok(path) = std::env::var("RPS_OVERRIDE_FILE") {
+        rps_override::spawn_file_watcher(path.into());
+    }
+
     // Spawn auto-OOM memory guard (Issue #72)
     if config.percentile_tracking_enabled {
         let memory_guard_config = MemoryGuardConfig {
@@ -1312,6 +2650,18 @@ async fn main() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
         info!("Memory guard not started - percentile tracking disabled via config");
     }
 
+    // Open/idle/new connection gauges sampled from the kernel (Linux only)
+    rust_loadtest::connection_pool::spawn_connection_sampler(Duration::from_secs(1));
+
+    // Time-weighted in-flight average for the Little's Law check
+    GLOBAL_CONCURRENCY.reset(time::Instant::now());
+    littles_law::spawn_concurrency_sampler();
+
+    // Pick up renewed mTLS certificates without restarting the run
+    if config.client_cert_path.is_some() && !config.client_cert_reload_interval.is_zero() {
+        rust_loadtest::tls::spawn_client_cert_watcher(config.client_cert_reload_interval);
+    }
+
     // Spawn memory monitoring task (Issue #69).
     // Also calls mi_collect() every 30s to return mimalloc arena pages to the
     // OS — without this, mimalloc retains freed pages as allocator caches which
@@ -1342,11 +2692,18 @@ async fn main() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
         let live_metrics_for_updater = live_metrics.clone();
         let test_state_for_updater = test_state.clone();
         let region = config.cluster.region.clone();
+        let zone = config.cluster.zone.clone();
         let node_id_for_updater = config.cluster.node_id.clone();
+        let mut saturation = SaturationDetector::new(SaturationConfig::from_env());
+        let mut abort_monitor = AbortMonitor::new();
+        let worker_pool_for_updater = worker_pool.clone();
+        let client_for_updater = client.clone();
+        let startup_standby_for_updater = startup_standby.clone();
         tokio::spawn(async move {
             let mut interval = time::interval(Duration::from_secs(1));
             let mut prev_requests: u64 = 0;
             let mut prev_errors: u64 = 0;
+            let mut prev_latency = (0.0_f64, 0_u64);
             let mut prev_run_id: String = String::new();
             // CPU tracking (Linux only) — tracks utime+stime jiffies
             #[cfg(target_os = "linux")]
@@ -1365,10 +2722,19 @@ async fn main() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
                 if run_id_str != prev_run_id {
                     prev_requests = 0;
                     prev_errors = 0;
+                    prev_latency = (0.0, 0);
                     prev_run_id = run_id_str.clone();
+                    saturation.reset();
+                    abort_monitor.reset();
                 }
                 let curr_requests = REQUEST_TOTAL
-                    .with_label_values(&[&region, &tenant_str, &node_id_for_updater, &run_id_str])
+                    .with_label_values(&[
+                        &region,
+                        &zone,
+                        &tenant_str,
+                        &node_id_for_updater,
+                        &run_id_str,
+                    ])
                     .get();
 
                 // ── Error counter: sum all known categories ───────────────
@@ -1379,6 +2745,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
                             .with_label_values(&[
                                 cat.label(),
                                 &region,
+                                &zone,
                                 &tenant_str,
                                 &node_id_for_updater,
                                 &run_id_str,
@@ -1387,6 +2754,20 @@ async fn main() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
                     })
                     .sum();
 
+                // ── Mean latency over the last second (for /live) ────────
+                let latency = REQUEST_DURATION_SECONDS.with_label_values(&[
+                    &region,
+                    &zone,
+                    &tenant_str,
+                    &node_id_for_updater,
+                    &run_id_str,
+                ]);
+                let curr_latency = (latency.get_sample_sum(), latency.get_sample_count());
+                let delta_latency_count = curr_latency.1.saturating_sub(prev_latency.1);
+                let mean_latency_ms = (delta_latency_count > 0).then(|| {
+                    (curr_latency.0 - prev_latency.0) / delta_latency_count as f64 * 1000.0
+                });
+
                 let delta_req = curr_requests.saturating_sub(prev_requests);
                 let delta_err = curr_errors.saturating_sub(prev_errors);
                 let rps = delta_req as f64;
@@ -1396,6 +2777,117 @@ async fn main() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
                     0.0
                 };
 
+                // ── Intended vs achieved RPS (generator saturation) ──────
+                // The first second after a run change has no baseline, so it
+                // is skipped rather than reported as a shortfall.
+                let intended_rps = {
+                    let ts = test_state_for_updater.lock().unwrap();
+                    match ts.node_state {
+                        "running" | "standby" => rps_override::target_rps(
+                            &ts.load_model,
+                            ts.start.elapsed().as_secs_f64(),
+                            ts.duration.as_secs_f64(),
+                        ),
+                        _ => 0.0,
+                    }
+                };
+                ACHIEVED_RPS.set(rps);
+                if intended_rps.is_finite() {
+                    INTENDED_RPS.set(intended_rps);
+                }
+                if prev_requests > 0 {
+                    if let Some(shortfall) =
+                        saturation.observe(intended_rps, rps, time::Instant::now())
+                    {
+                        RPS_SHORTFALL_WARNINGS_TOTAL.inc();
+                        warn!(
+                            intended_rps = shortfall.intended_rps,
+                            achieved_rps = shortfall.achieved_rps,
+                            deviation_percent = shortfall.deviation_percent,
+                            duration_secs = shortfall.duration.as_secs(),
+                            "Achieved RPS is below target — the target service or this \
+                             generator (CPU, workers, connections) is the bottleneck"
+                        );
+                    }
+                }
+
+                // ── Abort thresholds (Exponential load model) ───────────
+                // Stops the run once the error rate or latency has stayed
+                // past the model's limit; the rate reached is the knee.
+                let abort = {
+                    let ts = test_state_for_updater.lock().unwrap();
+                    match ts.load_model.abort_threshold() {
+                        Some(threshold) if ts.node_state == "running" => {
+                            Some((*threshold, ts.generation))
+                        }
+                        _ => None,
+                    }
+                };
+                match abort {
+                    Some((threshold, generation)) if prev_requests > 0 => {
+                        if let Some(reason) = abort_monitor.observe(
+                            &threshold,
+                            error_rate_pct,
+                            mean_latency_ms,
+                            time::Instant::now(),
+                        ) {
+                            warn!(
+                                reason = %reason,
+                                intended_rps,
+                                achieved_rps = rps,
+                                "Abort threshold reached — stopping the test"
+                            );
+                            let stop_generation = {
+                                let mut ts = test_state_for_updater.lock().unwrap();
+                                (ts.generation == generation).then(|| {
+                                    ts.generation += 1;
+                                    ts.generation
+                                })
+                            };
+                            if let Some(stop_generation) = stop_generation {
+                                spawn_completion_watcher(
+                                    test_state_for_updater.clone(),
+                                    worker_pool_for_updater.clone(),
+                                    client_for_updater.clone(),
+                                    startup_standby_for_updater.clone(),
+                                    stop_generation,
+                                    Duration::ZERO,
+                                    ephemeral,
+                                );
+                            }
+                        }
+                    }
+                    Some(_) => {}
+                    None => abort_monitor.reset(),
+                }
+
+                // ── Windowed percentile SLAs ─────────────────────────────
+                for sample in latency_sla::evaluate() {
+                    let sla = &sample.sla;
+                    let percentile = sla.percentile_label();
+                    let labels = [
+                        sla.scenario.as_str(),
+                        sla.step.as_str(),
+                        percentile.as_str(),
+                        node_id_for_updater.as_str(),
+                        run_id_str.as_str(),
+                    ];
+                    if let Some(value_ms) = sample.value_ms {
+                        LATENCY_SLA_PERCENTILE_SECONDS
+                            .with_label_values(&labels)
+                            .set(value_ms as f64 / 1000.0);
+                    }
+                    if sample.breach_started {
+                        LATENCY_SLA_BREACHES_TOTAL.with_label_values(&labels).inc();
+                        warn!(
+                            sla = %sla,
+                            value_ms = sample.value_ms,
+                            samples = sample.samples,
+                            "Latency SLA breached"
+                        );
+                    }
+                }
+
                 // ── Workers & memory ─────────────────────────────────────
                 let workers = WORKERS_CONFIGURED_TOTAL.get() as u32;
                 let memory_mb = PROCESS_MEMORY_RSS_BYTES.get() / (1024.0 * 1024.0);
@@ -1490,6 +2982,17 @@ async fn main() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
                     )
                 };
 
+                live_stream::publish(&live_stream::Tick {
+                    run_id: run_id_str.clone(),
+                    node_id: node_id_for_updater.clone(),
+                    node_state: node_state.clone(),
+                    rps,
+                    intended_rps: intended_rps.is_finite().then_some(intended_rps),
+                    error_rate_pct,
+                    mean_latency_ms,
+                    workers,
+                });
+
                 *live_metrics_for_updater.lock().unwrap() = NodeMetrics {
                     rps,
                     error_rate_pct,
@@ -1507,26 +3010,25 @@ async fn main() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
 
                 prev_requests = curr_requests;
                 prev_errors = curr_errors;
+                prev_latency = curr_latency;
             }
         });
     }
 
+    // HdrHistogram interval log, appended at each rotation (HDR_LOG_PATH)
+    if let Some(path) = &config.hdr_log_path {
+        hdr_log::open(path).map_err(|e| {
+            AppError::Config(format!(
+                "failed to create HDR_LOG_PATH {}: {}",
+                path.display(),
+                e
+            ))
+        })?;
+    }
+
     // Spawn histogram rotation task if enabled (Issue #67)
     if config.histogram_rotation_interval.as_secs() > 0 {
-        let rotation_interval = config.histogram_rotation_interval;
-        tokio::spawn(async move {
-            let mut interval = time::interval(rotation_interval);
-            interval.tick().await; // Skip the first immediate tick
-            loop {
-                interval.tick().await;
-                info!(
-                    rotation_interval_secs = rotation_interval.as_secs(),
-                    "Rotating histograms - clearing percentile data to free memory"
-                );
-                rotate_all_histograms();
-                info!("Histogram rotation complete - memory freed");
-            }
-        });
+        spawn_histogram_rotation(config.histogram_rotation_interval);
         info!(
             rotation_interval_secs = config.histogram_rotation_interval.as_secs(),
             "Histogram rotation enabled - will rotate every {} seconds",
@@ -1548,8 +3050,23 @@ async fn main() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
     WORKERS_CONFIGURED_TOTAL.set(config.num_concurrent_tasks as f64);
     PERCENTILE_SAMPLING_RATE_PERCENT.set(config.percentile_sampling_rate as f64);
 
-    // Main loop to run for a duration
-    let start_time = time::Instant::now();
+    // Open the connection pools before the test clock starts (WARMUP_CONNECTIONS)
+    if config.warmup_connections > 0 && !ephemeral {
+        warmup::run(
+            &worker_clients,
+            &config.target_url,
+            config.warmup_connections,
+        )
+        .await;
+    }
+
+    // Main loop to run for a duration; with START_AT, persistent nodes wait
+    // for the synchronized start (ephemeral nodes wait once their config
+    // arrives).
+    let start_time = match config.start_at {
+        Some(start_at) if !ephemeral => rust_loadtest::start_barrier::wait(start_at).await,
+        _ => time::Instant::now(),
+    };
 
     // ── Startup workers — persistent nodes only ────────────────────────────
     // Ephemeral nodes skip this block: they start in "ready" and wait for
@@ -1576,6 +3093,24 @@ async fn main() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
         info!("Ephemeral node ready — waiting for POST /config to start workers");
     }
 
+    #[cfg(feature = "raw-engine")]
+    let raw_target = match engine {
+        Engine::Raw => Some(Arc::new(
+            rust_loadtest::raw_engine::RawTarget::resolve(
+                &config.target_url,
+                &config.request_type,
+                config
+                    .send_json
+                    .then(|| config.json_payload.as_deref().unwrap_or_default()),
+            )
+            .await
+            .map_err(|e| AppError::Config(format!("ENGINE=raw: {}", e)))?,
+        )),
+        Engine::Reqwest => None,
+    };
+    #[cfg(not(feature = "raw-engine"))]
+    let _ = engine;
+
     let mut handles = Vec::new();
     if !ephemeral {
         for i in 0..config.num_concurrent_tasks {
@@ -1591,10 +3126,14 @@ async fn main() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
                 percentile_tracking_enabled: config.percentile_tracking_enabled,
                 percentile_sampling_rate: config.percentile_sampling_rate,
                 region: config.cluster.region.clone(),
+                zone: config.cluster.zone.clone(),
                 // Tenant from TENANT env var; overridden by metadata.tenant in POST /config.
                 tenant: startup_tenant.clone(),
                 node_id: config.cluster.node_id.clone(),
                 run_id: test_state.lock().unwrap().run_id.clone(),
+                pacing: config.worker_pacing(),
+                target_addr: worker_clients.target_addr_for_worker(i),
+                templates: templates.clone(),
                 // Graceful-stop signal (Issue #79). In cluster mode the
                 // config-watcher fires this before replacing the worker pool.
                 // In standalone mode it is never fired; workers self-terminate
@@ -1602,7 +3141,17 @@ async fn main() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
                 stop_rx: worker_stop_rx.clone(),
             };
 
-            let client_clone = client.clone();
+            #[cfg(feature = "raw-engine")]
+            if let Some(target) = &raw_target {
+                handles.push(tokio::spawn(rust_loadtest::raw_engine::run_raw_worker(
+                    target.clone(),
+                    worker_config,
+                    start_time,
+                )));
+                continue;
+            }
+
+            let client_clone = worker_clients.for_worker(i);
             let start_time_clone = start_time;
 
             let handle = tokio::spawn(async move {
@@ -1627,24 +3176,122 @@ async fn main() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
     // Brief pause to allow in-flight metrics to be updated
     tokio::time::sleep(Duration::from_secs(2)).await;
 
-    // Print percentile latency statistics (Issue #33, #66)
-    print_percentile_report(
-        config.percentile_tracking_enabled,
-        config.percentile_sampling_rate,
-    );
+    // Final reports run inside a span so every summary event carries run_id
+    let final_run_id = test_state.lock().unwrap().run_id.clone();
+    let slo_outcome = {
+        let _report_span = tracing::info_span!(
+            "report",
+            run_id = %final_run_id,
+            node_id = %config.cluster.node_id,
+            region = %config.cluster.region,
+            zone = %config.cluster.zone,
+        )
+        .entered();
+        info!(run_id = %final_run_id, "Final report for test run");
 
-    // Print per-scenario throughput statistics (Issue #35)
-    print_throughput_report();
+        // Close the HDR interval log with the interval still open
+        hdr_log::write_global_interval();
 
-    // Print connection pool statistics (Issue #36)
-    print_pool_report();
+        // Print percentile latency statistics (Issue #33, #66)
+        print_percentile_report(
+            config.percentile_tracking_enabled,
+            config.percentile_sampling_rate,
+        );
+
+        // Print the pre-test connection warm-up, kept out of the stats above
+        print_warmup_report();
+
+        // Print Apdex scores for steps with a response time SLO
+        print_apdex_report();
+
+        // Print where each scenario's time goes, step by step
+        print_latency_budget_report();
+
+        // Print the most frequent step failures grouped by signature
+        print_failure_signature_report();
+
+        // Check average concurrency against throughput × latency
+        print_littles_law_report();
+
+        // Print per-scenario success-rate thresholds and windowed percentile SLAs
+        let slo_outcome = print_scenario_slo_report();
+        let sla_outcome = print_latency_sla_report();
+
+        // Print per-scenario throughput statistics (Issue #35)
+        print_throughput_report();
+
+        // Print 429 counts and effective rate limits per endpoint
+        print_rate_limit_report();
+
+        // Print connection pool statistics (Issue #36)
+        print_pool_report();
+
+        slo_outcome.and(sla_outcome)
+    };
 
     // Gather and print final metrics
-    let final_metrics_output = gather_metrics_string(&registry_arc);
-    info!("\n--- FINAL METRICS ---\n{}", final_metrics_output);
-    info!("--- END OF FINAL METRICS ---");
+    // The full Prometheus text dump is only useful to a human reading the
+    // terminal; JSON log consumers scrape /metrics instead.
+    if !logging::json_output() {
+        let final_metrics_output = gather_metrics_string(&registry_arc);
+        info!("\n--- FINAL METRICS ---\n{}", final_metrics_output);
+        info!("--- END OF FINAL METRICS ---");
+    }
+
+    // Decided now: the counters are final, and SELF_DESTRUCT_CMD may end
+    // the process before it returns.
+    let (sent, responses) = request_outcome_totals();
+    let outcome = AppError::check_reachable(sent, responses).and(slo_outcome);
+    let summary = RunSummary::collect(&final_run_id, &config.cluster.node_id, &outcome);
+
+    // Thresholds and assertions as JUnit XML for CI test reports
+    if let Some(path) = std::env::var("JUNIT_PATH")
+        .ok()
+        .filter(|p| !p.trim().is_empty())
+    {
+        match junit::write(Path::new(&path), &summary) {
+            Ok(()) => info!(path = %path, "Wrote JUnit report"),
+            Err(e) => error!(path = %path, error = %e, "Failed to write JUnit report"),
+        }
+    }
+
+    // Push the summary, HTML report and JSONL results to object storage
+    #[cfg(feature = "artifacts")]
+    let report_url = match &artifact_store {
+        Some(store) => match store
+            .upload(&summary.run_id, &artifacts::render(&summary))
+            .await
+        {
+            Ok(_) => Some(store.url(&summary.run_id, "report.html")),
+            Err(e) => {
+                error!(error = %e, "Artifact upload failed");
+                None
+            }
+        },
+        None => None,
+    };
+    #[cfg(not(feature = "artifacts"))]
+    let report_url: Option<String> = None;
+
+    // Commit status and PR/MR comment (CI_REPORT)
+    if let Some(ci) = &ci_reporter {
+        if let Err(e) = ci.report(&summary, report_url.as_deref()).await {
+            error!(error = %e, "CI report failed");
+        }
+    }
+
+    // Post the compact summary to Slack or a webhook
+    if let Some(notifier) = &notifier {
+        if let Err(e) = notifier.notify(&summary, report_url.as_deref()).await {
+            error!(error = %e, "Completion notification failed");
+        }
+    }
 
     if ephemeral {
+        if let Err(e) = &outcome {
+            error!(error = %e, exit_code = e.exit_code(), "Test run failed");
+        }
+
         // Keep /metrics and /health alive for EPHEMERAL_FINAL_SCRAPE_DELAY so
         // GMP (or any Prometheus) can complete a final scrape of the test totals
         // before the instance is destroyed.
@@ -1666,6 +3313,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
         }
 
         info!("Ephemeral node — process exiting");
+        return outcome;
     } else {
         // Persistent nodes: keep the process alive in standby — workers, the
         // health API, and Prometheus remain active until stopped externally
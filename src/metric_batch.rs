@@ -0,0 +1,206 @@
+//! Worker-local batching of the per-request metrics.
+//!
+//! At very high request rates every worker incrementing the same
+//! `requests_total`, `requests_status_codes_total`, `request_errors_by_category`
+//! and `request_duration_seconds` atomics makes those cache lines bounce
+//! between cores. Each worker instead counts into a [`WorkerMetrics`] of
+//! local (non-atomic) counters and histograms and flushes them into the
+//! shared registry every `METRICS_FLUSH_INTERVAL_MS` (default 250 ms), before
+//! any sleep longer than that, and when the worker exits. Scrapes see the
+//! same counters and histograms, at most one interval behind; an interval of
+//! 0 flushes after every request. Step latencies for windowed percentile
+//! SLAs ([`crate::latency_sla`]) are buffered and flushed the same way.
+//!
+//! The `concurrent_requests` gauge is still updated directly, since it must
+//! reflect requests in flight right now.
+
+use std::env;
+use std::time::Duration;
+
+use prometheus::local::{LocalHistogram, LocalIntCounter, LocalIntCounterVec};
+use tokio::time::Instant;
+
+use crate::latency_sla::SlaBatch;
+use crate::metrics::{
+    REQUEST_DURATION_SECONDS, REQUEST_ERRORS_BY_CATEGORY, REQUEST_STATUS_CODES, REQUEST_TOTAL,
+};
+
+/// Default `METRICS_FLUSH_INTERVAL_MS`.
+pub const DEFAULT_FLUSH_INTERVAL: Duration = Duration::from_millis(250);
+
+lazy_static::lazy_static! {
+    /// How often workers flush their local metrics (`METRICS_FLUSH_INTERVAL_MS`).
+    pub static ref FLUSH_INTERVAL: Duration =
+        parse_flush_interval(env::var("METRICS_FLUSH_INTERVAL_MS").ok().as_deref());
+}
+
+/// Parses `METRICS_FLUSH_INTERVAL_MS`; unset or invalid means the default.
+pub fn parse_flush_interval(value: Option<&str>) -> Duration {
+    value
+        .and_then(|v| v.trim().parse::<u64>().ok())
+        .map_or(DEFAULT_FLUSH_INTERVAL, Duration::from_millis)
+}
+
+/// One worker's unflushed request metrics, labelled with its region, zone,
+/// tenant, node and run.
+pub struct WorkerMetrics {
+    labels: [String; 5],
+    requests: LocalIntCounter,
+    durations: LocalHistogram,
+    status_codes: LocalIntCounterVec,
+    errors: LocalIntCounterVec,
+    sla_latencies: SlaBatch,
+    interval: Duration,
+    last_flush: Instant,
+}
+
+impl WorkerMetrics {
+    /// Local metrics for a worker, flushed every [`FLUSH_INTERVAL`].
+    pub fn new(region: &str, zone: &str, tenant: &str, node_id: &str, run_id: &str) -> Self {
+        Self::with_interval(region, zone, tenant, node_id, run_id, *FLUSH_INTERVAL)
+    }
+
+    /// Local metrics for a worker, flushed every `interval`.
+    pub fn with_interval(
+        region: &str,
+        zone: &str,
+        tenant: &str,
+        node_id: &str,
+        run_id: &str,
+        interval: Duration,
+    ) -> Self {
+        let labels = [region, zone, tenant, node_id, run_id];
+        Self {
+            requests: REQUEST_TOTAL.with_label_values(&labels).local(),
+            durations: REQUEST_DURATION_SECONDS.with_label_values(&labels).local(),
+            status_codes: REQUEST_STATUS_CODES.local(),
+            errors: REQUEST_ERRORS_BY_CATEGORY.local(),
+            sla_latencies: SlaBatch::default(),
+            labels: labels.map(str::to_string),
+            interval,
+            last_flush: Instant::now(),
+        }
+    }
+
+    /// Count a request sent.
+    pub fn request(&mut self) {
+        self.requests.inc();
+    }
+
+    /// Count a response (or `"error"`) under its status code label.
+    pub fn status(&mut self, status_code: &str) {
+        let [region, zone, tenant, node_id, run_id] = &self.labels;
+        self.status_codes
+            .with_label_values(&[status_code, region, zone, tenant, node_id, run_id])
+            .inc();
+    }
+
+    /// Count a failed request under its error category label.
+    pub fn error(&mut self, category: &str) {
+        let [region, zone, tenant, node_id, run_id] = &self.labels;
+        self.errors
+            .with_label_values(&[category, region, zone, tenant, node_id, run_id])
+            .inc();
+    }
+
+    /// Record a request's latency.
+    pub fn duration(&mut self, seconds: f64) {
+        self.durations.observe(seconds);
+    }
+
+    /// Record a scenario step's latency for its percentile SLAs.
+    pub fn step_latency(&mut self, scenario: &str, step: &str, latency_ms: u64) {
+        self.sla_latencies.record(scenario, step, latency_ms);
+    }
+
+    /// Flush if the interval has passed since the last flush.
+    pub fn maybe_flush(&mut self) {
+        if self.last_flush.elapsed() >= self.interval {
+            self.flush();
+        }
+    }
+
+    /// Flush before sleeping until `wake_at` if that is more than an
+    /// interval away, so idle or slow workers don't hold back counts.
+    pub fn flush_before_sleep(&mut self, wake_at: Instant) {
+        if wake_at.saturating_duration_since(Instant::now()) > self.interval {
+            self.flush();
+        }
+    }
+
+    /// Add everything counted since the last flush to the shared metrics.
+    pub fn flush(&mut self) {
+        self.requests.flush();
+        self.durations.flush();
+        self.status_codes.flush();
+        self.errors.flush();
+        self.sla_latencies.flush();
+        self.last_flush = Instant::now();
+    }
+}
+
+impl Drop for WorkerMetrics {
+    fn drop(&mut self) {
+        self.flush();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn requests(run_id: &str) -> u64 {
+        REQUEST_TOTAL
+            .with_label_values(&["local", "", "", "batch-node", run_id])
+            .get()
+    }
+
+    #[test]
+    fn parses_flush_interval() {
+        assert_eq!(parse_flush_interval(None), DEFAULT_FLUSH_INTERVAL);
+        assert_eq!(parse_flush_interval(Some("0")), Duration::ZERO);
+        assert_eq!(parse_flush_interval(Some(" 1000 ")), Duration::from_secs(1));
+        assert_eq!(parse_flush_interval(Some("soon")), DEFAULT_FLUSH_INTERVAL);
+    }
+
+    #[test]
+    fn counts_are_published_on_flush_and_drop() {
+        let run_id = "batch-run-1";
+        let mut metrics = WorkerMetrics::with_interval(
+            "local",
+            "",
+            "",
+            "batch-node",
+            run_id,
+            Duration::from_secs(3600),
+        );
+        metrics.request();
+        metrics.request();
+        metrics.status("200");
+        metrics.maybe_flush();
+        assert_eq!(requests(run_id), 0, "held until the interval passes");
+
+        metrics.flush();
+        assert_eq!(requests(run_id), 2);
+        assert_eq!(
+            REQUEST_STATUS_CODES
+                .with_label_values(&["200", "local", "", "", "batch-node", run_id])
+                .get(),
+            1
+        );
+
+        metrics.request();
+        drop(metrics);
+        assert_eq!(requests(run_id), 3);
+    }
+
+    #[test]
+    fn zero_interval_flushes_every_time() {
+        let run_id = "batch-run-2";
+        let mut metrics =
+            WorkerMetrics::with_interval("local", "", "", "batch-node", run_id, Duration::ZERO);
+        metrics.request();
+        metrics.maybe_flush();
+        assert_eq!(requests(run_id), 1);
+    }
+}
@@ -1,15 +1,35 @@
+use base64::Engine;
+use hyper::server::conn::Http;
 use hyper::service::{make_service_fn, service_fn};
-use hyper::{Body, Request, Response, Server};
+use hyper::{Body, Method, Request, Response, Server, StatusCode};
 use prometheus::{
-    Encoder, Gauge, HistogramVec, IntCounter, IntCounterVec, Opts, Registry, TextEncoder,
+    Counter, Encoder, Gauge, HistogramVec, IntCounter, IntCounterVec, Opts, Registry, TextEncoder,
 };
+use std::collections::HashMap;
 use std::env;
+use std::net::SocketAddr;
 use std::sync::{Arc, Mutex};
-use tracing::{error, info};
+use thiserror::Error;
+use tracing::{debug, error, info};
 
 lazy_static::lazy_static! {
-    pub static ref METRIC_NAMESPACE: String =
-        env::var("METRIC_NAMESPACE").unwrap_or_else(|_| "rust_loadtest".to_string());
+    pub static ref METRIC_NAMESPACE: String = metric_namespace(
+        &env::var("METRIC_NAMESPACE").unwrap_or_else(|_| "rust_loadtest".to_string()),
+        env::var("METRIC_NAMESPACE_RUN_ID")
+            .is_ok_and(|v| v.eq_ignore_ascii_case("true"))
+            .then(|| env::var("RUN_ID").ok())
+            .flatten()
+            .as_deref(),
+    );
+
+    /// Constant labels (`METRIC_LABELS`) added to every metric.
+    pub static ref METRIC_LABELS: HashMap<String, String> = parse_metric_labels(
+        &env::var("METRIC_LABELS").unwrap_or_default(),
+        &METRIC_TAG_KEYS,
+    );
+
+    /// Registry of every metric below, labelled with `METRIC_LABELS`.
+    pub static ref REGISTRY: Registry = labelled_registry(METRIC_LABELS.clone());
 
     // === Single Request Metrics ===
 
@@ -17,21 +37,21 @@ lazy_static::lazy_static! {
         IntCounterVec::new(
             Opts::new("requests_total", "Total number of HTTP requests made")
                 .namespace(METRIC_NAMESPACE.as_str()),
-            &["region", "tenant", "node_id", "run_id"]
+            &["region", "zone", "tenant", "node_id", "run_id"]
         ).unwrap();
 
     pub static ref REQUEST_STATUS_CODES: IntCounterVec =
         IntCounterVec::new(
             Opts::new("requests_status_codes_total", "Number of HTTP requests by status code")
                 .namespace(METRIC_NAMESPACE.as_str()),
-            &["status_code", "region", "tenant", "node_id", "run_id"]
+            &["status_code", "region", "zone", "tenant", "node_id", "run_id"]
         ).unwrap();
 
     pub static ref CONCURRENT_REQUESTS: prometheus::GaugeVec =
         prometheus::GaugeVec::new(
             Opts::new("concurrent_requests", "Number of HTTP requests currently in flight")
                 .namespace(METRIC_NAMESPACE.as_str()),
-            &["region", "tenant", "node_id", "run_id"]
+            &["region", "zone", "tenant", "node_id", "run_id"]
         ).unwrap();
 
     pub static ref REQUEST_DURATION_SECONDS: HistogramVec =
@@ -40,7 +60,7 @@ lazy_static::lazy_static! {
                 "request_duration_seconds",
                 "HTTP request latencies in seconds."
             ).namespace(METRIC_NAMESPACE.as_str()),
-            &["region", "tenant", "node_id", "run_id"]
+            &["region", "zone", "tenant", "node_id", "run_id"]
         ).unwrap();
 
     // === Scenario Metrics ===
@@ -77,6 +97,46 @@ lazy_static::lazy_static! {
             &["scenario", "step", "node_id", "run_id"]
         ).unwrap();
 
+    /// Tag keys (`METRIC_TAG_KEYS`) added as labels to the `*_by_tag` metrics.
+    pub static ref METRIC_TAG_KEYS: Vec<String> =
+        parse_metric_tag_keys(&env::var("METRIC_TAG_KEYS").unwrap_or_default());
+
+    pub static ref SCENARIO_STEPS_BY_TAG_TOTAL: IntCounterVec =
+        IntCounterVec::new(
+            Opts::new(
+                "scenario_steps_by_tag_total",
+                "Scenario steps executed, labeled with the tags selected by METRIC_TAG_KEYS"
+            ).namespace(METRIC_NAMESPACE.as_str()),
+            &tag_label_names(&["scenario", "step", "status", "node_id", "run_id"])
+        ).unwrap();
+
+    pub static ref SCENARIO_STEP_DURATION_BY_TAG_SECONDS: HistogramVec =
+        HistogramVec::new(
+            prometheus::HistogramOpts::new(
+                "scenario_step_duration_by_tag_seconds",
+                "Scenario step duration in seconds, labeled with the tags selected by METRIC_TAG_KEYS"
+            ).namespace(METRIC_NAMESPACE.as_str()),
+            &tag_label_names(&["scenario", "step", "node_id", "run_id"])
+        ).unwrap();
+
+    pub static ref SCENARIO_STEPS_BY_CLIENT_TOTAL: IntCounterVec =
+        IntCounterVec::new(
+            Opts::new(
+                "scenario_steps_by_client_total",
+                "Scenario steps executed, labeled with the virtual user's client profile"
+            ).namespace(METRIC_NAMESPACE.as_str()),
+            &["scenario", "step", "client_profile", "status", "node_id", "run_id"]
+        ).unwrap();
+
+    pub static ref SCENARIO_STEP_DURATION_BY_CLIENT_SECONDS: HistogramVec =
+        HistogramVec::new(
+            prometheus::HistogramOpts::new(
+                "scenario_step_duration_by_client_seconds",
+                "Scenario step duration in seconds, labeled with the virtual user's client profile"
+            ).namespace(METRIC_NAMESPACE.as_str()),
+            &["scenario", "step", "client_profile", "node_id", "run_id"]
+        ).unwrap();
+
     pub static ref SCENARIO_STEP_STATUS_CODES: IntCounterVec =
         IntCounterVec::new(
             Opts::new("scenario_step_status_codes_total", "HTTP status codes per scenario step")
@@ -84,6 +144,55 @@ lazy_static::lazy_static! {
             &["scenario", "step", "status_code", "node_id", "run_id"]
         ).unwrap();
 
+    pub static ref SCENARIO_RESPONSE_BYTES_TOTAL: IntCounterVec =
+        IntCounterVec::new(
+            Opts::new("scenario_response_bytes_total", "Response body bytes received per scenario step")
+                .namespace(METRIC_NAMESPACE.as_str()),
+            &["scenario", "step", "node_id", "run_id"]
+        ).unwrap();
+
+    pub static ref RESPONSE_BODIES_TRUNCATED_TOTAL: IntCounterVec =
+        IntCounterVec::new(
+            Opts::new("response_bodies_truncated_total", "Response bodies cut at MAX_BODY_BUFFER")
+                .namespace(METRIC_NAMESPACE.as_str()),
+            &["scenario", "step", "node_id", "run_id"]
+        ).unwrap();
+
+    pub static ref HTTP_CACHE_RESPONSES_TOTAL: IntCounterVec =
+        IntCounterVec::new(
+            Opts::new("http_cache_responses_total", "Responses to cache-tracked steps by how they were served")
+                .namespace(METRIC_NAMESPACE.as_str()),
+            &["scenario", "step", "result", "node_id", "run_id"]  // result: hit, not_modified, miss
+        ).unwrap();
+
+    pub static ref STEP_RETRIES_TOTAL: IntCounterVec =
+        IntCounterVec::new(
+            Opts::new("step_retries_total", "Scenario step retries (retryCount)")
+                .namespace(METRIC_NAMESPACE.as_str()),
+            &["scenario", "step", "node_id", "run_id"]
+        ).unwrap();
+
+    pub static ref RETRY_AFTER_HONORED_TOTAL: IntCounterVec =
+        IntCounterVec::new(
+            Opts::new("retry_after_honored_total", "Retries delayed by a Retry-After response header")
+                .namespace(METRIC_NAMESPACE.as_str()),
+            &["scenario", "step", "node_id", "run_id"]
+        ).unwrap();
+
+    pub static ref RATE_LIMITED_RESPONSES_TOTAL: IntCounterVec =
+        IntCounterVec::new(
+            Opts::new("rate_limited_responses_total", "429 Too Many Requests responses per endpoint")
+                .namespace(METRIC_NAMESPACE.as_str()),
+            &["endpoint", "node_id", "run_id"]
+        ).unwrap();
+
+    pub static ref RATE_LIMIT_HEADER_VALUE: prometheus::GaugeVec =
+        prometheus::GaugeVec::new(
+            Opts::new("rate_limit_header_value", "Latest X-RateLimit-* / RateLimit-* header value per endpoint")
+                .namespace(METRIC_NAMESPACE.as_str()),
+            &["endpoint", "header", "node_id", "run_id"]  // header: limit, remaining, reset
+        ).unwrap();
+
     pub static ref SCENARIO_ASSERTIONS_TOTAL: IntCounterVec =
         IntCounterVec::new(
             Opts::new("scenario_assertions_total", "Total number of scenario assertions")
@@ -91,6 +200,48 @@ lazy_static::lazy_static! {
             &["scenario", "step", "result", "node_id", "run_id"]  // result: passed, failed
         ).unwrap();
 
+    pub static ref ASSERTION_SAMPLING_TOTAL: IntCounterVec =
+        IntCounterVec::new(
+            Opts::new(
+                "assertion_sampling_total",
+                "Responses of steps with assertions, by whether the assertions ran (assertionSampleRate)"
+            )
+            .namespace(METRIC_NAMESPACE.as_str()),
+            &["scenario", "step", "sampled", "node_id", "run_id"]  // sampled: true, false
+        ).unwrap();
+
+    pub static ref SCENARIO_STEP_PAGES: HistogramVec =
+        HistogramVec::new(
+            prometheus::HistogramOpts::new(
+                "scenario_step_pages",
+                "Pages fetched per execution of a paginated step"
+            )
+            .namespace(METRIC_NAMESPACE.as_str())
+            .buckets(vec![1.0, 2.0, 5.0, 10.0, 25.0, 50.0, 100.0, 250.0]),
+            &["scenario", "step", "node_id", "run_id"]
+        ).unwrap();
+
+    pub static ref SESSION_CACHE_HITS_TOTAL: IntCounterVec =
+        IntCounterVec::new(
+            Opts::new("session_cache_hits_total", "Cached steps served from the session store (no request sent)")
+                .namespace(METRIC_NAMESPACE.as_str()),
+            &["scenario", "step", "node_id", "run_id"]
+        ).unwrap();
+
+    pub static ref SESSION_CACHE_MISSES_TOTAL: IntCounterVec =
+        IntCounterVec::new(
+            Opts::new("session_cache_misses_total", "Cached steps with no fresh session store entry (request sent)")
+                .namespace(METRIC_NAMESPACE.as_str()),
+            &["scenario", "step", "node_id", "run_id"]
+        ).unwrap();
+
+    pub static ref SHARED_STORE_OPERATIONS_TOTAL: IntCounterVec =
+        IntCounterVec::new(
+            Opts::new("shared_store_operations_total", "Shared store operations by scenario steps (result: ok, empty, error)")
+                .namespace(METRIC_NAMESPACE.as_str()),
+            &["op", "result"]
+        ).unwrap();
+
     pub static ref CONCURRENT_SCENARIOS: Gauge =
         Gauge::with_opts(
             Opts::new("concurrent_scenarios", "Number of scenario executions currently running")
@@ -119,7 +270,7 @@ lazy_static::lazy_static! {
         IntCounterVec::new(
             Opts::new("request_errors_by_category", "Number of errors by category")
                 .namespace(METRIC_NAMESPACE.as_str()),
-            &["category", "region", "tenant", "node_id", "run_id"]
+            &["category", "region", "zone", "tenant", "node_id", "run_id"]
         ).unwrap();
 
     // === Connection Pool Metrics (Issue #36) ===
@@ -160,6 +311,47 @@ lazy_static::lazy_static! {
                 .namespace(METRIC_NAMESPACE.as_str())
         ).unwrap();
 
+    // Observed from the kernel's socket tables (Linux only)
+
+    pub static ref CONNECTIONS_OPEN: Gauge =
+        Gauge::with_opts(
+            Opts::new("connections_open", "Established outbound TCP connections held by this process")
+                .namespace(METRIC_NAMESPACE.as_str())
+        ).unwrap();
+
+    pub static ref CONNECTIONS_OPEN_BY_HOST: prometheus::GaugeVec =
+        prometheus::GaugeVec::new(
+            Opts::new("connections_open_by_host", "Established outbound TCP connections per remote address")
+                .namespace(METRIC_NAMESPACE.as_str()),
+            &["host"]
+        ).unwrap();
+
+    pub static ref CONNECTIONS_IDLE: Gauge =
+        Gauge::with_opts(
+            Opts::new("connections_idle", "Open connections not carrying a request (idle in the pool)")
+                .namespace(METRIC_NAMESPACE.as_str())
+        ).unwrap();
+
+    pub static ref CONNECTIONS_OPENED_TOTAL: IntCounter =
+        IntCounter::with_opts(
+            Opts::new("connections_opened_total", "Outbound TCP connections opened (kernel ActiveOpens)")
+                .namespace(METRIC_NAMESPACE.as_str())
+        ).unwrap();
+
+    pub static ref TLS_HANDSHAKES_TOTAL: IntCounterVec =
+        IntCounterVec::new(
+            Opts::new("tls_handshakes_total", "Client TLS handshakes by kind (full or resumed)")
+                .namespace(METRIC_NAMESPACE.as_str()),
+            &["kind"]
+        ).unwrap();
+
+    pub static ref CLIENT_CERT_RELOADS_TOTAL: IntCounterVec =
+        IntCounterVec::new(
+            Opts::new("client_cert_reloads_total", "mTLS client certificate reloads after the files changed, by result")
+                .namespace(METRIC_NAMESPACE.as_str()),
+            &["result"]
+        ).unwrap();
+
     // === Memory Usage Metrics (Issue #69) ===
 
     pub static ref PROCESS_MEMORY_RSS_BYTES: Gauge =
@@ -250,6 +442,305 @@ lazy_static::lazy_static! {
         )
         .unwrap();
 
+    // === Target vs Achieved Rate ===
+
+    /// 1 while a runtime target-RPS override (`POST /rps`,
+    /// `RPS_OVERRIDE_FILE`) replaces the load model's rate.
+    pub static ref RPS_OVERRIDE_ACTIVE: Gauge =
+        Gauge::with_opts(
+            Opts::new(
+                "rps_override_active",
+                "1 while a runtime target RPS override is in effect",
+            )
+            .namespace(METRIC_NAMESPACE.as_str()),
+        )
+        .unwrap();
+
+    /// RPS the load model asks for right now. Not set for the Concurrent
+    /// model, which has no target rate.
+    pub static ref INTENDED_RPS: Gauge =
+        Gauge::with_opts(
+            Opts::new(
+                "intended_rps",
+                "Requests per second the load model currently targets",
+            )
+            .namespace(METRIC_NAMESPACE.as_str()),
+        )
+        .unwrap();
+
+    pub static ref ACHIEVED_RPS: Gauge =
+        Gauge::with_opts(
+            Opts::new(
+                "achieved_rps",
+                "Requests per second actually sent over the last second",
+            )
+            .namespace(METRIC_NAMESPACE.as_str()),
+        )
+        .unwrap();
+
+    pub static ref CONCURRENCY_AVERAGE: Gauge =
+        Gauge::with_opts(
+            Opts::new(
+                "concurrency_average",
+                "Time-weighted average of requests in flight since the test started",
+            )
+            .namespace(METRIC_NAMESPACE.as_str()),
+        )
+        .unwrap();
+
+    pub static ref RPS_SHORTFALL_WARNINGS_TOTAL: IntCounter =
+        IntCounter::with_opts(
+            Opts::new(
+                "rps_shortfall_warnings_total",
+                "Times the achieved RPS stayed below the intended RPS beyond tolerance",
+            )
+            .namespace(METRIC_NAMESPACE.as_str()),
+        )
+        .unwrap();
+
+    pub static ref LATENCY_SLA_PERCENTILE_SECONDS: prometheus::GaugeVec =
+        prometheus::GaugeVec::new(
+            Opts::new(
+                "latency_sla_percentile_seconds",
+                "Windowed step latency percentile judged by a percentile assertion",
+            )
+            .namespace(METRIC_NAMESPACE.as_str()),
+            &["scenario", "step", "percentile", "node_id", "run_id"]
+        )
+        .unwrap();
+
+    pub static ref LATENCY_SLA_BREACHES_TOTAL: IntCounterVec =
+        IntCounterVec::new(
+            Opts::new(
+                "latency_sla_breaches_total",
+                "Times a windowed step latency percentile went above its percentile assertion's max",
+            )
+            .namespace(METRIC_NAMESPACE.as_str()),
+            &["scenario", "step", "percentile", "node_id", "run_id"]
+        )
+        .unwrap();
+
+    pub static ref RATE_CAP_THROTTLED_SECONDS_TOTAL: Counter =
+        Counter::with_opts(
+            Opts::new(
+                "rate_cap_throttled_seconds_total",
+                "Cumulative time requests were delayed by the HARD_MAX_RPS cap",
+            )
+            .namespace(METRIC_NAMESPACE.as_str()),
+        )
+        .unwrap();
+
+    pub static ref REQUEST_BUDGET_EXHAUSTED: Gauge =
+        Gauge::with_opts(
+            Opts::new(
+                "request_budget_exhausted",
+                "1 once MAX_TOTAL_REQUESTS requests have been sent, otherwise 0",
+            )
+            .namespace(METRIC_NAMESPACE.as_str()),
+        )
+        .unwrap();
+
+    // === In-flight cap (MAX_IN_FLIGHT) ===
+
+    pub static ref IN_FLIGHT_LIMIT: Gauge =
+        Gauge::with_opts(
+            Opts::new(
+                "in_flight_limit",
+                "Configured MAX_IN_FLIGHT (0 = no cap)",
+            )
+            .namespace(METRIC_NAMESPACE.as_str()),
+        )
+        .unwrap();
+
+    pub static ref IN_FLIGHT_WAITING: Gauge =
+        Gauge::with_opts(
+            Opts::new(
+                "in_flight_waiting",
+                "Workers blocked waiting for a MAX_IN_FLIGHT slot",
+            )
+            .namespace(METRIC_NAMESPACE.as_str()),
+        )
+        .unwrap();
+
+    pub static ref IN_FLIGHT_BLOCKED_TOTAL: IntCounter =
+        IntCounter::with_opts(
+            Opts::new(
+                "in_flight_blocked_total",
+                "Requests that had to wait for a MAX_IN_FLIGHT slot",
+            )
+            .namespace(METRIC_NAMESPACE.as_str()),
+        )
+        .unwrap();
+
+    pub static ref GENERATOR_DROPPED_TOTAL: IntCounterVec =
+        IntCounterVec::new(
+            Opts::new(
+                "generator_dropped_total",
+                "Requests (scenario \"\") and scenario iterations the generator dropped under BACKPRESSURE",
+            )
+            .namespace(METRIC_NAMESPACE.as_str()),
+            &["scenario"]
+        )
+        .unwrap();
+
+    pub static ref IN_FLIGHT_WAIT_SECONDS_TOTAL: Counter =
+        Counter::with_opts(
+            Opts::new(
+                "in_flight_wait_seconds_total",
+                "Cumulative time requests waited for a MAX_IN_FLIGHT slot",
+            )
+            .namespace(METRIC_NAMESPACE.as_str()),
+        )
+        .unwrap();
+
+    // === Synchronized start (START_AT) ===
+
+    pub static ref START_LATENESS_SECONDS: Gauge =
+        Gauge::with_opts(
+            Opts::new(
+                "start_lateness_seconds",
+                "How late this node reached the START_AT instant (0 = on time)",
+            )
+            .namespace(METRIC_NAMESPACE.as_str()),
+        )
+        .unwrap();
+
+    // === Service discovery (consul:// and k8s:// targets) ===
+
+    pub static ref DISCOVERED_INSTANCES: Gauge =
+        Gauge::with_opts(
+            Opts::new(
+                "discovered_instances",
+                "Target instances found by the last service discovery lookup",
+            )
+            .namespace(METRIC_NAMESPACE.as_str()),
+        )
+        .unwrap();
+
+    pub static ref DISCOVERY_FAILURES_TOTAL: IntCounter =
+        IntCounter::with_opts(
+            Opts::new(
+                "discovery_failures_total",
+                "Service discovery lookups that failed (the previous instances are kept)",
+            )
+            .namespace(METRIC_NAMESPACE.as_str()),
+        )
+        .unwrap();
+
+    // === Per-backend metrics (DNS_ROUND_ROBIN) ===
+
+    pub static ref REQUESTS_BY_TARGET_IP_TOTAL: IntCounterVec =
+        IntCounterVec::new(
+            Opts::new(
+                "requests_by_target_ip_total",
+                "HTTP requests by resolved target IP and status code (DNS_ROUND_ROBIN)",
+            )
+            .namespace(METRIC_NAMESPACE.as_str()),
+            &["target_ip", "status_code", "node_id", "run_id"]
+        ).unwrap();
+
+    pub static ref REQUEST_DURATION_BY_TARGET_IP_SECONDS: HistogramVec =
+        HistogramVec::new(
+            prometheus::HistogramOpts::new(
+                "request_duration_by_target_ip_seconds",
+                "HTTP request latencies in seconds by resolved target IP (DNS_ROUND_ROBIN)",
+            ).namespace(METRIC_NAMESPACE.as_str()),
+            &["target_ip", "node_id", "run_id"]
+        ).unwrap();
+
+    pub static ref REQUESTS_BY_IP_FAMILY_TOTAL: IntCounterVec =
+        IntCounterVec::new(
+            Opts::new(
+                "requests_by_ip_family_total",
+                "HTTP requests by the address family (v4, v6) of the connection used",
+            )
+            .namespace(METRIC_NAMESPACE.as_str()),
+            &["family", "node_id", "run_id"]
+        ).unwrap();
+
+    // === Apdex (step `slo`) ===
+    // `step` is empty for the scenario-level score over all its SLO steps.
+
+    pub static ref APDEX_SCORE: prometheus::GaugeVec =
+        prometheus::GaugeVec::new(
+            Opts::new("apdex_score", "Apdex score (0-1) against step response time SLOs")
+                .namespace(METRIC_NAMESPACE.as_str()),
+            &["scenario", "step", "node_id", "run_id"]
+        ).unwrap();
+
+    // === Concurrency limits (maxConcurrent) ===
+    // `step` is empty for scenario-level limits.
+
+    pub static ref CONCURRENCY_LIMIT: prometheus::GaugeVec =
+        prometheus::GaugeVec::new(
+            Opts::new("concurrency_limit", "Configured maxConcurrent for a scenario or step")
+                .namespace(METRIC_NAMESPACE.as_str()),
+            &["scenario", "step", "node_id", "run_id"]
+        ).unwrap();
+
+    pub static ref CONCURRENCY_IN_USE: prometheus::GaugeVec =
+        prometheus::GaugeVec::new(
+            Opts::new("concurrency_in_use", "Executions currently holding a maxConcurrent slot")
+                .namespace(METRIC_NAMESPACE.as_str()),
+            &["scenario", "step", "node_id", "run_id"]
+        ).unwrap();
+
+    pub static ref CONCURRENCY_WAITING: prometheus::GaugeVec =
+        prometheus::GaugeVec::new(
+            Opts::new("concurrency_waiting", "Workers waiting for a maxConcurrent slot")
+                .namespace(METRIC_NAMESPACE.as_str()),
+            &["scenario", "step", "node_id", "run_id"]
+        ).unwrap();
+
+    // === Rendezvous points ===
+
+    pub static ref RENDEZVOUS_WAITING: prometheus::GaugeVec =
+        prometheus::GaugeVec::new(
+            Opts::new("rendezvous_waiting", "Workers waiting at a rendezvous point")
+                .namespace(METRIC_NAMESPACE.as_str()),
+            &["rendezvous", "node_id", "run_id"]
+        ).unwrap();
+
+    pub static ref RENDEZVOUS_RELEASES_TOTAL: IntCounterVec =
+        IntCounterVec::new(
+            Opts::new("rendezvous_releases_total", "Rendezvous groups released (reason: full, timeout)")
+                .namespace(METRIC_NAMESPACE.as_str()),
+            &["rendezvous", "reason", "node_id", "run_id"]
+        ).unwrap();
+
+    // === Execution phases (phases / dependsOn) ===
+
+    pub static ref PHASE_ACTIVE: prometheus::GaugeVec =
+        prometheus::GaugeVec::new(
+            Opts::new("phase_active", "1 while the execution phase is running, 0 otherwise")
+                .namespace(METRIC_NAMESPACE.as_str()),
+            &["phase"]
+        ).unwrap();
+
+    pub static ref DNS_RESOLUTION_CHANGES_TOTAL: IntCounterVec =
+        IntCounterVec::new(
+            Opts::new(
+                "dns_resolution_changes_total",
+                "Re-resolutions (DNS_REFRESH_INTERVAL) that returned a different address set",
+            )
+            .namespace(METRIC_NAMESPACE.as_str()),
+            &["host"]
+        ).unwrap();
+
+    pub static ref TEMPLATE_REQUESTS_TOTAL: IntCounterVec =
+        IntCounterVec::new(
+            Opts::new("template_requests_total", "Requests sent per request template (TEMPLATE_DIR)")
+                .namespace(METRIC_NAMESPACE.as_str()),
+            &["template", "status_code", "node_id", "run_id"]
+        ).unwrap();
+
+    pub static ref CHAOS_FAULTS_INJECTED_TOTAL: IntCounterVec =
+        IntCounterVec::new(
+            Opts::new("chaos_faults_injected_total", "Faults injected into requests by chaos mode")
+                .namespace(METRIC_NAMESPACE.as_str()),
+            &["fault"]
+        ).unwrap();
+
     // === Cluster Node Info (Issue #45) ===
 
     /// Info gauge set to 1 when the node is running. Labels identify the node
@@ -258,114 +749,701 @@ lazy_static::lazy_static! {
         prometheus::GaugeVec::new(
             Opts::new(
                 "cluster_node_info",
-                "Cluster node identity and state (1 = running). Labels: node_id, region, state.",
+                "Cluster node identity and state (1 = running). Labels: node_id, region, zone, state.",
             )
             .namespace(METRIC_NAMESPACE.as_str()),
-            &["node_id", "region", "state"],
+            &["node_id", "region", "zone", "state"],
         )
         .unwrap();
 }
 
+/// The metric namespace: `base`, suffixed with `run_id` when given
+/// (`METRIC_NAMESPACE_RUN_ID=true`). Characters not allowed in metric names
+/// become `_`.
+pub fn metric_namespace(base: &str, run_id: Option<&str>) -> String {
+    match run_id.map(str::trim).filter(|id| !id.is_empty()) {
+        Some(run_id) => {
+            let suffix: String = run_id
+                .chars()
+                .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+                .collect();
+            format!("{}_{}", base, suffix)
+        }
+        None => base.to_string(),
+    }
+}
+
 /// Registers all metrics with the default Prometheus registry.
 pub fn register_metrics() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+    crate::openmetrics::mark_created();
+
     // Single request metrics
-    prometheus::default_registry().register(Box::new(REQUEST_TOTAL.clone()))?;
-    prometheus::default_registry().register(Box::new(REQUEST_STATUS_CODES.clone()))?;
-    prometheus::default_registry().register(Box::new(CONCURRENT_REQUESTS.clone()))?;
-    prometheus::default_registry().register(Box::new(REQUEST_DURATION_SECONDS.clone()))?;
+    REGISTRY.register(Box::new(REQUEST_TOTAL.clone()))?;
+    REGISTRY.register(Box::new(REQUEST_STATUS_CODES.clone()))?;
+    REGISTRY.register(Box::new(CONCURRENT_REQUESTS.clone()))?;
+    REGISTRY.register(Box::new(REQUEST_DURATION_SECONDS.clone()))?;
 
     // Scenario metrics
-    prometheus::default_registry().register(Box::new(SCENARIO_EXECUTIONS_TOTAL.clone()))?;
-    prometheus::default_registry().register(Box::new(SCENARIO_DURATION_SECONDS.clone()))?;
-    prometheus::default_registry().register(Box::new(SCENARIO_STEPS_TOTAL.clone()))?;
-    prometheus::default_registry().register(Box::new(SCENARIO_STEP_DURATION_SECONDS.clone()))?;
-    prometheus::default_registry().register(Box::new(SCENARIO_STEP_STATUS_CODES.clone()))?;
-    prometheus::default_registry().register(Box::new(SCENARIO_ASSERTIONS_TOTAL.clone()))?;
-    prometheus::default_registry().register(Box::new(CONCURRENT_SCENARIOS.clone()))?;
+    REGISTRY.register(Box::new(SCENARIO_EXECUTIONS_TOTAL.clone()))?;
+    REGISTRY.register(Box::new(SCENARIO_DURATION_SECONDS.clone()))?;
+    REGISTRY.register(Box::new(SCENARIO_STEPS_TOTAL.clone()))?;
+    REGISTRY.register(Box::new(SCENARIO_STEP_DURATION_SECONDS.clone()))?;
+    REGISTRY.register(Box::new(SCENARIO_STEPS_BY_CLIENT_TOTAL.clone()))?;
+    REGISTRY.register(Box::new(SCENARIO_STEP_DURATION_BY_CLIENT_SECONDS.clone()))?;
+    REGISTRY.register(Box::new(SCENARIO_STEP_STATUS_CODES.clone()))?;
+    REGISTRY.register(Box::new(SCENARIO_RESPONSE_BYTES_TOTAL.clone()))?;
+    REGISTRY.register(Box::new(RESPONSE_BODIES_TRUNCATED_TOTAL.clone()))?;
+    REGISTRY.register(Box::new(HTTP_CACHE_RESPONSES_TOTAL.clone()))?;
+    REGISTRY.register(Box::new(STEP_RETRIES_TOTAL.clone()))?;
+    REGISTRY.register(Box::new(RETRY_AFTER_HONORED_TOTAL.clone()))?;
+    REGISTRY.register(Box::new(RATE_LIMITED_RESPONSES_TOTAL.clone()))?;
+    REGISTRY.register(Box::new(RATE_LIMIT_HEADER_VALUE.clone()))?;
+    REGISTRY.register(Box::new(SCENARIO_ASSERTIONS_TOTAL.clone()))?;
+    REGISTRY.register(Box::new(ASSERTION_SAMPLING_TOTAL.clone()))?;
+    REGISTRY.register(Box::new(SCENARIO_STEP_PAGES.clone()))?;
+    REGISTRY.register(Box::new(SESSION_CACHE_HITS_TOTAL.clone()))?;
+    REGISTRY.register(Box::new(SESSION_CACHE_MISSES_TOTAL.clone()))?;
+    REGISTRY.register(Box::new(SHARED_STORE_OPERATIONS_TOTAL.clone()))?;
+    REGISTRY.register(Box::new(CONCURRENT_SCENARIOS.clone()))?;
 
     // Per-scenario throughput metrics
-    prometheus::default_registry().register(Box::new(SCENARIO_REQUESTS_TOTAL.clone()))?;
-    prometheus::default_registry().register(Box::new(SCENARIO_THROUGHPUT_RPS.clone()))?;
+    REGISTRY.register(Box::new(SCENARIO_REQUESTS_TOTAL.clone()))?;
+    REGISTRY.register(Box::new(SCENARIO_THROUGHPUT_RPS.clone()))?;
 
     // Error categorization metrics
-    prometheus::default_registry().register(Box::new(REQUEST_ERRORS_BY_CATEGORY.clone()))?;
+    REGISTRY.register(Box::new(REQUEST_ERRORS_BY_CATEGORY.clone()))?;
 
     // Connection pool metrics
-    prometheus::default_registry().register(Box::new(CONNECTION_POOL_MAX_IDLE.clone()))?;
-    prometheus::default_registry()
-        .register(Box::new(CONNECTION_POOL_IDLE_TIMEOUT_SECONDS.clone()))?;
-    prometheus::default_registry().register(Box::new(CONNECTION_POOL_REQUESTS_TOTAL.clone()))?;
-    prometheus::default_registry().register(Box::new(CONNECTION_POOL_LIKELY_REUSED.clone()))?;
-    prometheus::default_registry().register(Box::new(CONNECTION_POOL_LIKELY_NEW.clone()))?;
-    prometheus::default_registry().register(Box::new(CONNECTION_POOL_REUSE_RATE.clone()))?;
+    REGISTRY.register(Box::new(CONNECTION_POOL_MAX_IDLE.clone()))?;
+    REGISTRY.register(Box::new(CONNECTION_POOL_IDLE_TIMEOUT_SECONDS.clone()))?;
+    REGISTRY.register(Box::new(CONNECTION_POOL_REQUESTS_TOTAL.clone()))?;
+    REGISTRY.register(Box::new(CONNECTION_POOL_LIKELY_REUSED.clone()))?;
+    REGISTRY.register(Box::new(CONNECTION_POOL_LIKELY_NEW.clone()))?;
+    REGISTRY.register(Box::new(CONNECTIONS_OPEN.clone()))?;
+    REGISTRY.register(Box::new(CONNECTIONS_OPEN_BY_HOST.clone()))?;
+    REGISTRY.register(Box::new(CONNECTIONS_IDLE.clone()))?;
+    REGISTRY.register(Box::new(CONNECTIONS_OPENED_TOTAL.clone()))?;
+    REGISTRY.register(Box::new(TLS_HANDSHAKES_TOTAL.clone()))?;
+    REGISTRY.register(Box::new(CLIENT_CERT_RELOADS_TOTAL.clone()))?;
+    REGISTRY.register(Box::new(CONNECTION_POOL_REUSE_RATE.clone()))?;
 
     // Memory usage metrics
-    prometheus::default_registry().register(Box::new(PROCESS_MEMORY_RSS_BYTES.clone()))?;
-    prometheus::default_registry().register(Box::new(PROCESS_MEMORY_VIRTUAL_BYTES.clone()))?;
-    prometheus::default_registry().register(Box::new(HISTOGRAM_COUNT.clone()))?;
-    prometheus::default_registry().register(Box::new(HISTOGRAM_MEMORY_ESTIMATE_BYTES.clone()))?;
+    REGISTRY.register(Box::new(PROCESS_MEMORY_RSS_BYTES.clone()))?;
+    REGISTRY.register(Box::new(PROCESS_MEMORY_VIRTUAL_BYTES.clone()))?;
+    REGISTRY.register(Box::new(HISTOGRAM_COUNT.clone()))?;
+    REGISTRY.register(Box::new(HISTOGRAM_MEMORY_ESTIMATE_BYTES.clone()))?;
 
     // Memory guard & percentile tracking metrics
-    prometheus::default_registry().register(Box::new(PERCENTILE_TRACKING_ACTIVE_GAUGE.clone()))?;
-    prometheus::default_registry()
-        .register(Box::new(MEMORY_WARNING_THRESHOLD_EXCEEDED_TOTAL.clone()))?;
-    prometheus::default_registry()
-        .register(Box::new(MEMORY_CRITICAL_THRESHOLD_EXCEEDED_TOTAL.clone()))?;
-    prometheus::default_registry().register(Box::new(HISTOGRAM_LABELS_EVICTED_TOTAL.clone()))?;
+    REGISTRY.register(Box::new(PERCENTILE_TRACKING_ACTIVE_GAUGE.clone()))?;
+    REGISTRY.register(Box::new(MEMORY_WARNING_THRESHOLD_EXCEEDED_TOTAL.clone()))?;
+    REGISTRY.register(Box::new(MEMORY_CRITICAL_THRESHOLD_EXCEEDED_TOTAL.clone()))?;
+    REGISTRY.register(Box::new(HISTOGRAM_LABELS_EVICTED_TOTAL.clone()))?;
 
     // Test configuration metrics
-    prometheus::default_registry().register(Box::new(PERCENTILE_SAMPLING_RATE_PERCENT.clone()))?;
-    prometheus::default_registry().register(Box::new(WORKERS_CONFIGURED_TOTAL.clone()))?;
+    REGISTRY.register(Box::new(PERCENTILE_SAMPLING_RATE_PERCENT.clone()))?;
+    REGISTRY.register(Box::new(WORKERS_CONFIGURED_TOTAL.clone()))?;
+
+    // Target vs achieved rate
+    REGISTRY.register(Box::new(INTENDED_RPS.clone()))?;
+    REGISTRY.register(Box::new(RPS_OVERRIDE_ACTIVE.clone()))?;
+    REGISTRY.register(Box::new(ACHIEVED_RPS.clone()))?;
+    REGISTRY.register(Box::new(RPS_SHORTFALL_WARNINGS_TOTAL.clone()))?;
+    REGISTRY.register(Box::new(LATENCY_SLA_PERCENTILE_SECONDS.clone()))?;
+    REGISTRY.register(Box::new(LATENCY_SLA_BREACHES_TOTAL.clone()))?;
+    REGISTRY.register(Box::new(CONCURRENCY_AVERAGE.clone()))?;
+    REGISTRY.register(Box::new(CHAOS_FAULTS_INJECTED_TOTAL.clone()))?;
+    REGISTRY.register(Box::new(RATE_CAP_THROTTLED_SECONDS_TOTAL.clone()))?;
+    REGISTRY.register(Box::new(REQUEST_BUDGET_EXHAUSTED.clone()))?;
+    REGISTRY.register(Box::new(IN_FLIGHT_LIMIT.clone()))?;
+    REGISTRY.register(Box::new(IN_FLIGHT_WAITING.clone()))?;
+    REGISTRY.register(Box::new(IN_FLIGHT_BLOCKED_TOTAL.clone()))?;
+    REGISTRY.register(Box::new(IN_FLIGHT_WAIT_SECONDS_TOTAL.clone()))?;
+    REGISTRY.register(Box::new(GENERATOR_DROPPED_TOTAL.clone()))?;
+    REGISTRY.register(Box::new(START_LATENESS_SECONDS.clone()))?;
+    REGISTRY.register(Box::new(DISCOVERED_INSTANCES.clone()))?;
+    REGISTRY.register(Box::new(DISCOVERY_FAILURES_TOTAL.clone()))?;
+    REGISTRY.register(Box::new(REQUESTS_BY_TARGET_IP_TOTAL.clone()))?;
+    REGISTRY.register(Box::new(REQUEST_DURATION_BY_TARGET_IP_SECONDS.clone()))?;
+    REGISTRY.register(Box::new(REQUESTS_BY_IP_FAMILY_TOTAL.clone()))?;
+    REGISTRY.register(Box::new(DNS_RESOLUTION_CHANGES_TOTAL.clone()))?;
+    REGISTRY.register(Box::new(TEMPLATE_REQUESTS_TOTAL.clone()))?;
+    REGISTRY.register(Box::new(CONCURRENCY_LIMIT.clone()))?;
+    REGISTRY.register(Box::new(CONCURRENCY_IN_USE.clone()))?;
+    REGISTRY.register(Box::new(CONCURRENCY_WAITING.clone()))?;
+    REGISTRY.register(Box::new(RENDEZVOUS_WAITING.clone()))?;
+    REGISTRY.register(Box::new(RENDEZVOUS_RELEASES_TOTAL.clone()))?;
+    REGISTRY.register(Box::new(PHASE_ACTIVE.clone()))?;
+    REGISTRY.register(Box::new(APDEX_SCORE.clone()))?;
+
+    // Tag-labeled scenario metrics, only when tag keys are selected
+    if !METRIC_TAG_KEYS.is_empty() {
+        REGISTRY.register(Box::new(SCENARIO_STEPS_BY_TAG_TOTAL.clone()))?;
+        REGISTRY.register(Box::new(SCENARIO_STEP_DURATION_BY_TAG_SECONDS.clone()))?;
+    }
 
     // Cluster node info (Issue #45)
-    prometheus::default_registry().register(Box::new(CLUSTER_NODE_INFO.clone()))?;
+    REGISTRY.register(Box::new(CLUSTER_NODE_INFO.clone()))?;
 
     Ok(())
 }
 
+/// Labels reserved by the `*_by_tag` metrics; tag keys may not reuse them.
+const RESERVED_TAG_LABELS: &[&str] = &["scenario", "step", "status", "node_id", "run_id"];
+
+/// Label names used by the built-in metrics, plus the supervisor's `runner`;
+/// constant labels may not reuse them.
+const BUILT_IN_LABELS: &[&str] = &[
+    "category",
+    "client_profile",
+    "endpoint",
+    "family",
+    "fault",
+    "header",
+    "host",
+    "kind",
+    "node_id",
+    "op",
+    "percentile",
+    "phase",
+    "reason",
+    "region",
+    "rendezvous",
+    "result",
+    "run_id",
+    "runner",
+    "sampled",
+    "scenario",
+    "state",
+    "status",
+    "status_code",
+    "step",
+    "target_ip",
+    "template",
+    "tenant",
+    "zone",
+];
+
+fn is_label_name(key: &str) -> bool {
+    key.starts_with(|c: char| c.is_ascii_alphabetic() || c == '_')
+        && key.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+        && !key.starts_with("__")
+}
+
+/// A registry that adds the constant `labels` to every series it gathers.
+pub fn labelled_registry(labels: HashMap<String, String>) -> Registry {
+    Registry::new_custom(None, Some(labels)).unwrap_or_else(|e| {
+        tracing::warn!(error = %e, "Ignoring METRIC_LABELS");
+        Registry::new()
+    })
+}
+
+/// Parses `METRIC_LABELS` (`name:value,name:value`). Entries without a
+/// `:`, names that are not valid label names, and names already used by a
+/// metric's own labels or by `tag_keys` are skipped.
+pub fn parse_metric_labels(raw: &str, tag_keys: &[String]) -> HashMap<String, String> {
+    let mut labels = HashMap::new();
+    for pair in raw.split(',').map(str::trim).filter(|p| !p.is_empty()) {
+        let Some((name, value)) = pair.split_once(':') else {
+            tracing::warn!(pair, "Ignoring METRIC_LABELS entry: expected name:value");
+            continue;
+        };
+        let name = name.trim();
+        if !is_label_name(name)
+            || BUILT_IN_LABELS.contains(&name)
+            || tag_keys.iter().any(|k| k == name)
+        {
+            tracing::warn!(
+                name,
+                "Ignoring METRIC_LABELS entry: not a usable label name"
+            );
+            continue;
+        }
+        labels.insert(name.to_string(), value.trim().to_string());
+    }
+    labels
+}
+
+/// Parses `METRIC_TAG_KEYS` (comma-separated). Keys that are not valid
+/// Prometheus label names, or that clash with built-in labels, are skipped.
+pub fn parse_metric_tag_keys(raw: &str) -> Vec<String> {
+    let mut keys: Vec<String> = Vec::new();
+    for key in raw.split(',').map(str::trim).filter(|k| !k.is_empty()) {
+        if !is_label_name(key) || RESERVED_TAG_LABELS.contains(&key) {
+            tracing::warn!(
+                key,
+                "Ignoring METRIC_TAG_KEYS entry: not a usable label name"
+            );
+            continue;
+        }
+        if !keys.iter().any(|k| k == key) {
+            keys.push(key.to_string());
+        }
+    }
+    keys
+}
+
+/// `base` label names followed by the selected tag keys.
+fn tag_label_names(base: &[&'static str]) -> Vec<&'static str> {
+    let mut names = base.to_vec();
+    names.extend(METRIC_TAG_KEYS.iter().map(String::as_str));
+    names
+}
+
+/// Values of the selected tag keys in `tags`, in `METRIC_TAG_KEYS` order.
+/// Missing tags are reported as an empty label value.
+pub fn tag_label_values(tags: &std::collections::HashMap<String, String>) -> Vec<&str> {
+    METRIC_TAG_KEYS
+        .iter()
+        .map(|key| tags.get(key).map(String::as_str).unwrap_or(""))
+        .collect()
+}
+
+/// Errors from metrics server configuration.
+#[derive(Error, Debug)]
+pub enum MetricsServerError {
+    #[error("Invalid METRICS_ADDR '{0}': expected 'host:port' or a port number")]
+    InvalidAddr(String),
+
+    #[error("METRICS_TLS_CERT and METRICS_TLS_KEY must be set together")]
+    IncompleteTls,
+
+    #[error("Failed to load metrics TLS material: {0}")]
+    Tls(String),
+
+    #[error("Invalid METRICS_BASIC_AUTH: expected 'username:password'")]
+    InvalidBasicAuth,
+
+    #[error("METRICS_BASIC_AUTH and METRICS_BEARER_TOKEN are mutually exclusive")]
+    ConflictingAuth,
+}
+
+/// Authentication required to scrape `/metrics`.
+#[derive(Debug, Clone, PartialEq)]
+pub enum MetricsAuth {
+    /// `Authorization: Basic base64(username:password)`
+    Basic { username: String, password: String },
+    /// `Authorization: Bearer <token>`
+    Bearer(String),
+}
+
+impl MetricsAuth {
+    /// Returns true if the request's `Authorization` header satisfies this auth.
+    pub fn authorize(&self, header: Option<&str>) -> bool {
+        let Some(header) = header else {
+            return false;
+        };
+        match self {
+            MetricsAuth::Bearer(token) => header
+                .strip_prefix("Bearer ")
+                .is_some_and(|given| constant_time_eq(given.trim().as_bytes(), token.as_bytes())),
+            MetricsAuth::Basic { username, password } => {
+                let expected = base64::engine::general_purpose::STANDARD
+                    .encode(format!("{}:{}", username, password));
+                header.strip_prefix("Basic ").is_some_and(|given| {
+                    constant_time_eq(given.trim().as_bytes(), expected.as_bytes())
+                })
+            }
+        }
+    }
+
+    /// Value for the `WWW-Authenticate` header on a 401.
+    fn challenge(&self) -> &'static str {
+        match self {
+            MetricsAuth::Basic { .. } => "Basic realm=\"metrics\"",
+            MetricsAuth::Bearer(_) => "Bearer realm=\"metrics\"",
+        }
+    }
+}
+
+/// Compares two byte strings without short-circuiting on the first mismatch.
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    if a.len() != b.len() {
+        return false;
+    }
+    a.iter().zip(b).fold(0u8, |acc, (x, y)| acc | (x ^ y)) == 0
+}
+
+/// Metrics server settings.
+///
+/// Environment variables:
+/// - `METRICS_ADDR` — bind address, `host:port` or just a port (default `0.0.0.0:9090`)
+/// - `METRICS_TLS_CERT` / `METRICS_TLS_KEY` — PEM certificate chain and private
+///   key; when both are set the endpoint is served over HTTPS
+/// - `METRICS_BASIC_AUTH` — `username:password` required via HTTP basic auth
+/// - `METRICS_BEARER_TOKEN` — token required via `Authorization: Bearer`
+#[derive(Debug, Clone, PartialEq)]
+pub struct MetricsServerConfig {
+    pub addr: SocketAddr,
+    pub tls_cert_path: Option<String>,
+    pub tls_key_path: Option<String>,
+    pub auth: Option<MetricsAuth>,
+}
+
+impl Default for MetricsServerConfig {
+    fn default() -> Self {
+        Self {
+            addr: SocketAddr::from(([0, 0, 0, 0], 9090)),
+            tls_cert_path: None,
+            tls_key_path: None,
+            auth: None,
+        }
+    }
+}
+
+impl MetricsServerConfig {
+    /// Reads the metrics server settings from the environment.
+    pub fn from_env() -> Result<Self, MetricsServerError> {
+        let var = |name: &str| env::var(name).ok().filter(|v| !v.trim().is_empty());
+
+        let addr = match var("METRICS_ADDR") {
+            Some(raw) => parse_metrics_addr(&raw)?,
+            None => Self::default().addr,
+        };
+
+        let (tls_cert_path, tls_key_path) = match (var("METRICS_TLS_CERT"), var("METRICS_TLS_KEY"))
+        {
+            (Some(cert), Some(key)) => (Some(cert), Some(key)),
+            (None, None) => (None, None),
+            _ => return Err(MetricsServerError::IncompleteTls),
+        };
+
+        let auth = match (var("METRICS_BASIC_AUTH"), var("METRICS_BEARER_TOKEN")) {
+            (Some(_), Some(_)) => return Err(MetricsServerError::ConflictingAuth),
+            (Some(basic), None) => {
+                let (username, password) = basic
+                    .split_once(':')
+                    .ok_or(MetricsServerError::InvalidBasicAuth)?;
+                Some(MetricsAuth::Basic {
+                    username: username.to_string(),
+                    password: password.to_string(),
+                })
+            }
+            (None, Some(token)) => Some(MetricsAuth::Bearer(token.trim().to_string())),
+            (None, None) => None,
+        };
+
+        Ok(Self {
+            addr,
+            tls_cert_path,
+            tls_key_path,
+            auth,
+        })
+    }
+
+    /// Returns true when the endpoint is served over HTTPS.
+    pub fn tls_enabled(&self) -> bool {
+        self.tls_cert_path.is_some() && self.tls_key_path.is_some()
+    }
+}
+
+/// Parses `METRICS_ADDR`: `host:port`, `[v6]:port`, `:port` or a bare port.
+fn parse_metrics_addr(raw: &str) -> Result<SocketAddr, MetricsServerError> {
+    crate::addr::parse_listen_addr(raw)
+        .map_err(|_| MetricsServerError::InvalidAddr(raw.trim().to_string()))
+}
+
+/// Builds a TLS acceptor from PEM certificate chain and private key files.
+fn load_tls_acceptor(
+    cert_path: &str,
+    key_path: &str,
+) -> Result<tokio_rustls::TlsAcceptor, MetricsServerError> {
+    let tls_err = MetricsServerError::Tls;
+
+    let cert_file = std::fs::File::open(cert_path)
+        .map_err(|e| tls_err(format!("cannot open certificate '{}': {}", cert_path, e)))?;
+    let certs = rustls_pemfile::certs(&mut std::io::BufReader::new(cert_file))
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| tls_err(format!("invalid certificate '{}': {}", cert_path, e)))?;
+    if certs.is_empty() {
+        return Err(tls_err(format!("no certificates found in '{}'", cert_path)));
+    }
+
+    let key_file = std::fs::File::open(key_path)
+        .map_err(|e| tls_err(format!("cannot open private key '{}': {}", key_path, e)))?;
+    let key = rustls_pemfile::private_key(&mut std::io::BufReader::new(key_file))
+        .map_err(|e| tls_err(format!("invalid private key '{}': {}", key_path, e)))?
+        .ok_or_else(|| tls_err(format!("no private key found in '{}'", key_path)))?;
+
+    let mut server_config = rustls::ServerConfig::builder()
+        .with_no_client_auth()
+        .with_single_cert(certs, key)
+        .map_err(|e| tls_err(e.to_string()))?;
+    server_config.alpn_protocols = vec![b"http/1.1".to_vec()];
+
+    Ok(tokio_rustls::TlsAcceptor::from(Arc::new(server_config)))
+}
+
+/// What the metrics endpoint serves: this process's registry or, in
+/// supervisor mode, the merged metrics of the runner processes.
+#[derive(Clone)]
+pub enum MetricsSource {
+    Registry(Arc<Mutex<Registry>>),
+    #[cfg(feature = "supervisor")]
+    Runners(Arc<crate::supervisor::RunnerScraper>),
+}
+
+impl MetricsSource {
+    /// The current metrics and their content type: OpenMetrics when
+    /// requested and available, else the Prometheus text format. Merged
+    /// runner metrics are always served as Prometheus text.
+    async fn encode(&self, openmetrics: bool) -> (Vec<u8>, String) {
+        match self {
+            MetricsSource::Registry(registry) => {
+                let metric_families = registry.lock().unwrap().gather();
+                if openmetrics {
+                    let text = crate::openmetrics::encode(&metric_families);
+                    return (
+                        text.into_bytes(),
+                        crate::openmetrics::CONTENT_TYPE.to_string(),
+                    );
+                }
+                let encoder = TextEncoder::new();
+                let mut buffer = Vec::new();
+                encoder.encode(&metric_families, &mut buffer).unwrap();
+                (buffer, encoder.format_type().to_string())
+            }
+            #[cfg(feature = "supervisor")]
+            MetricsSource::Runners(scraper) => (
+                scraper.scrape().await.into_bytes(),
+                TextEncoder::new().format_type().to_string(),
+            ),
+        }
+    }
+}
+
 /// HTTP handler for the Prometheus metrics endpoint.
+///
+/// Only `GET`/`HEAD /metrics` is served; other paths return 404 and other
+/// methods 405. When `auth` is set, requests without valid credentials get
+/// a 401 with a `WWW-Authenticate` challenge.
 pub async fn metrics_handler(
-    _req: Request<Body>,
+    req: Request<Body>,
     registry: Arc<Mutex<Registry>>,
+    auth: Option<Arc<MetricsAuth>>,
 ) -> Result<Response<Body>, hyper::Error> {
-    let encoder = TextEncoder::new();
-    let metric_families = registry.lock().unwrap().gather();
-    let mut buffer = Vec::new();
-    encoder.encode(&metric_families, &mut buffer).unwrap();
+    metrics_source_handler(req, MetricsSource::Registry(registry), auth).await
+}
+
+/// [`metrics_handler`] serving any [`MetricsSource`].
+pub async fn metrics_source_handler(
+    req: Request<Body>,
+    source: MetricsSource,
+    auth: Option<Arc<MetricsAuth>>,
+) -> Result<Response<Body>, hyper::Error> {
+    if req.uri().path() != "/metrics" {
+        return Ok(Response::builder()
+            .status(StatusCode::NOT_FOUND)
+            .body(Body::from("Not Found. Metrics are served at /metrics\n"))
+            .unwrap());
+    }
+
+    if req.method() != Method::GET && req.method() != Method::HEAD {
+        return Ok(Response::builder()
+            .status(StatusCode::METHOD_NOT_ALLOWED)
+            .header("Allow", "GET, HEAD")
+            .body(Body::empty())
+            .unwrap());
+    }
+
+    if let Some(auth) = &auth {
+        let header = req
+            .headers()
+            .get(hyper::header::AUTHORIZATION)
+            .and_then(|v| v.to_str().ok());
+        if !auth.authorize(header) {
+            return Ok(Response::builder()
+                .status(StatusCode::UNAUTHORIZED)
+                .header("WWW-Authenticate", auth.challenge())
+                .body(Body::from("Unauthorized\n"))
+                .unwrap());
+        }
+    }
+
+    let openmetrics = crate::openmetrics::wants_openmetrics(
+        req.headers()
+            .get(hyper::header::ACCEPT)
+            .and_then(|v| v.to_str().ok()),
+    );
+    let (buffer, content_type) = source.encode(openmetrics).await;
 
     let response = Response::builder()
         .status(200)
-        .header("Content-Type", encoder.format_type())
+        .header("Content-Type", content_type)
         .body(Body::from(buffer))
         .unwrap();
 
     Ok(response)
 }
 
-/// Starts the Prometheus metrics HTTP server.
-pub async fn start_metrics_server(port: u16, registry: Arc<Mutex<Registry>>) {
-    let addr = ([0, 0, 0, 0], port).into();
-
-    let make_svc = make_service_fn(move |_conn| {
-        let registry_clone = registry.clone();
-        async move {
-            Ok::<_, hyper::Error>(service_fn(move |req| {
-                let registry_clone_inner = registry_clone.clone();
-                async move { metrics_handler(req, registry_clone_inner).await }
-            }))
+/// Starts the Prometheus metrics HTTP(S) server.
+pub async fn start_metrics_server(
+    config: MetricsServerConfig,
+    registry: Arc<Mutex<Registry>>,
+) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+    start_metrics_server_for(config, MetricsSource::Registry(registry)).await
+}
+
+/// Starts the Prometheus metrics HTTP(S) server for any [`MetricsSource`].
+pub async fn start_metrics_server_for(
+    config: MetricsServerConfig,
+    source: MetricsSource,
+) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+    let auth = config.auth.clone().map(Arc::new);
+    let make_service = move || {
+        let source = source.clone();
+        let auth = auth.clone();
+        service_fn(move |req| metrics_source_handler(req, source.clone(), auth.clone()))
+    };
+
+    match (&config.tls_cert_path, &config.tls_key_path) {
+        (Some(cert), Some(key)) => {
+            let acceptor = load_tls_acceptor(cert, key)?;
+            let listener = tokio::net::TcpListener::bind(config.addr).await?;
+            info!(
+                addr = %config.addr,
+                tls = true,
+                auth = config.auth.is_some(),
+                "Metrics server listening"
+            );
+
+            loop {
+                let (stream, peer) = match listener.accept().await {
+                    Ok(conn) => conn,
+                    Err(e) => {
+                        error!(error = %e, "Metrics server accept error");
+                        continue;
+                    }
+                };
+                let acceptor = acceptor.clone();
+                let service = make_service();
+                tokio::spawn(async move {
+                    match acceptor.accept(stream).await {
+                        Ok(tls_stream) => {
+                            if let Err(e) = Http::new().serve_connection(tls_stream, service).await
+                            {
+                                debug!(peer = %peer, error = %e, "Metrics connection error");
+                            }
+                        }
+                        Err(e) => debug!(peer = %peer, error = %e, "Metrics TLS handshake failed"),
+                    }
+                });
+            }
         }
-    });
+        _ => {
+            let make_svc = make_service_fn(move |_conn| {
+                let service = make_service();
+                async move { Ok::<_, hyper::Error>(service) }
+            });
+
+            let server = Server::try_bind(&config.addr)?.serve(make_svc);
+            info!(
+                addr = %config.addr,
+                tls = false,
+                auth = config.auth.is_some(),
+                "Metrics server listening"
+            );
+            server.await?;
+            Ok(())
+        }
+    }
+}
 
-    let server = Server::bind(&addr).serve(make_svc);
-    info!(
-        port = port,
-        addr = %addr,
-        "Metrics server listening"
-    );
+/// Requests sent and HTTP responses received so far, summed over all label
+/// sets. Transport errors (`status_code="error"`) are not responses.
+pub fn request_outcome_totals() -> (u64, u64) {
+    use prometheus::core::Collector;
+
+    let sent_families = REQUEST_TOTAL.collect();
+    let sent = sent_families
+        .iter()
+        .flat_map(|family| family.get_metric())
+        .map(|m| m.get_counter().get_value() as u64)
+        .sum();
+    let status_families = REQUEST_STATUS_CODES.collect();
+    let responses = status_families
+        .iter()
+        .flat_map(|family| family.get_metric())
+        .filter(|m| {
+            !m.get_label()
+                .iter()
+                .any(|l| l.get_name() == "status_code" && l.get_value() == "error")
+        })
+        .map(|m| m.get_counter().get_value() as u64)
+        .sum();
+    (sent, responses)
+}
 
-    if let Err(e) = server.await {
-        error!(error = %e, "Metrics server error");
+/// Sum (seconds) and count of `REQUEST_DURATION_SECONDS` over all labels.
+pub fn request_duration_totals() -> (f64, u64) {
+    use prometheus::core::Collector;
+
+    REQUEST_DURATION_SECONDS
+        .collect()
+        .iter()
+        .flat_map(|family| family.get_metric())
+        .fold((0.0, 0), |(sum, count), m| {
+            let histogram = m.get_histogram();
+            (
+                sum + histogram.get_sample_sum(),
+                count + histogram.get_sample_count(),
+            )
+        })
+}
+
+/// Successful and failed executions per scenario, summed over every node
+/// and run label of `SCENARIO_EXECUTIONS_TOTAL`.
+pub fn scenario_execution_totals() -> HashMap<String, (u64, u64)> {
+    use prometheus::core::Collector;
+
+    let mut totals: HashMap<String, (u64, u64)> = HashMap::new();
+    for family in SCENARIO_EXECUTIONS_TOTAL.collect() {
+        for metric in family.get_metric() {
+            let label = |name: &str| {
+                metric
+                    .get_label()
+                    .iter()
+                    .find(|l| l.get_name() == name)
+                    .map(|l| l.get_value().to_string())
+                    .unwrap_or_default()
+            };
+            let count = metric.get_counter().get_value() as u64;
+            let entry = totals.entry(label("scenario")).or_default();
+            if label("status") == "success" {
+                entry.0 += count;
+            } else {
+                entry.1 += count;
+            }
+        }
     }
+    totals
+}
+
+/// `SCENARIO_ASSERTIONS_TOTAL` (passed, failed) per scenario, summed over
+/// steps and the other labels.
+pub fn scenario_assertion_totals() -> HashMap<String, (u64, u64)> {
+    use prometheus::core::Collector;
+
+    let mut totals: HashMap<String, (u64, u64)> = HashMap::new();
+    for family in SCENARIO_ASSERTIONS_TOTAL.collect() {
+        for metric in family.get_metric() {
+            let label = |name: &str| {
+                metric
+                    .get_label()
+                    .iter()
+                    .find(|l| l.get_name() == name)
+                    .map(|l| l.get_value().to_string())
+                    .unwrap_or_default()
+            };
+            let count = metric.get_counter().get_value() as u64;
+            let entry = totals.entry(label("scenario")).or_default();
+            if label("result") == "passed" {
+                entry.0 += count;
+            } else {
+                entry.1 += count;
+            }
+        }
+    }
+    totals
 }
 
 /// Gathers and encodes metrics as a string for final output.
@@ -430,3 +1508,239 @@ pub fn update_memory_metrics() -> Result<(), Box<dyn std::error::Error + Send +
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serial_test::serial;
+
+    fn test_registry() -> Arc<Mutex<Registry>> {
+        let registry = Registry::new();
+        let counter = IntCounter::new("test_requests_total", "test").unwrap();
+        counter.inc();
+        registry.register(Box::new(counter)).unwrap();
+        Arc::new(Mutex::new(registry))
+    }
+
+    fn request(path: &str, authorization: Option<&str>) -> Request<Body> {
+        let mut builder = Request::builder().uri(path);
+        if let Some(value) = authorization {
+            builder = builder.header("Authorization", value);
+        }
+        builder.body(Body::empty()).unwrap()
+    }
+
+    #[test]
+    fn parses_metric_tag_keys() {
+        assert_eq!(
+            parse_metric_tag_keys(" team, tier ,team,,"),
+            vec!["team".to_string(), "tier".to_string()]
+        );
+        // Invalid label names and built-in labels are skipped
+        assert_eq!(
+            parse_metric_tag_keys("owner-team,1st,__x,scenario,run_id,cost_center"),
+            vec!["cost_center".to_string()]
+        );
+        assert!(parse_metric_tag_keys("").is_empty());
+    }
+
+    #[test]
+    fn parses_metric_labels() {
+        let labels = parse_metric_labels(
+            " env:staging, team : payments,bad,run_id:x,1st:y,cost:a:b,tier:z",
+            &["tier".to_string()],
+        );
+        assert_eq!(
+            labels,
+            HashMap::from([
+                ("env".to_string(), "staging".to_string()),
+                ("team".to_string(), "payments".to_string()),
+                ("cost".to_string(), "a:b".to_string()),
+            ])
+        );
+        assert!(parse_metric_labels("", &[]).is_empty());
+
+        // Constant labels end up on every series of a registered metric
+        let counter =
+            IntCounterVec::new(Opts::new("labelled_total", "test"), &["node_id"]).unwrap();
+        counter.with_label_values(&["n1"]).inc();
+        let registry = labelled_registry(labels);
+        registry.register(Box::new(counter)).unwrap();
+        let mut out = Vec::new();
+        TextEncoder::new()
+            .encode(&registry.gather(), &mut out)
+            .unwrap();
+        let out = String::from_utf8(out).unwrap();
+        let series = out
+            .lines()
+            .find(|line| line.starts_with("labelled_total{"))
+            .unwrap();
+        for pair in [
+            r#"cost="a:b""#,
+            r#"env="staging""#,
+            r#"node_id="n1""#,
+            r#"team="payments""#,
+        ] {
+            assert!(series.contains(pair), "{}", out);
+        }
+    }
+
+    #[test]
+    fn suffixes_namespace_with_run_id() {
+        assert_eq!(metric_namespace("rust_loadtest", None), "rust_loadtest");
+        assert_eq!(
+            metric_namespace("rust_loadtest", Some(" ")),
+            "rust_loadtest"
+        );
+        assert_eq!(
+            metric_namespace("rust_loadtest", Some("nightly-42.eu")),
+            "rust_loadtest_nightly_42_eu"
+        );
+    }
+
+    #[tokio::test]
+    async fn negotiates_openmetrics() {
+        let mut req = request("/metrics", None);
+        req.headers_mut().insert(
+            hyper::header::ACCEPT,
+            "application/openmetrics-text; version=1.0.0, text/plain; q=0.5"
+                .parse()
+                .unwrap(),
+        );
+        let resp = metrics_handler(req, test_registry(), None).await.unwrap();
+        assert_eq!(
+            resp.headers()["Content-Type"],
+            crate::openmetrics::CONTENT_TYPE
+        );
+        let body = hyper::body::to_bytes(resp.into_body()).await.unwrap();
+        let body = String::from_utf8_lossy(&body);
+        assert!(body.contains("# TYPE test_requests counter"), "{}", body);
+        assert!(body.contains("test_requests_total 1.0"), "{}", body);
+        assert!(body.contains("test_requests_created "), "{}", body);
+        assert!(body.ends_with("# EOF\n"));
+
+        let text = metrics_handler(request("/metrics", None), test_registry(), None)
+            .await
+            .unwrap();
+        assert_eq!(
+            text.headers()["Content-Type"],
+            TextEncoder::new().format_type()
+        );
+    }
+
+    #[tokio::test]
+    async fn serves_metrics_only_on_metrics_path() {
+        let ok = metrics_handler(request("/metrics", None), test_registry(), None)
+            .await
+            .unwrap();
+        assert_eq!(ok.status(), StatusCode::OK);
+        let body = hyper::body::to_bytes(ok.into_body()).await.unwrap();
+        assert!(String::from_utf8_lossy(&body).contains("test_requests_total 1"));
+
+        for path in ["/", "/metrics/extra", "/favicon.ico"] {
+            let resp = metrics_handler(request(path, None), test_registry(), None)
+                .await
+                .unwrap();
+            assert_eq!(resp.status(), StatusCode::NOT_FOUND, "path {}", path);
+        }
+    }
+
+    #[tokio::test]
+    async fn enforces_bearer_auth() {
+        let auth = Some(Arc::new(MetricsAuth::Bearer("s3cret".to_string())));
+        let cases = [
+            (None, StatusCode::UNAUTHORIZED),
+            (Some("Bearer wrong"), StatusCode::UNAUTHORIZED),
+            (Some("Basic s3cret"), StatusCode::UNAUTHORIZED),
+            (Some("Bearer s3cret"), StatusCode::OK),
+        ];
+        for (header, expected) in cases {
+            let resp = metrics_handler(request("/metrics", header), test_registry(), auth.clone())
+                .await
+                .unwrap();
+            assert_eq!(resp.status(), expected, "header {:?}", header);
+            if expected == StatusCode::UNAUTHORIZED {
+                assert!(resp.headers().contains_key("WWW-Authenticate"));
+            }
+        }
+    }
+
+    #[test]
+    fn basic_auth_checks_credentials() {
+        let auth = MetricsAuth::Basic {
+            username: "prom".to_string(),
+            password: "pa:ss".to_string(),
+        };
+        // base64("prom:pa:ss")
+        assert!(auth.authorize(Some("Basic cHJvbTpwYTpzcw==")));
+        // base64("prom:nope")
+        assert!(!auth.authorize(Some("Basic cHJvbTpub3Bl")));
+        assert!(!auth.authorize(None));
+    }
+
+    #[test]
+    fn parses_metrics_addr() {
+        assert_eq!(
+            parse_metrics_addr("127.0.0.1:9464").unwrap(),
+            SocketAddr::from(([127, 0, 0, 1], 9464))
+        );
+        assert_eq!(
+            parse_metrics_addr("9100").unwrap(),
+            SocketAddr::from(([0, 0, 0, 0], 9100))
+        );
+        assert_eq!(
+            parse_metrics_addr(":9100").unwrap(),
+            SocketAddr::from(([0, 0, 0, 0], 9100))
+        );
+        assert!(parse_metrics_addr("[::1]:9090").unwrap().is_ipv6());
+        assert!(parse_metrics_addr("localhost").is_err());
+    }
+
+    #[test]
+    #[serial]
+    fn config_from_env() {
+        let vars = [
+            "METRICS_ADDR",
+            "METRICS_TLS_CERT",
+            "METRICS_TLS_KEY",
+            "METRICS_BASIC_AUTH",
+            "METRICS_BEARER_TOKEN",
+        ];
+        for var in vars {
+            env::remove_var(var);
+        }
+        assert_eq!(
+            MetricsServerConfig::from_env().unwrap(),
+            MetricsServerConfig::default()
+        );
+
+        env::set_var("METRICS_ADDR", "127.0.0.1:9100");
+        env::set_var("METRICS_BASIC_AUTH", "prom:secret");
+        let config = MetricsServerConfig::from_env().unwrap();
+        assert_eq!(config.addr, SocketAddr::from(([127, 0, 0, 1], 9100)));
+        assert_eq!(
+            config.auth,
+            Some(MetricsAuth::Basic {
+                username: "prom".to_string(),
+                password: "secret".to_string()
+            })
+        );
+
+        env::set_var("METRICS_BEARER_TOKEN", "t");
+        assert!(matches!(
+            MetricsServerConfig::from_env(),
+            Err(MetricsServerError::ConflictingAuth)
+        ));
+        env::remove_var("METRICS_BEARER_TOKEN");
+
+        env::set_var("METRICS_TLS_CERT", "/tmp/cert.pem");
+        assert!(matches!(
+            MetricsServerConfig::from_env(),
+            Err(MetricsServerError::IncompleteTls)
+        ));
+
+        for var in vars {
+            env::remove_var(var);
+        }
+    }
+}
@@ -0,0 +1,302 @@
+//! Request middleware for scenario steps.
+//!
+//! A [`Middleware`] sees every HTTP request a [`crate::executor::ScenarioExecutor`]
+//! sends, after the step's method, URL, headers and body have been resolved,
+//! and every response (or transport error) that comes back. Middleware run in
+//! registration order on the way out and in reverse order on the way back, so
+//! cross-cutting concerns — request signing, header injection, body
+//! compression, custom auth, response auditing — can be added without
+//! touching the executor:
+//!
+//! ```ignore
+//! use rust_loadtest::middleware::{Middleware, MiddlewareError, RequestContext};
+//! use rust_loadtest::plugin::GLOBAL_PLUGIN_REGISTRY;
+//! use std::sync::Arc;
+//!
+//! struct Signer { key: String }
+//!
+//! impl Middleware for Signer {
+//!     fn name(&self) -> &str { "signer" }
+//!
+//!     fn on_request(
+//!         &self,
+//!         request: &mut reqwest::Request,
+//!         _ctx: &RequestContext,
+//!     ) -> Result<(), MiddlewareError> {
+//!         let body = request.body().and_then(|b| b.as_bytes()).unwrap_or_default();
+//!         let signature = sign(&self.key, request.url().path(), body);
+//!         request.headers_mut().insert("X-Signature", signature.parse().unwrap());
+//!         Ok(())
+//!     }
+//! }
+//!
+//! GLOBAL_PLUGIN_REGISTRY
+//!     .write()
+//!     .unwrap()
+//!     .register_middleware(Arc::new(Signer { key: "secret".into() }));
+//! ```
+//!
+//! Middleware registered on the [`crate::plugin::GLOBAL_PLUGIN_REGISTRY`]
+//! apply to every executor created afterwards; an executor can also be given
+//! its own chain with [`crate::executor::ScenarioExecutor::with_middleware`].
+
+use std::sync::Arc;
+
+use reqwest::header::{HeaderMap, HeaderName, HeaderValue};
+use thiserror::Error;
+
+/// Errors raised by middleware; the step fails with this message.
+#[derive(Error, Debug)]
+pub enum MiddlewareError {
+    #[error("Middleware '{name}' rejected the request: {message}")]
+    Rejected { name: String, message: String },
+
+    #[error("Invalid header '{0}'")]
+    InvalidHeader(String),
+}
+
+/// Where a request comes from.
+#[derive(Debug, Clone, Copy)]
+pub struct RequestContext<'a> {
+    pub scenario: &'a str,
+    pub step: &'a str,
+}
+
+/// What came back for a request.
+#[derive(Debug, Clone, Copy)]
+pub struct ResponseInfo<'a> {
+    /// Status code, or `None` when the request failed before a response.
+    pub status: Option<u16>,
+    /// Response headers (empty on transport errors).
+    pub headers: &'a HeaderMap,
+    /// Time from send to response headers.
+    pub response_time_ms: u64,
+    /// Transport error message, if the request failed.
+    pub error: Option<&'a str>,
+}
+
+/// A request transformer and/or response observer.
+pub trait Middleware: Send + Sync {
+    /// Middleware name (used in logs and errors).
+    fn name(&self) -> &str;
+
+    /// Modify the request before it is sent. Returning an error fails the
+    /// step without sending anything.
+    fn on_request(
+        &self,
+        _request: &mut reqwest::Request,
+        _ctx: &RequestContext,
+    ) -> Result<(), MiddlewareError> {
+        Ok(())
+    }
+
+    /// Observe the response (or transport error).
+    fn on_response(&self, _response: &ResponseInfo, _ctx: &RequestContext) {}
+}
+
+/// Ordered list of middleware.
+#[derive(Clone, Default)]
+pub struct MiddlewareChain {
+    layers: Vec<Arc<dyn Middleware>>,
+}
+
+impl MiddlewareChain {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Appends `middleware`; it runs after those already in the chain.
+    pub fn push(&mut self, middleware: Arc<dyn Middleware>) {
+        self.layers.push(middleware);
+    }
+
+    /// Builder form of [`MiddlewareChain::push`].
+    pub fn with(mut self, middleware: Arc<dyn Middleware>) -> Self {
+        self.push(middleware);
+        self
+    }
+
+    pub fn len(&self) -> usize {
+        self.layers.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.layers.is_empty()
+    }
+
+    /// Runs every `on_request` in order, stopping at the first error.
+    pub fn apply(
+        &self,
+        request: &mut reqwest::Request,
+        ctx: &RequestContext,
+    ) -> Result<(), MiddlewareError> {
+        for layer in &self.layers {
+            layer.on_request(request, ctx)?;
+        }
+        Ok(())
+    }
+
+    /// Runs every `on_response` in reverse order.
+    pub fn observe(&self, response: &ResponseInfo, ctx: &RequestContext) {
+        for layer in self.layers.iter().rev() {
+            layer.on_response(response, ctx);
+        }
+    }
+}
+
+impl std::fmt::Debug for MiddlewareChain {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_list()
+            .entries(self.layers.iter().map(|layer| layer.name()))
+            .finish()
+    }
+}
+
+/// Adds fixed headers to every request, replacing existing values.
+pub struct HeaderInjector {
+    headers: Vec<(HeaderName, HeaderValue)>,
+}
+
+impl HeaderInjector {
+    pub fn new<'a>(
+        headers: impl IntoIterator<Item = (&'a str, &'a str)>,
+    ) -> Result<Self, MiddlewareError> {
+        let headers = headers
+            .into_iter()
+            .map(|(name, value)| {
+                let parsed_name = HeaderName::from_bytes(name.as_bytes())
+                    .map_err(|_| MiddlewareError::InvalidHeader(name.to_string()))?;
+                let parsed_value = HeaderValue::from_str(value)
+                    .map_err(|_| MiddlewareError::InvalidHeader(name.to_string()))?;
+                Ok((parsed_name, parsed_value))
+            })
+            .collect::<Result<_, MiddlewareError>>()?;
+        Ok(Self { headers })
+    }
+}
+
+impl Middleware for HeaderInjector {
+    fn name(&self) -> &str {
+        "header-injector"
+    }
+
+    fn on_request(
+        &self,
+        request: &mut reqwest::Request,
+        _ctx: &RequestContext,
+    ) -> Result<(), MiddlewareError> {
+        for (name, value) in &self.headers {
+            request.headers_mut().insert(name.clone(), value.clone());
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Mutex;
+
+    /// Records the order in which hooks run.
+    struct Recorder {
+        name: &'static str,
+        log: Arc<Mutex<Vec<String>>>,
+    }
+
+    impl Middleware for Recorder {
+        fn name(&self) -> &str {
+            self.name
+        }
+
+        fn on_request(
+            &self,
+            request: &mut reqwest::Request,
+            _ctx: &RequestContext,
+        ) -> Result<(), MiddlewareError> {
+            self.log.lock().unwrap().push(format!("req:{}", self.name));
+            if request.url().path() == "/forbidden" {
+                return Err(MiddlewareError::Rejected {
+                    name: self.name.to_string(),
+                    message: "forbidden path".to_string(),
+                });
+            }
+            Ok(())
+        }
+
+        fn on_response(&self, _response: &ResponseInfo, _ctx: &RequestContext) {
+            self.log.lock().unwrap().push(format!("resp:{}", self.name));
+        }
+    }
+
+    fn request(path: &str) -> reqwest::Request {
+        reqwest::Client::new()
+            .get(format!("http://localhost{}", path))
+            .build()
+            .unwrap()
+    }
+
+    const CTX: RequestContext<'static> = RequestContext {
+        scenario: "s",
+        step: "t",
+    };
+
+    #[test]
+    fn runs_requests_in_order_and_responses_in_reverse() {
+        let log = Arc::new(Mutex::new(Vec::new()));
+        let recorder = |name| {
+            Arc::new(Recorder {
+                name,
+                log: log.clone(),
+            })
+        };
+        let chain = MiddlewareChain::new()
+            .with(recorder("a"))
+            .with(recorder("b"));
+
+        chain.apply(&mut request("/"), &CTX).unwrap();
+        let headers = HeaderMap::new();
+        chain.observe(
+            &ResponseInfo {
+                status: Some(200),
+                headers: &headers,
+                response_time_ms: 5,
+                error: None,
+            },
+            &CTX,
+        );
+        assert_eq!(
+            *log.lock().unwrap(),
+            vec!["req:a", "req:b", "resp:b", "resp:a"]
+        );
+    }
+
+    #[test]
+    fn stops_at_first_error() {
+        let log = Arc::new(Mutex::new(Vec::new()));
+        let chain = MiddlewareChain::new()
+            .with(Arc::new(Recorder {
+                name: "guard",
+                log: log.clone(),
+            }))
+            .with(Arc::new(Recorder {
+                name: "never",
+                log: log.clone(),
+            }));
+
+        let err = chain.apply(&mut request("/forbidden"), &CTX).unwrap_err();
+        assert!(err.to_string().contains("forbidden path"));
+        assert_eq!(*log.lock().unwrap(), vec!["req:guard"]);
+    }
+
+    #[test]
+    fn header_injector_overrides_headers() {
+        let injector = HeaderInjector::new([("X-Tenant", "blue")]).unwrap();
+        let mut req = request("/");
+        req.headers_mut()
+            .insert("x-tenant", HeaderValue::from_static("red"));
+        injector.on_request(&mut req, &CTX).unwrap();
+        assert_eq!(req.headers()["x-tenant"], "blue");
+
+        assert!(HeaderInjector::new([("bad header", "v")]).is_err());
+    }
+}
@@ -21,8 +21,8 @@ use std::sync::Arc;
 /// use rust_loadtest::scenario::Scenario;
 ///
 /// let scenarios = vec![
-///     Scenario { name: "Read".to_string(), weight: 80.0, steps: vec![] },
-///     Scenario { name: "Write".to_string(), weight: 20.0, steps: vec![] },
+///     Scenario { name: "Read".to_string(), weight: 80.0, steps: vec![], network_profile: None, tags: Default::default(), max_concurrent: None, depends_on: vec![], iterations: None, rps: None, retry: None, assertion_sample_rate: 1.0, client: None, headers: Default::default(), optional: false, data_source: None },
+///     Scenario { name: "Write".to_string(), weight: 20.0, steps: vec![], network_profile: None, tags: Default::default(), max_concurrent: None, depends_on: vec![], iterations: None, rps: None, retry: None, assertion_sample_rate: 1.0, client: None, headers: Default::default(), optional: false, data_source: None },
 /// ];
 ///
 /// let selector = ScenarioSelector::new(scenarios);
@@ -85,8 +85,7 @@ impl ScenarioSelector {
     ///
     /// Uses cumulative weight distribution for O(log n) selection.
     pub fn select(&self) -> &Scenario {
-        let mut rng = rand::thread_rng();
-        let random = rng.gen_range(0.0..self.total_weight);
+        let random = crate::seed::with_rng(|rng| rng.gen_range(0.0..self.total_weight));
 
         // Binary search for the selected scenario
         let index = self
@@ -146,8 +145,8 @@ impl ScenarioSelector {
 /// use rust_loadtest::scenario::Scenario;
 ///
 /// let scenarios = vec![
-///     Scenario { name: "S1".to_string(), weight: 1.0, steps: vec![] },
-///     Scenario { name: "S2".to_string(), weight: 1.0, steps: vec![] },
+///     Scenario { name: "S1".to_string(), weight: 1.0, steps: vec![], network_profile: None, tags: Default::default(), max_concurrent: None, depends_on: vec![], iterations: None, rps: None, retry: None, assertion_sample_rate: 1.0, client: None, headers: Default::default(), optional: false, data_source: None },
+///     Scenario { name: "S2".to_string(), weight: 1.0, steps: vec![], network_profile: None, tags: Default::default(), max_concurrent: None, depends_on: vec![], iterations: None, rps: None, retry: None, assertion_sample_rate: 1.0, client: None, headers: Default::default(), optional: false, data_source: None },
 /// ];
 ///
 /// let distributor = RoundRobinDistributor::new(scenarios);
@@ -382,16 +381,52 @@ mod tests {
                 name: "Read".to_string(),
                 weight: 80.0,
                 steps: vec![],
+                network_profile: None,
+                tags: HashMap::new(),
+                max_concurrent: None,
+                depends_on: Vec::new(),
+                iterations: None,
+                retry: None,
+                assertion_sample_rate: 1.0,
+                client: None,
+                headers: Default::default(),
+                optional: false,
+                data_source: None,
+                rps: None,
             },
             Scenario {
                 name: "Write".to_string(),
                 weight: 15.0,
                 steps: vec![],
+                network_profile: None,
+                tags: HashMap::new(),
+                max_concurrent: None,
+                depends_on: Vec::new(),
+                iterations: None,
+                retry: None,
+                assertion_sample_rate: 1.0,
+                client: None,
+                headers: Default::default(),
+                optional: false,
+                data_source: None,
+                rps: None,
             },
             Scenario {
                 name: "Delete".to_string(),
                 weight: 5.0,
                 steps: vec![],
+                network_profile: None,
+                tags: HashMap::new(),
+                max_concurrent: None,
+                depends_on: Vec::new(),
+                iterations: None,
+                retry: None,
+                assertion_sample_rate: 1.0,
+                client: None,
+                headers: Default::default(),
+                optional: false,
+                data_source: None,
+                rps: None,
             },
         ]
     }
@@ -468,6 +503,18 @@ mod tests {
             name: "Test".to_string(),
             weight: -1.0,
             steps: vec![],
+            network_profile: None,
+            tags: HashMap::new(),
+            max_concurrent: None,
+            depends_on: Vec::new(),
+            iterations: None,
+            retry: None,
+            assertion_sample_rate: 1.0,
+            client: None,
+            headers: Default::default(),
+            optional: false,
+            data_source: None,
+            rps: None,
         }];
         ScenarioSelector::new(scenarios);
     }
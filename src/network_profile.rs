@@ -0,0 +1,179 @@
+//! Client-side network emulation for scenarios (`networkProfile`).
+//!
+//! A profile adds per-request latency (with random jitter) before each
+//! request is sent and caps upload and download bandwidth, so a scenario can
+//! behave like a mobile or otherwise slow client. Download caps are enforced
+//! by reading the response body at the capped rate, which holds the
+//! connection open the way a slow reader does and lets TCP backpressure
+//! reach the server. Upload caps are approximated by delaying the send by
+//! the time the body would take to upload.
+//!
+//! Profiles are set per scenario in YAML, either as a preset name or as
+//! explicit values:
+//!
+//! ```yaml
+//! scenarios:
+//!   - name: mobile-checkout
+//!     networkProfile: 3g
+//!   - name: custom
+//!     networkProfile:
+//!       latencyMs: 250
+//!       jitterMs: 50
+//!       downloadKbps: 1000
+//!       uploadKbps: 256
+//! ```
+
+use std::time::Duration;
+
+use rand::Rng;
+use tokio::time::{sleep, Instant};
+
+/// Preset names accepted by [`NetworkProfile::preset`].
+pub const PRESET_NAMES: &[&str] = &["slow-3g", "3g", "4g", "wifi"];
+
+/// Latency and bandwidth limits applied to every request of a scenario.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct NetworkProfile {
+    /// Delay added before each request is sent.
+    pub latency: Duration,
+    /// Random variation applied to `latency` (uniform, ±jitter).
+    pub jitter: Duration,
+    /// Download cap in bytes per second. `None` = unlimited.
+    pub download_bytes_per_sec: Option<u64>,
+    /// Upload cap in bytes per second. `None` = unlimited.
+    pub upload_bytes_per_sec: Option<u64>,
+}
+
+impl NetworkProfile {
+    /// Builds a profile from latency/jitter in milliseconds and bandwidth
+    /// caps in kilobits per second.
+    pub fn from_kbps(
+        latency_ms: u64,
+        jitter_ms: u64,
+        download_kbps: Option<u64>,
+        upload_kbps: Option<u64>,
+    ) -> Self {
+        let kbps_to_bytes = |kbps: u64| (kbps * 1000 / 8).max(1);
+        Self {
+            latency: Duration::from_millis(latency_ms),
+            jitter: Duration::from_millis(jitter_ms),
+            download_bytes_per_sec: download_kbps.map(kbps_to_bytes),
+            upload_bytes_per_sec: upload_kbps.map(kbps_to_bytes),
+        }
+    }
+
+    /// Named presets, loosely following browser devtools throttling profiles.
+    pub fn preset(name: &str) -> Option<Self> {
+        let profile = match name.trim().to_ascii_lowercase().as_str() {
+            "slow-3g" => Self::from_kbps(2000, 200, Some(400), Some(400)),
+            "3g" => Self::from_kbps(563, 100, Some(1600), Some(750)),
+            "4g" => Self::from_kbps(100, 20, Some(12_000), Some(3_000)),
+            "wifi" => Self::from_kbps(20, 5, Some(30_000), Some(15_000)),
+            _ => return None,
+        };
+        Some(profile)
+    }
+
+    /// Latency for one request: `latency` ± a random amount up to `jitter`.
+    pub fn sample_latency(&self) -> Duration {
+        if self.jitter.is_zero() {
+            return self.latency;
+        }
+        let jitter_ms = self.jitter.as_millis() as i64;
+        let offset = crate::seed::with_rng(|rng| rng.gen_range(-jitter_ms..=jitter_ms));
+        let latency_ms = (self.latency.as_millis() as i64 + offset).max(0);
+        Duration::from_millis(latency_ms as u64)
+    }
+
+    /// Time needed to upload `bytes` at the upload cap.
+    pub fn upload_delay(&self, bytes: usize) -> Duration {
+        match self.upload_bytes_per_sec {
+            Some(rate) if bytes > 0 => Duration::from_secs_f64(bytes as f64 / rate as f64),
+            _ => Duration::ZERO,
+        }
+    }
+
+    /// Waits for the emulated latency plus the upload time of a
+    /// `body_len`-byte request body.
+    pub async fn delay_request(&self, body_len: usize) {
+        let delay = self.sample_latency() + self.upload_delay(body_len);
+        if !delay.is_zero() {
+            sleep(delay).await;
+        }
+    }
+
+    /// Reads the response body, pacing reads to the download cap.
+    pub async fn read_text(&self, response: reqwest::Response) -> reqwest::Result<String> {
+        if self.download_bytes_per_sec.is_none() {
+            return response.text().await;
+        }
+        let body = self.read_bytes(response).await?;
+        Ok(String::from_utf8_lossy(&body).into_owned())
+    }
+
+    /// Reads the raw response body, pacing reads to the download cap.
+    pub async fn read_bytes(&self, mut response: reqwest::Response) -> reqwest::Result<Vec<u8>> {
+        if self.download_bytes_per_sec.is_none() {
+            return Ok(response.bytes().await?.to_vec());
+        }
+        let start = Instant::now();
+        let mut body = Vec::new();
+        while let Some(chunk) = response.chunk().await? {
+            body.extend_from_slice(&chunk);
+            self.pace_download(start, body.len() as u64).await;
+        }
+        Ok(body)
+    }
+
+    /// Waits until `received` bytes, counted from `start`, are within the
+    /// download cap.
+    pub async fn pace_download(&self, start: Instant, received: u64) {
+        let Some(rate) = self.download_bytes_per_sec else {
+            return;
+        };
+        let due = Duration::from_secs_f64(received as f64 / rate as f64);
+        let elapsed = start.elapsed();
+        if due > elapsed {
+            sleep(due - elapsed).await;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn presets_resolve() {
+        for name in PRESET_NAMES {
+            assert!(NetworkProfile::preset(name).is_some(), "{}", name);
+        }
+        let three_g = NetworkProfile::preset("3G").unwrap();
+        assert_eq!(three_g.latency, Duration::from_millis(563));
+        assert_eq!(three_g.download_bytes_per_sec, Some(200_000));
+        assert!(NetworkProfile::preset("dialup").is_none());
+    }
+
+    #[test]
+    fn latency_jitter_stays_in_range() {
+        let p = NetworkProfile::from_kbps(100, 30, None, None);
+        for _ in 0..500 {
+            let l = p.sample_latency();
+            assert!(l >= Duration::from_millis(70) && l <= Duration::from_millis(130));
+        }
+        let no_jitter = NetworkProfile::from_kbps(100, 0, None, None);
+        assert_eq!(no_jitter.sample_latency(), Duration::from_millis(100));
+    }
+
+    #[test]
+    fn upload_delay_scales_with_body() {
+        // 80 kbps = 10,000 bytes/sec
+        let p = NetworkProfile::from_kbps(0, 0, None, Some(80));
+        assert_eq!(p.upload_delay(5_000), Duration::from_millis(500));
+        assert_eq!(p.upload_delay(0), Duration::ZERO);
+        assert_eq!(
+            NetworkProfile::default().upload_delay(5_000),
+            Duration::ZERO
+        );
+    }
+}
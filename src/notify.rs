@@ -0,0 +1,271 @@
+//! Completion notification to Slack or any webhook (`NOTIFY_WEBHOOK_URL`).
+//!
+//! When the test finishes, a compact summary — pass/fail, request count,
+//! p50/p95/p99 latency, failed scenario thresholds and a link to the
+//! uploaded HTML report — is posted to the webhook. Slack incoming webhooks
+//! get `{"text": ...}`; other webhooks get the same text plus the full
+//! [`RunSummary`] as JSON. `NOTIFY_ON=failure` only posts for failed runs.
+
+use std::env;
+use std::time::Duration;
+
+use serde_json::json;
+use thiserror::Error;
+
+use crate::run_summary::RunSummary;
+
+const NOTIFY_TIMEOUT: Duration = Duration::from_secs(10);
+
+#[derive(Error, Debug, PartialEq)]
+pub enum NotifyError {
+    #[error("invalid NOTIFY_WEBHOOK_URL '{0}': expected an http(s) URL")]
+    InvalidUrl(String),
+
+    #[error("invalid {name} '{value}': expected {expected}")]
+    InvalidOption {
+        name: &'static str,
+        value: String,
+        expected: &'static str,
+    },
+
+    #[error("webhook request failed: {0}")]
+    Request(String),
+}
+
+/// Body format of the webhook request.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum WebhookFormat {
+    /// `{"text": ...}` for Slack (and Mattermost, Rocket.Chat) incoming webhooks.
+    Slack,
+    /// The text plus the whole summary.
+    Json,
+}
+
+/// Posts the run summary to a webhook.
+#[derive(Debug, Clone, PartialEq)]
+pub struct WebhookNotifier {
+    pub url: String,
+    pub format: WebhookFormat,
+    /// Only notify when the run failed.
+    pub failures_only: bool,
+}
+
+impl WebhookNotifier {
+    /// Reads `NOTIFY_WEBHOOK_URL`, `NOTIFY_WEBHOOK_FORMAT` (`slack` or
+    /// `json`, default `slack` for `hooks.slack.com` and `json` otherwise)
+    /// and `NOTIFY_ON` (`always` or `failure`); `None` without a URL.
+    pub fn from_env() -> Result<Option<Self>, NotifyError> {
+        let var = |name: &str| env::var(name).ok().filter(|v| !v.trim().is_empty());
+        let Some(url) = var("NOTIFY_WEBHOOK_URL") else {
+            return Ok(None);
+        };
+        let url = url.trim().to_string();
+        let parsed = reqwest::Url::parse(&url).map_err(|_| NotifyError::InvalidUrl(url.clone()))?;
+        if !matches!(parsed.scheme(), "http" | "https") {
+            return Err(NotifyError::InvalidUrl(url));
+        }
+
+        let format = match var("NOTIFY_WEBHOOK_FORMAT").map(|f| f.trim().to_lowercase()) {
+            None if parsed.host_str() == Some("hooks.slack.com") => WebhookFormat::Slack,
+            None => WebhookFormat::Json,
+            Some(f) if f == "slack" => WebhookFormat::Slack,
+            Some(f) if f == "json" => WebhookFormat::Json,
+            Some(value) => {
+                return Err(NotifyError::InvalidOption {
+                    name: "NOTIFY_WEBHOOK_FORMAT",
+                    value,
+                    expected: "slack or json",
+                })
+            }
+        };
+        let failures_only = match var("NOTIFY_ON").map(|v| v.trim().to_lowercase()) {
+            None => false,
+            Some(v) if v == "always" => false,
+            Some(v) if v == "failure" => true,
+            Some(value) => {
+                return Err(NotifyError::InvalidOption {
+                    name: "NOTIFY_ON",
+                    value,
+                    expected: "always or failure",
+                })
+            }
+        };
+
+        Ok(Some(Self {
+            url,
+            format,
+            failures_only,
+        }))
+    }
+
+    /// Returns true if a run with this outcome is notified.
+    pub fn should_notify(&self, passed: bool) -> bool {
+        !(passed && self.failures_only)
+    }
+
+    /// Request body for `summary`.
+    pub fn payload(&self, summary: &RunSummary, report_url: Option<&str>) -> serde_json::Value {
+        let text = message(summary, report_url);
+        match self.format {
+            WebhookFormat::Slack => json!({ "text": text }),
+            WebhookFormat::Json => json!({
+                "text": text,
+                "report_url": report_url,
+                "summary": summary,
+            }),
+        }
+    }
+
+    /// Posts the summary unless [`should_notify`](Self::should_notify) says
+    /// otherwise.
+    pub async fn notify(
+        &self,
+        summary: &RunSummary,
+        report_url: Option<&str>,
+    ) -> Result<(), NotifyError> {
+        if !self.should_notify(summary.passed) {
+            return Ok(());
+        }
+        let response = reqwest::Client::new()
+            .post(&self.url)
+            .timeout(NOTIFY_TIMEOUT)
+            .json(&self.payload(summary, report_url))
+            .send()
+            .await
+            .map_err(|e| NotifyError::Request(e.to_string()))?;
+        if !response.status().is_success() {
+            return Err(NotifyError::Request(format!("HTTP {}", response.status())));
+        }
+        Ok(())
+    }
+}
+
+/// The compact summary: verdict line, failed thresholds, report link.
+pub fn message(summary: &RunSummary, report_url: Option<&str>) -> String {
+    let mut text = if summary.passed {
+        format!("✅ Load test {} PASSED", summary.run_id)
+    } else {
+        format!(
+            "❌ Load test {} FAILED ({})",
+            summary.run_id, summary.outcome
+        )
+    };
+    text.push_str(&format!(
+        " on {}: {} requests",
+        summary.node_id, summary.requests
+    ));
+    if let Some(latency) = &summary.latency {
+        text.push_str(&format!(
+            ", p50 {:.1} ms, p95 {:.1} ms, p99 {:.1} ms",
+            latency.p50_ms, latency.p95_ms, latency.p99_ms
+        ));
+    }
+    if !summary.message.is_empty() {
+        text.push_str(&format!("\n{}", summary.message));
+    }
+    for threshold in summary.thresholds.iter().filter(|t| !t.passed) {
+        let rate = threshold
+            .success_rate
+            .map_or_else(|| "no executions".to_string(), |r| format!("{:.3}%", r));
+        text.push_str(&format!(
+            "\n• {}: {}, required {}",
+            threshold.scenario, rate, threshold.required
+        ));
+    }
+    if let Some(url) = report_url {
+        text.push_str(&format!("\nReport: {}", url));
+    }
+    text
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::run_summary::{LatencySummary, ThresholdSummary};
+
+    fn summary(passed: bool) -> RunSummary {
+        RunSummary {
+            run_id: "nightly-42".to_string(),
+            node_id: "node-1".to_string(),
+            finished_at: 0,
+            passed,
+            outcome: if passed {
+                "success"
+            } else {
+                "threshold_failed"
+            }
+            .to_string(),
+            exit_code: if passed { 0 } else { 3 },
+            message: String::new(),
+            requests: 1200,
+            responses: 1200,
+            latency: Some(LatencySummary {
+                count: 1200,
+                mean_ms: 12.0,
+                p50_ms: 10.0,
+                p90_ms: 20.0,
+                p95_ms: 30.0,
+                p99_ms: 40.0,
+                max_ms: 50.0,
+            }),
+            scenarios: Vec::new(),
+            steps: Vec::new(),
+            thresholds: vec![ThresholdSummary {
+                scenario: "checkout".to_string(),
+                required: "successRate > 99.5%".to_string(),
+                success_rate: Some(98.0),
+                passed,
+            }],
+            assertions: Vec::new(),
+            seed: None,
+        }
+    }
+
+    #[test]
+    fn formats_compact_message() {
+        let text = message(&summary(false), Some("https://example.com/report.html"));
+        let lines: Vec<&str> = text.lines().collect();
+        assert_eq!(
+            lines[0],
+            "❌ Load test nightly-42 FAILED (threshold_failed) on node-1: 1200 requests, \
+             p50 10.0 ms, p95 30.0 ms, p99 40.0 ms"
+        );
+        assert_eq!(
+            lines[1],
+            "• checkout: 98.000%, required successRate > 99.5%"
+        );
+        assert_eq!(lines[2], "Report: https://example.com/report.html");
+
+        let passed = message(&summary(true), None);
+        assert!(passed.starts_with("✅ Load test nightly-42 PASSED"));
+        assert_eq!(passed.lines().count(), 1);
+    }
+
+    #[test]
+    fn builds_payload_per_format() {
+        let mut notifier = WebhookNotifier {
+            url: "https://hooks.slack.com/services/x".to_string(),
+            format: WebhookFormat::Slack,
+            failures_only: false,
+        };
+        let slack = notifier.payload(&summary(true), None);
+        assert_eq!(slack.as_object().unwrap().len(), 1);
+        assert!(slack["text"].as_str().unwrap().contains("PASSED"));
+
+        notifier.format = WebhookFormat::Json;
+        let generic = notifier.payload(&summary(true), Some("https://r"));
+        assert_eq!(generic["report_url"], "https://r");
+        assert_eq!(generic["summary"]["run_id"], "nightly-42");
+    }
+
+    #[test]
+    fn failure_only_mode_skips_passed_runs() {
+        let notifier = WebhookNotifier {
+            url: "https://example.com/hook".to_string(),
+            format: WebhookFormat::Json,
+            failures_only: true,
+        };
+        assert!(!notifier.should_notify(true));
+        assert!(notifier.should_notify(false));
+    }
+}
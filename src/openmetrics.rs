@@ -0,0 +1,278 @@
+//! OpenMetrics text exposition for the `/metrics` endpoint.
+//!
+//! Scrapers that send `Accept: application/openmetrics-text` get the
+//! OpenMetrics 1.0 format instead of the Prometheus text format. Besides the
+//! terminating `# EOF`, the differences that matter to strict parsers are:
+//!
+//! - counter families are named without their `_total` suffix, which only
+//!   the samples carry;
+//! - every counter series has a `_created` sample: the Unix time the
+//!   counter started from zero. All series of this process start at zero
+//!   when the metrics are registered, so that time is used for all of them;
+//! - `le`/`quantile` label values are canonical floats (`1.0`, `+Inf`).
+
+use std::fmt::Write;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use prometheus::proto::{LabelPair, MetricFamily, MetricType};
+
+/// Content type of [`encode`]'s output.
+pub const CONTENT_TYPE: &str = "application/openmetrics-text; version=1.0.0; charset=utf-8";
+
+lazy_static::lazy_static! {
+    /// Unix time the counters were registered, in seconds.
+    static ref CREATED: f64 = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs_f64();
+}
+
+/// Fixes the counters' `_created` time to now; called when the metrics are
+/// registered.
+pub fn mark_created() {
+    lazy_static::initialize(&CREATED);
+}
+
+/// Returns true if an `Accept` header asks for OpenMetrics.
+pub fn wants_openmetrics(accept: Option<&str>) -> bool {
+    accept.is_some_and(|accept| {
+        accept.split(',').any(|range| {
+            let mut params = range.split(';').map(str::trim);
+            params
+                .next()
+                .is_some_and(|media| media.eq_ignore_ascii_case("application/openmetrics-text"))
+                && !params.any(|p| matches!(p.replace(' ', "").as_str(), "q=0" | "q=0.0"))
+        })
+    })
+}
+
+/// Encodes `families` in the OpenMetrics text format.
+pub fn encode(families: &[MetricFamily]) -> String {
+    encode_with_created(families, *CREATED)
+}
+
+fn encode_with_created(families: &[MetricFamily], created: f64) -> String {
+    let mut out = String::new();
+    for family in families {
+        let metrics = family.get_metric();
+        if metrics.is_empty() {
+            continue;
+        }
+        let kind = family.get_field_type();
+        let name = match kind {
+            MetricType::COUNTER => family
+                .get_name()
+                .strip_suffix("_total")
+                .unwrap_or(family.get_name()),
+            _ => family.get_name(),
+        };
+        let type_name = match kind {
+            MetricType::COUNTER => "counter",
+            MetricType::GAUGE => "gauge",
+            MetricType::HISTOGRAM => "histogram",
+            MetricType::SUMMARY => "summary",
+            MetricType::UNTYPED => "unknown",
+        };
+        let _ = writeln!(out, "# TYPE {} {}", name, type_name);
+        if !family.get_help().is_empty() {
+            let _ = writeln!(out, "# HELP {} {}", name, escape(family.get_help()));
+        }
+
+        for metric in metrics {
+            let labels = metric.get_label();
+            match kind {
+                MetricType::COUNTER => {
+                    sample(
+                        &mut out,
+                        name,
+                        "_total",
+                        labels,
+                        None,
+                        metric.get_counter().get_value(),
+                    );
+                    sample(&mut out, name, "_created", labels, None, created);
+                }
+                MetricType::GAUGE => {
+                    sample(
+                        &mut out,
+                        name,
+                        "",
+                        labels,
+                        None,
+                        metric.get_gauge().get_value(),
+                    );
+                }
+                MetricType::UNTYPED => {
+                    sample(
+                        &mut out,
+                        name,
+                        "",
+                        labels,
+                        None,
+                        metric.get_untyped().get_value(),
+                    );
+                }
+                MetricType::HISTOGRAM => {
+                    let histogram = metric.get_histogram();
+                    let mut saw_inf = false;
+                    for bucket in histogram.get_bucket() {
+                        let bound = bucket.get_upper_bound();
+                        saw_inf |= bound == f64::INFINITY;
+                        let le = ("le", float(bound));
+                        sample(
+                            &mut out,
+                            name,
+                            "_bucket",
+                            labels,
+                            Some(le),
+                            bucket.get_cumulative_count() as f64,
+                        );
+                    }
+                    if !saw_inf {
+                        let le = ("le", float(f64::INFINITY));
+                        let count = histogram.get_sample_count() as f64;
+                        sample(&mut out, name, "_bucket", labels, Some(le), count);
+                    }
+                    let count = histogram.get_sample_count() as f64;
+                    sample(&mut out, name, "_count", labels, None, count);
+                    sample(
+                        &mut out,
+                        name,
+                        "_sum",
+                        labels,
+                        None,
+                        histogram.get_sample_sum(),
+                    );
+                }
+                MetricType::SUMMARY => {
+                    let summary = metric.get_summary();
+                    for quantile in summary.get_quantile() {
+                        let q = ("quantile", float(quantile.get_quantile()));
+                        sample(&mut out, name, "", labels, Some(q), quantile.get_value());
+                    }
+                    let count = summary.get_sample_count() as f64;
+                    sample(&mut out, name, "_count", labels, None, count);
+                    sample(
+                        &mut out,
+                        name,
+                        "_sum",
+                        labels,
+                        None,
+                        summary.get_sample_sum(),
+                    );
+                }
+            }
+        }
+    }
+    out.push_str("# EOF\n");
+    out
+}
+
+/// Writes one sample line.
+fn sample(
+    out: &mut String,
+    name: &str,
+    suffix: &str,
+    labels: &[LabelPair],
+    extra: Option<(&str, String)>,
+    value: f64,
+) {
+    out.push_str(name);
+    out.push_str(suffix);
+    if !labels.is_empty() || extra.is_some() {
+        out.push('{');
+        let pairs = labels
+            .iter()
+            .map(|l| (l.get_name(), l.get_value()))
+            .chain(extra.as_ref().map(|(n, v)| (*n, v.as_str())));
+        for (i, (label, label_value)) in pairs.enumerate() {
+            if i > 0 {
+                out.push(',');
+            }
+            let _ = write!(out, "{}=\"{}\"", label, escape(label_value));
+        }
+        out.push('}');
+    }
+    let _ = writeln!(out, " {}", float(value));
+}
+
+/// Formats a float the way OpenMetrics expects: `+Inf`, `NaN`, and a
+/// fractional part on whole numbers (`1.0`).
+fn float(value: f64) -> String {
+    if value.is_nan() {
+        "NaN".to_string()
+    } else if value.is_infinite() {
+        if value > 0.0 { "+Inf" } else { "-Inf" }.to_string()
+    } else if value.fract() == 0.0 && value.abs() < 1e15 {
+        format!("{:.1}", value)
+    } else {
+        value.to_string()
+    }
+}
+
+/// Escapes `\`, newlines and `"` in HELP text and label values.
+fn escape(text: &str) -> String {
+    text.replace('\\', "\\\\")
+        .replace('\n', "\\n")
+        .replace('"', "\\\"")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use prometheus::{HistogramOpts, HistogramVec, IntCounterVec, Opts, Registry};
+
+    #[test]
+    fn negotiates_on_accept() {
+        assert!(wants_openmetrics(Some(
+            "application/openmetrics-text;version=1.0.0,text/plain;version=0.0.4;q=0.5"
+        )));
+        assert!(wants_openmetrics(Some("Application/OpenMetrics-Text")));
+        assert!(!wants_openmetrics(Some("text/plain;version=0.0.4")));
+        assert!(!wants_openmetrics(Some(
+            "application/openmetrics-text; q=0, text/plain"
+        )));
+        assert!(!wants_openmetrics(None));
+    }
+
+    #[test]
+    fn encodes_counters_with_created_and_histograms() {
+        let registry = Registry::new();
+        let requests = IntCounterVec::new(
+            Opts::new("requests_total", "Total \"requests\"\nsent"),
+            &["path"],
+        )
+        .unwrap();
+        let latency = HistogramVec::new(
+            HistogramOpts::new("latency_seconds", "Latency").buckets(vec![0.5, 1.0]),
+            &["path"],
+        )
+        .unwrap();
+        registry.register(Box::new(requests.clone())).unwrap();
+        registry.register(Box::new(latency.clone())).unwrap();
+        requests.with_label_values(&["/a\"b"]).inc_by(3);
+        latency.with_label_values(&["/a"]).observe(0.7);
+
+        let text = encode_with_created(&registry.gather(), 1700000000.5);
+        let expected = "\
+# TYPE latency_seconds histogram
+# HELP latency_seconds Latency
+latency_seconds_bucket{path=\"/a\",le=\"0.5\"} 0.0
+latency_seconds_bucket{path=\"/a\",le=\"1.0\"} 1.0
+latency_seconds_bucket{path=\"/a\",le=\"+Inf\"} 1.0
+latency_seconds_count{path=\"/a\"} 1.0
+latency_seconds_sum{path=\"/a\"} 0.7
+# TYPE requests counter
+# HELP requests Total \\\"requests\\\"\\nsent
+requests_total{path=\"/a\\\"b\"} 3.0
+requests_created{path=\"/a\\\"b\"} 1700000000.5
+# EOF
+";
+        assert_eq!(text, expected);
+    }
+
+    #[test]
+    fn empty_registry_is_just_eof() {
+        assert_eq!(encode(&Registry::new().gather()), "# EOF\n");
+    }
+}
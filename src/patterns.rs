@@ -0,0 +1,174 @@
+//! JSONPath expressions and regexes of a step, compiled once per plan.
+//!
+//! Assertions and extractors name their JSONPath and regex as strings.
+//! Parsing those on every response dominated step CPU at high request
+//! rates, so each [`Step`](crate::scenario::Step) carries a
+//! [`StepPatterns`] that compiles them the first time they are needed (eagerly
+//! when a YAML plan is loaded) and is shared by every clone of the step, i.e.
+//! by every worker.
+//!
+//! Lookups are by source string. A pattern that is missing from the set
+//! (e.g. it failed to compile) is compiled on the spot, so invalid patterns
+//! still fail the assertion or extraction with the same error as before.
+
+use std::borrow::Cow;
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::{Arc, OnceLock};
+
+use regex::Regex;
+use serde_json_path::JsonPath;
+
+use crate::scenario::{Assertion, Extractor, VariableExtraction};
+
+/// Compiled JSONPath expressions and regexes, keyed by their source.
+#[derive(Debug, Default)]
+pub struct CompiledPatterns {
+    json_paths: HashMap<String, JsonPath>,
+    regexes: HashMap<String, Regex>,
+}
+
+impl CompiledPatterns {
+    /// Compile every JSONPath and regex used by `assertions` and `extractions`.
+    pub fn compile(assertions: &[Assertion], extractions: &[VariableExtraction]) -> Self {
+        let mut patterns = Self::default();
+        for assertion in assertions {
+            match assertion {
+                Assertion::JsonPath { path, .. } => patterns.add_json_path(path),
+                Assertion::BodyMatches(pattern) | Assertion::HeaderMatches { pattern, .. } => {
+                    patterns.add_regex(pattern)
+                }
+                _ => {}
+            }
+        }
+        for extraction in extractions {
+            match &extraction.extractor {
+                Extractor::JsonPath(path) => patterns.add_json_path(path),
+                Extractor::Regex { pattern, .. } => patterns.add_regex(pattern),
+                _ => {}
+            }
+        }
+        patterns
+    }
+
+    fn add_json_path(&mut self, path: &str) {
+        if let Ok(compiled) = JsonPath::parse(path) {
+            self.json_paths.insert(path.to_string(), compiled);
+        }
+    }
+
+    fn add_regex(&mut self, pattern: &str) {
+        if let Ok(compiled) = Regex::new(pattern) {
+            self.regexes.insert(pattern.to_string(), compiled);
+        }
+    }
+
+    /// The compiled form of `path`, parsing it now if it is not in the set.
+    pub fn json_path(&self, path: &str) -> Result<Cow<'_, JsonPath>, String> {
+        match self.json_paths.get(path) {
+            Some(compiled) => Ok(Cow::Borrowed(compiled)),
+            None => JsonPath::parse(path)
+                .map(Cow::Owned)
+                .map_err(|e| e.to_string()),
+        }
+    }
+
+    /// The compiled form of `pattern`, compiling it now if it is not in the set.
+    pub fn regex(&self, pattern: &str) -> Result<Cow<'_, Regex>, regex::Error> {
+        match self.regexes.get(pattern) {
+            Some(compiled) => Ok(Cow::Borrowed(compiled)),
+            None => Regex::new(pattern).map(Cow::Owned),
+        }
+    }
+
+    /// Number of compiled JSONPath expressions and regexes.
+    pub fn len(&self) -> usize {
+        self.json_paths.len() + self.regexes.len()
+    }
+
+    /// True when nothing was compiled.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+/// A step's [`CompiledPatterns`], built on first use and shared by clones.
+#[derive(Clone, Default)]
+pub struct StepPatterns(Arc<OnceLock<CompiledPatterns>>);
+
+impl StepPatterns {
+    /// The compiled patterns, compiling them from `assertions` and
+    /// `extractions` if this is the first call.
+    pub fn get_or_compile(
+        &self,
+        assertions: &[Assertion],
+        extractions: &[VariableExtraction],
+    ) -> &CompiledPatterns {
+        self.0
+            .get_or_init(|| CompiledPatterns::compile(assertions, extractions))
+    }
+}
+
+impl fmt::Debug for StepPatterns {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.0.get() {
+            Some(compiled) => write!(f, "StepPatterns({} compiled)", compiled.len()),
+            None => f.write_str("StepPatterns(not compiled)"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::scenario::VariableScope;
+
+    #[test]
+    fn compiles_assertion_and_extractor_patterns() {
+        let assertions = vec![
+            Assertion::JsonPath {
+                path: "$.status".to_string(),
+                expected: None,
+            },
+            Assertion::BodyMatches("ok|ready".to_string()),
+            Assertion::BodyMatches("(unclosed".to_string()),
+        ];
+        let extractions = vec![VariableExtraction {
+            name: "id".to_string(),
+            extractor: Extractor::Regex {
+                pattern: r"id=(?P<id>\d+)".to_string(),
+                group: "id".to_string(),
+            },
+            transforms: vec![],
+            scope: VariableScope::Iteration,
+        }];
+
+        let patterns = CompiledPatterns::compile(&assertions, &extractions);
+        assert_eq!(patterns.len(), 3);
+        assert!(matches!(
+            patterns.json_path("$.status"),
+            Ok(Cow::Borrowed(_))
+        ));
+        assert!(matches!(
+            patterns.regex(r"id=(?P<id>\d+)"),
+            Ok(Cow::Borrowed(_))
+        ));
+
+        // Not in the set: compiled on the spot, errors as before.
+        assert!(matches!(patterns.regex("other"), Ok(Cow::Owned(_))));
+        assert!(patterns.regex("(unclosed").is_err());
+        assert!(patterns.json_path("$[").is_err());
+    }
+
+    #[test]
+    fn step_patterns_are_shared_by_clones() {
+        let assertions = vec![Assertion::BodyMatches("ok".to_string())];
+        let patterns = StepPatterns::default();
+        let clone = patterns.clone();
+        assert_eq!(format!("{:?}", clone), "StepPatterns(not compiled)");
+
+        patterns.get_or_compile(&assertions, &[]);
+        assert_eq!(format!("{:?}", clone), "StepPatterns(1 compiled)");
+        assert!(clone.get_or_compile(&[], &[]).regex("ok").is_ok());
+    }
+}
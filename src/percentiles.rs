@@ -9,13 +9,16 @@
 //! - Per-scenario percentile tracking
 //! - Thread-safe concurrent updates
 //! - Memory-efficient histogram storage
+//! - Periodic rotation with a bounded rolling report of past intervals
 
 use hdrhistogram::Histogram;
 use lru::LruCache;
-use std::collections::HashMap;
+use std::collections::{HashMap, VecDeque};
 use std::num::NonZeroUsize;
+use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::{Arc, Mutex};
-use tracing::{debug, warn};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use tracing::{debug, info, warn};
 
 /// Percentile statistics for a set of latency measurements.
 #[derive(Debug, Clone)]
@@ -158,6 +161,12 @@ impl PercentileTracker {
         })
     }
 
+    /// Copy of the histogram (microseconds); None if no samples have been recorded.
+    pub fn histogram(&self) -> Option<Histogram<u64>> {
+        let hist = self.histogram.lock().unwrap();
+        (!hist.is_empty()).then(|| hist.clone())
+    }
+
     /// Reset all recorded samples.
     pub fn reset(&self) {
         let mut hist = self.histogram.lock().unwrap();
@@ -278,6 +287,17 @@ impl MultiLabelPercentileTracker {
         results
     }
 
+    /// Copies of the non-empty histograms, sorted by label.
+    pub fn histograms(&self) -> Vec<(String, Histogram<u64>)> {
+        let trackers = self.trackers.lock().unwrap();
+        let mut histograms: Vec<_> = trackers
+            .iter()
+            .filter_map(|(label, tracker)| tracker.histogram().map(|h| (label.clone(), h)))
+            .collect();
+        histograms.sort_by(|a, b| a.0.cmp(&b.0));
+        histograms
+    }
+
     /// Get all labels currently being tracked.
     pub fn labels(&self) -> Vec<String> {
         let trackers = self.trackers.lock().unwrap();
@@ -333,6 +353,125 @@ impl Default for MultiLabelPercentileTracker {
     }
 }
 
+/// Maximum number of rotation snapshots kept in a [`RollingReport`].
+///
+/// At an hourly rotation interval this covers one week; older intervals are
+/// dropped so multi-day soak tests keep a bounded memory footprint.
+pub const MAX_ROTATION_SNAPSHOTS: usize = 168;
+
+/// Percentile statistics captured just before a histogram rotation.
+#[derive(Debug, Clone)]
+pub struct RotationSnapshot {
+    /// Rotation sequence number, starting at 1.
+    pub sequence: u64,
+
+    /// Unix time (seconds) at which the interval ended.
+    pub ended_at_unix_secs: u64,
+
+    /// Single request latencies for the interval.
+    pub requests: Option<PercentileStats>,
+
+    /// Scenario latencies for the interval (by scenario name).
+    pub scenarios: HashMap<String, PercentileStats>,
+
+    /// Step latencies for the interval (by scenario:step).
+    pub steps: HashMap<String, PercentileStats>,
+}
+
+/// Bounded history of per-interval statistics (Issue #67).
+///
+/// Each rotation pushes a snapshot of the interval that just ended, so
+/// clearing the histograms doesn't lose the data from the end-of-test
+/// report. Only the most recent `max_snapshots` intervals are kept.
+pub struct RollingReport {
+    snapshots: Mutex<VecDeque<RotationSnapshot>>,
+    max_snapshots: usize,
+    rotations: AtomicU64,
+}
+
+impl RollingReport {
+    /// Create a rolling report that keeps at most `max_snapshots` intervals.
+    pub fn new(max_snapshots: usize) -> Self {
+        Self {
+            snapshots: Mutex::new(VecDeque::new()),
+            max_snapshots: max_snapshots.max(1),
+            rotations: AtomicU64::new(0),
+        }
+    }
+
+    /// Snapshot the given trackers into the report, then clear them.
+    ///
+    /// Intervals with no samples are counted but not stored.
+    pub fn rotate(
+        &self,
+        requests: &PercentileTracker,
+        scenarios: &MultiLabelPercentileTracker,
+        steps: &MultiLabelPercentileTracker,
+    ) {
+        let sequence = self.rotations.fetch_add(1, Ordering::Relaxed) + 1;
+
+        let snapshot = RotationSnapshot {
+            sequence,
+            ended_at_unix_secs: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map(|d| d.as_secs())
+                .unwrap_or(0),
+            requests: requests.stats(),
+            scenarios: scenarios.all_stats(),
+            steps: steps.all_stats(),
+        };
+
+        requests.reset();
+        scenarios.rotate();
+        steps.rotate();
+
+        if snapshot.requests.is_none() && snapshot.scenarios.is_empty() && snapshot.steps.is_empty()
+        {
+            return;
+        }
+
+        let mut snapshots = self.snapshots.lock().unwrap();
+        if snapshots.len() >= self.max_snapshots {
+            snapshots.pop_front();
+        }
+        snapshots.push_back(snapshot);
+    }
+
+    /// Stored snapshots, oldest first.
+    pub fn snapshots(&self) -> Vec<RotationSnapshot> {
+        self.snapshots.lock().unwrap().iter().cloned().collect()
+    }
+
+    /// Number of stored snapshots.
+    pub fn len(&self) -> usize {
+        self.snapshots.lock().unwrap().len()
+    }
+
+    /// Check if no snapshots are stored.
+    pub fn is_empty(&self) -> bool {
+        self.snapshots.lock().unwrap().is_empty()
+    }
+
+    /// Total number of rotations, including dropped and empty intervals.
+    pub fn rotations(&self) -> u64 {
+        self.rotations.load(Ordering::Relaxed)
+    }
+
+    /// Single request statistics per stored interval, labeled by sequence.
+    pub fn request_stats_by_interval(&self) -> HashMap<String, PercentileStats> {
+        self.snapshots
+            .lock()
+            .unwrap()
+            .iter()
+            .filter_map(|s| {
+                s.requests
+                    .clone()
+                    .map(|stats| (format!("interval #{:04}", s.sequence), stats))
+            })
+            .collect()
+    }
+}
+
 // Global percentile trackers for the application.
 //
 // These are lazily initialized and thread-safe.
@@ -345,16 +484,47 @@ lazy_static::lazy_static! {
 
     /// Global tracker for step latencies (by scenario:step)
     pub static ref GLOBAL_STEP_PERCENTILES: MultiLabelPercentileTracker = MultiLabelPercentileTracker::new();
+
+    /// Global tracker for step latencies by tag (`key=value`, for METRIC_TAG_KEYS)
+    pub static ref GLOBAL_TAG_PERCENTILES: MultiLabelPercentileTracker = MultiLabelPercentileTracker::new();
+
+    /// Snapshots of the global trackers taken at each rotation
+    pub static ref GLOBAL_ROLLING_REPORT: RollingReport = RollingReport::new(MAX_ROTATION_SNAPSHOTS);
 }
 
 /// Rotate all global histogram trackers (Issue #67).
 ///
-/// Clears histogram data to free memory while keeping labels intact.
+/// Appends the interval to the HDR interval log (`HDR_LOG_PATH`) and
+/// snapshots the current statistics into [`GLOBAL_ROLLING_REPORT`], then
+/// clears histogram data to free memory while keeping labels intact.
 /// Should be called periodically for long-running tests to bound memory usage.
 pub fn rotate_all_histograms() {
-    GLOBAL_REQUEST_PERCENTILES.reset();
-    GLOBAL_SCENARIO_PERCENTILES.rotate();
-    GLOBAL_STEP_PERCENTILES.rotate();
+    crate::hdr_log::write_global_interval();
+    GLOBAL_ROLLING_REPORT.rotate(
+        &GLOBAL_REQUEST_PERCENTILES,
+        &GLOBAL_SCENARIO_PERCENTILES,
+        &GLOBAL_STEP_PERCENTILES,
+    );
+    GLOBAL_TAG_PERCENTILES.rotate();
+}
+
+/// Spawn the background task that rotates the global histograms every
+/// `interval` (`HISTOGRAM_ROTATION_INTERVAL`). The first rotation happens
+/// one full interval after the call.
+pub fn spawn_histogram_rotation(interval: Duration) -> tokio::task::JoinHandle<()> {
+    tokio::spawn(async move {
+        let mut ticker = tokio::time::interval(interval);
+        ticker.tick().await; // Skip the first immediate tick
+        loop {
+            ticker.tick().await;
+            rotate_all_histograms();
+            info!(
+                rotation_interval_secs = interval.as_secs(),
+                rotations = GLOBAL_ROLLING_REPORT.rotations(),
+                "Histograms rotated - interval stats saved to rolling report"
+            );
+        }
+    })
 }
 
 /// Format percentile statistics as a table.
@@ -564,4 +734,33 @@ mod tests {
         let table = format_percentile_table("Empty Table", &stats_map);
         assert!(table.contains("No data available"));
     }
+
+    #[test]
+    fn test_rolling_report_snapshots_before_clearing() {
+        let requests = PercentileTracker::new();
+        let scenarios = MultiLabelPercentileTracker::new();
+        let steps = MultiLabelPercentileTracker::new();
+        let report = RollingReport::new(10);
+
+        requests.record_ms(100);
+        scenarios.record("checkout", 200);
+        report.rotate(&requests, &scenarios, &steps);
+
+        // Trackers are cleared, but the interval survives in the report
+        assert!(requests.stats().is_none());
+        assert!(scenarios.stats("checkout").is_none());
+        let snapshots = report.snapshots();
+        assert_eq!(snapshots.len(), 1);
+        assert_eq!(snapshots[0].sequence, 1);
+        assert_eq!(snapshots[0].requests.as_ref().unwrap().count, 1);
+        assert_eq!(snapshots[0].scenarios["checkout"].count, 1);
+
+        // Empty intervals are counted but not stored
+        report.rotate(&requests, &scenarios, &steps);
+        assert_eq!(report.rotations(), 2);
+        assert_eq!(report.len(), 1);
+        assert!(report
+            .request_stats_by_interval()
+            .contains_key("interval #0001"));
+    }
 }
@@ -0,0 +1,485 @@
+//! Ordered execution phases (`phases:` and `dependsOn:`).
+//!
+//! A test plan can be split into phases that run one after another — e.g.
+//! seed data, then steady-state load, then cleanup — with the scenarios of
+//! a phase running concurrently, weighted as usual. Phases are either listed
+//! explicitly:
+//!
+//! ```yaml
+//! phases:
+//!   - name: seed
+//!     scenarios: [Create Users]
+//!     duration: 2m
+//!   - name: load
+//!     scenarios: [Browse, Checkout]
+//!   - name: cleanup
+//!     scenarios: [Delete Users]
+//! ```
+//!
+//! or derived from `dependsOn:` on scenarios: a scenario starts in the phase
+//! after the last of its dependencies.
+//!
+//! A phase lasts its `duration` (default: the test duration) but ends early
+//! once every scenario in it has completed its `iterations`, so bounded setup
+//! and teardown scenarios hand over to the next phase as soon as they are
+//! done. Without `phases:` or `dependsOn:` every scenario runs in a single
+//! phase, as before.
+
+use std::collections::HashMap;
+use std::sync::atomic::AtomicU64;
+use std::sync::Arc;
+use std::time::Duration;
+
+use thiserror::Error;
+use tokio::task::JoinSet;
+use tokio::time::Instant;
+use tracing::info;
+
+use crate::metrics::PHASE_ACTIVE;
+use crate::multi_scenario::ScenarioSelector;
+use crate::scenario::Scenario;
+use crate::worker::{run_scenario_worker, ScenarioWorkerConfig};
+
+/// Errors in the phase or dependency definitions.
+#[derive(Error, Debug, PartialEq)]
+pub enum PhaseError {
+    #[error("Phase '{phase}' references unknown scenario '{scenario}'")]
+    UnknownScenario { phase: String, scenario: String },
+
+    #[error("Scenario '{scenario}' depends on unknown scenario '{dependency}'")]
+    UnknownDependency {
+        scenario: String,
+        dependency: String,
+    },
+
+    #[error("Scenario '{0}' is listed in more than one phase")]
+    DuplicateScenario(String),
+
+    #[error("Scenario '{0}' is not part of any phase")]
+    Unassigned(String),
+
+    #[error("Phase '{0}' has no scenarios")]
+    EmptyPhase(String),
+
+    #[error("Phase '{0}' has 0 workers")]
+    NoWorkers(String),
+
+    #[error(
+        "Scenario '{scenario}' depends on '{dependency}', which does not run in an earlier phase"
+    )]
+    DependencyOrder {
+        scenario: String,
+        dependency: String,
+    },
+
+    #[error("Dependency cycle between scenarios: {0}")]
+    Cycle(String),
+}
+
+/// A phase as written in the test plan.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PhaseSpec {
+    pub name: String,
+    /// Scenario names.
+    pub scenarios: Vec<String>,
+    /// How long the phase runs at most (default: the test duration).
+    pub duration: Option<Duration>,
+    /// Workers for this phase (default: the configured worker count).
+    pub workers: Option<usize>,
+}
+
+/// A phase ready to run.
+#[derive(Debug, Clone)]
+pub struct Phase {
+    pub name: String,
+    pub scenarios: Vec<Scenario>,
+    pub duration: Duration,
+    pub workers: Option<usize>,
+}
+
+/// Orders scenarios into phases, returning scenario indices per phase.
+///
+/// `scenarios` are (name, dependsOn) pairs. With `specs` the phases are
+/// taken as given and dependencies must point into earlier phases; without
+/// them, phases are the dependency levels of the scenarios.
+pub fn order(
+    scenarios: &[(&str, &[String])],
+    specs: &[PhaseSpec],
+) -> Result<Vec<Vec<usize>>, PhaseError> {
+    let index: HashMap<&str, usize> = scenarios
+        .iter()
+        .enumerate()
+        .map(|(i, (name, _))| (*name, i))
+        .collect();
+    for (name, deps) in scenarios {
+        if let Some(dep) = deps.iter().find(|d| !index.contains_key(d.as_str())) {
+            return Err(PhaseError::UnknownDependency {
+                scenario: name.to_string(),
+                dependency: dep.clone(),
+            });
+        }
+    }
+
+    if specs.is_empty() {
+        return dependency_levels(scenarios, &index);
+    }
+
+    let mut phase_of: Vec<Option<usize>> = vec![None; scenarios.len()];
+    let mut phases = Vec::with_capacity(specs.len());
+    for (p, spec) in specs.iter().enumerate() {
+        if spec.scenarios.is_empty() {
+            return Err(PhaseError::EmptyPhase(spec.name.clone()));
+        }
+        if spec.workers == Some(0) {
+            return Err(PhaseError::NoWorkers(spec.name.clone()));
+        }
+        let mut members = Vec::with_capacity(spec.scenarios.len());
+        for name in &spec.scenarios {
+            let i = *index
+                .get(name.as_str())
+                .ok_or_else(|| PhaseError::UnknownScenario {
+                    phase: spec.name.clone(),
+                    scenario: name.clone(),
+                })?;
+            if phase_of[i].replace(p).is_some() {
+                return Err(PhaseError::DuplicateScenario(name.clone()));
+            }
+            members.push(i);
+        }
+        phases.push(members);
+    }
+    for (i, (name, deps)) in scenarios.iter().enumerate() {
+        let phase = phase_of[i].ok_or_else(|| PhaseError::Unassigned(name.to_string()))?;
+        for dep in deps.iter() {
+            if phase_of[index[dep.as_str()]] >= Some(phase) {
+                return Err(PhaseError::DependencyOrder {
+                    scenario: name.to_string(),
+                    dependency: dep.clone(),
+                });
+            }
+        }
+    }
+    Ok(phases)
+}
+
+/// Groups scenarios by dependency depth: level 0 has no dependencies, level
+/// n depends on something at level n-1.
+fn dependency_levels(
+    scenarios: &[(&str, &[String])],
+    index: &HashMap<&str, usize>,
+) -> Result<Vec<Vec<usize>>, PhaseError> {
+    let mut level: Vec<Option<usize>> = vec![None; scenarios.len()];
+    let mut remaining = scenarios.len();
+    while remaining > 0 {
+        let mut progressed = false;
+        for (i, (_, deps)) in scenarios.iter().enumerate() {
+            if level[i].is_some() {
+                continue;
+            }
+            let dep_levels: Option<Vec<usize>> =
+                deps.iter().map(|d| level[index[d.as_str()]]).collect();
+            if let Some(dep_levels) = dep_levels {
+                level[i] = Some(dep_levels.into_iter().max().map_or(0, |l| l + 1));
+                remaining -= 1;
+                progressed = true;
+            }
+        }
+        if !progressed {
+            let stuck: Vec<&str> = scenarios
+                .iter()
+                .zip(&level)
+                .filter(|(_, l)| l.is_none())
+                .map(|((name, _), _)| *name)
+                .collect();
+            return Err(PhaseError::Cycle(stuck.join(", ")));
+        }
+    }
+
+    let depth = level.iter().flatten().max().map_or(0, |l| l + 1);
+    let mut phases = vec![Vec::new(); depth];
+    for (i, l) in level.into_iter().enumerate() {
+        phases[l.unwrap_or_default()].push(i);
+    }
+    Ok(phases)
+}
+
+/// Builds the phases for `scenarios` (see [`order`]).
+pub fn build(
+    scenarios: Vec<Scenario>,
+    specs: &[PhaseSpec],
+    default_duration: Duration,
+) -> Result<Vec<Phase>, PhaseError> {
+    let names: Vec<(&str, &[String])> = scenarios
+        .iter()
+        .map(|s| (s.name.as_str(), s.depends_on.as_slice()))
+        .collect();
+    let ordered = order(&names, specs)?;
+    let single = ordered.len() == 1;
+
+    Ok(ordered
+        .into_iter()
+        .enumerate()
+        .map(|(p, members)| {
+            let spec = specs.get(p);
+            Phase {
+                name: match spec {
+                    Some(spec) => spec.name.clone(),
+                    None if single => "main".to_string(),
+                    None => format!("phase-{}", p + 1),
+                },
+                scenarios: members.into_iter().map(|i| scenarios[i].clone()).collect(),
+                duration: spec.and_then(|s| s.duration).unwrap_or(default_duration),
+                workers: spec.and_then(|s| s.workers),
+            }
+        })
+        .collect())
+}
+
+/// Longest time the phases can take, run back to back.
+pub fn total_duration(phases: &[Phase]) -> Duration {
+    phases.iter().map(|p| p.duration).sum()
+}
+
+/// Picks a scenario for each of `workers` workers by weight. When the phase
+/// has bounded scenarios (`iterations`), every scenario gets at least one
+/// worker so the phase can finish; scenarios with their own `rps` always get
+/// one so their rate is generated.
+pub fn assign(phase: &Phase, workers: usize) -> Vec<Scenario> {
+    let selector = ScenarioSelector::new(phase.scenarios.clone());
+    let mut assigned = Vec::with_capacity(workers);
+    if phase.scenarios.iter().any(|s| s.iterations.is_some()) {
+        assigned.extend(phase.scenarios.iter().cloned());
+    } else {
+        assigned.extend(phase.scenarios.iter().filter(|s| s.rps.is_some()).cloned());
+    }
+    while assigned.len() < workers {
+        assigned.push(selector.select().clone());
+    }
+    assigned
+}
+
+/// Workers sharing each rate: per rate-limited scenario (by name), and
+/// `None` for the workers paced by the load model.
+fn pacing_groups(assigned: &[Scenario]) -> HashMap<Option<String>, usize> {
+    let mut groups = HashMap::new();
+    for scenario in assigned {
+        *groups
+            .entry(scenario.rps.map(|_| scenario.name.clone()))
+            .or_insert(0) += 1;
+    }
+    groups
+}
+
+/// Runs `phases` one after another, the first one starting at `start`
+/// (which may be in the past, see [`crate::start_barrier`]).
+///
+/// `make_worker(i, scenario)` builds the config for worker `i` of a phase;
+/// the phase's duration, worker count and iteration budgets are filled in
+/// here. A scenario with its own `rps` is paced by its workers alone; the
+/// load model's rate is shared by the workers of the other scenarios. Dropping (aborting) the returned future aborts the running workers.
+pub async fn run<F>(phases: Vec<Phase>, workers: usize, start: Instant, make_worker: F)
+where
+    F: Fn(usize, Scenario) -> ScenarioWorkerConfig,
+{
+    let count = phases.len();
+    for (p, phase) in phases.into_iter().enumerate() {
+        let budgets: HashMap<String, Arc<AtomicU64>> = phase
+            .scenarios
+            .iter()
+            .filter_map(|s| Some((s.name.clone(), Arc::new(AtomicU64::new(s.iterations?)))))
+            .collect();
+        let assigned = assign(&phase, phase.workers.unwrap_or(workers));
+        if count > 1 {
+            info!(
+                phase = %phase.name,
+                number = p + 1,
+                of = count,
+                scenarios = ?phase.scenarios.iter().map(|s| s.name.as_str()).collect::<Vec<_>>(),
+                workers = assigned.len(),
+                max_duration_secs = phase.duration.as_secs_f64(),
+                "Phase starting"
+            );
+        }
+        PHASE_ACTIVE.with_label_values(&[&phase.name]).set(1.0);
+
+        let start = if p == 0 { start } else { Instant::now() };
+        let pacing_groups = pacing_groups(&assigned);
+        let mut tasks = JoinSet::new();
+        for (i, scenario) in assigned.into_iter().enumerate() {
+            let budget = budgets.get(&scenario.name).cloned();
+            let group = scenario.rps.map(|_| scenario.name.clone());
+            let mut config = make_worker(i, scenario);
+            config.test_duration = phase.duration;
+            config.num_concurrent_tasks = pacing_groups[&group];
+            config.iteration_budget = budget;
+            tasks.spawn(run_scenario_worker(config, start));
+        }
+        while tasks.join_next().await.is_some() {}
+
+        PHASE_ACTIVE.with_label_values(&[&phase.name]).set(0.0);
+        if count > 1 {
+            info!(
+                phase = %phase.name,
+                elapsed_secs = start.elapsed().as_secs_f64(),
+                "Phase complete"
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn scenario(name: &str, depends_on: &[&str], iterations: Option<u64>) -> Scenario {
+        Scenario {
+            name: name.to_string(),
+            weight: 1.0,
+            steps: vec![],
+            network_profile: None,
+            tags: HashMap::new(),
+            max_concurrent: None,
+            depends_on: depends_on.iter().map(|d| d.to_string()).collect(),
+            iterations,
+            retry: None,
+            assertion_sample_rate: 1.0,
+            client: None,
+            headers: Default::default(),
+            optional: false,
+            data_source: None,
+            rps: None,
+        }
+    }
+
+    fn names(phases: &[Phase]) -> Vec<Vec<&str>> {
+        phases
+            .iter()
+            .map(|p| p.scenarios.iter().map(|s| s.name.as_str()).collect())
+            .collect()
+    }
+
+    #[test]
+    fn derives_phases_from_dependencies() {
+        let scenarios = vec![
+            scenario("Cleanup", &["Browse", "Checkout"], Some(1)),
+            scenario("Browse", &["Seed"], None),
+            scenario("Checkout", &["Seed"], None),
+            scenario("Seed", &[], Some(1)),
+        ];
+        let phases = build(scenarios, &[], Duration::from_secs(60)).unwrap();
+        assert_eq!(
+            names(&phases),
+            vec![vec!["Seed"], vec!["Browse", "Checkout"], vec!["Cleanup"]]
+        );
+        assert_eq!(phases[1].name, "phase-2");
+        assert_eq!(total_duration(&phases), Duration::from_secs(180));
+
+        let single = build(
+            vec![scenario("A", &[], None), scenario("B", &[], None)],
+            &[],
+            Duration::from_secs(60),
+        )
+        .unwrap();
+        assert_eq!(single.len(), 1);
+        assert_eq!(single[0].name, "main");
+    }
+
+    #[test]
+    fn rejects_cycles_and_unknown_dependencies() {
+        let cycle = vec![
+            scenario("A", &["B"], None),
+            scenario("B", &["A"], None),
+            scenario("C", &[], None),
+        ];
+        assert_eq!(
+            build(cycle, &[], Duration::ZERO).unwrap_err(),
+            PhaseError::Cycle("A, B".to_string())
+        );
+
+        let unknown = vec![scenario("A", &["Missing"], None)];
+        assert!(matches!(
+            build(unknown, &[], Duration::ZERO),
+            Err(PhaseError::UnknownDependency { .. })
+        ));
+    }
+
+    #[test]
+    fn uses_explicit_phases() {
+        let spec = |name: &str, scenarios: &[&str]| PhaseSpec {
+            name: name.to_string(),
+            scenarios: scenarios.iter().map(|s| s.to_string()).collect(),
+            duration: None,
+            workers: None,
+        };
+        let scenarios = || {
+            vec![
+                scenario("Seed", &[], Some(10)),
+                scenario("Load", &["Seed"], None),
+            ]
+        };
+        let mut specs = vec![spec("seed", &["Seed"]), spec("load", &["Load"])];
+        specs[0].duration = Some(Duration::from_secs(30));
+        specs[0].workers = Some(2);
+
+        let phases = build(scenarios(), &specs, Duration::from_secs(600)).unwrap();
+        assert_eq!(phases[0].name, "seed");
+        assert_eq!(phases[0].duration, Duration::from_secs(30));
+        assert_eq!(phases[0].workers, Some(2));
+        assert_eq!(phases[1].duration, Duration::from_secs(600));
+
+        let reversed = vec![spec("load", &["Load"]), spec("seed", &["Seed"])];
+        assert!(matches!(
+            build(scenarios(), &reversed, Duration::ZERO),
+            Err(PhaseError::DependencyOrder { .. })
+        ));
+        let missing = vec![spec("seed", &["Seed"])];
+        assert_eq!(
+            build(scenarios(), &missing, Duration::ZERO).unwrap_err(),
+            PhaseError::Unassigned("Load".to_string())
+        );
+        let duplicate = vec![spec("a", &["Seed", "Load"]), spec("b", &["Load"])];
+        assert_eq!(
+            build(scenarios(), &duplicate, Duration::ZERO).unwrap_err(),
+            PhaseError::DuplicateScenario("Load".to_string())
+        );
+    }
+
+    #[test]
+    fn bounded_scenarios_always_get_a_worker() {
+        let phase = Phase {
+            name: "p".to_string(),
+            scenarios: vec![scenario("Big", &[], None), scenario("Once", &[], Some(1))],
+            duration: Duration::from_secs(1),
+            workers: None,
+        };
+        let assigned = assign(&phase, 5);
+        assert_eq!(assigned.len(), 5);
+        assert!(assigned.iter().any(|s| s.name == "Once"));
+        assert!(assigned.iter().any(|s| s.name == "Big"));
+    }
+
+    #[test]
+    fn rate_limited_scenarios_are_paced_separately() {
+        let mut search = scenario("Search", &[], None);
+        search.weight = 1000.0;
+        search.rps = Some(800.0);
+        let mut checkout = scenario("Checkout", &[], None);
+        checkout.weight = 0.001;
+        checkout.rps = Some(20.0);
+        let phase = Phase {
+            name: "p".to_string(),
+            scenarios: vec![search, checkout, scenario("Browse", &[], None)],
+            duration: Duration::from_secs(1),
+            workers: None,
+        };
+        let assigned = assign(&phase, 10);
+        assert_eq!(assigned.len(), 10);
+        assert!(assigned.iter().any(|s| s.name == "Checkout"));
+
+        let groups = pacing_groups(&assigned);
+        let count = |name: &str| assigned.iter().filter(|s| s.name == name).count();
+        assert_eq!(groups[&Some("Search".to_string())], count("Search"));
+        assert_eq!(groups[&Some("Checkout".to_string())], count("Checkout"));
+        assert_eq!(groups.get(&None).copied().unwrap_or(0), count("Browse"));
+    }
+}
@@ -0,0 +1,775 @@
+//! Plugin system for custom protocols, assertions and data sources.
+//!
+//! Plugins let teams extend the load tester without forking the crate.
+//! Three extension points are defined as traits:
+//!
+//! - [`ExecutorPlugin`] — executes a step whose `method` is not a standard
+//!   HTTP verb (e.g. `method: "MQTT"`), so proprietary protocols can be
+//!   driven from ordinary YAML scenarios.
+//! - [`AssertionPlugin`] — validates a response (`type: plugin` assertions).
+//! - [`DataSourcePlugin`] — produces rows of variables for data-driven tests.
+//!
+//! Native [`Middleware`] (request transformers and response observers, see
+//! [`crate::middleware`]) are registered on the same registry.
+//!
+//! Native implementations can be registered directly on the
+//! [`GLOBAL_PLUGIN_REGISTRY`]. With the `wasm-plugins` Cargo feature enabled,
+//! plugins can also be loaded from WebAssembly modules at startup via the
+//! `PLUGIN_PATHS` environment variable (see [`load_plugins_from_env`]).
+//!
+//! # WASM plugin ABI (version 1)
+//!
+//! A WASM plugin module must export:
+//!
+//! | Export | Signature | Purpose |
+//! |--------|-----------|---------|
+//! | `memory` | memory | Linear memory shared with the host |
+//! | `rlt_abi_version` | `() -> i32` | Must return [`PLUGIN_ABI_VERSION`] |
+//! | `rlt_capabilities` | `() -> i32` | Bit set of `CAP_*` flags |
+//! | `rlt_alloc` | `(len: i32) -> i32` | Allocate `len` bytes for host input |
+//!
+//! and, depending on the advertised capabilities:
+//!
+//! | Export | Signature | Capability |
+//! |--------|-----------|------------|
+//! | `rlt_execute` | `(ptr: i32, len: i32) -> i64` | [`CAP_EXECUTOR`] |
+//! | `rlt_assert` | `(status: i32, elapsed_ms: i64, ptr: i32, len: i32) -> i64` | [`CAP_ASSERTION`] |
+//! | `rlt_next_row` | `() -> i64` | [`CAP_DATA_SOURCE`] |
+//!
+//! Inputs are JSON documents written into memory obtained from `rlt_alloc`.
+//! Outputs are JSON documents returned as a packed `(ptr << 32) | len` value;
+//! a zero length from `rlt_next_row` signals that the source is exhausted.
+//! The JSON shapes are the serde representations of [`PluginRequest`],
+//! [`PluginResponse`], [`AssertionInput`], [`PluginAssertionOutcome`] and
+//! a flat `{"column": "value"}` object for data rows.
+//!
+//! See `examples/plugins/status_below_500.wat` for a minimal assertion plugin.
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::{Arc, RwLock};
+use thiserror::Error;
+use tracing::info;
+
+use crate::middleware::{Middleware, MiddlewareChain};
+
+/// Version of the plugin ABI implemented by this host.
+///
+/// Bumped whenever the exported function signatures or JSON shapes change in
+/// a backwards-incompatible way. Plugins built for another version are rejected
+/// at load time.
+pub const PLUGIN_ABI_VERSION: u32 = 1;
+
+/// Capability flag: plugin implements `rlt_execute`.
+pub const CAP_EXECUTOR: u32 = 1;
+
+/// Capability flag: plugin implements `rlt_assert`.
+pub const CAP_ASSERTION: u32 = 1 << 1;
+
+/// Capability flag: plugin implements `rlt_next_row`.
+pub const CAP_DATA_SOURCE: u32 = 1 << 2;
+
+/// Errors that can occur when loading or invoking plugins.
+#[derive(Error, Debug)]
+pub enum PluginError {
+    #[error("Plugin '{name}' uses ABI version {found}, host supports version {expected}")]
+    AbiMismatch {
+        name: String,
+        found: u32,
+        expected: u32,
+    },
+
+    #[error("Plugin '{name}' is missing required export '{export}'")]
+    MissingExport { name: String, export: String },
+
+    #[error("Failed to load plugin from {path}: {message}")]
+    LoadFailed { path: String, message: String },
+
+    #[error("Plugin '{name}' failed: {message}")]
+    InvocationFailed { name: String, message: String },
+
+    #[error("Plugin '{0}' is not registered")]
+    NotFound(String),
+
+    #[error("WASM plugin support is not compiled in (rebuild with --features wasm-plugins)")]
+    WasmSupportDisabled,
+
+    #[error("Invalid plugin payload: {0}")]
+    InvalidPayload(#[from] serde_json::Error),
+}
+
+/// A request handed to an [`ExecutorPlugin`].
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct PluginRequest {
+    /// Protocol/method name from the step (e.g. "MQTT").
+    pub method: String,
+
+    /// Fully resolved target URL (base URL + path, variables substituted).
+    pub url: String,
+
+    /// Request headers after variable substitution.
+    #[serde(default)]
+    pub headers: HashMap<String, String>,
+
+    /// Request body after variable substitution.
+    #[serde(default)]
+    pub body: Option<String>,
+}
+
+/// A response produced by an [`ExecutorPlugin`].
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct PluginResponse {
+    /// Status code; success follows the step's `expectedStatus` (2xx/3xx by
+    /// default).
+    pub status: u16,
+
+    /// Response headers, usable by header extractors and assertions.
+    #[serde(default)]
+    pub headers: HashMap<String, String>,
+
+    /// Response body, usable by body extractors and assertions.
+    #[serde(default)]
+    pub body: String,
+}
+
+/// Response data handed to an [`AssertionPlugin`].
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct AssertionInput {
+    /// Response body.
+    pub body: String,
+
+    /// Response headers (lower-cased names).
+    #[serde(default)]
+    pub headers: HashMap<String, String>,
+
+    /// Free-form configuration string from the scenario (`config:` in YAML).
+    #[serde(default)]
+    pub config: Option<String>,
+}
+
+/// Outcome returned by an [`AssertionPlugin`].
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct PluginAssertionOutcome {
+    /// Whether the assertion passed.
+    pub passed: bool,
+
+    /// Optional explanation, shown when the assertion fails.
+    #[serde(default)]
+    pub message: Option<String>,
+}
+
+/// Executes requests for a custom protocol.
+pub trait ExecutorPlugin: Send + Sync {
+    /// Plugin name (used in logs).
+    fn name(&self) -> &str;
+
+    /// Execute a single request. Called from a blocking thread, so
+    /// implementations may perform synchronous I/O.
+    fn execute(&self, request: &PluginRequest) -> Result<PluginResponse, PluginError>;
+}
+
+/// Validates responses with custom logic.
+pub trait AssertionPlugin: Send + Sync {
+    /// Plugin name, referenced by `plugin:` in YAML assertions.
+    fn name(&self) -> &str;
+
+    /// Check a response.
+    fn check(
+        &self,
+        status_code: u16,
+        response_time_ms: u64,
+        input: &AssertionInput,
+    ) -> Result<PluginAssertionOutcome, PluginError>;
+}
+
+/// Produces rows of variables for data-driven tests.
+pub trait DataSourcePlugin: Send + Sync {
+    /// Plugin name.
+    fn name(&self) -> &str;
+
+    /// Return the next row, or `None` once the source is exhausted.
+    fn next_row(&self) -> Result<Option<HashMap<String, String>>, PluginError>;
+}
+
+/// Registry of loaded plugins, keyed by name.
+///
+/// Executor plugins are keyed by the upper-cased method name they handle.
+#[derive(Default)]
+pub struct PluginRegistry {
+    executors: HashMap<String, Arc<dyn ExecutorPlugin>>,
+    assertions: HashMap<String, Arc<dyn AssertionPlugin>>,
+    data_sources: HashMap<String, Arc<dyn DataSourcePlugin>>,
+    middleware: MiddlewareChain,
+}
+
+impl PluginRegistry {
+    /// Create an empty registry.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Register an executor plugin for the given method (case-insensitive).
+    pub fn register_executor(&mut self, method: &str, plugin: Arc<dyn ExecutorPlugin>) {
+        info!(method = %method, plugin = %plugin.name(), "Registered executor plugin");
+        self.executors.insert(method.to_uppercase(), plugin);
+    }
+
+    /// Register an assertion plugin under its own name.
+    pub fn register_assertion(&mut self, plugin: Arc<dyn AssertionPlugin>) {
+        info!(plugin = %plugin.name(), "Registered assertion plugin");
+        self.assertions.insert(plugin.name().to_string(), plugin);
+    }
+
+    /// Register a data source plugin under its own name.
+    pub fn register_data_source(&mut self, plugin: Arc<dyn DataSourcePlugin>) {
+        info!(plugin = %plugin.name(), "Registered data source plugin");
+        self.data_sources.insert(plugin.name().to_string(), plugin);
+    }
+
+    /// Append a middleware; it runs after those already registered.
+    pub fn register_middleware(&mut self, middleware: Arc<dyn Middleware>) {
+        info!(middleware = %middleware.name(), "Registered middleware");
+        self.middleware.push(middleware);
+    }
+
+    /// Look up the executor plugin handling `method`.
+    pub fn executor(&self, method: &str) -> Option<Arc<dyn ExecutorPlugin>> {
+        self.executors.get(&method.to_uppercase()).cloned()
+    }
+
+    /// Look up an assertion plugin by name.
+    pub fn assertion(&self, name: &str) -> Option<Arc<dyn AssertionPlugin>> {
+        self.assertions.get(name).cloned()
+    }
+
+    /// Look up a data source plugin by name.
+    pub fn data_source(&self, name: &str) -> Option<Arc<dyn DataSourcePlugin>> {
+        self.data_sources.get(name).cloned()
+    }
+
+    /// Registered middleware, in order.
+    pub fn middleware(&self) -> MiddlewareChain {
+        self.middleware.clone()
+    }
+
+    /// Total number of registered plugin entry points.
+    pub fn len(&self) -> usize {
+        self.executors.len()
+            + self.assertions.len()
+            + self.data_sources.len()
+            + self.middleware.len()
+    }
+
+    /// Returns true when no plugins are registered.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+lazy_static::lazy_static! {
+    /// Process-wide plugin registry consulted by the executor and assertions.
+    pub static ref GLOBAL_PLUGIN_REGISTRY: RwLock<PluginRegistry> =
+        RwLock::new(PluginRegistry::new());
+}
+
+/// Look up an executor plugin in the global registry.
+pub fn global_executor(method: &str) -> Option<Arc<dyn ExecutorPlugin>> {
+    GLOBAL_PLUGIN_REGISTRY.read().ok()?.executor(method)
+}
+
+/// Look up an assertion plugin in the global registry.
+pub fn global_assertion(name: &str) -> Option<Arc<dyn AssertionPlugin>> {
+    GLOBAL_PLUGIN_REGISTRY.read().ok()?.assertion(name)
+}
+
+/// Look up a data source plugin in the global registry.
+pub fn global_data_source(name: &str) -> Option<Arc<dyn DataSourcePlugin>> {
+    GLOBAL_PLUGIN_REGISTRY.read().ok()?.data_source(name)
+}
+
+/// Middleware registered in the global registry.
+pub fn global_middleware() -> MiddlewareChain {
+    GLOBAL_PLUGIN_REGISTRY
+        .read()
+        .map(|registry| registry.middleware())
+        .unwrap_or_default()
+}
+
+/// Load WASM plugins listed in `PLUGIN_PATHS` into the global registry.
+///
+/// `PLUGIN_PATHS` is a comma-separated list of `.wasm` (or `.wat`) files.
+/// The plugin name is the file stem; executor plugins handle the method whose
+/// name is the upper-cased file stem.
+///
+/// Returns the number of plugin modules loaded. Returns `Ok(0)` when
+/// `PLUGIN_PATHS` is unset or empty.
+pub fn load_plugins_from_env() -> Result<usize, PluginError> {
+    let paths: Vec<String> = std::env::var("PLUGIN_PATHS")
+        .unwrap_or_default()
+        .split(',')
+        .map(|s| s.trim().to_string())
+        .filter(|s| !s.is_empty())
+        .collect();
+
+    if paths.is_empty() {
+        return Ok(0);
+    }
+
+    load_wasm_plugins(&paths)
+}
+
+#[cfg(not(feature = "wasm-plugins"))]
+fn load_wasm_plugins(_paths: &[String]) -> Result<usize, PluginError> {
+    Err(PluginError::WasmSupportDisabled)
+}
+
+#[cfg(feature = "wasm-plugins")]
+fn load_wasm_plugins(paths: &[String]) -> Result<usize, PluginError> {
+    let mut registry = GLOBAL_PLUGIN_REGISTRY
+        .write()
+        .map_err(|e| PluginError::LoadFailed {
+            path: paths.join(","),
+            message: e.to_string(),
+        })?;
+
+    for path in paths {
+        let plugin = Arc::new(wasm::WasmPlugin::load(path)?);
+        let caps = plugin.capabilities();
+        if caps & CAP_EXECUTOR != 0 {
+            let method = plugin.name().to_string();
+            registry.register_executor(&method, plugin.clone());
+        }
+        if caps & CAP_ASSERTION != 0 {
+            registry.register_assertion(plugin.clone());
+        }
+        if caps & CAP_DATA_SOURCE != 0 {
+            registry.register_data_source(plugin.clone());
+        }
+    }
+
+    Ok(paths.len())
+}
+
+#[cfg(feature = "wasm-plugins")]
+pub mod wasm {
+    //! WASM plugin loader built on wasmtime.
+
+    use super::*;
+    use std::path::Path;
+    use std::sync::Mutex;
+    use wasmtime::{Engine, Instance, Memory, Module, Store};
+
+    struct WasmState {
+        store: Store<()>,
+        instance: Instance,
+        memory: Memory,
+    }
+
+    /// A plugin backed by a WebAssembly module.
+    ///
+    /// wasmtime stores are single-threaded, so calls are serialized through a
+    /// mutex. Plugins doing heavy work should be kept small or loaded once per
+    /// protocol.
+    pub struct WasmPlugin {
+        name: String,
+        capabilities: u32,
+        state: Mutex<WasmState>,
+    }
+
+    impl WasmPlugin {
+        /// Load and validate a plugin module from disk.
+        pub fn load(path: &str) -> Result<Self, PluginError> {
+            let load_err = |message: String| PluginError::LoadFailed {
+                path: path.to_string(),
+                message,
+            };
+
+            let name = Path::new(path)
+                .file_stem()
+                .and_then(|s| s.to_str())
+                .ok_or_else(|| load_err("invalid file name".to_string()))?
+                .to_string();
+
+            let engine = Engine::default();
+            let module = Module::from_file(&engine, path).map_err(|e| load_err(e.to_string()))?;
+            let mut store = Store::new(&engine, ());
+            let instance =
+                Instance::new(&mut store, &module, &[]).map_err(|e| load_err(e.to_string()))?;
+
+            let memory = instance.get_memory(&mut store, "memory").ok_or_else(|| {
+                PluginError::MissingExport {
+                    name: name.clone(),
+                    export: "memory".to_string(),
+                }
+            })?;
+
+            let version = call_i32(&mut store, &instance, &name, "rlt_abi_version")? as u32;
+            if version != PLUGIN_ABI_VERSION {
+                return Err(PluginError::AbiMismatch {
+                    name,
+                    found: version,
+                    expected: PLUGIN_ABI_VERSION,
+                });
+            }
+
+            let capabilities = call_i32(&mut store, &instance, &name, "rlt_capabilities")? as u32;
+
+            info!(
+                plugin = %name,
+                path = %path,
+                capabilities,
+                "Loaded WASM plugin"
+            );
+
+            Ok(Self {
+                name,
+                capabilities,
+                state: Mutex::new(WasmState {
+                    store,
+                    instance,
+                    memory,
+                }),
+            })
+        }
+
+        /// Plugin name (file stem of the module path).
+        pub fn name(&self) -> &str {
+            &self.name
+        }
+
+        /// Capability bit set advertised by the module.
+        pub fn capabilities(&self) -> u32 {
+            self.capabilities
+        }
+
+        fn fail(&self, message: impl ToString) -> PluginError {
+            PluginError::InvocationFailed {
+                name: self.name.clone(),
+                message: message.to_string(),
+            }
+        }
+
+        /// Copy `input` into guest memory, returning its (ptr, len).
+        fn write_input(
+            &self,
+            state: &mut WasmState,
+            input: &[u8],
+        ) -> Result<(i32, i32), PluginError> {
+            let alloc = state
+                .instance
+                .get_typed_func::<i32, i32>(&mut state.store, "rlt_alloc")
+                .map_err(|_| self.missing("rlt_alloc"))?;
+            let len = input.len() as i32;
+            let ptr = alloc
+                .call(&mut state.store, len)
+                .map_err(|e| self.fail(e))?;
+            state
+                .memory
+                .write(&mut state.store, ptr as usize, input)
+                .map_err(|e| self.fail(e))?;
+            Ok((ptr, len))
+        }
+
+        /// Read a packed `(ptr << 32) | len` result out of guest memory.
+        fn read_output(&self, state: &mut WasmState, packed: i64) -> Result<Vec<u8>, PluginError> {
+            let ptr = ((packed as u64) >> 32) as usize;
+            let len = (packed as u64 & 0xFFFF_FFFF) as usize;
+            let mut buf = vec![0u8; len];
+            state
+                .memory
+                .read(&state.store, ptr, &mut buf)
+                .map_err(|e| self.fail(e))?;
+            Ok(buf)
+        }
+
+        fn missing(&self, export: &str) -> PluginError {
+            PluginError::MissingExport {
+                name: self.name.clone(),
+                export: export.to_string(),
+            }
+        }
+    }
+
+    fn call_i32(
+        store: &mut Store<()>,
+        instance: &Instance,
+        name: &str,
+        export: &str,
+    ) -> Result<i32, PluginError> {
+        let func = instance
+            .get_typed_func::<(), i32>(&mut *store, export)
+            .map_err(|_| PluginError::MissingExport {
+                name: name.to_string(),
+                export: export.to_string(),
+            })?;
+        func.call(&mut *store, ())
+            .map_err(|e| PluginError::InvocationFailed {
+                name: name.to_string(),
+                message: e.to_string(),
+            })
+    }
+
+    impl ExecutorPlugin for WasmPlugin {
+        fn name(&self) -> &str {
+            &self.name
+        }
+
+        fn execute(&self, request: &PluginRequest) -> Result<PluginResponse, PluginError> {
+            let input = serde_json::to_vec(request)?;
+            let mut guard = self.state.lock().map_err(|e| self.fail(e))?;
+            let state = &mut *guard;
+            let (ptr, len) = self.write_input(state, &input)?;
+            let func = state
+                .instance
+                .get_typed_func::<(i32, i32), i64>(&mut state.store, "rlt_execute")
+                .map_err(|_| self.missing("rlt_execute"))?;
+            let packed = func
+                .call(&mut state.store, (ptr, len))
+                .map_err(|e| self.fail(e))?;
+            let output = self.read_output(state, packed)?;
+            Ok(serde_json::from_slice(&output)?)
+        }
+    }
+
+    impl AssertionPlugin for WasmPlugin {
+        fn name(&self) -> &str {
+            &self.name
+        }
+
+        fn check(
+            &self,
+            status_code: u16,
+            response_time_ms: u64,
+            input: &AssertionInput,
+        ) -> Result<PluginAssertionOutcome, PluginError> {
+            let payload = serde_json::to_vec(input)?;
+            let mut guard = self.state.lock().map_err(|e| self.fail(e))?;
+            let state = &mut *guard;
+            let (ptr, len) = self.write_input(state, &payload)?;
+            let func = state
+                .instance
+                .get_typed_func::<(i32, i64, i32, i32), i64>(&mut state.store, "rlt_assert")
+                .map_err(|_| self.missing("rlt_assert"))?;
+            let packed = func
+                .call(
+                    &mut state.store,
+                    (status_code as i32, response_time_ms as i64, ptr, len),
+                )
+                .map_err(|e| self.fail(e))?;
+            let output = self.read_output(state, packed)?;
+            Ok(serde_json::from_slice(&output)?)
+        }
+    }
+
+    impl DataSourcePlugin for WasmPlugin {
+        fn name(&self) -> &str {
+            &self.name
+        }
+
+        fn next_row(&self) -> Result<Option<HashMap<String, String>>, PluginError> {
+            let mut guard = self.state.lock().map_err(|e| self.fail(e))?;
+            let state = &mut *guard;
+            let func = state
+                .instance
+                .get_typed_func::<(), i64>(&mut state.store, "rlt_next_row")
+                .map_err(|_| self.missing("rlt_next_row"))?;
+            let packed = func.call(&mut state.store, ()).map_err(|e| self.fail(e))?;
+            if packed as u64 & 0xFFFF_FFFF == 0 {
+                return Ok(None);
+            }
+            let output = self.read_output(state, packed)?;
+            Ok(Some(serde_json::from_slice(&output)?))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    struct EchoExecutor;
+
+    impl ExecutorPlugin for EchoExecutor {
+        fn name(&self) -> &str {
+            "echo"
+        }
+
+        fn execute(&self, request: &PluginRequest) -> Result<PluginResponse, PluginError> {
+            Ok(PluginResponse {
+                status: 200,
+                headers: request.headers.clone(),
+                body: request.body.clone().unwrap_or_default(),
+            })
+        }
+    }
+
+    struct MinLength;
+
+    impl AssertionPlugin for MinLength {
+        fn name(&self) -> &str {
+            "min_length"
+        }
+
+        fn check(
+            &self,
+            _status_code: u16,
+            _response_time_ms: u64,
+            input: &AssertionInput,
+        ) -> Result<PluginAssertionOutcome, PluginError> {
+            let min: usize = input.config.as_deref().unwrap_or("0").parse().map_err(
+                |e: std::num::ParseIntError| PluginError::InvocationFailed {
+                    name: "min_length".to_string(),
+                    message: e.to_string(),
+                },
+            )?;
+            Ok(PluginAssertionOutcome {
+                passed: input.body.len() >= min,
+                message: None,
+            })
+        }
+    }
+
+    struct Counter(AtomicUsize);
+
+    impl DataSourcePlugin for Counter {
+        fn name(&self) -> &str {
+            "counter"
+        }
+
+        fn next_row(&self) -> Result<Option<HashMap<String, String>>, PluginError> {
+            let n = self.0.fetch_add(1, Ordering::Relaxed);
+            if n >= 2 {
+                return Ok(None);
+            }
+            let mut row = HashMap::new();
+            row.insert("n".to_string(), n.to_string());
+            Ok(Some(row))
+        }
+    }
+
+    #[test]
+    fn registry_starts_empty() {
+        let registry = PluginRegistry::new();
+        assert!(registry.is_empty());
+        assert!(registry.executor("MQTT").is_none());
+        assert!(registry.assertion("x").is_none());
+        assert!(registry.data_source("x").is_none());
+        assert!(registry.middleware().is_empty());
+    }
+
+    #[test]
+    fn registers_middleware_in_order() {
+        let mut registry = PluginRegistry::new();
+        let injector =
+            |value| Arc::new(crate::middleware::HeaderInjector::new([("X-Layer", value)]).unwrap());
+        registry.register_middleware(injector("1"));
+        registry.register_middleware(injector("2"));
+        assert_eq!(registry.len(), 2);
+        assert_eq!(
+            format!("{:?}", registry.middleware()),
+            r#"["header-injector", "header-injector"]"#
+        );
+    }
+
+    #[test]
+    fn executor_lookup_is_case_insensitive() {
+        let mut registry = PluginRegistry::new();
+        registry.register_executor("echo", Arc::new(EchoExecutor));
+
+        let plugin = registry.executor("ECHO").expect("executor registered");
+        let response = plugin
+            .execute(&PluginRequest {
+                method: "ECHO".to_string(),
+                url: "echo://host".to_string(),
+                headers: HashMap::new(),
+                body: Some("ping".to_string()),
+            })
+            .unwrap();
+        assert_eq!(response.status, 200);
+        assert_eq!(response.body, "ping");
+        assert!(registry.executor("Echo").is_some());
+    }
+
+    #[test]
+    fn assertion_plugin_receives_config() {
+        let mut registry = PluginRegistry::new();
+        registry.register_assertion(Arc::new(MinLength));
+
+        let plugin = registry.assertion("min_length").unwrap();
+        let input = AssertionInput {
+            body: "hello".to_string(),
+            headers: HashMap::new(),
+            config: Some("3".to_string()),
+        };
+        assert!(plugin.check(200, 10, &input).unwrap().passed);
+
+        let input = AssertionInput {
+            config: Some("10".to_string()),
+            ..input
+        };
+        assert!(!plugin.check(200, 10, &input).unwrap().passed);
+    }
+
+    #[test]
+    fn data_source_plugin_exhausts() {
+        let mut registry = PluginRegistry::new();
+        registry.register_data_source(Arc::new(Counter(AtomicUsize::new(0))));
+        assert_eq!(registry.len(), 1);
+
+        let source = registry.data_source("counter").unwrap();
+        assert_eq!(source.next_row().unwrap().unwrap()["n"], "0");
+        assert_eq!(source.next_row().unwrap().unwrap()["n"], "1");
+        assert!(source.next_row().unwrap().is_none());
+    }
+
+    #[test]
+    fn plugin_json_shapes_round_trip() {
+        let outcome: PluginAssertionOutcome = serde_json::from_str(r#"{"passed":true}"#).unwrap();
+        assert!(outcome.passed);
+        assert!(outcome.message.is_none());
+
+        let response: PluginResponse = serde_json::from_str(r#"{"status":201}"#).unwrap();
+        assert_eq!(response.status, 201);
+        assert!(response.body.is_empty());
+    }
+
+    #[test]
+    fn abi_mismatch_error_message() {
+        let err = PluginError::AbiMismatch {
+            name: "p".to_string(),
+            found: 2,
+            expected: PLUGIN_ABI_VERSION,
+        };
+        assert!(err.to_string().contains("ABI version 2"));
+    }
+
+    #[cfg(feature = "wasm-plugins")]
+    #[test]
+    fn loads_example_wasm_assertion_plugin() {
+        let path = concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/examples/plugins/status_below_500.wat"
+        );
+        let plugin = wasm::WasmPlugin::load(path).expect("example plugin loads");
+        assert_eq!(plugin.capabilities(), CAP_ASSERTION);
+
+        let input = AssertionInput {
+            body: "{}".to_string(),
+            headers: HashMap::new(),
+            config: None,
+        };
+        assert!(plugin.check(200, 5, &input).unwrap().passed);
+
+        let outcome = plugin.check(503, 5, &input).unwrap();
+        assert!(!outcome.passed);
+        assert_eq!(outcome.message.as_deref(), Some("status code >= 500"));
+    }
+
+    #[cfg(not(feature = "wasm-plugins"))]
+    #[test]
+    fn wasm_loading_requires_feature() {
+        let result = load_wasm_plugins(&["plugin.wasm".to_string()]);
+        assert!(matches!(result, Err(PluginError::WasmSupportDisabled)));
+    }
+}
@@ -0,0 +1,312 @@
+//! Target reachability and TLS pre-check (`TARGET_PRECHECK`).
+//!
+//! A typo in `TARGET_URL`, a firewall, or an expired certificate otherwise
+//! shows up as thousands of identical connection errors for the whole test
+//! duration. Before any load is sent, every target is checked step by step:
+//! DNS resolution, a TCP connect, the TLS handshake (for `https://`), and a
+//! single `HEAD` request through the real HTTP client. The first step that
+//! fails stops the node with `target_unreachable` (exit code 4) and names
+//! the step, so the fix is obvious from the one log line.
+//!
+//! The checks follow the client's own routing: `RESOLVE_TARGET_ADDR`,
+//! `TLS_SERVER_NAME`, `IP_FAMILY`, `SKIP_TLS_VERIFY` and the mTLS identity.
+//! Any HTTP response, whatever its status, passes the last step.
+
+use std::net::SocketAddr;
+use std::sync::Arc;
+use std::time::Duration;
+
+use reqwest::Url;
+use rustls::pki_types::ServerName;
+use thiserror::Error;
+use tokio::net::TcpStream;
+use tokio::time::{timeout, Instant};
+use tokio_rustls::TlsConnector;
+
+use crate::addr::resolved_addrs;
+use crate::client::{parse_resolve_target_addr, ClientConfig};
+use crate::tls;
+
+/// How long each step may take.
+pub const PRECHECK_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// The step a pre-check failed at.
+#[derive(Error, Debug)]
+pub enum PrecheckError {
+    #[error("invalid target URL '{url}': {message}")]
+    InvalidUrl { url: String, message: String },
+
+    #[error("DNS resolution of '{host}' failed: {message}")]
+    Dns { host: String, message: String },
+
+    #[error("TCP connect to {addr} failed: {message}")]
+    Connect { addr: String, message: String },
+
+    #[error("TLS handshake with '{server_name}' at {addr} failed: {message}")]
+    Tls {
+        server_name: String,
+        addr: SocketAddr,
+        message: String,
+    },
+
+    #[error("HEAD {url} got no response: {message}")]
+    Http { url: String, message: String },
+}
+
+/// A passed pre-check and how long each step took.
+#[derive(Debug, Clone)]
+pub struct PrecheckReport {
+    pub url: String,
+    pub addr: SocketAddr,
+    pub dns: Duration,
+    pub connect: Duration,
+    /// `None` for `http://` targets.
+    pub tls: Option<Duration>,
+    pub status: u16,
+    pub http: Duration,
+}
+
+/// Host connected to for `host`, after `TLS_SERVER_NAME` and
+/// `RESOLVE_TARGET_ADDR`: either pinned addresses or a name to resolve.
+enum ConnectTarget {
+    Pinned(Vec<SocketAddr>),
+    Lookup(String),
+}
+
+fn connect_target(host: &str, config: &ClientConfig) -> Result<ConnectTarget, String> {
+    if let Some(resolve) = config.resolve_target_addr.as_deref() {
+        if !resolve.is_empty() {
+            let addrs = resolved_addrs(&parse_resolve_target_addr(resolve)?, host);
+            if !addrs.is_empty() {
+                return Ok(ConnectTarget::Pinned(addrs));
+            }
+        }
+    }
+    if let Some(sni) = &config.sni_override {
+        if sni.server_name.eq_ignore_ascii_case(host) {
+            return Ok(ConnectTarget::Lookup(sni.connect_host.clone()));
+        }
+    }
+    Ok(ConnectTarget::Lookup(host.to_string()))
+}
+
+/// `error` and its sources, e.g. `error sending request: connection refused`.
+fn error_chain(error: &(dyn std::error::Error + 'static)) -> String {
+    let mut message = error.to_string();
+    let mut current = error.source();
+    while let Some(err) = current {
+        message.push_str(": ");
+        message.push_str(&err.to_string());
+        current = err.source();
+    }
+    message
+}
+
+/// Checks `url` step by step, sending its `HEAD` request through `client`.
+pub async fn check(
+    url: &str,
+    config: &ClientConfig,
+    client: &reqwest::Client,
+) -> Result<PrecheckReport, PrecheckError> {
+    let invalid = |message: String| PrecheckError::InvalidUrl {
+        url: url.to_string(),
+        message,
+    };
+    let parsed = Url::parse(url).map_err(|e| invalid(e.to_string()))?;
+    let host = parsed
+        .host_str()
+        .ok_or_else(|| invalid("no host".to_string()))?
+        .trim_matches(|c| c == '[' || c == ']')
+        .to_string();
+    let port = parsed
+        .port_or_known_default()
+        .ok_or_else(|| invalid("no port".to_string()))?;
+
+    // DNS
+    let started = Instant::now();
+    let dns_error = |message: String| PrecheckError::Dns {
+        host: host.clone(),
+        message,
+    };
+    let addrs: Vec<SocketAddr> = match connect_target(&host, config).map_err(dns_error)? {
+        ConnectTarget::Pinned(addrs) => addrs,
+        ConnectTarget::Lookup(name) => {
+            let lookup = timeout(
+                PRECHECK_TIMEOUT,
+                tokio::net::lookup_host((name.as_str(), port)),
+            )
+            .await
+            .map_err(|_| dns_error("timed out".to_string()))?
+            .map_err(|e| dns_error(e.to_string()))?;
+            let addrs: Vec<SocketAddr> = lookup
+                .filter(|addr| config.ip_family.allows(addr.ip()))
+                .collect();
+            if addrs.is_empty() {
+                return Err(dns_error(format!(
+                    "'{}' has no {} addresses",
+                    name, config.ip_family
+                )));
+            }
+            addrs
+        }
+    };
+    let dns = started.elapsed();
+
+    // TCP: the first address that accepts a connection
+    let started = Instant::now();
+    let mut last_error = String::new();
+    let mut connected = None;
+    for addr in &addrs {
+        match timeout(PRECHECK_TIMEOUT, TcpStream::connect(addr)).await {
+            Ok(Ok(stream)) => {
+                connected = Some((*addr, stream));
+                break;
+            }
+            Ok(Err(e)) => last_error = e.to_string(),
+            Err(_) => last_error = "timed out".to_string(),
+        }
+    }
+    let Some((addr, stream)) = connected else {
+        let addr = match addrs.as_slice() {
+            [addr] => addr.to_string(),
+            addrs => format!("{} addresses of '{}'", addrs.len(), host),
+        };
+        return Err(PrecheckError::Connect {
+            addr,
+            message: last_error,
+        });
+    };
+    let connect = started.elapsed();
+
+    // TLS
+    let tls = if parsed.scheme() == "https" {
+        let started = Instant::now();
+        let tls_error = |message: String| PrecheckError::Tls {
+            server_name: host.clone(),
+            addr,
+            message,
+        };
+        let tls_config = tls::client_tls_config(config).map_err(|e| tls_error(e.to_string()))?;
+        let server_name =
+            ServerName::try_from(host.clone()).map_err(|e| tls_error(e.to_string()))?;
+        timeout(
+            PRECHECK_TIMEOUT,
+            TlsConnector::from(Arc::new(tls_config)).connect(server_name, stream),
+        )
+        .await
+        .map_err(|_| tls_error("timed out".to_string()))?
+        .map_err(|e| tls_error(e.to_string()))?;
+        Some(started.elapsed())
+    } else {
+        drop(stream);
+        None
+    };
+
+    // HTTP, through the client the workers use
+    let started = Instant::now();
+    let response = timeout(PRECHECK_TIMEOUT, client.head(url).send())
+        .await
+        .map_err(|_| PrecheckError::Http {
+            url: url.to_string(),
+            message: "timed out".to_string(),
+        })?
+        .map_err(|e| PrecheckError::Http {
+            url: url.to_string(),
+            message: error_chain(&e),
+        })?;
+
+    Ok(PrecheckReport {
+        url: url.to_string(),
+        addr,
+        dns,
+        connect,
+        tls,
+        status: response.status().as_u16(),
+        http: started.elapsed(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ip_family::IpFamily;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+    fn client_config() -> ClientConfig {
+        ClientConfig {
+            skip_tls_verify: false,
+            resolve_target_addr: None,
+            client_cert_path: None,
+            client_key_path: None,
+            custom_headers: None,
+            pool_config: None,
+            cookie_store: false,
+            local_address: None,
+            pinned_target: None,
+            ip_family: IpFamily::Auto,
+            tls_session_resumption: true,
+            sni_override: None,
+            proxy: None,
+            timeout: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn passes_for_a_responding_target() {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move {
+            while let Ok((mut socket, _)) = listener.accept().await {
+                tokio::spawn(async move {
+                    let mut request = vec![0u8; 1024];
+                    if socket.read(&mut request).await.unwrap_or(0) > 0 {
+                        let _ = socket
+                            .write_all(
+                                b"HTTP/1.1 503 Service Unavailable\r\nContent-Length: 0\r\n\r\n",
+                            )
+                            .await;
+                    }
+                });
+            }
+        });
+
+        // RESOLVE_TARGET_ADDR sends the made-up name to the listener.
+        let mut config = client_config();
+        config.resolve_target_addr = Some(format!(
+            "other.test:10.0.0.1:80,precheck.test:{}:{}",
+            addr.ip(),
+            addr.port()
+        ));
+        let client = crate::client::build_client(&config).unwrap().client;
+        let report = check(
+            &format!("http://precheck.test:{}/health", addr.port()),
+            &config,
+            &client,
+        )
+        .await
+        .unwrap();
+        assert_eq!(report.addr, addr);
+        assert_eq!(report.status, 503);
+        assert!(report.tls.is_none());
+    }
+
+    #[tokio::test]
+    async fn names_the_failing_step() {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        drop(listener);
+
+        let config = client_config();
+        let client = reqwest::Client::new();
+        let err = check(&format!("http://{}/", addr), &config, &client)
+            .await
+            .unwrap_err();
+        assert!(matches!(err, PrecheckError::Connect { .. }), "{}", err);
+        assert!(err
+            .to_string()
+            .starts_with(&format!("TCP connect to {}", addr)));
+
+        let err = check("not a url", &config, &client).await.unwrap_err();
+        assert!(matches!(err, PrecheckError::InvalidUrl { .. }));
+    }
+}
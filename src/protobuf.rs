@@ -0,0 +1,227 @@
+//! Protocol Buffers request/response bodies over plain HTTP.
+//!
+//! A step with a `protobuf:` block keeps writing its body as JSON; the body is
+//! encoded to the binary wire format with the message type from a compiled
+//! descriptor set (`protoc --include_imports --descriptor_set_out=api.pb ...`)
+//! before it is sent. Responses are decoded back to JSON so the usual
+//! `jsonPath` extractors and assertions work on them.
+//!
+//! Encoding requires building with `--features protobuf`; without it, steps
+//! using `protobuf:` fail with [`ProtobufError::FeatureDisabled`].
+
+use std::path::PathBuf;
+use thiserror::Error;
+
+use crate::scenario::ProtobufConfig;
+
+/// Content type sent with encoded request bodies unless the step sets one.
+pub const PROTOBUF_CONTENT_TYPE: &str = "application/x-protobuf";
+
+/// Errors raised while encoding or decoding protobuf bodies.
+#[derive(Error, Debug)]
+pub enum ProtobufError {
+    #[error("protobuf bodies require building with `--features protobuf`")]
+    FeatureDisabled,
+
+    #[error("Failed to read descriptor set {path}: {source}")]
+    DescriptorRead {
+        path: PathBuf,
+        source: std::io::Error,
+    },
+
+    #[error("Invalid descriptor set {path}: {message}")]
+    InvalidDescriptor { path: PathBuf, message: String },
+
+    #[error("Message type '{0}' not found in descriptor set")]
+    UnknownMessage(String),
+
+    #[error("Failed to encode protobuf body: {0}")]
+    Encode(String),
+
+    #[error("Failed to decode protobuf response: {0}")]
+    Decode(String),
+}
+
+/// Encodes a JSON body as `config.request_message`.
+pub fn encode_json(config: &ProtobufConfig, json: &str) -> Result<Vec<u8>, ProtobufError> {
+    let message = config
+        .request_message
+        .as_deref()
+        .ok_or_else(|| ProtobufError::Encode("no requestMessage configured".to_string()))?;
+    imp::encode_json(config, message, json)
+}
+
+/// Decodes a binary response as `config.response_message` into JSON text.
+pub fn decode_to_json(config: &ProtobufConfig, bytes: &[u8]) -> Result<String, ProtobufError> {
+    let message = config
+        .response_message
+        .as_deref()
+        .ok_or_else(|| ProtobufError::Decode("no responseMessage configured".to_string()))?;
+    imp::decode_to_json(config, message, bytes)
+}
+
+#[cfg(feature = "protobuf")]
+mod imp {
+    use super::ProtobufError;
+    use crate::scenario::ProtobufConfig;
+    use prost::Message;
+    use prost_reflect::{DescriptorPool, DynamicMessage, MessageDescriptor};
+    use std::collections::HashMap;
+    use std::path::{Path, PathBuf};
+    use std::sync::Mutex;
+
+    lazy_static::lazy_static! {
+        /// Descriptor sets are parsed once per path and shared by all workers.
+        static ref POOLS: Mutex<HashMap<PathBuf, DescriptorPool>> = Mutex::new(HashMap::new());
+    }
+
+    fn message_descriptor(path: &Path, name: &str) -> Result<MessageDescriptor, ProtobufError> {
+        let mut pools = POOLS.lock().unwrap_or_else(|e| e.into_inner());
+        let pool = match pools.get(path) {
+            Some(pool) => pool.clone(),
+            None => {
+                let bytes =
+                    std::fs::read(path).map_err(|source| ProtobufError::DescriptorRead {
+                        path: path.to_path_buf(),
+                        source,
+                    })?;
+                let pool = DescriptorPool::decode(bytes.as_slice()).map_err(|e| {
+                    ProtobufError::InvalidDescriptor {
+                        path: path.to_path_buf(),
+                        message: e.to_string(),
+                    }
+                })?;
+                pools.insert(path.to_path_buf(), pool.clone());
+                pool
+            }
+        };
+        pool.get_message_by_name(name)
+            .ok_or_else(|| ProtobufError::UnknownMessage(name.to_string()))
+    }
+
+    pub fn encode_json(
+        config: &ProtobufConfig,
+        message: &str,
+        json: &str,
+    ) -> Result<Vec<u8>, ProtobufError> {
+        let descriptor = message_descriptor(&config.descriptor_set, message)?;
+        let mut deserializer = serde_json::Deserializer::from_str(json);
+        let dynamic = DynamicMessage::deserialize(descriptor, &mut deserializer)
+            .map_err(|e| ProtobufError::Encode(e.to_string()))?;
+        deserializer
+            .end()
+            .map_err(|e| ProtobufError::Encode(e.to_string()))?;
+        Ok(dynamic.encode_to_vec())
+    }
+
+    pub fn decode_to_json(
+        config: &ProtobufConfig,
+        message: &str,
+        bytes: &[u8],
+    ) -> Result<String, ProtobufError> {
+        let descriptor = message_descriptor(&config.descriptor_set, message)?;
+        let dynamic = DynamicMessage::decode(descriptor, bytes)
+            .map_err(|e| ProtobufError::Decode(e.to_string()))?;
+        serde_json::to_string(&dynamic).map_err(|e| ProtobufError::Decode(e.to_string()))
+    }
+}
+
+#[cfg(not(feature = "protobuf"))]
+mod imp {
+    use super::ProtobufError;
+    use crate::scenario::ProtobufConfig;
+
+    pub fn encode_json(
+        _config: &ProtobufConfig,
+        _message: &str,
+        _json: &str,
+    ) -> Result<Vec<u8>, ProtobufError> {
+        Err(ProtobufError::FeatureDisabled)
+    }
+
+    pub fn decode_to_json(
+        _config: &ProtobufConfig,
+        _message: &str,
+        _bytes: &[u8],
+    ) -> Result<String, ProtobufError> {
+        Err(ProtobufError::FeatureDisabled)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg(not(feature = "protobuf"))]
+    #[test]
+    fn reports_missing_feature() {
+        let config = ProtobufConfig {
+            descriptor_set: PathBuf::from("api.pb"),
+            request_message: Some("api.Ping".to_string()),
+            response_message: None,
+        };
+        assert!(matches!(
+            encode_json(&config, "{}"),
+            Err(ProtobufError::FeatureDisabled)
+        ));
+        assert!(matches!(
+            decode_to_json(&config, b""),
+            Err(ProtobufError::Decode(_))
+        ));
+    }
+
+    #[cfg(feature = "protobuf")]
+    #[test]
+    fn round_trips_json_through_descriptor_set() {
+        use prost::Message;
+        use prost_types::{
+            field_descriptor_proto::{Label, Type},
+            DescriptorProto, FieldDescriptorProto, FileDescriptorProto, FileDescriptorSet,
+        };
+
+        let field = |name: &str, number: i32, ty: Type| FieldDescriptorProto {
+            name: Some(name.to_string()),
+            number: Some(number),
+            label: Some(Label::Optional as i32),
+            r#type: Some(ty as i32),
+            json_name: Some(name.to_string()),
+            ..Default::default()
+        };
+        let set = FileDescriptorSet {
+            file: vec![FileDescriptorProto {
+                name: Some("orders.proto".to_string()),
+                package: Some("shop".to_string()),
+                syntax: Some("proto3".to_string()),
+                message_type: vec![DescriptorProto {
+                    name: Some("Order".to_string()),
+                    field: vec![field("id", 1, Type::String), field("qty", 2, Type::Int32)],
+                    ..Default::default()
+                }],
+                ..Default::default()
+            }],
+        };
+        let file = tempfile::NamedTempFile::new().unwrap();
+        std::fs::write(file.path(), set.encode_to_vec()).unwrap();
+
+        let config = ProtobufConfig {
+            descriptor_set: file.path().to_path_buf(),
+            request_message: Some("shop.Order".to_string()),
+            response_message: Some("shop.Order".to_string()),
+        };
+        let bytes = encode_json(&config, r#"{"id": "A-1", "qty": 3}"#).unwrap();
+        let json: serde_json::Value =
+            serde_json::from_str(&decode_to_json(&config, &bytes).unwrap()).unwrap();
+        assert_eq!(json["id"], "A-1");
+        assert_eq!(json["qty"], 3);
+
+        assert!(encode_json(&config, r#"{"nope": 1}"#).is_err());
+        let unknown = ProtobufConfig {
+            request_message: Some("shop.Missing".to_string()),
+            ..config
+        };
+        assert!(matches!(
+            encode_json(&unknown, "{}"),
+            Err(ProtobufError::UnknownMessage(_))
+        ));
+    }
+}
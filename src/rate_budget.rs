@@ -0,0 +1,112 @@
+//! Hard request caps that override the load model (`MAX_TOTAL_REQUESTS`,
+//! `HARD_MAX_RPS`).
+//!
+//! Every HTTP request passes through [`acquire`] just before it is sent:
+//!
+//! - `HARD_MAX_RPS` spaces requests from all workers at least `1 / cap`
+//!   seconds apart, whatever rate the load model asks for. Time spent waiting
+//!   is added to `rate_cap_throttled_seconds_total`.
+//! - `MAX_TOTAL_REQUESTS` stops workers once that many requests have been
+//!   sent and sets `request_budget_exhausted` to 1.
+//!
+//! Both default to 0, meaning no cap.
+
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use crate::metrics::{RATE_CAP_THROTTLED_SECONDS_TOTAL, REQUEST_BUDGET_EXHAUSTED};
+
+/// Request budget; 0 = unlimited.
+static MAX_TOTAL: AtomicU64 = AtomicU64::new(0);
+
+/// Requests admitted so far in the current test.
+static ISSUED: AtomicU64 = AtomicU64::new(0);
+
+/// Minimum spacing between requests in nanoseconds; 0 = uncapped.
+static MIN_INTERVAL_NANOS: AtomicU64 = AtomicU64::new(0);
+
+/// Earliest time the next capped request may be sent.
+static NEXT_SLOT: Mutex<Option<Instant>> = Mutex::new(None);
+
+/// Installs the caps and resets the request count for a new test.
+pub fn init(max_total_requests: u64, hard_max_rps: f64) {
+    MAX_TOTAL.store(max_total_requests, Ordering::Relaxed);
+    ISSUED.store(0, Ordering::Relaxed);
+    let interval = if hard_max_rps > 0.0 {
+        ((1e9 / hard_max_rps) as u64).max(1)
+    } else {
+        0
+    };
+    MIN_INTERVAL_NANOS.store(interval, Ordering::Relaxed);
+    *NEXT_SLOT.lock().unwrap_or_else(|e| e.into_inner()) = None;
+    REQUEST_BUDGET_EXHAUSTED.set(0.0);
+}
+
+/// Returns true once `MAX_TOTAL_REQUESTS` requests have been admitted.
+pub fn is_exhausted() -> bool {
+    let max = MAX_TOTAL.load(Ordering::Relaxed);
+    max > 0 && ISSUED.load(Ordering::Relaxed) >= max
+}
+
+/// Admits one request: returns false if the request budget is spent,
+/// otherwise waits until the rate cap allows the request.
+pub async fn acquire() -> bool {
+    let max = MAX_TOTAL.load(Ordering::Relaxed);
+    if max > 0 && ISSUED.fetch_add(1, Ordering::Relaxed) >= max {
+        REQUEST_BUDGET_EXHAUSTED.set(1.0);
+        return false;
+    }
+
+    let interval = MIN_INTERVAL_NANOS.load(Ordering::Relaxed);
+    if interval == 0 {
+        return true;
+    }
+    let wait = reserve_slot(&NEXT_SLOT, Instant::now(), Duration::from_nanos(interval));
+    if !wait.is_zero() {
+        RATE_CAP_THROTTLED_SECONDS_TOTAL.inc_by(wait.as_secs_f64());
+        tokio::time::sleep(wait).await;
+    }
+    true
+}
+
+/// Returns the budget share of a request admitted by [`acquire`] that is
+/// not sent after all, e.g. one shed under `BACKPRESSURE=shed`.
+pub fn refund() {
+    if MAX_TOTAL.load(Ordering::Relaxed) > 0 {
+        let _ = ISSUED.fetch_update(Ordering::Relaxed, Ordering::Relaxed, |n| {
+            Some(n.saturating_sub(1))
+        });
+    }
+}
+
+/// Reserves the next send slot and returns how long to wait for it.
+fn reserve_slot(next: &Mutex<Option<Instant>>, now: Instant, interval: Duration) -> Duration {
+    let mut next = next.lock().unwrap_or_else(|e| e.into_inner());
+    let slot = match *next {
+        Some(slot) if slot > now => slot,
+        _ => now,
+    };
+    *next = Some(slot + interval);
+    slot - now
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn slots_are_spaced_by_interval() {
+        let next = Mutex::new(None);
+        let now = Instant::now();
+        let interval = Duration::from_millis(10);
+
+        assert_eq!(reserve_slot(&next, now, interval), Duration::ZERO);
+        assert_eq!(reserve_slot(&next, now, interval), interval);
+        assert_eq!(reserve_slot(&next, now, interval), interval * 2);
+
+        // After an idle gap no debt carries over
+        let later = now + Duration::from_secs(1);
+        assert_eq!(reserve_slot(&next, later, interval), Duration::ZERO);
+    }
+}
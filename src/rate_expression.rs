@@ -0,0 +1,540 @@
+//! Math expressions for the Expression load model.
+//!
+//! `model: expression` takes the target rate from a formula of elapsed time,
+//! e.g. `100 + 50*sin(t/600) + (t > 1800 ? 200 : 0)`, for profiles no
+//! built-in model covers. The expression is parsed once and evaluated on
+//! every rate tick.
+//!
+//! - Variables: `t` (elapsed seconds), `duration` (test duration in
+//!   seconds), `pi`, `e`.
+//! - Operators, loosest first: `?:`, `||`, `&&`, comparisons (`< <= > >= ==
+//!   !=`), `+ -`, `* / %`, unary `- !`, `^` (power, right-associative).
+//!   Comparisons and logic yield 1 or 0; any non-zero value is true.
+//! - Functions: `sin cos tan abs sqrt exp ln log10 floor ceil round` of one
+//!   argument, `min max pow` of two and `clamp(x, lo, hi)`.
+//!
+//! A negative or non-finite result means a rate of 0. Parentheses, ternaries,
+//! calls and unary operators nest at most 64 deep.
+
+use std::fmt;
+
+use thiserror::Error;
+
+#[derive(Error, Debug, Clone, PartialEq)]
+pub enum ExpressionError {
+    #[error("at position {position}: {message}")]
+    Syntax { position: usize, message: String },
+
+    #[error("unknown variable '{0}' (expected t, duration, pi or e)")]
+    UnknownVariable(String),
+
+    #[error("unknown function '{0}'")]
+    UnknownFunction(String),
+
+    #[error("{name}() takes {expected} argument(s), got {got}")]
+    Arity {
+        name: String,
+        expected: usize,
+        got: usize,
+    },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Var {
+    T,
+    Duration,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum BinOp {
+    Add,
+    Sub,
+    Mul,
+    Div,
+    Rem,
+    Pow,
+    Lt,
+    Le,
+    Gt,
+    Ge,
+    Eq,
+    Ne,
+    And,
+    Or,
+}
+
+#[derive(Debug, Clone)]
+enum Node {
+    Num(f64),
+    Var(Var),
+    Neg(Box<Node>),
+    Not(Box<Node>),
+    Bin(BinOp, Box<Node>, Box<Node>),
+    Cond(Box<Node>, Box<Node>, Box<Node>),
+    Call(fn(&[f64]) -> f64, Vec<Node>),
+}
+
+/// A parsed rate expression.
+#[derive(Clone)]
+pub struct RateExpression {
+    source: String,
+    root: Node,
+}
+
+impl fmt::Debug for RateExpression {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("RateExpression").field(&self.source).finish()
+    }
+}
+
+impl fmt::Display for RateExpression {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.source)
+    }
+}
+
+impl RateExpression {
+    /// Parses `source`.
+    pub fn parse(source: &str) -> Result<Self, ExpressionError> {
+        let mut parser = Parser {
+            src: source.as_bytes(),
+            pos: 0,
+            depth: 0,
+        };
+        let root = parser.ternary()?;
+        parser.skip_ws();
+        if parser.pos < parser.src.len() {
+            return Err(parser.error("unexpected input"));
+        }
+        Ok(Self {
+            source: source.to_string(),
+            root,
+        })
+    }
+
+    /// The expression's value at `t` seconds into a test of `duration`
+    /// seconds.
+    pub fn eval(&self, t: f64, duration: f64) -> f64 {
+        eval(&self.root, t, duration)
+    }
+}
+
+fn flag(b: bool) -> f64 {
+    if b {
+        1.0
+    } else {
+        0.0
+    }
+}
+
+fn eval(node: &Node, t: f64, duration: f64) -> f64 {
+    let ev = |n: &Node| eval(n, t, duration);
+    match node {
+        Node::Num(x) => *x,
+        Node::Var(Var::T) => t,
+        Node::Var(Var::Duration) => duration,
+        Node::Neg(x) => -ev(x),
+        Node::Not(x) => flag(ev(x) == 0.0),
+        Node::Cond(c, a, b) => {
+            if ev(c) != 0.0 {
+                ev(a)
+            } else {
+                ev(b)
+            }
+        }
+        Node::Bin(BinOp::And, a, b) => flag(ev(a) != 0.0 && ev(b) != 0.0),
+        Node::Bin(BinOp::Or, a, b) => flag(ev(a) != 0.0 || ev(b) != 0.0),
+        Node::Bin(op, a, b) => {
+            let (a, b) = (ev(a), ev(b));
+            match op {
+                BinOp::Add => a + b,
+                BinOp::Sub => a - b,
+                BinOp::Mul => a * b,
+                BinOp::Div => a / b,
+                BinOp::Rem => a % b,
+                BinOp::Pow => a.powf(b),
+                BinOp::Lt => flag(a < b),
+                BinOp::Le => flag(a <= b),
+                BinOp::Gt => flag(a > b),
+                BinOp::Ge => flag(a >= b),
+                BinOp::Eq => flag(a == b),
+                BinOp::Ne => flag(a != b),
+                BinOp::And | BinOp::Or => unreachable!("handled above"),
+            }
+        }
+        Node::Call(f, args) => {
+            let values: Vec<f64> = args.iter().map(ev).collect();
+            f(&values)
+        }
+    }
+}
+
+type Function = fn(&[f64]) -> f64;
+
+/// Name, arity and implementation of each function.
+fn function(name: &str) -> Option<(usize, Function)> {
+    let f: (usize, Function) = match name {
+        "sin" => (1, |a| a[0].sin()),
+        "cos" => (1, |a| a[0].cos()),
+        "tan" => (1, |a| a[0].tan()),
+        "abs" => (1, |a| a[0].abs()),
+        "sqrt" => (1, |a| a[0].sqrt()),
+        "exp" => (1, |a| a[0].exp()),
+        "ln" => (1, |a| a[0].ln()),
+        "log10" => (1, |a| a[0].log10()),
+        "floor" => (1, |a| a[0].floor()),
+        "ceil" => (1, |a| a[0].ceil()),
+        "round" => (1, |a| a[0].round()),
+        "min" => (2, |a| a[0].min(a[1])),
+        "max" => (2, |a| a[0].max(a[1])),
+        "pow" => (2, |a| a[0].powf(a[1])),
+        "clamp" => (3, |a| a[0].max(a[1]).min(a[2])),
+        _ => return None,
+    };
+    Some(f)
+}
+
+/// How deeply parentheses, ternaries, calls and unary operators may nest,
+/// so a pathological expression is rejected instead of overflowing the stack.
+const MAX_DEPTH: usize = 64;
+
+struct Parser<'a> {
+    src: &'a [u8],
+    pos: usize,
+    depth: usize,
+}
+
+impl Parser<'_> {
+    fn error(&self, message: &str) -> ExpressionError {
+        ExpressionError::Syntax {
+            position: self.pos,
+            message: message.to_string(),
+        }
+    }
+
+    fn skip_ws(&mut self) {
+        while self.src.get(self.pos).is_some_and(u8::is_ascii_whitespace) {
+            self.pos += 1;
+        }
+    }
+
+    /// Consumes `token` if it comes next.
+    fn eat(&mut self, token: &str) -> bool {
+        self.skip_ws();
+        if self.src[self.pos..].starts_with(token.as_bytes()) {
+            self.pos += token.len();
+            true
+        } else {
+            false
+        }
+    }
+
+    fn expect(&mut self, token: &str) -> Result<(), ExpressionError> {
+        if self.eat(token) {
+            Ok(())
+        } else {
+            Err(self.error(&format!("expected '{}'", token)))
+        }
+    }
+
+    /// Parses one nested level with `f`, failing past [`MAX_DEPTH`].
+    fn nested(
+        &mut self,
+        f: fn(&mut Self) -> Result<Node, ExpressionError>,
+    ) -> Result<Node, ExpressionError> {
+        if self.depth >= MAX_DEPTH {
+            return Err(self.error("expression nested too deeply"));
+        }
+        self.depth += 1;
+        let node = f(self);
+        self.depth -= 1;
+        node
+    }
+
+    fn ternary(&mut self) -> Result<Node, ExpressionError> {
+        let cond = self.or()?;
+        if !self.eat("?") {
+            return Ok(cond);
+        }
+        let then = self.nested(Self::ternary)?;
+        self.expect(":")?;
+        let otherwise = self.nested(Self::ternary)?;
+        Ok(Node::Cond(
+            Box::new(cond),
+            Box::new(then),
+            Box::new(otherwise),
+        ))
+    }
+
+    fn or(&mut self) -> Result<Node, ExpressionError> {
+        let mut lhs = self.and()?;
+        while self.eat("||") {
+            lhs = Node::Bin(BinOp::Or, Box::new(lhs), Box::new(self.and()?));
+        }
+        Ok(lhs)
+    }
+
+    fn and(&mut self) -> Result<Node, ExpressionError> {
+        let mut lhs = self.comparison()?;
+        while self.eat("&&") {
+            lhs = Node::Bin(BinOp::And, Box::new(lhs), Box::new(self.comparison()?));
+        }
+        Ok(lhs)
+    }
+
+    fn comparison(&mut self) -> Result<Node, ExpressionError> {
+        let lhs = self.additive()?;
+        // Two-character operators first, so `<=` isn't read as `<`.
+        let ops = [
+            ("<=", BinOp::Le),
+            (">=", BinOp::Ge),
+            ("==", BinOp::Eq),
+            ("!=", BinOp::Ne),
+            ("<", BinOp::Lt),
+            (">", BinOp::Gt),
+        ];
+        for (token, op) in ops {
+            if self.eat(token) {
+                return Ok(Node::Bin(op, Box::new(lhs), Box::new(self.additive()?)));
+            }
+        }
+        Ok(lhs)
+    }
+
+    fn additive(&mut self) -> Result<Node, ExpressionError> {
+        let mut lhs = self.multiplicative()?;
+        loop {
+            let op = if self.eat("+") {
+                BinOp::Add
+            } else if self.eat("-") {
+                BinOp::Sub
+            } else {
+                return Ok(lhs);
+            };
+            lhs = Node::Bin(op, Box::new(lhs), Box::new(self.multiplicative()?));
+        }
+    }
+
+    fn multiplicative(&mut self) -> Result<Node, ExpressionError> {
+        let mut lhs = self.unary()?;
+        loop {
+            let op = if self.eat("*") {
+                BinOp::Mul
+            } else if self.eat("/") {
+                BinOp::Div
+            } else if self.eat("%") {
+                BinOp::Rem
+            } else {
+                return Ok(lhs);
+            };
+            lhs = Node::Bin(op, Box::new(lhs), Box::new(self.unary()?));
+        }
+    }
+
+    fn unary(&mut self) -> Result<Node, ExpressionError> {
+        if self.eat("-") {
+            return Ok(Node::Neg(Box::new(self.nested(Self::unary)?)));
+        }
+        // `!` but not `!=`, which can't start an operand anyway.
+        if self.eat("!") {
+            return Ok(Node::Not(Box::new(self.nested(Self::unary)?)));
+        }
+        let base = self.primary()?;
+        if self.eat("^") {
+            return Ok(Node::Bin(
+                BinOp::Pow,
+                Box::new(base),
+                Box::new(self.nested(Self::unary)?),
+            ));
+        }
+        Ok(base)
+    }
+
+    fn primary(&mut self) -> Result<Node, ExpressionError> {
+        self.skip_ws();
+        let start = self.pos;
+        match self.src.get(self.pos) {
+            Some(b'(') => {
+                self.pos += 1;
+                let inner = self.nested(Self::ternary)?;
+                self.expect(")")?;
+                Ok(inner)
+            }
+            Some(c) if c.is_ascii_digit() || *c == b'.' => {
+                while self
+                    .src
+                    .get(self.pos)
+                    .is_some_and(|c| c.is_ascii_digit() || *c == b'.')
+                {
+                    self.pos += 1;
+                }
+                // Exponent, e.g. 1e3 or 2.5E-2.
+                if matches!(self.src.get(self.pos), Some(b'e' | b'E')) {
+                    let mark = self.pos;
+                    self.pos += 1;
+                    if matches!(self.src.get(self.pos), Some(b'+' | b'-')) {
+                        self.pos += 1;
+                    }
+                    if self.src.get(self.pos).is_some_and(u8::is_ascii_digit) {
+                        while self.src.get(self.pos).is_some_and(u8::is_ascii_digit) {
+                            self.pos += 1;
+                        }
+                    } else {
+                        self.pos = mark;
+                    }
+                }
+                let text = std::str::from_utf8(&self.src[start..self.pos]).unwrap_or_default();
+                text.parse()
+                    .map(Node::Num)
+                    .map_err(|_| ExpressionError::Syntax {
+                        position: start,
+                        message: format!("invalid number '{}'", text),
+                    })
+            }
+            Some(c) if c.is_ascii_alphabetic() || *c == b'_' => {
+                while self
+                    .src
+                    .get(self.pos)
+                    .is_some_and(|c| c.is_ascii_alphanumeric() || *c == b'_')
+                {
+                    self.pos += 1;
+                }
+                let name = std::str::from_utf8(&self.src[start..self.pos])
+                    .unwrap_or_default()
+                    .to_string();
+                if self.eat("(") {
+                    return self.call(name);
+                }
+                match name.as_str() {
+                    "t" => Ok(Node::Var(Var::T)),
+                    "duration" => Ok(Node::Var(Var::Duration)),
+                    "pi" => Ok(Node::Num(std::f64::consts::PI)),
+                    "e" => Ok(Node::Num(std::f64::consts::E)),
+                    _ => Err(ExpressionError::UnknownVariable(name)),
+                }
+            }
+            Some(_) => Err(self.error("expected a number, variable, function or '('")),
+            None => Err(self.error("unexpected end of expression")),
+        }
+    }
+
+    fn call(&mut self, name: String) -> Result<Node, ExpressionError> {
+        let (arity, f) =
+            function(&name).ok_or_else(|| ExpressionError::UnknownFunction(name.clone()))?;
+        let mut args = Vec::new();
+        if !self.eat(")") {
+            loop {
+                args.push(self.nested(Self::ternary)?);
+                if self.eat(")") {
+                    break;
+                }
+                self.expect(",")?;
+            }
+        }
+        if args.len() != arity {
+            return Err(ExpressionError::Arity {
+                name,
+                expected: arity,
+                got: args.len(),
+            });
+        }
+        Ok(Node::Call(f, args))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn eval_at(source: &str, t: f64) -> f64 {
+        RateExpression::parse(source).unwrap().eval(t, 3600.0)
+    }
+
+    #[test]
+    fn evaluates_the_documented_example() {
+        let source = "100 + 50*sin(t/600) + (t>1800 ? 200 : 0)";
+        assert!((eval_at(source, 0.0) - 100.0).abs() < 1e-9);
+        let quarter = 600.0 * std::f64::consts::FRAC_PI_2;
+        assert!((eval_at(source, quarter) - 150.0).abs() < 1e-9);
+        assert!(
+            (eval_at(source, 1800.5) - (300.0 + 50.0 * (1800.5f64 / 600.0).sin())).abs() < 1e-9
+        );
+    }
+
+    #[test]
+    fn respects_precedence_and_associativity() {
+        assert_eq!(eval_at("1 + 2 * 3", 0.0), 7.0);
+        assert_eq!(eval_at("(1 + 2) * 3", 0.0), 9.0);
+        assert_eq!(eval_at("10 - 4 - 3", 0.0), 3.0);
+        assert_eq!(eval_at("2 ^ 3 ^ 2", 0.0), 512.0);
+        assert_eq!(eval_at("-2 ^ 2", 0.0), -4.0);
+        assert_eq!(eval_at("7 % 4", 0.0), 3.0);
+        assert_eq!(eval_at("1.5e2", 0.0), 150.0);
+    }
+
+    #[test]
+    fn comparisons_logic_and_ternaries() {
+        assert_eq!(eval_at("t >= 10 && t < 20", 15.0), 1.0);
+        assert_eq!(eval_at("t >= 10 && t < 20", 20.0), 0.0);
+        assert_eq!(eval_at("t < 5 || t == 30", 30.0), 1.0);
+        assert_eq!(eval_at("!(t != 3)", 3.0), 1.0);
+        assert_eq!(eval_at("t < 10 ? 1 : t < 20 ? 2 : 3", 15.0), 2.0);
+        assert_eq!(eval_at("t / duration * 100", 1800.0), 50.0);
+    }
+
+    #[test]
+    fn functions() {
+        assert_eq!(eval_at("max(t, 5) + min(1, 2)", 3.0), 6.0);
+        assert_eq!(eval_at("clamp(t, 10, 20)", 50.0), 20.0);
+        assert_eq!(eval_at("floor(2.7) + ceil(2.1) + round(2.5)", 0.0), 8.0);
+        assert_eq!(eval_at("pow(2, 10)", 0.0), 1024.0);
+    }
+
+    #[test]
+    fn rejects_invalid_expressions() {
+        assert_eq!(
+            RateExpression::parse("x + 1").unwrap_err(),
+            ExpressionError::UnknownVariable("x".to_string())
+        );
+        assert_eq!(
+            RateExpression::parse("foo(1)").unwrap_err(),
+            ExpressionError::UnknownFunction("foo".to_string())
+        );
+        assert!(matches!(
+            RateExpression::parse("min(1)").unwrap_err(),
+            ExpressionError::Arity {
+                expected: 2,
+                got: 1,
+                ..
+            }
+        ));
+        assert!(matches!(
+            RateExpression::parse("1 +").unwrap_err(),
+            ExpressionError::Syntax { .. }
+        ));
+        assert!(matches!(
+            RateExpression::parse("(1 + 2").unwrap_err(),
+            ExpressionError::Syntax { .. }
+        ));
+        assert!(matches!(
+            RateExpression::parse("1 2").unwrap_err(),
+            ExpressionError::Syntax { position: 2, .. }
+        ));
+    }
+
+    #[test]
+    fn rejects_deep_nesting() {
+        let parens = format!("{}1{}", "(".repeat(100_000), ")".repeat(100_000));
+        let negs = format!("{}1", "-".repeat(100_000));
+        let powers = format!("2{}", "^2".repeat(100_000));
+        for source in [parens, negs, powers] {
+            assert!(matches!(
+                RateExpression::parse(&source).unwrap_err(),
+                ExpressionError::Syntax { .. }
+            ));
+        }
+
+        let ok = format!("{}1{}", "(".repeat(32), ")".repeat(32));
+        assert_eq!(RateExpression::parse(&ok).unwrap().eval(0.0, 0.0), 1.0);
+    }
+}
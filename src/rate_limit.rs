@@ -0,0 +1,249 @@
+//! Rate-limit tracking: 429 responses and `X-RateLimit-*` headers.
+//!
+//! Every response is checked for `429 Too Many Requests` and for the
+//! rate-limit headers APIs advertise their budget with — `X-RateLimit-Limit`,
+//! `X-RateLimit-Remaining`, `X-RateLimit-Reset` and the IETF `RateLimit-*`
+//! equivalents. 429s are counted per endpoint (scenario step, request
+//! template or URL path) in `rate_limited_responses_total`, and the latest
+//! header values are exported as `rate_limit_header_value{header}`.
+//!
+//! Once an endpoint has returned a 429 or a rate-limit header, all of its
+//! responses are tracked, and the end-of-test rate limit report estimates
+//! its effective limit: the rate of accepted (non-429) responses from the
+//! first 429 onwards, i.e. the throughput the target actually lets through
+//! while it is pushing back, next to the limit it advertises.
+
+use std::collections::BTreeMap;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
+use std::time::Instant;
+
+use reqwest::header::HeaderMap;
+
+use crate::metrics::{RATE_LIMITED_RESPONSES_TOTAL, RATE_LIMIT_HEADER_VALUE};
+
+/// Advertised-budget headers: (label, header names).
+const HEADERS: [(&str, [&str; 2]); 3] = [
+    ("limit", ["x-ratelimit-limit", "ratelimit-limit"]),
+    (
+        "remaining",
+        ["x-ratelimit-remaining", "ratelimit-remaining"],
+    ),
+    ("reset", ["x-ratelimit-reset", "ratelimit-reset"]),
+];
+
+/// What has been seen of one endpoint's rate limit.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct EndpointLimit {
+    /// Responses since the endpoint was first seen rate limiting.
+    pub responses: u64,
+    /// 429 responses.
+    pub limited: u64,
+    /// Accepted responses since the first 429.
+    pub accepted_while_limited: u64,
+    /// Seconds from the first 429 to the last response.
+    pub limited_secs: f64,
+    /// Latest advertised limit.
+    pub limit: Option<f64>,
+    /// Lowest advertised remaining budget.
+    pub min_remaining: Option<f64>,
+    /// Latest advertised reset (seconds or timestamp, as sent).
+    pub reset: Option<f64>,
+}
+
+impl EndpointLimit {
+    /// Accepted responses per second while the endpoint was returning 429s.
+    pub fn effective_rps(&self) -> Option<f64> {
+        (self.limited > 0 && self.limited_secs > 0.0)
+            .then(|| self.accepted_while_limited as f64 / self.limited_secs)
+    }
+}
+
+#[derive(Default)]
+struct Tracked {
+    stats: EndpointLimit,
+    first_limited: Option<Instant>,
+}
+
+/// Set once any endpoint is tracked, so untracked responses skip the lock.
+static ANY_TRACKED: AtomicBool = AtomicBool::new(false);
+
+lazy_static::lazy_static! {
+    static ref ENDPOINTS: Mutex<BTreeMap<String, Tracked>> = Mutex::new(BTreeMap::new());
+}
+
+/// Leading number of a header value such as `100` or `100, 100;w=60`.
+fn header_number(headers: &HeaderMap, names: &[&str]) -> Option<f64> {
+    names.iter().find_map(|name| {
+        let value = headers.get(*name)?.to_str().ok()?.trim();
+        let end = value
+            .find(|c: char| !(c.is_ascii_digit() || c == '.'))
+            .unwrap_or(value.len());
+        value[..end].parse().ok()
+    })
+}
+
+/// Endpoint name for single-URL requests: the URL's path.
+pub fn url_endpoint(url: &str) -> String {
+    match reqwest::Url::parse(url) {
+        Ok(parsed) => parsed.path().to_string(),
+        Err(_) => url.to_string(),
+    }
+}
+
+/// Records a response from `endpoint`.
+pub fn observe(endpoint: &str, status: u16, headers: &HeaderMap, node_id: &str, run_id: &str) {
+    let limited = status == 429;
+    let values = HEADERS.map(|(label, names)| (label, header_number(headers, &names)));
+    let advertised = values.iter().any(|(_, v)| v.is_some());
+    if !limited && !advertised && !ANY_TRACKED.load(Ordering::Relaxed) {
+        return;
+    }
+
+    if limited {
+        RATE_LIMITED_RESPONSES_TOTAL
+            .with_label_values(&[endpoint, node_id, run_id])
+            .inc();
+    }
+    for (label, value) in values {
+        if let Some(value) = value {
+            RATE_LIMIT_HEADER_VALUE
+                .with_label_values(&[endpoint, label, node_id, run_id])
+                .set(value);
+        }
+    }
+
+    let mut endpoints = ENDPOINTS.lock().unwrap_or_else(|e| e.into_inner());
+    let tracked = match endpoints.get_mut(endpoint) {
+        Some(tracked) => tracked,
+        None if limited || advertised => {
+            ANY_TRACKED.store(true, Ordering::Relaxed);
+            endpoints.entry(endpoint.to_string()).or_default()
+        }
+        None => return,
+    };
+    let now = Instant::now();
+    let stats = &mut tracked.stats;
+    stats.responses += 1;
+    if limited {
+        stats.limited += 1;
+        tracked.first_limited.get_or_insert(now);
+    } else if tracked.first_limited.is_some() {
+        stats.accepted_while_limited += 1;
+    }
+    if let Some(first) = tracked.first_limited {
+        stats.limited_secs = now.duration_since(first).as_secs_f64();
+    }
+    let [(_, limit), (_, remaining), (_, reset)] = values;
+    stats.limit = limit.or(stats.limit);
+    stats.reset = reset.or(stats.reset);
+    if let Some(remaining) = remaining {
+        stats.min_remaining = Some(stats.min_remaining.map_or(remaining, |m| m.min(remaining)));
+    }
+}
+
+/// Tracked endpoints and their stats, by endpoint name.
+pub fn endpoints() -> Vec<(String, EndpointLimit)> {
+    ENDPOINTS
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .iter()
+        .map(|(name, tracked)| (name.clone(), tracked.stats.clone()))
+        .collect()
+}
+
+/// Forgets all endpoints, e.g. before a new test plan starts.
+pub fn clear() {
+    ENDPOINTS.lock().unwrap_or_else(|e| e.into_inner()).clear();
+    ANY_TRACKED.store(false, Ordering::Relaxed);
+}
+
+/// Formats the rate limit report table.
+pub fn format_rate_limit_table(endpoints: &[(String, EndpointLimit)]) -> String {
+    let mut output = String::new();
+    output.push_str(&format!(
+        "\n{:<40} {:>10} {:>8} {:>8} {:>10} {:>10} {:>14}\n",
+        "Endpoint", "Responses", "429s", "429 %", "Limit", "Min Left", "Effective RPS"
+    ));
+    output.push_str(&"-".repeat(106));
+    output.push('\n');
+
+    let number = |v: Option<f64>| v.map_or("-".to_string(), |v| format!("{}", v));
+    for (endpoint, stats) in endpoints {
+        output.push_str(&format!(
+            "{:<40} {:>10} {:>8} {:>7.1}% {:>10} {:>10} {:>14}\n",
+            endpoint,
+            stats.responses,
+            stats.limited,
+            stats.limited as f64 * 100.0 / stats.responses.max(1) as f64,
+            number(stats.limit),
+            number(stats.min_remaining),
+            stats
+                .effective_rps()
+                .map_or("-".to_string(), |rps| format!("{:.1}", rps)),
+        ));
+    }
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn headers(pairs: &[(&'static str, &'static str)]) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        for (name, value) in pairs {
+            headers.insert(*name, value.parse().unwrap());
+        }
+        headers
+    }
+
+    fn stats(endpoint: &str) -> Option<EndpointLimit> {
+        endpoints()
+            .into_iter()
+            .find(|(name, _)| name == endpoint)
+            .map(|(_, stats)| stats)
+    }
+
+    #[test]
+    fn tracks_endpoints_once_limited() {
+        observe("rl-test/free", 200, &HeaderMap::new(), "node", "run");
+        assert_eq!(stats("rl-test/free"), None);
+
+        let endpoint = "rl-test/limited";
+        observe(endpoint, 429, &HeaderMap::new(), "node", "run");
+        observe(endpoint, 200, &HeaderMap::new(), "node", "run");
+        observe(endpoint, 200, &HeaderMap::new(), "node", "run");
+        let seen = stats(endpoint).unwrap();
+        assert_eq!(seen.responses, 3);
+        assert_eq!(seen.limited, 1);
+        assert_eq!(seen.accepted_while_limited, 2);
+    }
+
+    #[test]
+    fn reads_advertised_budget() {
+        let endpoint = "rl-test/advertised";
+        observe(
+            endpoint,
+            200,
+            &headers(&[("x-ratelimit-limit", "100"), ("x-ratelimit-remaining", "7")]),
+            "node",
+            "run",
+        );
+        observe(
+            endpoint,
+            200,
+            &headers(&[
+                ("ratelimit-limit", "100, 100;w=60"),
+                ("ratelimit-remaining", "9"),
+            ]),
+            "node",
+            "run",
+        );
+        let seen = stats(endpoint).unwrap();
+        assert_eq!(seen.limit, Some(100.0));
+        assert_eq!(seen.min_remaining, Some(7.0));
+        assert_eq!(seen.limited, 0);
+        assert_eq!(seen.effective_rps(), None);
+    }
+}
@@ -0,0 +1,618 @@
+//! Raw HTTP/1.1 engine (`ENGINE=raw`, `raw-engine` feature).
+//!
+//! For throughput benchmarks where reqwest's per-request work (header maps,
+//! connection pool, body streams) is the bottleneck rather than the target.
+//! The request is encoded once; each worker owns one persistent connection,
+//! writes up to `RAW_PIPELINE_DEPTH` (default 1) copies of the request back to
+//! back and then reads the responses in order, parsing only the status line
+//! and `Content-Length`, `Transfer-Encoding` and `Connection`. Bodies are
+//! skipped, never decoded. The latency of a pipelined request is measured from
+//! the write of its batch to the end of its response, as wrk does.
+//!
+//! Pipelining only applies when the load model is unbounded (Concurrent);
+//! paced models send one request per cycle. Request counts, status codes,
+//! error categories, latencies and the hard caps work as with the default
+//! engine. Settings the engine cannot honour are rejected at startup, see
+//! [`crate::engine`].
+
+use std::net::SocketAddr;
+use std::sync::Arc;
+
+use thiserror::Error;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::TcpStream;
+use tokio::time::{self, Duration, Instant};
+use tracing::{debug, info, info_span, warn, Instrument};
+
+use crate::connection_pool::GLOBAL_POOL_STATS;
+use crate::errors::{self, ErrorCategory};
+use crate::in_flight_limit;
+use crate::memory_guard::is_percentile_tracking_active;
+use crate::metric_batch::WorkerMetrics;
+use crate::metrics::CONCURRENT_REQUESTS;
+use crate::percentiles::GLOBAL_REQUEST_PERCENTILES;
+use crate::rate_budget;
+use crate::rps_override;
+use crate::worker::{idle_pause, status_code_label, worker_cycle, WorkerConfig};
+
+/// Longest wait for a response before the connection is dropped.
+const READ_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Largest response head accepted.
+const MAX_HEAD_BYTES: usize = 64 * 1024;
+
+#[derive(Error, Debug)]
+pub enum RawError {
+    #[error("invalid target URL: {0}")]
+    InvalidUrl(String),
+
+    #[error("I/O error: {0}")]
+    Io(#[from] std::io::Error),
+
+    #[error("no response within {0:?}")]
+    Timeout(Duration),
+
+    #[error("connection closed by target")]
+    Closed,
+
+    #[error("malformed response: {0}")]
+    Malformed(&'static str),
+}
+
+impl RawError {
+    /// Error category for the `request_errors_by_category` metric.
+    pub fn category(&self) -> ErrorCategory {
+        match self {
+            RawError::Io(e) if errors::is_resource_exhaustion(e) => {
+                ErrorCategory::ResourceExhausted
+            }
+            RawError::Io(_) | RawError::Closed => ErrorCategory::NetworkError,
+            RawError::Timeout(_) => ErrorCategory::TimeoutError,
+            RawError::InvalidUrl(_) | RawError::Malformed(_) => ErrorCategory::OtherError,
+        }
+    }
+}
+
+/// The target address and the encoded request, shared by all raw workers.
+#[derive(Debug)]
+pub struct RawTarget {
+    pub addr: SocketAddr,
+    request: Vec<u8>,
+    head_request: bool,
+    depth: usize,
+}
+
+impl RawTarget {
+    /// Resolves `url` once and encodes the request. `RAW_PIPELINE_DEPTH` sets
+    /// how many requests are written before reading responses.
+    pub async fn resolve(
+        url: &str,
+        method: &str,
+        json_body: Option<&str>,
+    ) -> Result<Self, RawError> {
+        let parsed = reqwest::Url::parse(url).map_err(|e| RawError::InvalidUrl(e.to_string()))?;
+        let host = parsed
+            .host_str()
+            .ok_or_else(|| RawError::InvalidUrl(format!("{} has no host", url)))?;
+        let port = parsed.port_or_known_default().unwrap_or(80);
+        let addr = tokio::net::lookup_host((host, port))
+            .await?
+            .next()
+            .ok_or_else(|| RawError::InvalidUrl(format!("{} did not resolve", host)))?;
+        let host_header = match parsed.port() {
+            Some(port) => format!("{}:{}", host, port),
+            None => host.to_string(),
+        };
+        let path = match parsed.query() {
+            Some(query) => format!("{}?{}", parsed.path(), query),
+            None => parsed.path().to_string(),
+        };
+        let depth = std::env::var("RAW_PIPELINE_DEPTH")
+            .ok()
+            .and_then(|v| v.trim().parse::<usize>().ok())
+            .filter(|d| *d > 0)
+            .unwrap_or(1);
+        Ok(Self {
+            addr,
+            request: encode_request(method, &path, &host_header, json_body),
+            head_request: method == "HEAD",
+            depth,
+        })
+    }
+}
+
+/// Encodes one HTTP/1.1 request.
+pub fn encode_request(method: &str, path: &str, host: &str, json_body: Option<&str>) -> Vec<u8> {
+    let mut request = format!(
+        "{} {} HTTP/1.1\r\nHost: {}\r\nUser-Agent: rust_loadtest\r\nAccept: */*\r\n",
+        method, path, host
+    );
+    match json_body {
+        Some(body) => {
+            request.push_str(&format!(
+                "Content-Type: application/json\r\nContent-Length: {}\r\n\r\n{}",
+                body.len(),
+                body
+            ));
+        }
+        None => request.push_str("\r\n"),
+    }
+    request.into_bytes()
+}
+
+/// How the body of a response is delimited.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BodyLength {
+    Fixed(usize),
+    Chunked,
+    /// No length given: the body runs until the connection closes.
+    UntilClose,
+}
+
+/// Status line and framing of a response.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ResponseHead {
+    pub status: u16,
+    /// Bytes of status line and headers, including the blank line.
+    pub len: usize,
+    pub body: BodyLength,
+    /// The target will close the connection after this response.
+    pub close: bool,
+}
+
+/// Parses a response head from the start of `buf`; `None` until it is complete.
+pub fn parse_head(buf: &[u8], head_request: bool) -> Result<Option<ResponseHead>, RawError> {
+    let Some(end) = find(buf, b"\r\n\r\n") else {
+        if buf.len() > MAX_HEAD_BYTES {
+            return Err(RawError::Malformed("response head too large"));
+        }
+        return Ok(None);
+    };
+    let head = std::str::from_utf8(&buf[..end])
+        .map_err(|_| RawError::Malformed("response head is not UTF-8"))?;
+    let mut lines = head.split("\r\n");
+    let status_line = lines.next().unwrap_or_default();
+    let mut parts = status_line.splitn(3, ' ');
+    let version = parts.next().unwrap_or_default();
+    if !version.starts_with("HTTP/1.") {
+        return Err(RawError::Malformed("not an HTTP/1.x status line"));
+    }
+    let status: u16 = parts
+        .next()
+        .and_then(|s| s.parse().ok())
+        .ok_or(RawError::Malformed("invalid status code"))?;
+
+    let mut content_length = None;
+    let mut chunked = false;
+    let mut close = version == "HTTP/1.0";
+    for line in lines {
+        let Some((name, value)) = line.split_once(':') else {
+            continue;
+        };
+        let value = value.trim();
+        if name.eq_ignore_ascii_case("content-length") {
+            content_length = Some(
+                value
+                    .parse::<usize>()
+                    .map_err(|_| RawError::Malformed("invalid Content-Length"))?,
+            );
+        } else if name.eq_ignore_ascii_case("transfer-encoding") {
+            chunked = value.to_ascii_lowercase().contains("chunked");
+        } else if name.eq_ignore_ascii_case("connection") {
+            let value = value.to_ascii_lowercase();
+            close = value.contains("close") || (close && !value.contains("keep-alive"));
+        }
+    }
+
+    let body = if head_request || status / 100 == 1 || status == 204 || status == 304 {
+        BodyLength::Fixed(0)
+    } else if chunked {
+        BodyLength::Chunked
+    } else if let Some(len) = content_length {
+        BodyLength::Fixed(len)
+    } else {
+        BodyLength::UntilClose
+    };
+    Ok(Some(ResponseHead {
+        status,
+        len: end + 4,
+        body,
+        close: close || body == BodyLength::UntilClose,
+    }))
+}
+
+/// Length of the complete chunked body at the start of `buf` (trailers
+/// included); `None` until the last chunk has arrived.
+pub fn chunked_len(buf: &[u8]) -> Result<Option<usize>, RawError> {
+    let mut pos = 0;
+    loop {
+        let Some(line_end) = find(&buf[pos..], b"\r\n") else {
+            return Ok(None);
+        };
+        let line = std::str::from_utf8(&buf[pos..pos + line_end])
+            .map_err(|_| RawError::Malformed("invalid chunk size"))?;
+        let size_hex = line.split(';').next().unwrap_or_default().trim();
+        let size = usize::from_str_radix(size_hex, 16)
+            .map_err(|_| RawError::Malformed("invalid chunk size"))?;
+        pos += line_end + 2;
+        if size == 0 {
+            // Trailers end with an empty line.
+            return Ok(trailers_end(&buf[pos..]).map(|len| pos + len));
+        }
+        if buf.len() < pos + size + 2 {
+            return Ok(None);
+        }
+        pos += size + 2;
+    }
+}
+
+/// Bytes up to and including the blank line that ends the trailers.
+fn trailers_end(buf: &[u8]) -> Option<usize> {
+    if buf.starts_with(b"\r\n") {
+        return Some(2);
+    }
+    find(buf, b"\r\n\r\n").map(|end| end + 4)
+}
+
+fn find(haystack: &[u8], needle: &[u8]) -> Option<usize> {
+    haystack.windows(needle.len()).position(|w| w == needle)
+}
+
+/// A worker's connection and its unread bytes.
+struct RawConnection {
+    stream: TcpStream,
+    buf: Vec<u8>,
+}
+
+impl RawConnection {
+    async fn connect(addr: SocketAddr) -> Result<Self, RawError> {
+        let stream = TcpStream::connect(addr).await?;
+        stream.set_nodelay(true)?;
+        Ok(Self {
+            stream,
+            buf: Vec::with_capacity(16 * 1024),
+        })
+    }
+
+    /// Reads more bytes; `false` at end of stream.
+    async fn fill(&mut self) -> Result<bool, RawError> {
+        let mut chunk = [0u8; 16 * 1024];
+        let read = time::timeout(READ_TIMEOUT, self.stream.read(&mut chunk))
+            .await
+            .map_err(|_| RawError::Timeout(READ_TIMEOUT))??;
+        self.buf.extend_from_slice(&chunk[..read]);
+        Ok(read > 0)
+    }
+
+    /// Reads one response and discards it, returning its head.
+    async fn read_response(&mut self, head_request: bool) -> Result<ResponseHead, RawError> {
+        let head = loop {
+            if let Some(head) = parse_head(&self.buf, head_request)? {
+                break head;
+            }
+            if !self.fill().await? {
+                return Err(RawError::Closed);
+            }
+        };
+        let total = match head.body {
+            BodyLength::Fixed(len) => {
+                while self.buf.len() < head.len + len {
+                    if !self.fill().await? {
+                        return Err(RawError::Closed);
+                    }
+                }
+                head.len + len
+            }
+            BodyLength::Chunked => loop {
+                if let Some(len) = chunked_len(&self.buf[head.len..])? {
+                    break head.len + len;
+                }
+                if !self.fill().await? {
+                    return Err(RawError::Closed);
+                }
+            },
+            BodyLength::UntilClose => {
+                while self.fill().await? {
+                    self.buf.clear();
+                }
+                self.buf.len()
+            }
+        };
+        self.buf.drain(..total);
+        Ok(head)
+    }
+}
+
+/// Runs a raw-engine worker: the raw counterpart of [`crate::worker::run_worker`].
+pub async fn run_raw_worker(target: Arc<RawTarget>, config: WorkerConfig, start_time: Instant) {
+    let span = info_span!(
+        "raw_worker",
+        task_id = config.task_id,
+        run_id = %config.run_id,
+        region = %config.region,
+        zone = %config.zone,
+    );
+    let task_id = config.task_id;
+    crate::seed::scope(
+        "raw",
+        task_id,
+        raw_worker_loop(target, config, start_time).instrument(span),
+    )
+    .await
+}
+
+async fn raw_worker_loop(target: Arc<RawTarget>, config: WorkerConfig, start_time: Instant) {
+    let duration_secs = config.test_duration.as_secs_f64();
+    let initial_rps = rps_override::target_rps(&config.load_model, 0.0, duration_secs);
+    let initial_cycle = worker_cycle(initial_rps, config.num_concurrent_tasks).unwrap_or_default();
+    let mut next_fire = time::Instant::now()
+        + config
+            .pacing
+            .start_offset(config.task_id, config.num_concurrent_tasks, initial_cycle);
+
+    let labels = [
+        config.region.as_str(),
+        config.zone.as_str(),
+        config.tenant.as_str(),
+        config.node_id.as_str(),
+        config.run_id.as_str(),
+    ];
+    let mut metrics = WorkerMetrics::new(
+        &config.region,
+        &config.zone,
+        &config.tenant,
+        &config.node_id,
+        &config.run_id,
+    );
+    let batch_bytes = target.request.repeat(target.depth);
+    let mut connection: Option<RawConnection> = None;
+
+    'run: loop {
+        metrics.flush_before_sleep(next_fire);
+        time::sleep_until(next_fire).await;
+
+        if *config.stop_rx.borrow() {
+            info!(
+                task_id = config.task_id,
+                "Worker received stop signal, exiting cleanly"
+            );
+            break;
+        }
+        let now = time::Instant::now();
+        let elapsed_secs = now.duration_since(start_time).as_secs_f64();
+        if elapsed_secs >= duration_secs {
+            break;
+        }
+
+        let rps = rps_override::target_rps(&config.load_model, elapsed_secs, duration_secs);
+        let batch = match worker_cycle(rps, config.num_concurrent_tasks) {
+            Some(cycle) => {
+                next_fire += config.pacing.jittered(cycle);
+                1
+            }
+            None if rps == 0.0 => {
+                next_fire = now + idle_pause();
+                continue;
+            }
+            None => target.depth,
+        };
+
+        // One MAX_IN_FLIGHT slot per pipelined request: the batch waits for
+        // its first and shrinks to the slots free after that.
+        let mut in_flight_slots = Vec::with_capacity(batch);
+        let mut sent = 0;
+        while sent < batch {
+            // The first request's budget is taken before its slot, so no
+            // slot is held while HARD_MAX_RPS makes the batch wait.
+            let slot = if sent == 0 {
+                if !rate_budget::acquire().await {
+                    info!(
+                        task_id = config.task_id,
+                        "Worker stopping: MAX_TOTAL_REQUESTS reached"
+                    );
+                    break 'run;
+                }
+                in_flight_limit::acquire().await
+            } else {
+                let Some(slot) = in_flight_limit::try_acquire() else {
+                    break;
+                };
+                if !rate_budget::acquire().await {
+                    break;
+                }
+                slot
+            };
+            in_flight_slots.push(slot);
+            sent += 1;
+        }
+
+        CONCURRENT_REQUESTS
+            .with_label_values(&labels)
+            .add(sent as f64);
+        let batch_start = time::Instant::now();
+        let outcome = send_batch(
+            &mut connection,
+            &target,
+            &batch_bytes[..sent * target.request.len()],
+            sent,
+            |status| {
+                let latency = batch_start.elapsed();
+                metrics.request();
+                metrics.status(status_code_label(status));
+                if let Some(category) = ErrorCategory::from_status_code(status) {
+                    metrics.error(category.label());
+                }
+                metrics.duration(latency.as_secs_f64());
+                record_latency(&config, latency);
+            },
+        )
+        .await;
+        if let Err((failed, e)) = outcome {
+            connection = None;
+            let category = e.category();
+            for _ in 0..failed {
+                metrics.request();
+                metrics.status("error");
+                metrics.error(category.label());
+                metrics.duration(batch_start.elapsed().as_secs_f64());
+            }
+            warn!(
+                task_id = config.task_id,
+                addr = %target.addr,
+                error = %e,
+                error_category = %category.label(),
+                "Raw request failed"
+            );
+        }
+        CONCURRENT_REQUESTS
+            .with_label_values(&labels)
+            .sub(sent as f64);
+        drop(in_flight_slots);
+        metrics.maybe_flush();
+    }
+    debug!(task_id = config.task_id, "Raw worker finished");
+}
+
+/// Writes `count` pipelined requests and reads their responses, calling
+/// `on_response` with each status. On failure returns how many requests got
+/// no response; the connection must then be dropped.
+async fn send_batch(
+    connection: &mut Option<RawConnection>,
+    target: &RawTarget,
+    bytes: &[u8],
+    count: usize,
+    mut on_response: impl FnMut(u16),
+) -> Result<(), (usize, RawError)> {
+    if connection.is_none() {
+        *connection = Some(
+            RawConnection::connect(target.addr)
+                .await
+                .map_err(|e| (count, e))?,
+        );
+    }
+    let conn = connection.as_mut().expect("connected above");
+    conn.stream
+        .write_all(bytes)
+        .await
+        .map_err(|e| (count, e.into()))?;
+    for answered in 0..count {
+        let head = conn
+            .read_response(target.head_request)
+            .await
+            .map_err(|e| (count - answered, e))?;
+        on_response(head.status);
+        if head.close {
+            *connection = None;
+            if answered + 1 < count {
+                return Err((count - answered - 1, RawError::Closed));
+            }
+            break;
+        }
+    }
+    Ok(())
+}
+
+fn record_latency(config: &WorkerConfig, latency: Duration) {
+    let latency_ms = latency.as_millis() as u64;
+    if config.percentile_tracking_enabled && is_percentile_tracking_active() {
+        GLOBAL_REQUEST_PERCENTILES.record_ms(latency_ms);
+    }
+    GLOBAL_POOL_STATS.record_request(latency_ms);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn encodes_requests() {
+        let get = encode_request("GET", "/a?b=1", "example.com:8080", None);
+        assert_eq!(
+            String::from_utf8(get).unwrap(),
+            "GET /a?b=1 HTTP/1.1\r\nHost: example.com:8080\r\nUser-Agent: rust_loadtest\r\nAccept: */*\r\n\r\n"
+        );
+        let post = String::from_utf8(encode_request("POST", "/", "h", Some("{}"))).unwrap();
+        assert!(post.ends_with("Content-Length: 2\r\n\r\n{}"), "{}", post);
+    }
+
+    #[test]
+    fn parses_response_heads() {
+        let response = b"HTTP/1.1 200 OK\r\nContent-Length: 5\r\n\r\nhello";
+        assert_eq!(parse_head(&response[..20], false).unwrap(), None);
+        let head = parse_head(response, false).unwrap().unwrap();
+        assert_eq!(head.status, 200);
+        assert_eq!(head.len, response.len() - 5);
+        assert_eq!(head.body, BodyLength::Fixed(5));
+        assert!(!head.close);
+
+        let head = parse_head(b"HTTP/1.1 200 OK\r\nContent-Length: 5\r\n\r\n", true)
+            .unwrap()
+            .unwrap();
+        assert_eq!(
+            head.body,
+            BodyLength::Fixed(0),
+            "HEAD responses have no body"
+        );
+
+        let head = parse_head(b"HTTP/1.0 503 Busy\r\n\r\n", false)
+            .unwrap()
+            .unwrap();
+        assert_eq!((head.status, head.body), (503, BodyLength::UntilClose));
+        assert!(head.close);
+
+        let head = parse_head(
+            b"HTTP/1.1 201 Created\r\nTransfer-Encoding: chunked\r\nConnection: close\r\n\r\n",
+            false,
+        )
+        .unwrap()
+        .unwrap();
+        assert_eq!(head.body, BodyLength::Chunked);
+        assert!(head.close);
+
+        assert!(parse_head(b"SSH-2.0-OpenSSH\r\n\r\n", false).is_err());
+    }
+
+    #[test]
+    fn measures_chunked_bodies() {
+        let body = b"5\r\nhello\r\n3;ext=1\r\nabc\r\n0\r\n\r\n";
+        assert_eq!(chunked_len(body).unwrap(), Some(body.len()));
+        assert_eq!(chunked_len(&body[..body.len() - 1]).unwrap(), None);
+        assert_eq!(chunked_len(b"5\r\nhel").unwrap(), None);
+        let with_trailer = b"0\r\nX-Sum: 1\r\n\r\nHTTP/1.1";
+        assert_eq!(chunked_len(with_trailer).unwrap(), Some(16));
+        assert!(chunked_len(b"zz\r\n").is_err());
+    }
+
+    #[tokio::test]
+    async fn reads_pipelined_responses() {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move {
+            let (mut socket, _) = listener.accept().await.unwrap();
+            let mut request = vec![0u8; 1024];
+            let _ = socket.read(&mut request).await;
+            socket
+                .write_all(
+                    b"HTTP/1.1 200 OK\r\nContent-Length: 2\r\n\r\nok\
+                      HTTP/1.1 404 Not Found\r\nTransfer-Encoding: chunked\r\n\r\n2\r\nno\r\n0\r\n\r\n",
+                )
+                .await
+                .unwrap();
+        });
+
+        let target = RawTarget {
+            addr,
+            request: encode_request("GET", "/", "localhost", None),
+            head_request: false,
+            depth: 2,
+        };
+        let bytes = target.request.repeat(2);
+        let mut connection = None;
+        let mut statuses = Vec::new();
+        send_batch(&mut connection, &target, &bytes, 2, |s| statuses.push(s))
+            .await
+            .unwrap();
+        assert_eq!(statuses, vec![200, 404]);
+        assert!(connection.is_some(), "keep-alive connection is reused");
+    }
+}
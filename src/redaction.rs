@@ -0,0 +1,400 @@
+//! Masking of sensitive values before they reach logs or reports.
+//!
+//! Three kinds of values are redacted:
+//! - header values for credential-bearing headers (`Authorization`, `Cookie`,
+//!   API keys, anything named like a token or secret); the auth scheme is kept
+//!   so `Bearer [REDACTED]` still shows what kind of credential was sent
+//! - JSON (and form-encoded) body fields whose name matches a sensitive
+//!   pattern — `password`, `secret`, `token`, `api_key`, ... by default, plus
+//!   any extra patterns from `REDACT_FIELDS`
+//! - file system paths of credentials such as mTLS keys
+//!
+//! `REDACT_FIELDS` is a comma-separated list of case-insensitive regexes
+//! matched against field and header names, e.g. `ssn,card_?number`.
+//!
+//! Two more rule kinds mask response data by position or shape rather than
+//! by field name, so bodies can be captured under compliance rules:
+//! - `REDACT_JSON_PATHS`: `;`-separated JSONPaths whose matches in JSON
+//!   bodies are masked, e.g. `$.customer.dob;$.cards[*].number`
+//! - `REDACT_PATTERNS`: `;`-separated regexes whose matches are masked in
+//!   any captured text — bodies, assertion failures and failure messages —
+//!   e.g. `\b\d{3}-\d{2}-\d{4}\b` for US social security numbers
+
+use std::borrow::Cow;
+
+use regex::{Regex, RegexBuilder};
+use serde_json::Value;
+use serde_json_path::JsonPath;
+use tracing::warn;
+
+/// Replacement text for masked values.
+pub const REDACTED: &str = "[REDACTED]";
+
+/// Headers whose values are always masked.
+const SENSITIVE_HEADERS: &[&str] = &[
+    "authorization",
+    "proxy-authorization",
+    "cookie",
+    "set-cookie",
+    "x-api-key",
+    "x-auth-token",
+    "x-csrf-token",
+];
+
+/// Field-name patterns masked by default.
+const DEFAULT_FIELD_PATTERNS: &[&str] = &[
+    "pass(word|wd)?",
+    "secret",
+    "token",
+    "api[_-]?key",
+    "authorization",
+    "credential",
+    "private[_-]?key",
+    "session[_-]?id",
+];
+
+lazy_static::lazy_static! {
+    /// Process-wide redactor configured from `REDACT_FIELDS`,
+    /// `REDACT_JSON_PATHS` and `REDACT_PATTERNS`.
+    pub static ref GLOBAL_REDACTOR: Redactor = Redactor::from_env();
+
+    /// `"key": "string value"` pairs, for JSON that does not parse
+    /// (e.g. a truncated body prefix).
+    static ref JSON_STRING_PAIR: Regex =
+        Regex::new(r#""([^"\\]+)"\s*:\s*"((?:[^"\\]|\\.)*)""#).expect("valid regex");
+}
+
+/// Masks sensitive headers, body fields and paths.
+#[derive(Debug, Clone)]
+pub struct Redactor {
+    field_patterns: Vec<Regex>,
+    json_paths: Vec<JsonPath>,
+    value_patterns: Vec<Regex>,
+}
+
+impl Default for Redactor {
+    fn default() -> Self {
+        Self::with_extra_patterns(&[])
+    }
+}
+
+impl Redactor {
+    /// Build a redactor with the default patterns plus `extra` patterns.
+    /// Invalid patterns are skipped with a warning.
+    pub fn with_extra_patterns(extra: &[&str]) -> Self {
+        let field_patterns = DEFAULT_FIELD_PATTERNS
+            .iter()
+            .chain(extra.iter())
+            .filter(|p| !p.trim().is_empty())
+            .filter_map(
+                |p| match RegexBuilder::new(p.trim()).case_insensitive(true).build() {
+                    Ok(re) => Some(re),
+                    Err(e) => {
+                        warn!(pattern = %p, error = %e, "Ignoring invalid REDACT_FIELDS pattern");
+                        None
+                    }
+                },
+            )
+            .collect();
+        Self {
+            field_patterns,
+            json_paths: Vec::new(),
+            value_patterns: Vec::new(),
+        }
+    }
+
+    /// Also mask the values at these JSONPaths in JSON bodies. Invalid paths
+    /// are skipped with a warning.
+    pub fn with_json_paths(mut self, paths: &[&str]) -> Self {
+        self.json_paths = paths
+            .iter()
+            .map(|p| p.trim())
+            .filter(|p| !p.is_empty())
+            .filter_map(|p| match JsonPath::parse(p) {
+                Ok(path) => Some(path),
+                Err(e) => {
+                    warn!(path = %p, error = %e, "Ignoring invalid REDACT_JSON_PATHS entry");
+                    None
+                }
+            })
+            .collect();
+        self
+    }
+
+    /// Also mask every match of these regexes in captured text. Invalid
+    /// patterns are skipped with a warning.
+    pub fn with_value_patterns(mut self, patterns: &[&str]) -> Self {
+        self.value_patterns = patterns
+            .iter()
+            .map(|p| p.trim())
+            .filter(|p| !p.is_empty())
+            .filter_map(|p| match Regex::new(p) {
+                Ok(re) => Some(re),
+                Err(e) => {
+                    warn!(pattern = %p, error = %e, "Ignoring invalid REDACT_PATTERNS entry");
+                    None
+                }
+            })
+            .collect();
+        self
+    }
+
+    /// Build a redactor from `REDACT_FIELDS`, `REDACT_JSON_PATHS` and
+    /// `REDACT_PATTERNS`.
+    pub fn from_env() -> Self {
+        let var = |name| std::env::var(name).unwrap_or_default();
+        let extra = var("REDACT_FIELDS");
+        let json_paths = var("REDACT_JSON_PATHS");
+        let patterns = var("REDACT_PATTERNS");
+        Self::with_extra_patterns(&extra.split(',').collect::<Vec<_>>())
+            .with_json_paths(&json_paths.split(';').collect::<Vec<_>>())
+            .with_value_patterns(&patterns.split(';').collect::<Vec<_>>())
+    }
+
+    /// Returns true if a field or variable name looks sensitive.
+    pub fn is_sensitive_field(&self, name: &str) -> bool {
+        self.field_patterns.iter().any(|re| re.is_match(name))
+    }
+
+    /// Returns true if a header carries credentials.
+    pub fn is_sensitive_header(&self, name: &str) -> bool {
+        let lower = name.to_ascii_lowercase();
+        SENSITIVE_HEADERS.contains(&lower.as_str())
+            || lower.contains("token")
+            || lower.contains("secret")
+            || lower.contains("api-key")
+            || self.is_sensitive_field(&lower)
+    }
+
+    /// Mask a header value if the header carries credentials.
+    pub fn header<'a>(&self, name: &str, value: &'a str) -> Cow<'a, str> {
+        if !self.is_sensitive_header(name) {
+            return Cow::Borrowed(value);
+        }
+        match value.split_once(' ') {
+            Some((scheme, _)) if name.to_ascii_lowercase().ends_with("authorization") => {
+                Cow::Owned(format!("{} {}", scheme, REDACTED))
+            }
+            _ => Cow::Borrowed(REDACTED),
+        }
+    }
+
+    /// Mask a value if its name looks sensitive.
+    pub fn value<'a>(&self, name: &str, value: &'a str) -> Cow<'a, str> {
+        if self.is_sensitive_field(name) {
+            Cow::Borrowed(REDACTED)
+        } else {
+            Cow::Borrowed(value)
+        }
+    }
+
+    /// Mask sensitive fields in a request or response body.
+    ///
+    /// JSON bodies are masked field-by-field at any depth and at the
+    /// configured JSONPaths (string fields of JSON that fails to parse, such
+    /// as a truncated prefix, are masked by pattern); form-encoded bodies
+    /// (`a=1&password=x`) are masked per pair. `REDACT_PATTERNS` then apply
+    /// to the result, whatever the body type.
+    pub fn body<'a>(&self, body: &'a str) -> Cow<'a, str> {
+        match self.mask_fields(body) {
+            Cow::Borrowed(body) => self.text(body),
+            Cow::Owned(masked) => Cow::Owned(self.text(&masked).into_owned()),
+        }
+    }
+
+    /// Mask every `REDACT_PATTERNS` match in free-form text, such as an
+    /// assertion failure message.
+    pub fn text<'a>(&self, text: &'a str) -> Cow<'a, str> {
+        let mut masked = Cow::Borrowed(text);
+        for re in &self.value_patterns {
+            if re.is_match(&masked) {
+                masked = Cow::Owned(re.replace_all(&masked, REDACTED).into_owned());
+            }
+        }
+        masked
+    }
+
+    fn mask_fields<'a>(&self, body: &'a str) -> Cow<'a, str> {
+        let trimmed = body.trim_start();
+        if trimmed.starts_with('{') || trimmed.starts_with('[') {
+            if let Ok(mut value) = serde_json::from_str::<Value>(body) {
+                if self.mask_json(&mut value) | self.mask_json_paths(&mut value) {
+                    return Cow::Owned(value.to_string());
+                }
+                return Cow::Borrowed(body);
+            }
+            return JSON_STRING_PAIR.replace_all(body, |caps: &regex::Captures| {
+                if self.is_sensitive_field(&caps[1]) {
+                    format!("\"{}\":\"{}\"", &caps[1], REDACTED)
+                } else {
+                    caps[0].to_string()
+                }
+            });
+        }
+        if body.contains('=') && !body.contains(char::is_whitespace) {
+            let mut changed = false;
+            let masked: Vec<String> = body
+                .split('&')
+                .map(|pair| match pair.split_once('=') {
+                    Some((k, _)) if self.is_sensitive_field(k) => {
+                        changed = true;
+                        format!("{}={}", k, REDACTED)
+                    }
+                    _ => pair.to_string(),
+                })
+                .collect();
+            if changed {
+                return Cow::Owned(masked.join("&"));
+            }
+        }
+        Cow::Borrowed(body)
+    }
+
+    /// Mask the directory part of a credential path, keeping the file name
+    /// so operators can still tell which file was configured.
+    pub fn path(&self, path: &str) -> String {
+        match std::path::Path::new(path).file_name() {
+            Some(name) => format!("{}/{}", REDACTED, name.to_string_lossy()),
+            None => REDACTED.to_string(),
+        }
+    }
+
+    /// Mask the values matched by the configured JSONPaths. Returns true if
+    /// anything changed.
+    fn mask_json_paths(&self, value: &mut Value) -> bool {
+        let pointers: Vec<String> = self
+            .json_paths
+            .iter()
+            .flat_map(|path| {
+                path.query_located(value)
+                    .locations()
+                    .map(|location| location.to_json_pointer())
+                    .collect::<Vec<_>>()
+            })
+            .collect();
+        let mut changed = false;
+        for pointer in pointers {
+            if let Some(v) = value.pointer_mut(&pointer) {
+                *v = Value::String(REDACTED.to_string());
+                changed = true;
+            }
+        }
+        changed
+    }
+
+    /// Recursively mask sensitive fields. Returns true if anything changed.
+    fn mask_json(&self, value: &mut Value) -> bool {
+        let mut changed = false;
+        match value {
+            Value::Object(map) => {
+                for (key, v) in map.iter_mut() {
+                    if self.is_sensitive_field(key) && !v.is_object() && !v.is_array() {
+                        *v = Value::String(REDACTED.to_string());
+                        changed = true;
+                    } else {
+                        changed |= self.mask_json(v);
+                    }
+                }
+            }
+            Value::Array(items) => {
+                for item in items {
+                    changed |= self.mask_json(item);
+                }
+            }
+            _ => {}
+        }
+        changed
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn masks_credential_headers() {
+        let r = Redactor::default();
+        assert_eq!(
+            r.header("Authorization", "Bearer abc.def"),
+            "Bearer [REDACTED]"
+        );
+        assert_eq!(r.header("Cookie", "session=1"), REDACTED);
+        assert_eq!(r.header("X-Refresh-Token", "xyz"), REDACTED);
+        assert_eq!(
+            r.header("Content-Type", "application/json"),
+            "application/json"
+        );
+    }
+
+    #[test]
+    fn masks_nested_json_fields() {
+        let r = Redactor::default();
+        let body = r#"{"user":"bob","password":"hunter2","nested":{"apiKey":"k","items":[{"token":"t"}]}}"#;
+        let masked: Value = serde_json::from_str(&r.body(body)).unwrap();
+        assert_eq!(masked["user"], "bob");
+        assert_eq!(masked["password"], REDACTED);
+        assert_eq!(masked["nested"]["apiKey"], REDACTED);
+        assert_eq!(masked["nested"]["items"][0]["token"], REDACTED);
+    }
+
+    #[test]
+    fn leaves_non_sensitive_bodies_untouched() {
+        let r = Redactor::default();
+        assert!(matches!(r.body(r#"{"id":1}"#), Cow::Borrowed(_)));
+        assert!(matches!(r.body("plain text body"), Cow::Borrowed(_)));
+    }
+
+    #[test]
+    fn masks_truncated_json_by_pattern() {
+        let r = Redactor::default();
+        assert_eq!(
+            r.body(r#"{"user":"bob","token": "abc\"def","list":[1,2"#),
+            r#"{"user":"bob","token":"[REDACTED]","list":[1,2"#
+        );
+    }
+
+    #[test]
+    fn masks_json_paths_and_value_patterns() {
+        let r = Redactor::default()
+            .with_json_paths(&["$.customer.dob", "$.cards[*].number", "$[invalid"])
+            .with_value_patterns(&[r"\b\d{3}-\d{2}-\d{4}\b"]);
+        let body = r#"{"customer":{"name":"Ann","dob":"1990-01-01","note":"ssn 123-45-6789"},"cards":[{"number":"4111"},{"number":"5500"}]}"#;
+        let masked: Value = serde_json::from_str(&r.body(body)).unwrap();
+        assert_eq!(masked["customer"]["name"], "Ann");
+        assert_eq!(masked["customer"]["dob"], REDACTED);
+        assert_eq!(masked["customer"]["note"], "ssn [REDACTED]");
+        assert_eq!(masked["cards"][1]["number"], REDACTED);
+
+        assert_eq!(
+            r.text("expected 'x', got '123-45-6789'"),
+            "expected 'x', got '[REDACTED]'"
+        );
+        assert!(matches!(r.text("nothing here"), Cow::Borrowed(_)));
+        assert_eq!(r.body("plain 123-45-6789"), "plain [REDACTED]");
+    }
+
+    #[test]
+    fn masks_form_fields() {
+        let r = Redactor::default();
+        assert_eq!(
+            r.body("user=bob&password=hunter2"),
+            "user=bob&password=[REDACTED]"
+        );
+    }
+
+    #[test]
+    fn extra_patterns_extend_defaults() {
+        let r = Redactor::with_extra_patterns(&["ssn", "card_?number", "("]);
+        assert!(r.is_sensitive_field("customer_SSN"));
+        assert!(r.is_sensitive_field("cardNumber"));
+        assert!(r.is_sensitive_field("password"));
+        assert!(!r.is_sensitive_field("quantity"));
+    }
+
+    #[test]
+    fn masks_paths_but_keeps_file_name() {
+        let r = Redactor::default();
+        assert_eq!(r.path("/etc/secrets/client.key"), "[REDACTED]/client.key");
+        assert_eq!(r.path(""), REDACTED);
+    }
+}
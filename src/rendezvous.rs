@@ -0,0 +1,199 @@
+//! Rendezvous points (`rendezvous:`): synchronized bursts across workers.
+//!
+//! A step with `rendezvous: {name, count, timeout}` waits until `count`
+//! workers have arrived at the point, then all of them send their request at
+//! once — e.g. to hit an inventory endpoint with a burst of simultaneous
+//! purchases. If the group is not complete within `timeout`, the workers
+//! already waiting are released together anyway, so a short worker pool
+//! (or the end of a phase) never blocks a test.
+//!
+//! Points are process-wide and keyed by name, so steps in different
+//! scenarios can meet at the same point. Time spent waiting is not counted
+//! in step latency. Waiting workers are exported as `rendezvous_waiting` and
+//! releases as `rendezvous_releases_total{reason="full"|"timeout"}`.
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+use tokio::sync::watch;
+use tracing::debug;
+
+use crate::metrics::{RENDEZVOUS_RELEASES_TOTAL, RENDEZVOUS_WAITING};
+use crate::scenario::Rendezvous;
+
+/// Why a group of workers was released.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Release {
+    /// `count` workers arrived.
+    Full,
+    /// The timeout expired before the group was complete.
+    Timeout,
+}
+
+impl Release {
+    fn label(self) -> &'static str {
+        match self {
+            Release::Full => "full",
+            Release::Timeout => "timeout",
+        }
+    }
+}
+
+/// Arrivals for the current group; `generation` advances on every release.
+struct Group {
+    generation: u64,
+    arrived: usize,
+}
+
+struct Point {
+    count: usize,
+    group: Mutex<Group>,
+    released: watch::Sender<(u64, Release)>,
+}
+
+impl Point {
+    fn new(count: usize) -> Self {
+        Self {
+            count,
+            group: Mutex::new(Group {
+                generation: 0,
+                arrived: 0,
+            }),
+            released: watch::channel((0, Release::Full)).0,
+        }
+    }
+
+    /// Releases the waiting group; the caller holds the group lock.
+    fn release(&self, group: &mut Group, reason: Release) {
+        group.generation += 1;
+        group.arrived = 0;
+        self.released.send_replace((group.generation, reason));
+    }
+}
+
+lazy_static::lazy_static! {
+    /// Rendezvous name → point, replaced if the configured count changed.
+    static ref POINTS: Mutex<HashMap<String, Arc<Point>>> = Mutex::new(HashMap::new());
+}
+
+fn point(name: &str, count: usize) -> Arc<Point> {
+    let mut points = POINTS.lock().unwrap_or_else(|e| e.into_inner());
+    let entry = points
+        .entry(name.to_string())
+        .or_insert_with(|| Arc::new(Point::new(count)));
+    if entry.count != count {
+        *entry = Arc::new(Point::new(count));
+    }
+    entry.clone()
+}
+
+/// Drops all points, e.g. before a new test plan starts.
+pub fn clear() {
+    POINTS.lock().unwrap_or_else(|e| e.into_inner()).clear();
+}
+
+/// Waits at `rendezvous` until its group is released.
+pub async fn wait(rendezvous: &Rendezvous, node_id: &str, run_id: &str) -> Release {
+    let point = point(&rendezvous.name, rendezvous.count);
+    let labels = [rendezvous.name.as_str(), node_id, run_id];
+    let waiting = RENDEZVOUS_WAITING.with_label_values(&labels);
+
+    let (generation, mut released) = {
+        let mut group = point.group.lock().unwrap_or_else(|e| e.into_inner());
+        group.arrived += 1;
+        if group.arrived >= point.count {
+            point.release(&mut group, Release::Full);
+            record_release(&labels, Release::Full, point.count);
+            return Release::Full;
+        }
+        waiting.inc();
+        (group.generation, point.released.subscribe())
+    };
+
+    let wait_for_release = released.wait_for(|(g, _)| *g > generation);
+    let reason = match tokio::time::timeout(rendezvous.timeout, wait_for_release).await {
+        Ok(Ok(release)) => release.1,
+        _ => {
+            let mut group = point.group.lock().unwrap_or_else(|e| e.into_inner());
+            if group.generation == generation {
+                let arrived = group.arrived;
+                point.release(&mut group, Release::Timeout);
+                record_release(&labels, Release::Timeout, arrived);
+                Release::Timeout
+            } else {
+                // Released while the timeout fired.
+                point.released.borrow().1
+            }
+        }
+    };
+    waiting.dec();
+    reason
+}
+
+fn record_release(labels: &[&str; 3], reason: Release, workers: usize) {
+    debug!(
+        rendezvous = labels[0],
+        reason = reason.label(),
+        workers,
+        "Rendezvous released"
+    );
+    RENDEZVOUS_RELEASES_TOTAL
+        .with_label_values(&[labels[0], reason.label(), labels[1], labels[2]])
+        .inc();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    fn rendezvous(name: &str, count: usize, timeout_ms: u64) -> Rendezvous {
+        Rendezvous {
+            name: name.to_string(),
+            count,
+            timeout: Duration::from_millis(timeout_ms),
+        }
+    }
+
+    #[tokio::test]
+    async fn releases_when_group_is_complete() {
+        let point = rendezvous("full-test", 3, 5_000);
+        let early: Vec<_> = (0..2)
+            .map(|_| {
+                let point = point.clone();
+                tokio::spawn(async move { wait(&point, "node", "run").await })
+            })
+            .collect();
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        assert!(early.iter().all(|h| !h.is_finished()));
+
+        assert_eq!(wait(&point, "node", "run").await, Release::Full);
+        for handle in early {
+            assert_eq!(handle.await.unwrap(), Release::Full);
+        }
+    }
+
+    #[tokio::test]
+    async fn timeout_releases_waiting_workers() {
+        let point = rendezvous("timeout-test", 10, 100);
+        let waiters: Vec<_> = (0..3)
+            .map(|_| {
+                let point = point.clone();
+                tokio::spawn(async move { wait(&point, "node", "run").await })
+            })
+            .collect();
+        for handle in waiters {
+            assert_eq!(handle.await.unwrap(), Release::Timeout);
+        }
+
+        // The next group starts empty.
+        let group = point_state("timeout-test");
+        assert_eq!(group, 0);
+    }
+
+    fn point_state(name: &str) -> usize {
+        let points = POINTS.lock().unwrap();
+        let group = points[name].group.lock().unwrap();
+        group.arrived
+    }
+}
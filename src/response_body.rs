@@ -0,0 +1,167 @@
+//! Streaming response body reads (`MAX_BODY_BUFFER`).
+//!
+//! Scenario steps read the body chunk by chunk. It is only kept in memory
+//! when something reads it as text — an extractor, a body assertion,
+//! pagination or debug sampling — and then only up to `MAX_BODY_BUFFER`
+//! bytes; past that the rest is counted but dropped, and extractors and
+//! assertions see the truncated prefix. Steps that only check status,
+//! headers, latency or `bodySha256`/`bodySizeBytes` never buffer the body,
+//! so large or binary downloads cost no more memory than a small page.
+//!
+//! Bytes received are counted per step in `scenario_response_bytes_total`;
+//! truncated bodies in `response_bodies_truncated_total`.
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+use tokio::time::Instant;
+
+use crate::assertions::{BodyDigest, BodyHasher};
+use crate::network_profile::NetworkProfile;
+
+/// Default for `MAX_BODY_BUFFER`: 10 MiB.
+pub const DEFAULT_MAX_BODY_BUFFER: usize = 10 * 1024 * 1024;
+
+/// Most body bytes kept in memory for a single response.
+static MAX_BODY_BUFFER: AtomicUsize = AtomicUsize::new(DEFAULT_MAX_BODY_BUFFER);
+
+/// Sets the most body bytes kept in memory per response.
+pub fn init(max_body_buffer: usize) {
+    MAX_BODY_BUFFER.store(max_body_buffer, Ordering::Relaxed);
+}
+
+/// Returns the configured `MAX_BODY_BUFFER`.
+pub fn max_body_buffer() -> usize {
+    MAX_BODY_BUFFER.load(Ordering::Relaxed)
+}
+
+/// What to keep from a response body.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct BodyNeeds {
+    /// Keep the body as text (up to the buffer limit).
+    pub text: bool,
+    /// Compute the SHA-256 and size of the full body.
+    pub digest: bool,
+}
+
+/// A body read by [`read`].
+#[derive(Debug, Default)]
+pub struct BodyRead {
+    /// The body as text, empty unless requested; lossy UTF-8.
+    pub text: String,
+    /// Bytes received.
+    pub size: u64,
+    /// Digest of the full body, if requested.
+    pub digest: Option<BodyDigest>,
+    /// True if the text was cut at the buffer limit.
+    pub truncated: bool,
+}
+
+/// Accumulates a body chunk by chunk.
+struct Accumulator {
+    needs: BodyNeeds,
+    limit: usize,
+    kept: Vec<u8>,
+    hasher: Option<BodyHasher>,
+    size: u64,
+    truncated: bool,
+}
+
+impl Accumulator {
+    fn new(needs: BodyNeeds, limit: usize) -> Self {
+        Self {
+            needs,
+            limit,
+            kept: Vec::new(),
+            hasher: needs.digest.then(BodyHasher::new),
+            size: 0,
+            truncated: false,
+        }
+    }
+
+    fn push(&mut self, chunk: &[u8]) {
+        self.size += chunk.len() as u64;
+        if let Some(hasher) = self.hasher.as_mut() {
+            hasher.update(chunk);
+        }
+        if self.needs.text {
+            let room = self.limit.saturating_sub(self.kept.len());
+            self.truncated |= chunk.len() > room;
+            self.kept.extend_from_slice(&chunk[..chunk.len().min(room)]);
+        }
+    }
+
+    fn finish(self) -> BodyRead {
+        BodyRead {
+            text: String::from_utf8_lossy(&self.kept).into_owned(),
+            size: self.size,
+            digest: self.hasher.map(BodyHasher::finish),
+            truncated: self.truncated,
+        }
+    }
+}
+
+/// Streams `response`'s body, keeping only what `needs` asks for and pacing
+/// reads to `network`'s download cap.
+pub async fn read(
+    mut response: reqwest::Response,
+    network: Option<&NetworkProfile>,
+    needs: BodyNeeds,
+) -> reqwest::Result<BodyRead> {
+    let mut body = Accumulator::new(needs, max_body_buffer());
+    let start = Instant::now();
+    while let Some(chunk) = response.chunk().await? {
+        body.push(&chunk);
+        if let Some(profile) = network {
+            profile.pace_download(start, body.size).await;
+        }
+    }
+    Ok(body.finish())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TEXT: BodyNeeds = BodyNeeds {
+        text: true,
+        digest: false,
+    };
+
+    #[test]
+    fn counts_without_buffering() {
+        let mut body = Accumulator::new(BodyNeeds::default(), 1024);
+        body.push(&[0xff; 4096]);
+        body.push(&[0xff; 4096]);
+        let read = body.finish();
+        assert_eq!(read.size, 8192);
+        assert!(read.text.is_empty());
+        assert!(read.digest.is_none());
+        assert!(!read.truncated);
+
+        let needs = BodyNeeds {
+            text: false,
+            digest: true,
+        };
+        let mut body = Accumulator::new(needs, 1024);
+        body.push(b"hel");
+        body.push(b"lo");
+        assert_eq!(body.finish().digest, Some(BodyDigest::of(b"hello")));
+    }
+
+    #[test]
+    fn keeps_text_up_to_limit() {
+        let mut body = Accumulator::new(TEXT, 1024);
+        body.push(b"hello");
+        let read = body.finish();
+        assert_eq!(read.text, "hello");
+        assert!(!read.truncated);
+
+        let mut body = Accumulator::new(TEXT, 8);
+        body.push(b"hello ");
+        body.push(b"world");
+        body.push(b"!");
+        let read = body.finish();
+        assert_eq!(read.text, "hello wo");
+        assert_eq!(read.size, 12);
+        assert!(read.truncated);
+    }
+}
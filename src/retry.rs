@@ -0,0 +1,129 @@
+//! Step retries (`retryCount`, `retryDelay`) and `Retry-After` handling.
+//!
+//! A scenario with `config.retryCount: N` retries a step up to N times when
+//! the request could not be completed or was answered `429` or `5xx`.
+//! Assertion failures on other statuses are not retried. Each attempt is a
+//! real request, but only the last is reported as the step's result, with
+//! the number of retries before it; retries are counted in
+//! `step_retries_total`.
+//!
+//! Between attempts the worker waits `retryDelay`. With
+//! `honorRetryAfter: true`, a `429` or `503` carrying a `Retry-After` header
+//! (seconds or an HTTP date) sets the wait instead, capped at
+//! `maxRetryAfter`, so the test backs off like a well-mannered client and
+//! measures the throughput a rate-limited API actually sustains. Honored
+//! headers are counted in `retry_after_honored_total`.
+//!
+//! A step with `idempotencyKey` sends a fresh UUID in an `Idempotency-Key`
+//! header (or the header it names) each time it runs, and the same key on
+//! every retry of that run, so a target that de-duplicates by key does not
+//! create a second order when the first attempt went through but its
+//! response was lost. The key is also the `${idempotency_key}` variable.
+
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use reqwest::header::{HeaderMap, RETRY_AFTER};
+
+use crate::executor::StepResult;
+use crate::scenario::RetryPolicy;
+
+/// Variable holding the current idempotency key.
+pub const IDEMPOTENCY_KEY_VARIABLE: &str = "idempotency_key";
+
+/// Delay requested by a `429` or `503` response's `Retry-After` header.
+pub fn retry_after(status: u16, headers: &HeaderMap) -> Option<Duration> {
+    if status != 429 && status != 503 {
+        return None;
+    }
+    let value = headers.get(RETRY_AFTER)?.to_str().ok()?.trim();
+    if let Ok(secs) = value.parse::<u64>() {
+        return Some(Duration::from_secs(secs));
+    }
+    let date = chrono::DateTime::parse_from_rfc2822(value).ok()?;
+    let now = SystemTime::now().duration_since(UNIX_EPOCH).ok()?.as_secs() as i64;
+    Some(Duration::from_secs(
+        date.timestamp().saturating_sub(now).max(0) as u64,
+    ))
+}
+
+/// True if `result` failed in a way worth retrying: no response at all, or
+/// a `429` / `5xx` status.
+pub fn is_retryable(result: &StepResult) -> bool {
+    if result.success || result.cache_hit {
+        return false;
+    }
+    match result.status_code {
+        Some(status) => status == 429 || (500..600).contains(&status),
+        None => true,
+    }
+}
+
+/// How long to wait before retrying `result`, and whether the wait came
+/// from its `Retry-After` header.
+pub fn backoff(policy: &RetryPolicy, result: &StepResult) -> (Duration, bool) {
+    match result.retry_after.filter(|_| policy.honor_retry_after) {
+        Some(requested) => (requested.min(policy.max_retry_after), true),
+        None => (policy.delay, false),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn headers(retry_after: &str) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        headers.insert(RETRY_AFTER, retry_after.parse().unwrap());
+        headers
+    }
+
+    fn failed(status: Option<u16>, retry_after: Option<Duration>) -> StepResult {
+        StepResult {
+            step_name: "step".to_string(),
+            status_code: status,
+            response_time_ms: 5,
+            retry_after,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn parses_retry_after() {
+        assert_eq!(
+            retry_after(429, &headers("120")),
+            Some(Duration::from_secs(120))
+        );
+        assert_eq!(retry_after(200, &headers("120")), None);
+        assert_eq!(retry_after(503, &headers("soon")), None);
+        // A date in the past means "now".
+        assert_eq!(
+            retry_after(503, &headers("Wed, 21 Oct 2015 07:28:00 GMT")),
+            Some(Duration::ZERO)
+        );
+    }
+
+    #[test]
+    fn backs_off_per_policy() {
+        let mut policy = RetryPolicy {
+            count: 2,
+            delay: Duration::from_millis(100),
+            honor_retry_after: false,
+            max_retry_after: Duration::from_secs(30),
+        };
+        let limited = failed(Some(429), Some(Duration::from_secs(60)));
+        assert!(is_retryable(&limited));
+        assert!(!is_retryable(&failed(Some(404), None)));
+        assert!(is_retryable(&failed(None, None)));
+
+        assert_eq!(
+            backoff(&policy, &limited),
+            (Duration::from_millis(100), false)
+        );
+        policy.honor_retry_after = true;
+        assert_eq!(backoff(&policy, &limited), (Duration::from_secs(30), true));
+        assert_eq!(
+            backoff(&policy, &failed(Some(500), None)),
+            (Duration::from_millis(100), false)
+        );
+    }
+}
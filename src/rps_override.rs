@@ -0,0 +1,191 @@
+//! Runtime target-RPS override: a manual throttle for game days.
+//!
+//! While an override is set, every worker paces itself to the override
+//! instead of its load model, whatever the model would ask for at this point
+//! of the test. Clearing the override hands control back to the model. The
+//! override is the node's total rate, like the load model's, and is set
+//! either way:
+//!
+//! - `POST /rps` on the control API with `{"rps": 250}`; `{"rps": null}`
+//!   clears it. The response reports the override now in effect.
+//! - `RPS_OVERRIDE_FILE`: a file containing a number, checked every second.
+//!   An empty or deleted file clears the override.
+//!
+//! The most recent change wins: a file is only applied when its contents
+//! change, so a `POST /rps` stays in effect until the file is edited again.
+//! `intended_rps` reports the override while it is set and
+//! `rps_override_active` is 1.
+
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+use tokio::task::JoinHandle;
+use tracing::{info, warn};
+
+use crate::load_models::LoadModel;
+use crate::metrics::RPS_OVERRIDE_ACTIVE;
+
+/// How often `RPS_OVERRIDE_FILE` is checked for changes.
+pub const FILE_POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Bit pattern for "no override"; a NaN, so never a valid rate.
+const UNSET: u64 = u64::MAX;
+
+static OVERRIDE_BITS: AtomicU64 = AtomicU64::new(UNSET);
+
+/// Errors raised when parsing an override value.
+#[derive(Error, Debug, PartialEq)]
+pub enum OverrideError {
+    #[error("invalid RPS override '{0}': expected a non-negative number")]
+    Invalid(String),
+}
+
+/// Sets (`Some`) or clears (`None`) the override.
+pub fn set(rps: Option<f64>) {
+    OVERRIDE_BITS.store(rps.map_or(UNSET, f64::to_bits), Ordering::Relaxed);
+    RPS_OVERRIDE_ACTIVE.set(if rps.is_some() { 1.0 } else { 0.0 });
+}
+
+/// The override in effect, if any.
+pub fn get() -> Option<f64> {
+    match OVERRIDE_BITS.load(Ordering::Relaxed) {
+        UNSET => None,
+        bits => Some(f64::from_bits(bits)),
+    }
+}
+
+/// Target rate at `elapsed_secs`: the override if set, otherwise the
+/// load model's rate. In a supervisor's runner, this runner's share of it.
+pub fn target_rps(model: &LoadModel, elapsed_secs: f64, duration_secs: f64) -> f64 {
+    crate::runner::runner_rps(
+        get().unwrap_or_else(|| model.calculate_current_rps(elapsed_secs, duration_secs)),
+    )
+}
+
+/// Parses an override file's contents: a number sets the override,
+/// blank contents clear it.
+pub fn parse(text: &str) -> Result<Option<f64>, OverrideError> {
+    let text = text.trim();
+    if text.is_empty() {
+        return Ok(None);
+    }
+    match text.parse::<f64>() {
+        Ok(rps) if rps.is_finite() && rps >= 0.0 => Ok(Some(rps)),
+        _ => Err(OverrideError::Invalid(text.to_string())),
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+struct OverrideBody {
+    rps: Option<f64>,
+}
+
+#[derive(Serialize)]
+struct OverrideResponse {
+    rps: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<String>,
+}
+
+/// Handles a `POST /rps` body, returning the HTTP status and JSON response.
+pub fn handle_request(body: &[u8]) -> (u16, String) {
+    let (status, response) = match serde_json::from_slice::<OverrideBody>(body) {
+        Ok(OverrideBody { rps: Some(rps) }) if !rps.is_finite() || rps < 0.0 => (
+            400,
+            OverrideResponse {
+                rps: get(),
+                error: Some(OverrideError::Invalid(rps.to_string()).to_string()),
+            },
+        ),
+        Ok(OverrideBody { rps }) => {
+            set(rps);
+            match rps {
+                Some(rps) => info!(rps, source = "api", "Target RPS override set"),
+                None => info!(source = "api", "Target RPS override cleared"),
+            }
+            (200, OverrideResponse { rps, error: None })
+        }
+        Err(e) => (
+            400,
+            OverrideResponse {
+                rps: get(),
+                error: Some(format!("invalid RPS override: {}", e)),
+            },
+        ),
+    };
+    (
+        status,
+        serde_json::to_string(&response).unwrap_or_else(|_| "{}".to_string()),
+    )
+}
+
+/// Watches `path` for the lifetime of the process, applying its contents
+/// whenever they change. Unparsable contents are logged and ignored.
+pub fn spawn_file_watcher(path: PathBuf) -> JoinHandle<()> {
+    info!(path = %path.display(), "Watching RPS_OVERRIDE_FILE for target RPS overrides");
+    tokio::spawn(async move {
+        let mut last: Option<String> = None;
+        let mut interval = tokio::time::interval(FILE_POLL_INTERVAL);
+        loop {
+            interval.tick().await;
+            let contents = tokio::fs::read_to_string(&path).await.unwrap_or_default();
+            if last.as_deref() == Some(contents.as_str()) {
+                continue;
+            }
+            // A missing file at startup leaves any API override alone.
+            let first = last.is_none();
+            last = Some(contents.clone());
+            match parse(&contents) {
+                Ok(None) if first => {}
+                Ok(Some(rps)) => {
+                    set(Some(rps));
+                    info!(rps, source = "file", "Target RPS override set");
+                }
+                Ok(None) => {
+                    set(None);
+                    info!(source = "file", "Target RPS override cleared");
+                }
+                Err(e) => warn!(path = %path.display(), error = %e, "Ignoring RPS_OVERRIDE_FILE"),
+            }
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_override_values() {
+        assert_eq!(parse("250\n"), Ok(Some(250.0)));
+        assert_eq!(parse(" 0.5 "), Ok(Some(0.5)));
+        assert_eq!(parse(""), Ok(None));
+        assert_eq!(parse("  \n"), Ok(None));
+        assert!(parse("-1").is_err());
+        assert!(parse("fast").is_err());
+        assert!(parse("inf").is_err());
+    }
+
+    #[test]
+    fn override_takes_precedence_over_model() {
+        let model = LoadModel::Rps { target_rps: 100.0 };
+        assert_eq!(target_rps(&model, 10.0, 60.0), 100.0);
+
+        let (status, body) = handle_request(br#"{"rps": 25}"#);
+        assert_eq!(status, 200);
+        assert_eq!(body, r#"{"rps":25.0}"#);
+        assert_eq!(target_rps(&model, 10.0, 60.0), 25.0);
+
+        let (status, _) = handle_request(br#"{"rps": -5}"#);
+        assert_eq!(status, 400);
+        assert_eq!(get(), Some(25.0));
+
+        let (status, body) = handle_request(br#"{"rps": null}"#);
+        assert_eq!(status, 200);
+        assert_eq!(body, r#"{"rps":null}"#);
+        assert_eq!(target_rps(&model, 10.0, 60.0), 100.0);
+    }
+}
@@ -0,0 +1,74 @@
+//! Test run identification (`RUN_ID`, `RUN_ID_HEADER`).
+//!
+//! Every run carries an ID used as the `run_id` metrics label and in the
+//! final JSON report. With `RUN_ID_HEADER=true` it is also sent on every
+//! request as [`RUN_ID_HEADER`], so server-side logs and traces can be
+//! filtered down to a single run.
+
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use reqwest::RequestBuilder;
+
+/// Header carrying the run ID when `RUN_ID_HEADER=true`.
+pub const RUN_ID_HEADER: &str = "X-Load-Test-Run";
+
+/// Whether requests carry the run ID header.
+static HEADER_ENABLED: AtomicBool = AtomicBool::new(false);
+
+/// Enables or disables the run ID request header.
+pub fn init(header_enabled: bool) {
+    HEADER_ENABLED.store(header_enabled, Ordering::Relaxed);
+}
+
+/// Returns true if requests carry the run ID header.
+pub fn header_enabled() -> bool {
+    HEADER_ENABLED.load(Ordering::Relaxed)
+}
+
+/// Generates a run ID from the current time (`run-<unix seconds>`).
+pub fn generate() -> String {
+    let secs = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs();
+    format!("run-{}", secs)
+}
+
+/// Adds the run ID header to a request if enabled.
+pub fn apply(builder: RequestBuilder, run_id: &str) -> RequestBuilder {
+    if header_enabled() {
+        builder.header(RUN_ID_HEADER, run_id)
+    } else {
+        builder
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn header_added_only_when_enabled() {
+        let client = reqwest::Client::new();
+        let header = |builder: RequestBuilder| {
+            builder
+                .build()
+                .unwrap()
+                .headers()
+                .get(RUN_ID_HEADER)
+                .map(|v| v.to_str().unwrap().to_string())
+        };
+
+        init(false);
+        assert_eq!(header(apply(client.get("http://localhost/"), "r1")), None);
+        init(true);
+        assert_eq!(
+            header(apply(client.get("http://localhost/"), "r1")).as_deref(),
+            Some("r1")
+        );
+        init(false);
+
+        assert!(generate().starts_with("run-"));
+    }
+}
@@ -0,0 +1,407 @@
+//! Machine-readable summary of a finished test run.
+//!
+//! [`RunSummary::collect`] gathers the outcome, request totals, latency
+//! percentiles, scenario thresholds and assertion counts once the final
+//! report has been printed. It renders as a JSON document, as JSON Lines (one
+//! row per latency label or threshold, for loading into a table) and as a
+//! standalone HTML page; see [`crate::artifacts`] for where they end up.
+
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use serde::Serialize;
+
+use crate::app_error::AppError;
+use crate::metrics::{request_outcome_totals, scenario_assertion_totals};
+use crate::percentiles::{
+    PercentileStats, GLOBAL_REQUEST_PERCENTILES, GLOBAL_SCENARIO_PERCENTILES,
+    GLOBAL_STEP_PERCENTILES,
+};
+use crate::scenario_slo::{self, ThresholdResult};
+
+/// Latency percentiles in milliseconds.
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct LatencySummary {
+    pub count: u64,
+    pub mean_ms: f64,
+    pub p50_ms: f64,
+    pub p90_ms: f64,
+    pub p95_ms: f64,
+    pub p99_ms: f64,
+    pub max_ms: f64,
+}
+
+impl From<&PercentileStats> for LatencySummary {
+    fn from(stats: &PercentileStats) -> Self {
+        Self {
+            count: stats.count,
+            mean_ms: stats.mean / 1000.0,
+            p50_ms: stats.p50 as f64 / 1000.0,
+            p90_ms: stats.p90 as f64 / 1000.0,
+            p95_ms: stats.p95 as f64 / 1000.0,
+            p99_ms: stats.p99 as f64 / 1000.0,
+            max_ms: stats.max as f64 / 1000.0,
+        }
+    }
+}
+
+/// Latency of one scenario or step.
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct LabelledLatency {
+    pub label: String,
+    #[serde(flatten)]
+    pub latency: LatencySummary,
+}
+
+/// Outcome of one `scenarioThresholds` entry.
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct ThresholdSummary {
+    pub scenario: String,
+    pub required: String,
+    /// Percent; `None` when the scenario never ran.
+    pub success_rate: Option<f64>,
+    pub passed: bool,
+}
+
+impl From<&ThresholdResult> for ThresholdSummary {
+    fn from(result: &ThresholdResult) -> Self {
+        Self {
+            scenario: result.scenario.clone(),
+            required: result.threshold.to_string(),
+            success_rate: result.success_rate,
+            passed: result.passed,
+        }
+    }
+}
+
+/// Assertion results of one scenario.
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct AssertionSummary {
+    pub scenario: String,
+    pub passed: u64,
+    pub failed: u64,
+}
+
+/// Everything the end-of-run artifacts report.
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct RunSummary {
+    pub run_id: String,
+    pub node_id: String,
+    /// `SEED` the run's randomness was drawn from; rerun with it to reproduce.
+    pub seed: Option<u64>,
+    /// Unix time the summary was collected, in seconds.
+    pub finished_at: u64,
+    pub passed: bool,
+    /// `success` or the [`AppError::kind`] the run failed with.
+    pub outcome: String,
+    pub exit_code: u8,
+    /// Why the run failed; empty when it passed.
+    pub message: String,
+    pub requests: u64,
+    /// Requests that got an HTTP response (any status).
+    pub responses: u64,
+    /// Over all single requests; `None` with percentile tracking disabled.
+    pub latency: Option<LatencySummary>,
+    pub scenarios: Vec<LabelledLatency>,
+    pub steps: Vec<LabelledLatency>,
+    pub thresholds: Vec<ThresholdSummary>,
+    /// Per scenario, for scenarios whose steps have assertions.
+    pub assertions: Vec<AssertionSummary>,
+}
+
+impl RunSummary {
+    /// Collects the summary from the process-wide trackers.
+    pub fn collect(run_id: &str, node_id: &str, outcome: &Result<(), AppError>) -> Self {
+        let (requests, responses) = request_outcome_totals();
+        let labelled = |stats: std::collections::HashMap<String, PercentileStats>| {
+            let mut rows: Vec<LabelledLatency> = stats
+                .iter()
+                .map(|(label, stats)| LabelledLatency {
+                    label: label.clone(),
+                    latency: stats.into(),
+                })
+                .collect();
+            rows.sort_by(|a, b| a.label.cmp(&b.label));
+            rows
+        };
+        let mut assertions: Vec<AssertionSummary> = scenario_assertion_totals()
+            .into_iter()
+            .map(|(scenario, (passed, failed))| AssertionSummary {
+                scenario,
+                passed,
+                failed,
+            })
+            .collect();
+        assertions.sort_by(|a, b| a.scenario.cmp(&b.scenario));
+        let (outcome, exit_code, message) = match outcome {
+            Ok(()) => ("success".to_string(), 0, String::new()),
+            Err(e) => (e.kind().to_string(), e.exit_code(), e.to_string()),
+        };
+
+        Self {
+            run_id: run_id.to_string(),
+            node_id: node_id.to_string(),
+            seed: crate::seed::seed(),
+            finished_at: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .unwrap_or_default()
+                .as_secs(),
+            passed: exit_code == 0,
+            outcome,
+            exit_code,
+            message,
+            requests,
+            responses,
+            latency: GLOBAL_REQUEST_PERCENTILES.stats().as_ref().map(Into::into),
+            scenarios: labelled(GLOBAL_SCENARIO_PERCENTILES.all_stats()),
+            steps: labelled(GLOBAL_STEP_PERCENTILES.all_stats()),
+            thresholds: scenario_slo::evaluate().iter().map(Into::into).collect(),
+            assertions,
+        }
+    }
+
+    /// The summary as a pretty-printed JSON document.
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).unwrap_or_default()
+    }
+
+    /// One JSON object per line: the overall latency (`kind: request`), each
+    /// scenario and step, then each threshold. Every row carries the run ID.
+    pub fn to_jsonl(&self) -> String {
+        let mut out = String::new();
+        let mut push = |value: serde_json::Value| {
+            out.push_str(&value.to_string());
+            out.push('\n');
+        };
+        let latency_row = |kind: &str, label: &str, latency: &LatencySummary| {
+            let mut row = serde_json::json!({
+                "run_id": self.run_id,
+                "kind": kind,
+                "label": label,
+            });
+            if let (Some(row), Ok(serde_json::Value::Object(fields))) =
+                (row.as_object_mut(), serde_json::to_value(latency))
+            {
+                row.extend(fields);
+            }
+            row
+        };
+
+        if let Some(latency) = &self.latency {
+            push(latency_row("request", "all", latency));
+        }
+        for row in &self.scenarios {
+            push(latency_row("scenario", &row.label, &row.latency));
+        }
+        for row in &self.steps {
+            push(latency_row("step", &row.label, &row.latency));
+        }
+        for threshold in &self.thresholds {
+            push(serde_json::json!({
+                "run_id": self.run_id,
+                "kind": "threshold",
+                "label": threshold.scenario,
+                "required": threshold.required,
+                "success_rate": threshold.success_rate,
+                "passed": threshold.passed,
+            }));
+        }
+        out
+    }
+
+    /// A standalone HTML page with the outcome and the latency tables.
+    pub fn to_html(&self) -> String {
+        let verdict = if self.passed { "PASSED" } else { "FAILED" };
+        let mut html = format!(
+            "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n\
+             <title>Load test {run_id}</title>\n<style>\n\
+             body {{ font-family: sans-serif; margin: 2em; }}\n\
+             table {{ border-collapse: collapse; margin-bottom: 2em; }}\n\
+             th, td {{ border: 1px solid #ccc; padding: 4px 10px; text-align: right; }}\n\
+             th:first-child, td:first-child {{ text-align: left; }}\n\
+             .passed {{ color: #080; }} .failed {{ color: #c00; }}\n\
+             </style>\n</head>\n<body>\n\
+             <h1>Load test {run_id}</h1>\n\
+             <p class=\"{class}\"><strong>{verdict}</strong> ({outcome}, exit code {exit_code})</p>\n",
+            run_id = escape_html(&self.run_id),
+            class = verdict.to_lowercase(),
+            verdict = verdict,
+            outcome = escape_html(&self.outcome),
+            exit_code = self.exit_code,
+        );
+        if !self.message.is_empty() {
+            html.push_str(&format!("<p>{}</p>\n", escape_html(&self.message)));
+        }
+        html.push_str(&format!(
+            "<p>Node {}: {} requests, {} responses</p>\n",
+            escape_html(&self.node_id),
+            self.requests,
+            self.responses
+        ));
+        if let Some(seed) = self.seed {
+            html.push_str(&format!(
+                "<p>Seed {} (rerun with SEED={})</p>\n",
+                seed, seed
+            ));
+        }
+
+        let overall: Vec<LabelledLatency> = self
+            .latency
+            .iter()
+            .map(|latency| LabelledLatency {
+                label: "all requests".to_string(),
+                latency: latency.clone(),
+            })
+            .collect();
+        for (title, rows) in [
+            ("Request latency", &overall),
+            ("Scenario latency", &self.scenarios),
+            ("Step latency", &self.steps),
+        ] {
+            if rows.is_empty() {
+                continue;
+            }
+            html.push_str(&format!(
+                "<h2>{}</h2>\n<table>\n<tr><th>Label</th><th>Count</th><th>Mean (ms)</th>\
+                 <th>P50 (ms)</th><th>P90 (ms)</th><th>P95 (ms)</th><th>P99 (ms)</th>\
+                 <th>Max (ms)</th></tr>\n",
+                title
+            ));
+            for row in rows {
+                let l = &row.latency;
+                html.push_str(&format!(
+                    "<tr><td>{}</td><td>{}</td><td>{:.2}</td><td>{:.2}</td><td>{:.2}</td>\
+                     <td>{:.2}</td><td>{:.2}</td><td>{:.2}</td></tr>\n",
+                    escape_html(&row.label),
+                    l.count,
+                    l.mean_ms,
+                    l.p50_ms,
+                    l.p90_ms,
+                    l.p95_ms,
+                    l.p99_ms,
+                    l.max_ms
+                ));
+            }
+            html.push_str("</table>\n");
+        }
+
+        if !self.thresholds.is_empty() {
+            html.push_str(
+                "<h2>Scenario thresholds</h2>\n<table>\n\
+                 <tr><th>Scenario</th><th>Required</th><th>Success rate</th><th>Result</th></tr>\n",
+            );
+            for threshold in &self.thresholds {
+                let (class, result) = if threshold.passed {
+                    ("passed", "PASS")
+                } else {
+                    ("failed", "FAIL")
+                };
+                let rate = threshold
+                    .success_rate
+                    .map_or_else(|| "-".to_string(), |rate| format!("{:.3}%", rate));
+                html.push_str(&format!(
+                    "<tr><td>{}</td><td>{}</td><td>{}</td><td class=\"{}\">{}</td></tr>\n",
+                    escape_html(&threshold.scenario),
+                    escape_html(&threshold.required),
+                    rate,
+                    class,
+                    result
+                ));
+            }
+            html.push_str("</table>\n");
+        }
+        html.push_str("</body>\n</html>\n");
+        html
+    }
+}
+
+fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn latency(p95_ms: f64) -> LatencySummary {
+        LatencySummary {
+            count: 10,
+            mean_ms: 12.0,
+            p50_ms: 10.0,
+            p90_ms: 20.0,
+            p95_ms,
+            p99_ms: 40.0,
+            max_ms: 50.0,
+        }
+    }
+
+    fn summary() -> RunSummary {
+        RunSummary {
+            run_id: "nightly-42".to_string(),
+            node_id: "node-1".to_string(),
+            finished_at: 1_700_000_000,
+            passed: false,
+            outcome: "threshold_failed".to_string(),
+            exit_code: 3,
+            message: "checkout <99.5%".to_string(),
+            requests: 100,
+            responses: 98,
+            latency: Some(latency(30.0)),
+            scenarios: vec![LabelledLatency {
+                label: "checkout".to_string(),
+                latency: latency(31.0),
+            }],
+            steps: Vec::new(),
+            thresholds: vec![ThresholdSummary {
+                scenario: "checkout".to_string(),
+                required: "successRate > 99.5%".to_string(),
+                success_rate: Some(98.0),
+                passed: false,
+            }],
+            assertions: Vec::new(),
+            seed: None,
+        }
+    }
+
+    #[test]
+    fn renders_json_lines_per_row() {
+        let lines: Vec<serde_json::Value> = summary()
+            .to_jsonl()
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect();
+        assert_eq!(lines.len(), 3);
+        assert_eq!(lines[0]["kind"], "request");
+        assert_eq!(lines[0]["p95_ms"], 30.0);
+        assert_eq!(lines[1]["label"], "checkout");
+        assert_eq!(lines[1]["run_id"], "nightly-42");
+        assert_eq!(lines[2]["kind"], "threshold");
+        assert_eq!(lines[2]["passed"], false);
+
+        let json: serde_json::Value = serde_json::from_str(&summary().to_json()).unwrap();
+        assert_eq!(json["outcome"], "threshold_failed");
+        assert_eq!(json["scenarios"][0]["p95_ms"], 31.0);
+    }
+
+    #[test]
+    fn renders_escaped_html() {
+        let html = summary().to_html();
+        assert!(html.contains("<strong>FAILED</strong> (threshold_failed, exit code 3)"));
+        assert!(html.contains("checkout &lt;99.5%"));
+        assert!(html.contains("<td class=\"failed\">FAIL</td>"));
+        assert!(html.contains("<h2>Scenario latency</h2>"));
+        assert!(!html.contains("<h2>Step latency</h2>"));
+        assert!(!html.contains("SEED="));
+    }
+
+    #[test]
+    fn records_seed() {
+        let mut seeded = summary();
+        seeded.seed = Some(1234);
+        assert!(seeded.to_html().contains("rerun with SEED=1234"));
+        let json: serde_json::Value = serde_json::from_str(&seeded.to_json()).unwrap();
+        assert_eq!(json["seed"], 1234);
+    }
+}
@@ -0,0 +1,103 @@
+//! This process's share of the load when a supervisor (`RUNNER_PROCESSES`)
+//! started it as runner `RUNNER_INDEX` of `RUNNER_COUNT`.
+//!
+//! Outside supervisor mode every share is the whole.
+
+use crate::config::Config;
+
+/// This process's slot when it runs as one of a supervisor's runners.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RunnerShare {
+    pub index: usize,
+    pub count: usize,
+}
+
+lazy_static::lazy_static! {
+    /// Set from `RUNNER_INDEX` / `RUNNER_COUNT` in runner processes.
+    pub static ref RUNNER: Option<RunnerShare> = RunnerShare::from_env();
+}
+
+impl RunnerShare {
+    /// Reads `RUNNER_INDEX` and `RUNNER_COUNT`; `None` unless both are valid.
+    pub fn from_env() -> Option<Self> {
+        let var = |name| std::env::var(name).ok()?.trim().parse::<usize>().ok();
+        match (var("RUNNER_INDEX"), var("RUNNER_COUNT")) {
+            (Some(index), Some(count)) if index < count => Some(Self { index, count }),
+            _ => None,
+        }
+    }
+
+    /// This runner's share of a rate. Unbounded rates (the concurrent
+    /// model) are left as they are.
+    pub fn rate(&self, total: f64) -> f64 {
+        if total == f64::MAX || !total.is_finite() {
+            total
+        } else {
+            total / self.count as f64
+        }
+    }
+
+    /// This runner's share of a count; the remainder goes to the first
+    /// runners, so the shares add up to `total`.
+    pub fn count_share(&self, total: u64) -> u64 {
+        let count = self.count as u64;
+        total / count + u64::from((self.index as u64) < total % count)
+    }
+}
+
+/// A runner's share of `total` RPS; `total` outside supervisor mode.
+pub fn runner_rps(total: f64) -> f64 {
+    RUNNER.as_ref().map_or(total, |share| share.rate(total))
+}
+
+/// Scales workers and hard caps down to this runner's share.
+pub fn split_config(config: Config) -> Config {
+    match RUNNER.as_ref() {
+        Some(share) => share_config(config, share),
+        None => config,
+    }
+}
+
+fn share_config(mut config: Config, share: &RunnerShare) -> Config {
+    config.num_concurrent_tasks =
+        (share.count_share(config.num_concurrent_tasks as u64) as usize).max(1);
+    if config.max_total_requests > 0 {
+        config.max_total_requests = share.count_share(config.max_total_requests).max(1);
+    }
+    if config.hard_max_rps > 0.0 {
+        config.hard_max_rps = share.rate(config.hard_max_rps);
+    }
+    if config.max_in_flight > 0 {
+        config.max_in_flight = (share.count_share(config.max_in_flight as u64) as usize).max(1);
+    }
+    config
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn shares_add_up_to_totals() {
+        let shares: Vec<_> = (0..3)
+            .map(|index| RunnerShare { index, count: 3 })
+            .collect();
+        assert_eq!(
+            shares.iter().map(|s| s.count_share(10)).collect::<Vec<_>>(),
+            vec![4, 3, 3]
+        );
+        assert_eq!(shares[1].rate(300.0), 100.0);
+        assert_eq!(shares[1].rate(f64::MAX), f64::MAX);
+
+        let mut config = Config::for_testing();
+        config.num_concurrent_tasks = 2;
+        config.max_total_requests = 0;
+        config.hard_max_rps = 90.0;
+        config.max_in_flight = 100;
+        let config = share_config(config, &shares[2]);
+        assert_eq!(config.num_concurrent_tasks, 1, "every runner gets a worker");
+        assert_eq!(config.max_total_requests, 0, "unlimited stays unlimited");
+        assert_eq!(config.hard_max_rps, 30.0);
+        assert_eq!(config.max_in_flight, 33);
+    }
+}
@@ -0,0 +1,458 @@
+//! Guardrails against pointing load at the wrong target.
+//!
+//! Targets are checked against three optional lists of patterns — hostnames
+//! (`api.example.com`), subdomain wildcards (`*.prod.example.com`) or CIDR
+//! ranges (`10.20.0.0/16`, matched against the host's IP or its resolved
+//! addresses):
+//!
+//! | Variable | Effect |
+//! |----------|--------|
+//! | `TARGET_DENYLIST` | Matching targets are always refused |
+//! | `TARGET_ALLOWLIST` | When set, only matching targets are accepted |
+//! | `PROTECTED_TARGETS` | Matching targets require `I_UNDERSTAND_THIS_SENDS_LOAD=true` |
+//!
+//! The deny-list wins over everything else, including the confirmation.
+//!
+//! [`SafetyPolicy::check_targets`] covers everything a test sends traffic
+//! to: the base URL, `RESOLVE_TARGET_ADDR` and `TLS_SERVER_NAME` connect
+//! addresses, absolute step URLs, step `Host` headers, `tcpCheck` addresses
+//! and `TEMPLATE_DIR` templates. Discovered service instances and `tcpCheck`
+//! connections are also checked when they are resolved.
+
+use std::collections::HashMap;
+use std::net::IpAddr;
+
+use reqwest::Url;
+use thiserror::Error;
+
+use crate::addr;
+use crate::client::ClientConfig;
+use crate::scenario::Scenario;
+use crate::templates::TemplateMix;
+
+/// Variable that must be `true` to send load to a protected target.
+pub const CONFIRM_VAR: &str = "I_UNDERSTAND_THIS_SENDS_LOAD";
+
+/// Reasons a target is refused.
+#[derive(Error, Debug, PartialEq)]
+pub enum SafetyError {
+    #[error("Invalid target pattern '{pattern}': {message}")]
+    InvalidPattern { pattern: String, message: String },
+
+    #[error("Target URL '{0}' has no host")]
+    NoHost(String),
+
+    #[error("Target '{host}' matches TARGET_DENYLIST entry '{pattern}'")]
+    Denied { host: String, pattern: String },
+
+    #[error("Target '{host}' is not in TARGET_ALLOWLIST")]
+    NotAllowed { host: String },
+
+    #[error(
+        "Target '{host}' matches PROTECTED_TARGETS entry '{pattern}'; \
+         set I_UNDERSTAND_THIS_SENDS_LOAD=true to send load to it"
+    )]
+    ConfirmationRequired { host: String, pattern: String },
+}
+
+/// A hostname, subdomain wildcard or CIDR range.
+#[derive(Debug, Clone, PartialEq)]
+pub enum TargetPattern {
+    /// Exact hostname, lower-cased.
+    Host(String),
+    /// `*.example.com`: any subdomain of the stored suffix (`example.com`).
+    Subdomains(String),
+    /// IP range; a plain IP is a /32 or /128.
+    Cidr { network: IpAddr, prefix: u8 },
+}
+
+impl TargetPattern {
+    pub fn parse(pattern: &str) -> Result<Self, SafetyError> {
+        let invalid = |message: String| SafetyError::InvalidPattern {
+            pattern: pattern.to_string(),
+            message,
+        };
+        let pattern_lc = pattern.trim().to_ascii_lowercase();
+
+        if let Some((addr, prefix)) = pattern_lc.split_once('/') {
+            let network: IpAddr = addr.parse().map_err(|e| invalid(format!("{}", e)))?;
+            let max = if network.is_ipv4() { 32 } else { 128 };
+            let prefix: u8 = prefix
+                .parse()
+                .ok()
+                .filter(|p| *p <= max)
+                .ok_or_else(|| invalid(format!("prefix must be 0-{}", max)))?;
+            return Ok(TargetPattern::Cidr { network, prefix });
+        }
+        if let Ok(ip) = pattern_lc
+            .trim_matches(|c| c == '[' || c == ']')
+            .parse::<IpAddr>()
+        {
+            let prefix = if ip.is_ipv4() { 32 } else { 128 };
+            return Ok(TargetPattern::Cidr {
+                network: ip,
+                prefix,
+            });
+        }
+        if let Some(suffix) = pattern_lc.strip_prefix("*.") {
+            if suffix.is_empty() || suffix.contains('*') {
+                return Err(invalid("wildcards are only allowed as '*.domain'".into()));
+            }
+            return Ok(TargetPattern::Subdomains(suffix.to_string()));
+        }
+        if pattern_lc.is_empty() || pattern_lc.contains('*') {
+            return Err(invalid("wildcards are only allowed as '*.domain'".into()));
+        }
+        Ok(TargetPattern::Host(pattern_lc))
+    }
+
+    /// Returns true if `host` (lower-cased) or any of `ips` match.
+    pub fn matches(&self, host: &str, ips: &[IpAddr]) -> bool {
+        match self {
+            TargetPattern::Host(name) => host == name,
+            TargetPattern::Subdomains(suffix) => host
+                .strip_suffix(suffix.as_str())
+                .is_some_and(|rest| rest.ends_with('.')),
+            TargetPattern::Cidr { network, prefix } => {
+                ips.iter().any(|ip| cidr_contains(*network, *prefix, *ip))
+            }
+        }
+    }
+}
+
+impl std::fmt::Display for TargetPattern {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TargetPattern::Host(name) => write!(f, "{}", name),
+            TargetPattern::Subdomains(suffix) => write!(f, "*.{}", suffix),
+            TargetPattern::Cidr { network, prefix } => write!(f, "{}/{}", network, prefix),
+        }
+    }
+}
+
+fn cidr_contains(network: IpAddr, prefix: u8, ip: IpAddr) -> bool {
+    match (network, ip) {
+        (IpAddr::V4(net), IpAddr::V4(ip)) => {
+            let mask = u32::MAX.checked_shl(32 - prefix as u32).unwrap_or(0);
+            u32::from(net) & mask == u32::from(ip) & mask
+        }
+        (IpAddr::V6(net), IpAddr::V6(ip)) => {
+            let mask = u128::MAX.checked_shl(128 - prefix as u32).unwrap_or(0);
+            u128::from(net) & mask == u128::from(ip) & mask
+        }
+        _ => false,
+    }
+}
+
+/// Allow-, deny- and protected-lists plus the load confirmation flag.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SafetyPolicy {
+    pub allow: Vec<TargetPattern>,
+    pub deny: Vec<TargetPattern>,
+    pub protected: Vec<TargetPattern>,
+    pub confirmed: bool,
+}
+
+impl SafetyPolicy {
+    /// Parses a comma-separated pattern list.
+    pub fn parse_list(raw: &str) -> Result<Vec<TargetPattern>, SafetyError> {
+        raw.split(',')
+            .map(str::trim)
+            .filter(|s| !s.is_empty())
+            .map(TargetPattern::parse)
+            .collect()
+    }
+
+    /// True when no lists are configured, so every target is accepted.
+    pub fn is_empty(&self) -> bool {
+        self.allow.is_empty() && self.deny.is_empty() && self.protected.is_empty()
+    }
+
+    fn has_cidr(&self) -> bool {
+        self.allow
+            .iter()
+            .chain(&self.deny)
+            .chain(&self.protected)
+            .any(|p| matches!(p, TargetPattern::Cidr { .. }))
+    }
+
+    /// Checks a host and its addresses against the policy.
+    pub fn check(&self, host: &str, ips: &[IpAddr]) -> Result<(), SafetyError> {
+        let host = host.to_ascii_lowercase();
+        let find = |list: &[TargetPattern]| list.iter().find(|p| p.matches(&host, ips)).cloned();
+
+        if let Some(pattern) = find(&self.deny) {
+            return Err(SafetyError::Denied {
+                host,
+                pattern: pattern.to_string(),
+            });
+        }
+        if !self.allow.is_empty() && find(&self.allow).is_none() {
+            return Err(SafetyError::NotAllowed { host });
+        }
+        if let Some(pattern) = find(&self.protected) {
+            if !self.confirmed {
+                return Err(SafetyError::ConfirmationRequired {
+                    host,
+                    pattern: pattern.to_string(),
+                });
+            }
+        }
+        Ok(())
+    }
+
+    /// Checks a hostname or IP, resolving it when CIDR patterns are in use.
+    pub async fn check_host(&self, host: &str) -> Result<(), SafetyError> {
+        if self.is_empty() {
+            return Ok(());
+        }
+        let host = host.trim_matches(|c| c == '[' || c == ']');
+        let mut ips: Vec<IpAddr> = host.parse().into_iter().collect();
+        if ips.is_empty() && self.has_cidr() {
+            if let Ok(addrs) = tokio::net::lookup_host((host, 0)).await {
+                ips.extend(addrs.map(|a| a.ip()));
+            }
+        }
+        self.check(host, &ips)
+    }
+
+    /// Checks a target URL, resolving its host when CIDR patterns are in use.
+    pub async fn check_url(&self, url: &str) -> Result<(), SafetyError> {
+        if self.is_empty() {
+            return Ok(());
+        }
+        let parsed = Url::parse(url).map_err(|_| SafetyError::NoHost(url.to_string()))?;
+        let host = parsed
+            .host_str()
+            .ok_or_else(|| SafetyError::NoHost(url.to_string()))?;
+        self.check_host(host).await
+    }
+
+    /// Checks every URL, returning the first refusal.
+    pub async fn check_urls<'a>(
+        &self,
+        urls: impl IntoIterator<Item = &'a str>,
+    ) -> Result<(), SafetyError> {
+        for url in urls {
+            self.check_url(url).await?;
+        }
+        Ok(())
+    }
+
+    /// Checks every target a test sends traffic to (see the module docs)
+    /// before any load is sent.
+    pub async fn check_targets(
+        &self,
+        base_url: &str,
+        client: &ClientConfig,
+        scenarios: &[Scenario],
+        templates: Option<&TemplateMix>,
+    ) -> Result<(), SafetyError> {
+        if self.is_empty() {
+            return Ok(());
+        }
+        self.check_url(base_url).await?;
+
+        // Connect overrides: each pinned name together with its address. An
+        // invalid RESOLVE_TARGET_ADDR already fails building the client.
+        let overrides = client
+            .resolve_target_addr
+            .as_deref()
+            .and_then(|raw| addr::parse_resolve_overrides(raw).ok())
+            .unwrap_or_default();
+        for o in &overrides {
+            self.check(&o.host, &[o.addr.ip()])?;
+        }
+        if let Some(sni) = &client.sni_override {
+            self.check_host(&sni.connect_host).await?;
+        }
+
+        for step in scenarios.iter().flat_map(|s| &s.steps) {
+            if is_absolute(&step.request.path) {
+                self.check_url(&step.request.path).await?;
+            }
+            let host = step
+                .request
+                .host
+                .as_deref()
+                .or_else(|| host_header(&step.request.headers));
+            if let Some(host) = host {
+                self.check_host_header(host).await?;
+            }
+            if let Some((host, _)) = step.tcp_check.as_ref().and_then(|c| c.target.as_ref()) {
+                self.check_host(host).await?;
+            }
+        }
+
+        for template in templates.map(TemplateMix::templates).unwrap_or_default() {
+            if is_absolute(&template.path) {
+                self.check_url(&template.path).await?;
+            }
+            if let Some(host) = host_header(&template.headers) {
+                self.check_host_header(host).await?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Checks a `Host` header value (`name` or `name:port`). Values with
+    /// variable references are only known per request and are skipped.
+    async fn check_host_header(&self, value: &str) -> Result<(), SafetyError> {
+        if value.contains("${") || value.contains("{{") {
+            return Ok(());
+        }
+        self.check_url(&format!("http://{}/", value.trim())).await
+    }
+}
+
+fn is_absolute(path: &str) -> bool {
+    path.starts_with("http://") || path.starts_with("https://")
+}
+
+fn host_header(headers: &HashMap<String, String>) -> Option<&str> {
+    headers
+        .iter()
+        .find(|(name, _)| name.eq_ignore_ascii_case("host"))
+        .map(|(_, value)| value.as_str())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn policy(allow: &str, deny: &str, protected: &str) -> SafetyPolicy {
+        SafetyPolicy {
+            allow: SafetyPolicy::parse_list(allow).unwrap(),
+            deny: SafetyPolicy::parse_list(deny).unwrap(),
+            protected: SafetyPolicy::parse_list(protected).unwrap(),
+            confirmed: false,
+        }
+    }
+
+    #[test]
+    fn parses_patterns() {
+        assert_eq!(
+            TargetPattern::parse("API.Example.com").unwrap(),
+            TargetPattern::Host("api.example.com".into())
+        );
+        assert_eq!(
+            TargetPattern::parse("*.prod.example.com").unwrap(),
+            TargetPattern::Subdomains("prod.example.com".into())
+        );
+        assert_eq!(
+            TargetPattern::parse("10.0.0.0/8").unwrap().to_string(),
+            "10.0.0.0/8"
+        );
+        assert_eq!(TargetPattern::parse("::1").unwrap().to_string(), "::1/128");
+        assert!(TargetPattern::parse("10.0.0.0/33").is_err());
+        assert!(TargetPattern::parse("api.*.com").is_err());
+    }
+
+    #[test]
+    fn deny_allow_and_protected_lists() {
+        let p = policy(
+            "*.staging.example.com, 10.0.0.0/8",
+            "db.staging.example.com",
+            "",
+        );
+        assert!(p.check("web.staging.example.com", &[]).is_ok());
+        assert!(p.check("staging.example.com", &[]).is_err());
+        assert!(p.check("internal", &["10.1.2.3".parse().unwrap()]).is_ok());
+        assert!(matches!(
+            p.check("DB.staging.example.com", &[]),
+            Err(SafetyError::Denied { .. })
+        ));
+        assert!(matches!(
+            p.check("api.example.com", &[]),
+            Err(SafetyError::NotAllowed { .. })
+        ));
+
+        let mut p = policy("", "", "*.prod.example.com");
+        assert!(p.check("api.dev.example.com", &[]).is_ok());
+        assert!(matches!(
+            p.check("api.prod.example.com", &[]),
+            Err(SafetyError::ConfirmationRequired { .. })
+        ));
+        p.confirmed = true;
+        assert!(p.check("api.prod.example.com", &[]).is_ok());
+    }
+
+    #[tokio::test]
+    async fn checks_urls_with_ip_hosts() {
+        let p = policy("", "192.168.0.0/16, ::1", "");
+        assert!(p.check_url("http://10.0.0.1:8080/x").await.is_ok());
+        assert!(p.check_url("http://192.168.1.10/").await.is_err());
+        assert!(p.check_url("http://[::1]:3000/").await.is_err());
+        assert!(matches!(
+            p.check_url("not a url").await,
+            Err(SafetyError::NoHost(_))
+        ));
+        assert!(SafetyPolicy::default().check_url("not a url").await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn checks_every_target_of_a_test() {
+        use crate::scenario::{RequestConfig, Step};
+        use crate::tcp_check::TcpCheck;
+
+        let p = policy("", "10.9.0.0/16, *.prod.example.com", "");
+        let base = "http://staging.example.com";
+        let client = || ClientConfig {
+            skip_tls_verify: false,
+            resolve_target_addr: None,
+            client_cert_path: None,
+            client_key_path: None,
+            custom_headers: None,
+            pool_config: None,
+            cookie_store: false,
+            local_address: None,
+            pinned_target: None,
+            ip_family: crate::ip_family::IpFamily::Auto,
+            tls_session_resumption: true,
+            sni_override: None,
+            proxy: None,
+            timeout: None,
+        };
+        let step = |request: RequestConfig, tcp: Option<&str>| Scenario {
+            steps: vec![Step {
+                request,
+                tcp_check: tcp.map(|host| TcpCheck {
+                    target: Some((host.to_string(), 5432)),
+                    tls: false,
+                    server_name: None,
+                    timeout: TcpCheck::DEFAULT_TIMEOUT,
+                }),
+                ..Step::default()
+            }],
+            ..Scenario::default()
+        };
+        assert!(p.check_targets(base, &client(), &[], None).await.is_ok());
+
+        let mut pinned = client();
+        pinned.resolve_target_addr = Some("staging.example.com:10.9.0.1:80".to_string());
+        assert!(p.check_targets(base, &pinned, &[], None).await.is_err());
+
+        let absolute = RequestConfig {
+            path: "https://api.prod.example.com/x".to_string(),
+            ..RequestConfig::default()
+        };
+        let host = RequestConfig {
+            host: Some("api.prod.example.com:8080".to_string()),
+            ..RequestConfig::default()
+        };
+        let templated = RequestConfig {
+            host: Some("${tenant}.example.com".to_string()),
+            ..RequestConfig::default()
+        };
+        for (scenario, refused) in [
+            (step(absolute, None), true),
+            (step(host, None), true),
+            (step(templated, None), false),
+            (step(RequestConfig::default(), Some("10.9.3.4")), true),
+            (step(RequestConfig::default(), Some("10.8.3.4")), false),
+        ] {
+            let result = p.check_targets(base, &client(), &[scenario], None).await;
+            assert_eq!(result.is_err(), refused, "{:?}", result);
+        }
+    }
+}
@@ -0,0 +1,170 @@
+//! Achieved-vs-intended RPS tracking (generator saturation detection).
+//!
+//! Once per second the health updater publishes the RPS the load model asks
+//! for (`intended_rps`) next to the RPS actually achieved (`achieved_rps`).
+//! When the achieved rate stays more than `RPS_DEVIATION_WARN_PERCENT` below
+//! the intended rate for `RPS_DEVIATION_WARN_SECS` consecutive seconds, a
+//! warning is logged: either the target is too slow to keep up or the
+//! generator itself (CPU, workers, connections) is the bottleneck.
+
+use std::env;
+
+use tokio::time::{Duration, Instant};
+
+/// Defaults: warn when more than 10% below target for 10 seconds.
+const DEFAULT_TOLERANCE_PERCENT: f64 = 10.0;
+const DEFAULT_WINDOW_SECS: u64 = 10;
+
+/// Thresholds for the saturation warning.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SaturationConfig {
+    /// Allowed shortfall, in percent of the intended RPS.
+    pub tolerance_percent: f64,
+    /// How long the shortfall must persist before warning.
+    pub window: Duration,
+}
+
+impl Default for SaturationConfig {
+    fn default() -> Self {
+        Self {
+            tolerance_percent: DEFAULT_TOLERANCE_PERCENT,
+            window: Duration::from_secs(DEFAULT_WINDOW_SECS),
+        }
+    }
+}
+
+impl SaturationConfig {
+    /// Reads `RPS_DEVIATION_WARN_PERCENT` and `RPS_DEVIATION_WARN_SECS`.
+    /// Unparseable values fall back to the defaults.
+    pub fn from_env() -> Self {
+        let tolerance_percent = env::var("RPS_DEVIATION_WARN_PERCENT")
+            .ok()
+            .and_then(|v| v.trim().parse::<f64>().ok())
+            .filter(|v| *v > 0.0)
+            .unwrap_or(DEFAULT_TOLERANCE_PERCENT);
+        let window_secs = env::var("RPS_DEVIATION_WARN_SECS")
+            .ok()
+            .and_then(|v| v.trim().parse::<u64>().ok())
+            .unwrap_or(DEFAULT_WINDOW_SECS);
+        Self {
+            tolerance_percent,
+            window: Duration::from_secs(window_secs),
+        }
+    }
+}
+
+/// A sustained shortfall reported by [`SaturationDetector::observe`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Shortfall {
+    pub intended_rps: f64,
+    pub achieved_rps: f64,
+    /// Shortfall in percent of the intended RPS.
+    pub deviation_percent: f64,
+    /// How long the achieved rate has been below tolerance.
+    pub duration: Duration,
+}
+
+/// Tracks how long the achieved RPS has been below the intended RPS.
+#[derive(Debug)]
+pub struct SaturationDetector {
+    config: SaturationConfig,
+    below_since: Option<Instant>,
+    warned: bool,
+}
+
+impl SaturationDetector {
+    pub fn new(config: SaturationConfig) -> Self {
+        Self {
+            config,
+            below_since: None,
+            warned: false,
+        }
+    }
+
+    /// Records one sample. Returns a [`Shortfall`] once per episode, when
+    /// the achieved rate has been out of tolerance for the whole window.
+    ///
+    /// Samples with no finite positive intended rate (Concurrent model,
+    /// idle standby) reset the detector.
+    pub fn observe(
+        &mut self,
+        intended_rps: f64,
+        achieved_rps: f64,
+        now: Instant,
+    ) -> Option<Shortfall> {
+        if !(intended_rps > 0.0 && intended_rps.is_finite()) {
+            self.reset();
+            return None;
+        }
+
+        let deviation_percent = (intended_rps - achieved_rps) / intended_rps * 100.0;
+        if deviation_percent <= self.config.tolerance_percent {
+            self.reset();
+            return None;
+        }
+
+        let since = *self.below_since.get_or_insert(now);
+        let duration = now.duration_since(since);
+        if self.warned || duration < self.config.window {
+            return None;
+        }
+
+        self.warned = true;
+        Some(Shortfall {
+            intended_rps,
+            achieved_rps,
+            deviation_percent,
+            duration,
+        })
+    }
+
+    /// Clears the current episode, e.g. when a new test starts.
+    pub fn reset(&mut self) {
+        self.below_since = None;
+        self.warned = false;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn detector() -> SaturationDetector {
+        SaturationDetector::new(SaturationConfig {
+            tolerance_percent: 10.0,
+            window: Duration::from_secs(3),
+        })
+    }
+
+    #[test]
+    fn warns_once_after_sustained_shortfall() {
+        let mut d = detector();
+        let t0 = Instant::now();
+        let at = |s: u64| t0 + Duration::from_secs(s);
+
+        assert_eq!(d.observe(100.0, 80.0, at(0)), None);
+        assert_eq!(d.observe(100.0, 80.0, at(2)), None);
+        let shortfall = d.observe(100.0, 80.0, at(3)).expect("warning after window");
+        assert_eq!(shortfall.deviation_percent, 20.0);
+        assert_eq!(shortfall.duration, Duration::from_secs(3));
+        // Only once per episode.
+        assert_eq!(d.observe(100.0, 80.0, at(4)), None);
+
+        // Recovery starts a new episode.
+        assert_eq!(d.observe(100.0, 95.0, at(5)), None);
+        assert_eq!(d.observe(100.0, 50.0, at(6)), None);
+        assert!(d.observe(100.0, 50.0, at(9)).is_some());
+    }
+
+    #[test]
+    fn ignores_small_deviation_and_unbounded_models() {
+        let mut d = detector();
+        let t0 = Instant::now();
+        for s in 0..10 {
+            let now = t0 + Duration::from_secs(s);
+            assert_eq!(d.observe(100.0, 91.0, now), None);
+            assert_eq!(d.observe(f64::MAX, 10.0, now), None);
+            assert_eq!(d.observe(0.0, 0.0, now), None);
+        }
+    }
+}
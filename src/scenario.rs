@@ -5,8 +5,18 @@
 //! that can extract variables, make assertions, and maintain state across requests.
 
 use std::collections::HashMap;
+use std::path::PathBuf;
 use std::time::{Duration, Instant};
 
+use crate::client::ClientOverrides;
+use crate::extractor::Transform;
+use crate::header_layers::ScenarioHeaders;
+use crate::network_profile::NetworkProfile;
+use crate::patterns::{CompiledPatterns, StepPatterns};
+use crate::shared_state::StoreOp;
+use crate::substitution::{RequestTemplates, StepTemplates, Template};
+use crate::tcp_check::TcpCheck;
+
 /// A multi-step test scenario representing a user journey.
 ///
 /// # Example
@@ -27,13 +37,39 @@ use std::time::{Duration, Instant};
 ///                 body: None,
 ///                 body_size: None,
 ///                 headers: HashMap::new(),
+///                 protobuf: None,
+///                 host: None,
 ///             },
 ///             extractions: vec![],
 ///             assertions: vec![],
 ///             cache: None,
 ///             think_time: Some(ThinkTime::Fixed(Duration::from_secs(2))),
+///             tags: HashMap::new(),
+///             paginate: None,
+///             max_concurrent: None,
+///             slo: None,
+///             shared: vec![],
+///             rendezvous: None,
+///             conditional: false,
+///             expected_status: Vec::new(),
+///             patterns: Default::default(),
+///             templates: Default::default(),
+///             idempotency_key: None,
+///             tcp_check: None,
 ///         },
 ///     ],
+///     network_profile: None,
+///     tags: HashMap::new(),
+///     max_concurrent: None,
+///     depends_on: Vec::new(),
+///     iterations: None,
+///     retry: None,
+///     assertion_sample_rate: 1.0,
+///     client: None,
+///     headers: Default::default(),
+///     optional: false,
+///     data_source: None,
+///     rps: None,
 /// };
 /// ```
 #[derive(Debug, Clone)]
@@ -47,6 +83,86 @@ pub struct Scenario {
 
     /// Sequential steps to execute
     pub steps: Vec<Step>,
+
+    /// Client-side network emulation (latency, jitter, bandwidth caps)
+    /// applied to every request in this scenario.
+    pub network_profile: Option<NetworkProfile>,
+
+    /// Free-form tags (e.g. `team: checkout`). Keys listed in
+    /// `METRIC_TAG_KEYS` become Prometheus labels and report groupings.
+    pub tags: HashMap<String, String>,
+
+    /// Most workers allowed to run this scenario at once, process-wide
+    /// (see [`crate::concurrency_limit`]).
+    pub max_concurrent: Option<usize>,
+
+    /// Scenarios that must finish before this one starts
+    /// (see [`crate::phases`]).
+    pub depends_on: Vec<String>,
+
+    /// Total executions across all workers, after which the scenario is
+    /// done; `None` runs until the end of its phase.
+    pub iterations: Option<u64>,
+
+    /// Fixed rate (iterations per second) for this scenario, paced by its
+    /// own workers independently of the load model (see [`crate::phases`]).
+    pub rps: Option<f64>,
+
+    /// Retries for failed steps (see [`crate::retry`]).
+    pub retry: Option<RetryPolicy>,
+
+    /// Fraction of iterations (0.0–1.0) whose responses are checked against
+    /// the steps' assertions; 1.0 checks every iteration. Extractions always
+    /// run, since later steps depend on them.
+    pub assertion_sample_rate: f64,
+
+    /// Client settings (TLS, headers, proxy, timeout) this scenario's
+    /// workers use instead of the node's.
+    pub client: Option<ClientOverrides>,
+
+    /// Headers and auth this scenario adds to every step, between the
+    /// global headers and the step's own (see [`crate::header_layers`]).
+    pub headers: ScenarioHeaders,
+
+    /// Iterations are skipped while the in-flight cap is saturated under
+    /// `BACKPRESSURE=degrade` (see [`crate::in_flight_limit`]).
+    pub optional: bool,
+
+    /// Data source plugin whose next row each iteration sets as variables
+    /// (see [`crate::plugin::DataSourcePlugin`]).
+    pub data_source: Option<String>,
+}
+
+/// An empty scenario with weight 1 that checks every iteration's assertions.
+impl Default for Scenario {
+    fn default() -> Self {
+        Self {
+            name: String::new(),
+            weight: 1.0,
+            steps: Vec::new(),
+            network_profile: None,
+            tags: HashMap::new(),
+            max_concurrent: None,
+            depends_on: Vec::new(),
+            iterations: None,
+            rps: None,
+            retry: None,
+            assertion_sample_rate: 1.0,
+            client: None,
+            headers: ScenarioHeaders::default(),
+            optional: false,
+            data_source: None,
+        }
+    }
+}
+
+impl Scenario {
+    /// Tags for `step`: the scenario's tags overridden by the step's own.
+    pub fn step_tags(&self, step: &Step) -> HashMap<String, String> {
+        let mut tags = self.tags.clone();
+        tags.extend(step.tags.iter().map(|(k, v)| (k.clone(), v.clone())));
+        tags
+    }
 }
 
 /// Think time configuration for realistic user behavior simulation.
@@ -94,7 +210,7 @@ impl ThinkTime {
                     return *min;
                 }
 
-                let random_ms = rand::thread_rng().gen_range(min_ms..=max_ms);
+                let random_ms = crate::seed::with_rng(|rng| rng.gen_range(min_ms..=max_ms));
                 Duration::from_millis(random_ms)
             }
         }
@@ -111,10 +227,84 @@ impl ThinkTime {
 pub struct StepCache {
     /// How long to reuse the cached variables before making a fresh request.
     pub ttl: Duration,
+
+    /// Optional cache key template (e.g. "${username}"), so each distinct
+    /// value gets its own entry. Without it there is one entry per step.
+    pub key: Option<String>,
+
+    /// Share entries between all workers in the process. When an entry
+    /// expires, one worker refreshes it and the rest wait for its result.
+    pub shared: bool,
+}
+
+impl StepCache {
+    /// The key template with variables substituted, when one is set; part
+    /// of the step's [`crate::executor::SessionKey`].
+    pub fn render_key(&self, context: &ScenarioContext) -> Option<String> {
+        self.key
+            .as_ref()
+            .map(|key| context.substitute_variables(key))
+    }
+}
+
+/// Automatic pagination for a step: after each successful page, the value at
+/// `next_field` is used to request the next page until it is missing, empty
+/// or `null`, or `max_pages` pages have been fetched.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Paginate {
+    /// JSONPath to the next page URL or cursor (e.g. "$.next").
+    pub next_field: String,
+
+    /// Upper bound on pages fetched, including the first.
+    pub max_pages: usize,
+
+    /// Send the extracted value as this query parameter instead of using it
+    /// as the next page's URL.
+    pub cursor_param: Option<String>,
+}
+
+impl Paginate {
+    /// Default `max_pages` when not configured.
+    pub const DEFAULT_MAX_PAGES: usize = 50;
+
+    /// Builds the step that fetches the page identified by `next`.
+    pub fn page_step(&self, step: &Step, next: &str) -> Step {
+        let mut page = step.clone();
+        page.request.path = match &self.cursor_param {
+            Some(param) => with_query_param(&step.request.path, param, next),
+            None => next.to_string(),
+        };
+        page.templates = StepTemplates::default();
+        page
+    }
+}
+
+/// Sets `name=value` on a path's query string, replacing an existing value.
+fn with_query_param(path: &str, name: &str, value: &str) -> String {
+    let (base, query) = path.split_once('?').unwrap_or((path, ""));
+    let mut pairs: Vec<&str> = query
+        .split('&')
+        .filter(|pair| !pair.is_empty() && pair.split('=').next() != Some(name))
+        .collect();
+    let encoded = format!("{}={}", name, encode_query_value(value));
+    pairs.push(&encoded);
+    format!("{}?{}", base, pairs.join("&"))
+}
+
+fn encode_query_value(value: &str) -> String {
+    value
+        .bytes()
+        .map(|b| match b {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => {
+                (b as char).to_string()
+            }
+            _ => format!("%{:02X}", b),
+        })
+        .collect()
 }
 
 /// A single step within a scenario.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Default)]
 pub struct Step {
     /// Descriptive name for this step (e.g., "Login", "Add to Cart")
     pub name: String,
@@ -132,6 +322,10 @@ pub struct Step {
     /// instead of making a real HTTP request on every scenario iteration.
     pub cache: Option<StepCache>,
 
+    /// Revalidate with `If-None-Match` / `If-Modified-Since` from the last
+    /// response's validators (see [`crate::http_cache`]).
+    pub conditional: bool,
+
     /// Optional delay after this step completes (think time)
     ///
     /// Think time simulates realistic user behavior by adding delays between
@@ -158,6 +352,159 @@ pub struct Step {
     /// };
     /// ```
     pub think_time: Option<ThinkTime>,
+
+    /// Tags for this step; override scenario tags with the same key.
+    pub tags: HashMap<String, String>,
+
+    /// Follow next-page links/cursors from the response (see [`Paginate`]).
+    pub paginate: Option<Paginate>,
+
+    /// Most workers allowed to run this step at once, process-wide
+    /// (see [`crate::concurrency_limit`]).
+    pub max_concurrent: Option<usize>,
+
+    /// Response time objective used for Apdex scoring (see [`crate::apdex`]).
+    pub slo: Option<StepSlo>,
+
+    /// Shared store reads (before the request) and writes (after the step
+    /// succeeds); see [`crate::shared_state`].
+    pub shared: Vec<SharedAction>,
+
+    /// Wait for other workers before sending the request
+    /// (see [`crate::rendezvous`]).
+    pub rendezvous: Option<Rendezvous>,
+
+    /// Header that carries an idempotency key: a fresh UUID for each
+    /// execution of the step, resent unchanged by its retries so the target
+    /// can recognize them as the same operation. Also available to the
+    /// request's templates as `${idempotency_key}`.
+    pub idempotency_key: Option<String>,
+
+    /// Open a TCP connection (optionally completing a TLS handshake) instead
+    /// of sending the request (see [`crate::tcp_check`]).
+    pub tcp_check: Option<TcpCheck>,
+
+    /// Status codes that count as success. Empty means any 2xx or 3xx;
+    /// negative tests list the error they expect, e.g. `[404]`.
+    pub expected_status: Vec<u16>,
+
+    /// JSONPath expressions and regexes of the assertions and extractions,
+    /// compiled once and shared by clones (see [`crate::patterns`]).
+    pub patterns: StepPatterns,
+
+    /// Path, host, header and body templates, parsed once and shared by
+    /// clones (see [`crate::substitution`]).
+    pub templates: StepTemplates,
+}
+
+impl Step {
+    /// The step's compiled JSONPath expressions and regexes, compiled on
+    /// the first call.
+    pub fn patterns(&self) -> &CompiledPatterns {
+        self.patterns
+            .get_or_compile(&self.assertions, &self.extractions)
+    }
+
+    /// The step's request templates, parsed on the first call.
+    pub fn templates(&self) -> &RequestTemplates {
+        self.templates.get_or_parse(&self.request)
+    }
+
+    /// Scope of the variable `name` extracted by this step.
+    pub fn variable_scope(&self, name: &str) -> VariableScope {
+        self.extractions
+            .iter()
+            .find(|e| e.name == name)
+            .map_or(VariableScope::Iteration, |e| e.scope)
+    }
+
+    /// Whether a response with `status` counts as a successful step.
+    pub fn status_ok(&self, status: u16) -> bool {
+        if self.expected_status.is_empty() {
+            (200..400).contains(&status)
+        } else {
+            self.expected_status.contains(&status)
+        }
+    }
+}
+
+/// Step retry policy from a scenario's `config.retryCount` / `retryDelay`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RetryPolicy {
+    /// Retries after the first attempt.
+    pub count: u32,
+
+    /// Wait between attempts.
+    pub delay: Duration,
+
+    /// Wait as long as a 429/503 response's `Retry-After` asks instead.
+    pub honor_retry_after: bool,
+
+    /// Longest `Retry-After` wait honored.
+    pub max_retry_after: Duration,
+}
+
+impl RetryPolicy {
+    pub const DEFAULT_DELAY: Duration = Duration::from_secs(1);
+    pub const DEFAULT_MAX_RETRY_AFTER: Duration = Duration::from_secs(60);
+}
+
+/// Synchronization point: workers wait until `count` of them have arrived,
+/// or `timeout` expires, then are released together.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Rendezvous {
+    /// Point name; steps using the same name meet at the same point.
+    pub name: String,
+
+    /// Workers to wait for.
+    pub count: usize,
+
+    /// Longest time to wait for the group to fill up.
+    pub timeout: Duration,
+}
+
+impl Rendezvous {
+    /// Default `timeout` when not configured.
+    pub const DEFAULT_TIMEOUT: Duration = Duration::from_secs(30);
+}
+
+/// A read or write on the shared store made by a step.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SharedAction {
+    /// The operation; `${var}` references in its key and value are
+    /// substituted when the step runs.
+    pub op: StoreOp,
+
+    /// Variable that receives the value read by `get`, `pop` or `incr`
+    /// (default: the key).
+    pub save_as: Option<String>,
+}
+
+impl SharedAction {
+    /// Variable the value read is stored in.
+    pub fn variable(&self) -> &str {
+        self.save_as.as_deref().unwrap_or_else(|| self.op.key())
+    }
+}
+
+/// Response time objective for a step.
+///
+/// Responses within `satisfied_ms` are satisfied, within `tolerating_ms`
+/// tolerating, and slower responses or failed steps frustrated.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct StepSlo {
+    pub satisfied_ms: u64,
+    pub tolerating_ms: u64,
+}
+
+impl StepSlo {
+    /// Apdex convention: tolerating up to four times the satisfied threshold.
+    pub fn new(satisfied_ms: u64) -> Self {
+        Self {
+            satisfied_ms,
+            tolerating_ms: satisfied_ms.saturating_mul(4),
+        }
+    }
 }
 
 /// HTTP request configuration for a step.
@@ -177,6 +524,41 @@ pub struct RequestConfig {
 
     /// Request headers (values can contain variable references)
     pub headers: HashMap<String, String>,
+
+    /// Explicit `Host` header (can contain variable references); overrides
+    /// the URL's host and any `Host` entry in `headers`.
+    pub host: Option<String>,
+
+    /// Encode the body / decode the response as protobuf (see [`crate::protobuf`]).
+    pub protobuf: Option<ProtobufConfig>,
+}
+
+/// `GET /` with no body or headers.
+impl Default for RequestConfig {
+    fn default() -> Self {
+        Self {
+            method: "GET".to_string(),
+            path: "/".to_string(),
+            body: None,
+            body_size: None,
+            headers: HashMap::new(),
+            host: None,
+            protobuf: None,
+        }
+    }
+}
+
+/// Protobuf message types for a step's request and response bodies.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ProtobufConfig {
+    /// Compiled `FileDescriptorSet` containing the message types.
+    pub descriptor_set: PathBuf,
+
+    /// Fully-qualified type the JSON body is encoded as (e.g. "shop.CreateOrder").
+    pub request_message: Option<String>,
+
+    /// Fully-qualified type the response is decoded from into JSON.
+    pub response_message: Option<String>,
 }
 
 /// Extract a variable from the response for use in subsequent steps.
@@ -187,6 +569,29 @@ pub struct VariableExtraction {
 
     /// How to extract the value from the response
     pub extractor: Extractor,
+
+    /// Applied in order to the extracted value before it is stored
+    /// (see [`crate::extractor::parse_transforms`])
+    pub transforms: Vec<Transform>,
+
+    /// How long the variable lives
+    pub scope: VariableScope,
+}
+
+/// Lifetime of an extracted variable.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum VariableScope {
+    /// Dropped when the scenario iteration ends (the default)
+    #[default]
+    Iteration,
+
+    /// Kept by the virtual user (worker) for all of its later iterations,
+    /// e.g. an auth token obtained once per user
+    User,
+
+    /// Shared with every worker on the node from their next iteration on,
+    /// alongside the test's `variables:`
+    Global,
 }
 
 /// Methods for extracting values from HTTP responses.
@@ -195,6 +600,9 @@ pub enum Extractor {
     /// Extract from JSON response using JSONPath (e.g., "$.user.id")
     JsonPath(String),
 
+    /// Extract from XML response using an XPath subset (e.g., "//user/@id")
+    XmlPath(String),
+
     /// Extract using regex with named capture group
     Regex { pattern: String, group: String },
 
@@ -220,6 +628,12 @@ pub enum Assertion {
         expected: Option<String>,
     },
 
+    /// Assert XPath matches and optionally that the first match equals value
+    XmlPath {
+        path: String,
+        expected: Option<String>,
+    },
+
     /// Assert response body contains substring
     BodyContains(String),
 
@@ -228,6 +642,64 @@ pub enum Assertion {
 
     /// Assert response header exists
     HeaderExists(String),
+
+    /// Assert a response header (name case-insensitive) has exactly this
+    /// value; with repeated headers any one value may match
+    HeaderEquals { header: String, expected: String },
+
+    /// Assert a response header value matches a regex
+    HeaderMatches { header: String, pattern: String },
+
+    /// Assert the SHA-256 of the raw response body (hex, case-insensitive)
+    BodySha256 { expected: String },
+
+    /// Assert the response body size in bytes is within bounds (inclusive)
+    BodySizeBytes { min: Option<u64>, max: Option<u64> },
+
+    /// Assert a conditional request was answered `304 Not Modified`; skipped
+    /// when no validators were sent
+    NotModified,
+
+    /// Assert the step's running cache-hit ratio (see `crate::http_cache`)
+    /// is at least `min` once `min_samples` responses have been seen
+    CacheHitRatio { min: f64, min_samples: u64 },
+
+    /// Assert the JSON body equals a stored golden file once the
+    /// `ignore_fields` are removed (see `crate::golden`)
+    MatchesGolden {
+        path: String,
+        ignore_fields: Vec<String>,
+    },
+
+    /// Delegate to a registered assertion plugin (see `crate::plugin`)
+    Plugin {
+        name: String,
+        config: Option<String>,
+    },
+}
+
+impl Assertion {
+    /// True for assertions on the body's checksum or size, which are checked
+    /// against the raw bytes as they stream in.
+    pub fn needs_digest(&self) -> bool {
+        matches!(
+            self,
+            Assertion::BodySha256 { .. } | Assertion::BodySizeBytes { .. }
+        )
+    }
+
+    /// True for assertions that read the body as text.
+    pub fn needs_body_text(&self) -> bool {
+        matches!(
+            self,
+            Assertion::JsonPath { .. }
+                | Assertion::XmlPath { .. }
+                | Assertion::BodyContains(_)
+                | Assertion::BodyMatches(_)
+                | Assertion::MatchesGolden { .. }
+                | Assertion::Plugin { .. }
+        )
+    }
 }
 
 /// Execution context maintained across steps in a scenario.
@@ -239,11 +711,18 @@ pub struct ScenarioContext {
     /// Extracted variables from previous steps
     variables: HashMap<String, String>,
 
+    /// User-scoped variables, restored at the start of every iteration
+    user_variables: HashMap<String, String>,
+
     /// When this scenario execution started
     scenario_start: Instant,
 
     /// Current step index being executed
     current_step: usize,
+
+    /// Whether this iteration runs assertions (see
+    /// [`Scenario::assertion_sample_rate`])
+    validating: bool,
 }
 
 impl ScenarioContext {
@@ -251,8 +730,10 @@ impl ScenarioContext {
     pub fn new() -> Self {
         Self {
             variables: HashMap::new(),
+            user_variables: HashMap::new(),
             scenario_start: Instant::now(),
             current_step: 0,
+            validating: true,
         }
     }
 
@@ -261,6 +742,20 @@ impl ScenarioContext {
         self.variables.insert(name, value);
     }
 
+    /// Store a variable with the given lifetime. User-scoped values survive
+    /// [`reset`](Self::reset); global ones are published to
+    /// [`crate::shared_state::set_global_variable`] for every worker.
+    pub fn set_scoped_variable(&mut self, name: String, value: String, scope: VariableScope) {
+        match scope {
+            VariableScope::Iteration => {}
+            VariableScope::User => {
+                self.user_variables.insert(name.clone(), value.clone());
+            }
+            VariableScope::Global => crate::shared_state::set_global_variable(&name, &value),
+        }
+        self.variables.insert(name, value);
+    }
+
     /// Load variables from a CSV data row (Issue #31).
     ///
     /// This copies all key-value pairs from the data row into the context,
@@ -307,32 +802,10 @@ impl ScenarioContext {
     /// assert_eq!(result, "/users/12345/profile");
     /// ```
     pub fn substitute_variables(&self, input: &str) -> String {
-        let mut result = input.to_string();
-
-        // Replace special ${timestamp} variable with current timestamp
-        if result.contains("${timestamp}") {
-            let timestamp = std::time::SystemTime::now()
-                .duration_since(std::time::UNIX_EPOCH)
-                .unwrap()
-                .as_millis()
-                .to_string();
-            result = result.replace("${timestamp}", &timestamp);
-        }
-
-        // Replace ${var} syntax
-        for (name, value) in &self.variables {
-            let pattern = format!("${{{}}}", name);
-            result = result.replace(&pattern, value);
+        if !input.contains('$') {
+            return input.to_string();
         }
-
-        // Replace $var syntax (for simple variable names)
-        for (name, value) in &self.variables {
-            let pattern = format!("${}", name);
-            // Only replace if not followed by { (to avoid replacing ${var} twice)
-            result = result.replace(&pattern, value);
-        }
-
-        result
+        Template::parse(input).render(self).into_owned()
     }
 
     /// Get elapsed time since scenario started.
@@ -345,17 +818,39 @@ impl ScenarioContext {
         self.current_step
     }
 
+    /// Whether assertions run in this iteration.
+    pub fn is_validating(&self) -> bool {
+        self.validating
+    }
+
+    /// Choose whether assertions run in this iteration.
+    pub fn set_validating(&mut self, validating: bool) {
+        self.validating = validating;
+    }
+
     /// Advance to next step.
     pub fn next_step(&mut self) {
         self.current_step += 1;
     }
 
-    /// Reset context for a new scenario execution.
+    /// Reset context for a new scenario execution. Iteration-scoped
+    /// variables are dropped; user-scoped ones are kept.
     pub fn reset(&mut self) {
-        self.variables.clear();
+        self.variables.clone_from(&self.user_variables);
         self.scenario_start = Instant::now();
         self.current_step = 0;
     }
+
+    /// Start the next iteration of a virtual user: reset, then load
+    /// `globals` underneath the user's own variables.
+    pub fn begin_iteration(&mut self, globals: &HashMap<String, String>) {
+        self.reset();
+        for (name, value) in globals {
+            self.variables
+                .entry(name.clone())
+                .or_insert_with(|| value.clone());
+        }
+    }
 }
 
 impl Default for ScenarioContext {
@@ -368,6 +863,25 @@ impl Default for ScenarioContext {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_step_cache_render_key() {
+        let mut ctx = ScenarioContext::new();
+        ctx.set_variable("username".to_string(), "alice".to_string());
+
+        let per_step = StepCache {
+            ttl: Duration::from_secs(60),
+            key: None,
+            shared: false,
+        };
+        assert_eq!(per_step.render_key(&ctx), None);
+
+        let per_user = StepCache {
+            key: Some("${username}".to_string()),
+            ..per_step
+        };
+        assert_eq!(per_user.render_key(&ctx).as_deref(), Some("alice"));
+    }
+
     #[test]
     fn test_scenario_context_variables() {
         let mut ctx = ScenarioContext::new();
@@ -439,6 +953,22 @@ mod tests {
         assert_eq!(ctx.current_step(), 0);
     }
 
+    #[test]
+    fn test_scoped_variables_across_iterations() {
+        let mut ctx = ScenarioContext::new();
+        ctx.set_scoped_variable("orderId".into(), "o-1".into(), VariableScope::Iteration);
+        ctx.set_scoped_variable("token".into(), "t-1".into(), VariableScope::User);
+
+        let mut globals = HashMap::new();
+        globals.insert("token".to_string(), "default".to_string());
+        globals.insert("tenant".to_string(), "acme".to_string());
+        ctx.begin_iteration(&globals);
+
+        assert_eq!(ctx.get_variable("orderId"), None);
+        assert_eq!(ctx.get_variable("token"), Some(&"t-1".to_string()));
+        assert_eq!(ctx.get_variable("tenant"), Some(&"acme".to_string()));
+    }
+
     #[test]
     fn test_timestamp_substitution() {
         let ctx = ScenarioContext::new();
@@ -469,12 +999,38 @@ mod tests {
                     body: None,
                     body_size: None,
                     headers: HashMap::new(),
+                    protobuf: None,
+                    host: None,
                 },
                 extractions: vec![],
                 assertions: vec![],
                 cache: None,
                 think_time: None,
+                tags: HashMap::new(),
+                paginate: None,
+                max_concurrent: None,
+                slo: None,
+                shared: vec![],
+                rendezvous: None,
+                conditional: false,
+                expected_status: Vec::new(),
+                patterns: Default::default(),
+                templates: Default::default(),
+                idempotency_key: None,
+                tcp_check: None,
             }],
+            network_profile: None,
+            tags: HashMap::new(),
+            max_concurrent: None,
+            depends_on: Vec::new(),
+            iterations: None,
+            retry: None,
+            assertion_sample_rate: 1.0,
+            client: None,
+            headers: Default::default(),
+            optional: false,
+            data_source: None,
+            rps: None,
         };
 
         assert_eq!(scenario.name, "Test Scenario");
@@ -534,4 +1090,57 @@ mod tests {
         let delay = think_time.calculate_delay();
         assert_eq!(delay, Duration::from_secs(5));
     }
+
+    #[test]
+    fn test_paginate_page_step() {
+        let step = Step {
+            name: "List".to_string(),
+            request: RequestConfig {
+                method: "GET".to_string(),
+                path: "/items?limit=10&cursor=old".to_string(),
+                body: None,
+                body_size: None,
+                headers: HashMap::new(),
+                protobuf: None,
+                host: None,
+            },
+            extractions: vec![],
+            assertions: vec![],
+            cache: None,
+            think_time: None,
+            tags: HashMap::new(),
+            paginate: None,
+            max_concurrent: None,
+            slo: None,
+            shared: vec![],
+            rendezvous: None,
+            conditional: false,
+            expected_status: Vec::new(),
+            patterns: Default::default(),
+            templates: Default::default(),
+            idempotency_key: None,
+            tcp_check: None,
+        };
+
+        let cursor = Paginate {
+            next_field: "$.next".to_string(),
+            max_pages: 5,
+            cursor_param: Some("cursor".to_string()),
+        };
+        assert_eq!(
+            cursor.page_step(&step, "a+b/c=").request.path,
+            "/items?limit=10&cursor=a%2Bb%2Fc%3D"
+        );
+
+        let link = Paginate {
+            cursor_param: None,
+            ..cursor
+        };
+        assert_eq!(
+            link.page_step(&step, "https://api.test/items?page=2")
+                .request
+                .path,
+            "https://api.test/items?page=2"
+        );
+    }
 }
@@ -0,0 +1,266 @@
+//! Per-scenario success-rate thresholds (`scenarioThresholds`).
+//!
+//! ```yaml
+//! scenarioThresholds:
+//!   checkout: "successRate > 99.5%"
+//!   browse: "successRate >= 95"
+//! ```
+//!
+//! Each scenario is judged on its own executions, so a 0.1% checkout failure
+//! can't hide inside a 99.9% overall success rate. [`begin`] snapshots
+//! `SCENARIO_EXECUTIONS_TOTAL` when a test starts and [`evaluate`] compares
+//! the deltas since then against each threshold. Results are reported in
+//! their own section of the final report; an ephemeral node whose scenario
+//! misses its threshold exits with `threshold_failed`.
+
+use std::collections::HashMap;
+use std::fmt;
+use std::str::FromStr;
+use std::sync::Mutex;
+
+use thiserror::Error;
+
+use crate::metrics::scenario_execution_totals;
+
+#[derive(Error, Debug, PartialEq)]
+pub enum ThresholdError {
+    #[error("invalid scenario threshold '{0}': expected 'successRate > <percent>' or 'successRate >= <percent>'")]
+    Syntax(String),
+
+    #[error("invalid scenario threshold '{0}': percent must be between 0 and 100")]
+    OutOfRange(String),
+}
+
+/// Minimum success rate, in percent.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SuccessRateThreshold {
+    pub percent: f64,
+    /// `>=` rather than `>`.
+    pub inclusive: bool,
+}
+
+impl SuccessRateThreshold {
+    pub fn passes(&self, rate_percent: f64) -> bool {
+        if self.inclusive {
+            rate_percent >= self.percent
+        } else {
+            rate_percent > self.percent
+        }
+    }
+}
+
+impl FromStr for SuccessRateThreshold {
+    type Err = ThresholdError;
+
+    /// Parses `successRate > 99.5%`; the metric name and `%` are optional.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let syntax = || ThresholdError::Syntax(s.to_string());
+        let rest = s.trim();
+        let rest = rest
+            .strip_prefix("successRate")
+            .unwrap_or(rest)
+            .trim_start();
+        let (inclusive, rest) = if let Some(rest) = rest.strip_prefix(">=") {
+            (true, rest)
+        } else if let Some(rest) = rest.strip_prefix('>') {
+            (false, rest)
+        } else {
+            return Err(syntax());
+        };
+        let rest = rest.trim();
+        let number = rest.strip_suffix('%').unwrap_or(rest).trim();
+        let percent: f64 = number.parse().map_err(|_| syntax())?;
+        if !(0.0..=100.0).contains(&percent) {
+            return Err(ThresholdError::OutOfRange(s.to_string()));
+        }
+        Ok(Self { percent, inclusive })
+    }
+}
+
+impl fmt::Display for SuccessRateThreshold {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let op = if self.inclusive { ">=" } else { ">" };
+        write!(f, "successRate {} {}%", op, self.percent)
+    }
+}
+
+/// A scenario's threshold.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ScenarioThreshold {
+    pub scenario: String,
+    pub success_rate: SuccessRateThreshold,
+}
+
+/// Outcome of one threshold.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ThresholdResult {
+    pub scenario: String,
+    pub threshold: SuccessRateThreshold,
+    pub successes: u64,
+    pub failures: u64,
+    /// `None` when the scenario never ran, which fails the threshold.
+    pub success_rate: Option<f64>,
+    pub passed: bool,
+}
+
+impl ThresholdResult {
+    fn new(threshold: &ScenarioThreshold, successes: u64, failures: u64) -> Self {
+        let executions = successes + failures;
+        let success_rate = (executions > 0).then(|| successes as f64 * 100.0 / executions as f64);
+        Self {
+            scenario: threshold.scenario.clone(),
+            threshold: threshold.success_rate,
+            successes,
+            failures,
+            success_rate,
+            passed: success_rate.is_some_and(|rate| threshold.success_rate.passes(rate)),
+        }
+    }
+}
+
+impl fmt::Display for ThresholdResult {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let verdict = if self.passed { "PASS" } else { "FAIL" };
+        match self.success_rate {
+            Some(rate) => write!(
+                f,
+                "{} {}: {:.3}% ({} of {} succeeded), required {}",
+                verdict,
+                self.scenario,
+                rate,
+                self.successes,
+                self.successes + self.failures,
+                self.threshold
+            ),
+            None => write!(
+                f,
+                "{} {}: no executions, required {}",
+                verdict, self.scenario, self.threshold
+            ),
+        }
+    }
+}
+
+struct ActiveThresholds {
+    thresholds: Vec<ScenarioThreshold>,
+    /// `SCENARIO_EXECUTIONS_TOTAL` (successes, failures) when the test began.
+    baseline: HashMap<String, (u64, u64)>,
+}
+
+lazy_static::lazy_static! {
+    static ref ACTIVE: Mutex<Option<ActiveThresholds>> = Mutex::new(None);
+}
+
+/// Starts evaluating `thresholds` from the current execution counts,
+/// replacing any earlier test's. An empty list clears them.
+pub fn begin(thresholds: Vec<ScenarioThreshold>) {
+    let active = (!thresholds.is_empty()).then(|| ActiveThresholds {
+        thresholds,
+        baseline: scenario_execution_totals(),
+    });
+    *ACTIVE.lock().unwrap_or_else(|e| e.into_inner()) = active;
+}
+
+/// Each active threshold against the executions since [`begin`].
+pub fn evaluate() -> Vec<ThresholdResult> {
+    let active = ACTIVE.lock().unwrap_or_else(|e| e.into_inner());
+    let Some(active) = active.as_ref() else {
+        return Vec::new();
+    };
+    evaluate_against(
+        &active.thresholds,
+        &active.baseline,
+        &scenario_execution_totals(),
+    )
+}
+
+fn evaluate_against(
+    thresholds: &[ScenarioThreshold],
+    baseline: &HashMap<String, (u64, u64)>,
+    current: &HashMap<String, (u64, u64)>,
+) -> Vec<ThresholdResult> {
+    thresholds
+        .iter()
+        .map(|threshold| {
+            let (start_ok, start_failed) = baseline
+                .get(&threshold.scenario)
+                .copied()
+                .unwrap_or_default();
+            let (ok, failed) = current
+                .get(&threshold.scenario)
+                .copied()
+                .unwrap_or_default();
+            ThresholdResult::new(
+                threshold,
+                ok.saturating_sub(start_ok),
+                failed.saturating_sub(start_failed),
+            )
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn threshold(scenario: &str, spec: &str) -> ScenarioThreshold {
+        ScenarioThreshold {
+            scenario: scenario.to_string(),
+            success_rate: spec.parse().unwrap(),
+        }
+    }
+
+    #[test]
+    fn parses_thresholds() {
+        assert_eq!(
+            "successRate > 99.5%".parse::<SuccessRateThreshold>(),
+            Ok(SuccessRateThreshold {
+                percent: 99.5,
+                inclusive: false
+            })
+        );
+        assert_eq!(
+            ">=95".parse::<SuccessRateThreshold>(),
+            Ok(SuccessRateThreshold {
+                percent: 95.0,
+                inclusive: true
+            })
+        );
+        assert!(matches!(
+            "successRate < 1%".parse::<SuccessRateThreshold>(),
+            Err(ThresholdError::Syntax(_))
+        ));
+        assert!(matches!(
+            "successRate > 101".parse::<SuccessRateThreshold>(),
+            Err(ThresholdError::OutOfRange(_))
+        ));
+        assert_eq!(
+            threshold("a", "successRate>=99.9").success_rate.to_string(),
+            "successRate >= 99.9%"
+        );
+    }
+
+    #[test]
+    fn evaluates_deltas_per_scenario() {
+        let thresholds = vec![
+            threshold("checkout", "successRate > 99.5%"),
+            threshold("browse", "successRate >= 95%"),
+            threshold("idle", "successRate > 0"),
+        ];
+        let baseline = HashMap::from([("checkout".to_string(), (1000, 50))]);
+        let current = HashMap::from([
+            ("checkout".to_string(), (1995, 55)),
+            ("browse".to_string(), (95, 5)),
+        ]);
+
+        let results = evaluate_against(&thresholds, &baseline, &current);
+        // checkout: 995 of 1000 since the baseline = 99.5%, not > 99.5%
+        assert_eq!(results[0].successes, 995);
+        assert_eq!(results[0].failures, 5);
+        assert!(!results[0].passed);
+        assert!(results[0].to_string().starts_with("FAIL checkout: 99.500%"));
+        assert!(results[1].passed);
+        assert_eq!(results[2].success_rate, None);
+        assert!(!results[2].passed);
+    }
+}
@@ -0,0 +1,150 @@
+//! Seeded randomness for reproducible runs (`SEED`).
+//!
+//! With `SEED` set, every random choice the tool makes — think-time jitter,
+//! pacing jitter, weighted scenario and template selection, `${uuid}`,
+//! assertion and debug sampling, network emulation jitter and chaos faults —
+//! is drawn from a seeded RNG instead of the thread RNG. Each worker gets its
+//! own RNG, seeded from `SEED`, its kind (scenario name, `worker` or `raw`)
+//! and its task id, so a worker draws the same sequence on every run with
+//! the same seed no matter how the runtime schedules it. Draws made outside
+//! a worker (e.g. assigning scenarios to workers) use one shared seeded RNG.
+//!
+//! Runners started by a supervisor (`RUNNER_PROCESSES`) add their runner
+//! index to the seed, so they don't all draw the same sequences.
+//!
+//! The seed is recorded in the run summary so a failing run can be repeated
+//! with the same `SEED`. Without `SEED`, the thread RNG is used as before.
+
+use std::cell::RefCell;
+use std::future::Future;
+use std::sync::{Mutex, OnceLock};
+
+use rand::rngs::StdRng;
+use rand::{RngCore, SeedableRng};
+use thiserror::Error;
+
+static SEED: OnceLock<Option<u64>> = OnceLock::new();
+
+static SHARED_RNG: OnceLock<Mutex<StdRng>> = OnceLock::new();
+
+tokio::task_local! {
+    static WORKER_RNG: RefCell<StdRng>;
+}
+
+#[derive(Error, Debug, PartialEq)]
+pub enum SeedError {
+    #[error("invalid SEED '{0}': expected an unsigned 64-bit integer")]
+    Invalid(String),
+}
+
+/// Reads `SEED`; `None` when unset or empty.
+pub fn from_env() -> Result<Option<u64>, SeedError> {
+    match std::env::var("SEED") {
+        Ok(value) if !value.trim().is_empty() => parse(&value).map(Some),
+        _ => Ok(None),
+    }
+}
+
+fn parse(value: &str) -> Result<u64, SeedError> {
+    value
+        .trim()
+        .parse()
+        .map_err(|_| SeedError::Invalid(value.to_string()))
+}
+
+/// Fixes the seed for the process; only the first call has an effect.
+pub fn init(seed: Option<u64>) {
+    if SEED.set(seed).is_ok() {
+        if let Some(seed) = seed {
+            let _ = SHARED_RNG.set(Mutex::new(StdRng::seed_from_u64(process_seed(seed))));
+        }
+    }
+}
+
+/// The seed in effect, if any.
+pub fn seed() -> Option<u64> {
+    SEED.get().copied().flatten()
+}
+
+/// `seed` offset by this process's runner index, if it is a runner.
+fn process_seed(seed: u64) -> u64 {
+    crate::runner::RUNNER
+        .as_ref()
+        .map_or(seed, |share| seed.wrapping_add(share.index as u64))
+}
+
+/// Seed of the RNG for worker `task_id` of kind `label`.
+fn worker_seed(seed: u64, label: &str, task_id: usize) -> u64 {
+    // FNV-1a of the label: stable across builds, unlike `DefaultHasher`.
+    let label_hash = label.bytes().fold(0xcbf2_9ce4_8422_2325u64, |hash, b| {
+        (hash ^ b as u64).wrapping_mul(0x0000_0100_0000_01b3)
+    });
+    splitmix64(seed ^ splitmix64(label_hash ^ splitmix64(task_id as u64)))
+}
+
+fn splitmix64(x: u64) -> u64 {
+    let mut z = x.wrapping_add(0x9e37_79b9_7f4a_7c15);
+    z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+    z ^ (z >> 31)
+}
+
+/// Runs a worker's future with its own seeded RNG (when `SEED` is set).
+pub async fn scope<F: Future>(label: &str, task_id: usize, future: F) -> F::Output {
+    match seed() {
+        Some(seed) => {
+            let rng = StdRng::seed_from_u64(worker_seed(process_seed(seed), label, task_id));
+            WORKER_RNG.scope(RefCell::new(rng), future).await
+        }
+        None => future.await,
+    }
+}
+
+/// Calls `f` with the current worker's RNG, the shared seeded RNG outside a
+/// worker, or the thread RNG without `SEED`.
+pub fn with_rng<R>(f: impl FnOnce(&mut dyn RngCore) -> R) -> R {
+    if WORKER_RNG.try_with(|_| ()).is_ok() {
+        return WORKER_RNG.with(|rng| f(&mut *rng.borrow_mut()));
+    }
+    match SHARED_RNG.get() {
+        Some(rng) => f(&mut *rng.lock().unwrap_or_else(|e| e.into_inner())),
+        None => f(&mut rand::thread_rng()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::Rng;
+
+    #[test]
+    fn parses_seed() {
+        assert_eq!(parse(" 42 "), Ok(42));
+        assert_eq!(parse("-1"), Err(SeedError::Invalid("-1".to_string())));
+        assert!(parse("abc").is_err());
+    }
+
+    #[test]
+    fn worker_seeds_differ_by_label_and_task() {
+        let a = worker_seed(7, "checkout", 0);
+        assert_eq!(a, worker_seed(7, "checkout", 0));
+        assert_ne!(a, worker_seed(7, "checkout", 1));
+        assert_ne!(a, worker_seed(7, "browse", 0));
+        assert_ne!(a, worker_seed(8, "checkout", 0));
+    }
+
+    #[tokio::test]
+    async fn worker_rng_is_reproducible() {
+        let draw = || async {
+            let rng = StdRng::seed_from_u64(worker_seed(42, "worker", 3));
+            WORKER_RNG
+                .scope(RefCell::new(rng), async {
+                    (0..5)
+                        .map(|_| with_rng(|rng| rng.gen_range(0..1000)))
+                        .collect::<Vec<u32>>()
+                })
+                .await
+        };
+        assert_eq!(draw().await, draw().await);
+    }
+}
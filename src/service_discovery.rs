@@ -0,0 +1,592 @@
+//! Targets found through service discovery (`consul://` and `k8s://` base
+//! URLs).
+//!
+//! Internal services move between hosts as they are rescheduled, so a static
+//! `TARGET_URL` / `baseUrl` goes stale. The base URL can name a service
+//! instead:
+//!
+//! - `consul://checkout/api` — the passing instances of the Consul service
+//!   `checkout`, from the agent at `CONSUL_HTTP_ADDR` (default
+//!   `http://127.0.0.1:8500`; ACL token from `CONSUL_HTTP_TOKEN`);
+//! - `k8s://checkout.shop:http/api` — the ready endpoints of the Kubernetes
+//!   service `checkout` in namespace `shop` (default: the pod's own), on the
+//!   port named `http` or numbered (default: the first port), read from the
+//!   API server with the pod's service account.
+//!
+//! Instances are sent plain HTTP; `consul+https://` and `k8s+https://` use
+//! HTTPS. The service is resolved when the test starts and, with
+//! `DISCOVERY_REFRESH`, again at that interval. Requests rotate across the
+//! instances (a scenario iteration stays on one), and a failed refresh keeps
+//! the previous instances. Instances go through the target safety guard
+//! ([`crate::safety`]) like any other target: a refused instance fails the
+//! lookup.
+//!
+//! Lookups need the `service-discovery` feature (on by default); without it
+//! discovery URLs are rejected as invalid.
+
+use std::borrow::Cow;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Mutex, RwLock};
+use std::time::Duration;
+
+use thiserror::Error;
+use tokio::task::JoinHandle;
+use tracing::{info, warn};
+
+use crate::metrics::{DISCOVERED_INSTANCES, DISCOVERY_FAILURES_TOTAL};
+use crate::safety::{SafetyError, SafetyPolicy};
+
+#[derive(Error, Debug)]
+pub enum DiscoveryError {
+    #[error("consul:// and k8s:// targets require building with `--features service-discovery`")]
+    FeatureDisabled,
+
+    #[error("invalid service URL '{url}': {message}")]
+    InvalidUrl { url: String, message: String },
+
+    #[error("service discovery request failed: {0}")]
+    Request(#[from] reqwest::Error),
+
+    #[error("failed to read '{path}': {source}")]
+    Read {
+        path: String,
+        source: std::io::Error,
+    },
+
+    #[error("service '{service}' has no port '{port}'")]
+    UnknownPort { service: String, port: String },
+
+    #[error("service '{0}' has no healthy instances")]
+    NoInstances(String),
+
+    #[error("discovered instance refused by safety guard: {0}")]
+    Refused(#[from] SafetyError),
+}
+
+/// Where a service is looked up.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum DiscoverySource {
+    Consul,
+    Kubernetes,
+}
+
+/// A service named by a discovery URL.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ServiceRef {
+    pub source: DiscoverySource,
+    pub service: String,
+    /// Kubernetes namespace; `None` uses the pod's own.
+    pub namespace: Option<String>,
+    /// Kubernetes port name or number; `None` uses the first port.
+    pub port: Option<String>,
+    pub https: bool,
+    /// `scheme://authority` as written, replaced by an instance's address.
+    prefix: String,
+}
+
+impl ServiceRef {
+    /// Parses a discovery URL. Returns `None` for any other URL.
+    pub fn parse(url: &str) -> Result<Option<Self>, DiscoveryError> {
+        let Some((scheme, rest)) = url.split_once("://") else {
+            return Ok(None);
+        };
+        let (source, https) = match scheme.to_ascii_lowercase().as_str() {
+            "consul" => (DiscoverySource::Consul, false),
+            "consul+https" => (DiscoverySource::Consul, true),
+            "k8s" => (DiscoverySource::Kubernetes, false),
+            "k8s+https" => (DiscoverySource::Kubernetes, true),
+            _ => return Ok(None),
+        };
+        if !cfg!(feature = "service-discovery") {
+            return Err(DiscoveryError::FeatureDisabled);
+        }
+        let invalid = |message: &str| DiscoveryError::InvalidUrl {
+            url: url.to_string(),
+            message: message.to_string(),
+        };
+
+        let authority_len = rest.find(['/', '?', '#']).unwrap_or(rest.len());
+        let authority = &rest[..authority_len];
+        let prefix = url[..scheme.len() + 3 + authority_len].to_string();
+        let (name, port) = match authority.split_once(':') {
+            Some((_, "")) => return Err(invalid("empty port")),
+            Some((name, port)) => (name, Some(port.to_string())),
+            None => (authority, None),
+        };
+
+        let (service, namespace) = match source {
+            DiscoverySource::Consul => {
+                if port.is_some() {
+                    return Err(invalid("Consul instances carry their own port"));
+                }
+                (name, None)
+            }
+            DiscoverySource::Kubernetes => match name.split_once('.') {
+                Some((_, "")) => return Err(invalid("empty namespace")),
+                Some((service, namespace)) => (service, Some(namespace.to_string())),
+                None => (name, None),
+            },
+        };
+        if service.is_empty() {
+            return Err(invalid("missing service name"));
+        }
+
+        Ok(Some(Self {
+            source,
+            service: service.to_string(),
+            namespace,
+            port,
+            https,
+            prefix,
+        }))
+    }
+
+    /// Looks up the service's instances as base URLs (`http://10.0.0.7:8080`).
+    #[cfg(feature = "service-discovery")]
+    pub async fn discover(&self) -> Result<Vec<String>, DiscoveryError> {
+        let mut urls = match self.source {
+            DiscoverySource::Consul => self.discover_consul().await?,
+            DiscoverySource::Kubernetes => self.discover_kubernetes().await?,
+        };
+        urls.sort();
+        urls.dedup();
+        if urls.is_empty() {
+            return Err(DiscoveryError::NoInstances(self.service.clone()));
+        }
+        Ok(urls)
+    }
+
+    /// Lookups need the `service-discovery` feature.
+    #[cfg(not(feature = "service-discovery"))]
+    pub async fn discover(&self) -> Result<Vec<String>, DiscoveryError> {
+        Err(DiscoveryError::FeatureDisabled)
+    }
+}
+
+/// Whether `url` is a discovery URL (possibly an invalid one).
+pub fn is_discovery_url(url: &str) -> bool {
+    ServiceRef::parse(url).map_or(true, |service| service.is_some())
+}
+
+/// Consul and Kubernetes lookups.
+#[cfg(feature = "service-discovery")]
+mod lookup {
+    use super::*;
+
+    use std::net::IpAddr;
+
+    use serde::Deserialize;
+
+    const DEFAULT_CONSUL_ADDR: &str = "http://127.0.0.1:8500";
+    const SERVICE_ACCOUNT_DIR: &str = "/var/run/secrets/kubernetes.io/serviceaccount";
+    const LOOKUP_TIMEOUT: Duration = Duration::from_secs(10);
+
+    impl ServiceRef {
+        pub(super) async fn discover_consul(&self) -> Result<Vec<String>, DiscoveryError> {
+            let addr = std::env::var("CONSUL_HTTP_ADDR")
+                .ok()
+                .filter(|a| !a.trim().is_empty())
+                .unwrap_or_else(|| DEFAULT_CONSUL_ADDR.to_string());
+            let addr = if addr.contains("://") {
+                addr
+            } else {
+                format!("http://{}", addr)
+            };
+
+            let client = reqwest::Client::builder().timeout(LOOKUP_TIMEOUT).build()?;
+            let mut request = client
+                .get(format!(
+                    "{}/v1/health/service/{}",
+                    addr.trim_end_matches('/'),
+                    self.service
+                ))
+                .query(&[("passing", "true")]);
+            if let Ok(token) = std::env::var("CONSUL_HTTP_TOKEN") {
+                request = request.header("X-Consul-Token", token);
+            }
+            let entries: Vec<ConsulEntry> =
+                request.send().await?.error_for_status()?.json().await?;
+            Ok(consul_instances(&entries, self.https))
+        }
+
+        pub(super) async fn discover_kubernetes(&self) -> Result<Vec<String>, DiscoveryError> {
+            let namespace = match &self.namespace {
+                Some(namespace) => namespace.clone(),
+                None => read_service_account("namespace")?.trim().to_string(),
+            };
+            let token = read_service_account("token")?;
+            let ca = reqwest::Certificate::from_pem(read_service_account("ca.crt")?.as_bytes())?;
+            let host = std::env::var("KUBERNETES_SERVICE_HOST")
+                .unwrap_or_else(|_| "kubernetes.default.svc".to_string());
+            let port =
+                std::env::var("KUBERNETES_SERVICE_PORT").unwrap_or_else(|_| "443".to_string());
+            let host = match host.parse::<IpAddr>() {
+                Ok(IpAddr::V6(ip)) => format!("[{}]", ip),
+                _ => host,
+            };
+
+            let client = reqwest::Client::builder()
+                .timeout(LOOKUP_TIMEOUT)
+                .add_root_certificate(ca)
+                .build()?;
+            let endpoints: Endpoints = client
+                .get(format!(
+                    "https://{}:{}/api/v1/namespaces/{}/endpoints/{}",
+                    host, port, namespace, self.service
+                ))
+                .bearer_auth(token.trim())
+                .send()
+                .await?
+                .error_for_status()?
+                .json()
+                .await?;
+            kubernetes_instances(&endpoints, self)
+        }
+    }
+
+    fn read_service_account(file: &str) -> Result<String, DiscoveryError> {
+        let path = format!("{}/{}", SERVICE_ACCOUNT_DIR, file);
+        std::fs::read_to_string(&path).map_err(|source| DiscoveryError::Read { path, source })
+    }
+
+    fn instance_url(host: &str, port: u16, https: bool) -> String {
+        let scheme = if https { "https" } else { "http" };
+        match host.parse::<IpAddr>() {
+            Ok(IpAddr::V6(ip)) => format!("{}://[{}]:{}", scheme, ip, port),
+            _ => format!("{}://{}:{}", scheme, host, port),
+        }
+    }
+
+    #[derive(Debug, Deserialize)]
+    pub(super) struct ConsulEntry {
+        #[serde(rename = "Node")]
+        node: ConsulNode,
+        #[serde(rename = "Service")]
+        service: ConsulService,
+    }
+
+    #[derive(Debug, Deserialize)]
+    struct ConsulNode {
+        #[serde(rename = "Address")]
+        address: String,
+    }
+
+    #[derive(Debug, Deserialize)]
+    struct ConsulService {
+        #[serde(rename = "Address", default)]
+        address: String,
+        #[serde(rename = "Port")]
+        port: u16,
+    }
+
+    /// Instance URLs from a Consul health response. A service registered
+    /// without an address runs on its node's address.
+    pub(super) fn consul_instances(entries: &[ConsulEntry], https: bool) -> Vec<String> {
+        entries
+            .iter()
+            .map(|entry| {
+                let host = if entry.service.address.is_empty() {
+                    &entry.node.address
+                } else {
+                    &entry.service.address
+                };
+                instance_url(host, entry.service.port, https)
+            })
+            .collect()
+    }
+
+    #[derive(Debug, Deserialize)]
+    pub(super) struct Endpoints {
+        #[serde(default)]
+        subsets: Vec<EndpointSubset>,
+    }
+
+    #[derive(Debug, Deserialize)]
+    struct EndpointSubset {
+        /// Ready addresses only; `notReadyAddresses` are not sent load.
+        #[serde(default)]
+        addresses: Vec<EndpointAddress>,
+        #[serde(default)]
+        ports: Vec<EndpointPort>,
+    }
+
+    #[derive(Debug, Deserialize)]
+    struct EndpointAddress {
+        ip: String,
+    }
+
+    #[derive(Debug, Deserialize)]
+    struct EndpointPort {
+        #[serde(default)]
+        name: Option<String>,
+        port: u16,
+    }
+
+    /// Instance URLs from a Kubernetes Endpoints object.
+    pub(super) fn kubernetes_instances(
+        endpoints: &Endpoints,
+        service: &ServiceRef,
+    ) -> Result<Vec<String>, DiscoveryError> {
+        let mut urls = Vec::new();
+        for subset in endpoints.subsets.iter().filter(|s| !s.addresses.is_empty()) {
+            let port = match &service.port {
+                None => subset.ports.first().map(|p| p.port),
+                Some(wanted) => subset
+                    .ports
+                    .iter()
+                    .find(|p| {
+                        p.name.as_deref() == Some(wanted.as_str()) || p.port.to_string() == *wanted
+                    })
+                    .map(|p| p.port),
+            };
+            let port = port.ok_or_else(|| DiscoveryError::UnknownPort {
+                service: service.service.clone(),
+                port: service.port.clone().unwrap_or_default(),
+            })?;
+            urls.extend(
+                subset
+                    .addresses
+                    .iter()
+                    .map(|a| instance_url(&a.ip, port, service.https)),
+            );
+        }
+        Ok(urls)
+    }
+}
+
+/// The current instances of the discovered target.
+struct Instances {
+    prefix: String,
+    urls: Vec<String>,
+}
+
+impl Instances {
+    /// `url` with its service prefix replaced by instance `n` (mod the
+    /// instance count), or `None` when `url` is not under the prefix.
+    fn rewrite(&self, url: &str, n: usize) -> Option<String> {
+        let rest = url.strip_prefix(&self.prefix)?;
+        if !(rest.is_empty() || rest.starts_with(['/', '?', '#'])) {
+            return None;
+        }
+        let base = self.urls.get(n % self.urls.len().max(1))?;
+        Some(format!("{}{}", base, rest))
+    }
+}
+
+lazy_static::lazy_static! {
+    static ref INSTANCES: RwLock<Option<Instances>> = RwLock::new(None);
+    static ref REFRESHER: Mutex<Option<JoinHandle<()>>> = Mutex::new(None);
+}
+
+/// Rotates requests across the instances.
+static NEXT: AtomicUsize = AtomicUsize::new(0);
+
+/// Maps a URL under the discovered service to the next instance. Other
+/// URLs, or any URL while no service is discovered, are returned unchanged.
+pub fn rewrite(url: &str) -> Cow<'_, str> {
+    let instances = INSTANCES.read().unwrap_or_else(|e| e.into_inner());
+    match instances
+        .as_ref()
+        .and_then(|i| i.rewrite(url, NEXT.fetch_add(1, Ordering::Relaxed)))
+    {
+        Some(rewritten) => Cow::Owned(rewritten),
+        None => Cow::Borrowed(url),
+    }
+}
+
+fn set_instances(service: &ServiceRef, urls: Vec<String>) {
+    DISCOVERED_INSTANCES.set(urls.len() as f64);
+    *INSTANCES.write().unwrap_or_else(|e| e.into_inner()) = Some(Instances {
+        prefix: service.prefix.clone(),
+        urls,
+    });
+}
+
+/// Looks up `service`'s instances and checks them against `safety`.
+async fn discover_checked(
+    service: &ServiceRef,
+    safety: &SafetyPolicy,
+) -> Result<Vec<String>, DiscoveryError> {
+    let urls = service.discover().await?;
+    safety.check_urls(urls.iter().map(String::as_str)).await?;
+    Ok(urls)
+}
+
+/// Resolves `url` when it names a service and, with a non-zero `refresh`,
+/// keeps re-resolving it. Replaces any previously discovered target; a plain
+/// URL just clears it.
+pub async fn start(
+    url: &str,
+    refresh: Duration,
+    safety: &SafetyPolicy,
+) -> Result<(), DiscoveryError> {
+    stop();
+    let Some(service) = ServiceRef::parse(url)? else {
+        return Ok(());
+    };
+    let urls = discover_checked(&service, safety).await?;
+    info!(
+        service = %service.service,
+        instances = ?urls,
+        "Service discovery: spreading requests across discovered instances"
+    );
+    set_instances(&service, urls.clone());
+    if !refresh.is_zero() {
+        let handle = tokio::spawn(refresh_loop(service, urls, refresh, safety.clone()));
+        *REFRESHER.lock().unwrap_or_else(|e| e.into_inner()) = Some(handle);
+    }
+    Ok(())
+}
+
+/// Stops refreshing and forgets the discovered target.
+pub fn stop() {
+    if let Some(handle) = REFRESHER.lock().unwrap_or_else(|e| e.into_inner()).take() {
+        handle.abort();
+    }
+    *INSTANCES.write().unwrap_or_else(|e| e.into_inner()) = None;
+}
+
+async fn refresh_loop(
+    service: ServiceRef,
+    mut current: Vec<String>,
+    refresh: Duration,
+    safety: SafetyPolicy,
+) {
+    let mut interval = tokio::time::interval(refresh);
+    interval.tick().await;
+    loop {
+        interval.tick().await;
+        match discover_checked(&service, &safety).await {
+            Ok(urls) if urls != current => {
+                info!(
+                    service = %service.service,
+                    instances = ?urls,
+                    "Service discovery: instances changed"
+                );
+                set_instances(&service, urls.clone());
+                current = urls;
+            }
+            Ok(_) => {}
+            Err(e) => {
+                DISCOVERY_FAILURES_TOTAL.inc();
+                warn!(
+                    service = %service.service,
+                    error = %e,
+                    "Service discovery refresh failed; keeping the previous instances"
+                );
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[cfg(feature = "service-discovery")]
+    use lookup::{consul_instances, kubernetes_instances, ConsulEntry, Endpoints};
+
+    #[cfg(feature = "service-discovery")]
+    #[test]
+    fn parses_discovery_urls() {
+        assert_eq!(ServiceRef::parse("https://example.com").unwrap(), None);
+        assert_eq!(ServiceRef::parse("not a url").unwrap(), None);
+
+        let consul = ServiceRef::parse("consul://checkout/api?x=1")
+            .unwrap()
+            .unwrap();
+        assert_eq!(consul.source, DiscoverySource::Consul);
+        assert_eq!(consul.service, "checkout");
+        assert_eq!(consul.prefix, "consul://checkout");
+        assert!(!consul.https);
+
+        let k8s = ServiceRef::parse("k8s+https://checkout.shop:web")
+            .unwrap()
+            .unwrap();
+        assert_eq!(k8s.source, DiscoverySource::Kubernetes);
+        assert_eq!(k8s.service, "checkout");
+        assert_eq!(k8s.namespace.as_deref(), Some("shop"));
+        assert_eq!(k8s.port.as_deref(), Some("web"));
+        assert!(k8s.https);
+
+        assert!(ServiceRef::parse("consul://checkout:8080").is_err());
+        assert!(ServiceRef::parse("k8s:///api").is_err());
+        assert!(ServiceRef::parse("k8s://checkout.:80").is_err());
+        assert!(is_discovery_url("consul://"));
+        assert!(!is_discovery_url("http://checkout"));
+    }
+
+    #[cfg(not(feature = "service-discovery"))]
+    #[test]
+    fn rejects_discovery_urls_without_the_feature() {
+        assert_eq!(ServiceRef::parse("https://example.com").unwrap(), None);
+        assert!(matches!(
+            ServiceRef::parse("consul://checkout"),
+            Err(DiscoveryError::FeatureDisabled)
+        ));
+        assert!(is_discovery_url("k8s://checkout"));
+    }
+
+    #[cfg(feature = "service-discovery")]
+    #[test]
+    fn reads_consul_instances() {
+        let entries: Vec<ConsulEntry> = serde_json::from_str(
+            r#"[
+                {"Node": {"Address": "10.0.0.1"}, "Service": {"Address": "", "Port": 8080}},
+                {"Node": {"Address": "10.0.0.2"}, "Service": {"Address": "fd00::2", "Port": 9090}}
+            ]"#,
+        )
+        .unwrap();
+        assert_eq!(
+            consul_instances(&entries, false),
+            vec!["http://10.0.0.1:8080", "http://[fd00::2]:9090"]
+        );
+    }
+
+    #[cfg(feature = "service-discovery")]
+    #[test]
+    fn reads_kubernetes_endpoints() {
+        let endpoints: Endpoints = serde_json::from_str(
+            r#"{"subsets": [
+                {"addresses": [{"ip": "10.1.0.4"}, {"ip": "10.1.0.5"}],
+                 "ports": [{"name": "metrics", "port": 9100}, {"name": "http", "port": 8080}]},
+                {"notReadyAddresses": [{"ip": "10.1.0.6"}],
+                 "ports": [{"name": "http", "port": 8080}]}
+            ]}"#,
+        )
+        .unwrap();
+        let service = |url: &str| ServiceRef::parse(url).unwrap().unwrap();
+
+        assert_eq!(
+            kubernetes_instances(&endpoints, &service("k8s://web:http")).unwrap(),
+            vec!["http://10.1.0.4:8080", "http://10.1.0.5:8080"]
+        );
+        assert_eq!(
+            kubernetes_instances(&endpoints, &service("k8s+https://web")).unwrap(),
+            vec!["https://10.1.0.4:9100", "https://10.1.0.5:9100"]
+        );
+        assert!(kubernetes_instances(&endpoints, &service("k8s://web:grpc")).is_err());
+    }
+
+    #[test]
+    fn rewrites_urls_under_the_service() {
+        let instances = Instances {
+            prefix: "consul://checkout".to_string(),
+            urls: vec![
+                "http://10.0.0.1:80".to_string(),
+                "http://10.0.0.2:80".to_string(),
+            ],
+        };
+        assert_eq!(
+            instances
+                .rewrite("consul://checkout/cart?id=1", 0)
+                .as_deref(),
+            Some("http://10.0.0.1:80/cart?id=1")
+        );
+        assert_eq!(
+            instances.rewrite("consul://checkout", 3).as_deref(),
+            Some("http://10.0.0.2:80")
+        );
+        assert_eq!(instances.rewrite("consul://checkout2/cart", 0), None);
+        assert_eq!(instances.rewrite("https://example.com/", 0), None);
+    }
+}
@@ -0,0 +1,546 @@
+//! Global variables (`variables:`) and the shared key-value store.
+//!
+//! `variables:` at the top of a YAML plan defines values every scenario can
+//! reference as `${name}`. They are resolved once when the test starts, so
+//! computed values are the same for every worker:
+//!
+//! ```yaml
+//! variables:
+//!   tenant: "acme"
+//!   apiKey: "${env:API_KEY}"    # environment variable (must be set)
+//!   runTag: "load-${timestamp}" # start time in Unix milliseconds
+//!   batch: "${uuid}"            # random UUID
+//! ```
+//!
+//! The shared store lets scenarios hand values to each other, e.g. a login
+//! scenario producing tokens that other scenarios consume. Steps read and
+//! write it with `shared:` actions; every operation is atomic:
+//!
+//! | Action | Effect | Binds a variable |
+//! |--------|--------|------------------|
+//! | `set`  | replace the key's values with `value` | no |
+//! | `push` | append `value` to the key | no |
+//! | `get`  | read the newest value | yes |
+//! | `pop`  | remove and return the oldest value | yes |
+//! | `incr` | add `by` (default 1) to an integer counter | yes |
+//!
+//! The store is process-wide. With `SHARED_STORE_URL` set, operations are
+//! sent to that node's `POST /store` endpoint instead, so every node of a
+//! distributed test can point at one node and share its store (requests
+//! carry `API_AUTH_TOKEN` when it is set).
+
+use std::collections::{HashMap, VecDeque};
+use std::sync::{Arc, Mutex, RwLock};
+use std::time::Duration;
+
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::metrics::SHARED_STORE_OPERATIONS_TOTAL;
+
+/// Timeout for operations sent to a remote store (`SHARED_STORE_URL`).
+const REMOTE_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Errors raised by shared store operations.
+#[derive(Error, Debug, PartialEq)]
+pub enum StoreError {
+    #[error("Shared store key '{0}' has no values")]
+    Empty(String),
+
+    #[error("Shared store key '{key}' holds '{value}', which is not an integer")]
+    NotInteger { key: String, value: String },
+
+    #[error("Shared store at {url} failed: {message}")]
+    Remote { url: String, message: String },
+}
+
+/// Errors raised while resolving `variables:`.
+#[derive(Error, Debug, PartialEq)]
+pub enum VariableError {
+    #[error("Variable '{variable}' references unset environment variable '{env}'")]
+    MissingEnv { variable: String, env: String },
+
+    #[error("Variable '{variable}' has unknown function '${{{function}}}'")]
+    UnknownFunction { variable: String, function: String },
+}
+
+/// A single operation on the shared store.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "op", rename_all = "lowercase")]
+pub enum StoreOp {
+    Set {
+        key: String,
+        value: String,
+    },
+    Push {
+        key: String,
+        value: String,
+    },
+    Get {
+        key: String,
+    },
+    Pop {
+        key: String,
+    },
+    Incr {
+        key: String,
+        #[serde(default = "default_incr")]
+        by: i64,
+    },
+}
+
+fn default_incr() -> i64 {
+    1
+}
+
+impl StoreOp {
+    /// Operation name, as used in YAML and the metrics label.
+    pub fn name(&self) -> &'static str {
+        match self {
+            StoreOp::Set { .. } => "set",
+            StoreOp::Push { .. } => "push",
+            StoreOp::Get { .. } => "get",
+            StoreOp::Pop { .. } => "pop",
+            StoreOp::Incr { .. } => "incr",
+        }
+    }
+
+    pub fn key(&self) -> &str {
+        match self {
+            StoreOp::Set { key, .. }
+            | StoreOp::Push { key, .. }
+            | StoreOp::Get { key }
+            | StoreOp::Pop { key }
+            | StoreOp::Incr { key, .. } => key,
+        }
+    }
+
+    /// True for operations that return a value (`get`, `pop`, `incr`).
+    pub fn is_read(&self) -> bool {
+        matches!(
+            self,
+            StoreOp::Get { .. } | StoreOp::Pop { .. } | StoreOp::Incr { .. }
+        )
+    }
+
+    /// Returns the operation with `substitute` applied to its key and value.
+    pub fn map_strings(&self, substitute: impl Fn(&str) -> String) -> StoreOp {
+        match self {
+            StoreOp::Set { key, value } => StoreOp::Set {
+                key: substitute(key),
+                value: substitute(value),
+            },
+            StoreOp::Push { key, value } => StoreOp::Push {
+                key: substitute(key),
+                value: substitute(value),
+            },
+            StoreOp::Get { key } => StoreOp::Get {
+                key: substitute(key),
+            },
+            StoreOp::Pop { key } => StoreOp::Pop {
+                key: substitute(key),
+            },
+            StoreOp::Incr { key, by } => StoreOp::Incr {
+                key: substitute(key),
+                by: *by,
+            },
+        }
+    }
+}
+
+/// In-memory store: key → values, oldest first.
+#[derive(Default)]
+pub struct SharedStore {
+    entries: Mutex<HashMap<String, VecDeque<String>>>,
+}
+
+impl SharedStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Applies `op`, returning the value read for `get`, `pop` and `incr`.
+    pub fn apply(&self, op: &StoreOp) -> Result<Option<String>, StoreError> {
+        let mut entries = self.entries.lock().unwrap_or_else(|e| e.into_inner());
+        match op {
+            StoreOp::Set { key, value } => {
+                entries.insert(key.clone(), VecDeque::from([value.clone()]));
+                Ok(None)
+            }
+            StoreOp::Push { key, value } => {
+                entries
+                    .entry(key.clone())
+                    .or_default()
+                    .push_back(value.clone());
+                Ok(None)
+            }
+            StoreOp::Get { key } => entries
+                .get(key)
+                .and_then(|values| values.back().cloned())
+                .map(Some)
+                .ok_or_else(|| StoreError::Empty(key.clone())),
+            StoreOp::Pop { key } => entries
+                .get_mut(key)
+                .and_then(|values| values.pop_front())
+                .map(Some)
+                .ok_or_else(|| StoreError::Empty(key.clone())),
+            StoreOp::Incr { key, by } => {
+                let values = entries.entry(key.clone()).or_default();
+                let current = match values.back() {
+                    Some(value) => value.parse::<i64>().map_err(|_| StoreError::NotInteger {
+                        key: key.clone(),
+                        value: value.clone(),
+                    })?,
+                    None => 0,
+                };
+                let next = current.saturating_add(*by).to_string();
+                *values = VecDeque::from([next.clone()]);
+                Ok(Some(next))
+            }
+        }
+    }
+
+    /// Drops every key, e.g. when a new test starts.
+    pub fn clear(&self) {
+        self.entries
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .clear();
+    }
+}
+
+/// Body of a `POST /store` response.
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct StoreResponse {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    value: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    error: Option<String>,
+}
+
+lazy_static::lazy_static! {
+    /// The node's own store, served on `POST /store`.
+    pub static ref GLOBAL_STORE: SharedStore = SharedStore::new();
+
+    /// Resolved `variables:` of the running test.
+    static ref GLOBAL_VARIABLES: RwLock<Arc<HashMap<String, String>>> =
+        RwLock::new(Arc::new(HashMap::new()));
+
+    /// Base URL of the node whose store is used instead of the local one.
+    static ref REMOTE_STORE_URL: Option<String> = std::env::var("SHARED_STORE_URL")
+        .ok()
+        .map(|url| url.trim().trim_end_matches('/').to_string())
+        .filter(|url| !url.is_empty());
+
+    /// Sent as the bearer token to the remote store; nodes of one test
+    /// share `API_AUTH_TOKEN`.
+    static ref REMOTE_TOKEN: Option<String> = std::env::var("API_AUTH_TOKEN").ok();
+
+    static ref REMOTE_CLIENT: reqwest::Client = reqwest::Client::builder()
+        .timeout(REMOTE_TIMEOUT)
+        .build()
+        .unwrap_or_default();
+}
+
+/// Replaces the global variables, e.g. when a new test starts.
+pub fn set_global_variables(variables: HashMap<String, String>) {
+    *GLOBAL_VARIABLES.write().unwrap_or_else(|e| e.into_inner()) = Arc::new(variables);
+}
+
+/// Sets one global variable; iterations started afterwards see it.
+pub fn set_global_variable(name: &str, value: &str) {
+    let mut globals = GLOBAL_VARIABLES.write().unwrap_or_else(|e| e.into_inner());
+    Arc::make_mut(&mut globals).insert(name.to_string(), value.to_string());
+}
+
+/// The global variables of the running test.
+pub fn global_variables() -> Arc<HashMap<String, String>> {
+    GLOBAL_VARIABLES
+        .read()
+        .unwrap_or_else(|e| e.into_inner())
+        .clone()
+}
+
+/// Applies `op` to the shared store: the remote store when
+/// `SHARED_STORE_URL` is set, the local one otherwise.
+pub async fn apply(op: &StoreOp) -> Result<Option<String>, StoreError> {
+    let result = match REMOTE_STORE_URL.as_deref() {
+        Some(url) => apply_remote(url, op).await,
+        None => GLOBAL_STORE.apply(op),
+    };
+    let outcome = match &result {
+        Ok(_) => "ok",
+        Err(StoreError::Empty(_)) => "empty",
+        Err(_) => "error",
+    };
+    SHARED_STORE_OPERATIONS_TOTAL
+        .with_label_values(&[op.name(), outcome])
+        .inc();
+    result
+}
+
+async fn apply_remote(url: &str, op: &StoreOp) -> Result<Option<String>, StoreError> {
+    let remote_error = |message: String| StoreError::Remote {
+        url: url.to_string(),
+        message,
+    };
+    let mut request = REMOTE_CLIENT.post(format!("{}/store", url)).json(op);
+    if let Some(token) = REMOTE_TOKEN.as_deref() {
+        request = request.bearer_auth(token);
+    }
+    let response = request
+        .send()
+        .await
+        .map_err(|e| remote_error(e.to_string()))?;
+    let status = response.status();
+    let body: StoreResponse = response
+        .json()
+        .await
+        .map_err(|e| remote_error(format!("HTTP {}: {}", status.as_u16(), e)))?;
+    match body.error {
+        Some(_) if status == reqwest::StatusCode::NOT_FOUND => {
+            Err(StoreError::Empty(op.key().to_string()))
+        }
+        Some(message) => Err(remote_error(message)),
+        None => Ok(body.value),
+    }
+}
+
+/// Handles a `POST /store` request body against the local store, returning
+/// the HTTP status and JSON response body.
+pub fn handle_request(body: &[u8]) -> (u16, String) {
+    let (status, response) = match serde_json::from_slice::<StoreOp>(body) {
+        Ok(op) => match GLOBAL_STORE.apply(&op) {
+            Ok(value) => (200, StoreResponse { value, error: None }),
+            Err(e) => {
+                let status = if matches!(e, StoreError::Empty(_)) {
+                    404
+                } else {
+                    409
+                };
+                (
+                    status,
+                    StoreResponse {
+                        value: None,
+                        error: Some(e.to_string()),
+                    },
+                )
+            }
+        },
+        Err(e) => (
+            400,
+            StoreResponse {
+                value: None,
+                error: Some(format!("invalid store operation: {}", e)),
+            },
+        ),
+    };
+    (
+        status,
+        serde_json::to_string(&response).unwrap_or_else(|_| "{}".to_string()),
+    )
+}
+
+/// Resolves `variables:` definitions: `${env:NAME}`, `${timestamp}` and
+/// `${uuid}` are replaced; anything else is kept as written.
+pub fn resolve_variables(
+    definitions: &HashMap<String, String>,
+) -> Result<HashMap<String, String>, VariableError> {
+    let timestamp = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_millis()
+        .to_string();
+
+    definitions
+        .iter()
+        .map(|(name, template)| {
+            let mut value = String::with_capacity(template.len());
+            let mut rest = template.as_str();
+            while let Some(start) = rest.find("${") {
+                value.push_str(&rest[..start]);
+                let Some(len) = rest[start..].find('}') else {
+                    break;
+                };
+                let function = &rest[start + 2..start + len];
+                match function {
+                    "timestamp" => value.push_str(&timestamp),
+                    "uuid" => value.push_str(&uuid_v4()),
+                    _ => match function.strip_prefix("env:") {
+                        Some(env) => {
+                            let resolved =
+                                std::env::var(env).map_err(|_| VariableError::MissingEnv {
+                                    variable: name.clone(),
+                                    env: env.to_string(),
+                                })?;
+                            value.push_str(&resolved);
+                        }
+                        None => {
+                            return Err(VariableError::UnknownFunction {
+                                variable: name.clone(),
+                                function: function.to_string(),
+                            })
+                        }
+                    },
+                }
+                rest = &rest[start + len + 1..];
+            }
+            value.push_str(rest);
+            Ok((name.clone(), value))
+        })
+        .collect()
+}
+
+/// Random (version 4) UUID.
+pub(crate) fn uuid_v4() -> String {
+    let mut bytes: [u8; 16] = crate::seed::with_rng(|rng| rng.gen());
+    bytes[6] = (bytes[6] & 0x0f) | 0x40;
+    bytes[8] = (bytes[8] & 0x3f) | 0x80;
+    let hex: String = bytes.iter().map(|b| format!("{:02x}", b)).collect();
+    format!(
+        "{}-{}-{}-{}-{}",
+        &hex[0..8],
+        &hex[8..12],
+        &hex[12..16],
+        &hex[16..20],
+        &hex[20..32]
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn key(k: &str) -> String {
+        k.to_string()
+    }
+
+    #[test]
+    fn push_pop_get_and_set() {
+        let store = SharedStore::new();
+        for token in ["t1", "t2"] {
+            store
+                .apply(&StoreOp::Push {
+                    key: key("tokens"),
+                    value: token.to_string(),
+                })
+                .unwrap();
+        }
+        assert_eq!(
+            store.apply(&StoreOp::Get { key: key("tokens") }),
+            Ok(Some("t2".to_string()))
+        );
+        assert_eq!(
+            store.apply(&StoreOp::Pop { key: key("tokens") }),
+            Ok(Some("t1".to_string()))
+        );
+        assert_eq!(
+            store.apply(&StoreOp::Pop { key: key("tokens") }),
+            Ok(Some("t2".to_string()))
+        );
+        assert_eq!(
+            store.apply(&StoreOp::Pop { key: key("tokens") }),
+            Err(StoreError::Empty(key("tokens")))
+        );
+
+        store
+            .apply(&StoreOp::Set {
+                key: key("mode"),
+                value: "a".to_string(),
+            })
+            .unwrap();
+        store
+            .apply(&StoreOp::Set {
+                key: key("mode"),
+                value: "b".to_string(),
+            })
+            .unwrap();
+        assert_eq!(
+            store.apply(&StoreOp::Pop { key: key("mode") }),
+            Ok(Some("b".to_string()))
+        );
+    }
+
+    #[test]
+    fn concurrent_increments_are_atomic() {
+        let store = Arc::new(SharedStore::new());
+        let threads: Vec<_> = (0..8)
+            .map(|_| {
+                let store = store.clone();
+                std::thread::spawn(move || {
+                    for _ in 0..1000 {
+                        store
+                            .apply(&StoreOp::Incr {
+                                key: key("n"),
+                                by: 1,
+                            })
+                            .unwrap();
+                    }
+                })
+            })
+            .collect();
+        for t in threads {
+            t.join().unwrap();
+        }
+        assert_eq!(
+            store.apply(&StoreOp::Get { key: key("n") }),
+            Ok(Some("8000".to_string()))
+        );
+
+        store
+            .apply(&StoreOp::Set {
+                key: key("n"),
+                value: "x".to_string(),
+            })
+            .unwrap();
+        assert!(matches!(
+            store.apply(&StoreOp::Incr {
+                key: key("n"),
+                by: 1
+            }),
+            Err(StoreError::NotInteger { .. })
+        ));
+    }
+
+    #[test]
+    fn handles_store_requests() {
+        let (status, body) = handle_request(br#"{"op":"incr","key":"handler-test","by":5}"#);
+        assert_eq!(status, 200);
+        assert_eq!(body, r#"{"value":"5"}"#);
+
+        let (status, _) = handle_request(br#"{"op":"pop","key":"handler-missing"}"#);
+        assert_eq!(status, 404);
+
+        let (status, _) = handle_request(br#"{"op":"drop","key":"x"}"#);
+        assert_eq!(status, 400);
+    }
+
+    #[test]
+    fn resolves_variables() {
+        std::env::set_var("SHARED_STATE_TEST_KEY", "secret");
+        let definitions = HashMap::from([
+            (key("plain"), key("acme")),
+            (key("apiKey"), key("Bearer ${env:SHARED_STATE_TEST_KEY}")),
+            (key("batch"), key("${uuid}")),
+            (key("started"), key("t-${timestamp}")),
+        ]);
+        let resolved = resolve_variables(&definitions).unwrap();
+        assert_eq!(resolved["plain"], "acme");
+        assert_eq!(resolved["apiKey"], "Bearer secret");
+        assert_eq!(resolved["batch"].len(), 36);
+        assert_eq!(&resolved["batch"][14..15], "4");
+        assert!(resolved["started"][2..].parse::<u128>().is_ok());
+
+        let missing = HashMap::from([(key("x"), key("${env:SHARED_STATE_TEST_UNSET}"))]);
+        assert!(matches!(
+            resolve_variables(&missing),
+            Err(VariableError::MissingEnv { .. })
+        ));
+        let unknown = HashMap::from([(key("x"), key("${random}"))]);
+        assert!(matches!(
+            resolve_variables(&unknown),
+            Err(VariableError::UnknownFunction { .. })
+        ));
+    }
+}
@@ -0,0 +1,254 @@
+//! Smoke mode (`MODE=smoke`): check that a plan works before running it
+//! under load.
+//!
+//! Every scenario runs exactly once, one after another, as a single virtual
+//! user. The load model, worker count and think times are ignored, every
+//! request and response is logged (as with `DEBUG_SAMPLE_RATE=1`), and a
+//! report lists each step with its status, latency and every failed
+//! assertion. The process exits with code 3 if any scenario failed, or 4 if
+//! the target never responded (see [`crate::app_error`]).
+
+use std::collections::HashMap;
+use std::str::FromStr;
+
+use crate::executor::{ScenarioExecutor, ScenarioResult, SessionStore};
+use crate::scenario::{RequestConfig, Scenario, ScenarioContext, Step};
+use crate::shared_state;
+
+/// How the process runs a test plan.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum RunMode {
+    /// Generate load according to the load model.
+    #[default]
+    Load,
+    /// Run every scenario once and report (see module docs).
+    Smoke,
+}
+
+impl FromStr for RunMode {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "" | "load" => Ok(RunMode::Load),
+            "smoke" | "debug" => Ok(RunMode::Smoke),
+            other => Err(format!("expected load or smoke (got '{}')", other)),
+        }
+    }
+}
+
+/// Results of a smoke run, one per scenario.
+#[derive(Debug)]
+pub struct SmokeReport {
+    pub results: Vec<ScenarioResult>,
+}
+
+impl SmokeReport {
+    /// True when every scenario succeeded.
+    pub fn passed(&self) -> bool {
+        self.results.iter().all(|r| r.success)
+    }
+
+    /// True when requests were attempted but none got an HTTP response.
+    pub fn unreachable(&self) -> bool {
+        let mut steps = self
+            .results
+            .iter()
+            .flat_map(|r| &r.steps)
+            .filter(|s| !s.cache_hit)
+            .peekable();
+        steps.peek().is_some() && steps.all(|s| s.status_code.is_none())
+    }
+
+    /// Formats the report as a human-readable listing.
+    pub fn format(&self) -> String {
+        let mut output = String::from("\nSmoke test results\n");
+        output.push_str(&"-".repeat(70));
+        output.push('\n');
+        for result in &self.results {
+            output.push_str(&format!(
+                "{} {} ({} ms)\n",
+                mark(result.success),
+                result.scenario_name,
+                result.total_time_ms
+            ));
+            for step in &result.steps {
+                let status = step
+                    .status_code
+                    .map_or_else(|| "-".to_string(), |code| code.to_string());
+                output.push_str(&format!(
+                    "    {} {:<30} status {:>3}  {:>6} ms  assertions {}/{}\n",
+                    mark(step.success),
+                    step.step_name,
+                    status,
+                    step.response_time_ms,
+                    step.assertions_passed,
+                    step.assertions_passed + step.assertions_failed
+                ));
+                for failure in &step.assertion_failures {
+                    output.push_str(&format!("        assertion failed: {}\n", failure));
+                }
+                if let Some(error) = step
+                    .error
+                    .as_ref()
+                    .filter(|_| step.assertion_failures.is_empty())
+                {
+                    output.push_str(&format!("        error: {}\n", error));
+                }
+            }
+        }
+        output.push_str(&"-".repeat(70));
+        output.push('\n');
+        let passed = self.results.iter().filter(|r| r.success).count();
+        output.push_str(&format!(
+            "{} of {} scenario(s) passed\n",
+            passed,
+            self.results.len()
+        ));
+        output
+    }
+}
+
+fn mark(success: bool) -> &'static str {
+    if success {
+        "PASS"
+    } else {
+        "FAIL"
+    }
+}
+
+/// Runs every scenario once, sequentially, without think times and with
+/// every assertion checked.
+pub async fn run(executor: &ScenarioExecutor, scenarios: &[Scenario]) -> SmokeReport {
+    let mut results = Vec::with_capacity(scenarios.len());
+    for scenario in scenarios {
+        let mut scenario = scenario.clone();
+        scenario.assertion_sample_rate = 1.0;
+        for step in &mut scenario.steps {
+            step.think_time = None;
+        }
+        let mut context = ScenarioContext::new();
+        context.load_data_row(&shared_state::global_variables());
+        let result = executor
+            .execute(&scenario, &mut context, &mut SessionStore::new())
+            .await;
+        results.push(result);
+    }
+    SmokeReport { results }
+}
+
+/// One-step scenario for a plain `TARGET_URL` configuration.
+pub fn single_request_scenario(url: &str, method: &str, body: Option<String>) -> Scenario {
+    let mut headers = HashMap::new();
+    if body.is_some() {
+        headers.insert("Content-Type".to_string(), "application/json".to_string());
+    }
+    Scenario {
+        name: "TARGET_URL".to_string(),
+        weight: 1.0,
+        steps: vec![Step {
+            name: format!("{} {}", method.to_uppercase(), url),
+            request: RequestConfig {
+                method: method.to_uppercase(),
+                path: url.to_string(),
+                body,
+                body_size: None,
+                headers,
+                protobuf: None,
+                host: None,
+            },
+            extractions: vec![],
+            assertions: vec![],
+            cache: None,
+            think_time: None,
+            tags: HashMap::new(),
+            paginate: None,
+            max_concurrent: None,
+            slo: None,
+            shared: vec![],
+            rendezvous: None,
+            conditional: false,
+            expected_status: Vec::new(),
+            patterns: Default::default(),
+            templates: Default::default(),
+            idempotency_key: None,
+            tcp_check: None,
+        }],
+        network_profile: None,
+        tags: HashMap::new(),
+        max_concurrent: None,
+        depends_on: Vec::new(),
+        iterations: None,
+        retry: None,
+        assertion_sample_rate: 1.0,
+        client: None,
+        headers: Default::default(),
+        optional: false,
+        rps: None,
+        data_source: None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::executor::StepResult;
+
+    #[test]
+    fn parses_run_mode() {
+        assert_eq!("".parse::<RunMode>().unwrap(), RunMode::Load);
+        assert_eq!("Smoke".parse::<RunMode>().unwrap(), RunMode::Smoke);
+        assert!("soak".parse::<RunMode>().is_err());
+    }
+
+    #[test]
+    fn report_lists_failed_assertions() {
+        let step = |name: &str, success: bool, failures: Vec<String>| StepResult {
+            step_name: name.to_string(),
+            success,
+            status_code: Some(200),
+            response_time_ms: 12,
+            error: (!success).then(|| "1 assertion(s) failed".to_string()),
+            assertions_passed: 1,
+            assertions_failed: failures.len(),
+            assertion_failures: failures,
+            ..Default::default()
+        };
+        let report = SmokeReport {
+            results: vec![ScenarioResult {
+                scenario_name: "Checkout".to_string(),
+                success: false,
+                steps: vec![
+                    step("Login", true, vec![]),
+                    step("Pay", false, vec!["expected 201, got 200".to_string()]),
+                ],
+                total_time_ms: 30,
+                steps_completed: 1,
+                failed_at_step: Some(1),
+            }],
+        };
+
+        assert!(!report.passed());
+        assert!(!report.unreachable());
+        let text = report.format();
+        assert!(text.contains("FAIL Checkout"));
+        assert!(text.contains("PASS Login"));
+        assert!(text.contains("assertion failed: expected 201, got 200"));
+        assert!(text.contains("0 of 1 scenario(s) passed"));
+
+        let mut down = report;
+        for step in &mut down.results[0].steps {
+            step.status_code = None;
+        }
+        assert!(down.unreachable());
+    }
+
+    #[test]
+    fn single_request_scenario_targets_full_url() {
+        let scenario = single_request_scenario("http://localhost:3000/health", "get", None);
+        let step = &scenario.steps[0];
+        assert_eq!(step.request.method, "GET");
+        assert_eq!(step.request.path, "http://localhost:3000/health");
+        assert!(step.request.headers.is_empty());
+    }
+}
@@ -0,0 +1,137 @@
+//! Presenting a different TLS server name than the address connected to
+//! (`TLS_SERVER_NAME`).
+//!
+//! Testing an origin server behind a CDN means connecting to the origin while
+//! presenting the production hostname. `RESOLVE_TARGET_ADDR` covers this when
+//! the target URL already uses the production hostname; `TLS_SERVER_NAME`
+//! covers the other direction: the target URL names the connect address (an
+//! IP or internal hostname) and the server name is given separately.
+//!
+//! The target URL's host is replaced by the server name, so it is what the
+//! client sends as SNI, verifies the certificate against and sends as the
+//! `Host` header, while [`SniResolver`] resolves that name to the original
+//! host's addresses. A step can still send a different `Host` header with
+//! `request.host`.
+
+use std::net::SocketAddr;
+
+use reqwest::dns::{Addrs, Name, Resolve, Resolving};
+use reqwest::Url;
+
+use crate::ip_family::IpFamily;
+
+/// The server name presented on connections to `connect_host`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SniOverride {
+    /// Name sent as SNI and used for certificate verification.
+    pub server_name: String,
+    /// Host actually connected to: an IP literal or a hostname.
+    pub connect_host: String,
+}
+
+/// Rewrites `url` to use `server_name` as its host and returns the
+/// rewritten URL with the override that maps it back to the original host.
+pub fn apply(url: &str, server_name: &str) -> Result<(String, SniOverride), String> {
+    let server_name = server_name.trim();
+    if server_name.is_empty() || server_name.parse::<std::net::IpAddr>().is_ok() {
+        return Err(format!(
+            "'{}' is not a hostname; SNI requires a DNS name",
+            server_name
+        ));
+    }
+    let mut parsed = Url::parse(url).map_err(|e| format!("invalid target URL '{}': {}", url, e))?;
+    let connect_host = parsed
+        .host_str()
+        .ok_or_else(|| format!("target URL '{}' has no host", url))?
+        .trim_matches(|c| c == '[' || c == ']')
+        .to_string();
+    parsed
+        .set_host(Some(server_name))
+        .map_err(|e| format!("invalid server name '{}': {}", server_name, e))?;
+    Ok((
+        parsed.to_string(),
+        SniOverride {
+            server_name: parsed.host_str().unwrap_or(server_name).to_string(),
+            connect_host,
+        },
+    ))
+}
+
+/// DNS resolver that resolves the override's server name to its connect
+/// host, honouring `IP_FAMILY`. Other names resolve normally.
+pub struct SniResolver {
+    sni: SniOverride,
+    family: IpFamily,
+}
+
+impl SniResolver {
+    pub fn new(sni: SniOverride, family: IpFamily) -> Self {
+        Self { sni, family }
+    }
+}
+
+impl Resolve for SniResolver {
+    fn resolve(&self, name: Name) -> Resolving {
+        let host = if name.as_str().eq_ignore_ascii_case(&self.sni.server_name) {
+            self.sni.connect_host.clone()
+        } else {
+            name.as_str().to_string()
+        };
+        let family = self.family;
+        Box::pin(async move {
+            let addrs: Vec<SocketAddr> = tokio::net::lookup_host((host.as_str(), 0))
+                .await?
+                .filter(|addr| family.allows(addr.ip()))
+                .collect();
+            if addrs.is_empty() {
+                let err: Box<dyn std::error::Error + Send + Sync> =
+                    format!("'{}' has no {} addresses", host, family).into();
+                return Err(err);
+            }
+            let addrs: Addrs = Box::new(addrs.into_iter());
+            Ok(addrs)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::str::FromStr;
+
+    #[test]
+    fn rewrites_url_host() {
+        let (url, sni) = apply("https://203.0.113.5:8443/api?x=1", "www.example.com").unwrap();
+        assert_eq!(url, "https://www.example.com:8443/api?x=1");
+        assert_eq!(
+            sni,
+            SniOverride {
+                server_name: "www.example.com".to_string(),
+                connect_host: "203.0.113.5".to_string(),
+            }
+        );
+
+        let (_, sni) = apply("https://[2001:db8::1]/", "www.example.com").unwrap();
+        assert_eq!(sni.connect_host, "2001:db8::1");
+
+        assert!(apply("https://origin.internal/", "10.0.0.1").is_err());
+        assert!(apply("not a url", "www.example.com").is_err());
+    }
+
+    #[tokio::test]
+    async fn resolves_server_name_to_connect_host() {
+        let resolver = SniResolver::new(
+            SniOverride {
+                server_name: "www.example.com".to_string(),
+                connect_host: "127.0.0.1".to_string(),
+            },
+            IpFamily::Auto,
+        );
+        let addrs: Vec<SocketAddr> = resolver
+            .resolve(Name::from_str("www.example.com").unwrap())
+            .await
+            .unwrap()
+            .collect();
+        assert_eq!(addrs, vec!["127.0.0.1:0".parse().unwrap()]);
+    }
+}
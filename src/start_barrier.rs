@@ -0,0 +1,120 @@
+//! Synchronized start across nodes (`START_AT`).
+//!
+//! Each node of a cluster starts generating load when it happens to finish
+//! booting, so a few seconds of pod scheduling skew smear a ramp that should
+//! rise in step everywhere. With `START_AT` (or YAML `config.startAt`) set to
+//! a wall-clock instant, every node waits for that instant before starting
+//! its workers. Pushing the same config to each node's `POST /config` then
+//! starts the whole cluster together; nothing distributes it for you.
+//!
+//! The test's start is pinned to the barrier instant itself, not to when the
+//! node got there: a node that arrives late joins the load curve at the
+//! point the others have reached, so ramps and phase boundaries of the load
+//! model line up across the cluster. The lateness is logged and exported as
+//! `start_lateness_seconds`. Alignment is only as good as the nodes' clock
+//! synchronization (NTP).
+
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use tokio::time::Instant;
+use tracing::{info, warn};
+
+use crate::metrics::START_LATENESS_SECONDS;
+
+/// Barriers further in the future than this are almost certainly a mistake
+/// (wrong unit, wrong year), so they are logged loudly.
+const FAR_FUTURE: Duration = Duration::from_secs(3600);
+
+/// Parses a start instant: RFC 3339 (`2026-10-16T12:00:00Z`) or Unix time
+/// in milliseconds.
+pub fn parse_start_at(value: &str) -> Result<SystemTime, String> {
+    let value = value.trim();
+    if let Ok(millis) = value.parse::<u64>() {
+        return Ok(UNIX_EPOCH + Duration::from_millis(millis));
+    }
+    let datetime = chrono::DateTime::parse_from_rfc3339(value).map_err(|e| {
+        format!(
+            "expected an RFC 3339 timestamp or Unix milliseconds (got '{}'): {}",
+            value, e
+        )
+    })?;
+    let millis = u64::try_from(datetime.timestamp_millis())
+        .map_err(|_| format!("start time '{}' is before 1970", value))?;
+    Ok(UNIX_EPOCH + Duration::from_millis(millis))
+}
+
+/// Time to wait until `start_at`, or how late `now` already is.
+fn offset(start_at: SystemTime, now: SystemTime) -> Result<Duration, Duration> {
+    match start_at.duration_since(now) {
+        Ok(wait) => Ok(wait),
+        Err(late) => Err(late.duration()),
+    }
+}
+
+/// Waits until `start_at` and returns the monotonic instant matching it,
+/// which is in the past when this node arrived late.
+pub async fn wait(start_at: SystemTime) -> Instant {
+    match offset(start_at, SystemTime::now()) {
+        Ok(wait) => {
+            if wait > FAR_FUTURE {
+                warn!(
+                    wait_secs = wait.as_secs(),
+                    "START_AT is more than an hour away; check its value"
+                );
+            }
+            info!(
+                wait_secs = wait.as_secs_f64(),
+                "Waiting for the synchronized start (START_AT)"
+            );
+            tokio::time::sleep(wait).await;
+            START_LATENESS_SECONDS.set(0.0);
+            Instant::now()
+        }
+        Err(late) => {
+            warn!(
+                late_secs = late.as_secs_f64(),
+                "Reached START_AT late; joining the load curve where the cluster is"
+            );
+            START_LATENESS_SECONDS.set(late.as_secs_f64());
+            let now = Instant::now();
+            now.checked_sub(late).unwrap_or(now)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_rfc3339_and_unix_millis() {
+        let expected = UNIX_EPOCH + Duration::from_millis(1_791_000_000_500);
+        assert_eq!(parse_start_at("1791000000500"), Ok(expected));
+        assert_eq!(parse_start_at("2026-10-03T04:00:00.5Z"), Ok(expected));
+        assert_eq!(
+            parse_start_at(" 2026-10-03T06:00:00.5+02:00 "),
+            Ok(expected)
+        );
+        assert!(parse_start_at("tomorrow").is_err());
+        assert!(parse_start_at("1969-12-31T23:59:59Z").is_err());
+    }
+
+    #[test]
+    fn offset_waits_or_reports_lateness() {
+        let start = UNIX_EPOCH + Duration::from_secs(100);
+        assert_eq!(
+            offset(start, UNIX_EPOCH + Duration::from_secs(90)),
+            Ok(Duration::from_secs(10))
+        );
+        assert_eq!(
+            offset(start, UNIX_EPOCH + Duration::from_secs(103)),
+            Err(Duration::from_secs(3))
+        );
+    }
+
+    #[tokio::test]
+    async fn late_start_is_pinned_to_the_barrier() {
+        let start = wait(SystemTime::now() - Duration::from_secs(2)).await;
+        assert!(start.elapsed() >= Duration::from_secs(2));
+    }
+}
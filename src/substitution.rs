@@ -0,0 +1,241 @@
+//! Variable substitution in request paths, headers and bodies.
+//!
+//! Substituting by repeated `String::replace` allocated a new string for
+//! every header, path and body of every request, even when the value had no
+//! variables in it. Instead, each value is parsed once into a [`Template`] of
+//! literal and variable segments: rendering a template without variables
+//! borrows the original string, and rendering one with variables builds the
+//! result in a single pass. A step's templates are parsed the first time it
+//! runs (eagerly when a YAML plan is loaded) and shared by every clone of the
+//! step, like [`crate::patterns`].
+//!
+//! Syntax:
+//! - `${name}` — the variable `name`; `${timestamp}` is the current Unix time
+//!   in milliseconds.
+//! - `$name` — the variable named by the longest run of ASCII letters,
+//!   digits and `_` after the `$`; a `$` followed by a digit (`$5`) is left
+//!   as written.
+//!
+//! References to unknown variables are left as written.
+
+use std::borrow::Cow;
+use std::fmt;
+use std::ops::Range;
+use std::sync::{Arc, OnceLock};
+
+use crate::scenario::{RequestConfig, ScenarioContext};
+
+#[derive(Debug, Clone, PartialEq)]
+enum Segment {
+    /// Text copied as is (byte range of the source).
+    Literal(Range<usize>),
+    /// A variable reference: byte range of the whole reference and of the name.
+    Variable {
+        reference: Range<usize>,
+        name: Range<usize>,
+    },
+    /// `${timestamp}`
+    Timestamp,
+}
+
+/// A string split into literal and variable segments.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Template {
+    source: String,
+    segments: Vec<Segment>,
+}
+
+impl Template {
+    /// Split `source` into segments.
+    pub fn parse(source: &str) -> Self {
+        let bytes = source.as_bytes();
+        let mut segments = Vec::new();
+        let mut literal_start = 0;
+        let mut i = 0;
+        while i < bytes.len() {
+            if bytes[i] != b'$' {
+                i += 1;
+                continue;
+            }
+            let (segment, end) = if bytes.get(i + 1) == Some(&b'{') {
+                match source[i + 2..].find('}') {
+                    Some(len) if &source[i + 2..i + 2 + len] == "timestamp" => {
+                        (Segment::Timestamp, i + 2 + len + 1)
+                    }
+                    Some(len) => {
+                        let end = i + 2 + len + 1;
+                        (
+                            Segment::Variable {
+                                reference: i..end,
+                                name: i + 2..i + 2 + len,
+                            },
+                            end,
+                        )
+                    }
+                    None => break,
+                }
+            } else {
+                let len = bytes[i + 1..]
+                    .iter()
+                    .take_while(|b| b.is_ascii_alphanumeric() || **b == b'_')
+                    .count();
+                if len == 0 || bytes[i + 1].is_ascii_digit() {
+                    i += 1;
+                    continue;
+                }
+                let end = i + 1 + len;
+                (
+                    Segment::Variable {
+                        reference: i..end,
+                        name: i + 1..end,
+                    },
+                    end,
+                )
+            };
+            if literal_start < i {
+                segments.push(Segment::Literal(literal_start..i));
+            }
+            segments.push(segment);
+            i = end;
+            literal_start = end;
+        }
+        if literal_start < source.len() {
+            segments.push(Segment::Literal(literal_start..source.len()));
+        }
+        Self {
+            source: source.to_string(),
+            segments,
+        }
+    }
+
+    /// The string the template was parsed from.
+    pub fn source(&self) -> &str {
+        &self.source
+    }
+
+    /// True when there is nothing to substitute.
+    pub fn is_static(&self) -> bool {
+        self.segments
+            .iter()
+            .all(|s| matches!(s, Segment::Literal(_)))
+    }
+
+    /// Substitute `context`'s variables. Static templates are borrowed.
+    pub fn render(&self, context: &ScenarioContext) -> Cow<'_, str> {
+        if self.is_static() {
+            return Cow::Borrowed(&self.source);
+        }
+        let mut output = String::with_capacity(self.source.len() + 32);
+        for segment in &self.segments {
+            match segment {
+                Segment::Literal(range) => output.push_str(&self.source[range.clone()]),
+                Segment::Variable { reference, name } => {
+                    match context.get_variable(&self.source[name.clone()]) {
+                        Some(value) => output.push_str(value),
+                        None => output.push_str(&self.source[reference.clone()]),
+                    }
+                }
+                Segment::Timestamp => {
+                    let millis = std::time::SystemTime::now()
+                        .duration_since(std::time::UNIX_EPOCH)
+                        .unwrap()
+                        .as_millis();
+                    output.push_str(&millis.to_string());
+                }
+            }
+        }
+        Cow::Owned(output)
+    }
+}
+
+/// Templates for every substitutable part of a request.
+#[derive(Debug)]
+pub struct RequestTemplates {
+    pub path: Template,
+    pub host: Option<Template>,
+    pub headers: Vec<(String, Template)>,
+    pub body: Option<Template>,
+}
+
+impl RequestTemplates {
+    /// Parse the path, host, header values and body of `request`.
+    pub fn parse(request: &RequestConfig) -> Self {
+        Self {
+            path: Template::parse(&request.path),
+            host: request.host.as_deref().map(Template::parse),
+            headers: request
+                .headers
+                .iter()
+                .map(|(name, value)| (name.clone(), Template::parse(value)))
+                .collect(),
+            body: request.body.as_deref().map(Template::parse),
+        }
+    }
+}
+
+/// A step's [`RequestTemplates`], parsed on first use and shared by clones.
+#[derive(Clone, Default)]
+pub struct StepTemplates(Arc<OnceLock<RequestTemplates>>);
+
+impl StepTemplates {
+    /// The parsed templates, parsing `request` if this is the first call.
+    pub fn get_or_parse(&self, request: &RequestConfig) -> &RequestTemplates {
+        self.0.get_or_init(|| RequestTemplates::parse(request))
+    }
+}
+
+impl fmt::Debug for StepTemplates {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.0.get() {
+            Some(_) => f.write_str("StepTemplates(parsed)"),
+            None => f.write_str("StepTemplates(not parsed)"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn context() -> ScenarioContext {
+        let mut ctx = ScenarioContext::new();
+        ctx.set_variable("id".to_string(), "42".to_string());
+        ctx.set_variable("user_id".to_string(), "u-7".to_string());
+        ctx
+    }
+
+    #[test]
+    fn static_templates_are_borrowed() {
+        let template = Template::parse(r#"{"price": "$5", "tag": "a$"}"#);
+        assert!(template.is_static());
+        assert!(matches!(template.render(&context()), Cow::Borrowed(_)));
+    }
+
+    #[test]
+    fn substitutes_both_syntaxes() {
+        let ctx = context();
+        let template = Template::parse("/users/${user_id}/items/$id?x=$user_id.");
+        assert!(!template.is_static());
+        assert_eq!(template.render(&ctx), "/users/u-7/items/42?x=u-7.");
+        assert_eq!(template.source(), "/users/${user_id}/items/$id?x=$user_id.");
+    }
+
+    #[test]
+    fn unknown_and_unterminated_references_are_kept() {
+        let ctx = context();
+        assert_eq!(
+            Template::parse("$missing ${missing} $identity").render(&ctx),
+            "$missing ${missing} $identity"
+        );
+        assert_eq!(Template::parse("$id ${id").render(&ctx), "42 ${id");
+    }
+
+    #[test]
+    fn substitutes_timestamp() {
+        let rendered = Template::parse("user-${timestamp}@example.com")
+            .render(&ScenarioContext::new())
+            .into_owned();
+        let millis = &rendered["user-".len()..rendered.len() - "@example.com".len()];
+        assert!(millis.parse::<u128>().is_ok(), "{}", rendered);
+    }
+}
@@ -0,0 +1,491 @@
+//! Supervisor mode (`RUNNER_PROCESSES=N`): shard one host's load across N
+//! runner processes.
+//!
+//! A single tokio runtime stops scaling well before the core count of a big
+//! machine. With `RUNNER_PROCESSES` set, the process becomes a supervisor: it
+//! starts N copies of itself ("runners") with the same arguments and
+//! environment, serves one merged `/metrics` on `METRICS_ADDR`, and exits
+//! when every runner has (with the first failing runner's exit code).
+//!
+//! Each runner gets `RUNNER_INDEX` and `RUNNER_COUNT` and runs 1/N of the
+//! configured load: target and override RPS, `HARD_MAX_RPS`, workers and
+//! `MAX_TOTAL_REQUESTS` are divided, so the host as a whole runs the
+//! configured totals. On Linux runners are pinned to disjoint sets of the
+//! cores the supervisor may use (`RUNNER_PIN_CORES=false` turns that off).
+//! Runner `i` serves its own metrics and control API on loopback only, on
+//! port `METRICS_ADDR + 1 + i` and `CLUSTER_HEALTH_ADDR + 1 + i`.
+//!
+//! Merging: counters and histograms are summed across runners; gauges,
+//! summaries and untyped metrics keep one series per runner, with a `runner`
+//! label.
+
+use std::collections::HashMap;
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use tokio::process::Command;
+use tokio::task::JoinSet;
+use tracing::{error, info, warn};
+
+use crate::addr;
+use crate::app_error::AppError;
+use crate::metrics::{start_metrics_server_for, MetricsServerConfig, MetricsSource};
+use crate::runner::RUNNER;
+
+/// Environment variables of the metrics endpoint that runners don't inherit:
+/// the supervisor scrapes them over plain loopback HTTP.
+const RUNNER_CLEARED_ENV: [&str; 5] = [
+    "RUNNER_PROCESSES",
+    "METRICS_TLS_CERT",
+    "METRICS_TLS_KEY",
+    "METRICS_BASIC_AUTH",
+    "METRICS_BEARER_TOKEN",
+];
+
+/// Number of runners requested by `RUNNER_PROCESSES` (a number, or `auto`
+/// for one per available core). `None` when unset, 0 or 1, and in runners.
+pub fn processes_from_env() -> Result<Option<usize>, String> {
+    if RUNNER.is_some() {
+        return Ok(None);
+    }
+    let Ok(raw) = std::env::var("RUNNER_PROCESSES") else {
+        return Ok(None);
+    };
+    let processes = match raw.trim() {
+        "" => 0,
+        "auto" => std::thread::available_parallelism().map_or(1, |n| n.get()),
+        n => n
+            .parse::<usize>()
+            .map_err(|_| format!("RUNNER_PROCESSES must be a number or auto (got '{}')", n))?,
+    };
+    Ok((processes > 1).then_some(processes))
+}
+
+/// Splits `cpus` into `runners` disjoint sets; with more runners than
+/// CPUs, runners share CPUs round-robin.
+pub fn core_sets(cpus: &[usize], runners: usize) -> Vec<Vec<usize>> {
+    if cpus.is_empty() {
+        return vec![Vec::new(); runners];
+    }
+    (0..runners)
+        .map(|i| {
+            if runners >= cpus.len() {
+                vec![cpus[i % cpus.len()]]
+            } else {
+                cpus[i * cpus.len() / runners..(i + 1) * cpus.len() / runners].to_vec()
+            }
+        })
+        .collect()
+}
+
+/// CPUs this process may run on.
+#[cfg(target_os = "linux")]
+fn allowed_cpus() -> Vec<usize> {
+    // Safety: `set` is a plain bitmask that sched_getaffinity fills in.
+    unsafe {
+        let mut set: libc::cpu_set_t = std::mem::zeroed();
+        if libc::sched_getaffinity(0, std::mem::size_of::<libc::cpu_set_t>(), &mut set) != 0 {
+            return Vec::new();
+        }
+        (0..libc::CPU_SETSIZE as usize)
+            .filter(|cpu| libc::CPU_ISSET(*cpu, &set))
+            .collect()
+    }
+}
+
+/// Restricts the spawned process to `cpus`.
+#[cfg(target_os = "linux")]
+fn pin_to_cpus(command: &mut Command, cpus: Vec<usize>) {
+    if cpus.is_empty() {
+        return;
+    }
+    // Safety: the hook only builds a bitmask and makes one syscall, both
+    // async-signal-safe, between fork and exec.
+    unsafe {
+        command.pre_exec(move || {
+            let mut set: libc::cpu_set_t = std::mem::zeroed();
+            for cpu in &cpus {
+                libc::CPU_SET(*cpu, &mut set);
+            }
+            if libc::sched_setaffinity(0, std::mem::size_of::<libc::cpu_set_t>(), &set) != 0 {
+                return Err(std::io::Error::last_os_error());
+            }
+            Ok(())
+        });
+    }
+}
+
+/// Scrapes the runners' `/metrics` and merges them.
+pub struct RunnerScraper {
+    client: reqwest::Client,
+    urls: Vec<String>,
+}
+
+impl RunnerScraper {
+    pub fn new(urls: Vec<String>) -> Self {
+        Self {
+            client: reqwest::Client::new(),
+            urls,
+        }
+    }
+
+    /// Merged exposition of every runner that answered.
+    pub async fn scrape(&self) -> String {
+        let mut expositions = Vec::with_capacity(self.urls.len());
+        for (index, url) in self.urls.iter().enumerate() {
+            let body = match self
+                .client
+                .get(url)
+                .timeout(Duration::from_secs(5))
+                .send()
+                .await
+            {
+                Ok(response) => response.text().await,
+                Err(e) => Err(e),
+            };
+            match body {
+                Ok(text) => expositions.push((index, text)),
+                Err(e) => warn!(runner = index, error = %e, "Failed to scrape runner metrics"),
+            }
+        }
+        merge_expositions(&expositions)
+    }
+}
+
+/// One metric family being merged.
+struct Family {
+    name: String,
+    help: Option<String>,
+    kind: String,
+    samples: Vec<(String, f64)>,
+    index: HashMap<String, usize>,
+}
+
+impl Family {
+    fn summed(&self) -> bool {
+        self.kind == "counter" || self.kind == "histogram"
+    }
+}
+
+/// Merges Prometheus text expositions, one per `(runner index, text)`.
+pub fn merge_expositions(expositions: &[(usize, String)]) -> String {
+    let mut families: Vec<Family> = Vec::new();
+    let mut by_name: HashMap<String, usize> = HashMap::new();
+    let mut family_index = |families: &mut Vec<Family>, name: &str| -> usize {
+        *by_name.entry(name.to_string()).or_insert_with(|| {
+            families.push(Family {
+                name: name.to_string(),
+                help: None,
+                kind: "untyped".to_string(),
+                samples: Vec::new(),
+                index: HashMap::new(),
+            });
+            families.len() - 1
+        })
+    };
+
+    for (runner, text) in expositions {
+        let mut current: Option<usize> = None;
+        for line in text.lines() {
+            let line = line.trim();
+            if let Some(rest) = line.strip_prefix("# HELP ") {
+                let (name, help) = rest.split_once(' ').unwrap_or((rest, ""));
+                let i = family_index(&mut families, name);
+                families[i].help.get_or_insert_with(|| help.to_string());
+                continue;
+            }
+            if let Some(rest) = line.strip_prefix("# TYPE ") {
+                let (name, kind) = rest.split_once(' ').unwrap_or((rest, "untyped"));
+                let i = family_index(&mut families, name);
+                families[i].kind = kind.trim().to_string();
+                current = Some(i);
+                continue;
+            }
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let Some((series, value)) = split_sample(line) else {
+                continue;
+            };
+            let sample_name = series.split('{').next().unwrap_or(series);
+            let i = match current {
+                Some(i) if sample_name.starts_with(families[i].name.as_str()) => i,
+                _ => family_index(&mut families, sample_name),
+            };
+            let family = &mut families[i];
+            let key = if family.summed() {
+                series.to_string()
+            } else {
+                with_runner_label(series, *runner)
+            };
+            match family.index.get(&key) {
+                Some(&at) => family.samples[at].1 += value,
+                None => {
+                    family.index.insert(key.clone(), family.samples.len());
+                    family.samples.push((key, value));
+                }
+            }
+        }
+    }
+
+    let mut output = String::new();
+    for family in families.iter().filter(|f| !f.samples.is_empty()) {
+        if let Some(help) = &family.help {
+            output.push_str(&format!("# HELP {} {}\n", family.name, help));
+        }
+        output.push_str(&format!("# TYPE {} {}\n", family.name, family.kind));
+        for (series, value) in &family.samples {
+            output.push_str(&format!("{} {}\n", series, format_value(*value)));
+        }
+    }
+    output
+}
+
+/// Splits a sample line into its series (name and labels) and value.
+fn split_sample(line: &str) -> Option<(&str, f64)> {
+    let end = match line.find('{') {
+        Some(open) => {
+            // Find the closing brace outside quoted label values.
+            let mut in_quotes = false;
+            let mut escaped = false;
+            let mut close = None;
+            for (i, c) in line[open..].char_indices() {
+                match c {
+                    _ if escaped => escaped = false,
+                    '\\' if in_quotes => escaped = true,
+                    '"' => in_quotes = !in_quotes,
+                    '}' if !in_quotes => {
+                        close = Some(open + i + 1);
+                        break;
+                    }
+                    _ => {}
+                }
+            }
+            close?
+        }
+        None => line.find(char::is_whitespace)?,
+    };
+    let value = line[end..].split_whitespace().next()?;
+    let value = match value {
+        "+Inf" => f64::INFINITY,
+        "-Inf" => f64::NEG_INFINITY,
+        v => v.parse().ok()?,
+    };
+    Some((&line[..end], value))
+}
+
+/// Adds `runner="<index>"` to a series' labels.
+fn with_runner_label(series: &str, runner: usize) -> String {
+    match series.strip_suffix('}') {
+        Some(open) if open.ends_with('{') => format!("{}runner=\"{}\"}}", open, runner),
+        Some(open) => format!("{},runner=\"{}\"}}", open, runner),
+        None => format!("{}{{runner=\"{}\"}}", series, runner),
+    }
+}
+
+fn format_value(value: f64) -> String {
+    if value.is_nan() {
+        "NaN".to_string()
+    } else if value == f64::INFINITY {
+        "+Inf".to_string()
+    } else if value == f64::NEG_INFINITY {
+        "-Inf".to_string()
+    } else {
+        value.to_string()
+    }
+}
+
+/// Port `offset` above `addr`'s, on the loopback address of its family.
+fn runner_addr(addr: SocketAddr, offset: usize) -> Result<SocketAddr, AppError> {
+    u16::try_from(offset)
+        .ok()
+        .and_then(|offset| addr.port().checked_add(offset))
+        .map(|port| {
+            let loopback = match addr.ip() {
+                IpAddr::V4(_) => IpAddr::V4(Ipv4Addr::LOCALHOST),
+                IpAddr::V6(_) => IpAddr::V6(Ipv6Addr::LOCALHOST),
+            };
+            SocketAddr::new(loopback, port)
+        })
+        .ok_or_else(|| {
+            AppError::Config(format!(
+                "RUNNER_PROCESSES: no free port {} above {}",
+                offset, addr
+            ))
+        })
+}
+
+/// Runs as supervisor of `processes` runners until they have all exited.
+pub async fn run(processes: usize) -> Result<(), AppError> {
+    let metrics_config = MetricsServerConfig::from_env()
+        .map_err(|e| AppError::Config(format!("metrics server configuration: {}", e)))?;
+    let control_addr: SocketAddr = std::env::var("CLUSTER_HEALTH_ADDR")
+        .ok()
+        .and_then(|a| addr::parse_listen_addr(&a).ok())
+        .unwrap_or_else(|| SocketAddr::from(([0, 0, 0, 0], 8080)));
+    let exe = std::env::current_exe()
+        .map_err(|e| AppError::Internal(format!("cannot locate own executable: {}", e)))?;
+    let args: Vec<_> = std::env::args_os().skip(1).collect();
+    let pin = std::env::var("RUNNER_PIN_CORES").map_or(true, |v| v != "false" && v != "0");
+    #[cfg(target_os = "linux")]
+    let cpu_sets = core_sets(&allowed_cpus(), processes);
+
+    let mut runners = JoinSet::new();
+    let mut pids = Vec::new();
+    let mut urls = Vec::new();
+    // `index` also numbers the runner, not just the CPU set.
+    #[allow(clippy::needless_range_loop)]
+    for index in 0..processes {
+        let metrics_addr = runner_addr(metrics_config.addr, index + 1)?;
+        let mut command = Command::new(&exe);
+        command
+            .args(&args)
+            .env("RUNNER_INDEX", index.to_string())
+            .env("RUNNER_COUNT", processes.to_string())
+            .env("METRICS_ADDR", metrics_addr.to_string())
+            .env(
+                "CLUSTER_HEALTH_ADDR",
+                runner_addr(control_addr, index + 1)?.to_string(),
+            )
+            .kill_on_drop(true);
+        for name in RUNNER_CLEARED_ENV {
+            command.env_remove(name);
+        }
+        #[cfg(target_os = "linux")]
+        if pin {
+            pin_to_cpus(&mut command, cpu_sets[index].clone());
+        }
+        #[cfg(not(target_os = "linux"))]
+        let _ = pin;
+        let mut child = command
+            .spawn()
+            .map_err(|e| AppError::Internal(format!("failed to start runner {}: {}", index, e)))?;
+        pids.extend(child.id());
+        urls.push(format!("http://{}/metrics", metrics_addr));
+        runners.spawn(async move { (index, child.wait().await) });
+    }
+    info!(
+        runners = processes,
+        metrics_addr = %metrics_config.addr,
+        pinned = pin && cfg!(target_os = "linux"),
+        "Supervisor started runner processes"
+    );
+
+    let source = MetricsSource::Runners(Arc::new(RunnerScraper::new(urls)));
+    tokio::spawn(async move {
+        if let Err(e) = start_metrics_server_for(metrics_config, source).await {
+            error!(error = %e, "Metrics server failed");
+        }
+    });
+    let pids = Arc::new(Mutex::new(pids));
+    tokio::spawn(forward_shutdown(pids));
+
+    let mut failure = None;
+    while let Some(joined) = runners.join_next().await {
+        let (index, status) = joined.map_err(|e| AppError::Internal(e.to_string()))?;
+        let status = status.map_err(|e| AppError::Internal(format!("runner {}: {}", index, e)))?;
+        info!(runner = index, status = %status, "Runner exited");
+        if !status.success() && failure.is_none() {
+            failure = Some(AppError::from_exit_code(
+                status.code(),
+                format!("runner {} exited with {}", index, status),
+            ));
+        }
+    }
+    failure.map_or(Ok(()), Err)
+}
+
+/// Passes SIGINT/SIGTERM on to the runners as SIGTERM, so they stop the way
+/// a single process would.
+async fn forward_shutdown(pids: Arc<Mutex<Vec<u32>>>) {
+    #[cfg(unix)]
+    {
+        use tokio::signal::unix::{signal, SignalKind};
+        let Ok(mut terminate) = signal(SignalKind::terminate()) else {
+            return;
+        };
+        tokio::select! {
+            _ = terminate.recv() => {}
+            _ = tokio::signal::ctrl_c() => {}
+        }
+        info!("Supervisor stopping runners");
+        for pid in pids.lock().unwrap_or_else(|e| e.into_inner()).iter() {
+            // Safety: plain kill(2) on a child's pid.
+            unsafe {
+                libc::kill(*pid as libc::pid_t, libc::SIGTERM);
+            }
+        }
+    }
+    #[cfg(not(unix))]
+    let _ = pids;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn runners_listen_on_loopback_of_the_same_family() {
+        let addr = |s: &str| s.parse::<SocketAddr>().unwrap();
+        assert_eq!(
+            runner_addr(addr("0.0.0.0:8080"), 2).unwrap(),
+            addr("127.0.0.1:8082")
+        );
+        assert_eq!(
+            runner_addr(addr("[::]:9090"), 1).unwrap(),
+            addr("[::1]:9091")
+        );
+        assert!(runner_addr(addr("0.0.0.0:65535"), 1).is_err());
+    }
+
+    #[test]
+    fn splits_cores_between_runners() {
+        let cpus: Vec<usize> = (0..8).collect();
+        assert_eq!(
+            core_sets(&cpus, 3),
+            vec![vec![0, 1], vec![2, 3, 4], vec![5, 6, 7]]
+        );
+        assert_eq!(core_sets(&cpus[..2], 3), vec![vec![0], vec![1], vec![0]]);
+    }
+
+    #[test]
+    fn merges_runner_metrics() {
+        let runner = |requests: u64, in_flight: u64| {
+            format!(
+                "# HELP rl_requests_total Total requests\n\
+                 # TYPE rl_requests_total counter\n\
+                 rl_requests_total{{node_id=\"n\",run_id=\"r\"}} {}\n\
+                 # HELP rl_latency Latency\n\
+                 # TYPE rl_latency histogram\n\
+                 rl_latency_bucket{{le=\"0.1\"}} {}\n\
+                 rl_latency_bucket{{le=\"+Inf\"}} {}\n\
+                 rl_latency_sum 1.5\n\
+                 rl_latency_count {}\n\
+                 # HELP rl_in_flight In flight\n\
+                 # TYPE rl_in_flight gauge\n\
+                 rl_in_flight {}\n",
+                requests, requests, requests, requests, in_flight
+            )
+        };
+        let merged = merge_expositions(&[(0, runner(3, 1)), (1, runner(4, 2))]);
+
+        assert!(merged.contains("# TYPE rl_requests_total counter\n"));
+        assert!(merged.contains("rl_requests_total{node_id=\"n\",run_id=\"r\"} 7\n"));
+        assert!(merged.contains("rl_latency_bucket{le=\"+Inf\"} 7\n"));
+        assert!(merged.contains("rl_latency_sum 3\n"));
+        assert!(merged.contains("rl_latency_count 7\n"));
+        assert!(merged.contains("rl_in_flight{runner=\"0\"} 1\n"));
+        assert!(merged.contains("rl_in_flight{runner=\"1\"} 2\n"));
+        assert_eq!(merged.matches("# HELP rl_requests_total").count(), 1);
+    }
+
+    #[test]
+    fn parses_quoted_label_values() {
+        let (series, value) = split_sample(r#"m{error="a } b \" c"} 2"#).unwrap();
+        assert_eq!(series, r#"m{error="a } b \" c"}"#);
+        assert_eq!(value, 2.0);
+        assert_eq!(with_runner_label("m{}", 3), "m{runner=\"3\"}");
+        assert_eq!(format_value(f64::INFINITY), "+Inf");
+    }
+}
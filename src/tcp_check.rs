@@ -0,0 +1,344 @@
+//! TCP health-ping steps (`tcpCheck:` / `ping:`).
+//!
+//! ```yaml
+//! steps:
+//!   - name: "Database port"
+//!     ping: "db.internal:5432"
+//!   - name: "Edge TLS"
+//!     tcpCheck:
+//!       address: "edge.example.com:443"
+//!       tls: true
+//!       timeout: "2s"
+//! ```
+//!
+//! A liveness probe inside a scenario doesn't need HTTP: the step resolves
+//! the address, opens a TCP connection and, with `tls: true`, completes a
+//! TLS handshake, then closes the connection. Its response time is the time
+//! all of that took. Without an `address` the step checks the base URL's
+//! host and port.
+//!
+//! Checks follow the HTTP client's routing and trust: `RESOLVE_TARGET_ADDR`
+//! entries, `IP_FAMILY`, `SKIP_TLS_VERIFY` and the mTLS identity, as set by
+//! [`init`]. The resolved host and addresses must pass the target safety
+//! guard ([`crate::safety`]) before anything is connected.
+
+use std::net::SocketAddr;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use rustls::pki_types::ServerName;
+use thiserror::Error;
+use tokio::net::TcpStream;
+use tokio::time::{timeout, Instant};
+use tokio_rustls::TlsConnector;
+use tracing::warn;
+
+use crate::addr::{self, ResolveOverride};
+use crate::client::ClientConfig;
+use crate::ip_family::IpFamily;
+use crate::safety::{SafetyError, SafetyPolicy};
+use crate::tls::{self, TlsError};
+
+/// A step's TCP check.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TcpCheck {
+    /// Host and port to connect to; `None` uses the base URL's.
+    pub target: Option<(String, u16)>,
+
+    /// Complete a TLS handshake after connecting.
+    pub tls: bool,
+
+    /// Server name for SNI and certificate verification; defaults to the host.
+    pub server_name: Option<String>,
+
+    /// Longest the whole check may take.
+    pub timeout: Duration,
+}
+
+impl TcpCheck {
+    pub const DEFAULT_TIMEOUT: Duration = Duration::from_secs(5);
+}
+
+/// Why a check failed.
+#[derive(Error, Debug)]
+pub enum TcpCheckError {
+    #[error("DNS resolution of '{host}' failed: {message}")]
+    Resolve { host: String, message: String },
+
+    #[error("TCP connect to {addr} failed: {message}")]
+    Connect { addr: String, message: String },
+
+    #[error("TLS handshake with '{server_name}' failed: {message}")]
+    Tls {
+        server_name: String,
+        message: String,
+    },
+
+    #[error("timed out after {}ms", .0.as_millis())]
+    TimedOut(Duration),
+
+    #[error("refused by safety guard: {0}")]
+    Refused(#[from] SafetyError),
+}
+
+/// A passed check.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TcpCheckOutcome {
+    /// The address that accepted the connection.
+    pub addr: SocketAddr,
+    /// Resolution and TCP connect.
+    pub connect: Duration,
+    /// The TLS handshake, for `tls: true`.
+    pub tls: Option<Duration>,
+}
+
+/// Routing and trust shared with the HTTP client.
+#[derive(Clone)]
+pub struct TcpCheckSettings {
+    pub tls: Arc<rustls::ClientConfig>,
+    pub ip_family: IpFamily,
+    pub overrides: Vec<ResolveOverride>,
+    pub safety: SafetyPolicy,
+}
+
+impl TcpCheckSettings {
+    /// The settings of clients built from `config`.
+    pub fn from_client_config(config: &ClientConfig) -> Result<Self, TlsError> {
+        let mut tls = tls::client_tls_config(config)?;
+        tls.alpn_protocols.clear();
+        // An invalid value already fails building the HTTP client.
+        let overrides = config
+            .resolve_target_addr
+            .as_deref()
+            .filter(|raw| !raw.is_empty())
+            .and_then(|raw| addr::parse_resolve_overrides(raw).ok())
+            .unwrap_or_default();
+        Ok(Self {
+            tls: Arc::new(tls),
+            ip_family: config.ip_family,
+            overrides,
+            safety: SafetyPolicy::default(),
+        })
+    }
+
+    /// Native root certificates, any address family, no overrides and no
+    /// safety lists.
+    pub fn defaults() -> Result<Self, TlsError> {
+        Ok(Self {
+            tls: Arc::new(tls::default_client_tls_config()?),
+            ip_family: IpFamily::Auto,
+            overrides: Vec::new(),
+            safety: SafetyPolicy::default(),
+        })
+    }
+}
+
+lazy_static::lazy_static! {
+    static ref SETTINGS: Mutex<Option<TcpCheckSettings>> = Mutex::new(None);
+}
+
+/// Makes checks use the routing and trust of clients built from `config`,
+/// refusing targets `safety` refuses.
+pub fn init(config: &ClientConfig, safety: &SafetyPolicy) {
+    match TcpCheckSettings::from_client_config(config) {
+        Ok(mut settings) => {
+            settings.safety = safety.clone();
+            *SETTINGS.lock().unwrap_or_else(|e| e.into_inner()) = Some(settings);
+        }
+        Err(e) => warn!(error = %e, "TCP checks fall back to default TLS settings"),
+    }
+}
+
+/// The settings from [`init`], or [`TcpCheckSettings::defaults`].
+pub fn settings() -> Result<TcpCheckSettings, TlsError> {
+    let mut guard = SETTINGS.lock().unwrap_or_else(|e| e.into_inner());
+    if let Some(settings) = guard.as_ref() {
+        return Ok(settings.clone());
+    }
+    let settings = TcpCheckSettings::defaults()?;
+    *guard = Some(settings.clone());
+    Ok(settings)
+}
+
+async fn resolve(
+    host: &str,
+    port: u16,
+    settings: &TcpCheckSettings,
+) -> Result<Vec<SocketAddr>, TcpCheckError> {
+    // Like the HTTP client, overrides replace the address but not the port.
+    let pinned = addr::resolved_addrs(&settings.overrides, host);
+    if !pinned.is_empty() {
+        return Ok(pinned
+            .into_iter()
+            .map(|addr| SocketAddr::new(addr.ip(), port))
+            .collect());
+    }
+    let resolve_error = |message: String| TcpCheckError::Resolve {
+        host: host.to_string(),
+        message,
+    };
+    let addrs: Vec<SocketAddr> = tokio::net::lookup_host((host, port))
+        .await
+        .map_err(|e| resolve_error(e.to_string()))?
+        .filter(|addr| settings.ip_family.allows(addr.ip()))
+        .collect();
+    if addrs.is_empty() {
+        return Err(resolve_error(format!(
+            "no {} addresses",
+            settings.ip_family
+        )));
+    }
+    Ok(addrs)
+}
+
+async fn connect_and_handshake(
+    check: &TcpCheck,
+    host: &str,
+    port: u16,
+    settings: &TcpCheckSettings,
+) -> Result<TcpCheckOutcome, TcpCheckError> {
+    let started = Instant::now();
+    let addrs = resolve(host, port, settings).await?;
+    let ips: Vec<_> = addrs.iter().map(SocketAddr::ip).collect();
+    settings.safety.check(host, &ips)?;
+
+    // The first address that accepts a connection
+    let mut last_error = String::new();
+    let mut connected = None;
+    for addr in &addrs {
+        match TcpStream::connect(addr).await {
+            Ok(stream) => {
+                connected = Some((*addr, stream));
+                break;
+            }
+            Err(e) => last_error = e.to_string(),
+        }
+    }
+    let Some((addr, stream)) = connected else {
+        let addr = match addrs.as_slice() {
+            [addr] => addr.to_string(),
+            addrs => format!("{} addresses of '{}'", addrs.len(), host),
+        };
+        return Err(TcpCheckError::Connect {
+            addr,
+            message: last_error,
+        });
+    };
+    let connect = started.elapsed();
+
+    let tls = if check.tls {
+        let started = Instant::now();
+        let server_name = check.server_name.as_deref().unwrap_or(host).to_string();
+        let tls_error = |message: String| TcpCheckError::Tls {
+            server_name: server_name.clone(),
+            message,
+        };
+        let name =
+            ServerName::try_from(server_name.clone()).map_err(|e| tls_error(e.to_string()))?;
+        TlsConnector::from(settings.tls.clone())
+            .connect(name, stream)
+            .await
+            .map_err(|e| tls_error(e.to_string()))?;
+        Some(started.elapsed())
+    } else {
+        None
+    };
+
+    Ok(TcpCheckOutcome { addr, connect, tls })
+}
+
+/// Runs `check` against `host:port` (the check's own target, if set, wins)
+/// and closes the connection.
+pub async fn run(
+    check: &TcpCheck,
+    host: &str,
+    port: u16,
+    settings: &TcpCheckSettings,
+) -> Result<TcpCheckOutcome, TcpCheckError> {
+    let (host, port) = match &check.target {
+        Some((host, port)) => (host.as_str(), *port),
+        None => (host, port),
+    };
+    timeout(
+        check.timeout,
+        connect_and_handshake(check, host, port, settings),
+    )
+    .await
+    .map_err(|_| TcpCheckError::TimedOut(check.timeout))?
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn check(tls: bool) -> TcpCheck {
+        TcpCheck {
+            target: None,
+            tls,
+            server_name: None,
+            timeout: Duration::from_secs(2),
+        }
+    }
+
+    #[tokio::test]
+    async fn connects_to_a_listening_port() {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let settings = TcpCheckSettings::defaults().unwrap();
+
+        let outcome = run(&check(false), "127.0.0.1", addr.port(), &settings)
+            .await
+            .unwrap();
+        assert_eq!(outcome.addr, addr);
+        assert!(outcome.tls.is_none());
+
+        // The check's own target wins over the base URL's.
+        let mut own_target = check(false);
+        own_target.target = Some(("localhost".to_string(), addr.port()));
+        let mut pinned = settings.clone();
+        pinned.overrides = addr::parse_resolve_overrides(&format!("localhost:{}", addr)).unwrap();
+        let outcome = run(&own_target, "unused.test", 1, &pinned).await.unwrap();
+        assert_eq!(outcome.addr, addr);
+    }
+
+    #[tokio::test]
+    async fn names_the_failing_step() {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let settings = TcpCheckSettings::defaults().unwrap();
+
+        // Accepts, then closes without speaking TLS.
+        tokio::spawn(async move {
+            while let Ok((socket, _)) = listener.accept().await {
+                drop(socket);
+            }
+        });
+        let err = run(&check(true), "127.0.0.1", addr.port(), &settings)
+            .await
+            .unwrap_err();
+        assert!(matches!(err, TcpCheckError::Tls { .. }), "{}", err);
+
+        let closed = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let closed_addr = closed.local_addr().unwrap();
+        drop(closed);
+        let err = run(&check(false), "127.0.0.1", closed_addr.port(), &settings)
+            .await
+            .unwrap_err();
+        assert!(err
+            .to_string()
+            .starts_with(&format!("TCP connect to {}", closed_addr)));
+    }
+
+    #[tokio::test]
+    async fn refuses_targets_the_safety_guard_refuses() {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let mut settings = TcpCheckSettings::defaults().unwrap();
+        settings.safety.deny = SafetyPolicy::parse_list("127.0.0.0/8").unwrap();
+
+        let err = run(&check(false), "127.0.0.1", addr.port(), &settings)
+            .await
+            .unwrap_err();
+        assert!(matches!(err, TcpCheckError::Refused(_)), "{}", err);
+    }
+}
@@ -0,0 +1,296 @@
+//! Weighted request-template mixes (`TEMPLATE_DIR`).
+//!
+//! A lighter alternative to scenarios for replaying a realistic spread of
+//! URLs: a directory holds one request template per file (`.yaml`, `.yml` or
+//! `.json`), and every request a single-URL worker sends is drawn from them
+//! in proportion to their `weight`.
+//!
+//! ```yaml
+//! # templates/product.yaml
+//! method: GET
+//! path: /products/42
+//! weight: 70
+//! ```
+//!
+//! ```yaml
+//! # templates/checkout.yaml
+//! method: POST
+//! path: /checkout
+//! body: '{"cart": "c-1"}'
+//! headers:
+//!   Content-Type: application/json
+//! weight: 5
+//! ```
+//!
+//! Relative paths are joined to `TARGET_URL`. Each template's name is its
+//! file stem unless the file sets `name:`; requests are counted per template
+//! in `template_requests_total`.
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+use rand::Rng;
+use serde::Deserialize;
+use thiserror::Error;
+use tracing::info;
+
+/// Errors raised while loading a template directory.
+#[derive(Error, Debug)]
+pub enum TemplateError {
+    #[error("Failed to read template {path}: {source}")]
+    Read {
+        path: PathBuf,
+        source: std::io::Error,
+    },
+
+    #[error("Invalid template {path}: {message}")]
+    Parse { path: PathBuf, message: String },
+
+    #[error("Template '{name}' has an invalid weight {weight} (must be positive)")]
+    InvalidWeight { name: String, weight: f64 },
+
+    #[error("No request templates (.yaml, .yml, .json) found in {0}")]
+    Empty(PathBuf),
+}
+
+fn default_method() -> String {
+    "GET".to_string()
+}
+
+fn default_weight() -> f64 {
+    1.0
+}
+
+/// One request in a template mix.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct RequestTemplate {
+    #[serde(default)]
+    pub name: String,
+
+    #[serde(default = "default_method")]
+    pub method: String,
+
+    pub path: String,
+
+    #[serde(default)]
+    pub body: Option<String>,
+
+    #[serde(default)]
+    pub headers: HashMap<String, String>,
+
+    /// Relative share of traffic.
+    #[serde(default = "default_weight")]
+    pub weight: f64,
+}
+
+impl RequestTemplate {
+    /// Full URL for this template against `base_url`.
+    pub fn url(&self, base_url: &str) -> String {
+        if self.path.starts_with("http://") || self.path.starts_with("https://") {
+            self.path.clone()
+        } else {
+            format!(
+                "{}/{}",
+                base_url.trim_end_matches('/'),
+                self.path.trim_start_matches('/')
+            )
+        }
+    }
+
+    /// Builds the request for this template.
+    pub fn build_request(
+        &self,
+        client: &reqwest::Client,
+        base_url: &str,
+    ) -> reqwest::RequestBuilder {
+        let method = reqwest::Method::from_bytes(self.method.to_uppercase().as_bytes())
+            .unwrap_or(reqwest::Method::GET);
+        let mut req = client.request(method, self.url(base_url));
+        for (key, value) in &self.headers {
+            req = req.header(key, value);
+        }
+        if let Some(body) = &self.body {
+            req = req.body(body.clone());
+        }
+        req
+    }
+}
+
+/// Templates with cumulative weights for sampling.
+#[derive(Debug, Clone)]
+pub struct TemplateMix {
+    templates: Vec<RequestTemplate>,
+    cumulative: Vec<f64>,
+}
+
+impl TemplateMix {
+    /// Builds a mix, rejecting non-positive weights.
+    pub fn new(templates: Vec<RequestTemplate>) -> Result<Self, TemplateError> {
+        let mut cumulative = Vec::with_capacity(templates.len());
+        let mut total = 0.0;
+        for t in &templates {
+            if !t.weight.is_finite() || t.weight <= 0.0 {
+                return Err(TemplateError::InvalidWeight {
+                    name: t.name.clone(),
+                    weight: t.weight,
+                });
+            }
+            total += t.weight;
+            cumulative.push(total);
+        }
+        Ok(Self {
+            templates,
+            cumulative,
+        })
+    }
+
+    /// Loads every `.yaml`, `.yml` and `.json` file in `dir`, in file name order.
+    pub fn load_dir(dir: &Path) -> Result<Self, TemplateError> {
+        let read_err = |source| TemplateError::Read {
+            path: dir.to_path_buf(),
+            source,
+        };
+        let mut paths: Vec<PathBuf> = std::fs::read_dir(dir)
+            .map_err(read_err)?
+            .filter_map(|entry| entry.ok().map(|e| e.path()))
+            .filter(|p| {
+                matches!(
+                    p.extension().and_then(|e| e.to_str()),
+                    Some("yaml" | "yml" | "json")
+                )
+            })
+            .collect();
+        paths.sort();
+        if paths.is_empty() {
+            return Err(TemplateError::Empty(dir.to_path_buf()));
+        }
+
+        let mut templates = Vec::with_capacity(paths.len());
+        for path in paths {
+            let text = std::fs::read_to_string(&path).map_err(|source| TemplateError::Read {
+                path: path.clone(),
+                source,
+            })?;
+            // JSON is valid YAML, so one parser covers both.
+            let mut template: RequestTemplate =
+                serde_yaml::from_str(&text).map_err(|e| TemplateError::Parse {
+                    path: path.clone(),
+                    message: e.to_string(),
+                })?;
+            if template.name.is_empty() {
+                template.name = path
+                    .file_stem()
+                    .map(|s| s.to_string_lossy().into_owned())
+                    .unwrap_or_default();
+            }
+            templates.push(template);
+        }
+
+        let mix = Self::new(templates)?;
+        info!(
+            dir = %dir.display(),
+            templates = mix.templates.len(),
+            mix = ?mix.proportions(),
+            "Loaded request templates"
+        );
+        Ok(mix)
+    }
+
+    pub fn templates(&self) -> &[RequestTemplate] {
+        &self.templates
+    }
+
+    /// Each template's share of traffic, as `(name, fraction)`.
+    pub fn proportions(&self) -> Vec<(String, f64)> {
+        let total = self.cumulative.last().copied().unwrap_or(0.0);
+        self.templates
+            .iter()
+            .map(|t| (t.name.clone(), t.weight / total))
+            .collect()
+    }
+
+    /// Template at position `point` in `[0, total weight)`.
+    fn pick(&self, point: f64) -> &RequestTemplate {
+        let idx = self
+            .cumulative
+            .partition_point(|&c| c <= point)
+            .min(self.templates.len() - 1);
+        &self.templates[idx]
+    }
+
+    /// Draws a template in proportion to the weights.
+    pub fn sample(&self) -> &RequestTemplate {
+        let total = self.cumulative.last().copied().unwrap_or(0.0);
+        self.pick(crate::seed::with_rng(|rng| rng.gen_range(0.0..total)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn template(name: &str, weight: f64) -> RequestTemplate {
+        RequestTemplate {
+            name: name.to_string(),
+            method: "GET".to_string(),
+            path: format!("/{}", name),
+            body: None,
+            headers: HashMap::new(),
+            weight,
+        }
+    }
+
+    #[test]
+    fn picks_by_cumulative_weight() {
+        let mix = TemplateMix::new(vec![template("a", 70.0), template("b", 30.0)]).unwrap();
+        assert_eq!(mix.pick(0.0).name, "a");
+        assert_eq!(mix.pick(69.9).name, "a");
+        assert_eq!(mix.pick(70.0).name, "b");
+        assert_eq!(mix.pick(99.9).name, "b");
+        assert_eq!(mix.proportions()[1], ("b".to_string(), 0.3));
+
+        assert!(matches!(
+            TemplateMix::new(vec![template("zero", 0.0)]),
+            Err(TemplateError::InvalidWeight { .. })
+        ));
+    }
+
+    #[test]
+    fn joins_relative_paths() {
+        let t = template("items", 1.0);
+        assert_eq!(t.url("http://api.local/"), "http://api.local/items");
+        let absolute = RequestTemplate {
+            path: "https://cdn.local/x".to_string(),
+            ..t
+        };
+        assert_eq!(absolute.url("http://api.local"), "https://cdn.local/x");
+    }
+
+    #[test]
+    fn loads_yaml_and_json_files() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(
+            dir.path().join("browse.yaml"),
+            "path: /products\nweight: 9\n",
+        )
+        .unwrap();
+        std::fs::write(
+            dir.path().join("buy.json"),
+            r#"{"name": "checkout", "method": "POST", "path": "/checkout", "body": "{}"}"#,
+        )
+        .unwrap();
+        std::fs::write(dir.path().join("notes.txt"), "ignored").unwrap();
+
+        let mix = TemplateMix::load_dir(dir.path()).unwrap();
+        let names: Vec<_> = mix.templates().iter().map(|t| t.name.as_str()).collect();
+        assert_eq!(names, vec!["browse", "checkout"]);
+        assert_eq!(mix.templates()[0].method, "GET");
+        assert_eq!(mix.templates()[1].weight, 1.0);
+
+        let empty = tempfile::tempdir().unwrap();
+        assert!(matches!(
+            TemplateMix::load_dir(empty.path()),
+            Err(TemplateError::Empty(_))
+        ));
+    }
+}
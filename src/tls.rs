@@ -0,0 +1,551 @@
+//! TLS client configuration and handshake tracking.
+//!
+//! The HTTP client uses a rustls configuration built here rather than
+//! reqwest's default, so every handshake can be observed through the session
+//! store: each new TLS connection asks the store for a cached session, and
+//! the answer decides whether the client attempts resumption or performs a
+//! full handshake. Handshakes are counted in
+//! `tls_handshakes_total{kind="full"|"resumed"}`; `resumed` means a cached
+//! session was offered to the server.
+//!
+//! `TLS_SESSION_RESUMPTION=false` disables session caching entirely, so every
+//! new connection performs a full handshake — useful to stress the target's
+//! TLS stack rather than its session cache.
+//!
+//! The mTLS identity (`CLIENT_CERT_PATH`/`CLIENT_KEY_PATH`) is served by a
+//! [`RotatingClientCert`] shared by every client using the same files. The
+//! files are re-read whenever they change (checked every
+//! `CLIENT_CERT_RELOAD_INTERVAL`), so short-lived certificates can be renewed
+//! during multi-day runs: new connections present the new certificate, while
+//! pooled connections keep the one they were established with until they are
+//! closed — no request is interrupted.
+
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::BufReader;
+use std::sync::{Arc, Mutex, RwLock};
+use std::time::{Duration, SystemTime};
+
+use rustls::client::danger::{HandshakeSignatureValid, ServerCertVerified, ServerCertVerifier};
+use rustls::client::{
+    ClientSessionMemoryCache, ClientSessionStore, ResolvesClientCert, Resumption,
+    Tls12ClientSessionValue, Tls12Resumption, Tls13ClientSessionValue,
+};
+use rustls::crypto::{ring, CryptoProvider};
+use rustls::pki_types::{CertificateDer, ServerName, UnixTime};
+use rustls::sign::CertifiedKey;
+use rustls::{DigitallySignedStruct, NamedGroup, RootCertStore, SignatureScheme};
+use thiserror::Error;
+use tracing::{debug, error, info, warn};
+
+use crate::client::ClientConfig;
+use crate::metrics::{CLIENT_CERT_RELOADS_TOTAL, TLS_HANDSHAKES_TOTAL};
+use crate::redaction::GLOBAL_REDACTOR;
+
+/// Sessions cached per client (rustls' default).
+const SESSION_CACHE_SIZE: usize = 256;
+
+/// Default interval between checks of the mTLS certificate files.
+pub const DEFAULT_CLIENT_CERT_RELOAD_INTERVAL: Duration = Duration::from_secs(30);
+
+/// Errors raised while building the TLS configuration.
+#[derive(Error, Debug)]
+pub enum TlsError {
+    #[error("Failed to read {what} '{path}': {source}")]
+    Read {
+        what: &'static str,
+        path: String,
+        source: std::io::Error,
+    },
+
+    #[error("No PEM certificates found in '{0}'")]
+    NoCertificates(String),
+
+    #[error("No private key found in '{0}'. Ensure the file contains a PEM-encoded private key.")]
+    NoPrivateKey(String),
+
+    #[error("Unsupported private key in '{path}': {source}")]
+    UnsupportedKey { path: String, source: rustls::Error },
+
+    #[error("CLIENT_CERT_PATH is set, but CLIENT_KEY_PATH is missing for mTLS.")]
+    MissingKey,
+
+    #[error("CLIENT_KEY_PATH is set, but CLIENT_CERT_PATH is missing for mTLS.")]
+    MissingCert,
+
+    #[error("Invalid TLS configuration: {0}")]
+    Rustls(#[from] rustls::Error),
+}
+
+/// Builds the rustls configuration for `config`: native root certificates
+/// (or no verification with `SKIP_TLS_VERIFY`), the mTLS identity if one is
+/// configured, and a session store that records handshakes.
+pub fn client_tls_config(config: &ClientConfig) -> Result<rustls::ClientConfig, TlsError> {
+    let provider = Arc::new(ring::default_provider());
+    let builder = rustls::ClientConfig::builder_with_provider(provider.clone())
+        .with_safe_default_protocol_versions()?;
+
+    let builder = if config.skip_tls_verify {
+        builder
+            .dangerous()
+            .with_custom_certificate_verifier(Arc::new(NoVerification(provider)))
+    } else {
+        builder.with_root_certificates(native_roots())
+    };
+
+    let mut tls = match (
+        config.client_cert_path.as_deref(),
+        config.client_key_path.as_deref(),
+    ) {
+        (Some(cert_path), Some(key_path)) => {
+            builder.with_client_cert_resolver(client_cert(cert_path, key_path)?)
+        }
+        (Some(_), None) => return Err(TlsError::MissingKey),
+        (None, Some(_)) => return Err(TlsError::MissingCert),
+        (None, None) => builder.with_no_client_auth(),
+    };
+
+    // Only HTTP/1.1 is enabled in the client.
+    tls.alpn_protocols = vec![b"http/1.1".to_vec()];
+    tls.resumption = if config.tls_session_resumption {
+        Resumption::store(Arc::new(TrackingSessionStore::new(true)))
+    } else {
+        Resumption::store(Arc::new(TrackingSessionStore::new(false)))
+            .tls12_resumption(Tls12Resumption::Disabled)
+    };
+    Ok(tls)
+}
+
+/// Native root certificates and no client identity, for connections made
+/// without the node's client settings.
+pub fn default_client_tls_config() -> Result<rustls::ClientConfig, TlsError> {
+    Ok(
+        rustls::ClientConfig::builder_with_provider(Arc::new(ring::default_provider()))
+            .with_safe_default_protocol_versions()?
+            .with_root_certificates(native_roots())
+            .with_no_client_auth(),
+    )
+}
+
+fn native_roots() -> RootCertStore {
+    let mut roots = RootCertStore::empty();
+    let native = rustls_native_certs::load_native_certs();
+    for error in &native.errors {
+        warn!(error = %error, "Failed to load some native root certificates");
+    }
+    let (added, ignored) = roots.add_parsable_certificates(native.certs);
+    debug!(added, ignored, "Loaded native root certificates");
+    roots
+}
+
+/// Loads the mTLS certificate chain and private key.
+fn load_identity(cert_path: &str, key_path: &str) -> Result<CertifiedKey, TlsError> {
+    let open = |what, path: &str| {
+        File::open(path)
+            .map(BufReader::new)
+            .map_err(|source| TlsError::Read {
+                what,
+                path: path.to_string(),
+                source,
+            })
+    };
+    let read_err = |what, path: &str| {
+        let path = path.to_string();
+        move |source| TlsError::Read { what, path, source }
+    };
+
+    let certs = rustls_pemfile::certs(&mut open("client certificate", cert_path)?)
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(read_err("client certificate", cert_path))?;
+    if certs.is_empty() {
+        return Err(TlsError::NoCertificates(cert_path.to_string()));
+    }
+    let key = rustls_pemfile::private_key(&mut open("client key", key_path)?)
+        .map_err(read_err("client key", key_path))?
+        .ok_or_else(|| TlsError::NoPrivateKey(key_path.to_string()))?;
+    let signing_key = ring::default_provider()
+        .key_provider
+        .load_private_key(key)
+        .map_err(|source| TlsError::UnsupportedKey {
+            path: key_path.to_string(),
+            source,
+        })?;
+
+    Ok(CertifiedKey::new(certs, signing_key))
+}
+
+/// Modification times of the certificate and key files.
+fn modified(cert_path: &str, key_path: &str) -> Option<(SystemTime, SystemTime)> {
+    let mtime = |path| std::fs::metadata(path).and_then(|m| m.modified()).ok();
+    Some((mtime(cert_path)?, mtime(key_path)?))
+}
+
+/// mTLS identity that is re-read from disk when the files change.
+#[derive(Debug)]
+pub struct RotatingClientCert {
+    cert_path: String,
+    key_path: String,
+    current: RwLock<Arc<CertifiedKey>>,
+    loaded_from: Mutex<Option<(SystemTime, SystemTime)>>,
+}
+
+impl RotatingClientCert {
+    /// Loads the identity; fails if the files are missing or invalid.
+    pub fn load(cert_path: &str, key_path: &str) -> Result<Self, TlsError> {
+        debug!(
+            cert_path = %GLOBAL_REDACTOR.path(cert_path),
+            key_path = %GLOBAL_REDACTOR.path(key_path),
+            "Loading mTLS client certificate and key"
+        );
+        let loaded_from = modified(cert_path, key_path);
+        let identity = load_identity(cert_path, key_path)?;
+        debug!("Configured mTLS with client certificate and key");
+        Ok(Self {
+            cert_path: cert_path.to_string(),
+            key_path: key_path.to_string(),
+            current: RwLock::new(Arc::new(identity)),
+            loaded_from: Mutex::new(loaded_from),
+        })
+    }
+
+    /// Re-reads the files if either changed since the last successful load.
+    ///
+    /// Returns whether a new identity was installed. On error the current
+    /// identity stays in use and the files are tried again next time, so a
+    /// half-written renewal (new certificate, old key) is simply retried.
+    pub fn reload_if_changed(&self) -> Result<bool, TlsError> {
+        let now = modified(&self.cert_path, &self.key_path);
+        let mut loaded_from = self.loaded_from.lock().unwrap_or_else(|e| e.into_inner());
+        if now.is_none() || now == *loaded_from {
+            return Ok(false);
+        }
+        let identity = load_identity(&self.cert_path, &self.key_path)?;
+        *self.current.write().unwrap_or_else(|e| e.into_inner()) = Arc::new(identity);
+        *loaded_from = now;
+        Ok(true)
+    }
+
+    /// The identity presented on new connections.
+    pub fn current(&self) -> Arc<CertifiedKey> {
+        self.current
+            .read()
+            .unwrap_or_else(|e| e.into_inner())
+            .clone()
+    }
+}
+
+impl ResolvesClientCert for RotatingClientCert {
+    fn resolve(
+        &self,
+        _root_hint_subjects: &[&[u8]],
+        _sigschemes: &[SignatureScheme],
+    ) -> Option<Arc<CertifiedKey>> {
+        Some(self.current())
+    }
+
+    fn has_certs(&self) -> bool {
+        true
+    }
+}
+
+lazy_static::lazy_static! {
+    /// (cert path, key path) → identity shared by every client using them.
+    static ref CLIENT_CERTS: Mutex<HashMap<(String, String), Arc<RotatingClientCert>>> =
+        Mutex::new(HashMap::new());
+}
+
+/// Shared identity for `cert_path`/`key_path`, loading it on first use.
+fn client_cert(cert_path: &str, key_path: &str) -> Result<Arc<RotatingClientCert>, TlsError> {
+    let mut certs = CLIENT_CERTS.lock().unwrap_or_else(|e| e.into_inner());
+    let key = (cert_path.to_string(), key_path.to_string());
+    if let Some(cert) = certs.get(&key) {
+        return Ok(cert.clone());
+    }
+    let cert = Arc::new(RotatingClientCert::load(cert_path, key_path)?);
+    certs.insert(key, cert.clone());
+    Ok(cert)
+}
+
+/// Re-reads every loaded mTLS identity whose files changed.
+pub fn reload_client_certs() {
+    let certs: Vec<_> = CLIENT_CERTS
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .values()
+        .cloned()
+        .collect();
+    for cert in certs {
+        match cert.reload_if_changed() {
+            Ok(true) => {
+                CLIENT_CERT_RELOADS_TOTAL
+                    .with_label_values(&["success"])
+                    .inc();
+                info!(
+                    cert_path = %GLOBAL_REDACTOR.path(&cert.cert_path),
+                    "Reloaded mTLS client certificate; new connections use the new identity"
+                );
+            }
+            Ok(false) => {}
+            Err(e) => {
+                CLIENT_CERT_RELOADS_TOTAL
+                    .with_label_values(&["error"])
+                    .inc();
+                error!(
+                    error = %e,
+                    "Failed to reload mTLS client certificate; keeping the current one"
+                );
+            }
+        }
+    }
+}
+
+/// Checks the mTLS certificate files for changes every `interval`.
+pub fn spawn_client_cert_watcher(interval: Duration) -> tokio::task::JoinHandle<()> {
+    tokio::spawn(async move {
+        let mut ticker = tokio::time::interval(interval);
+        ticker.tick().await;
+        loop {
+            ticker.tick().await;
+            reload_client_certs();
+        }
+    })
+}
+
+fn record_handshake(resumed: bool) {
+    let kind = if resumed { "resumed" } else { "full" };
+    TLS_HANDSHAKES_TOTAL.with_label_values(&[kind]).inc();
+}
+
+/// Session store that records whether each handshake offers a cached session.
+///
+/// For every new connection rustls first asks for a TLS 1.3 ticket and, if
+/// there is none, for a TLS 1.2 session, so each handshake is counted exactly
+/// once: as resumed when either lookup succeeds, as full otherwise. With
+/// resumption disabled nothing is cached and every handshake is full.
+#[derive(Debug)]
+pub struct TrackingSessionStore {
+    cache: Option<ClientSessionMemoryCache>,
+}
+
+impl TrackingSessionStore {
+    pub fn new(resumption: bool) -> Self {
+        Self {
+            cache: resumption.then(|| ClientSessionMemoryCache::new(SESSION_CACHE_SIZE)),
+        }
+    }
+}
+
+impl ClientSessionStore for TrackingSessionStore {
+    fn set_kx_hint(&self, server_name: ServerName<'static>, group: NamedGroup) {
+        if let Some(cache) = &self.cache {
+            cache.set_kx_hint(server_name, group);
+        }
+    }
+
+    fn kx_hint(&self, server_name: &ServerName<'_>) -> Option<NamedGroup> {
+        self.cache.as_ref()?.kx_hint(server_name)
+    }
+
+    fn set_tls12_session(&self, server_name: ServerName<'static>, value: Tls12ClientSessionValue) {
+        if let Some(cache) = &self.cache {
+            cache.set_tls12_session(server_name, value);
+        }
+    }
+
+    fn tls12_session(&self, server_name: &ServerName<'_>) -> Option<Tls12ClientSessionValue> {
+        let session = self
+            .cache
+            .as_ref()
+            .and_then(|cache| cache.tls12_session(server_name));
+        record_handshake(session.is_some());
+        session
+    }
+
+    fn remove_tls12_session(&self, server_name: &ServerName<'static>) {
+        if let Some(cache) = &self.cache {
+            cache.remove_tls12_session(server_name);
+        }
+    }
+
+    fn insert_tls13_ticket(
+        &self,
+        server_name: ServerName<'static>,
+        value: Tls13ClientSessionValue,
+    ) {
+        if let Some(cache) = &self.cache {
+            cache.insert_tls13_ticket(server_name, value);
+        }
+    }
+
+    fn take_tls13_ticket(
+        &self,
+        server_name: &ServerName<'static>,
+    ) -> Option<Tls13ClientSessionValue> {
+        let ticket = self
+            .cache
+            .as_ref()
+            .and_then(|cache| cache.take_tls13_ticket(server_name));
+        // Without a ticket rustls falls back to tls12_session, which counts.
+        if ticket.is_some() {
+            record_handshake(true);
+        }
+        ticket
+    }
+}
+
+/// Accepts any server certificate (`SKIP_TLS_VERIFY`), while still checking
+/// handshake signatures so the connection itself is well-formed.
+#[derive(Debug)]
+struct NoVerification(Arc<CryptoProvider>);
+
+impl ServerCertVerifier for NoVerification {
+    fn verify_server_cert(
+        &self,
+        _end_entity: &CertificateDer<'_>,
+        _intermediates: &[CertificateDer<'_>],
+        _server_name: &ServerName<'_>,
+        _ocsp_response: &[u8],
+        _now: UnixTime,
+    ) -> Result<ServerCertVerified, rustls::Error> {
+        Ok(ServerCertVerified::assertion())
+    }
+
+    fn verify_tls12_signature(
+        &self,
+        message: &[u8],
+        cert: &CertificateDer<'_>,
+        dss: &DigitallySignedStruct,
+    ) -> Result<HandshakeSignatureValid, rustls::Error> {
+        rustls::crypto::verify_tls12_signature(
+            message,
+            cert,
+            dss,
+            &self.0.signature_verification_algorithms,
+        )
+    }
+
+    fn verify_tls13_signature(
+        &self,
+        message: &[u8],
+        cert: &CertificateDer<'_>,
+        dss: &DigitallySignedStruct,
+    ) -> Result<HandshakeSignatureValid, rustls::Error> {
+        rustls::crypto::verify_tls13_signature(
+            message,
+            cert,
+            dss,
+            &self.0.signature_verification_algorithms,
+        )
+    }
+
+    fn supported_verify_schemes(&self) -> Vec<SignatureScheme> {
+        self.0.signature_verification_algorithms.supported_schemes()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config(resumption: bool) -> ClientConfig {
+        ClientConfig {
+            skip_tls_verify: true,
+            resolve_target_addr: None,
+            client_cert_path: None,
+            client_key_path: None,
+            custom_headers: None,
+            pool_config: None,
+            cookie_store: false,
+            local_address: None,
+            pinned_target: None,
+            ip_family: crate::ip_family::IpFamily::Auto,
+            tls_session_resumption: resumption,
+            sni_override: None,
+            proxy: None,
+            timeout: None,
+        }
+    }
+
+    #[test]
+    fn counts_full_handshakes_without_cached_sessions() {
+        let store = TrackingSessionStore::new(true);
+        let name = ServerName::try_from("example.com").unwrap();
+        let full = TLS_HANDSHAKES_TOTAL.with_label_values(&["full"]);
+        let before = full.get();
+
+        assert!(store.take_tls13_ticket(&name).is_none());
+        assert!(store.tls12_session(&name).is_none());
+        assert!(full.get() > before);
+    }
+
+    #[test]
+    fn builds_config_with_and_without_resumption() {
+        let tls = client_tls_config(&config(true)).unwrap();
+        assert_eq!(tls.alpn_protocols, vec![b"http/1.1".to_vec()]);
+        assert!(client_tls_config(&config(false)).is_ok());
+
+        let mut missing_key = config(true);
+        missing_key.client_cert_path = Some("/tmp/cert.pem".to_string());
+        assert!(matches!(
+            client_tls_config(&missing_key),
+            Err(TlsError::MissingKey)
+        ));
+
+        let mut missing_files = config(true);
+        missing_files.client_cert_path = Some("/nonexistent/cert.pem".to_string());
+        missing_files.client_key_path = Some("/nonexistent/key.pem".to_string());
+        assert!(matches!(
+            client_tls_config(&missing_files),
+            Err(TlsError::Read { .. })
+        ));
+    }
+
+    fn fixture(name: &str) -> std::path::PathBuf {
+        std::path::Path::new(env!("CARGO_MANIFEST_DIR"))
+            .join("tests/fixtures/tls")
+            .join(name)
+    }
+
+    #[test]
+    fn reloads_rotated_certificate() {
+        let dir = std::env::temp_dir().join(format!("tls-rotation-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let cert = dir.join("client.crt");
+        let key = dir.join("client.key");
+        std::fs::copy(fixture("client_a.crt"), &cert).unwrap();
+        std::fs::copy(fixture("client_a.key"), &key).unwrap();
+
+        let rotating =
+            RotatingClientCert::load(cert.to_str().unwrap(), key.to_str().unwrap()).unwrap();
+        let first = rotating.current().cert.clone();
+        assert!(!rotating.reload_if_changed().unwrap());
+
+        // A half-written renewal keeps the current identity.
+        let later = SystemTime::now() + Duration::from_secs(60);
+        std::fs::write(&key, "not a key").unwrap();
+        File::options()
+            .write(true)
+            .open(&key)
+            .unwrap()
+            .set_modified(later)
+            .unwrap();
+        assert!(rotating.reload_if_changed().is_err());
+        assert_eq!(rotating.current().cert, first);
+
+        std::fs::copy(fixture("client_b.crt"), &cert).unwrap();
+        std::fs::copy(fixture("client_b.key"), &key).unwrap();
+        for path in [&cert, &key] {
+            File::options()
+                .write(true)
+                .open(path)
+                .unwrap()
+                .set_modified(later + Duration::from_secs(60))
+                .unwrap();
+        }
+        assert!(rotating.reload_if_changed().unwrap());
+        assert_ne!(rotating.current().cert, first);
+        assert!(rotating.resolve(&[], &[]).is_some());
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,183 @@
+//! Weekday and special-date multipliers for the DailyTraffic model.
+//!
+//! A DailyTraffic cycle describes one day; a calendar scales it by the day
+//! the load falls on, so week-long simulations can have quiet weekends and a
+//! Black Friday spike. The calendar is a small YAML file:
+//!
+//! ```yaml
+//! weekdays:
+//!   saturday: 0.4
+//!   sunday: 0.4
+//! dates:
+//!   "2026-11-27": 3.0   # Black Friday
+//!   "2026-12-25": 0.2
+//! ```
+//!
+//! Days are UTC calendar days of the wall clock. A date override replaces
+//! the weekday multiplier for that day; days listed in neither use 1.0.
+
+use std::collections::BTreeMap;
+use std::path::Path;
+
+use chrono::{DateTime, Datelike, NaiveDate};
+use serde::Deserialize;
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum CalendarError {
+    #[error("failed to read calendar file '{path}': {source}")]
+    Read {
+        path: String,
+        source: std::io::Error,
+    },
+
+    #[error("failed to parse calendar file: {0}")]
+    Parse(#[from] serde_yaml::Error),
+
+    #[error("unknown weekday '{0}' (expected monday .. sunday)")]
+    InvalidWeekday(String),
+
+    #[error("invalid date '{0}' (expected YYYY-MM-DD)")]
+    InvalidDate(String),
+
+    #[error("multiplier for '{0}' must be a finite number >= 0")]
+    InvalidMultiplier(String),
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct CalendarFile {
+    #[serde(default)]
+    weekdays: BTreeMap<String, f64>,
+    #[serde(default)]
+    dates: BTreeMap<String, f64>,
+}
+
+/// Per-day traffic multipliers.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TrafficCalendar {
+    /// Multiplier per weekday, Monday first.
+    weekdays: [f64; 7],
+    dates: BTreeMap<NaiveDate, f64>,
+}
+
+const WEEKDAYS: [&str; 7] = [
+    "monday",
+    "tuesday",
+    "wednesday",
+    "thursday",
+    "friday",
+    "saturday",
+    "sunday",
+];
+
+impl TrafficCalendar {
+    /// Loads a calendar file.
+    pub fn from_file(path: impl AsRef<Path>) -> Result<Self, CalendarError> {
+        let path = path.as_ref();
+        let content = std::fs::read_to_string(path).map_err(|source| CalendarError::Read {
+            path: path.display().to_string(),
+            source,
+        })?;
+        Self::from_yaml(&content)
+    }
+
+    /// Parses calendar YAML.
+    pub fn from_yaml(content: &str) -> Result<Self, CalendarError> {
+        let file: CalendarFile = serde_yaml::from_str(content)?;
+
+        let mut weekdays = [1.0; 7];
+        for (name, multiplier) in file.weekdays {
+            let day = WEEKDAYS
+                .iter()
+                .position(|d| {
+                    d.eq_ignore_ascii_case(name.trim()) || d[..3].eq_ignore_ascii_case(name.trim())
+                })
+                .ok_or_else(|| CalendarError::InvalidWeekday(name.clone()))?;
+            weekdays[day] = check_multiplier(&name, multiplier)?;
+        }
+
+        let mut dates = BTreeMap::new();
+        for (date, multiplier) in file.dates {
+            let day = NaiveDate::parse_from_str(date.trim(), "%Y-%m-%d")
+                .map_err(|_| CalendarError::InvalidDate(date.clone()))?;
+            dates.insert(day, check_multiplier(&date, multiplier)?);
+        }
+
+        Ok(Self { weekdays, dates })
+    }
+
+    /// The multiplier for the UTC day containing `unix_secs`.
+    pub fn multiplier(&self, unix_secs: f64) -> f64 {
+        let Some(datetime) = DateTime::from_timestamp(unix_secs.floor() as i64, 0) else {
+            return 1.0;
+        };
+        let day = datetime.date_naive();
+        self.dates
+            .get(&day)
+            .copied()
+            .unwrap_or(self.weekdays[day.weekday().num_days_from_monday() as usize])
+    }
+}
+
+fn check_multiplier(key: &str, multiplier: f64) -> Result<f64, CalendarError> {
+    if multiplier.is_finite() && multiplier >= 0.0 {
+        Ok(multiplier)
+    } else {
+        Err(CalendarError::InvalidMultiplier(key.to_string()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DAY: f64 = 86400.0;
+    /// 2026-11-23T12:00:00Z, a Monday.
+    const MONDAY_NOON: f64 = 1_795_435_200.0;
+
+    fn calendar() -> TrafficCalendar {
+        TrafficCalendar::from_yaml(
+            r#"
+weekdays:
+  saturday: 0.4
+  Sun: 0.4
+dates:
+  "2026-11-27": 3.0
+"#,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn applies_weekday_multipliers() {
+        let calendar = calendar();
+        assert_eq!(calendar.multiplier(MONDAY_NOON), 1.0);
+        assert_eq!(calendar.multiplier(MONDAY_NOON + 5.0 * DAY), 0.4);
+        assert_eq!(calendar.multiplier(MONDAY_NOON + 6.0 * DAY), 0.4);
+        assert_eq!(calendar.multiplier(MONDAY_NOON + 7.0 * DAY), 1.0);
+    }
+
+    #[test]
+    fn date_overrides_win() {
+        // Friday 2026-11-27.
+        assert_eq!(calendar().multiplier(MONDAY_NOON + 4.0 * DAY), 3.0);
+    }
+
+    #[test]
+    fn rejects_invalid_entries() {
+        assert!(matches!(
+            TrafficCalendar::from_yaml("weekdays:\n  funday: 1.0\n"),
+            Err(CalendarError::InvalidWeekday(_))
+        ));
+        assert!(matches!(
+            TrafficCalendar::from_yaml("dates:\n  \"2026-13-01\": 1.0\n"),
+            Err(CalendarError::InvalidDate(_))
+        ));
+        assert!(matches!(
+            TrafficCalendar::from_yaml("weekdays:\n  monday: -1\n"),
+            Err(CalendarError::InvalidMultiplier(_))
+        ));
+        assert!(TrafficCalendar::from_yaml("holidays: {}\n").is_err());
+    }
+}
@@ -1,7 +1,22 @@
 use std::str::FromStr;
 use tokio::time::Duration;
 
-/// Parses a duration string in the format "30s", "10m", "5h", "3d".
+/// JSON Schema pattern for the duration strings [`parse_duration_string`]
+/// accepts (short of overflowing values).
+pub const DURATION_PATTERN: &str = concat!(
+    r"^\s*([0-9]+",
+    r"|[0-9]+d(\s*[0-9]+h)?(\s*[0-9]+m)?(\s*[0-9]+s)?",
+    r"|[0-9]+h(\s*[0-9]+m)?(\s*[0-9]+s)?",
+    r"|[0-9]+m(\s*[0-9]+s)?",
+    r"|[0-9]+s",
+    r")\s*$"
+);
+
+/// Parses a duration string such as "30s", "10m", "1h30m" or "90".
+///
+/// A duration is either a bare number of seconds or one or more
+/// `<number><unit>` components, largest unit first, optionally separated by
+/// spaces ("1d 12h", "2m30s"). Each unit may appear once.
 ///
 /// Supported units:
 /// - `s` for seconds
@@ -15,29 +30,62 @@ pub fn parse_duration_string(s: &str) -> Result<Duration, String> {
         return Err("Duration string cannot be empty".to_string());
     }
 
-    let unit_char = s.chars().last().unwrap();
-    let value_str = &s[0..s.len() - 1];
+    if s.bytes().all(|b| b.is_ascii_digit()) {
+        return u64::from_str(s)
+            .map(Duration::from_secs)
+            .map_err(|_| format!("Invalid numeric value in duration: '{}'", s));
+    }
 
-    let value = match u64::from_str(value_str) {
-        Ok(v) => v,
-        Err(_) => {
+    let mut total: u64 = 0;
+    let mut previous_unit = u64::MAX;
+    let mut rest = s;
+    while !rest.is_empty() {
+        let value_len = rest
+            .find(|c: char| c.is_ascii_alphabetic())
+            .unwrap_or(rest.len());
+        let value_str = &rest[..value_len];
+        let value = match u64::from_str(value_str) {
+            Ok(v) if value_str.bytes().all(|b| b.is_ascii_digit()) => v,
+            _ => {
+                return Err(format!(
+                    "Invalid numeric value in duration: '{}'",
+                    value_str
+                ))
+            }
+        };
+
+        let unit_len = rest[value_len..]
+            .find(|c: char| !c.is_ascii_alphabetic())
+            .unwrap_or(rest.len() - value_len);
+        let unit_str = &rest[value_len..value_len + unit_len];
+        let unit_secs = match unit_str {
+            "s" => 1,
+            "m" => 60,
+            "h" => 60 * 60,
+            "d" => 24 * 60 * 60,
+            _ => {
+                return Err(format!(
+                    "Unknown duration unit: '{}'. Use 's', 'm', 'h', or 'd'.",
+                    unit_str
+                ))
+            }
+        };
+        if unit_secs >= previous_unit {
             return Err(format!(
-                "Invalid numeric value in duration: '{}'",
-                value_str
-            ))
+                "Duration units must go from largest to smallest, each at most once (got '{}')",
+                s
+            ));
         }
-    };
+        previous_unit = unit_secs;
 
-    match unit_char {
-        's' => Ok(Duration::from_secs(value)),
-        'm' => Ok(Duration::from_secs(value * 60)),
-        'h' => Ok(Duration::from_secs(value * 60 * 60)),
-        'd' => Ok(Duration::from_secs(value * 24 * 60 * 60)),
-        _ => Err(format!(
-            "Unknown duration unit: '{}'. Use 's', 'm', 'h', or 'd'.",
-            unit_char
-        )),
+        total = value
+            .checked_mul(unit_secs)
+            .and_then(|secs| total.checked_add(secs))
+            .ok_or_else(|| format!("Duration is too large: '{}'", s))?;
+        rest = rest[value_len + unit_len..].trim_start();
     }
+
+    Ok(Duration::from_secs(total))
 }
 
 /// Parses a body size string like "512B", "512KB", or "1MB" into bytes.
@@ -217,11 +265,53 @@ mod tests {
         }
 
         #[test]
-        fn no_suffix_errors() {
-            let err = parse_duration_string("10").unwrap_err();
+        fn bare_number_is_seconds() {
+            assert_eq!(
+                parse_duration_string("90").unwrap(),
+                Duration::from_secs(90)
+            );
+            assert_eq!(
+                parse_duration_string(" 0 ").unwrap(),
+                Duration::from_secs(0)
+            );
+        }
+
+        #[test]
+        fn parse_compound() {
+            assert_eq!(
+                parse_duration_string("1h30m").unwrap(),
+                Duration::from_secs(5400)
+            );
+            assert_eq!(
+                parse_duration_string("1d 2h 3m 4s").unwrap(),
+                Duration::from_secs(86400 + 7200 + 180 + 4)
+            );
+            assert_eq!(
+                parse_duration_string("2m30s").unwrap(),
+                Duration::from_secs(150)
+            );
+        }
+
+        #[test]
+        fn compound_units_out_of_order_error() {
+            let err = parse_duration_string("30s1m").unwrap_err();
+            assert!(err.contains("largest to smallest"), "error was: {}", err);
+            let err = parse_duration_string("1m1m").unwrap_err();
+            assert!(err.contains("largest to smallest"), "error was: {}", err);
+        }
+
+        #[test]
+        fn trailing_number_without_unit_errors() {
+            let err = parse_duration_string("1h30").unwrap_err();
             assert!(err.contains("Unknown duration unit"), "error was: {}", err);
         }
 
+        #[test]
+        fn overflow_errors() {
+            let err = parse_duration_string("18446744073709551615d").unwrap_err();
+            assert!(err.contains("too large"), "error was: {}", err);
+        }
+
         #[test]
         fn no_number_errors() {
             let err = parse_duration_string("m").unwrap_err();
@@ -245,6 +335,51 @@ mod tests {
             let err = parse_duration_string("abcm").unwrap_err();
             assert!(err.contains("Invalid numeric"), "error was: {}", err);
         }
+
+        #[test]
+        fn signed_number_errors() {
+            let err = parse_duration_string("+5m").unwrap_err();
+            assert!(err.contains("Invalid numeric"), "error was: {}", err);
+        }
+
+        #[test]
+        fn schema_pattern_matches_the_parser() {
+            let pattern = regex::Regex::new(DURATION_PATTERN).unwrap();
+            for s in [
+                "90",
+                " 0 ",
+                "30s",
+                "10m",
+                "5h",
+                "3d",
+                "1h30m",
+                "2m30s",
+                "1d 2h 3m 4s",
+                "1d\t4s",
+                "  10m  ",
+                "1d12h",
+                "",
+                "   ",
+                "10x",
+                "30s1m",
+                "1m1m",
+                "1h30",
+                "m",
+                "5.5h",
+                "-5m",
+                "+5m",
+                "5 m",
+                "1h 30",
+                "h1",
+            ] {
+                assert_eq!(
+                    pattern.is_match(s),
+                    parse_duration_string(s).is_ok(),
+                    "'{}'",
+                    s
+                );
+            }
+        }
     }
 
     // --- parse_headers_with_escapes tests ---
@@ -0,0 +1,191 @@
+//! Connection warm-up before the measured test (`WARMUP_CONNECTIONS`).
+//!
+//! The first requests of a test pay for DNS, TCP and TLS handshakes that
+//! later requests don't, so early latency samples overstate steady-state
+//! latency. With `WARMUP_CONNECTIONS=N`, the node sends N concurrent
+//! requests to the target before the test clock starts, spread over the
+//! worker clients the way workers are (`LOCAL_ADDRS`, `DNS_ROUND_ROBIN`),
+//! so each pool already holds open connections when load begins.
+//!
+//! Warm-up requests are not recorded in any metric or percentile. Their
+//! outcome is logged when the warm-up ends and repeated in the final report.
+
+use std::fmt;
+use std::sync::Mutex;
+use std::time::Duration;
+
+use tokio::task::JoinSet;
+use tokio::time::Instant;
+use tracing::{info, warn};
+
+use crate::client::SourceAddrClients;
+
+/// Outcome of a warm-up.
+#[derive(Debug, Clone, PartialEq)]
+pub struct WarmupStats {
+    /// Connections requested.
+    pub connections: usize,
+    /// Requests that got a response, whatever its status.
+    pub succeeded: usize,
+    pub failed: usize,
+    /// Wall-clock time of the whole warm-up.
+    pub elapsed: Duration,
+    /// Request latencies of the successful requests.
+    pub min: Option<Duration>,
+    pub mean: Option<Duration>,
+    pub max: Option<Duration>,
+}
+
+impl WarmupStats {
+    /// Aggregates per-request outcomes: the latency on success.
+    pub fn from_results(results: &[Option<Duration>], elapsed: Duration) -> Self {
+        let latencies: Vec<Duration> = results.iter().flatten().copied().collect();
+        let mean = (!latencies.is_empty())
+            .then(|| latencies.iter().sum::<Duration>() / latencies.len() as u32);
+        Self {
+            connections: results.len(),
+            succeeded: latencies.len(),
+            failed: results.len() - latencies.len(),
+            elapsed,
+            min: latencies.iter().min().copied(),
+            mean,
+            max: latencies.iter().max().copied(),
+        }
+    }
+}
+
+impl fmt::Display for WarmupStats {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} of {} connections warmed in {}ms",
+            self.succeeded,
+            self.connections,
+            self.elapsed.as_millis()
+        )?;
+        if let (Some(min), Some(mean), Some(max)) = (self.min, self.mean, self.max) {
+            write!(
+                f,
+                ": min {}ms, mean {}ms, max {}ms",
+                min.as_millis(),
+                mean.as_millis(),
+                max.as_millis()
+            )?;
+        }
+        if self.failed > 0 {
+            write!(f, " ({} failed)", self.failed)?;
+        }
+        Ok(())
+    }
+}
+
+lazy_static::lazy_static! {
+    static ref LAST_WARMUP: Mutex<Option<WarmupStats>> = Mutex::new(None);
+}
+
+/// Opens `connections` connections by sending that many concurrent `GET`
+/// requests to `url`, the i-th through worker i's client.
+pub async fn run(clients: &SourceAddrClients, url: &str, connections: usize) -> WarmupStats {
+    info!(connections, url, "Warming up connections before the test");
+    let started = Instant::now();
+    let mut requests = JoinSet::new();
+    for i in 0..connections {
+        let client = clients.for_worker(i);
+        let url = url.to_string();
+        requests.spawn(async move {
+            let sent = Instant::now();
+            match client.get(&url).send().await {
+                // Reading the body returns the connection to the pool.
+                Ok(response) => {
+                    let _ = response.bytes().await;
+                    Some(sent.elapsed())
+                }
+                Err(e) => {
+                    warn!(error = %e, "Warm-up request failed");
+                    None
+                }
+            }
+        });
+    }
+    let mut results = Vec::with_capacity(connections);
+    while let Some(result) = requests.join_next().await {
+        results.push(result.ok().flatten());
+    }
+
+    let stats = WarmupStats::from_results(&results, started.elapsed());
+    if stats.failed > 0 {
+        warn!(failed = stats.failed, "Connection warm-up: {}", stats);
+    } else {
+        info!("Connection warm-up: {}", stats);
+    }
+    *LAST_WARMUP.lock().unwrap_or_else(|e| e.into_inner()) = Some(stats.clone());
+    stats
+}
+
+/// The most recent warm-up's outcome, if one ran.
+pub fn last() -> Option<WarmupStats> {
+    LAST_WARMUP
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .clone()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+    #[test]
+    fn aggregates_and_displays_results() {
+        let ms = Duration::from_millis;
+        let stats = WarmupStats::from_results(&[Some(ms(10)), None, Some(ms(30))], ms(45));
+        assert_eq!(
+            (stats.connections, stats.succeeded, stats.failed),
+            (3, 2, 1)
+        );
+        assert_eq!(
+            (stats.min, stats.mean, stats.max),
+            (Some(ms(10)), Some(ms(20)), Some(ms(30)))
+        );
+        assert_eq!(
+            stats.to_string(),
+            "2 of 3 connections warmed in 45ms: min 10ms, mean 20ms, max 30ms (1 failed)"
+        );
+        assert_eq!(
+            WarmupStats::from_results(&[None], ms(5)).to_string(),
+            "0 of 1 connections warmed in 5ms (1 failed)"
+        );
+    }
+
+    #[tokio::test]
+    async fn opens_one_connection_per_request() {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let accepted = Arc::new(AtomicUsize::new(0));
+        let counter = accepted.clone();
+        tokio::spawn(async move {
+            loop {
+                let (mut socket, _) = listener.accept().await.unwrap();
+                counter.fetch_add(1, Ordering::SeqCst);
+                tokio::spawn(async move {
+                    let mut request = vec![0u8; 1024];
+                    while socket.read(&mut request).await.unwrap_or(0) > 0 {
+                        // Hold the response so the requests overlap.
+                        tokio::time::sleep(Duration::from_millis(50)).await;
+                        let _ = socket
+                            .write_all(b"HTTP/1.1 200 OK\r\nContent-Length: 2\r\n\r\nok")
+                            .await;
+                    }
+                });
+            }
+        });
+
+        let clients = SourceAddrClients::single(reqwest::Client::new());
+        let stats = run(&clients, &format!("http://{}/", addr), 4).await;
+        assert_eq!((stats.succeeded, stats.failed), (4, 0));
+        assert_eq!(accepted.load(Ordering::SeqCst), 4);
+        assert_eq!(last(), Some(stats));
+    }
+}
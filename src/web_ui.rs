@@ -0,0 +1,85 @@
+//! Embedded web UI (`GET /ui` on the control API).
+//!
+//! A single static page, compiled into the binary, for operators without
+//! Grafana: it plots the load model's target curve (`GET /load-curve`) with
+//! the achieved rate, live RPS, error rate and latency from the `/live`
+//! WebSocket, and has buttons to pause and resume traffic (`POST /rps`),
+//! stop the test (`POST /stop`) and submit a new YAML config
+//! (`POST /config`). It needs no external assets, so it works offline.
+
+use serde::Serialize;
+
+use crate::load_models::LoadModel;
+
+/// The UI page.
+pub const INDEX_HTML: &str = include_str!("web_ui/index.html");
+
+/// Points sampled along the load curve.
+pub const CURVE_POINTS: usize = 120;
+
+/// The load model's target rate over the whole test.
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct LoadCurve {
+    pub duration_secs: f64,
+    pub elapsed_secs: f64,
+    /// `[seconds, rps]` pairs; empty for the Concurrent model, which has no
+    /// target rate.
+    pub points: Vec<(f64, f64)>,
+    /// Active runtime override (`POST /rps`), if any.
+    pub rps_override: Option<f64>,
+}
+
+impl LoadCurve {
+    /// Samples `model` at `CURVE_POINTS + 1` evenly spaced times.
+    pub fn sample(
+        model: &LoadModel,
+        duration_secs: f64,
+        elapsed_secs: f64,
+        rps_override: Option<f64>,
+    ) -> Self {
+        let points = if matches!(model, LoadModel::Concurrent) || duration_secs <= 0.0 {
+            Vec::new()
+        } else {
+            // Clock-aligned models follow the wall clock, so sample them at
+            // the time each point of the test falls on.
+            let start_unix = crate::load_models::unix_now_secs() - elapsed_secs;
+            (0..=CURVE_POINTS)
+                .map(|i| {
+                    let t = duration_secs * i as f64 / CURVE_POINTS as f64;
+                    (t, model.calculate_rps_at(t, duration_secs, start_unix + t))
+                })
+                .collect()
+        };
+        Self {
+            duration_secs,
+            elapsed_secs: elapsed_secs.min(duration_secs),
+            points,
+            rps_override,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn samples_the_load_model() {
+        let model = LoadModel::RampRps {
+            min_rps: 10.0,
+            max_rps: 100.0,
+            ramp_duration: std::time::Duration::from_secs(60),
+            clock_start: None,
+        };
+        let curve = LoadCurve::sample(&model, 60.0, 90.0, None);
+        assert_eq!(curve.points.len(), CURVE_POINTS + 1);
+        assert_eq!(curve.points[0].0, 0.0);
+        assert_eq!(curve.points[CURVE_POINTS].0, 60.0);
+        assert_eq!(curve.elapsed_secs, 60.0);
+
+        let concurrent = LoadCurve::sample(&LoadModel::Concurrent, 60.0, 0.0, Some(5.0));
+        assert!(concurrent.points.is_empty());
+        assert_eq!(concurrent.rps_override, Some(5.0));
+        assert!(INDEX_HTML.contains("/live"));
+    }
+}
@@ -1,8 +1,11 @@
+use std::net::SocketAddr;
 use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
 
+use rand::Rng;
 use tokio::sync::watch;
 use tokio::time::{self, Duration, Instant};
-use tracing::{debug, error, info};
+use tracing::{debug, error, info, info_span, Instrument};
 
 /// Atomic counter for deterministic percentile sampling (Issue #70).
 static SAMPLE_COUNTER: AtomicU64 = AtomicU64::new(0);
@@ -20,22 +23,103 @@ fn should_sample(rate: u8) -> bool {
     counter % 100 < rate as u64
 }
 
+use crate::chaos;
 use crate::client::{build_client, ClientConfig};
-use crate::connection_pool::GLOBAL_POOL_STATS;
+use crate::client_profiles;
+use crate::connection_pool::{self, GLOBAL_POOL_STATS};
+use crate::debug_sample;
+use crate::dns_refresh;
+use crate::dns_round_robin;
 use crate::errors::ErrorCategory;
 use crate::executor::{ScenarioExecutor, SessionStore};
+use crate::fd_limits;
+use crate::in_flight_limit;
+use crate::ip_family::{self, IpFamily};
+use crate::live_stream;
 use crate::load_models::LoadModel;
 use crate::memory_guard::is_percentile_tracking_active;
-use crate::metrics::{
-    CONCURRENT_REQUESTS, REQUEST_DURATION_SECONDS, REQUEST_ERRORS_BY_CATEGORY,
-    REQUEST_STATUS_CODES, REQUEST_TOTAL, SCENARIO_REQUESTS_TOTAL,
-};
+use crate::metric_batch::WorkerMetrics;
+use crate::metrics::{CONCURRENT_REQUESTS, SCENARIO_REQUESTS_TOTAL, TEMPLATE_REQUESTS_TOTAL};
 use crate::percentiles::{
     GLOBAL_REQUEST_PERCENTILES, GLOBAL_SCENARIO_PERCENTILES, GLOBAL_STEP_PERCENTILES,
 };
+use crate::rate_budget;
+use crate::rate_limit;
+use crate::rps_override;
+use crate::run_id;
 use crate::scenario::{Scenario, ScenarioContext};
+use crate::seed;
+use crate::service_discovery;
+use crate::shared_state;
+use crate::sni::SniOverride;
+use crate::templates::TemplateMix;
 use crate::throughput::GLOBAL_THROUGHPUT_TRACKER;
 
+/// Start-up ramp and pacing jitter shared by all workers of a run.
+///
+/// Without these every worker starts at the same instant and fires on an
+/// identical schedule, so requests arrive in periodic bursts. `ramp` spreads
+/// worker start times linearly over the ramp-in period (`WORKER_RAMP`), and
+/// `jitter_percent` randomizes each worker's start offset and every pacing
+/// interval by up to ±N% of the cycle (`WORKER_JITTER_PERCENT`). The jitter is
+/// symmetric, so the mean request rate is unchanged.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct WorkerPacing {
+    pub ramp: Duration,
+    pub jitter_percent: f64,
+}
+
+impl WorkerPacing {
+    /// Delay before worker `task_id` of `num_tasks` fires its first request:
+    /// its slot in the ramp-in period, plus its evenly staggered position in
+    /// the first cycle, plus a random offset of up to `jitter_percent` of a cycle.
+    pub fn start_offset(&self, task_id: usize, num_tasks: usize, cycle: Duration) -> Duration {
+        let num_tasks = num_tasks.max(1) as u32;
+        let ramp_offset = self.ramp * task_id as u32 / num_tasks;
+        let stagger = cycle * task_id as u32 / num_tasks;
+        let jitter = if self.jitter_percent > 0.0 {
+            let draw = seed::with_rng(|rng| rng.gen::<f64>());
+            cycle.mul_f64(draw * self.jitter_percent / 100.0)
+        } else {
+            Duration::ZERO
+        };
+        ramp_offset + stagger + jitter
+    }
+
+    /// Pacing interval for the next request: `cycle` scaled by a random
+    /// factor in `[1 - jitter, 1 + jitter]`.
+    pub fn jittered(&self, cycle: Duration) -> Duration {
+        if self.jitter_percent <= 0.0 {
+            return cycle;
+        }
+        let spread = (self.jitter_percent / 100.0).min(1.0);
+        let draw = seed::with_rng(|rng| rng.gen::<f64>());
+        let factor = 1.0 + spread * (draw * 2.0 - 1.0);
+        cycle.mul_f64(factor)
+    }
+}
+
+/// One worker's share of the target rate: the time between its requests.
+pub(crate) fn worker_cycle(target_rps: f64, num_tasks: usize) -> Option<Duration> {
+    if target_rps > 0.0 && target_rps.is_finite() {
+        let cycle_ms = (num_tasks as f64 * 1000.0 / target_rps).round() as u64;
+        Some(Duration::from_millis(cycle_ms))
+    } else {
+        None
+    }
+}
+
+/// How long a worker idles at 0 RPS before checking the target again. An
+/// override can lift the rate at any moment, so it is polled every second;
+/// otherwise 0 RPS lasts until the workers are replaced.
+pub(crate) fn idle_pause() -> Duration {
+    if rps_override::get().is_some() {
+        Duration::from_secs(1)
+    } else {
+        Duration::from_secs(3600)
+    }
+}
+
 /// Configuration for a worker task.
 pub struct WorkerConfig {
     pub task_id: usize,
@@ -52,6 +136,8 @@ pub struct WorkerConfig {
     /// In standalone mode this is "local"; in cluster mode it is the node's
     /// geographic region (e.g. "us-central1").
     pub region: String,
+    /// Availability zone label (`ZONE`), attached next to `region`.
+    pub zone: String,
     /// Optional tenant identifier. Empty string when no tenant is configured.
     /// Attached as a `tenant` label to all request metrics so per-tenant
     /// request counts can be queried from Prometheus for billing.
@@ -63,6 +149,14 @@ pub struct WorkerConfig {
     /// if absent from the YAML `metadata.run_id` field.  Attached as a
     /// `run_id` label so sequential tests on the same node can be isolated.
     pub run_id: String,
+    /// Start-up ramp and pacing jitter.
+    pub pacing: WorkerPacing,
+    /// Resolved target address this worker's client is pinned to
+    /// (`DNS_ROUND_ROBIN`); labels the per-IP metrics.
+    pub target_addr: Option<SocketAddr>,
+    /// Weighted request templates (`TEMPLATE_DIR`). When set, each request is
+    /// drawn from the mix and `url` is the base for relative template paths.
+    pub templates: Option<Arc<TemplateMix>>,
     /// Graceful-stop signal (Issue #79).  When the sender fires `true` the
     /// worker finishes its current request and exits at the top of the next
     /// loop iteration so no in-flight request is aborted.
@@ -70,38 +164,67 @@ pub struct WorkerConfig {
 }
 
 /// Runs a single worker task that sends HTTP requests according to the load model.
+///
+/// Runs inside a `worker` span carrying `task_id`, `run_id`, `region` and
+/// `zone` so every event the worker emits can be attributed in structured logs.
 pub async fn run_worker(client: reqwest::Client, config: WorkerConfig, start_time: Instant) {
+    let span = info_span!(
+        "worker",
+        task_id = config.task_id,
+        run_id = %config.run_id,
+        region = %config.region,
+        zone = %config.zone,
+    );
+    let task_id = config.task_id;
+    seed::scope(
+        "worker",
+        task_id,
+        worker_loop(client, config, start_time).instrument(span),
+    )
+    .await
+}
+
+async fn worker_loop(client: reqwest::Client, config: WorkerConfig, start_time: Instant) {
     debug!(
         task_id = config.task_id,
         url = %config.url,
         load_model = ?config.load_model,
         "Worker starting"
     );
+    let endpoint = rate_limit::url_endpoint(&config.url);
 
-    // Stagger worker start times evenly across one target cycle.
+    // Stagger worker start times evenly across one target cycle, offset by the
+    // worker's slot in the ramp-in period and a random jitter.
     // Without staggering all N workers fire simultaneously at t=0, creating burst
     // waves that repeat every cycle — distorting RPS measurements and overloading
     // the target. Spreading start times gives a smooth, continuous request rate.
-    let initial_rps = config
-        .load_model
-        .calculate_current_rps(0.0, config.test_duration.as_secs_f64());
-    let initial_stagger = if initial_rps > 0.0 && initial_rps.is_finite() {
-        let cycle_ms = (config.num_concurrent_tasks as f64 * 1000.0 / initial_rps).round() as u64;
-        let stagger_ms = (config.task_id as u64 * cycle_ms) / config.num_concurrent_tasks as u64;
-        Duration::from_millis(stagger_ms)
-    } else {
-        Duration::ZERO
-    };
+    let initial_rps =
+        rps_override::target_rps(&config.load_model, 0.0, config.test_duration.as_secs_f64());
+    let initial_cycle = worker_cycle(initial_rps, config.num_concurrent_tasks).unwrap_or_default();
+    let initial_stagger =
+        config
+            .pacing
+            .start_offset(config.task_id, config.num_concurrent_tasks, initial_cycle);
 
     // next_fire is the absolute time at which the worker should fire its next request.
     // Using absolute time (sleep_until) instead of relative sleep (sleep(remaining_ms))
     // eliminates integer truncation error and self-corrects for timer overshoot.
     let mut next_fire = time::Instant::now() + initial_stagger;
 
+    // Request counters and latencies, flushed to the registry in batches
+    let mut metrics = WorkerMetrics::new(
+        &config.region,
+        &config.zone,
+        &config.tenant,
+        &config.node_id,
+        &config.run_id,
+    );
+
     loop {
         // Wait until the next scheduled fire time.
         // If the previous request ran long and next_fire is already in the past,
         // sleep_until returns immediately — the worker naturally catches up.
+        metrics.flush_before_sleep(next_fire);
         time::sleep_until(next_fire).await;
 
         // Graceful-stop check (Issue #79): exit between requests so no
@@ -130,81 +253,152 @@ pub async fn run_worker(client: reqwest::Client, config: WorkerConfig, start_tim
         // Advance next_fire by one cycle based on the CURRENT target RPS.
         // Doing this before the request means next_fire drifts forward by exactly
         // one cycle period regardless of how long the request actually takes.
-        let current_target_rps = config
-            .load_model
-            .calculate_current_rps(elapsed_total_secs, config.test_duration.as_secs_f64());
-
-        if current_target_rps > 0.0 && current_target_rps.is_finite() {
-            let cycle_ms =
-                (config.num_concurrent_tasks as f64 * 1000.0 / current_target_rps).round() as u64;
-            next_fire += Duration::from_millis(cycle_ms);
+        let current_target_rps = rps_override::target_rps(
+            &config.load_model,
+            elapsed_total_secs,
+            config.test_duration.as_secs_f64(),
+        );
+
+        if let Some(cycle) = worker_cycle(current_target_rps, config.num_concurrent_tasks) {
+            next_fire += config.pacing.jittered(cycle);
         } else {
             // Concurrent model (f64::MAX) or 0 RPS: don't advance — sleep_until fires
             // immediately next iteration (Concurrent) or we set a long pause (0 RPS).
             if current_target_rps == 0.0 {
-                next_fire = now + Duration::from_secs(3600);
+                next_fire = now + idle_pause();
                 // rps=0 means idle standby — skip request entirely and wait for the next cycle.
                 continue;
             }
             // For Concurrent (f64::MAX), next_fire stays in the past → fires immediately.
         }
 
+        // Chaos: a dropped request is never sent and not counted as a request.
+        let faults = chaos::next_request_faults();
+        if faults.drop {
+            debug!(
+                task_id = config.task_id,
+                "Chaos: request dropped before sending"
+            );
+            // Concurrent model fires immediately; yield so drops can't starve the runtime.
+            tokio::task::yield_now().await;
+            continue;
+        }
+
+        // Hard caps (MAX_TOTAL_REQUESTS / HARD_MAX_RPS) override the load model.
+        // Taken before the in-flight slot so no slot is held while HARD_MAX_RPS
+        // makes the request wait.
+        if !rate_budget::acquire().await {
+            info!(
+                task_id = config.task_id,
+                "Worker stopping: MAX_TOTAL_REQUESTS reached"
+            );
+            break;
+        }
+
+        // In-flight cap (MAX_IN_FLIGHT); the slot is held until the response
+        // is handled. Under BACKPRESSURE=shed a saturated cap drops the request,
+        // which then doesn't count against MAX_TOTAL_REQUESTS.
+        let Some(_in_flight_slot) = in_flight_limit::admit_request().await else {
+            rate_budget::refund();
+            tokio::task::yield_now().await;
+            continue;
+        };
+
         // Track metrics
         CONCURRENT_REQUESTS
             .with_label_values(&[
                 &config.region,
+                &config.zone,
                 &config.tenant,
                 &config.node_id,
                 &config.run_id,
             ])
             .inc();
-        REQUEST_TOTAL
-            .with_label_values(&[
-                &config.region,
-                &config.tenant,
-                &config.node_id,
-                &config.run_id,
-            ])
-            .inc();
+        metrics.request();
 
         let request_start_time = time::Instant::now();
 
-        // Build and send request
-        let req = build_request(&client, &config);
+        // Build and send request. Debug-sampled requests are built first so
+        // the exact request (after client defaults) can be logged.
+        let url = service_discovery::rewrite(&config.url);
+        let template = config.templates.as_ref().map(|mix| mix.sample());
+        let request = match template {
+            Some(t) => t.build_request(&client, &url),
+            None => build_request(&client, &config, &url),
+        };
+        let req = faults.apply(dns_refresh::apply(run_id::apply(request, &config.run_id)));
+        let _in_flight = connection_pool::track_in_flight();
+        let sampled = debug_sample::should_sample();
+        let send_result = if sampled {
+            match req.build() {
+                Ok(request) => {
+                    debug_sample::log_request(&format!("task {}", config.task_id), &request);
+                    client.execute(request).await
+                }
+                Err(e) => Err(e),
+            }
+        } else {
+            req.send().await
+        };
 
-        match req.send().await {
+        let mut target_ip = config.target_addr.map(|addr| addr.ip());
+        let mut target_ip_status = "error";
+        match send_result {
             Ok(mut response) => {
                 let status = response.status().as_u16();
                 // Use static strings to avoid a heap allocation on every request
                 let status_str = status_code_label(status);
-                REQUEST_STATUS_CODES
-                    .with_label_values(&[
-                        status_str,
-                        &config.region,
-                        &config.tenant,
-                        &config.node_id,
-                        &config.run_id,
-                    ])
-                    .inc();
+                let remote_ip = response.remote_addr().map(|addr| addr.ip());
+                ip_family::record(remote_ip, &config.node_id, &config.run_id);
+                target_ip = remote_ip.or(target_ip);
+                target_ip_status = status_str;
+                rate_limit::observe(
+                    template.map_or(endpoint.as_str(), |t| t.name.as_str()),
+                    status,
+                    response.headers(),
+                    &config.node_id,
+                    &config.run_id,
+                );
+                metrics.status(status_str);
 
                 // Categorize HTTP errors (Issue #34)
                 if let Some(category) = ErrorCategory::from_status_code(status) {
-                    REQUEST_ERRORS_BY_CATEGORY
-                        .with_label_values(&[
-                            category.label(),
-                            &config.region,
-                            &config.tenant,
-                            &config.node_id,
-                            &config.run_id,
-                        ])
-                        .inc();
+                    metrics.error(category.label());
+                    live_stream::record_failure(
+                        "",
+                        template.map_or(endpoint.as_str(), |t| t.name.as_str()),
+                        Some(status),
+                        category.label(),
+                    );
                 }
 
                 // Issue #74: CRITICAL - Must consume response body in chunks to prevent buffering
                 // At 50K RPS, unconsumed bodies accumulate in memory causing rapid OOM
                 // Stream and discard body without allocating full buffer
-                while let Ok(Some(_chunk)) = response.chunk().await {
-                    // Chunk read and immediately dropped - minimal memory footprint
+                let sample_headers = sampled.then(|| response.headers().clone());
+                let mut sample_body = Vec::new();
+                let mut body_len = 0;
+                while let Ok(Some(chunk)) = response.chunk().await {
+                    // Chunk read and immediately dropped - minimal memory footprint.
+                    // Only a debug sample keeps a bounded prefix.
+                    if sampled {
+                        body_len += chunk.len();
+                        debug_sample::capture_chunk(&mut sample_body, &chunk);
+                    }
+                    // Chaos: dropping the response mid-body closes the connection.
+                    if faults.abort_response {
+                        break;
+                    }
+                }
+                if let Some(headers) = sample_headers {
+                    debug_sample::log_response(
+                        &format!("task {}", config.task_id),
+                        status,
+                        &headers,
+                        &sample_body,
+                        body_len,
+                        request_start_time.elapsed().as_millis() as u64,
+                    );
                 }
 
                 debug!(
@@ -216,27 +410,21 @@ pub async fn run_worker(client: reqwest::Client, config: WorkerConfig, start_tim
                 );
             }
             Err(e) => {
-                REQUEST_STATUS_CODES
-                    .with_label_values(&[
-                        "error",
-                        &config.region,
-                        &config.tenant,
-                        &config.node_id,
-                        &config.run_id,
-                    ])
-                    .inc();
+                metrics.status("error");
 
                 // Categorize request error (Issue #34)
                 let error_category = ErrorCategory::from_reqwest_error(&e);
-                REQUEST_ERRORS_BY_CATEGORY
-                    .with_label_values(&[
-                        error_category.label(),
-                        &config.region,
-                        &config.tenant,
-                        &config.node_id,
-                        &config.run_id,
-                    ])
-                    .inc();
+                metrics.error(error_category.label());
+
+                if error_category == ErrorCategory::ResourceExhausted {
+                    fd_limits::log_exhaustion_hint(&e);
+                }
+                live_stream::record_failure(
+                    "",
+                    template.map_or(endpoint.as_str(), |t| t.name.as_str()),
+                    None,
+                    &e.to_string(),
+                );
 
                 error!(
                     task_id = config.task_id,
@@ -250,17 +438,23 @@ pub async fn run_worker(client: reqwest::Client, config: WorkerConfig, start_tim
         }
 
         let actual_latency_ms = request_start_time.elapsed().as_millis() as u64;
-        REQUEST_DURATION_SECONDS
-            .with_label_values(&[
-                &config.region,
-                &config.tenant,
-                &config.node_id,
-                &config.run_id,
-            ])
-            .observe(request_start_time.elapsed().as_secs_f64());
+        metrics.duration(request_start_time.elapsed().as_secs_f64());
+        if let Some(t) = template {
+            TEMPLATE_REQUESTS_TOTAL
+                .with_label_values(&[&t.name, target_ip_status, &config.node_id, &config.run_id])
+                .inc();
+        }
+        dns_round_robin::record(
+            target_ip,
+            target_ip_status,
+            request_start_time.elapsed().as_secs_f64(),
+            &config.node_id,
+            &config.run_id,
+        );
         CONCURRENT_REQUESTS
             .with_label_values(&[
                 &config.region,
+                &config.zone,
                 &config.tenant,
                 &config.node_id,
                 &config.run_id,
@@ -278,6 +472,7 @@ pub async fn run_worker(client: reqwest::Client, config: WorkerConfig, start_tim
 
         // Record connection pool statistics (Issue #36)
         GLOBAL_POOL_STATS.record_request(actual_latency_ms);
+        metrics.maybe_flush();
 
         // No explicit sleep here — sleep_until(next_fire) at the top of the next
         // iteration handles all timing with sub-millisecond precision.
@@ -288,7 +483,7 @@ pub async fn run_worker(client: reqwest::Client, config: WorkerConfig, start_tim
 ///
 /// Avoids a heap `String` allocation on every request in the hot path.
 /// Uncommon codes fall back to "other" rather than allocating a unique string.
-fn status_code_label(code: u16) -> &'static str {
+pub(crate) fn status_code_label(code: u16) -> &'static str {
     match code {
         100 => "100",
         200 => "200",
@@ -315,11 +510,15 @@ fn status_code_label(code: u16) -> &'static str {
     }
 }
 
-fn build_request(client: &reqwest::Client, config: &WorkerConfig) -> reqwest::RequestBuilder {
+fn build_request(
+    client: &reqwest::Client,
+    config: &WorkerConfig,
+    url: &str,
+) -> reqwest::RequestBuilder {
     match config.request_type.as_str() {
-        "GET" => client.get(&config.url),
+        "GET" => client.get(url),
         "POST" => {
-            let req = client.post(&config.url);
+            let req = client.post(url);
             if config.send_json {
                 req.header("Content-Type", "application/json")
                     .body(config.json_payload.clone().unwrap_or_default())
@@ -328,7 +527,7 @@ fn build_request(client: &reqwest::Client, config: &WorkerConfig) -> reqwest::Re
             }
         }
         "PUT" => {
-            let req = client.put(&config.url);
+            let req = client.put(url);
             if config.send_json {
                 req.header("Content-Type", "application/json")
                     .body(config.json_payload.clone().unwrap_or_default())
@@ -337,7 +536,7 @@ fn build_request(client: &reqwest::Client, config: &WorkerConfig) -> reqwest::Re
             }
         }
         "PATCH" => {
-            let req = client.patch(&config.url);
+            let req = client.patch(url);
             if config.send_json {
                 req.header("Content-Type", "application/json")
                     .body(config.json_payload.clone().unwrap_or_default())
@@ -345,15 +544,15 @@ fn build_request(client: &reqwest::Client, config: &WorkerConfig) -> reqwest::Re
                 req
             }
         }
-        "DELETE" => client.delete(&config.url),
-        "HEAD" => client.head(&config.url),
-        "OPTIONS" => client.request(reqwest::Method::OPTIONS, &config.url),
+        "DELETE" => client.delete(url),
+        "HEAD" => client.head(url),
+        "OPTIONS" => client.request(reqwest::Method::OPTIONS, url),
         _ => {
             error!(
                 request_type = %config.request_type,
                 "Unsupported request type, falling back to GET"
             );
-            client.get(&config.url)
+            client.get(url)
         }
     }
 }
@@ -370,6 +569,8 @@ pub struct ScenarioWorkerConfig {
     pub percentile_sampling_rate: u8,
     /// Region label attached to all metrics emitted by this worker (Issue #45).
     pub region: String,
+    /// Availability zone label (`ZONE`), attached next to `region`.
+    pub zone: String,
     /// Optional tenant identifier. Empty string when no tenant is configured.
     pub tenant: String,
     /// Node identifier (Issue #106).
@@ -378,8 +579,24 @@ pub struct ScenarioWorkerConfig {
     pub run_id: String,
     /// Skip TLS certificate verification (propagated from global config).
     pub skip_tls_verify: bool,
-    /// DNS override string in `hostname:ip:port` format (propagated from global config).
+    /// DNS override string, comma-separated `hostname:ip:port` entries (propagated from global config).
     pub resolve_target_addr: Option<String>,
+    /// Source address for this worker's connections (from `LOCAL_ADDRS`).
+    pub local_address: Option<std::net::IpAddr>,
+    /// Resolved address of the base URL's host to pin this worker's client
+    /// to (`DNS_ROUND_ROBIN`); labels the per-IP metrics.
+    pub target_addr: Option<SocketAddr>,
+    /// Address family for this worker's connections (`IP_FAMILY`).
+    pub ip_family: IpFamily,
+    /// Start-up ramp and pacing jitter.
+    pub pacing: WorkerPacing,
+    /// Cache TLS sessions for resumption (`TLS_SESSION_RESUMPTION`).
+    pub tls_session_resumption: bool,
+    /// Server name presented instead of the connect host (`TLS_SERVER_NAME`).
+    pub sni_override: Option<SniOverride>,
+    /// Executions left for this scenario, shared by all of its workers
+    /// (`iterations`); the worker stops when it reaches zero.
+    pub iteration_budget: Option<Arc<AtomicU64>>,
 }
 
 /// Runs a scenario-based worker task that executes multi-step scenarios according to the load model.
@@ -393,6 +610,38 @@ pub struct ScenarioWorkerConfig {
 /// worker config (DNS override, TLS settings). This ensures cookies from one virtual
 /// user don't leak to another while preserving global client settings.
 pub async fn run_scenario_worker(config: ScenarioWorkerConfig, start_time: Instant) {
+    let span = info_span!(
+        "scenario_worker",
+        task_id = config.task_id,
+        scenario = %config.scenario.name,
+        run_id = %config.run_id,
+        region = %config.region,
+        zone = %config.zone,
+    );
+    let scenario = config.scenario.name.clone();
+    seed::scope(
+        &scenario,
+        config.task_id,
+        scenario_worker_loop(config, start_time).instrument(span),
+    )
+    .await
+}
+
+/// Target scenario rate for all of this scenario's workers: its own `rps`
+/// when set (not affected by the `POST /rps` override), otherwise the load
+/// model's.
+fn scenario_target_sps(config: &ScenarioWorkerConfig, elapsed_secs: f64) -> f64 {
+    match config.scenario.rps {
+        Some(rps) => crate::runner::runner_rps(rps),
+        None => rps_override::target_rps(
+            &config.load_model,
+            elapsed_secs,
+            config.test_duration.as_secs_f64(),
+        ),
+    }
+}
+
+async fn scenario_worker_loop(config: ScenarioWorkerConfig, start_time: Instant) {
     debug!(
         task_id = config.task_id,
         scenario = %config.scenario.name,
@@ -401,28 +650,40 @@ pub async fn run_scenario_worker(config: ScenarioWorkerConfig, start_time: Insta
         "Scenario worker starting"
     );
 
-    // Stagger worker start times evenly across one target cycle (same rationale as run_worker).
-    let initial_sps = config
-        .load_model
-        .calculate_current_rps(0.0, config.test_duration.as_secs_f64());
-    let initial_stagger = if initial_sps > 0.0 && initial_sps.is_finite() {
-        let cycle_ms = (config.num_concurrent_tasks as f64 * 1000.0 / initial_sps).round() as u64;
-        let stagger_ms = (config.task_id as u64 * cycle_ms) / config.num_concurrent_tasks as u64;
-        Duration::from_millis(stagger_ms)
-    } else {
-        Duration::ZERO
-    };
+    // Stagger worker start times (same rationale as run_worker).
+    let initial_sps = scenario_target_sps(&config, 0.0);
+    let initial_cycle = worker_cycle(initial_sps, config.num_concurrent_tasks).unwrap_or_default();
+    let initial_stagger =
+        config
+            .pacing
+            .start_offset(config.task_id, config.num_concurrent_tasks, initial_cycle);
 
     let mut next_fire = time::Instant::now() + initial_stagger;
 
     // Session store persists across iterations for this worker.
     // Steps with `cache: { ttl }` store their extracted variables here so
     // subsequent iterations skip the HTTP request until the TTL expires.
-    let mut session = SessionStore::new();
+    let mut session = SessionStore::for_vu(config.task_id);
+
+    // The worker is one virtual user: its context keeps user-scoped
+    // variables across iterations, and it keeps one client profile for the run.
+    let mut context = ScenarioContext::new();
+    let client_profile = client_profiles::client_profiles().sample();
+
+    // Request counters and latencies, flushed to the registry in batches
+    let mut metrics = WorkerMetrics::new(
+        &config.region,
+        &config.zone,
+        &config.tenant,
+        &config.node_id,
+        &config.run_id,
+    );
 
     // Build the HTTP client once per worker with DNS override, TLS, and cookie store enabled.
     // Building once avoids log flooding and expensive reconstruction on every loop iteration.
-    let worker_client = build_client(&ClientConfig {
+    // A scenario `client:` block layers its TLS, headers, proxy and timeout
+    // on top, giving the scenario a dedicated client.
+    let mut client_config = ClientConfig {
         skip_tls_verify: config.skip_tls_verify,
         resolve_target_addr: config.resolve_target_addr.clone(),
         client_cert_path: None,
@@ -430,14 +691,41 @@ pub async fn run_scenario_worker(config: ScenarioWorkerConfig, start_time: Insta
         custom_headers: None,
         pool_config: None,
         cookie_store: true,
-    })
-    .map(|r| r.client)
-    .unwrap_or_else(|e| {
-        error!(error = %e, "Failed to build scenario worker client; falling back to default");
-        reqwest::Client::new()
-    });
+        local_address: config.local_address,
+        pinned_target: config.target_addr.and_then(|addr| {
+            reqwest::Url::parse(&config.base_url)
+                .ok()
+                .and_then(|u| u.host_str().map(|h| (h.to_string(), addr)))
+        }),
+        ip_family: config.ip_family,
+        tls_session_resumption: config.tls_session_resumption,
+        sni_override: config.sni_override.clone(),
+        proxy: None,
+        timeout: None,
+    };
+    if let Some(overrides) = &config.scenario.client {
+        overrides.apply(&mut client_config);
+    }
+    let worker_client = match build_client(&client_config) {
+        Ok(result) => result.client,
+        // Falling back to a default client would silently drop the
+        // scenario's own TLS identity or proxy.
+        Err(e) if config.scenario.client.is_some() => {
+            error!(
+                error = %e,
+                scenario = %config.scenario.name,
+                "Failed to build the scenario's client; worker not started"
+            );
+            return;
+        }
+        Err(e) => {
+            error!(error = %e, "Failed to build scenario worker client; falling back to default");
+            reqwest::Client::new()
+        }
+    };
 
     loop {
+        metrics.flush_before_sleep(next_fire);
         time::sleep_until(next_fire).await;
 
         let now = time::Instant::now();
@@ -454,31 +742,60 @@ pub async fn run_scenario_worker(config: ScenarioWorkerConfig, start_time: Insta
             break;
         }
 
+        if rate_budget::is_exhausted() {
+            info!(
+                task_id = config.task_id,
+                scenario = %config.scenario.name,
+                "Scenario worker stopping: MAX_TOTAL_REQUESTS reached"
+            );
+            break;
+        }
+
         // Advance next_fire by one cycle based on current target SPS.
-        let current_target_sps = config
-            .load_model
-            .calculate_current_rps(elapsed_total_secs, config.test_duration.as_secs_f64());
-
-        if current_target_sps > 0.0 && current_target_sps.is_finite() {
-            let cycle_ms =
-                (config.num_concurrent_tasks as f64 * 1000.0 / current_target_sps).round() as u64;
-            next_fire += Duration::from_millis(cycle_ms);
+        let current_target_sps = scenario_target_sps(&config, elapsed_total_secs);
+
+        if let Some(cycle) = worker_cycle(current_target_sps, config.num_concurrent_tasks) {
+            next_fire += config.pacing.jittered(cycle);
         } else if current_target_sps == 0.0 {
-            next_fire = now + Duration::from_secs(3600);
+            next_fire = now + idle_pause();
             // rps=0 means idle standby — skip scenario execution entirely and wait for the next cycle.
             continue;
         }
 
+        // BACKPRESSURE=shed/degrade: skip this iteration while MAX_IN_FLIGHT
+        // is saturated rather than queue behind it.
+        if in_flight_limit::shed_iteration(&config.scenario.name, config.scenario.optional) {
+            tokio::task::yield_now().await;
+            continue;
+        }
+
+        // Bounded scenarios (`iterations`) stop once every execution is claimed.
+        if let Some(budget) = &config.iteration_budget {
+            if budget
+                .fetch_update(Ordering::Relaxed, Ordering::Relaxed, |n| n.checked_sub(1))
+                .is_err()
+            {
+                info!(
+                    task_id = config.task_id,
+                    scenario = %config.scenario.name,
+                    "Scenario worker stopping: iterations completed"
+                );
+                break;
+            }
+        }
+
         // Create executor with the worker's configured client
         let executor = ScenarioExecutor::new(
-            config.base_url.clone(),
+            service_discovery::rewrite(&config.base_url).into_owned(),
             worker_client.clone(),
             config.node_id.clone(),
             config.run_id.clone(),
-        );
+        )
+        .with_client_profile(client_profile.clone());
 
-        // Create new context for this scenario execution
-        let mut context = ScenarioContext::new();
+        // Start a fresh iteration, seeded with the test's global variables
+        // and this worker's user-scoped ones
+        context.begin_iteration(&shared_state::global_variables());
 
         // Execute the scenario
         let result = executor
@@ -503,7 +820,8 @@ pub async fn run_scenario_worker(config: ScenarioWorkerConfig, start_time: Insta
             GLOBAL_SCENARIO_PERCENTILES.record(&config.scenario.name, result.total_time_ms);
 
             // Record individual step latencies (Issue #33, #66, #70, #72)
-            for step in &result.steps {
+            // Cache hits sent no request; their 0 ms would skew step latency.
+            for step in result.steps.iter().filter(|s| !s.cache_hit) {
                 let label = format!("{}:{}", config.scenario.name, step.step_name);
                 GLOBAL_STEP_PERCENTILES.record(&label, step.response_time_ms);
             }
@@ -517,33 +835,34 @@ pub async fn run_scenario_worker(config: ScenarioWorkerConfig, start_time: Insta
             if step.cache_hit {
                 continue;
             }
-            REQUEST_TOTAL
-                .with_label_values(&[
-                    &config.region,
-                    &config.tenant,
-                    &config.node_id,
-                    &config.run_id,
-                ])
-                .inc();
+            if !step.success {
+                live_stream::record_failure(
+                    &config.scenario.name,
+                    &step.step_name,
+                    step.status_code,
+                    step.error.as_deref().unwrap_or("step failed"),
+                );
+            }
+            metrics.step_latency(
+                &config.scenario.name,
+                &step.step_name,
+                step.response_time_ms,
+            );
+            // Retried attempts were sent too; only the last one is reported.
+            for _ in 0..=step.retries {
+                metrics.request();
+            }
             if let Some(code) = step.status_code {
-                REQUEST_STATUS_CODES
-                    .with_label_values(&[
-                        status_code_label(code),
-                        &config.region,
-                        &config.tenant,
-                        &config.node_id,
-                        &config.run_id,
-                    ])
-                    .inc();
+                metrics.status(status_code_label(code));
             }
-            REQUEST_DURATION_SECONDS
-                .with_label_values(&[
-                    &config.region,
-                    &config.tenant,
-                    &config.node_id,
-                    &config.run_id,
-                ])
-                .observe(step.response_time_ms as f64 / 1000.0);
+            metrics.duration(step.response_time_ms as f64 / 1000.0);
+            dns_round_robin::record(
+                config.target_addr.map(|addr| addr.ip()),
+                step.status_code.map_or("error", status_code_label),
+                step.response_time_ms as f64 / 1000.0,
+                &config.node_id,
+                &config.run_id,
+            );
         }
 
         // Record throughput (Issue #35)
@@ -559,6 +878,7 @@ pub async fn run_scenario_worker(config: ScenarioWorkerConfig, start_time: Insta
             &config.scenario.name,
             std::time::Duration::from_millis(result.total_time_ms),
         );
+        metrics.maybe_flush();
 
         // No explicit sleep — sleep_until(next_fire) at the top handles timing.
     }
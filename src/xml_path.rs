@@ -0,0 +1,417 @@
+//! XPath-style queries over XML response bodies (`xmlPath`).
+//!
+//! Supports the subset of XPath 1.0 that load tests need to pull values out
+//! of SOAP and other XML APIs, evaluated with `roxmltree`:
+//!
+//! - absolute paths: `/Envelope/Body/GetUserResponse/id`
+//! - descendants: `//id`, `/Envelope//id`
+//! - wildcards: `/Envelope/*/id`
+//! - predicates: `[2]`, `[last()]`, `[@type]`, `[@type='admin']`, `[name='x']`
+//! - terminal attribute and text selectors: `//user/@id`, `//name/text()`
+//!
+//! Namespace prefixes in the path are ignored and elements are matched by
+//! local name, so `/soap:Envelope/soap:Body` works without declaring
+//! namespaces. Results are trimmed string values in document order.
+
+use roxmltree::{Document, Node};
+use thiserror::Error;
+
+/// Errors from parsing or evaluating an `xmlPath`.
+#[derive(Error, Debug)]
+pub enum XmlPathError {
+    #[error("Invalid XPath '{path}': {message}")]
+    InvalidPath { path: String, message: String },
+
+    #[error("Invalid XML: {0}")]
+    InvalidXml(String),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Axis {
+    Child,
+    Descendant,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Predicate {
+    Index(usize),
+    Last,
+    HasAttr(String),
+    AttrEquals(String, String),
+    ChildEquals(String, String),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct LocationStep {
+    axis: Axis,
+    /// Local name to match, or `None` for `*`.
+    name: Option<String>,
+    predicates: Vec<Predicate>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Terminal {
+    Element,
+    Attribute(Axis, String),
+    Text,
+}
+
+/// A parsed `xmlPath` expression.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct XmlPath {
+    steps: Vec<LocationStep>,
+    terminal: Terminal,
+}
+
+impl XmlPath {
+    /// Parses an expression such as `/Envelope/Body//user[@id='7']/name`.
+    pub fn parse(path: &str) -> Result<Self, XmlPathError> {
+        let invalid = |message: &str| XmlPathError::InvalidPath {
+            path: path.to_string(),
+            message: message.to_string(),
+        };
+
+        let trimmed = path.trim();
+        if !trimmed.starts_with('/') {
+            return Err(invalid("must start with '/' or '//'"));
+        }
+
+        let mut steps = Vec::new();
+        let mut terminal = Terminal::Element;
+        let mut rest = trimmed;
+        while !rest.is_empty() {
+            if terminal != Terminal::Element {
+                return Err(invalid("'@attr' and 'text()' must be the last step"));
+            }
+            let axis = if let Some(r) = rest.strip_prefix("//") {
+                rest = r;
+                Axis::Descendant
+            } else if let Some(r) = rest.strip_prefix('/') {
+                rest = r;
+                Axis::Child
+            } else {
+                return Err(invalid("expected '/'"));
+            };
+
+            let end = step_end(rest).ok_or_else(|| invalid("unbalanced '[' or quote"))?;
+            let step = &rest[..end];
+            rest = &rest[end..];
+            if step.is_empty() {
+                return Err(invalid("empty step"));
+            }
+
+            if let Some(attr) = step.strip_prefix('@') {
+                if !is_name(attr) {
+                    return Err(invalid("invalid attribute name"));
+                }
+                terminal = Terminal::Attribute(axis, local_name(attr).to_string());
+            } else if step == "text()" {
+                if axis == Axis::Descendant {
+                    return Err(invalid("'//text()' is not supported"));
+                }
+                terminal = Terminal::Text;
+            } else {
+                steps.push(parse_step(axis, step).map_err(|m| invalid(&m))?);
+            }
+        }
+
+        if steps.is_empty() && !matches!(terminal, Terminal::Attribute(Axis::Descendant, _)) {
+            return Err(invalid("no element steps"));
+        }
+        Ok(Self { steps, terminal })
+    }
+
+    /// Evaluates the path against `doc`, returning string values in
+    /// document order.
+    pub fn query(&self, doc: &Document) -> Vec<String> {
+        let mut context = vec![doc.root()];
+        for step in &self.steps {
+            let mut next: Vec<Node> = Vec::new();
+            for node in &context {
+                let candidates: Vec<Node> = match step.axis {
+                    Axis::Child => node.children().filter(|n| step.matches(n)).collect(),
+                    Axis::Descendant => node
+                        .descendants()
+                        .skip(1)
+                        .filter(|n| step.matches(n))
+                        .collect(),
+                };
+                for n in step.filter(candidates) {
+                    if !next.iter().any(|m| m.id() == n.id()) {
+                        next.push(n);
+                    }
+                }
+            }
+            context = next;
+        }
+
+        match &self.terminal {
+            Terminal::Element => context.iter().map(string_value).collect(),
+            Terminal::Text => context
+                .iter()
+                .map(|n| {
+                    n.children()
+                        .filter(|c| c.is_text())
+                        .filter_map(|c| c.text())
+                        .collect::<String>()
+                        .trim()
+                        .to_string()
+                })
+                .collect(),
+            Terminal::Attribute(axis, name) => {
+                let mut values = Vec::new();
+                for node in &context {
+                    let elements: Vec<Node> = match axis {
+                        Axis::Child => vec![*node],
+                        Axis::Descendant => node.descendants().filter(|n| n.is_element()).collect(),
+                    };
+                    values.extend(elements.iter().filter_map(|n| attribute(n, name)));
+                }
+                values
+            }
+        }
+    }
+}
+
+impl LocationStep {
+    fn matches(&self, node: &Node) -> bool {
+        node.is_element()
+            && match &self.name {
+                Some(name) => node.tag_name().name() == name.as_str(),
+                None => true,
+            }
+    }
+
+    /// Applies predicates in order, each to the result of the previous one.
+    fn filter<'a, 'input>(&self, mut nodes: Vec<Node<'a, 'input>>) -> Vec<Node<'a, 'input>> {
+        for predicate in &self.predicates {
+            nodes = match predicate {
+                Predicate::Index(i) => nodes.get(i - 1).copied().into_iter().collect(),
+                Predicate::Last => nodes.last().copied().into_iter().collect(),
+                Predicate::HasAttr(name) => nodes
+                    .into_iter()
+                    .filter(|n| attribute(n, name).is_some())
+                    .collect(),
+                Predicate::AttrEquals(name, value) => nodes
+                    .into_iter()
+                    .filter(|n| attribute(n, name).as_deref() == Some(value.as_str()))
+                    .collect(),
+                Predicate::ChildEquals(name, value) => nodes
+                    .into_iter()
+                    .filter(|n| {
+                        n.children().any(|c| {
+                            c.is_element()
+                                && c.tag_name().name() == name.as_str()
+                                && string_value(&c) == *value
+                        })
+                    })
+                    .collect(),
+            };
+        }
+        nodes
+    }
+}
+
+/// Parses one location step such as `item[@type='book'][2]`.
+fn parse_step(axis: Axis, step: &str) -> Result<LocationStep, String> {
+    let (name, mut rest) = match step.find('[') {
+        Some(i) => (&step[..i], &step[i..]),
+        None => (step, ""),
+    };
+    let name = match name {
+        "*" => None,
+        n if is_name(n) => Some(local_name(n).to_string()),
+        _ => return Err(format!("invalid element name '{}'", name)),
+    };
+
+    let mut predicates = Vec::new();
+    while !rest.is_empty() {
+        let inner_end = rest
+            .find(']')
+            .filter(|_| rest.starts_with('['))
+            .ok_or_else(|| "malformed predicate".to_string())?;
+        predicates.push(parse_predicate(rest[1..inner_end].trim())?);
+        rest = &rest[inner_end + 1..];
+    }
+
+    Ok(LocationStep {
+        axis,
+        name,
+        predicates,
+    })
+}
+
+fn parse_predicate(expr: &str) -> Result<Predicate, String> {
+    if expr == "last()" {
+        return Ok(Predicate::Last);
+    }
+    if let Ok(index) = expr.parse::<usize>() {
+        return if index == 0 {
+            Err("positions start at 1".to_string())
+        } else {
+            Ok(Predicate::Index(index))
+        };
+    }
+    let (lhs, value) = match expr.split_once('=') {
+        Some((lhs, rhs)) => (lhs.trim(), Some(unquote(rhs.trim())?)),
+        None => (expr, None),
+    };
+    match (lhs.strip_prefix('@'), value) {
+        (Some(attr), None) if is_name(attr) => Ok(Predicate::HasAttr(local_name(attr).into())),
+        (Some(attr), Some(v)) if is_name(attr) => {
+            Ok(Predicate::AttrEquals(local_name(attr).into(), v))
+        }
+        (None, Some(v)) if is_name(lhs) => Ok(Predicate::ChildEquals(local_name(lhs).into(), v)),
+        _ => Err(format!("unsupported predicate '[{}]'", expr)),
+    }
+}
+
+fn unquote(s: &str) -> Result<String, String> {
+    let quoted = s.len() >= 2
+        && ((s.starts_with('\'') && s.ends_with('\'')) || (s.starts_with('"') && s.ends_with('"')));
+    if quoted {
+        Ok(s[1..s.len() - 1].to_string())
+    } else {
+        Err(format!("predicate value {} must be quoted", s))
+    }
+}
+
+/// Byte offset of the next top-level '/' (outside predicates and quotes).
+fn step_end(s: &str) -> Option<usize> {
+    let mut depth = 0usize;
+    let mut quote: Option<char> = None;
+    for (i, c) in s.char_indices() {
+        match (quote, c) {
+            (Some(q), c) if c == q => quote = None,
+            (Some(_), _) => {}
+            (None, '\'' | '"') => quote = Some(c),
+            (None, '[') => depth += 1,
+            (None, ']') => depth = depth.checked_sub(1)?,
+            (None, '/') if depth == 0 => return Some(i),
+            _ => {}
+        }
+    }
+    (depth == 0 && quote.is_none()).then_some(s.len())
+}
+
+fn is_name(s: &str) -> bool {
+    !s.is_empty()
+        && s.chars()
+            .all(|c| c.is_alphanumeric() || matches!(c, '_' | '-' | '.' | ':'))
+}
+
+/// `soap:Body` -> `Body`.
+fn local_name(name: &str) -> &str {
+    name.rsplit(':').next().unwrap_or(name)
+}
+
+fn attribute(node: &Node, name: &str) -> Option<String> {
+    node.attributes()
+        .find(|a| a.name() == name)
+        .map(|a| a.value().to_string())
+}
+
+/// XPath string value of an element: all descendant text, trimmed.
+fn string_value(node: &Node) -> String {
+    node.descendants()
+        .filter(|n| n.is_text())
+        .filter_map(|n| n.text())
+        .collect::<String>()
+        .trim()
+        .to_string()
+}
+
+/// Parses `xml` and evaluates `path` against it.
+pub fn select(xml: &str, path: &str) -> Result<Vec<String>, XmlPathError> {
+    let path = XmlPath::parse(path)?;
+    let doc = Document::parse(xml).map_err(|e| XmlPathError::InvalidXml(e.to_string()))?;
+    Ok(path.query(&doc))
+}
+
+/// Returns true if a `Content-Type` value denotes an XML body
+/// (`application/xml`, `text/xml`, `application/soap+xml`, ...).
+pub fn is_xml_content_type(content_type: &str) -> bool {
+    let mime = content_type
+        .split(';')
+        .next()
+        .unwrap_or("")
+        .trim()
+        .to_ascii_lowercase();
+    mime.ends_with("/xml") || mime.ends_with("+xml")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SOAP: &str = r#"<?xml version="1.0"?>
+<soap:Envelope xmlns:soap="http://schemas.xmlsoap.org/soap/envelope/">
+  <soap:Body>
+    <GetUsersResponse xmlns="urn:users">
+      <user id="1" role="admin"><name> Alice </name><token>abc</token></user>
+      <user id="2"><name>Bob</name></user>
+      <user id="3" role="admin"><name>Carol</name></user>
+    </GetUsersResponse>
+  </soap:Body>
+</soap:Envelope>"#;
+
+    #[test]
+    fn selects_by_absolute_and_descendant_paths() {
+        assert_eq!(
+            select(SOAP, "/soap:Envelope/soap:Body/GetUsersResponse/user/name").unwrap(),
+            vec!["Alice", "Bob", "Carol"]
+        );
+        assert_eq!(select(SOAP, "//token").unwrap(), vec!["abc"]);
+        assert_eq!(
+            select(SOAP, "/Envelope/*/GetUsersResponse/user/@id").unwrap(),
+            vec!["1", "2", "3"]
+        );
+        assert_eq!(select(SOAP, "//@role").unwrap(), vec!["admin", "admin"]);
+    }
+
+    #[test]
+    fn applies_predicates() {
+        assert_eq!(select(SOAP, "//user[2]/name").unwrap(), vec!["Bob"]);
+        assert_eq!(select(SOAP, "//user[last()]/@id").unwrap(), vec!["3"]);
+        assert_eq!(
+            select(SOAP, "//user[@role='admin']/name/text()").unwrap(),
+            vec!["Alice", "Carol"]
+        );
+        assert_eq!(select(SOAP, "//user[@role][2]/@id").unwrap(), vec!["3"]);
+        assert_eq!(
+            select(SOAP, r#"//user[name="Bob"]/@id"#).unwrap(),
+            vec!["2"]
+        );
+        assert!(select(SOAP, "//user[@id='9']").unwrap().is_empty());
+    }
+
+    #[test]
+    fn rejects_invalid_paths_and_xml() {
+        for path in [
+            "user/name",
+            "//user[",
+            "//user[@id=1]",
+            "//@id/name",
+            "//user[0]",
+            "/",
+        ] {
+            assert!(
+                matches!(XmlPath::parse(path), Err(XmlPathError::InvalidPath { .. })),
+                "{}",
+                path
+            );
+        }
+        assert!(matches!(
+            select("<a><b></a>", "//b"),
+            Err(XmlPathError::InvalidXml(_))
+        ));
+    }
+
+    #[test]
+    fn detects_xml_content_types() {
+        assert!(is_xml_content_type("application/xml"));
+        assert!(is_xml_content_type("text/xml; charset=utf-8"));
+        assert!(is_xml_content_type("application/soap+xml"));
+        assert!(!is_xml_content_type("application/json"));
+    }
+}
@@ -5,19 +5,38 @@
 //! reusable scenarios, and easier configuration management.
 
 use serde::{Deserialize, Serialize};
+use std::borrow::Cow;
+use std::collections::HashMap;
 use std::fs;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use std::time::Duration as StdDuration;
 use thiserror::Error;
 
+use crate::abort_threshold::AbortThreshold;
+use crate::addr;
+use crate::client::ClientOverrides;
+use crate::client_profiles::{ClientProfile, ClientProfiles};
 use crate::config_validation::{
     HttpMethodValidator, LoadModelValidator, RangeValidator, UrlValidator, ValidationContext,
 };
 use crate::config_version::VersionChecker;
-use crate::load_models::LoadModel;
+use crate::extractor::parse_transforms;
+use crate::header_layers::{AuthScheme, HeaderLayer, ScenarioHeaders};
+use crate::latency_sla::LatencySla;
+use crate::load_models::{LoadModel, LoadOverlay, OverlayOp};
+use crate::network_profile::NetworkProfile;
+use crate::phases::{self, Phase, PhaseSpec};
+use crate::rate_expression::RateExpression;
 use crate::scenario::{
-    Assertion, Extractor, RequestConfig, Scenario, Step, StepCache, VariableExtraction,
+    Assertion, Extractor, Paginate, ProtobufConfig, Rendezvous, RequestConfig, RetryPolicy,
+    Scenario, SharedAction, Step, StepCache, StepSlo, VariableExtraction, VariableScope,
 };
+use crate::scenario_slo::{ScenarioThreshold, SuccessRateThreshold, ThresholdError};
+use crate::service_discovery::{self, ServiceRef};
+use crate::shared_state::{self, StoreOp};
+use crate::substitution::Template;
+use crate::tcp_check::TcpCheck;
+use crate::traffic_calendar::TrafficCalendar;
 use crate::utils::parse_body_size;
 
 /// Errors that can occur when loading or parsing YAML configuration.
@@ -58,19 +77,24 @@ impl YamlDuration {
 /// Metadata about the test configuration.
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
 pub struct YamlMetadata {
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub description: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub author: Option<String>,
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub tags: Vec<String>,
     /// Optional tenant identifier for multi-tenant SaaS deployments.
     /// When set, all metrics emitted by this test run include a `tenant` label
     /// so per-tenant request counts can be queried from Prometheus.
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub tenant: Option<String>,
     /// Optional run identifier.  Auto-generated from the current Unix timestamp
     /// if absent.  Included as a `run_id` label on all request and scenario
     /// metrics so multiple sequential tests on the same node can be distinguished
     /// in Prometheus (Issue #106).
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub run_id: Option<String>,
 }
 
@@ -91,19 +115,36 @@ pub struct YamlGlobalConfig {
     #[serde(rename = "skipTlsVerify", default)]
     pub skip_tls_verify: bool,
 
-    #[serde(rename = "customHeaders")]
+    #[serde(rename = "customHeaders", skip_serializing_if = "Option::is_none")]
     pub custom_headers: Option<String>,
 
     /// DNS override: force hostname to resolve to a specific IP.
     /// Format: "hostname:ip:port"  e.g. "api.example.com:1.2.3.4:443"
+    /// or "api.example.com:[2001:db8::1]:443"; comma-separate several entries.
     /// Equivalent to the RESOLVE_TARGET_ADDR env var; env var takes precedence.
-    #[serde(rename = "resolveTargetAddr")]
+    #[serde(rename = "resolveTargetAddr", skip_serializing_if = "Option::is_none")]
     pub resolve_target_addr: Option<String>,
 
     /// Connection pool settings.  When omitted the pool uses env-var defaults
     /// (`POOL_MAX_IDLE_PER_HOST`, `POOL_IDLE_TIMEOUT_SECS`).
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub pool: Option<YamlPoolConfig>,
+    /// Directory of weighted request templates sampled by single-URL workers.
+    /// Equivalent to the TEMPLATE_DIR env var; env var takes precedence.
+    #[serde(rename = "templateDir", skip_serializing_if = "Option::is_none")]
+    pub template_dir: Option<String>,
+
+    /// TLS server name (SNI) to present instead of the baseUrl host, which
+    /// then becomes the connect address. Equivalent to the TLS_SERVER_NAME
+    /// env var; env var takes precedence.
+    #[serde(rename = "tlsServerName", skip_serializing_if = "Option::is_none")]
+    pub tls_server_name: Option<String>,
+
+    /// Wall-clock instant to start generating load at, as RFC 3339 or Unix
+    /// milliseconds, so every node of a cluster starts together. Equivalent
+    /// to the START_AT env var; env var takes precedence.
+    #[serde(rename = "startAt", skip_serializing_if = "Option::is_none")]
+    pub start_at: Option<String>,
 }
 
 /// Connection pool tuning exposed via YAML.
@@ -166,6 +207,14 @@ pub enum YamlLoadModel {
         max: f64,
         #[serde(rename = "rampDuration")]
         ramp_duration: YamlDuration,
+        #[serde(
+            rename = "alignToClock",
+            default,
+            skip_serializing_if = "std::ops::Not::not"
+        )]
+        align_to_clock: bool,
+        #[serde(rename = "cycleStart", skip_serializing_if = "Option::is_none")]
+        cycle_start: Option<String>,
     },
     #[serde(rename = "dailytraffic")]
     DailyTraffic {
@@ -187,13 +236,100 @@ pub enum YamlLoadModel {
             default = "default_evening_decline_ratio"
         )]
         evening_decline_ratio: f64,
+        /// Weekday/date multipliers file (see [`crate::traffic_calendar`]).
+        #[serde(rename = "calendarFile", skip_serializing_if = "Option::is_none")]
+        calendar_file: Option<String>,
+        #[serde(
+            rename = "alignToClock",
+            default,
+            skip_serializing_if = "std::ops::Not::not"
+        )]
+        align_to_clock: bool,
+        #[serde(rename = "cycleStart", skip_serializing_if = "Option::is_none")]
+        cycle_start: Option<String>,
     },
+    /// Rate from a math expression of elapsed time `t`
+    /// (see [`crate::rate_expression`]).
+    Expression {
+        rps: String,
+    },
+    /// Rate doubling every `doublingPeriod` until an abort threshold trips
+    /// (see [`LoadModel::Exponential`]).
+    Exponential {
+        #[serde(rename = "startRps")]
+        start_rps: f64,
+        #[serde(rename = "doublingPeriod")]
+        doubling_period: YamlDuration,
+        #[serde(rename = "maxRps", skip_serializing_if = "Option::is_none")]
+        max_rps: Option<f64>,
+        /// Percent of failing requests that stops the test
+        #[serde(rename = "abortErrorRate", skip_serializing_if = "Option::is_none")]
+        abort_error_rate: Option<f64>,
+        /// Mean latency in milliseconds that stops the test
+        #[serde(rename = "abortLatencyMs", skip_serializing_if = "Option::is_none")]
+        abort_latency_ms: Option<u64>,
+    },
+    /// A base model with add/multiply overlays (see [`LoadModel::Composite`]).
+    Composite {
+        base: Box<YamlLoadModel>,
+        #[serde(default)]
+        overlays: Vec<YamlLoadOverlay>,
+    },
+}
+
+/// One overlay of a composite load model.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct YamlLoadOverlay {
+    pub op: OverlayOp,
+    pub load: YamlLoadModel,
+}
+
+/// Time of day Ramp/DailyTraffic cycles start at with `alignToClock: true`:
+/// `cycleStart` (UTC `HH:MM[:SS]`), default midnight.
+fn clock_start(
+    align_to_clock: bool,
+    cycle_start: Option<&str>,
+) -> Result<Option<StdDuration>, YamlConfigError> {
+    if !align_to_clock {
+        return Ok(None);
+    }
+    match cycle_start {
+        Some(start) => crate::load_models::parse_time_of_day(start)
+            .map(Some)
+            .map_err(|e| YamlConfigError::Validation(format!("Invalid cycleStart: {}", e))),
+        None => Ok(Some(StdDuration::ZERO)),
+    }
 }
 
 impl YamlLoadModel {
     pub fn to_load_model(&self) -> Result<LoadModel, YamlConfigError> {
         match self {
             YamlLoadModel::Concurrent => Ok(LoadModel::Concurrent),
+            YamlLoadModel::Expression { rps } => Ok(LoadModel::Expression {
+                expression: RateExpression::parse(rps).map_err(|e| {
+                    YamlConfigError::Validation(format!("Invalid rps expression: {}", e))
+                })?,
+            }),
+            YamlLoadModel::Composite { base, overlays } => {
+                let component = |load: &YamlLoadModel| match load {
+                    YamlLoadModel::Concurrent => Err(YamlConfigError::Validation(
+                        "composite components must be rate-based (not concurrent)".to_string(),
+                    )),
+                    load => load.to_load_model(),
+                };
+                Ok(LoadModel::Composite {
+                    base: Box::new(component(base)?),
+                    overlays: overlays
+                        .iter()
+                        .map(|overlay| {
+                            Ok(LoadOverlay {
+                                op: overlay.op,
+                                model: component(&overlay.load)?,
+                            })
+                        })
+                        .collect::<Result<_, YamlConfigError>>()?,
+                })
+            }
             YamlLoadModel::Rps { target } => Ok(LoadModel::Rps {
                 target_rps: *target,
             }),
@@ -201,10 +337,13 @@ impl YamlLoadModel {
                 min,
                 max,
                 ramp_duration,
+                align_to_clock,
+                cycle_start,
             } => Ok(LoadModel::RampRps {
                 min_rps: *min,
                 max_rps: *max,
                 ramp_duration: ramp_duration.to_std_duration()?,
+                clock_start: clock_start(*align_to_clock, cycle_start.as_deref())?,
             }),
             YamlLoadModel::DailyTraffic {
                 min,
@@ -216,6 +355,9 @@ impl YamlLoadModel {
                 mid_decline_ratio,
                 mid_sustain_ratio,
                 evening_decline_ratio,
+                calendar_file,
+                align_to_clock,
+                cycle_start,
             } => Ok(LoadModel::DailyTraffic {
                 min_rps: *min,
                 mid_rps: *mid,
@@ -226,7 +368,97 @@ impl YamlLoadModel {
                 mid_decline_ratio: *mid_decline_ratio,
                 mid_sustain_ratio: *mid_sustain_ratio,
                 evening_decline_ratio: *evening_decline_ratio,
+                clock_start: clock_start(*align_to_clock, cycle_start.as_deref())?,
+                calendar: calendar_file
+                    .as_deref()
+                    .map(TrafficCalendar::from_file)
+                    .transpose()
+                    .map_err(|e| YamlConfigError::Validation(format!("calendarFile: {}", e)))?,
             }),
+            YamlLoadModel::Exponential {
+                start_rps,
+                doubling_period,
+                max_rps,
+                abort_error_rate,
+                abort_latency_ms,
+            } => {
+                let doubling_period = doubling_period.to_std_duration()?;
+                if doubling_period.is_zero() {
+                    return Err(YamlConfigError::Validation(
+                        "doublingPeriod must be greater than zero".to_string(),
+                    ));
+                }
+                Ok(LoadModel::Exponential {
+                    start_rps: *start_rps,
+                    doubling_period,
+                    max_rps: *max_rps,
+                    abort: AbortThreshold {
+                        error_rate_percent: *abort_error_rate,
+                        latency: abort_latency_ms.map(StdDuration::from_millis),
+                    },
+                })
+            }
+        }
+    }
+}
+
+/// A weighted client profile under the top-level `clients:` list.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct YamlClientProfile {
+    pub name: String,
+
+    #[serde(default = "default_weight")]
+    pub weight: f64,
+
+    /// Sent as `User-Agent`
+    #[serde(rename = "userAgent", skip_serializing_if = "Option::is_none")]
+    pub user_agent: Option<String>,
+
+    /// Sent as `Accept-Language`
+    #[serde(rename = "acceptLanguage", skip_serializing_if = "Option::is_none")]
+    pub accept_language: Option<String>,
+
+    /// Further headers sent by this client; `${var}` is substituted per request
+    #[serde(default, skip_serializing_if = "std::collections::HashMap::is_empty")]
+    pub headers: std::collections::HashMap<String, String>,
+
+    /// Factor applied to every step's think time (default 1)
+    #[serde(
+        rename = "thinkTimeMultiplier",
+        default = "default_think_time_multiplier"
+    )]
+    pub think_time_multiplier: f64,
+}
+
+fn default_think_time_multiplier() -> f64 {
+    1.0
+}
+
+impl YamlClientProfile {
+    /// The profile's headers; `userAgent` and `acceptLanguage` override the
+    /// same names in `headers`.
+    pub fn to_profile(&self) -> ClientProfile {
+        let overridden = |name: &str| {
+            (self.user_agent.is_some() && name.eq_ignore_ascii_case("User-Agent"))
+                || (self.accept_language.is_some() && name.eq_ignore_ascii_case("Accept-Language"))
+        };
+        let mut headers: Vec<(&str, &str)> = self
+            .headers
+            .iter()
+            .filter(|(name, _)| !overridden(name))
+            .map(|(name, value)| (name.as_str(), value.as_str()))
+            .collect();
+        if let Some(user_agent) = &self.user_agent {
+            headers.push(("User-Agent", user_agent));
+        }
+        if let Some(language) = &self.accept_language {
+            headers.push(("Accept-Language", language));
+        }
+        ClientProfile {
+            name: self.name.clone(),
+            weight: self.weight,
+            headers: HeaderLayer::new(headers),
+            think_time_multiplier: self.think_time_multiplier,
         }
     }
 }
@@ -242,12 +474,279 @@ pub struct YamlScenario {
     pub steps: Vec<YamlStep>,
 
     /// Optional data file for data-driven testing
-    #[serde(rename = "dataFile")]
+    #[serde(rename = "dataFile", skip_serializing_if = "Option::is_none")]
     pub data_file: Option<YamlDataFile>,
 
+    /// Data source plugin supplying each iteration's variables
+    #[serde(rename = "dataSource", skip_serializing_if = "Option::is_none")]
+    pub data_source: Option<String>,
+
     /// Optional scenario-level configuration overrides
     #[serde(default)]
     pub config: YamlScenarioConfig,
+
+    /// Optional client-side network emulation (preset name or explicit limits)
+    #[serde(rename = "networkProfile", skip_serializing_if = "Option::is_none")]
+    pub network_profile: Option<YamlNetworkProfile>,
+
+    /// Free-form tags, e.g. `{team: checkout, tier: critical}`
+    #[serde(default, skip_serializing_if = "std::collections::HashMap::is_empty")]
+    pub tags: std::collections::HashMap<String, String>,
+
+    /// Cap on concurrent executions of this scenario across all workers
+    #[serde(rename = "maxConcurrent", skip_serializing_if = "Option::is_none")]
+    pub max_concurrent: Option<usize>,
+
+    /// Scenarios that must finish before this one starts
+    #[serde(rename = "dependsOn", default, skip_serializing_if = "Vec::is_empty")]
+    pub depends_on: Vec<String>,
+
+    /// Run the scenario this many times in total, then finish
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub iterations: Option<u64>,
+
+    /// Run the scenario at this fixed rate instead of a share of the load model
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub rps: Option<f64>,
+
+    /// Dedicated HTTP client settings for this scenario's workers
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub client: Option<YamlScenarioClient>,
+
+    /// Headers added to every step; `${var}` is substituted per request
+    #[serde(default, skip_serializing_if = "std::collections::HashMap::is_empty")]
+    pub headers: std::collections::HashMap<String, String>,
+
+    /// Credentials injected into every step (overrides any other `Authorization`)
+    #[serde(
+        default,
+        with = "serde_yaml::with::singleton_map",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub auth: Option<YamlScenarioAuth>,
+
+    /// Skipped first when the in-flight cap is saturated (`BACKPRESSURE=degrade`)
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub optional: bool,
+
+    /// Headers, query parameters and a path prefix merged into every step's request
+    #[serde(rename = "requestDefaults", skip_serializing_if = "Option::is_none")]
+    pub request_defaults: Option<YamlRequestDefaults>,
+}
+
+/// Scenario-level request defaults (`requestDefaults:`). Step values win.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct YamlRequestDefaults {
+    #[serde(default, skip_serializing_if = "std::collections::HashMap::is_empty")]
+    pub headers: std::collections::HashMap<String, String>,
+
+    #[serde(
+        rename = "queryParams",
+        default,
+        skip_serializing_if = "std::collections::HashMap::is_empty"
+    )]
+    pub query_params: std::collections::HashMap<String, String>,
+
+    /// Prefixed to every relative step path, e.g. `/api/v2`
+    #[serde(rename = "basePath", skip_serializing_if = "Option::is_none")]
+    pub base_path: Option<String>,
+}
+
+impl YamlRequestDefaults {
+    /// `request` with the defaults merged in: headers (compared
+    /// case-insensitively) and query parameters the step doesn't set, and
+    /// `basePath` in front of a path that isn't an absolute URL.
+    pub fn apply(&self, request: &YamlRequest) -> YamlRequest {
+        let mut merged = request.clone();
+
+        let mut headers = request.headers.clone().unwrap_or_default();
+        for (name, value) in &self.headers {
+            if !headers.keys().any(|n| n.eq_ignore_ascii_case(name)) {
+                headers.insert(name.clone(), value.clone());
+            }
+        }
+        if !headers.is_empty() {
+            merged.headers = Some(headers);
+        }
+
+        let mut query_params = request.query_params.clone().unwrap_or_default();
+        for (name, value) in &self.query_params {
+            query_params
+                .entry(name.clone())
+                .or_insert_with(|| value.clone());
+        }
+        if !query_params.is_empty() {
+            merged.query_params = Some(query_params);
+        }
+
+        if let Some(base) = &self.base_path {
+            if !(request.path.starts_with("http://") || request.path.starts_with("https://")) {
+                merged.path = format!(
+                    "{}/{}",
+                    base.trim_end_matches('/'),
+                    request.path.trim_start_matches('/')
+                );
+            }
+        }
+        merged
+    }
+}
+
+/// Scenario-level credentials (`auth:`), one of `bearer`, `basic` or `apiKey`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum YamlScenarioAuth {
+    /// `Authorization: Bearer <token>`
+    Bearer(String),
+    /// `Authorization: Basic ...` from a username and password
+    Basic { username: String, password: String },
+    /// A key sent in its own header, e.g. `X-Api-Key`
+    ApiKey { header: String, value: String },
+}
+
+impl YamlScenarioAuth {
+    pub fn to_auth_scheme(&self) -> AuthScheme {
+        match self {
+            YamlScenarioAuth::Bearer(token) => AuthScheme::Bearer(Template::parse(token)),
+            YamlScenarioAuth::Basic { username, password } => AuthScheme::Basic {
+                username: Template::parse(username),
+                password: Template::parse(password),
+            },
+            YamlScenarioAuth::ApiKey { header, value } => AuthScheme::ApiKey {
+                header: header.clone(),
+                value: Template::parse(value),
+            },
+        }
+    }
+}
+
+/// Scenario-level HTTP client overrides (`client:`).
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct YamlScenarioClient {
+    /// Skip TLS certificate verification
+    #[serde(rename = "skipTlsVerify", skip_serializing_if = "Option::is_none")]
+    pub skip_tls_verify: Option<bool>,
+
+    /// PEM client certificate for mTLS (requires `clientKey`)
+    #[serde(rename = "clientCert", skip_serializing_if = "Option::is_none")]
+    pub client_cert: Option<String>,
+
+    /// PEM private key for `clientCert`
+    #[serde(rename = "clientKey", skip_serializing_if = "Option::is_none")]
+    pub client_key: Option<String>,
+
+    /// Headers sent with every request of the scenario
+    #[serde(default, skip_serializing_if = "std::collections::HashMap::is_empty")]
+    pub headers: std::collections::HashMap<String, String>,
+
+    /// Proxy URL for all requests (http://, https:// or socks5://)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub proxy: Option<String>,
+
+    /// Request timeout
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub timeout: Option<YamlDuration>,
+}
+
+impl YamlScenarioClient {
+    pub fn to_overrides(&self) -> Result<ClientOverrides, YamlConfigError> {
+        if self.client_cert.is_some() != self.client_key.is_some() {
+            return Err(YamlConfigError::Validation(
+                "client.clientCert and client.clientKey must be set together".to_string(),
+            ));
+        }
+        if let Some(proxy) = &self.proxy {
+            reqwest::Proxy::all(proxy.as_str()).map_err(|e| {
+                YamlConfigError::Validation(format!("Invalid client.proxy '{}': {}", proxy, e))
+            })?;
+        }
+        let timeout = self
+            .timeout
+            .as_ref()
+            .map(|t| t.to_std_duration())
+            .transpose()?;
+        if timeout == Some(StdDuration::ZERO) {
+            return Err(YamlConfigError::Validation(
+                "client.timeout must be greater than 0".to_string(),
+            ));
+        }
+        let mut headers: Vec<(String, String)> = self
+            .headers
+            .iter()
+            .map(|(name, value)| (name.clone(), value.clone()))
+            .collect();
+        headers.sort();
+        for (name, value) in &headers {
+            if reqwest::header::HeaderName::from_bytes(name.as_bytes()).is_err()
+                || reqwest::header::HeaderValue::from_str(value).is_err()
+            {
+                return Err(YamlConfigError::Validation(format!(
+                    "Invalid client header '{}: {}'",
+                    name, value
+                )));
+            }
+        }
+        Ok(ClientOverrides {
+            skip_tls_verify: self.skip_tls_verify,
+            client_cert_path: self.client_cert.clone(),
+            client_key_path: self.client_key.clone(),
+            headers,
+            proxy: self.proxy.clone(),
+            timeout,
+        })
+    }
+}
+
+/// Network emulation in YAML: a preset name (`3g`) or explicit limits.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum YamlNetworkProfile {
+    /// Preset name: `slow-3g`, `3g`, `4g`, `wifi`
+    Preset(String),
+
+    /// Explicit latency (ms) and bandwidth caps (kbps)
+    Custom {
+        #[serde(rename = "latencyMs", default)]
+        latency_ms: u64,
+        #[serde(rename = "jitterMs", default)]
+        jitter_ms: u64,
+        #[serde(rename = "downloadKbps", skip_serializing_if = "Option::is_none")]
+        download_kbps: Option<u64>,
+        #[serde(rename = "uploadKbps", skip_serializing_if = "Option::is_none")]
+        upload_kbps: Option<u64>,
+    },
+}
+
+impl YamlNetworkProfile {
+    pub fn to_network_profile(&self) -> Result<NetworkProfile, YamlConfigError> {
+        match self {
+            YamlNetworkProfile::Preset(name) => NetworkProfile::preset(name).ok_or_else(|| {
+                YamlConfigError::Validation(format!(
+                    "Unknown networkProfile '{}'. Valid presets: {}",
+                    name,
+                    crate::network_profile::PRESET_NAMES.join(", ")
+                ))
+            }),
+            YamlNetworkProfile::Custom {
+                latency_ms,
+                jitter_ms,
+                download_kbps,
+                upload_kbps,
+            } => {
+                if *download_kbps == Some(0) || *upload_kbps == Some(0) {
+                    return Err(YamlConfigError::Validation(
+                        "networkProfile bandwidth caps must be greater than 0".to_string(),
+                    ));
+                }
+                Ok(NetworkProfile::from_kbps(
+                    *latency_ms,
+                    *jitter_ms,
+                    *download_kbps,
+                    *upload_kbps,
+                ))
+            }
+        }
+    }
 }
 
 /// Data file configuration for data-driven scenarios.
@@ -277,15 +776,52 @@ fn default_data_strategy() -> String {
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
 pub struct YamlScenarioConfig {
     /// Override global timeout for this scenario
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub timeout: Option<YamlDuration>,
 
     /// Number of times to retry failed requests in this scenario
-    #[serde(rename = "retryCount")]
+    #[serde(rename = "retryCount", skip_serializing_if = "Option::is_none")]
     pub retry_count: Option<u32>,
 
     /// Delay between retries
-    #[serde(rename = "retryDelay")]
+    #[serde(rename = "retryDelay", skip_serializing_if = "Option::is_none")]
     pub retry_delay: Option<YamlDuration>,
+
+    /// Wait as long as a 429/503 response's Retry-After header asks before retrying
+    #[serde(rename = "honorRetryAfter", default)]
+    pub honor_retry_after: bool,
+
+    /// Cap on Retry-After waits (default: 60s)
+    #[serde(rename = "maxRetryAfter", skip_serializing_if = "Option::is_none")]
+    pub max_retry_after: Option<YamlDuration>,
+
+    /// Fraction of iterations whose assertions run (default: 1.0)
+    #[serde(
+        rename = "assertionSampleRate",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub assertion_sample_rate: Option<f64>,
+}
+
+impl YamlScenarioConfig {
+    /// The retry policy, if `retryCount` is set.
+    pub fn retry_policy(&self) -> Result<Option<RetryPolicy>, YamlConfigError> {
+        let Some(count) = self.retry_count.filter(|count| *count > 0) else {
+            return Ok(None);
+        };
+        Ok(Some(RetryPolicy {
+            count,
+            delay: match &self.retry_delay {
+                Some(delay) => delay.to_std_duration()?,
+                None => RetryPolicy::DEFAULT_DELAY,
+            },
+            honor_retry_after: self.honor_retry_after,
+            max_retry_after: match &self.max_retry_after {
+                Some(max) => max.to_std_duration()?,
+                None => RetryPolicy::DEFAULT_MAX_RETRY_AFTER,
+            },
+        }))
+    }
 }
 
 fn default_weight() -> f64 {
@@ -324,47 +860,410 @@ impl YamlThinkTime {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct YamlStepCache {
     pub ttl: YamlDuration,
+
+    /// Cache key template; `${var}` references are substituted per iteration.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub key: Option<String>,
+
+    /// Share the cache between all workers, with single-flight refresh.
+    #[serde(default)]
+    pub shared: bool,
 }
 
 /// Step definition in YAML.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct YamlStep {
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub name: Option<String>,
 
+    /// May be omitted (or partial) when `fromCurl` is given.
+    #[serde(default)]
     pub request: YamlRequest,
 
-    #[serde(default)]
+    /// Build the request from a `curl ...` command line. Fields set in
+    /// `request` override the parsed values.
+    #[serde(rename = "fromCurl", skip_serializing_if = "Option::is_none")]
+    pub from_curl: Option<String>,
+
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub extract: Vec<YamlExtractor>,
 
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub assertions: Vec<YamlAssertion>,
 
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub cache: Option<YamlStepCache>,
 
-    #[serde(rename = "thinkTime")]
+    /// Revalidate with the validators (`ETag`, `Last-Modified`) of the last response
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub conditional: bool,
+
+    #[serde(rename = "thinkTime", skip_serializing_if = "Option::is_none")]
     pub think_time: Option<YamlThinkTime>,
+
+    /// Step tags; override scenario tags with the same key
+    #[serde(default, skip_serializing_if = "std::collections::HashMap::is_empty")]
+    pub tags: std::collections::HashMap<String, String>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub paginate: Option<YamlPaginate>,
+
+    /// Cap on concurrent executions of this step across all workers
+    #[serde(rename = "maxConcurrent", skip_serializing_if = "Option::is_none")]
+    pub max_concurrent: Option<usize>,
+
+    /// Response time objective for Apdex scoring
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub slo: Option<YamlStepSlo>,
+
+    /// Shared store reads and writes
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub shared: Vec<YamlSharedAction>,
+
+    /// Wait for other workers before sending the request
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub rendezvous: Option<YamlRendezvous>,
+
+    /// Send a per-execution idempotency key, kept across retries
+    #[serde(rename = "idempotencyKey", skip_serializing_if = "Option::is_none")]
+    pub idempotency_key: Option<YamlIdempotencyKey>,
+
+    /// Status codes that count as success (default: any 2xx/3xx)
+    #[serde(
+        rename = "expectedStatus",
+        default,
+        skip_serializing_if = "Vec::is_empty"
+    )]
+    pub expected_status: Vec<u16>,
+
+    /// Open a TCP connection instead of sending `request`
+    #[serde(
+        rename = "tcpCheck",
+        alias = "ping",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub tcp_check: Option<YamlTcpCheck>,
 }
 
-/// Request configuration in YAML.
+/// TCP health ping on a step: just the address (`ping: "db:5432"`), or the
+/// full form.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum YamlTcpCheck {
+    Address(String),
+    Options(YamlTcpCheckOptions),
+}
+
+/// Full form of a step's TCP check.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct YamlTcpCheckOptions {
+    /// `host:port` or `[ipv6]:port` (default: the base URL's host and port)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub address: Option<String>,
+
+    /// Complete a TLS handshake after connecting
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub tls: bool,
+
+    /// SNI and certificate name (default: the host)
+    #[serde(rename = "serverName", skip_serializing_if = "Option::is_none")]
+    pub server_name: Option<String>,
+
+    /// Longest the check may take (default: 5s)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub timeout: Option<YamlDuration>,
+}
+
+impl YamlTcpCheck {
+    pub fn to_tcp_check(&self) -> Result<TcpCheck, YamlConfigError> {
+        let options = match self {
+            Self::Address(address) => YamlTcpCheckOptions {
+                address: Some(address.clone()),
+                ..Default::default()
+            },
+            Self::Options(options) => options.clone(),
+        };
+        let target = options
+            .address
+            .as_deref()
+            .map(addr::parse_host_port)
+            .transpose()
+            .map_err(|e| YamlConfigError::Validation(format!("tcpCheck address {}", e)))?;
+        let timeout = match &options.timeout {
+            Some(timeout) => timeout.to_std_duration()?,
+            None => TcpCheck::DEFAULT_TIMEOUT,
+        };
+        if timeout.is_zero() {
+            return Err(YamlConfigError::Validation(
+                "tcpCheck timeout must be greater than zero".to_string(),
+            ));
+        }
+        Ok(TcpCheck {
+            target,
+            tls: options.tls,
+            server_name: options.server_name,
+            timeout,
+        })
+    }
+}
+
+/// Idempotency key on a step: `true` for the `Idempotency-Key` header, or
+/// the name of the header to send it in.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum YamlIdempotencyKey {
+    Enabled(bool),
+    Header(String),
+}
+
+impl YamlIdempotencyKey {
+    /// Default header for `idempotencyKey: true`.
+    pub const DEFAULT_HEADER: &'static str = "Idempotency-Key";
+
+    /// The header to send the key in, if enabled.
+    pub fn header(&self) -> Option<&str> {
+        match self {
+            Self::Enabled(true) => Some(Self::DEFAULT_HEADER),
+            Self::Enabled(false) => None,
+            Self::Header(name) => Some(name.as_str()),
+        }
+    }
+}
+
+/// Rendezvous point on a step.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct YamlRendezvous {
+    pub name: String,
+
+    /// Workers to wait for
+    pub count: usize,
+
+    /// Longest wait for the group to fill up (default: 30s)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub timeout: Option<YamlDuration>,
+}
+
+/// Shared store action on a step: exactly one of `set`, `push`, `get`, `pop`
+/// or `incr`, naming the key.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct YamlSharedAction {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub set: Option<String>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub push: Option<String>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub get: Option<String>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub pop: Option<String>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub incr: Option<String>,
+
+    /// Value written by `set` and `push`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub value: Option<String>,
+
+    /// Amount added by `incr` (default 1)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub by: Option<i64>,
+
+    /// Variable receiving the value read (default: the key)
+    #[serde(rename = "as", skip_serializing_if = "Option::is_none")]
+    pub save_as: Option<String>,
+}
+
+impl YamlSharedAction {
+    pub fn to_action(&self) -> Result<SharedAction, String> {
+        let ops = [
+            ("set", &self.set),
+            ("push", &self.push),
+            ("get", &self.get),
+            ("pop", &self.pop),
+            ("incr", &self.incr),
+        ];
+        let mut named = ops
+            .iter()
+            .filter_map(|(op, key)| Some((*op, (*key).clone()?)));
+        let (op, key) = match (named.next(), named.next()) {
+            (Some(op), None) => op,
+            _ => {
+                return Err(
+                    "shared actions need exactly one of set, push, get, pop or incr".to_string(),
+                )
+            }
+        };
+        if key.is_empty() {
+            return Err(format!("shared {} needs a key", op));
+        }
+        let value = match (op, &self.value) {
+            ("set" | "push", Some(value)) => value.clone(),
+            ("set" | "push", None) => return Err(format!("shared {} '{}' needs a value", op, key)),
+            (_, Some(_)) => return Err(format!("shared {} '{}' does not take a value", op, key)),
+            (_, None) => String::new(),
+        };
+        if self.by.is_some() && op != "incr" {
+            return Err(format!("shared {} '{}' does not take 'by'", op, key));
+        }
+        if self.save_as.is_some() && matches!(op, "set" | "push") {
+            return Err(format!("shared {} '{}' does not take 'as'", op, key));
+        }
+        let op = match op {
+            "set" => StoreOp::Set { key, value },
+            "push" => StoreOp::Push { key, value },
+            "get" => StoreOp::Get { key },
+            "pop" => StoreOp::Pop { key },
+            _ => StoreOp::Incr {
+                key,
+                by: self.by.unwrap_or(1),
+            },
+        };
+        Ok(SharedAction {
+            op,
+            save_as: self.save_as.clone(),
+        })
+    }
+}
+
+/// Response time objective on a step.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct YamlStepSlo {
+    #[serde(rename = "satisfiedMs")]
+    pub satisfied_ms: u64,
+
+    /// Defaults to four times `satisfiedMs`.
+    #[serde(rename = "toleratingMs", skip_serializing_if = "Option::is_none")]
+    pub tolerating_ms: Option<u64>,
+}
+
+/// Pagination settings on a step.
 #[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct YamlPaginate {
+    /// JSONPath to the next page URL or cursor.
+    #[serde(rename = "nextField")]
+    pub next_field: String,
+
+    #[serde(rename = "maxPages", skip_serializing_if = "Option::is_none")]
+    pub max_pages: Option<usize>,
+
+    /// Query parameter to send the cursor in; the value is used as the next
+    /// URL when omitted.
+    #[serde(rename = "cursorParam", skip_serializing_if = "Option::is_none")]
+    pub cursor_param: Option<String>,
+}
+
+impl YamlStep {
+    /// The first option set on this step that only applies to HTTP
+    /// requests; `tcpCheck` steps allow only `responseTime` and percentile
+    /// assertions on the connect time.
+    pub fn http_only_option(&self) -> Option<&'static str> {
+        let request = &self.request;
+        let options = [
+            (
+                !request.method.is_empty() || !request.path.is_empty(),
+                "request",
+            ),
+            (self.from_curl.is_some(), "fromCurl"),
+            (!self.extract.is_empty(), "extract"),
+            (self.cache.is_some(), "cache"),
+            (self.conditional, "conditional"),
+            (self.paginate.is_some(), "paginate"),
+            (self.idempotency_key.is_some(), "idempotencyKey"),
+            (!self.expected_status.is_empty(), "expectedStatus"),
+            (
+                self.assertions.iter().any(|a| {
+                    !matches!(
+                        a,
+                        YamlAssertion::ResponseTime { .. } | YamlAssertion::Percentile { .. }
+                    )
+                }),
+                "assertions",
+            ),
+        ];
+        options
+            .into_iter()
+            .find(|(set, _)| *set)
+            .map(|(_, option)| option)
+    }
+
+    /// The request to send, with `fromCurl` applied if present.
+    #[cfg(feature = "importers")]
+    pub fn resolved_request(&self) -> Result<Cow<'_, YamlRequest>, YamlConfigError> {
+        match &self.from_curl {
+            Some(command) => {
+                let parsed = crate::importers::curl::parse_curl(command)
+                    .map_err(|e| YamlConfigError::Validation(format!("fromCurl: {}", e)))?;
+                Ok(Cow::Owned(crate::importers::curl::merge_request(
+                    &parsed,
+                    &self.request,
+                )))
+            }
+            None => Ok(Cow::Borrowed(&self.request)),
+        }
+    }
+
+    /// The request to send; `fromCurl` needs the `importers` feature.
+    #[cfg(not(feature = "importers"))]
+    pub fn resolved_request(&self) -> Result<Cow<'_, YamlRequest>, YamlConfigError> {
+        match &self.from_curl {
+            Some(_) => Err(YamlConfigError::Validation(
+                "fromCurl requires building with --features importers".to_string(),
+            )),
+            None => Ok(Cow::Borrowed(&self.request)),
+        }
+    }
+}
+
+/// Request configuration in YAML.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct YamlRequest {
+    #[serde(default)]
     pub method: String,
+    #[serde(default)]
     pub path: String,
 
-    #[serde(rename = "queryParams")]
+    #[serde(rename = "queryParams", skip_serializing_if = "Option::is_none")]
     pub query_params: Option<std::collections::HashMap<String, String>>,
 
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub headers: Option<std::collections::HashMap<String, String>>,
 
+    /// Explicit `Host` header, overriding the URL's host.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub host: Option<String>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub body: Option<String>,
 
     /// Generate a synthetic body of this size instead of using `body`.
     /// Mutually exclusive with `body`. Supports "512B", "512KB", "1MB".
-    #[serde(rename = "bodySize")]
+    #[serde(rename = "bodySize", skip_serializing_if = "Option::is_none")]
     pub body_size: Option<String>,
+
+    /// Send `body` (JSON) encoded as protobuf and/or decode the response.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub protobuf: Option<YamlProtobuf>,
+}
+
+/// Protobuf body settings in YAML.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct YamlProtobuf {
+    /// Path to a compiled descriptor set (`protoc --descriptor_set_out`).
+    #[serde(rename = "descriptorSet")]
+    pub descriptor_set: String,
+
+    #[serde(rename = "requestMessage", skip_serializing_if = "Option::is_none")]
+    pub request_message: Option<String>,
+
+    #[serde(rename = "responseMessage", skip_serializing_if = "Option::is_none")]
+    pub response_message: Option<String>,
 }
 
-/// Extractor definition in YAML.
+/// Extractor definition in YAML. Each takes an optional `transform`
+/// pipeline applied to the extracted value (see [`parse_transforms`]) and an
+/// optional `scope` (default `iteration`).
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(tag = "type", rename_all = "lowercase")]
 pub enum YamlExtractor {
@@ -373,21 +1272,66 @@ pub enum YamlExtractor {
         name: String,
         #[serde(rename = "jsonPath")]
         json_path: String,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        transform: Option<String>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        scope: Option<YamlVariableScope>,
+    },
+    #[serde(rename = "xmlPath")]
+    XmlPath {
+        name: String,
+        #[serde(rename = "xmlPath")]
+        xml_path: String,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        transform: Option<String>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        scope: Option<YamlVariableScope>,
     },
     Regex {
         name: String,
         regex: String,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        transform: Option<String>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        scope: Option<YamlVariableScope>,
     },
     Header {
         name: String,
         header: String,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        transform: Option<String>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        scope: Option<YamlVariableScope>,
     },
     Cookie {
         name: String,
         cookie: String,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        transform: Option<String>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        scope: Option<YamlVariableScope>,
     },
 }
 
+/// Lifetime of an extracted variable (see [`VariableScope`]).
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum YamlVariableScope {
+    Iteration,
+    User,
+    Global,
+}
+
+impl From<YamlVariableScope> for VariableScope {
+    fn from(scope: YamlVariableScope) -> Self {
+        match scope {
+            YamlVariableScope::Iteration => VariableScope::Iteration,
+            YamlVariableScope::User => VariableScope::User,
+            YamlVariableScope::Global => VariableScope::Global,
+        }
+    }
+}
+
 /// Assertion definition in YAML.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(tag = "type", rename_all = "camelCase")]
@@ -401,12 +1345,70 @@ pub enum YamlAssertion {
         path: String,
         expected: Option<String>,
     },
+    #[serde(rename = "xmlPath")]
+    XmlPath {
+        path: String,
+        expected: Option<String>,
+    },
     #[serde(rename = "bodyContains")]
     BodyContains { text: String },
     #[serde(rename = "bodyMatches")]
     BodyMatches { regex: String },
     #[serde(rename = "headerExists")]
     HeaderExists { header: String },
+    /// Exact header value (header name is case-insensitive)
+    #[serde(rename = "headerEquals")]
+    HeaderEquals { header: String, expected: String },
+    /// Header value matching a regex
+    #[serde(rename = "headerMatches")]
+    HeaderMatches { header: String, regex: String },
+    /// SHA-256 (hex) of the raw response body
+    #[serde(rename = "bodySha256")]
+    BodySha256 { expected: String },
+    /// Response body size in bytes, inclusive bounds
+    #[serde(rename = "bodySizeBytes")]
+    BodySizeBytes { min: Option<u64>, max: Option<u64> },
+    /// `304 Not Modified` on revalidation
+    #[serde(rename = "notModified")]
+    NotModified,
+    /// Running share of cached responses (hits and 304s) for the step
+    #[serde(rename = "cacheHitRatio")]
+    CacheHitRatio {
+        min: f64,
+        #[serde(rename = "minSamples", default = "default_cache_min_samples")]
+        min_samples: u64,
+    },
+    /// JSON body equal to a golden file, minus the ignored fields
+    #[serde(rename = "matchesGolden")]
+    MatchesGolden {
+        path: String,
+        #[serde(rename = "ignoreFields", default)]
+        ignore_fields: Vec<String>,
+    },
+    /// Percentile of the step's latency over a sliding window, judged once
+    /// per second rather than per response (see [`crate::latency_sla`])
+    #[serde(rename = "percentile")]
+    Percentile {
+        percentile: f64,
+        window: YamlDuration,
+        #[serde(rename = "maxMs")]
+        max_ms: u64,
+        #[serde(rename = "minSamples", default = "default_sla_min_samples")]
+        min_samples: u64,
+    },
+    /// Custom assertion implemented by a loaded plugin.
+    Plugin {
+        plugin: String,
+        config: Option<String>,
+    },
+}
+
+fn default_sla_min_samples() -> u64 {
+    20
+}
+
+fn default_cache_min_samples() -> u64 {
+    20
 }
 
 /// Standby configuration: applied after the test completes to keep connections warm.
@@ -424,6 +1426,23 @@ fn default_standby_workers() -> usize {
     2
 }
 
+/// An execution phase: scenarios that run together, after the previous phase.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct YamlPhase {
+    pub name: String,
+
+    /// Names of the scenarios in this phase
+    pub scenarios: Vec<String>,
+
+    /// Longest the phase runs (default: `config.duration`)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub duration: Option<YamlDuration>,
+
+    /// Workers for this phase (default: `config.workers`)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub workers: Option<usize>,
+}
+
 /// Root YAML configuration structure.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct YamlConfig {
@@ -439,12 +1458,32 @@ pub struct YamlConfig {
     pub scenarios: Vec<YamlScenario>,
 
     /// Optional standby configuration applied after test duration expires.
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub standby: Option<YamlStandbyConfig>,
-}
 
-impl YamlConfig {
-    /// Load configuration from a YAML file.
+    /// Ordered execution phases; scenarios within a phase run concurrently.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub phases: Vec<YamlPhase>,
+
+    /// Global variables, resolved once when the test starts.
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub variables: HashMap<String, String>,
+
+    /// Success-rate thresholds per scenario, e.g. `checkout: "successRate > 99.5%"`.
+    #[serde(
+        rename = "scenarioThresholds",
+        default,
+        skip_serializing_if = "HashMap::is_empty"
+    )]
+    pub scenario_thresholds: HashMap<String, String>,
+
+    /// Weighted client profiles, one drawn per virtual user.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub clients: Vec<YamlClientProfile>,
+}
+
+impl YamlConfig {
+    /// Load configuration from a YAML file.
     pub fn from_file<P: AsRef<Path>>(path: P) -> Result<Self, YamlConfigError> {
         let content = fs::read_to_string(path)?;
         Self::from_str(&content)
@@ -474,7 +1513,11 @@ impl YamlConfig {
 
         // Validate base URL
         ctx.enter("baseUrl");
-        if let Err(e) = UrlValidator::validate(&self.config.base_url) {
+        if service_discovery::is_discovery_url(&self.config.base_url) {
+            if let Err(e) = ServiceRef::parse(&self.config.base_url) {
+                ctx.field_error(e.to_string());
+            }
+        } else if let Err(e) = UrlValidator::validate(&self.config.base_url) {
             ctx.field_error(e.to_string());
         }
         ctx.exit();
@@ -495,6 +1538,14 @@ impl YamlConfig {
         }
         ctx.exit();
 
+        if let Some(start_at) = &self.config.start_at {
+            ctx.enter("startAt");
+            if let Err(e) = crate::start_barrier::parse_start_at(start_at) {
+                ctx.field_error(e);
+            }
+            ctx.exit();
+        }
+
         ctx.exit(); // config
 
         // Validate load model
@@ -515,7 +1566,37 @@ impl YamlConfig {
                     ctx.field_error(e.to_string());
                 }
             }
+            YamlLoadModel::Exponential {
+                start_rps, max_rps, ..
+            } => {
+                if let Err(e) = LoadModelValidator::validate_exponential(*start_rps, *max_rps) {
+                    ctx.field_error(e.to_string());
+                }
+                if let Err(e) = self.load.to_load_model() {
+                    ctx.field_error(e.to_string());
+                }
+            }
             YamlLoadModel::Concurrent => {} // No validation needed
+            YamlLoadModel::Expression { .. } | YamlLoadModel::Composite { .. } => {
+                if let Err(e) = self.load.to_load_model() {
+                    ctx.field_error(e.to_string());
+                }
+            }
+        }
+        if let YamlLoadModel::Ramp {
+            align_to_clock,
+            cycle_start,
+            ..
+        }
+        | YamlLoadModel::DailyTraffic {
+            align_to_clock,
+            cycle_start,
+            ..
+        } = &self.load
+        {
+            if let Err(e) = clock_start(*align_to_clock, cycle_start.as_deref()) {
+                ctx.field_error(e.to_string());
+            }
         }
         ctx.exit(); // load
 
@@ -540,6 +1621,96 @@ impl YamlConfig {
             }
             ctx.exit();
 
+            if scenario.max_concurrent == Some(0) {
+                ctx.enter("maxConcurrent");
+                ctx.field_error("maxConcurrent must be at least 1".to_string());
+                ctx.exit();
+            }
+
+            if scenario.iterations == Some(0) {
+                ctx.enter("iterations");
+                ctx.field_error("iterations must be at least 1".to_string());
+                ctx.exit();
+            }
+
+            if let Some(rps) = scenario.rps {
+                if !(rps.is_finite() && rps > 0.0) {
+                    ctx.enter("rps");
+                    ctx.field_error(format!("rps {} must be greater than 0", rps));
+                    ctx.exit();
+                }
+            }
+
+            if let Some(rate) = scenario.config.assertion_sample_rate {
+                if !(0.0..=1.0).contains(&rate) {
+                    ctx.enter("config");
+                    ctx.enter("assertionSampleRate");
+                    ctx.field_error(format!(
+                        "assertionSampleRate {} must be between 0 and 1",
+                        rate
+                    ));
+                    ctx.exit();
+                    ctx.exit();
+                }
+            }
+
+            if let Some(client) = &scenario.client {
+                ctx.enter("client");
+                if let Err(e) = client.to_overrides() {
+                    ctx.field_error(e.to_string());
+                }
+                ctx.exit();
+            }
+
+            let mut header_names: Vec<&String> = scenario.headers.keys().collect();
+            header_names.sort();
+            for name in header_names {
+                if reqwest::header::HeaderName::from_bytes(name.as_bytes()).is_err() {
+                    ctx.enter("headers");
+                    ctx.field_error(format!("Invalid header name '{}'", name));
+                    ctx.exit();
+                }
+            }
+
+            if let Some(defaults) = &scenario.request_defaults {
+                ctx.enter("requestDefaults");
+                let mut header_names: Vec<&String> = defaults.headers.keys().collect();
+                header_names.sort();
+                for name in header_names {
+                    if reqwest::header::HeaderName::from_bytes(name.as_bytes()).is_err() {
+                        ctx.enter("headers");
+                        ctx.field_error(format!("Invalid header name '{}'", name));
+                        ctx.exit();
+                    }
+                }
+                if let Some(base) = &defaults.base_path {
+                    if !base.starts_with('/') {
+                        ctx.enter("basePath");
+                        ctx.field_error(format!("basePath '{}' must start with '/'", base));
+                        ctx.exit();
+                    }
+                }
+                ctx.exit();
+            }
+
+            if let Some(YamlScenarioAuth::ApiKey { header, .. }) = &scenario.auth {
+                if reqwest::header::HeaderName::from_bytes(header.as_bytes()).is_err() {
+                    ctx.enter("auth");
+                    ctx.enter("apiKey");
+                    ctx.field_error(format!("Invalid header name '{}'", header));
+                    ctx.exit();
+                    ctx.exit();
+                }
+            }
+
+            if let Some(profile) = &scenario.network_profile {
+                ctx.enter("networkProfile");
+                if let Err(e) = profile.to_network_profile() {
+                    ctx.field_error(e.to_string());
+                }
+                ctx.exit();
+            }
+
             // Validate steps
             ctx.enter("steps");
             if scenario.steps.is_empty() {
@@ -551,23 +1722,65 @@ impl YamlConfig {
 
             for (step_idx, step) in scenario.steps.iter().enumerate() {
                 ctx.enter(&format!("[{}]", step_idx));
+
+                if let Some(check) = &step.tcp_check {
+                    ctx.enter("tcpCheck");
+                    if let Err(e) = check.to_tcp_check() {
+                        ctx.field_error(e.to_string());
+                    }
+                    if let Some(option) = step.http_only_option() {
+                        ctx.field_error(format!(
+                            "'{}' needs an HTTP request and cannot be used with tcpCheck",
+                            option
+                        ));
+                    }
+                    ctx.exit();
+                    ctx.exit(); // step
+                    continue;
+                }
+
+                let request = match step.resolved_request() {
+                    Ok(request) => request,
+                    Err(e) => {
+                        ctx.enter("fromCurl");
+                        ctx.field_error(e.to_string());
+                        ctx.exit();
+                        ctx.exit(); // step
+                        continue;
+                    }
+                };
                 ctx.enter("request");
 
                 // Validate HTTP method
                 ctx.enter("method");
-                if let Err(e) = HttpMethodValidator::validate(&step.request.method) {
+                if let Err(e) = HttpMethodValidator::validate(&request.method) {
                     ctx.field_error(e.to_string());
                 }
                 ctx.exit();
 
                 // Validate path
                 ctx.enter("path");
-                if step.request.path.is_empty() {
+                if request.path.is_empty() {
                     ctx.field_error("Request path cannot be empty".to_string());
                 }
                 ctx.exit();
 
                 ctx.exit(); // request
+
+                if step.max_concurrent == Some(0) {
+                    ctx.enter("maxConcurrent");
+                    ctx.field_error("maxConcurrent must be at least 1".to_string());
+                    ctx.exit();
+                }
+                if let Some(code) = step
+                    .expected_status
+                    .iter()
+                    .find(|code| !(100..=599).contains(*code))
+                {
+                    ctx.enter("expectedStatus");
+                    ctx.field_error(format!("{} is not an HTTP status code", code));
+                    ctx.exit();
+                }
                 ctx.exit(); // step
             }
 
@@ -576,11 +1789,193 @@ impl YamlConfig {
         }
         ctx.exit(); // scenarios
 
+        // Validate phases and scenario dependencies
+        ctx.enter("phases");
+        match self.phase_specs() {
+            Ok(specs) => {
+                let names: Vec<(&str, &[String])> = self
+                    .scenarios
+                    .iter()
+                    .map(|s| (s.name.as_str(), s.depends_on.as_slice()))
+                    .collect();
+                if let Err(e) = phases::order(&names, &specs) {
+                    ctx.field_error(e.to_string());
+                }
+            }
+            Err(e) => ctx.field_error(e.to_string()),
+        }
+        ctx.exit(); // phases
+
+        // Validate per-scenario thresholds
+        ctx.enter("scenarioThresholds");
+        let mut threshold_names: Vec<&String> = self.scenario_thresholds.keys().collect();
+        threshold_names.sort();
+        for name in threshold_names {
+            ctx.enter(name);
+            if !self.scenarios.iter().any(|s| &s.name == name) {
+                ctx.field_error(format!("Unknown scenario '{}'", name));
+            }
+            if let Err(e) = self.scenario_thresholds[name].parse::<SuccessRateThreshold>() {
+                ctx.field_error(e.to_string());
+            }
+            ctx.exit();
+        }
+        ctx.exit(); // scenarioThresholds
+
+        // Validate client profiles
+        ctx.enter("clients");
+        for (idx, client) in self.clients.iter().enumerate() {
+            ctx.enter(&format!("[{}]", idx));
+            if client.name.is_empty() {
+                ctx.enter("name");
+                ctx.field_error("Client profile name cannot be empty".to_string());
+                ctx.exit();
+            } else if self.clients[..idx].iter().any(|c| c.name == client.name) {
+                ctx.enter("name");
+                ctx.field_error(format!("Duplicate client profile '{}'", client.name));
+                ctx.exit();
+            }
+            ctx.enter("weight");
+            if let Err(e) = RangeValidator::validate_positive_f64(client.weight, "weight") {
+                ctx.field_error(e.to_string());
+            }
+            ctx.exit();
+            if !(client.think_time_multiplier.is_finite() && client.think_time_multiplier >= 0.0) {
+                ctx.enter("thinkTimeMultiplier");
+                ctx.field_error(format!(
+                    "thinkTimeMultiplier {} must be 0 or greater",
+                    client.think_time_multiplier
+                ));
+                ctx.exit();
+            }
+            let mut header_names: Vec<&String> = client.headers.keys().collect();
+            header_names.sort();
+            for name in header_names {
+                if reqwest::header::HeaderName::from_bytes(name.as_bytes()).is_err() {
+                    ctx.enter("headers");
+                    ctx.field_error(format!("Invalid header name '{}'", name));
+                    ctx.exit();
+                }
+            }
+            ctx.exit();
+        }
+        ctx.exit(); // clients
+
+        if let Err(e) = self.latency_slas() {
+            ctx.enter("scenarios");
+            ctx.field_error(e.to_string());
+            ctx.exit();
+        }
+
         // Convert validation context to result
         ctx.into_result()
             .map_err(|e| YamlConfigError::Validation(e.to_string()))
     }
 
+    fn phase_specs(&self) -> Result<Vec<PhaseSpec>, YamlConfigError> {
+        self.phases
+            .iter()
+            .map(|phase| {
+                Ok(PhaseSpec {
+                    name: phase.name.clone(),
+                    scenarios: phase.scenarios.clone(),
+                    duration: phase
+                        .duration
+                        .as_ref()
+                        .map(|d| d.to_std_duration())
+                        .transpose()?,
+                    workers: phase.workers,
+                })
+            })
+            .collect()
+    }
+
+    /// Per-scenario success-rate thresholds (see [`crate::scenario_slo`]),
+    /// ordered by scenario name.
+    pub fn scenario_thresholds(&self) -> Result<Vec<ScenarioThreshold>, YamlConfigError> {
+        let mut thresholds = self
+            .scenario_thresholds
+            .iter()
+            .map(|(scenario, spec)| {
+                Ok(ScenarioThreshold {
+                    scenario: scenario.clone(),
+                    success_rate: spec
+                        .parse()
+                        .map_err(|e: ThresholdError| YamlConfigError::Validation(e.to_string()))?,
+                })
+            })
+            .collect::<Result<Vec<_>, YamlConfigError>>()?;
+        thresholds.sort_by(|a, b| a.scenario.cmp(&b.scenario));
+        Ok(thresholds)
+    }
+
+    /// The steps' `percentile` assertions (see [`crate::latency_sla`]).
+    pub fn latency_slas(&self) -> Result<Vec<LatencySla>, YamlConfigError> {
+        let mut slas = Vec::new();
+        for scenario in &self.scenarios {
+            for (idx, step) in scenario.steps.iter().enumerate() {
+                for assertion in &step.assertions {
+                    let YamlAssertion::Percentile {
+                        percentile,
+                        window,
+                        max_ms,
+                        min_samples,
+                    } = assertion
+                    else {
+                        continue;
+                    };
+                    let step_name = step
+                        .name
+                        .clone()
+                        .unwrap_or_else(|| format!("Step {}", idx + 1));
+                    if !(*percentile > 0.0 && *percentile <= 100.0) {
+                        return Err(YamlConfigError::Validation(format!(
+                            "Step '{}': percentile {} must be above 0 and at most 100",
+                            step_name, percentile
+                        )));
+                    }
+                    let window = window.to_std_duration()?;
+                    if window < StdDuration::from_secs(1) {
+                        return Err(YamlConfigError::Validation(format!(
+                            "Step '{}': percentile window must be at least 1s",
+                            step_name
+                        )));
+                    }
+                    slas.push(LatencySla {
+                        scenario: scenario.name.clone(),
+                        step: step_name,
+                        percentile: *percentile,
+                        window: StdDuration::from_secs(window.as_secs()),
+                        max_ms: *max_ms,
+                        min_samples: *min_samples,
+                    });
+                }
+            }
+        }
+        Ok(slas)
+    }
+
+    /// The `clients:` profiles (see [`crate::client_profiles`]).
+    pub fn client_profiles(&self) -> ClientProfiles {
+        ClientProfiles::new(self.clients.iter().map(|c| c.to_profile()).collect())
+    }
+
+    /// Convert YAML scenarios to execution phases (see [`crate::phases`]).
+    ///
+    /// Without `phases:` or `dependsOn:` this is a single phase with every
+    /// scenario, lasting `config.duration`.
+    pub fn to_phases(&self) -> Result<Vec<Phase>, YamlConfigError> {
+        let duration = self.config.duration.to_std_duration()?;
+        phases::build(self.to_scenarios()?, &self.phase_specs()?, duration)
+            .map_err(|e| YamlConfigError::Validation(e.to_string()))
+    }
+
+    /// Resolves the global `variables:` (see [`crate::shared_state`]).
+    pub fn resolve_variables(&self) -> Result<HashMap<String, String>, YamlConfigError> {
+        shared_state::resolve_variables(&self.variables)
+            .map_err(|e| YamlConfigError::Validation(e.to_string()))
+    }
+
     /// Convert YAML scenarios to Scenario structs.
     pub fn to_scenarios(&self) -> Result<Vec<Scenario>, YamlConfigError> {
         let mut scenarios = Vec::new();
@@ -594,25 +1989,30 @@ impl YamlConfig {
                     .clone()
                     .unwrap_or_else(|| format!("Step {}", idx + 1));
 
+                let mut yaml_request = yaml_step.resolved_request()?;
+                if let Some(defaults) = &yaml_scenario.request_defaults {
+                    yaml_request = Cow::Owned(defaults.apply(&yaml_request));
+                }
+
                 // Build request config
                 let mut headers = std::collections::HashMap::new();
-                if let Some(yaml_headers) = &yaml_step.request.headers {
+                if let Some(yaml_headers) = &yaml_request.headers {
                     headers.extend(yaml_headers.clone());
                 }
 
                 // Build body with query params if present
-                let path = if let Some(query_params) = &yaml_step.request.query_params {
+                let path = if let Some(query_params) = &yaml_request.query_params {
                     let query_string: Vec<String> = query_params
                         .iter()
                         .map(|(k, v)| format!("{}={}", k, v))
                         .collect();
-                    format!("{}?{}", yaml_step.request.path, query_string.join("&"))
+                    format!("{}?{}", yaml_request.path, query_string.join("&"))
                 } else {
-                    yaml_step.request.path.clone()
+                    yaml_request.path.clone()
                 };
 
                 // Validate mutual exclusion of body and body_size
-                if yaml_step.request.body.is_some() && yaml_step.request.body_size.is_some() {
+                if yaml_request.body.is_some() && yaml_request.body_size.is_some() {
                     return Err(YamlConfigError::Validation(format!(
                         "Step '{}': 'body' and 'bodySize' are mutually exclusive — use one or the other",
                         step_name
@@ -620,8 +2020,7 @@ impl YamlConfig {
                 }
 
                 // Parse body_size string to bytes
-                let body_size = yaml_step
-                    .request
+                let body_size = yaml_request
                     .body_size
                     .as_deref()
                     .map(parse_body_size)
@@ -633,12 +2032,37 @@ impl YamlConfig {
                         ))
                     })?;
 
+                let protobuf = match &yaml_request.protobuf {
+                    Some(pb) => {
+                        if pb.request_message.is_none() && pb.response_message.is_none() {
+                            return Err(YamlConfigError::Validation(format!(
+                                "Step '{}': protobuf needs 'requestMessage' and/or 'responseMessage'",
+                                step_name
+                            )));
+                        }
+                        if pb.request_message.is_some() && body_size.is_some() {
+                            return Err(YamlConfigError::Validation(format!(
+                                "Step '{}': protobuf 'requestMessage' encodes 'body' and cannot be used with 'bodySize'",
+                                step_name
+                            )));
+                        }
+                        Some(ProtobufConfig {
+                            descriptor_set: PathBuf::from(&pb.descriptor_set),
+                            request_message: pb.request_message.clone(),
+                            response_message: pb.response_message.clone(),
+                        })
+                    }
+                    None => None,
+                };
+
                 let request = RequestConfig {
-                    method: yaml_step.request.method.clone(),
+                    method: yaml_request.method.clone(),
                     path,
-                    body: yaml_step.request.body.clone(),
+                    body: yaml_request.body.clone(),
                     body_size,
                     headers,
+                    protobuf,
+                    host: yaml_request.host.clone(),
                 };
 
                 // Convert extractors
@@ -646,12 +2070,13 @@ impl YamlConfig {
                     .extract
                     .iter()
                     .map(|e| self.convert_extractor(e))
-                    .collect();
+                    .collect::<Result<Vec<_>, _>>()?;
 
-                // Convert assertions
+                // Convert assertions; percentile SLAs are collected by latency_slas()
                 let assertions = yaml_step
                     .assertions
                     .iter()
+                    .filter(|a| !matches!(a, YamlAssertion::Percentile { .. }))
                     .map(|a| self.convert_assertion(a))
                     .collect::<Result<Vec<_>, _>>()?;
 
@@ -665,58 +2090,230 @@ impl YamlConfig {
                 let cache = if let Some(c) = &yaml_step.cache {
                     Some(StepCache {
                         ttl: c.ttl.to_std_duration()?,
+                        key: c.key.clone(),
+                        shared: c.shared,
                     })
                 } else {
                     None
                 };
 
-                steps.push(Step {
+                let slo = match &yaml_step.slo {
+                    Some(slo) => {
+                        let mut step_slo = StepSlo::new(slo.satisfied_ms);
+                        if let Some(tolerating_ms) = slo.tolerating_ms {
+                            step_slo.tolerating_ms = tolerating_ms;
+                        }
+                        if step_slo.satisfied_ms == 0
+                            || step_slo.tolerating_ms < step_slo.satisfied_ms
+                        {
+                            return Err(YamlConfigError::Validation(format!(
+                                "Step '{}': slo.satisfiedMs must be positive and no greater than slo.toleratingMs",
+                                step_name
+                            )));
+                        }
+                        Some(step_slo)
+                    }
+                    None => None,
+                };
+
+                let shared = yaml_step
+                    .shared
+                    .iter()
+                    .map(|action| {
+                        action.to_action().map_err(|e| {
+                            YamlConfigError::Validation(format!("Step '{}': {}", step_name, e))
+                        })
+                    })
+                    .collect::<Result<Vec<_>, _>>()?;
+
+                let rendezvous = match &yaml_step.rendezvous {
+                    Some(r) => {
+                        if r.count == 0 || r.name.is_empty() {
+                            return Err(YamlConfigError::Validation(format!(
+                                "Step '{}': rendezvous needs a name and a count of at least 1",
+                                step_name
+                            )));
+                        }
+                        Some(Rendezvous {
+                            name: r.name.clone(),
+                            count: r.count,
+                            timeout: match &r.timeout {
+                                Some(timeout) => timeout.to_std_duration()?,
+                                None => Rendezvous::DEFAULT_TIMEOUT,
+                            },
+                        })
+                    }
+                    None => None,
+                };
+
+                let idempotency_key = match yaml_step.idempotency_key.as_ref() {
+                    Some(key) => match key.header() {
+                        Some(header)
+                            if reqwest::header::HeaderName::from_bytes(header.as_bytes())
+                                .is_err() =>
+                        {
+                            return Err(YamlConfigError::Validation(format!(
+                                "Step '{}': idempotencyKey '{}' is not a valid header name",
+                                step_name, header
+                            )));
+                        }
+                        header => header.map(str::to_string),
+                    },
+                    None => None,
+                };
+
+                let tcp_check = yaml_step
+                    .tcp_check
+                    .as_ref()
+                    .map(|check| {
+                        check.to_tcp_check().map_err(|e| {
+                            YamlConfigError::Validation(format!("Step '{}': {}", step_name, e))
+                        })
+                    })
+                    .transpose()?;
+
+                let paginate = match &yaml_step.paginate {
+                    Some(p) => {
+                        let max_pages = p.max_pages.unwrap_or(Paginate::DEFAULT_MAX_PAGES);
+                        if max_pages == 0 {
+                            return Err(YamlConfigError::Validation(format!(
+                                "Step '{}': paginate.maxPages must be at least 1",
+                                step_name
+                            )));
+                        }
+                        Some(Paginate {
+                            next_field: p.next_field.clone(),
+                            max_pages,
+                            cursor_param: p.cursor_param.clone(),
+                        })
+                    }
+                    None => None,
+                };
+
+                let step = Step {
                     name: step_name,
                     request,
                     extractions: extractors,
                     assertions,
                     cache,
                     think_time,
-                });
+                    tags: yaml_step.tags.clone(),
+                    paginate,
+                    max_concurrent: yaml_step.max_concurrent,
+                    slo,
+                    shared,
+                    rendezvous,
+                    conditional: yaml_step.conditional,
+                    expected_status: yaml_step.expected_status.clone(),
+                    patterns: Default::default(),
+                    templates: Default::default(),
+                    idempotency_key,
+                    tcp_check,
+                };
+                // Compile JSONPath/regex and templates once here rather than per request
+                step.patterns();
+                step.templates();
+                steps.push(step);
             }
 
             scenarios.push(Scenario {
                 name: yaml_scenario.name.clone(),
                 weight: yaml_scenario.weight,
                 steps,
+                network_profile: yaml_scenario
+                    .network_profile
+                    .as_ref()
+                    .map(|p| p.to_network_profile())
+                    .transpose()?,
+                tags: yaml_scenario.tags.clone(),
+                max_concurrent: yaml_scenario.max_concurrent,
+                depends_on: yaml_scenario.depends_on.clone(),
+                iterations: yaml_scenario.iterations,
+                retry: yaml_scenario.config.retry_policy()?,
+                assertion_sample_rate: yaml_scenario.config.assertion_sample_rate.unwrap_or(1.0),
+                client: yaml_scenario
+                    .client
+                    .as_ref()
+                    .map(|c| c.to_overrides())
+                    .transpose()?,
+                headers: ScenarioHeaders {
+                    scenario: HeaderLayer::new(&yaml_scenario.headers),
+                    auth: yaml_scenario.auth.as_ref().map(|a| a.to_auth_scheme()),
+                },
+                optional: yaml_scenario.optional,
+                rps: yaml_scenario.rps,
+                data_source: yaml_scenario.data_source.clone(),
             });
         }
 
         Ok(scenarios)
     }
 
-    fn convert_extractor(&self, extractor: &YamlExtractor) -> VariableExtraction {
-        match extractor {
-            YamlExtractor::JsonPath { name, json_path } => VariableExtraction {
-                name: name.clone(),
-                extractor: Extractor::JsonPath(json_path.clone()),
-            },
-            YamlExtractor::Regex { name, regex } => {
+    fn convert_extractor(
+        &self,
+        extractor: &YamlExtractor,
+    ) -> Result<VariableExtraction, YamlConfigError> {
+        let (name, extractor, transform, scope) = match extractor {
+            YamlExtractor::JsonPath {
+                name,
+                json_path,
+                transform,
+                scope,
+            } => (
+                name,
+                Extractor::JsonPath(json_path.clone()),
+                transform,
+                scope,
+            ),
+            YamlExtractor::XmlPath {
+                name,
+                xml_path,
+                transform,
+                scope,
+            } => (name, Extractor::XmlPath(xml_path.clone()), transform, scope),
+            YamlExtractor::Regex {
+                name,
+                regex,
+                transform,
+                scope,
+            } => {
                 // For Regex, we need to parse the regex to extract pattern and group
                 // For now, use the entire regex as pattern and empty group
                 // TODO: Improve regex parsing to separate pattern and group
-                VariableExtraction {
-                    name: name.clone(),
-                    extractor: Extractor::Regex {
+                (
+                    name,
+                    Extractor::Regex {
                         pattern: regex.clone(),
                         group: String::from("0"), // Default to capture group 0 (full match)
                     },
-                }
+                    transform,
+                    scope,
+                )
             }
-            YamlExtractor::Header { name, header } => VariableExtraction {
-                name: name.clone(),
-                extractor: Extractor::Header(header.clone()),
-            },
-            YamlExtractor::Cookie { name, cookie } => VariableExtraction {
-                name: name.clone(),
-                extractor: Extractor::Cookie(cookie.clone()),
-            },
-        }
+            YamlExtractor::Header {
+                name,
+                header,
+                transform,
+                scope,
+            } => (name, Extractor::Header(header.clone()), transform, scope),
+            YamlExtractor::Cookie {
+                name,
+                cookie,
+                transform,
+                scope,
+            } => (name, Extractor::Cookie(cookie.clone()), transform, scope),
+        };
+        let transforms = match transform {
+            Some(spec) => parse_transforms(spec)
+                .map_err(|e| YamlConfigError::Validation(format!("Extractor '{}': {}", name, e)))?,
+            None => Vec::new(),
+        };
+        Ok(VariableExtraction {
+            name: name.clone(),
+            extractor,
+            transforms,
+            scope: scope.map(VariableScope::from).unwrap_or_default(),
+        })
     }
 
     fn convert_assertion(&self, assertion: &YamlAssertion) -> Result<Assertion, YamlConfigError> {
@@ -729,9 +2326,87 @@ impl YamlConfig {
                 path: path.clone(),
                 expected: expected.clone(),
             }),
+            YamlAssertion::XmlPath { path, expected } => Ok(Assertion::XmlPath {
+                path: path.clone(),
+                expected: expected.clone(),
+            }),
             YamlAssertion::BodyContains { text } => Ok(Assertion::BodyContains(text.clone())),
             YamlAssertion::BodyMatches { regex } => Ok(Assertion::BodyMatches(regex.clone())),
             YamlAssertion::HeaderExists { header } => Ok(Assertion::HeaderExists(header.clone())),
+            YamlAssertion::HeaderEquals { header, expected } => Ok(Assertion::HeaderEquals {
+                header: header.clone(),
+                expected: expected.clone(),
+            }),
+            YamlAssertion::HeaderMatches { header, regex } => {
+                if let Err(e) = regex::Regex::new(regex) {
+                    return Err(YamlConfigError::Validation(format!(
+                        "headerMatches regex '{}' is invalid: {}",
+                        regex, e
+                    )));
+                }
+                Ok(Assertion::HeaderMatches {
+                    header: header.clone(),
+                    pattern: regex.clone(),
+                })
+            }
+            YamlAssertion::BodySha256 { expected } => {
+                if expected.len() != 64 || !expected.chars().all(|c| c.is_ascii_hexdigit()) {
+                    return Err(YamlConfigError::Validation(format!(
+                        "bodySha256 expected '{}' is not a 64-character hex SHA-256",
+                        expected
+                    )));
+                }
+                Ok(Assertion::BodySha256 {
+                    expected: expected.clone(),
+                })
+            }
+            YamlAssertion::BodySizeBytes { min, max } => {
+                let inverted = matches!((min, max), (Some(min), Some(max)) if min > max);
+                if (min.is_none() && max.is_none()) || inverted {
+                    return Err(YamlConfigError::Validation(
+                        "bodySizeBytes needs min and/or max, with min <= max".to_string(),
+                    ));
+                }
+                Ok(Assertion::BodySizeBytes {
+                    min: *min,
+                    max: *max,
+                })
+            }
+            YamlAssertion::NotModified => Ok(Assertion::NotModified),
+            YamlAssertion::CacheHitRatio { min, min_samples } => {
+                if !(0.0..=1.0).contains(min) {
+                    return Err(YamlConfigError::Validation(format!(
+                        "cacheHitRatio min {} must be between 0 and 1",
+                        min
+                    )));
+                }
+                Ok(Assertion::CacheHitRatio {
+                    min: *min,
+                    min_samples: *min_samples,
+                })
+            }
+            YamlAssertion::MatchesGolden {
+                path,
+                ignore_fields,
+            } => {
+                if path.trim().is_empty() || ignore_fields.iter().any(|f| f.trim().is_empty()) {
+                    return Err(YamlConfigError::Validation(
+                        "matchesGolden needs a path, and ignoreFields entries cannot be empty"
+                            .to_string(),
+                    ));
+                }
+                Ok(Assertion::MatchesGolden {
+                    path: path.clone(),
+                    ignore_fields: ignore_fields.clone(),
+                })
+            }
+            YamlAssertion::Percentile { .. } => Err(YamlConfigError::Validation(
+                "percentile assertions are judged over a window, not per response".to_string(),
+            )),
+            YamlAssertion::Plugin { plugin, config } => Ok(Assertion::Plugin {
+                name: plugin.clone(),
+                config: config.clone(),
+            }),
         }
     }
 }
@@ -750,10 +2425,17 @@ impl Default for YamlConfig {
                 custom_headers: None,
                 resolve_target_addr: None,
                 pool: None,
+                template_dir: None,
+                tls_server_name: None,
+                start_at: None,
             },
             load: YamlLoadModel::Concurrent,
             scenarios: vec![],
             standby: None,
+            phases: Vec::new(),
+            variables: HashMap::new(),
+            scenario_thresholds: HashMap::new(),
+            clients: Vec::new(),
         }
     }
 }
@@ -761,6 +2443,7 @@ impl Default for YamlConfig {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::extractor::Transform;
 
     #[test]
     fn test_parse_simple_yaml() {
@@ -959,6 +2642,7 @@ scenarios:
                 min_rps,
                 max_rps,
                 ramp_duration,
+                ..
             } => {
                 assert_eq!(min_rps, 10.0);
                 assert_eq!(max_rps, 100.0);
@@ -967,4 +2651,1514 @@ scenarios:
             _ => panic!("Expected RampRps load model"),
         }
     }
+
+    #[test]
+    fn test_load_model_aligned_to_clock() {
+        let yaml = r#"
+version: "1.0"
+config:
+  baseUrl: "https://test.com"
+  duration: "1m"
+load:
+  model: "dailytraffic"
+  min: 10
+  mid: 50
+  max: 100
+  cycleDuration: "1d"
+  alignToClock: true
+  cycleStart: "04:12"
+scenarios:
+  - name: "Test"
+    steps:
+      - request:
+          method: "GET"
+          path: "/"
+"#;
+
+        let config = YamlConfig::from_str(yaml).unwrap();
+        let load_model = config.load.to_load_model().unwrap();
+        assert_eq!(
+            load_model.clock_start(),
+            Some(StdDuration::from_secs(4 * 3600 + 12 * 60))
+        );
+
+        let invalid = yaml.replace("04:12", "25:00");
+        assert!(YamlConfig::from_str(&invalid).is_err());
+
+        let unaligned = yaml.replace("  alignToClock: true\n", "");
+        let config = YamlConfig::from_str(&unaligned).unwrap();
+        assert_eq!(config.load.to_load_model().unwrap().clock_start(), None);
+    }
+
+    #[test]
+    fn test_composite_load_model() {
+        let yaml = r#"
+version: "1.0"
+config:
+  baseUrl: "https://test.com"
+  duration: "1m"
+load:
+  model: "composite"
+  base:
+    model: "ramp"
+    min: 10
+    max: 100
+    rampDuration: "90s"
+  overlays:
+    - op: "add"
+      load:
+        model: "rps"
+        target: 5
+    - op: "multiply"
+      load:
+        model: "rps"
+        target: 2
+scenarios:
+  - name: "Test"
+    steps:
+      - request:
+          method: "GET"
+          path: "/"
+"#;
+
+        let config = YamlConfig::from_str(yaml).unwrap();
+        let load_model = config.load.to_load_model().unwrap();
+        assert!((load_model.calculate_current_rps(45.0, 60.0) - 210.0).abs() < 0.001);
+
+        let concurrent = yaml.replace("model: \"rps\"\n        target: 5", "model: \"concurrent\"");
+        assert!(YamlConfig::from_str(&concurrent).is_err());
+    }
+
+    #[test]
+    fn test_exponential_load_model() {
+        let yaml = r#"
+version: "1.0"
+config:
+  baseUrl: "https://test.com"
+  duration: "1h"
+load:
+  model: "exponential"
+  startRps: 10
+  doublingPeriod: "2m"
+  maxRps: 5000
+  abortErrorRate: 5
+  abortLatencyMs: 2000
+scenarios:
+  - name: "Test"
+    steps:
+      - request:
+          method: "GET"
+          path: "/"
+"#;
+
+        let config = YamlConfig::from_str(yaml).unwrap();
+        let load_model = config.load.to_load_model().unwrap();
+        assert!((load_model.calculate_current_rps(240.0, 3600.0) - 40.0).abs() < 0.001);
+        assert_eq!(
+            load_model.abort_threshold(),
+            Some(&AbortThreshold {
+                error_rate_percent: Some(5.0),
+                latency: Some(StdDuration::from_secs(2)),
+            })
+        );
+
+        assert!(YamlConfig::from_str(&yaml.replace("\"2m\"", "\"0s\"")).is_err());
+        assert!(YamlConfig::from_str(&yaml.replace("maxRps: 5000", "maxRps: 5")).is_err());
+    }
+
+    #[test]
+    fn test_daily_traffic_calendar_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("calendar.yaml");
+        std::fs::write(&path, "weekdays:\n  sunday: 0.4\n").unwrap();
+        let yaml = format!(
+            r#"
+version: "1.0"
+config:
+  baseUrl: "https://test.com"
+  duration: "1m"
+load:
+  model: "dailytraffic"
+  min: 10
+  mid: 50
+  max: 100
+  cycleDuration: "1d"
+  calendarFile: "{}"
+scenarios:
+  - name: "Test"
+    steps:
+      - request:
+          method: "GET"
+          path: "/"
+"#,
+            path.display()
+        );
+
+        let config = YamlConfig::from_str(&yaml).unwrap();
+        match config.load.to_load_model().unwrap() {
+            LoadModel::DailyTraffic { calendar, .. } => assert!(calendar.is_some()),
+            _ => panic!("Expected DailyTraffic load model"),
+        }
+
+        let missing = yaml.replace("calendar.yaml", "missing.yaml");
+        let config = YamlConfig::from_str(&missing).unwrap();
+        assert!(config.load.to_load_model().is_err());
+    }
+
+    #[cfg(feature = "importers")]
+    #[test]
+    fn test_from_curl_step() {
+        let yaml = r#"
+version: "1.0"
+config:
+  baseUrl: "https://test.com"
+  duration: "1m"
+load:
+  model: "concurrent"
+scenarios:
+  - name: "Test"
+    steps:
+      - name: "Create"
+        fromCurl: >-
+          curl -X POST 'https://test.com/items'
+          -H 'Content-Type: application/json'
+          -d '{"name":"x"}'
+      - fromCurl: "curl https://test.com/items/1"
+        request:
+          method: "DELETE"
+          headers:
+            Authorization: "Bearer ${token}"
+"#;
+
+        let config = YamlConfig::from_str(yaml).unwrap();
+        let scenarios = config.to_scenarios().unwrap();
+        let steps = &scenarios[0].steps;
+
+        assert_eq!(steps[0].request.method, "POST");
+        assert_eq!(steps[0].request.path, "https://test.com/items");
+        assert_eq!(steps[0].request.body.as_deref(), Some(r#"{"name":"x"}"#));
+        assert_eq!(steps[0].request.headers["Content-Type"], "application/json");
+
+        assert_eq!(steps[1].request.method, "DELETE");
+        assert_eq!(steps[1].request.path, "https://test.com/items/1");
+        assert_eq!(steps[1].request.headers["Authorization"], "Bearer ${token}");
+    }
+
+    #[test]
+    fn test_from_curl_invalid_command() {
+        let yaml = r#"
+version: "1.0"
+config:
+  baseUrl: "https://test.com"
+  duration: "1m"
+load:
+  model: "concurrent"
+scenarios:
+  - name: "Test"
+    steps:
+      - fromCurl: "wget https://test.com"
+"#;
+
+        let err = YamlConfig::from_str(yaml).unwrap_err();
+        assert!(err.to_string().contains("fromCurl"));
+    }
+
+    #[test]
+    fn test_step_without_request_or_curl_is_invalid() {
+        let yaml = r#"
+version: "1.0"
+config:
+  baseUrl: "https://test.com"
+  duration: "1m"
+load:
+  model: "concurrent"
+scenarios:
+  - name: "Test"
+    steps:
+      - name: "Empty"
+"#;
+
+        assert!(YamlConfig::from_str(yaml).is_err());
+    }
+
+    #[test]
+    fn test_network_profile_preset_and_custom() {
+        let yaml = r#"
+version: "1.0"
+config:
+  baseUrl: "https://test.com"
+  duration: "1m"
+load:
+  model: "concurrent"
+scenarios:
+  - name: "Mobile"
+    networkProfile: 3g
+    steps:
+      - request:
+          method: "GET"
+          path: "/"
+  - name: "Custom"
+    networkProfile:
+      latencyMs: 250
+      downloadKbps: 800
+    steps:
+      - request:
+          method: "GET"
+          path: "/"
+"#;
+
+        let config = YamlConfig::from_str(yaml).unwrap();
+        let scenarios = config.to_scenarios().unwrap();
+        assert_eq!(
+            scenarios[0].network_profile,
+            crate::network_profile::NetworkProfile::preset("3g")
+        );
+        let custom = scenarios[1].network_profile.unwrap();
+        assert_eq!(custom.latency, std::time::Duration::from_millis(250));
+        assert_eq!(custom.download_bytes_per_sec, Some(100_000));
+        assert_eq!(custom.upload_bytes_per_sec, None);
+
+        let bad = yaml.replace("networkProfile: 3g", "networkProfile: dialup");
+        let err = YamlConfig::from_str(&bad).unwrap_err();
+        assert!(err.to_string().contains("dialup"));
+    }
+
+    #[test]
+    fn test_scenario_client_overrides() {
+        let yaml = r#"
+version: "1.0"
+config:
+  baseUrl: "https://test.com"
+  duration: "1m"
+load:
+  model: "concurrent"
+scenarios:
+  - name: "Admin"
+    client:
+      clientCert: "/certs/admin.pem"
+      clientKey: "/certs/admin-key.pem"
+      headers:
+        X-Admin: "a,b"
+      proxy: "http://proxy:3128"
+      timeout: "10s"
+    steps:
+      - request:
+          method: "GET"
+          path: "/admin"
+  - name: "Public"
+    steps:
+      - request:
+          method: "GET"
+          path: "/"
+"#;
+
+        let config = YamlConfig::from_str(yaml).unwrap();
+        let scenarios = config.to_scenarios().unwrap();
+        assert!(scenarios[1].client.is_none());
+        let overrides = scenarios[0].client.clone().unwrap();
+        assert_eq!(overrides.timeout, Some(StdDuration::from_secs(10)));
+
+        let mut client_config = crate::config::Config::for_testing().to_client_config();
+        client_config.custom_headers = Some("X-Node:1".to_string());
+        overrides.apply(&mut client_config);
+        assert_eq!(
+            client_config.client_cert_path.as_deref(),
+            Some("/certs/admin.pem")
+        );
+        assert_eq!(
+            client_config.custom_headers.as_deref(),
+            Some("X-Node:1,X-Admin:a\\,b")
+        );
+        assert_eq!(client_config.proxy.as_deref(), Some("http://proxy:3128"));
+
+        let no_key = yaml.replace("      clientKey: \"/certs/admin-key.pem\"\n", "");
+        let err = YamlConfig::from_str(&no_key).unwrap_err();
+        assert!(err.to_string().contains("clientKey"), "{}", err);
+        let bad_proxy = yaml.replace("http://proxy:3128", "::not a url");
+        assert!(YamlConfig::from_str(&bad_proxy).is_err());
+    }
+
+    #[test]
+    fn test_scenario_headers_and_auth_layers() {
+        let yaml = r#"
+version: "1.0"
+config:
+  baseUrl: "https://test.com"
+  duration: "1m"
+load:
+  model: "concurrent"
+scenarios:
+  - name: "Tenant"
+    headers:
+      X-Tenant: "${tenant}"
+      Accept: "application/json"
+    auth:
+      basic:
+        username: "${user}"
+        password: "secret"
+    steps:
+      - name: "List"
+        request:
+          method: "GET"
+          path: "/items"
+          headers:
+            x-tenant: "override"
+"#;
+
+        let config = YamlConfig::from_str(yaml).unwrap();
+        let scenarios = config.to_scenarios().unwrap();
+        let headers = &scenarios[0].headers;
+        assert_eq!(headers.scenario.headers.len(), 2);
+        assert!(matches!(headers.auth, Some(AuthScheme::Basic { .. })));
+
+        let global = HeaderLayer::from_custom_headers(Some("Authorization:Bearer x")).unwrap();
+        let conflicts: Vec<String> = crate::header_layers::conflicts(&global, &scenarios)
+            .iter()
+            .map(|c| c.to_string())
+            .collect();
+        assert_eq!(
+            conflicts,
+            vec![
+                "header 'Authorization' in scenario 'Tenant' is set by global, auth layers; auth wins",
+                "header 'X-Tenant' in scenario 'Tenant' step 'List' is set by scenario, step layers; step wins",
+            ]
+        );
+
+        let bearer = yaml.replace(
+            "      basic:\n        username: \"${user}\"\n        password: \"secret\"\n",
+            "      bearer: \"${token}\"\n",
+        );
+        let config = YamlConfig::from_str(&bearer).unwrap();
+        assert!(matches!(
+            config.to_scenarios().unwrap()[0].headers.auth,
+            Some(AuthScheme::Bearer(_))
+        ));
+
+        let bad = yaml.replace("X-Tenant: \"${tenant}\"", "\"Bad Name\": \"1\"");
+        let err = YamlConfig::from_str(&bad).unwrap_err();
+        assert!(err.to_string().contains("Bad Name"), "{}", err);
+    }
+
+    #[test]
+    fn test_request_defaults() {
+        let yaml = r#"
+version: "1.0"
+config:
+  baseUrl: "https://test.com"
+  duration: "1m"
+load:
+  model: "concurrent"
+scenarios:
+  - name: "Orders"
+    requestDefaults:
+      basePath: "/api/v2/"
+      headers:
+        Accept: "application/json"
+        X-Client: "loadtest"
+      queryParams:
+        locale: "en"
+    steps:
+      - name: "List"
+        request:
+          method: "GET"
+          path: "/orders"
+          headers:
+            accept: "text/csv"
+      - name: "External"
+        request:
+          method: "GET"
+          path: "https://other.test/ping"
+          queryParams:
+            locale: "fr"
+"#;
+
+        let config = YamlConfig::from_str(yaml).unwrap();
+        let scenarios = config.to_scenarios().unwrap();
+        let list = &scenarios[0].steps[0].request;
+        assert_eq!(list.path, "/api/v2/orders?locale=en");
+        assert_eq!(list.headers.len(), 2);
+        assert_eq!(list.headers["accept"], "text/csv");
+        assert_eq!(list.headers["X-Client"], "loadtest");
+        let external = &scenarios[0].steps[1].request;
+        assert_eq!(external.path, "https://other.test/ping?locale=fr");
+
+        let bad = yaml.replace("basePath: \"/api/v2/\"", "basePath: \"api\"");
+        let err = YamlConfig::from_str(&bad).unwrap_err();
+        assert!(err.to_string().contains("basePath"), "{}", err);
+    }
+
+    #[test]
+    fn test_percentile_assertions_become_latency_slas() {
+        let yaml = r#"
+version: "1.0"
+config:
+  baseUrl: "https://test.com"
+  duration: "1m"
+load:
+  model: "concurrent"
+scenarios:
+  - name: "Checkout"
+    steps:
+      - name: "Pay"
+        request:
+          method: "POST"
+          path: "/pay"
+        assertions:
+          - type: statusCode
+            expected: 200
+          - type: percentile
+            percentile: 95
+            window: "1m"
+            maxMs: 300
+"#;
+
+        let config = YamlConfig::from_str(yaml).unwrap();
+        let scenarios = config.to_scenarios().unwrap();
+        assert_eq!(scenarios[0].steps[0].assertions.len(), 1);
+        let slas = config.latency_slas().unwrap();
+        assert_eq!(slas.len(), 1);
+        assert_eq!(slas[0].to_string(), "p95 of Checkout/Pay over 60s < 300ms");
+        assert_eq!(slas[0].min_samples, 20);
+
+        let bad = yaml.replace("percentile: 95", "percentile: 0");
+        let err = YamlConfig::from_str(&bad).unwrap_err();
+        assert!(err.to_string().contains("percentile 0"), "{}", err);
+    }
+
+    #[test]
+    fn test_client_profiles() {
+        let yaml = r#"
+version: "1.0"
+config:
+  baseUrl: "https://test.com"
+  duration: "1m"
+load:
+  model: "concurrent"
+scenarios:
+  - name: "Browse"
+    steps:
+      - request:
+          method: "GET"
+          path: "/"
+clients:
+  - name: "ios"
+    weight: 60
+    userAgent: "Shop/5.2 (iPhone; iOS 17.4)"
+    acceptLanguage: "en-US"
+    headers:
+      X-Platform: "ios"
+      user-agent: "ignored"
+    thinkTimeMultiplier: 1.5
+  - name: "desktop"
+    weight: 40
+"#;
+
+        let config = YamlConfig::from_str(yaml).unwrap();
+        let profiles = config.client_profiles();
+        let ios = &profiles.profiles()[0];
+        assert_eq!(ios.name, "ios");
+        assert_eq!(ios.think_time_multiplier, 1.5);
+        let headers: Vec<(&str, &str)> = ios
+            .headers
+            .headers
+            .iter()
+            .map(|(name, value)| (name.as_str(), value.source()))
+            .collect();
+        assert_eq!(
+            headers,
+            vec![
+                ("Accept-Language", "en-US"),
+                ("User-Agent", "Shop/5.2 (iPhone; iOS 17.4)"),
+                ("X-Platform", "ios"),
+            ]
+        );
+        let desktop = &profiles.profiles()[1];
+        assert!(desktop.headers.is_empty());
+        assert_eq!(desktop.think_time_multiplier, 1.0);
+
+        let duplicate = yaml.replace("name: \"desktop\"", "name: \"ios\"");
+        let err = YamlConfig::from_str(&duplicate).unwrap_err();
+        assert!(
+            err.to_string().contains("Duplicate client profile"),
+            "{}",
+            err
+        );
+        let bad_weight = yaml.replace("weight: 40", "weight: 0");
+        assert!(YamlConfig::from_str(&bad_weight).is_err());
+        let bad_multiplier = yaml.replace("thinkTimeMultiplier: 1.5", "thinkTimeMultiplier: -1");
+        let err = YamlConfig::from_str(&bad_multiplier).unwrap_err();
+        assert!(err.to_string().contains("thinkTimeMultiplier"), "{}", err);
+    }
+
+    #[test]
+    fn test_scenario_thresholds() {
+        let yaml = r#"
+version: "1.0"
+config:
+  baseUrl: "https://test.com"
+  duration: "1m"
+load:
+  model: "concurrent"
+scenarioThresholds:
+  checkout: "successRate > 99.5%"
+  browse: "successRate >= 95"
+scenarios:
+  - name: "checkout"
+    steps:
+      - request:
+          method: "POST"
+          path: "/pay"
+  - name: "browse"
+    steps:
+      - request:
+          method: "GET"
+          path: "/"
+"#;
+
+        let config = YamlConfig::from_str(yaml).unwrap();
+        let thresholds = config.scenario_thresholds().unwrap();
+        assert_eq!(thresholds.len(), 2);
+        assert_eq!(thresholds[0].scenario, "browse");
+        assert!(thresholds[0].success_rate.inclusive);
+        assert_eq!(thresholds[1].success_rate.percent, 99.5);
+
+        let unknown = yaml.replace("  browse: \"successRate", "  search: \"successRate");
+        let err = YamlConfig::from_str(&unknown).unwrap_err();
+        assert!(
+            err.to_string().contains("Unknown scenario 'search'"),
+            "{}",
+            err
+        );
+        let bad = yaml.replace("> 99.5%", "< 99.5%");
+        let err = YamlConfig::from_str(&bad).unwrap_err();
+        assert!(err.to_string().contains("successRate >"), "{}", err);
+    }
+
+    #[test]
+    fn test_scenario_and_step_tags() {
+        let yaml = r#"
+version: "1.0"
+config:
+  baseUrl: "https://test.com"
+  duration: "1m"
+load:
+  model: "concurrent"
+scenarios:
+  - name: "Checkout"
+    tags:
+      team: checkout
+      tier: critical
+    steps:
+      - name: "Pay"
+        tags:
+          tier: payments
+        request:
+          method: "POST"
+          path: "/pay"
+"#;
+
+        let scenarios = YamlConfig::from_str(yaml).unwrap().to_scenarios().unwrap();
+        let scenario = &scenarios[0];
+        assert_eq!(scenario.tags["team"], "checkout");
+        let tags = scenario.step_tags(&scenario.steps[0]);
+        assert_eq!(tags["team"], "checkout");
+        assert_eq!(tags["tier"], "payments");
+    }
+
+    #[test]
+    fn test_xml_path_extractor_and_assertion() {
+        let yaml = r#"
+version: "1.0"
+config:
+  baseUrl: "https://test.com"
+  duration: "1m"
+load:
+  model: "concurrent"
+scenarios:
+  - name: "SOAP"
+    steps:
+      - request:
+          method: "POST"
+          path: "/soap"
+        extract:
+          - type: "xmlPath"
+            name: "price"
+            xmlPath: "//Price"
+        assertions:
+          - type: "xmlPath"
+            path: "//Price/@currency"
+            expected: "USD"
+"#;
+
+        let config = YamlConfig::from_str(yaml).unwrap();
+        let scenarios = config.to_scenarios().unwrap();
+        let step = &scenarios[0].steps[0];
+        assert!(matches!(
+            &step.extractions[0].extractor,
+            Extractor::XmlPath(path) if path == "//Price"
+        ));
+        assert!(matches!(
+            &step.assertions[0],
+            Assertion::XmlPath { path, expected: Some(v) } if path == "//Price/@currency" && v == "USD"
+        ));
+    }
+
+    #[test]
+    fn test_extractor_transforms_and_scope() {
+        let yaml = r#"
+version: "1.0"
+config:
+  baseUrl: "https://test.com"
+  duration: "1m"
+load:
+  model: "concurrent"
+scenarios:
+  - name: "Search"
+    steps:
+      - request:
+          method: "GET"
+          path: "/session"
+        extract:
+          - type: "jsonPath"
+            name: "shortId"
+            jsonPath: "$.id"
+            transform: "toUpper | substring(0,8)"
+          - type: "header"
+            name: "trace"
+            header: "X-Trace"
+            scope: user
+"#;
+
+        let scenarios = YamlConfig::from_str(yaml).unwrap().to_scenarios().unwrap();
+        let extractions = &scenarios[0].steps[0].extractions;
+        assert_eq!(
+            extractions[0].transforms,
+            vec![
+                Transform::ToUpper,
+                Transform::Substring { start: 0, end: 8 }
+            ]
+        );
+        assert!(extractions[1].transforms.is_empty());
+        assert_eq!(extractions[0].scope, VariableScope::Iteration);
+        assert_eq!(extractions[1].scope, VariableScope::User);
+
+        let unknown = yaml.replace("toUpper", "toTitle");
+        let config = YamlConfig::from_str(&unknown).unwrap();
+        assert!(config.to_scenarios().is_err());
+    }
+
+    #[test]
+    fn test_checksum_assertions() {
+        let yaml = r#"
+version: "1.0"
+config:
+  baseUrl: "https://test.com"
+  duration: "1m"
+load:
+  model: "concurrent"
+scenarios:
+  - name: "Download"
+    steps:
+      - request:
+          method: "GET"
+          path: "/files/firmware.bin"
+        assertions:
+          - type: "bodySha256"
+            expected: "2cf24dba5fb0a30e26e83b2ac5b9e29e1b161e5c1fa7425e73043362938b9824"
+          - type: "bodySizeBytes"
+            min: 1024
+            max: 4096
+"#;
+
+        let scenarios = YamlConfig::from_str(yaml).unwrap().to_scenarios().unwrap();
+        let assertions = &scenarios[0].steps[0].assertions;
+        assert!(
+            matches!(&assertions[0], Assertion::BodySha256 { expected } if expected.starts_with("2cf24d"))
+        );
+        assert!(matches!(
+            assertions[1],
+            Assertion::BodySizeBytes {
+                min: Some(1024),
+                max: Some(4096)
+            }
+        ));
+
+        for invalid in [
+            yaml.replace("2cf24dba", "not-hex!"),
+            yaml.replace("min: 1024", "min: 8192"),
+            yaml.replace("            min: 1024\n            max: 4096\n", ""),
+        ] {
+            let config = YamlConfig::from_str(&invalid).unwrap();
+            assert!(config.to_scenarios().is_err(), "{}", invalid);
+        }
+    }
+
+    #[test]
+    fn test_header_value_assertions() {
+        let yaml = r#"
+version: "1.0"
+config:
+  baseUrl: "https://test.com"
+  duration: "1m"
+load:
+  model: "concurrent"
+scenarios:
+  - name: "CORS"
+    steps:
+      - request:
+          method: "GET"
+          path: "/api"
+        assertions:
+          - type: headerEquals
+            header: Access-Control-Allow-Origin
+            expected: "https://app.test.com"
+          - type: headerMatches
+            header: Cache-Control
+            regex: "max-age=\\d+"
+"#;
+
+        let scenarios = YamlConfig::from_str(yaml).unwrap().to_scenarios().unwrap();
+        let assertions = &scenarios[0].steps[0].assertions;
+        assert!(matches!(
+            &assertions[0],
+            Assertion::HeaderEquals { header, expected }
+                if header == "Access-Control-Allow-Origin" && expected == "https://app.test.com"
+        ));
+        assert!(matches!(
+            &assertions[1],
+            Assertion::HeaderMatches { pattern, .. } if pattern == r"max-age=\d+"
+        ));
+
+        let invalid = yaml.replace("max-age=\\\\d+", "max-age=(");
+        let config = YamlConfig::from_str(&invalid).unwrap();
+        assert!(config.to_scenarios().is_err());
+    }
+
+    #[test]
+    fn test_conditional_step_and_cache_assertions() {
+        let yaml = r#"
+version: "1.0"
+config:
+  baseUrl: "https://test.com"
+  duration: "1m"
+load:
+  model: "concurrent"
+scenarios:
+  - name: "Catalog"
+    steps:
+      - request:
+          method: "GET"
+          path: "/catalog.json"
+        conditional: true
+        assertions:
+          - type: "notModified"
+          - type: "cacheHitRatio"
+            min: 0.9
+      - request:
+          method: "GET"
+          path: "/other"
+"#;
+
+        let scenarios = YamlConfig::from_str(yaml).unwrap().to_scenarios().unwrap();
+        let steps = &scenarios[0].steps;
+        assert!(steps[0].conditional);
+        assert!(!steps[1].conditional);
+        assert!(matches!(steps[0].assertions[0], Assertion::NotModified));
+        assert!(matches!(
+            steps[0].assertions[1],
+            Assertion::CacheHitRatio {
+                min,
+                min_samples: 20
+            } if min == 0.9
+        ));
+
+        let invalid = yaml.replace("min: 0.9", "min: 90");
+        let config = YamlConfig::from_str(&invalid).unwrap();
+        assert!(config.to_scenarios().is_err());
+    }
+
+    #[test]
+    fn test_matches_golden_assertion() {
+        let yaml = r#"
+version: "1.0"
+config:
+  baseUrl: "https://test.com"
+  duration: "1m"
+load:
+  model: "concurrent"
+scenarios:
+  - name: "Product"
+    steps:
+      - request:
+          method: "GET"
+          path: "/products/42"
+        assertions:
+          - type: "matchesGolden"
+            path: "goldens/product-42.json"
+            ignoreFields: ["updatedAt", "items.*.id"]
+"#;
+
+        let scenarios = YamlConfig::from_str(yaml).unwrap().to_scenarios().unwrap();
+        match &scenarios[0].steps[0].assertions[0] {
+            Assertion::MatchesGolden {
+                path,
+                ignore_fields,
+            } => {
+                assert_eq!(path, "goldens/product-42.json");
+                assert_eq!(ignore_fields, &vec!["updatedAt", "items.*.id"]);
+            }
+            other => panic!("unexpected assertion {:?}", other),
+        }
+
+        let empty = yaml.replace("goldens/product-42.json", "");
+        let config = YamlConfig::from_str(&empty).unwrap();
+        assert!(config.to_scenarios().is_err());
+    }
+
+    #[test]
+    fn test_protobuf_request_settings() {
+        let yaml = r#"
+version: "1.0"
+config:
+  baseUrl: "https://test.com"
+  duration: "1m"
+load:
+  model: "concurrent"
+scenarios:
+  - name: "Proto"
+    steps:
+      - request:
+          method: "POST"
+          path: "/rpc/CreateOrder"
+          body: '{"sku": "A-1"}'
+          protobuf:
+            descriptorSet: "protos/shop.pb"
+            requestMessage: "shop.CreateOrderRequest"
+            responseMessage: "shop.CreateOrderResponse"
+"#;
+
+        let config = YamlConfig::from_str(yaml).unwrap();
+        let scenarios = config.to_scenarios().unwrap();
+        let pb = scenarios[0].steps[0].request.protobuf.as_ref().unwrap();
+        assert_eq!(pb.descriptor_set, PathBuf::from("protos/shop.pb"));
+        assert_eq!(
+            pb.request_message.as_deref(),
+            Some("shop.CreateOrderRequest")
+        );
+        assert_eq!(
+            pb.response_message.as_deref(),
+            Some("shop.CreateOrderResponse")
+        );
+
+        let no_messages = yaml
+            .replace(
+                "            requestMessage: \"shop.CreateOrderRequest\"\n",
+                "",
+            )
+            .replace(
+                "            responseMessage: \"shop.CreateOrderResponse\"\n",
+                "",
+            );
+        let config = YamlConfig::from_str(&no_messages).unwrap();
+        assert!(config.to_scenarios().is_err());
+    }
+
+    #[test]
+    fn test_paginate_settings() {
+        let yaml = r#"
+version: "1.0"
+config:
+  baseUrl: "https://test.com"
+  duration: "1m"
+load:
+  model: "concurrent"
+scenarios:
+  - name: "List"
+    steps:
+      - request:
+          method: "GET"
+          path: "/items"
+        paginate:
+          nextField: "$.meta.next_cursor"
+          cursorParam: "after"
+"#;
+
+        let config = YamlConfig::from_str(yaml).unwrap();
+        let scenarios = config.to_scenarios().unwrap();
+        let paginate = scenarios[0].steps[0].paginate.as_ref().unwrap();
+        assert_eq!(paginate.next_field, "$.meta.next_cursor");
+        assert_eq!(paginate.max_pages, Paginate::DEFAULT_MAX_PAGES);
+        assert_eq!(paginate.cursor_param.as_deref(), Some("after"));
+
+        let zero = yaml.replace("cursorParam: \"after\"", "maxPages: 0");
+        let config = YamlConfig::from_str(&zero).unwrap();
+        assert!(config.to_scenarios().is_err());
+    }
+
+    #[test]
+    fn test_max_concurrent() {
+        let yaml = r#"
+version: "1.0"
+config:
+  baseUrl: "https://test.com"
+  duration: "1m"
+load:
+  model: "concurrent"
+scenarios:
+  - name: "Checkout"
+    maxConcurrent: 5
+    steps:
+      - name: "Pay"
+        maxConcurrent: 2
+        request:
+          method: "POST"
+          path: "/pay"
+      - request:
+          method: "GET"
+          path: "/receipt"
+"#;
+
+        let scenarios = YamlConfig::from_str(yaml).unwrap().to_scenarios().unwrap();
+        assert_eq!(scenarios[0].max_concurrent, Some(5));
+        assert_eq!(scenarios[0].steps[0].max_concurrent, Some(2));
+        assert_eq!(scenarios[0].steps[1].max_concurrent, None);
+
+        let zero = yaml.replace("maxConcurrent: 2", "maxConcurrent: 0");
+        assert!(YamlConfig::from_str(&zero).is_err());
+    }
+
+    #[test]
+    fn test_scenario_retry_policy() {
+        let yaml = r#"
+version: "1.0"
+config:
+  baseUrl: "https://test.com"
+  duration: "1m"
+load:
+  model: "concurrent"
+scenarios:
+  - name: "Rate Limited"
+    config:
+      retryCount: 3
+      retryDelay: "2s"
+      honorRetryAfter: true
+    steps:
+      - request:
+          method: "GET"
+          path: "/limited"
+  - name: "No Retries"
+    steps:
+      - request:
+          method: "GET"
+          path: "/"
+"#;
+
+        let scenarios = YamlConfig::from_str(yaml).unwrap().to_scenarios().unwrap();
+        assert_eq!(
+            scenarios[0].retry,
+            Some(RetryPolicy {
+                count: 3,
+                delay: StdDuration::from_secs(2),
+                honor_retry_after: true,
+                max_retry_after: RetryPolicy::DEFAULT_MAX_RETRY_AFTER,
+            })
+        );
+        assert_eq!(scenarios[1].retry, None);
+    }
+
+    #[test]
+    fn test_phases_and_depends_on() {
+        let yaml = r#"
+version: "1.0"
+config:
+  baseUrl: "https://test.com"
+  duration: "10m"
+load:
+  model: "concurrent"
+phases:
+  - name: seed
+    scenarios: ["Seed"]
+    duration: "1m"
+    workers: 2
+  - name: load
+    scenarios: ["Browse"]
+scenarios:
+  - name: "Seed"
+    iterations: 20
+    steps:
+      - request:
+          method: "POST"
+          path: "/users"
+  - name: "Browse"
+    dependsOn: ["Seed"]
+    steps:
+      - request:
+          method: "GET"
+          path: "/"
+"#;
+
+        let config = YamlConfig::from_str(yaml).unwrap();
+        let phases = config.to_phases().unwrap();
+        assert_eq!(phases.len(), 2);
+        assert_eq!(phases[0].name, "seed");
+        assert_eq!(phases[0].duration, StdDuration::from_secs(60));
+        assert_eq!(phases[0].workers, Some(2));
+        assert_eq!(phases[0].scenarios[0].iterations, Some(20));
+        assert_eq!(phases[1].scenarios[0].depends_on, vec!["Seed".to_string()]);
+        assert_eq!(phases[1].duration, StdDuration::from_secs(600));
+
+        let unknown = yaml.replace(r#"scenarios: ["Browse"]"#, r#"scenarios: ["Missing"]"#);
+        assert!(YamlConfig::from_str(&unknown).is_err());
+
+        let cycle = yaml
+            .replace("phases:", "unused:")
+            .replace("iterations: 20", "dependsOn: [\"Browse\"]");
+        assert!(YamlConfig::from_str(&cycle).is_err());
+
+        let zero = yaml.replace("iterations: 20", "iterations: 0");
+        assert!(YamlConfig::from_str(&zero).is_err());
+    }
+
+    #[cfg(feature = "service-discovery")]
+    #[test]
+    fn test_discovery_base_url() {
+        let yaml = r#"
+version: "1.0"
+config:
+  baseUrl: "k8s://checkout.shop:http/api"
+  duration: "1m"
+load:
+  model: "concurrent"
+scenarios:
+  - name: "Cart"
+    steps:
+      - request:
+          method: "GET"
+          path: "/cart"
+"#;
+
+        assert!(YamlConfig::from_str(yaml).is_ok());
+        assert!(YamlConfig::from_str(&yaml.replace("shop:http", "shop:")).is_err());
+        assert!(YamlConfig::from_str(&yaml.replace("k8s://", "ftp://")).is_err());
+    }
+
+    #[test]
+    fn test_per_scenario_rps() {
+        let yaml = r#"
+version: "1.0"
+config:
+  baseUrl: "https://test.com"
+  duration: "10m"
+load:
+  model: "rps"
+  target: 100
+scenarios:
+  - name: "Search"
+    rps: 800
+    steps:
+      - request:
+          method: "GET"
+          path: "/search"
+  - name: "Browse"
+    steps:
+      - request:
+          method: "GET"
+          path: "/"
+"#;
+
+        let config = YamlConfig::from_str(yaml).unwrap();
+        let scenarios = config.to_scenarios().unwrap();
+        assert_eq!(scenarios[0].rps, Some(800.0));
+        assert_eq!(scenarios[1].rps, None);
+
+        let zero = yaml.replace("rps: 800", "rps: 0");
+        assert!(YamlConfig::from_str(&zero).is_err());
+    }
+
+    #[test]
+    fn test_variables_and_shared_actions() {
+        let yaml = r#"
+version: "1.0"
+config:
+  baseUrl: "https://test.com"
+  duration: "1m"
+load:
+  model: "concurrent"
+variables:
+  tenant: "acme"
+  started: "${timestamp}"
+scenarios:
+  - name: "Login"
+    steps:
+      - request:
+          method: "POST"
+          path: "/login"
+        shared:
+          - push: tokens
+            value: "${token}"
+  - name: "Browse"
+    steps:
+      - request:
+          method: "GET"
+          path: "/orders"
+        shared:
+          - pop: tokens
+            as: token
+          - incr: requests
+            by: 2
+"#;
+
+        let config = YamlConfig::from_str(yaml).unwrap();
+        let variables = config.resolve_variables().unwrap();
+        assert_eq!(variables["tenant"], "acme");
+        assert!(variables["started"].parse::<u128>().is_ok());
+
+        let scenarios = config.to_scenarios().unwrap();
+        assert_eq!(
+            scenarios[0].steps[0].shared[0].op,
+            StoreOp::Push {
+                key: "tokens".to_string(),
+                value: "${token}".to_string()
+            }
+        );
+        let reads = &scenarios[1].steps[0].shared;
+        assert_eq!(reads[0].variable(), "token");
+        assert_eq!(
+            reads[1].op,
+            StoreOp::Incr {
+                key: "requests".to_string(),
+                by: 2
+            }
+        );
+        assert_eq!(reads[1].variable(), "requests");
+
+        for invalid in [
+            yaml.replace("            value: \"${token}\"\n", ""),
+            yaml.replace("- pop: tokens", "- pop: tokens\n            get: tokens"),
+            yaml.replace("as: token", "value: token"),
+        ] {
+            let config = YamlConfig::from_str(&invalid).unwrap();
+            assert!(config.to_scenarios().is_err(), "{}", invalid);
+        }
+    }
+
+    #[test]
+    fn test_step_expected_status() {
+        let yaml = r#"
+version: "1.0"
+config:
+  baseUrl: "https://test.com"
+  duration: "1m"
+load:
+  model: "concurrent"
+scenarios:
+  - name: "Negative"
+    steps:
+      - name: "Unknown Item"
+        expectedStatus: [404]
+        request:
+          method: "GET"
+          path: "/items/missing"
+      - request:
+          method: "GET"
+          path: "/items"
+"#;
+
+        let scenarios = YamlConfig::from_str(yaml).unwrap().to_scenarios().unwrap();
+        let steps = &scenarios[0].steps;
+        assert_eq!(steps[0].expected_status, vec![404]);
+        assert!(steps[0].status_ok(404));
+        assert!(!steps[0].status_ok(200));
+        assert!(steps[1].expected_status.is_empty());
+        assert!(steps[1].status_ok(302));
+        assert!(!steps[1].status_ok(404));
+
+        let invalid = yaml.replace("[404]", "[4040]");
+        assert!(YamlConfig::from_str(&invalid).is_err());
+    }
+
+    #[test]
+    fn test_scenario_assertion_sample_rate() {
+        let yaml = r#"
+version: "1.0"
+config:
+  baseUrl: "https://test.com"
+  duration: "1m"
+load:
+  model: "rps"
+  target: 50000
+scenarios:
+  - name: "Hot Path"
+    config:
+      assertionSampleRate: 0.1
+    steps:
+      - request:
+          method: "GET"
+          path: "/"
+        assertions:
+          - type: statusCode
+            expected: 200
+"#;
+
+        let scenarios = YamlConfig::from_str(yaml).unwrap().to_scenarios().unwrap();
+        assert_eq!(scenarios[0].assertion_sample_rate, 0.1);
+
+        let default = yaml.replace(
+            "      assertionSampleRate: 0.1\n",
+            "      timeout: \"5s\"\n",
+        );
+        let scenarios = YamlConfig::from_str(&default)
+            .unwrap()
+            .to_scenarios()
+            .unwrap();
+        assert_eq!(scenarios[0].assertion_sample_rate, 1.0);
+
+        let invalid = yaml.replace("0.1", "1.5");
+        assert!(YamlConfig::from_str(&invalid).is_err());
+    }
+
+    #[test]
+    fn test_step_idempotency_key() {
+        let yaml = r#"
+version: "1.0"
+config:
+  baseUrl: "https://test.com"
+  duration: "1m"
+load:
+  model: "concurrent"
+scenarios:
+  - name: "Checkout"
+    steps:
+      - name: "Order"
+        idempotencyKey: true
+        request:
+          method: "POST"
+          path: "/orders"
+      - name: "Pay"
+        idempotencyKey: "X-Request-Key"
+        request:
+          method: "POST"
+          path: "/pay"
+      - request:
+          method: "GET"
+          path: "/orders"
+"#;
+
+        let scenarios = YamlConfig::from_str(yaml).unwrap().to_scenarios().unwrap();
+        let keys: Vec<_> = scenarios[0]
+            .steps
+            .iter()
+            .map(|s| s.idempotency_key.as_deref())
+            .collect();
+        assert_eq!(
+            keys,
+            vec![Some("Idempotency-Key"), Some("X-Request-Key"), None]
+        );
+
+        let disabled = yaml.replace("idempotencyKey: true", "idempotencyKey: false");
+        let scenarios = YamlConfig::from_str(&disabled)
+            .unwrap()
+            .to_scenarios()
+            .unwrap();
+        assert_eq!(scenarios[0].steps[0].idempotency_key, None);
+
+        let invalid = yaml.replace("X-Request-Key", "Request Key");
+        assert!(YamlConfig::from_str(&invalid)
+            .unwrap()
+            .to_scenarios()
+            .is_err());
+    }
+
+    #[test]
+    fn test_step_tcp_check() {
+        let yaml = r#"
+version: "1.0"
+config:
+  baseUrl: "https://test.com"
+  duration: "1m"
+load:
+  model: "concurrent"
+scenarios:
+  - name: "Probes"
+    steps:
+      - name: "Database"
+        ping: "db.internal:5432"
+      - name: "Edge"
+        tcpCheck:
+          address: "[2001:db8::1]:443"
+          tls: true
+          serverName: "www.test.com"
+          timeout: "2s"
+        assertions:
+          - type: "responseTime"
+            max: "1s"
+      - name: "Base"
+        tcpCheck: {}
+"#;
+
+        let scenarios = YamlConfig::from_str(yaml).unwrap().to_scenarios().unwrap();
+        let checks: Vec<_> = scenarios[0]
+            .steps
+            .iter()
+            .map(|s| s.tcp_check.clone().unwrap())
+            .collect();
+        assert_eq!(
+            checks[0],
+            TcpCheck {
+                target: Some(("db.internal".to_string(), 5432)),
+                tls: false,
+                server_name: None,
+                timeout: TcpCheck::DEFAULT_TIMEOUT,
+            }
+        );
+        assert_eq!(
+            checks[1],
+            TcpCheck {
+                target: Some(("2001:db8::1".to_string(), 443)),
+                tls: true,
+                server_name: Some("www.test.com".to_string()),
+                timeout: StdDuration::from_secs(2),
+            }
+        );
+        assert_eq!(checks[2].target, None);
+
+        for invalid in [
+            yaml.replace("db.internal:5432", "db.internal"),
+            yaml.replace("[2001:db8::1]:443", "2001:db8::1:443"),
+            yaml.replace("timeout: \"2s\"", "timeout: \"0s\""),
+            yaml.replace(
+                "type: \"responseTime\"\n            max: \"1s\"",
+                "type: \"statusCode\"\n            expected: 200",
+            ),
+            yaml.replace(
+                "tcpCheck: {}",
+                "tcpCheck: {}\n        request:\n          method: \"GET\"\n          path: \"/\"",
+            ),
+        ] {
+            assert!(YamlConfig::from_str(&invalid).is_err(), "{}", invalid);
+        }
+    }
+
+    #[test]
+    fn test_step_rendezvous() {
+        let yaml = r#"
+version: "1.0"
+config:
+  baseUrl: "https://test.com"
+  duration: "1m"
+load:
+  model: "concurrent"
+scenarios:
+  - name: "Flash Sale"
+    steps:
+      - name: "Buy"
+        rendezvous:
+          name: sale
+          count: 50
+          timeout: "10s"
+        request:
+          method: "POST"
+          path: "/buy"
+      - request:
+          method: "GET"
+          path: "/orders"
+"#;
+
+        let scenarios = YamlConfig::from_str(yaml).unwrap().to_scenarios().unwrap();
+        assert_eq!(
+            scenarios[0].steps[0].rendezvous,
+            Some(Rendezvous {
+                name: "sale".to_string(),
+                count: 50,
+                timeout: StdDuration::from_secs(10),
+            })
+        );
+        assert_eq!(scenarios[0].steps[1].rendezvous, None);
+
+        let default_timeout = yaml.replace("          timeout: \"10s\"\n", "");
+        let scenarios = YamlConfig::from_str(&default_timeout)
+            .unwrap()
+            .to_scenarios()
+            .unwrap();
+        assert_eq!(
+            scenarios[0].steps[0].rendezvous.as_ref().unwrap().timeout,
+            Rendezvous::DEFAULT_TIMEOUT
+        );
+
+        let zero = yaml.replace("count: 50", "count: 0");
+        assert!(YamlConfig::from_str(&zero).unwrap().to_scenarios().is_err());
+    }
+
+    #[test]
+    fn test_step_slo() {
+        let yaml = r#"
+version: "1.0"
+config:
+  baseUrl: "https://test.com"
+  duration: "1m"
+load:
+  model: "concurrent"
+scenarios:
+  - name: "Checkout"
+    steps:
+      - name: "Cart"
+        request:
+          method: "GET"
+          path: "/cart"
+        slo:
+          satisfiedMs: 200
+      - name: "Pay"
+        request:
+          method: "POST"
+          path: "/pay"
+        slo:
+          satisfiedMs: 500
+          toleratingMs: 1000
+"#;
+
+        let scenarios = YamlConfig::from_str(yaml).unwrap().to_scenarios().unwrap();
+        assert_eq!(scenarios[0].steps[0].slo, Some(StepSlo::new(200)));
+        assert_eq!(
+            scenarios[0].steps[1].slo,
+            Some(StepSlo {
+                satisfied_ms: 500,
+                tolerating_ms: 1000
+            })
+        );
+
+        let inverted = yaml.replace("toleratingMs: 1000", "toleratingMs: 100");
+        let config = YamlConfig::from_str(&inverted).unwrap();
+        assert!(config.to_scenarios().is_err());
+    }
+
+    #[test]
+    fn test_host_and_tls_server_name() {
+        let yaml = r#"
+version: "1.0"
+config:
+  baseUrl: "https://203.0.113.5"
+  tlsServerName: "www.example.com"
+  duration: "1m"
+load:
+  model: "concurrent"
+scenarios:
+  - name: "Origin"
+    steps:
+      - request:
+          method: "GET"
+          path: "/"
+          host: "${tenant}.example.com"
+"#;
+
+        let config = YamlConfig::from_str(yaml).unwrap();
+        assert_eq!(
+            config.config.tls_server_name.as_deref(),
+            Some("www.example.com")
+        );
+        let scenarios = config.to_scenarios().unwrap();
+        assert_eq!(
+            scenarios[0].steps[0].request.host.as_deref(),
+            Some("${tenant}.example.com")
+        );
+    }
 }
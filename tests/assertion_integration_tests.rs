@@ -5,11 +5,12 @@
 //! scenarios.  E-commerce specific tests require ecom.edge.baugus-lab.com
 //! and are marked #[ignore].
 
+use rust_loadtest::assertions::BodyDigest;
 use rust_loadtest::executor::{ScenarioExecutor, SessionStore};
 use rust_loadtest::scenario::{Assertion, RequestConfig, Scenario, ScenarioContext, Step};
 use std::collections::HashMap;
 use std::time::Duration;
-use wiremock::matchers::{method, path};
+use wiremock::matchers::{header, method, path};
 use wiremock::{Mock, MockServer, ResponseTemplate};
 
 // E-commerce test API - may not be accessible in all environments
@@ -43,12 +44,38 @@ async fn test_status_code_assertion_pass() {
                 body: None,
                 body_size: None,
                 headers: HashMap::new(),
+                protobuf: None,
+                host: None,
             },
             extractions: vec![],
             assertions: vec![Assertion::StatusCode(200)],
             cache: None,
             think_time: None,
+            tags: HashMap::new(),
+            paginate: None,
+            max_concurrent: None,
+            slo: None,
+            shared: vec![],
+            rendezvous: None,
+            conditional: false,
+            expected_status: Vec::new(),
+            patterns: Default::default(),
+            templates: Default::default(),
+            idempotency_key: None,
+            tcp_check: None,
         }],
+        network_profile: None,
+        tags: HashMap::new(),
+        max_concurrent: None,
+        depends_on: Vec::new(),
+        iterations: None,
+        retry: None,
+        assertion_sample_rate: 1.0,
+        client: None,
+        headers: Default::default(),
+        optional: false,
+        data_source: None,
+        rps: None,
     };
 
     let client = create_test_client();
@@ -94,12 +121,38 @@ async fn test_status_code_assertion_fail() {
                 body: None,
                 body_size: None,
                 headers: HashMap::new(),
+                protobuf: None,
+                host: None,
             },
             extractions: vec![],
             assertions: vec![Assertion::StatusCode(404)],
             cache: None,
             think_time: None,
+            tags: HashMap::new(),
+            paginate: None,
+            max_concurrent: None,
+            slo: None,
+            shared: vec![],
+            rendezvous: None,
+            conditional: false,
+            expected_status: Vec::new(),
+            patterns: Default::default(),
+            templates: Default::default(),
+            idempotency_key: None,
+            tcp_check: None,
         }],
+        network_profile: None,
+        tags: HashMap::new(),
+        max_concurrent: None,
+        depends_on: Vec::new(),
+        iterations: None,
+        retry: None,
+        assertion_sample_rate: 1.0,
+        client: None,
+        headers: Default::default(),
+        optional: false,
+        data_source: None,
+        rps: None,
     };
 
     let client = create_test_client();
@@ -145,12 +198,38 @@ async fn test_response_time_assertion_pass() {
                 body: None,
                 body_size: None,
                 headers: HashMap::new(),
+                protobuf: None,
+                host: None,
             },
             extractions: vec![],
             assertions: vec![Assertion::ResponseTime(Duration::from_secs(5))],
             cache: None,
             think_time: None,
+            tags: HashMap::new(),
+            paginate: None,
+            max_concurrent: None,
+            slo: None,
+            shared: vec![],
+            rendezvous: None,
+            conditional: false,
+            expected_status: Vec::new(),
+            patterns: Default::default(),
+            templates: Default::default(),
+            idempotency_key: None,
+            tcp_check: None,
         }],
+        network_profile: None,
+        tags: HashMap::new(),
+        max_concurrent: None,
+        depends_on: Vec::new(),
+        iterations: None,
+        retry: None,
+        assertion_sample_rate: 1.0,
+        client: None,
+        headers: Default::default(),
+        optional: false,
+        data_source: None,
+        rps: None,
     };
 
     let client = create_test_client();
@@ -197,12 +276,38 @@ async fn test_response_time_assertion_fail() {
                 body: None,
                 body_size: None,
                 headers: HashMap::new(),
+                protobuf: None,
+                host: None,
             },
             extractions: vec![],
             assertions: vec![Assertion::ResponseTime(Duration::from_millis(1))],
             cache: None,
             think_time: None,
+            tags: HashMap::new(),
+            paginate: None,
+            max_concurrent: None,
+            slo: None,
+            shared: vec![],
+            rendezvous: None,
+            conditional: false,
+            expected_status: Vec::new(),
+            patterns: Default::default(),
+            templates: Default::default(),
+            idempotency_key: None,
+            tcp_check: None,
         }],
+        network_profile: None,
+        tags: HashMap::new(),
+        max_concurrent: None,
+        depends_on: Vec::new(),
+        iterations: None,
+        retry: None,
+        assertion_sample_rate: 1.0,
+        client: None,
+        headers: Default::default(),
+        optional: false,
+        data_source: None,
+        rps: None,
     };
 
     let client = create_test_client();
@@ -251,6 +356,8 @@ async fn test_json_path_assertion_existence() {
                 body: None,
                 body_size: None,
                 headers: HashMap::new(),
+                protobuf: None,
+                host: None,
             },
             extractions: vec![],
             assertions: vec![Assertion::JsonPath {
@@ -259,7 +366,31 @@ async fn test_json_path_assertion_existence() {
             }],
             cache: None,
             think_time: None,
+            tags: HashMap::new(),
+            paginate: None,
+            max_concurrent: None,
+            slo: None,
+            shared: vec![],
+            rendezvous: None,
+            conditional: false,
+            expected_status: Vec::new(),
+            patterns: Default::default(),
+            templates: Default::default(),
+            idempotency_key: None,
+            tcp_check: None,
         }],
+        network_profile: None,
+        tags: HashMap::new(),
+        max_concurrent: None,
+        depends_on: Vec::new(),
+        iterations: None,
+        retry: None,
+        assertion_sample_rate: 1.0,
+        client: None,
+        headers: Default::default(),
+        optional: false,
+        data_source: None,
+        rps: None,
     };
 
     let client = create_test_client();
@@ -305,6 +436,8 @@ async fn test_json_path_assertion_value_match() {
                 body: None,
                 body_size: None,
                 headers: HashMap::new(),
+                protobuf: None,
+                host: None,
             },
             extractions: vec![],
             assertions: vec![Assertion::JsonPath {
@@ -313,7 +446,31 @@ async fn test_json_path_assertion_value_match() {
             }],
             cache: None,
             think_time: None,
+            tags: HashMap::new(),
+            paginate: None,
+            max_concurrent: None,
+            slo: None,
+            shared: vec![],
+            rendezvous: None,
+            conditional: false,
+            expected_status: Vec::new(),
+            patterns: Default::default(),
+            templates: Default::default(),
+            idempotency_key: None,
+            tcp_check: None,
         }],
+        network_profile: None,
+        tags: HashMap::new(),
+        max_concurrent: None,
+        depends_on: Vec::new(),
+        iterations: None,
+        retry: None,
+        assertion_sample_rate: 1.0,
+        client: None,
+        headers: Default::default(),
+        optional: false,
+        data_source: None,
+        rps: None,
     };
 
     let client = create_test_client();
@@ -359,6 +516,8 @@ async fn test_json_path_assertion_value_mismatch() {
                 body: None,
                 body_size: None,
                 headers: HashMap::new(),
+                protobuf: None,
+                host: None,
             },
             extractions: vec![],
             assertions: vec![Assertion::JsonPath {
@@ -367,7 +526,31 @@ async fn test_json_path_assertion_value_mismatch() {
             }],
             cache: None,
             think_time: None,
+            tags: HashMap::new(),
+            paginate: None,
+            max_concurrent: None,
+            slo: None,
+            shared: vec![],
+            rendezvous: None,
+            conditional: false,
+            expected_status: Vec::new(),
+            patterns: Default::default(),
+            templates: Default::default(),
+            idempotency_key: None,
+            tcp_check: None,
         }],
+        network_profile: None,
+        tags: HashMap::new(),
+        max_concurrent: None,
+        depends_on: Vec::new(),
+        iterations: None,
+        retry: None,
+        assertion_sample_rate: 1.0,
+        client: None,
+        headers: Default::default(),
+        optional: false,
+        data_source: None,
+        rps: None,
     };
 
     let client = create_test_client();
@@ -416,12 +599,38 @@ async fn test_body_contains_assertion_pass() {
                 body: None,
                 body_size: None,
                 headers: HashMap::new(),
+                protobuf: None,
+                host: None,
             },
             extractions: vec![],
             assertions: vec![Assertion::BodyContains("slideshow".to_string())],
             cache: None,
             think_time: None,
+            tags: HashMap::new(),
+            paginate: None,
+            max_concurrent: None,
+            slo: None,
+            shared: vec![],
+            rendezvous: None,
+            conditional: false,
+            expected_status: Vec::new(),
+            patterns: Default::default(),
+            templates: Default::default(),
+            idempotency_key: None,
+            tcp_check: None,
         }],
+        network_profile: None,
+        tags: HashMap::new(),
+        max_concurrent: None,
+        depends_on: Vec::new(),
+        iterations: None,
+        retry: None,
+        assertion_sample_rate: 1.0,
+        client: None,
+        headers: Default::default(),
+        optional: false,
+        data_source: None,
+        rps: None,
     };
 
     let client = create_test_client();
@@ -467,12 +676,38 @@ async fn test_body_contains_assertion_fail() {
                 body: None,
                 body_size: None,
                 headers: HashMap::new(),
+                protobuf: None,
+                host: None,
             },
             extractions: vec![],
             assertions: vec![Assertion::BodyContains("MISSING_TEXT_XYZ".to_string())],
             cache: None,
             think_time: None,
+            tags: HashMap::new(),
+            paginate: None,
+            max_concurrent: None,
+            slo: None,
+            shared: vec![],
+            rendezvous: None,
+            conditional: false,
+            expected_status: Vec::new(),
+            patterns: Default::default(),
+            templates: Default::default(),
+            idempotency_key: None,
+            tcp_check: None,
         }],
+        network_profile: None,
+        tags: HashMap::new(),
+        max_concurrent: None,
+        depends_on: Vec::new(),
+        iterations: None,
+        retry: None,
+        assertion_sample_rate: 1.0,
+        client: None,
+        headers: Default::default(),
+        optional: false,
+        data_source: None,
+        rps: None,
     };
 
     let client = create_test_client();
@@ -518,6 +753,8 @@ async fn test_body_matches_regex_assertion() {
                 body: None,
                 body_size: None,
                 headers: HashMap::new(),
+                protobuf: None,
+                host: None,
             },
             extractions: vec![],
             assertions: vec![Assertion::BodyMatches(
@@ -525,7 +762,31 @@ async fn test_body_matches_regex_assertion() {
             )],
             cache: None,
             think_time: None,
+            tags: HashMap::new(),
+            paginate: None,
+            max_concurrent: None,
+            slo: None,
+            shared: vec![],
+            rendezvous: None,
+            conditional: false,
+            expected_status: Vec::new(),
+            patterns: Default::default(),
+            templates: Default::default(),
+            idempotency_key: None,
+            tcp_check: None,
         }],
+        network_profile: None,
+        tags: HashMap::new(),
+        max_concurrent: None,
+        depends_on: Vec::new(),
+        iterations: None,
+        retry: None,
+        assertion_sample_rate: 1.0,
+        client: None,
+        headers: Default::default(),
+        optional: false,
+        data_source: None,
+        rps: None,
     };
 
     let client = create_test_client();
@@ -568,12 +829,38 @@ async fn test_header_exists_assertion_pass() {
                 body: None,
                 body_size: None,
                 headers: HashMap::new(),
+                protobuf: None,
+                host: None,
             },
             extractions: vec![],
             assertions: vec![Assertion::HeaderExists("content-type".to_string())],
             cache: None,
             think_time: None,
+            tags: HashMap::new(),
+            paginate: None,
+            max_concurrent: None,
+            slo: None,
+            shared: vec![],
+            rendezvous: None,
+            conditional: false,
+            expected_status: Vec::new(),
+            patterns: Default::default(),
+            templates: Default::default(),
+            idempotency_key: None,
+            tcp_check: None,
         }],
+        network_profile: None,
+        tags: HashMap::new(),
+        max_concurrent: None,
+        depends_on: Vec::new(),
+        iterations: None,
+        retry: None,
+        assertion_sample_rate: 1.0,
+        client: None,
+        headers: Default::default(),
+        optional: false,
+        data_source: None,
+        rps: None,
     };
 
     let client = create_test_client();
@@ -596,6 +883,94 @@ async fn test_header_exists_assertion_pass() {
     println!("✅ Header exists assertion passed");
 }
 
+#[tokio::test]
+async fn test_header_value_assertions() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/cors"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .insert_header("Access-Control-Allow-Origin", "https://app.example.com")
+                .insert_header("Cache-Control", "public, max-age=600"),
+        )
+        .mount(&server)
+        .await;
+
+    let scenario = Scenario {
+        name: "Header Values".to_string(),
+        weight: 1.0,
+        steps: vec![Step {
+            name: "Check CORS and Caching".to_string(),
+            request: RequestConfig {
+                method: "GET".to_string(),
+                path: "/cors".to_string(),
+                body: None,
+                body_size: None,
+                headers: HashMap::new(),
+                protobuf: None,
+                host: None,
+            },
+            extractions: vec![],
+            assertions: vec![
+                Assertion::HeaderEquals {
+                    header: "access-control-allow-origin".to_string(),
+                    expected: "https://app.example.com".to_string(),
+                },
+                Assertion::HeaderMatches {
+                    header: "cache-control".to_string(),
+                    pattern: r"max-age=\d+".to_string(),
+                },
+                Assertion::HeaderMatches {
+                    header: "Cache-Control".to_string(),
+                    pattern: "no-store".to_string(),
+                },
+            ],
+            cache: None,
+            think_time: None,
+            tags: HashMap::new(),
+            paginate: None,
+            max_concurrent: None,
+            slo: None,
+            shared: vec![],
+            rendezvous: None,
+            conditional: false,
+            expected_status: Vec::new(),
+            patterns: Default::default(),
+            templates: Default::default(),
+            idempotency_key: None,
+            tcp_check: None,
+        }],
+        network_profile: None,
+        tags: HashMap::new(),
+        max_concurrent: None,
+        depends_on: Vec::new(),
+        iterations: None,
+        retry: None,
+        assertion_sample_rate: 1.0,
+        client: None,
+        headers: Default::default(),
+        optional: false,
+        data_source: None,
+        rps: None,
+    };
+
+    let executor = ScenarioExecutor::new(
+        server.uri(),
+        create_test_client(),
+        "test-node".to_string(),
+        "run-0".to_string(),
+    );
+    let mut context = ScenarioContext::new();
+
+    let result = executor
+        .execute(&scenario, &mut context, &mut SessionStore::new())
+        .await;
+
+    assert!(!result.success, "no-store assertion should fail");
+    assert_eq!(result.steps[0].assertions_passed, 2);
+    assert_eq!(result.steps[0].assertions_failed, 1);
+}
+
 #[tokio::test]
 async fn test_header_exists_assertion_fail() {
     let server = MockServer::start().await;
@@ -617,12 +992,38 @@ async fn test_header_exists_assertion_fail() {
                 body: None,
                 body_size: None,
                 headers: HashMap::new(),
+                protobuf: None,
+                host: None,
             },
             extractions: vec![],
             assertions: vec![Assertion::HeaderExists("x-missing-header".to_string())],
             cache: None,
             think_time: None,
+            tags: HashMap::new(),
+            paginate: None,
+            max_concurrent: None,
+            slo: None,
+            shared: vec![],
+            rendezvous: None,
+            conditional: false,
+            expected_status: Vec::new(),
+            patterns: Default::default(),
+            templates: Default::default(),
+            idempotency_key: None,
+            tcp_check: None,
         }],
+        network_profile: None,
+        tags: HashMap::new(),
+        max_concurrent: None,
+        depends_on: Vec::new(),
+        iterations: None,
+        retry: None,
+        assertion_sample_rate: 1.0,
+        client: None,
+        headers: Default::default(),
+        optional: false,
+        data_source: None,
+        rps: None,
     };
 
     let client = create_test_client();
@@ -670,6 +1071,8 @@ async fn test_multiple_assertions_all_pass() {
                 body: None,
                 body_size: None,
                 headers: HashMap::new(),
+                protobuf: None,
+                host: None,
             },
             extractions: vec![],
             assertions: vec![
@@ -684,7 +1087,31 @@ async fn test_multiple_assertions_all_pass() {
             ],
             cache: None,
             think_time: None,
+            tags: HashMap::new(),
+            paginate: None,
+            max_concurrent: None,
+            slo: None,
+            shared: vec![],
+            rendezvous: None,
+            conditional: false,
+            expected_status: Vec::new(),
+            patterns: Default::default(),
+            templates: Default::default(),
+            idempotency_key: None,
+            tcp_check: None,
         }],
+        network_profile: None,
+        tags: HashMap::new(),
+        max_concurrent: None,
+        depends_on: Vec::new(),
+        iterations: None,
+        retry: None,
+        assertion_sample_rate: 1.0,
+        client: None,
+        headers: Default::default(),
+        optional: false,
+        data_source: None,
+        rps: None,
     };
 
     let client = create_test_client();
@@ -731,6 +1158,8 @@ async fn test_multiple_assertions_mixed_results() {
                 body: None,
                 body_size: None,
                 headers: HashMap::new(),
+                protobuf: None,
+                host: None,
             },
             extractions: vec![],
             assertions: vec![
@@ -741,7 +1170,31 @@ async fn test_multiple_assertions_mixed_results() {
             ],
             cache: None,
             think_time: None,
+            tags: HashMap::new(),
+            paginate: None,
+            max_concurrent: None,
+            slo: None,
+            shared: vec![],
+            rendezvous: None,
+            conditional: false,
+            expected_status: Vec::new(),
+            patterns: Default::default(),
+            templates: Default::default(),
+            idempotency_key: None,
+            tcp_check: None,
         }],
+        network_profile: None,
+        tags: HashMap::new(),
+        max_concurrent: None,
+        depends_on: Vec::new(),
+        iterations: None,
+        retry: None,
+        assertion_sample_rate: 1.0,
+        client: None,
+        headers: Default::default(),
+        optional: false,
+        data_source: None,
+        rps: None,
     };
 
     let client = create_test_client();
@@ -796,11 +1249,25 @@ async fn test_multi_step_assertion_stops_on_failure() {
                     body: None,
                     body_size: None,
                     headers: HashMap::new(),
+                    protobuf: None,
+                    host: None,
                 },
                 extractions: vec![],
                 assertions: vec![Assertion::StatusCode(200)],
                 cache: None,
                 think_time: None,
+                tags: HashMap::new(),
+                paginate: None,
+                max_concurrent: None,
+                slo: None,
+                shared: vec![],
+                rendezvous: None,
+                conditional: false,
+                expected_status: Vec::new(),
+                patterns: Default::default(),
+                templates: Default::default(),
+                idempotency_key: None,
+                tcp_check: None,
             },
             Step {
                 name: "Step 2 - Fail".to_string(),
@@ -810,11 +1277,25 @@ async fn test_multi_step_assertion_stops_on_failure() {
                     body: None,
                     body_size: None,
                     headers: HashMap::new(),
+                    protobuf: None,
+                    host: None,
                 },
                 extractions: vec![],
                 assertions: vec![Assertion::StatusCode(404)], // Will fail
                 cache: None,
                 think_time: None,
+                tags: HashMap::new(),
+                paginate: None,
+                max_concurrent: None,
+                slo: None,
+                shared: vec![],
+                rendezvous: None,
+                conditional: false,
+                expected_status: Vec::new(),
+                patterns: Default::default(),
+                templates: Default::default(),
+                idempotency_key: None,
+                tcp_check: None,
             },
             Step {
                 name: "Step 3 - Never Reached".to_string(),
@@ -824,13 +1305,39 @@ async fn test_multi_step_assertion_stops_on_failure() {
                     body: None,
                     body_size: None,
                     headers: HashMap::new(),
+                    protobuf: None,
+                    host: None,
                 },
                 extractions: vec![],
                 assertions: vec![],
                 cache: None,
                 think_time: None,
+                tags: HashMap::new(),
+                paginate: None,
+                max_concurrent: None,
+                slo: None,
+                shared: vec![],
+                rendezvous: None,
+                conditional: false,
+                expected_status: Vec::new(),
+                patterns: Default::default(),
+                templates: Default::default(),
+                idempotency_key: None,
+                tcp_check: None,
             },
         ],
+        network_profile: None,
+        tags: HashMap::new(),
+        max_concurrent: None,
+        depends_on: Vec::new(),
+        iterations: None,
+        retry: None,
+        assertion_sample_rate: 1.0,
+        client: None,
+        headers: Default::default(),
+        optional: false,
+        data_source: None,
+        rps: None,
     };
 
     let client = create_test_client();
@@ -880,6 +1387,8 @@ async fn test_realistic_e_commerce_flow_with_assertions() {
                     body: None,
                     body_size: None,
                     headers: HashMap::new(),
+                    protobuf: None,
+                    host: None,
                 },
                 extractions: vec![],
                 assertions: vec![
@@ -888,6 +1397,18 @@ async fn test_realistic_e_commerce_flow_with_assertions() {
                 ],
                 cache: None,
                 think_time: None,
+                tags: HashMap::new(),
+                paginate: None,
+                max_concurrent: None,
+                slo: None,
+                shared: vec![],
+                rendezvous: None,
+                conditional: false,
+                expected_status: Vec::new(),
+                patterns: Default::default(),
+                templates: Default::default(),
+                idempotency_key: None,
+                tcp_check: None,
             },
             Step {
                 name: "Get Products".to_string(),
@@ -897,6 +1418,8 @@ async fn test_realistic_e_commerce_flow_with_assertions() {
                     body: None,
                     body_size: None,
                     headers: HashMap::new(),
+                    protobuf: None,
+                    host: None,
                 },
                 extractions: vec![],
                 assertions: vec![
@@ -908,6 +1431,18 @@ async fn test_realistic_e_commerce_flow_with_assertions() {
                 ],
                 cache: None,
                 think_time: None,
+                tags: HashMap::new(),
+                paginate: None,
+                max_concurrent: None,
+                slo: None,
+                shared: vec![],
+                rendezvous: None,
+                conditional: false,
+                expected_status: Vec::new(),
+                patterns: Default::default(),
+                templates: Default::default(),
+                idempotency_key: None,
+                tcp_check: None,
             },
             Step {
                 name: "Check Status".to_string(),
@@ -917,6 +1452,8 @@ async fn test_realistic_e_commerce_flow_with_assertions() {
                     body: None,
                     body_size: None,
                     headers: HashMap::new(),
+                    protobuf: None,
+                    host: None,
                 },
                 extractions: vec![],
                 assertions: vec![
@@ -929,8 +1466,32 @@ async fn test_realistic_e_commerce_flow_with_assertions() {
                 ],
                 cache: None,
                 think_time: None,
+                tags: HashMap::new(),
+                paginate: None,
+                max_concurrent: None,
+                slo: None,
+                shared: vec![],
+                rendezvous: None,
+                conditional: false,
+                expected_status: Vec::new(),
+                patterns: Default::default(),
+                templates: Default::default(),
+                idempotency_key: None,
+                tcp_check: None,
             },
         ],
+        network_profile: None,
+        tags: HashMap::new(),
+        max_concurrent: None,
+        depends_on: Vec::new(),
+        iterations: None,
+        retry: None,
+        assertion_sample_rate: 1.0,
+        client: None,
+        headers: Default::default(),
+        optional: false,
+        data_source: None,
+        rps: None,
     };
 
     let client = create_test_client();
@@ -961,3 +1522,283 @@ async fn test_realistic_e_commerce_flow_with_assertions() {
         total_assertions_passed
     );
 }
+
+/// Checksum and size assertions verify a binary download against its raw
+/// bytes, which are not valid UTF-8.
+#[tokio::test]
+async fn test_body_checksum_assertions_on_binary_download() {
+    let server = MockServer::start().await;
+    let payload: Vec<u8> = (0..64 * 1024).map(|i| (i % 251) as u8 | 0x80).collect();
+    Mock::given(method("GET"))
+        .and(path("/firmware.bin"))
+        .respond_with(ResponseTemplate::new(200).set_body_bytes(payload.clone()))
+        .mount(&server)
+        .await;
+
+    let digest = BodyDigest::of(&payload);
+    let scenario = |assertions: Vec<Assertion>| Scenario {
+        name: "Download".to_string(),
+        weight: 1.0,
+        steps: vec![Step {
+            name: "Firmware".to_string(),
+            request: RequestConfig {
+                method: "GET".to_string(),
+                path: "/firmware.bin".to_string(),
+                body: None,
+                body_size: None,
+                headers: HashMap::new(),
+                protobuf: None,
+                host: None,
+            },
+            extractions: vec![],
+            assertions,
+            cache: None,
+            think_time: None,
+            tags: HashMap::new(),
+            paginate: None,
+            max_concurrent: None,
+            slo: None,
+            shared: vec![],
+            rendezvous: None,
+            conditional: false,
+            expected_status: Vec::new(),
+            patterns: Default::default(),
+            templates: Default::default(),
+            idempotency_key: None,
+            tcp_check: None,
+        }],
+        network_profile: None,
+        tags: HashMap::new(),
+        max_concurrent: None,
+        depends_on: Vec::new(),
+        iterations: None,
+        retry: None,
+        assertion_sample_rate: 1.0,
+        client: None,
+        headers: Default::default(),
+        optional: false,
+        data_source: None,
+        rps: None,
+    };
+    let executor = ScenarioExecutor::new(
+        server.uri(),
+        create_test_client(),
+        "test-node".to_string(),
+        "run-0".to_string(),
+    );
+
+    let matching = scenario(vec![
+        Assertion::BodySha256 {
+            expected: digest.sha256.to_uppercase(),
+        },
+        Assertion::BodySizeBytes {
+            min: Some(64 * 1024),
+            max: Some(64 * 1024),
+        },
+    ]);
+    let result = executor
+        .execute(
+            &matching,
+            &mut ScenarioContext::new(),
+            &mut SessionStore::new(),
+        )
+        .await;
+    assert!(result.success, "{:?}", result.steps[0].assertion_failures);
+    assert_eq!(result.steps[0].assertions_passed, 2);
+
+    let mismatched = scenario(vec![Assertion::BodySha256 {
+        expected: BodyDigest::of(b"other").sha256,
+    }]);
+    let result = executor
+        .execute(
+            &mismatched,
+            &mut ScenarioContext::new(),
+            &mut SessionStore::new(),
+        )
+        .await;
+    assert!(!result.success);
+    assert!(result.steps[0].assertion_failures[0].contains("SHA-256 mismatch"));
+}
+
+/// A conditional step sends the ETag of its last response back as
+/// If-None-Match on the next iteration and expects 304.
+#[tokio::test]
+async fn test_conditional_request_revalidates_with_etag() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/catalog.json"))
+        .and(header("if-none-match", "\"v1\""))
+        .respond_with(ResponseTemplate::new(304).insert_header("etag", "\"v1\""))
+        .with_priority(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/catalog.json"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .insert_header("etag", "\"v1\"")
+                .set_body_string(r#"{"items": []}"#),
+        )
+        .mount(&server)
+        .await;
+
+    let scenario = Scenario {
+        name: "Catalog".to_string(),
+        weight: 1.0,
+        steps: vec![Step {
+            name: "Catalog".to_string(),
+            request: RequestConfig {
+                method: "GET".to_string(),
+                path: "/catalog.json".to_string(),
+                body: None,
+                body_size: None,
+                headers: HashMap::new(),
+                protobuf: None,
+                host: None,
+            },
+            extractions: vec![],
+            assertions: vec![
+                Assertion::NotModified,
+                Assertion::CacheHitRatio {
+                    min: 0.5,
+                    min_samples: 2,
+                },
+            ],
+            cache: None,
+            think_time: None,
+            tags: HashMap::new(),
+            paginate: None,
+            max_concurrent: None,
+            slo: None,
+            shared: vec![],
+            rendezvous: None,
+            conditional: true,
+            expected_status: Vec::new(),
+            patterns: Default::default(),
+            templates: Default::default(),
+            idempotency_key: None,
+            tcp_check: None,
+        }],
+        network_profile: None,
+        tags: HashMap::new(),
+        max_concurrent: None,
+        depends_on: Vec::new(),
+        iterations: None,
+        retry: None,
+        assertion_sample_rate: 1.0,
+        client: None,
+        headers: Default::default(),
+        optional: false,
+        data_source: None,
+        rps: None,
+    };
+    let executor = ScenarioExecutor::new(
+        server.uri(),
+        create_test_client(),
+        "test-node".to_string(),
+        "run-0".to_string(),
+    );
+    let mut session = SessionStore::new();
+
+    // First iteration: no validators yet, so notModified is skipped.
+    let first = executor
+        .execute(&scenario, &mut ScenarioContext::new(), &mut session)
+        .await;
+    assert!(first.success, "{:?}", first.steps[0].assertion_failures);
+    assert_eq!(first.steps[0].status_code, Some(200));
+    assert_eq!(first.steps[0].assertions_passed, 1);
+
+    // Second iteration revalidates and is answered 304.
+    let second = executor
+        .execute(&scenario, &mut ScenarioContext::new(), &mut session)
+        .await;
+    assert!(second.success, "{:?}", second.steps[0].assertion_failures);
+    assert_eq!(second.steps[0].status_code, Some(304));
+    assert_eq!(second.steps[0].assertions_passed, 2);
+}
+
+#[tokio::test]
+async fn test_assertions_sampled_out() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/status/200"))
+        .respond_with(ResponseTemplate::new(200))
+        .mount(&server)
+        .await;
+
+    let mut scenario = Scenario {
+        name: "Assertion Sampling".to_string(),
+        weight: 1.0,
+        steps: vec![Step {
+            name: "Expect 404".to_string(),
+            request: RequestConfig {
+                method: "GET".to_string(),
+                path: "/status/200".to_string(),
+                body: None,
+                body_size: None,
+                headers: HashMap::new(),
+                protobuf: None,
+                host: None,
+            },
+            extractions: vec![],
+            assertions: vec![Assertion::StatusCode(404)],
+            cache: None,
+            think_time: None,
+            tags: HashMap::new(),
+            paginate: None,
+            max_concurrent: None,
+            slo: None,
+            shared: vec![],
+            rendezvous: None,
+            conditional: false,
+            expected_status: Vec::new(),
+            patterns: Default::default(),
+            templates: Default::default(),
+            idempotency_key: None,
+            tcp_check: None,
+        }],
+        network_profile: None,
+        tags: HashMap::new(),
+        max_concurrent: None,
+        depends_on: Vec::new(),
+        iterations: None,
+        retry: None,
+        assertion_sample_rate: 0.0,
+        client: None,
+        headers: Default::default(),
+        optional: false,
+        data_source: None,
+        rps: None,
+    };
+
+    let executor = ScenarioExecutor::new(
+        server.uri(),
+        create_test_client(),
+        "test-node".to_string(),
+        "run-0".to_string(),
+    );
+
+    // Rate 0: the failing assertion is never checked.
+    let result = executor
+        .execute(
+            &scenario,
+            &mut ScenarioContext::new(),
+            &mut SessionStore::new(),
+        )
+        .await;
+    assert!(result.success);
+    assert_eq!(result.steps[0].assertions_passed, 0);
+    assert_eq!(result.steps[0].assertions_failed, 0);
+
+    // Rate 1: every iteration validates.
+    scenario.assertion_sample_rate = 1.0;
+    let result = executor
+        .execute(
+            &scenario,
+            &mut ScenarioContext::new(),
+            &mut SessionStore::new(),
+        )
+        .await;
+    assert!(!result.success);
+    assert_eq!(result.steps[0].assertions_failed, 1);
+}
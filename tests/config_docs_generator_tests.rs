@@ -6,6 +6,8 @@
 //! - VS Code snippets generation
 //! - Output file generation
 
+#![cfg(feature = "config-docs")]
+
 use rust_loadtest::config_docs_generator::ConfigDocsGenerator;
 use std::fs;
 use tempfile::TempDir;
@@ -7,6 +7,8 @@
 //! - Debouncing of rapid changes
 //! - Development mode enable/disable
 
+#![cfg(feature = "hot-reload")]
+
 use rust_loadtest::config_hot_reload::{
     ConfigWatcher, ConfigWatcherError, HotReloadConfig, ReloadNotifier,
 };
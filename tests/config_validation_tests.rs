@@ -35,6 +35,8 @@ fn test_duration_validator_valid_formats() {
     assert!(DurationValidator::validate("5m").is_ok());
     assert!(DurationValidator::validate("2h").is_ok());
     assert!(DurationValidator::validate("1d").is_ok());
+    assert!(DurationValidator::validate("30").is_ok()); // bare seconds
+    assert!(DurationValidator::validate("1h30m").is_ok());
 
     println!("✅ Valid duration formats pass validation");
 }
@@ -42,7 +44,8 @@ fn test_duration_validator_valid_formats() {
 #[test]
 fn test_duration_validator_invalid_formats() {
     assert!(DurationValidator::validate("invalid").is_err());
-    assert!(DurationValidator::validate("30").is_err()); // missing unit
+    assert!(DurationValidator::validate("30x").is_err()); // unknown unit
+    assert!(DurationValidator::validate("30s1h").is_err()); // units out of order
     assert!(DurationValidator::validate("abc").is_err());
 
     println!("✅ Invalid duration formats are rejected");
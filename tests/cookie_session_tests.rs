@@ -6,6 +6,7 @@
 use rust_loadtest::executor::{ScenarioExecutor, SessionStore};
 use rust_loadtest::scenario::{
     Extractor, RequestConfig, Scenario, ScenarioContext, Step, ThinkTime, VariableExtraction,
+    VariableScope,
 };
 use std::collections::HashMap;
 use std::time::Duration;
@@ -48,11 +49,25 @@ async fn test_cookies_persist_across_steps() {
                         headers.insert("Content-Type".to_string(), "application/json".to_string());
                         headers
                     },
+                    protobuf: None,
+                    host: None,
                 },
                 extractions: vec![],
                 assertions: vec![],
                 cache: None,
                 think_time: Some(ThinkTime::Fixed(Duration::from_millis(100))),
+                tags: HashMap::new(),
+                paginate: None,
+                max_concurrent: None,
+                slo: None,
+                shared: vec![],
+                rendezvous: None,
+                conditional: false,
+                expected_status: Vec::new(),
+                patterns: Default::default(),
+                templates: Default::default(),
+                idempotency_key: None,
+                tcp_check: None,
             },
             Step {
                 name: "Access Protected Resource (uses cookies)".to_string(),
@@ -62,13 +77,39 @@ async fn test_cookies_persist_across_steps() {
                     body: None,
                     body_size: None,
                     headers: HashMap::new(), // No manual auth header needed - cookies handle it
+                    protobuf: None,
+                    host: None,
                 },
                 extractions: vec![],
                 assertions: vec![],
                 cache: None,
                 think_time: None,
+                tags: HashMap::new(),
+                paginate: None,
+                max_concurrent: None,
+                slo: None,
+                shared: vec![],
+                rendezvous: None,
+                conditional: false,
+                expected_status: Vec::new(),
+                patterns: Default::default(),
+                templates: Default::default(),
+                idempotency_key: None,
+                tcp_check: None,
             },
         ],
+        network_profile: None,
+        tags: HashMap::new(),
+        max_concurrent: None,
+        depends_on: Vec::new(),
+        iterations: None,
+        retry: None,
+        assertion_sample_rate: 1.0,
+        client: None,
+        headers: Default::default(),
+        optional: false,
+        data_source: None,
+        rps: None,
     };
 
     let client = create_cookie_client();
@@ -135,17 +176,33 @@ async fn test_auth_flow_with_token_and_cookies() {
                         headers.insert("Content-Type".to_string(), "application/json".to_string());
                         headers
                     },
+                    protobuf: None,
+                    host: None,
                 },
                 extractions: vec![
                     // Extract token from response
                     VariableExtraction {
                         name: "auth_token".to_string(),
                         extractor: Extractor::JsonPath("$.token".to_string()),
+                        transforms: Vec::new(),
+                        scope: VariableScope::Iteration,
                     },
                 ],
                 assertions: vec![],
                 cache: None,
                 think_time: Some(ThinkTime::Fixed(Duration::from_millis(500))),
+                tags: HashMap::new(),
+                paginate: None,
+                max_concurrent: None,
+                slo: None,
+                shared: vec![],
+                rendezvous: None,
+                conditional: false,
+                expected_status: Vec::new(),
+                patterns: Default::default(),
+                templates: Default::default(),
+                idempotency_key: None,
+                tcp_check: None,
             },
             Step {
                 name: "Access Profile with Token".to_string(),
@@ -163,13 +220,39 @@ async fn test_auth_flow_with_token_and_cookies() {
                         );
                         headers
                     },
+                    protobuf: None,
+                    host: None,
                 },
                 extractions: vec![],
                 assertions: vec![],
                 cache: None,
                 think_time: None,
+                tags: HashMap::new(),
+                paginate: None,
+                max_concurrent: None,
+                slo: None,
+                shared: vec![],
+                rendezvous: None,
+                conditional: false,
+                expected_status: Vec::new(),
+                patterns: Default::default(),
+                templates: Default::default(),
+                idempotency_key: None,
+                tcp_check: None,
             },
         ],
+        network_profile: None,
+        tags: HashMap::new(),
+        max_concurrent: None,
+        depends_on: Vec::new(),
+        iterations: None,
+        retry: None,
+        assertion_sample_rate: 1.0,
+        client: None,
+        headers: Default::default(),
+        optional: false,
+        data_source: None,
+        rps: None,
     };
 
     let client = create_cookie_client();
@@ -240,12 +323,38 @@ async fn test_cookie_isolation_between_clients() {
                     headers.insert("Content-Type".to_string(), "application/json".to_string());
                     headers
                 },
+                protobuf: None,
+                host: None,
             },
             extractions: vec![],
             assertions: vec![],
             cache: None,
             think_time: None,
+            tags: HashMap::new(),
+            paginate: None,
+            max_concurrent: None,
+            slo: None,
+            shared: vec![],
+            rendezvous: None,
+            conditional: false,
+            expected_status: Vec::new(),
+            patterns: Default::default(),
+            templates: Default::default(),
+            idempotency_key: None,
+            tcp_check: None,
         }],
+        network_profile: None,
+        tags: HashMap::new(),
+        max_concurrent: None,
+        depends_on: Vec::new(),
+        iterations: None,
+        retry: None,
+        assertion_sample_rate: 1.0,
+        client: None,
+        headers: Default::default(),
+        optional: false,
+        data_source: None,
+        rps: None,
     };
 
     // Create two separate cookie-enabled clients
@@ -303,14 +412,30 @@ async fn test_shopping_flow_with_session() {
                     body: None,
                     body_size: None,
                     headers: HashMap::new(),
+                    protobuf: None,
+                    host: None,
                 },
                 extractions: vec![VariableExtraction {
                     name: "product_id".to_string(),
                     extractor: Extractor::JsonPath("$.products[0].id".to_string()),
+                    transforms: Vec::new(),
+                    scope: VariableScope::Iteration,
                 }],
                 assertions: vec![],
                 cache: None,
                 think_time: Some(ThinkTime::Fixed(Duration::from_millis(500))),
+                tags: HashMap::new(),
+                paginate: None,
+                max_concurrent: None,
+                slo: None,
+                shared: vec![],
+                rendezvous: None,
+                conditional: false,
+                expected_status: Vec::new(),
+                patterns: Default::default(),
+                templates: Default::default(),
+                idempotency_key: None,
+                tcp_check: None,
             },
             Step {
                 name: "Register and Login".to_string(),
@@ -331,14 +456,30 @@ async fn test_shopping_flow_with_session() {
                         headers.insert("Content-Type".to_string(), "application/json".to_string());
                         headers
                     },
+                    protobuf: None,
+                    host: None,
                 },
                 extractions: vec![VariableExtraction {
                     name: "token".to_string(),
                     extractor: Extractor::JsonPath("$.token".to_string()),
+                    transforms: Vec::new(),
+                    scope: VariableScope::Iteration,
                 }],
                 assertions: vec![],
                 cache: None,
                 think_time: Some(ThinkTime::Fixed(Duration::from_millis(500))),
+                tags: HashMap::new(),
+                paginate: None,
+                max_concurrent: None,
+                slo: None,
+                shared: vec![],
+                rendezvous: None,
+                conditional: false,
+                expected_status: Vec::new(),
+                patterns: Default::default(),
+                templates: Default::default(),
+                idempotency_key: None,
+                tcp_check: None,
             },
             Step {
                 name: "Add to Cart (with auth)".to_string(),
@@ -359,11 +500,25 @@ async fn test_shopping_flow_with_session() {
                         headers.insert("Authorization".to_string(), "Bearer ${token}".to_string());
                         headers
                     },
+                    protobuf: None,
+                    host: None,
                 },
                 extractions: vec![],
                 assertions: vec![],
                 cache: None,
                 think_time: Some(ThinkTime::Fixed(Duration::from_millis(500))),
+                tags: HashMap::new(),
+                paginate: None,
+                max_concurrent: None,
+                slo: None,
+                shared: vec![],
+                rendezvous: None,
+                conditional: false,
+                expected_status: Vec::new(),
+                patterns: Default::default(),
+                templates: Default::default(),
+                idempotency_key: None,
+                tcp_check: None,
             },
             Step {
                 name: "View Cart (session maintained)".to_string(),
@@ -377,13 +532,39 @@ async fn test_shopping_flow_with_session() {
                         headers.insert("Authorization".to_string(), "Bearer ${token}".to_string());
                         headers
                     },
+                    protobuf: None,
+                    host: None,
                 },
                 extractions: vec![],
                 assertions: vec![],
                 cache: None,
                 think_time: None,
+                tags: HashMap::new(),
+                paginate: None,
+                max_concurrent: None,
+                slo: None,
+                shared: vec![],
+                rendezvous: None,
+                conditional: false,
+                expected_status: Vec::new(),
+                patterns: Default::default(),
+                templates: Default::default(),
+                idempotency_key: None,
+                tcp_check: None,
             },
         ],
+        network_profile: None,
+        tags: HashMap::new(),
+        max_concurrent: None,
+        depends_on: Vec::new(),
+        iterations: None,
+        retry: None,
+        assertion_sample_rate: 1.0,
+        client: None,
+        headers: Default::default(),
+        optional: false,
+        data_source: None,
+        rps: None,
     };
 
     let client = create_cookie_client();
@@ -443,12 +624,38 @@ async fn test_client_without_cookies_fails_session() {
                     headers.insert("Content-Type".to_string(), "application/json".to_string());
                     headers
                 },
+                protobuf: None,
+                host: None,
             },
             extractions: vec![],
             assertions: vec![],
             cache: None,
             think_time: None,
+            tags: HashMap::new(),
+            paginate: None,
+            max_concurrent: None,
+            slo: None,
+            shared: vec![],
+            rendezvous: None,
+            conditional: false,
+            expected_status: Vec::new(),
+            patterns: Default::default(),
+            templates: Default::default(),
+            idempotency_key: None,
+            tcp_check: None,
         }],
+        network_profile: None,
+        tags: HashMap::new(),
+        max_concurrent: None,
+        depends_on: Vec::new(),
+        iterations: None,
+        retry: None,
+        assertion_sample_rate: 1.0,
+        client: None,
+        headers: Default::default(),
+        optional: false,
+        data_source: None,
+        rps: None,
     };
 
     // Client WITHOUT cookies
@@ -145,12 +145,38 @@ async fn test_scenario_with_csv_data() {
                     h.insert("Content-Type".to_string(), "application/json".to_string());
                     h
                 },
+                protobuf: None,
+                host: None,
             },
             extractions: vec![],
             assertions: vec![],
             cache: None,
             think_time: None,
+            tags: HashMap::new(),
+            paginate: None,
+            max_concurrent: None,
+            slo: None,
+            shared: vec![],
+            rendezvous: None,
+            conditional: false,
+            expected_status: Vec::new(),
+            patterns: Default::default(),
+            templates: Default::default(),
+            idempotency_key: None,
+            tcp_check: None,
         }],
+        network_profile: None,
+        tags: HashMap::new(),
+        max_concurrent: None,
+        depends_on: Vec::new(),
+        iterations: None,
+        retry: None,
+        assertion_sample_rate: 1.0,
+        client: None,
+        headers: Default::default(),
+        optional: false,
+        data_source: None,
+        rps: None,
     };
 
     // Execute scenario twice with different data rows
@@ -198,12 +224,38 @@ async fn test_multiple_users_different_data() {
                 body: None,
                 body_size: None,
                 headers: HashMap::new(),
+                protobuf: None,
+                host: None,
             },
             extractions: vec![],
             assertions: vec![Assertion::StatusCode(200)],
             cache: None,
             think_time: None,
+            tags: HashMap::new(),
+            paginate: None,
+            max_concurrent: None,
+            slo: None,
+            shared: vec![],
+            rendezvous: None,
+            conditional: false,
+            expected_status: Vec::new(),
+            patterns: Default::default(),
+            templates: Default::default(),
+            idempotency_key: None,
+            tcp_check: None,
         }],
+        network_profile: None,
+        tags: HashMap::new(),
+        max_concurrent: None,
+        depends_on: Vec::new(),
+        iterations: None,
+        retry: None,
+        assertion_sample_rate: 1.0,
+        client: None,
+        headers: Default::default(),
+        optional: false,
+        data_source: None,
+        rps: None,
     };
 
     // Simulate 3 virtual users, each getting different data
@@ -280,11 +332,25 @@ dave,dave012,dave@company.com,manager"#;
                     body: None,
                     body_size: None,
                     headers: HashMap::new(),
+                    protobuf: None,
+                    host: None,
                 },
                 extractions: vec![],
                 assertions: vec![Assertion::StatusCode(200)],
                 cache: None,
                 think_time: None,
+                tags: HashMap::new(),
+                paginate: None,
+                max_concurrent: None,
+                slo: None,
+                shared: vec![],
+                rendezvous: None,
+                conditional: false,
+                expected_status: Vec::new(),
+                patterns: Default::default(),
+                templates: Default::default(),
+                idempotency_key: None,
+                tcp_check: None,
             },
             Step {
                 name: "Check Status".to_string(),
@@ -294,13 +360,39 @@ dave,dave012,dave@company.com,manager"#;
                     body: None,
                     body_size: None,
                     headers: HashMap::new(),
+                    protobuf: None,
+                    host: None,
                 },
                 extractions: vec![],
                 assertions: vec![],
                 cache: None,
                 think_time: None,
+                tags: HashMap::new(),
+                paginate: None,
+                max_concurrent: None,
+                slo: None,
+                shared: vec![],
+                rendezvous: None,
+                conditional: false,
+                expected_status: Vec::new(),
+                patterns: Default::default(),
+                templates: Default::default(),
+                idempotency_key: None,
+                tcp_check: None,
             },
         ],
+        network_profile: None,
+        tags: HashMap::new(),
+        max_concurrent: None,
+        depends_on: Vec::new(),
+        iterations: None,
+        retry: None,
+        assertion_sample_rate: 1.0,
+        client: None,
+        headers: Default::default(),
+        optional: false,
+        data_source: None,
+        rps: None,
     };
 
     // Simulate 8 virtual users (2 full cycles through 4 users)
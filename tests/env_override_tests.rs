@@ -330,6 +330,7 @@ scenarios:
             min_rps,
             max_rps,
             ramp_duration,
+            ..
         } => {
             assert_eq!(min_rps, 50.0);
             assert_eq!(max_rps, 500.0);
@@ -145,12 +145,13 @@ fn test_categorized_error_display() {
 fn test_all_error_categories() {
     let categories = ErrorCategory::all();
 
-    assert_eq!(categories.len(), 6);
+    assert_eq!(categories.len(), 7);
     assert!(categories.contains(&ErrorCategory::ClientError));
     assert!(categories.contains(&ErrorCategory::ServerError));
     assert!(categories.contains(&ErrorCategory::NetworkError));
     assert!(categories.contains(&ErrorCategory::TimeoutError));
     assert!(categories.contains(&ErrorCategory::TlsError));
+    assert!(categories.contains(&ErrorCategory::ResourceExhausted));
     assert!(categories.contains(&ErrorCategory::OtherError));
 
     println!("✅ All error categories enumerated");
@@ -180,12 +181,38 @@ async fn test_404_error_categorization() {
                 body: None,
                 body_size: None,
                 headers: HashMap::new(),
+                protobuf: None,
+                host: None,
             },
             extractions: vec![],
             assertions: vec![],
             cache: None,
             think_time: None,
+            tags: HashMap::new(),
+            paginate: None,
+            max_concurrent: None,
+            slo: None,
+            shared: vec![],
+            rendezvous: None,
+            conditional: false,
+            expected_status: Vec::new(),
+            patterns: Default::default(),
+            templates: Default::default(),
+            idempotency_key: None,
+            tcp_check: None,
         }],
+        network_profile: None,
+        tags: HashMap::new(),
+        max_concurrent: None,
+        depends_on: Vec::new(),
+        iterations: None,
+        retry: None,
+        assertion_sample_rate: 1.0,
+        client: None,
+        headers: Default::default(),
+        optional: false,
+        data_source: None,
+        rps: None,
     };
 
     let client = create_test_client();
@@ -225,12 +252,38 @@ async fn test_timeout_error_categorization() {
                 body: None,
                 body_size: None,
                 headers: HashMap::new(),
+                protobuf: None,
+                host: None,
             },
             extractions: vec![],
             assertions: vec![],
             cache: None,
             think_time: None,
+            tags: HashMap::new(),
+            paginate: None,
+            max_concurrent: None,
+            slo: None,
+            shared: vec![],
+            rendezvous: None,
+            conditional: false,
+            expected_status: Vec::new(),
+            patterns: Default::default(),
+            templates: Default::default(),
+            idempotency_key: None,
+            tcp_check: None,
         }],
+        network_profile: None,
+        tags: HashMap::new(),
+        max_concurrent: None,
+        depends_on: Vec::new(),
+        iterations: None,
+        retry: None,
+        assertion_sample_rate: 1.0,
+        client: None,
+        headers: Default::default(),
+        optional: false,
+        data_source: None,
+        rps: None,
     };
 
     // Create client with extremely short timeout to force timeout
@@ -272,12 +325,38 @@ async fn test_network_error_categorization() {
                 body: None,
                 body_size: None,
                 headers: HashMap::new(),
+                protobuf: None,
+                host: None,
             },
             extractions: vec![],
             assertions: vec![],
             cache: None,
             think_time: None,
+            tags: HashMap::new(),
+            paginate: None,
+            max_concurrent: None,
+            slo: None,
+            shared: vec![],
+            rendezvous: None,
+            conditional: false,
+            expected_status: Vec::new(),
+            patterns: Default::default(),
+            templates: Default::default(),
+            idempotency_key: None,
+            tcp_check: None,
         }],
+        network_profile: None,
+        tags: HashMap::new(),
+        max_concurrent: None,
+        depends_on: Vec::new(),
+        iterations: None,
+        retry: None,
+        assertion_sample_rate: 1.0,
+        client: None,
+        headers: Default::default(),
+        optional: false,
+        data_source: None,
+        rps: None,
     };
 
     let client = create_test_client();
@@ -316,11 +395,25 @@ async fn test_mixed_error_types_in_scenario() {
                     body: None,
                     body_size: None,
                     headers: HashMap::new(),
+                    protobuf: None,
+                    host: None,
                 },
                 extractions: vec![],
                 assertions: vec![Assertion::StatusCode(200)],
                 cache: None,
                 think_time: None,
+                tags: HashMap::new(),
+                paginate: None,
+                max_concurrent: None,
+                slo: None,
+                shared: vec![],
+                rendezvous: None,
+                conditional: false,
+                expected_status: Vec::new(),
+                patterns: Default::default(),
+                templates: Default::default(),
+                idempotency_key: None,
+                tcp_check: None,
             },
             Step {
                 name: "404 Client Error".to_string(),
@@ -330,13 +423,39 @@ async fn test_mixed_error_types_in_scenario() {
                     body: None,
                     body_size: None,
                     headers: HashMap::new(),
+                    protobuf: None,
+                    host: None,
                 },
                 extractions: vec![],
                 assertions: vec![],
                 cache: None,
                 think_time: None,
+                tags: HashMap::new(),
+                paginate: None,
+                max_concurrent: None,
+                slo: None,
+                shared: vec![],
+                rendezvous: None,
+                conditional: false,
+                expected_status: Vec::new(),
+                patterns: Default::default(),
+                templates: Default::default(),
+                idempotency_key: None,
+                tcp_check: None,
             },
         ],
+        network_profile: None,
+        tags: HashMap::new(),
+        max_concurrent: None,
+        depends_on: Vec::new(),
+        iterations: None,
+        retry: None,
+        assertion_sample_rate: 1.0,
+        client: None,
+        headers: Default::default(),
+        optional: false,
+        data_source: None,
+        rps: None,
     };
 
     let client = create_test_client();
@@ -8,7 +8,7 @@ use rust_loadtest::metrics::{
     register_metrics, CONCURRENT_REQUESTS, REQUEST_DURATION_SECONDS, REQUEST_STATUS_CODES,
     REQUEST_TOTAL,
 };
-use rust_loadtest::worker::{run_worker, WorkerConfig};
+use rust_loadtest::worker::{run_worker, WorkerConfig, WorkerPacing};
 
 // Register metrics once across all tests in this file.
 // Calling register_metrics() more than once would panic due to duplicate registration.
@@ -22,19 +22,19 @@ fn init_metrics() {
 
 fn get_total_requests() -> u64 {
     REQUEST_TOTAL
-        .with_label_values(&["local", "", "test-node", "run-0"])
+        .with_label_values(&["local", "local", "", "test-node", "run-0"])
         .get()
 }
 
 fn get_status_code_count(code: &str) -> u64 {
     REQUEST_STATUS_CODES
-        .with_label_values(&[code, "local", "", "test-node", "run-0"])
+        .with_label_values(&[code, "local", "local", "", "test-node", "run-0"])
         .get()
 }
 
 fn get_duration_count() -> u64 {
     REQUEST_DURATION_SECONDS
-        .with_label_values(&["local", "", "test-node", "run-0"])
+        .with_label_values(&["local", "local", "", "test-node", "run-0"])
         .get_sample_count()
 }
 
@@ -66,9 +66,13 @@ async fn worker_sends_get_requests() {
         percentile_tracking_enabled: true,
         percentile_sampling_rate: 100,
         region: "local".to_string(),
+        zone: "local".to_string(),
         tenant: String::new(),
         node_id: "test-node".to_string(),
         run_id: "run-0".to_string(),
+        pacing: WorkerPacing::default(),
+        target_addr: None,
+        templates: None,
         stop_rx: tokio::sync::watch::channel(false).1,
     };
 
@@ -110,9 +114,13 @@ async fn worker_sends_post_requests() {
         percentile_tracking_enabled: true,
         percentile_sampling_rate: 100,
         region: "local".to_string(),
+        zone: "local".to_string(),
         tenant: String::new(),
         node_id: "test-node".to_string(),
         run_id: "run-0".to_string(),
+        pacing: WorkerPacing::default(),
+        target_addr: None,
+        templates: None,
         stop_rx: tokio::sync::watch::channel(false).1,
     };
 
@@ -150,9 +158,13 @@ async fn worker_sends_json_post_body() {
         percentile_tracking_enabled: true,
         percentile_sampling_rate: 100,
         region: "local".to_string(),
+        zone: "local".to_string(),
         tenant: String::new(),
         node_id: "test-node".to_string(),
         run_id: "run-0".to_string(),
+        pacing: WorkerPacing::default(),
+        target_addr: None,
+        templates: None,
         stop_rx: tokio::sync::watch::channel(false).1,
     };
 
@@ -189,9 +201,13 @@ async fn worker_tracks_200_status_codes() {
         percentile_tracking_enabled: true,
         percentile_sampling_rate: 100,
         region: "local".to_string(),
+        zone: "local".to_string(),
         tenant: String::new(),
         node_id: "test-node".to_string(),
         run_id: "run-0".to_string(),
+        pacing: WorkerPacing::default(),
+        target_addr: None,
+        templates: None,
         stop_rx: tokio::sync::watch::channel(false).1,
     };
 
@@ -232,9 +248,13 @@ async fn worker_tracks_404_status_codes() {
         percentile_tracking_enabled: true,
         percentile_sampling_rate: 100,
         region: "local".to_string(),
+        zone: "local".to_string(),
         tenant: String::new(),
         node_id: "test-node".to_string(),
         run_id: "run-0".to_string(),
+        pacing: WorkerPacing::default(),
+        target_addr: None,
+        templates: None,
         stop_rx: tokio::sync::watch::channel(false).1,
     };
 
@@ -275,9 +295,13 @@ async fn worker_tracks_500_status_codes() {
         percentile_tracking_enabled: true,
         percentile_sampling_rate: 100,
         region: "local".to_string(),
+        zone: "local".to_string(),
         tenant: String::new(),
         node_id: "test-node".to_string(),
         run_id: "run-0".to_string(),
+        pacing: WorkerPacing::default(),
+        target_addr: None,
+        templates: None,
         stop_rx: tokio::sync::watch::channel(false).1,
     };
 
@@ -320,9 +344,13 @@ async fn worker_records_request_duration() {
         percentile_tracking_enabled: true,
         percentile_sampling_rate: 100,
         region: "local".to_string(),
+        zone: "local".to_string(),
         tenant: String::new(),
         node_id: "test-node".to_string(),
         run_id: "run-0".to_string(),
+        pacing: WorkerPacing::default(),
+        target_addr: None,
+        templates: None,
         stop_rx: tokio::sync::watch::channel(false).1,
     };
 
@@ -363,9 +391,13 @@ async fn concurrent_requests_returns_to_zero_after_worker_finishes() {
         percentile_tracking_enabled: true,
         percentile_sampling_rate: 100,
         region: "local".to_string(),
+        zone: "local".to_string(),
         tenant: String::new(),
         node_id: "test-node".to_string(),
         run_id: "run-0".to_string(),
+        pacing: WorkerPacing::default(),
+        target_addr: None,
+        templates: None,
         stop_rx: tokio::sync::watch::channel(false).1,
     };
 
@@ -374,7 +406,7 @@ async fn concurrent_requests_returns_to_zero_after_worker_finishes() {
 
     // After worker finishes, concurrent requests gauge should not be negative
     let gauge = CONCURRENT_REQUESTS
-        .with_label_values(&["local", "", "test-node", "run-0"])
+        .with_label_values(&["local", "local", "", "test-node", "run-0"])
         .get();
     assert!(
         gauge >= 0.0,
@@ -404,9 +436,13 @@ async fn worker_handles_connection_error_gracefully() {
         percentile_tracking_enabled: true,
         percentile_sampling_rate: 100,
         region: "local".to_string(),
+        zone: "local".to_string(),
         tenant: String::new(),
         node_id: "test-node".to_string(),
         run_id: "run-0".to_string(),
+        pacing: WorkerPacing::default(),
+        target_addr: None,
+        templates: None,
         stop_rx: tokio::sync::watch::channel(false).1,
     };
 
@@ -451,9 +487,13 @@ async fn worker_respects_rps_rate_limit() {
         percentile_tracking_enabled: true,
         percentile_sampling_rate: 100,
         region: "local".to_string(),
+        zone: "local".to_string(),
         tenant: String::new(),
         node_id: "test-node".to_string(),
         run_id: "run-0".to_string(),
+        pacing: WorkerPacing::default(),
+        target_addr: None,
+        templates: None,
         stop_rx: tokio::sync::watch::channel(false).1,
     };
 
@@ -495,9 +535,13 @@ async fn worker_stops_after_test_duration() {
         percentile_tracking_enabled: true,
         percentile_sampling_rate: 100,
         region: "local".to_string(),
+        zone: "local".to_string(),
         tenant: String::new(),
         node_id: "test-node".to_string(),
         run_id: "run-0".to_string(),
+        pacing: WorkerPacing::default(),
+        target_addr: None,
+        templates: None,
         stop_rx: tokio::sync::watch::channel(false).1,
     };
 
@@ -546,9 +590,13 @@ async fn worker_handles_slow_responses() {
         percentile_tracking_enabled: true,
         percentile_sampling_rate: 100,
         region: "local".to_string(),
+        zone: "local".to_string(),
         tenant: String::new(),
         node_id: "test-node".to_string(),
         run_id: "run-0".to_string(),
+        pacing: WorkerPacing::default(),
+        target_addr: None,
+        templates: None,
         stop_rx: tokio::sync::watch::channel(false).1,
     };
 
@@ -563,3 +611,133 @@ async fn worker_handles_slow_responses() {
         after
     );
 }
+
+// --- Ramp-in and pacing jitter ---
+
+#[test]
+fn worker_pacing_spreads_start_offsets_over_ramp() {
+    let pacing = WorkerPacing {
+        ramp: Duration::from_secs(30),
+        jitter_percent: 0.0,
+    };
+    let cycle = Duration::from_millis(100);
+    assert_eq!(pacing.start_offset(0, 10, cycle), Duration::ZERO);
+    assert_eq!(
+        pacing.start_offset(5, 10, cycle),
+        Duration::from_secs(15) + Duration::from_millis(50)
+    );
+    assert_eq!(pacing.jittered(cycle), cycle);
+}
+
+#[test]
+fn worker_pacing_jitter_stays_within_bounds() {
+    let pacing = WorkerPacing {
+        ramp: Duration::ZERO,
+        jitter_percent: 20.0,
+    };
+    let cycle = Duration::from_millis(1000);
+    for _ in 0..1000 {
+        let next = pacing.jittered(cycle);
+        assert!(next >= Duration::from_millis(800) && next <= Duration::from_millis(1200));
+        let start = pacing.start_offset(0, 4, cycle);
+        assert!(start <= Duration::from_millis(200));
+    }
+}
+
+#[tokio::test]
+async fn worker_ramp_delays_late_workers() {
+    init_metrics();
+    let server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/ramp"))
+        .respond_with(ResponseTemplate::new(200))
+        .expect(0)
+        .mount(&server)
+        .await;
+
+    // Worker 1 of 2 with a 4s ramp starts at t=2s, after the 1s test ends.
+    let config = WorkerConfig {
+        task_id: 1,
+        url: format!("{}/ramp", server.uri()),
+        request_type: "GET".to_string(),
+        send_json: false,
+        json_payload: None,
+        test_duration: Duration::from_secs(1),
+        load_model: LoadModel::Concurrent,
+        num_concurrent_tasks: 2,
+        percentile_tracking_enabled: true,
+        percentile_sampling_rate: 100,
+        region: "local".to_string(),
+        zone: "local".to_string(),
+        tenant: String::new(),
+        node_id: "test-node".to_string(),
+        run_id: "run-0".to_string(),
+        pacing: WorkerPacing {
+            ramp: Duration::from_secs(4),
+            jitter_percent: 0.0,
+        },
+        target_addr: None,
+        templates: None,
+        stop_rx: tokio::sync::watch::channel(false).1,
+    };
+
+    let start = Instant::now();
+    run_worker(reqwest::Client::new(), config, start).await;
+    assert!(start.elapsed() >= Duration::from_secs(2));
+}
+
+// --- Deadline-based pacing ---
+
+#[tokio::test]
+async fn rps_model_pacing_is_independent_of_response_time() {
+    init_metrics();
+    let server = MockServer::start().await;
+
+    // 150ms latency per request. With sleep-after-response pacing, 4 workers
+    // at a 200ms cycle would only reach 4 / (0.2 + 0.15) ≈ 11 RPS instead of 20.
+    Mock::given(method("GET"))
+        .and(path("/paced"))
+        .respond_with(ResponseTemplate::new(200).set_delay(Duration::from_millis(150)))
+        .mount(&server)
+        .await;
+
+    let num_workers = 4;
+    let start = Instant::now();
+    let handles: Vec<_> = (0..num_workers)
+        .map(|i| {
+            let config = WorkerConfig {
+                task_id: i,
+                url: format!("{}/paced", server.uri()),
+                request_type: "GET".to_string(),
+                send_json: false,
+                json_payload: None,
+                test_duration: Duration::from_secs(2),
+                load_model: LoadModel::Rps { target_rps: 20.0 },
+                num_concurrent_tasks: num_workers,
+                percentile_tracking_enabled: true,
+                percentile_sampling_rate: 100,
+                region: "local".to_string(),
+                zone: "local".to_string(),
+                tenant: String::new(),
+                node_id: "test-node".to_string(),
+                run_id: "run-0".to_string(),
+                pacing: WorkerPacing::default(),
+                target_addr: None,
+                templates: None,
+                stop_rx: tokio::sync::watch::channel(false).1,
+            };
+            tokio::spawn(run_worker(reqwest::Client::new(), config, start))
+        })
+        .collect();
+    for handle in handles {
+        handle.await.unwrap();
+    }
+
+    let received = server.received_requests().await.unwrap().len();
+    assert!(
+        (32..=44).contains(&received),
+        "expected ~40 requests at 20 RPS for 2s, got {}",
+        received
+    );
+}
@@ -17,16 +17,52 @@ fn create_test_scenarios() -> Vec<Scenario> {
             name: "Read Operations".to_string(),
             weight: 80.0,
             steps: vec![],
+            network_profile: None,
+            tags: HashMap::new(),
+            max_concurrent: None,
+            depends_on: Vec::new(),
+            iterations: None,
+            retry: None,
+            assertion_sample_rate: 1.0,
+            client: None,
+            headers: Default::default(),
+            optional: false,
+            data_source: None,
+            rps: None,
         },
         Scenario {
             name: "Write Operations".to_string(),
             weight: 15.0,
             steps: vec![],
+            network_profile: None,
+            tags: HashMap::new(),
+            max_concurrent: None,
+            depends_on: Vec::new(),
+            iterations: None,
+            retry: None,
+            assertion_sample_rate: 1.0,
+            client: None,
+            headers: Default::default(),
+            optional: false,
+            data_source: None,
+            rps: None,
         },
         Scenario {
             name: "Delete Operations".to_string(),
             weight: 5.0,
             steps: vec![],
+            network_profile: None,
+            tags: HashMap::new(),
+            max_concurrent: None,
+            depends_on: Vec::new(),
+            iterations: None,
+            retry: None,
+            assertion_sample_rate: 1.0,
+            client: None,
+            headers: Default::default(),
+            optional: false,
+            data_source: None,
+            rps: None,
         },
     ]
 }
@@ -127,16 +163,52 @@ fn test_scenario_selector_equal_weights() {
             name: "S1".to_string(),
             weight: 1.0,
             steps: vec![],
+            network_profile: None,
+            tags: HashMap::new(),
+            max_concurrent: None,
+            depends_on: Vec::new(),
+            iterations: None,
+            retry: None,
+            assertion_sample_rate: 1.0,
+            client: None,
+            headers: Default::default(),
+            optional: false,
+            data_source: None,
+            rps: None,
         },
         Scenario {
             name: "S2".to_string(),
             weight: 1.0,
             steps: vec![],
+            network_profile: None,
+            tags: HashMap::new(),
+            max_concurrent: None,
+            depends_on: Vec::new(),
+            iterations: None,
+            retry: None,
+            assertion_sample_rate: 1.0,
+            client: None,
+            headers: Default::default(),
+            optional: false,
+            data_source: None,
+            rps: None,
         },
         Scenario {
             name: "S3".to_string(),
             weight: 1.0,
             steps: vec![],
+            network_profile: None,
+            tags: HashMap::new(),
+            max_concurrent: None,
+            depends_on: Vec::new(),
+            iterations: None,
+            retry: None,
+            assertion_sample_rate: 1.0,
+            client: None,
+            headers: Default::default(),
+            optional: false,
+            data_source: None,
+            rps: None,
         },
     ];
 
@@ -171,11 +243,35 @@ fn test_scenario_selector_extreme_weights() {
             name: "Dominant".to_string(),
             weight: 99.0,
             steps: vec![],
+            network_profile: None,
+            tags: HashMap::new(),
+            max_concurrent: None,
+            depends_on: Vec::new(),
+            iterations: None,
+            retry: None,
+            assertion_sample_rate: 1.0,
+            client: None,
+            headers: Default::default(),
+            optional: false,
+            data_source: None,
+            rps: None,
         },
         Scenario {
             name: "Rare".to_string(),
             weight: 1.0,
             steps: vec![],
+            network_profile: None,
+            tags: HashMap::new(),
+            max_concurrent: None,
+            depends_on: Vec::new(),
+            iterations: None,
+            retry: None,
+            assertion_sample_rate: 1.0,
+            client: None,
+            headers: Default::default(),
+            optional: false,
+            data_source: None,
+            rps: None,
         },
     ];
 
@@ -211,6 +307,18 @@ fn test_scenario_selector_negative_weight() {
         name: "Invalid".to_string(),
         weight: -5.0,
         steps: vec![],
+        network_profile: None,
+        tags: HashMap::new(),
+        max_concurrent: None,
+        depends_on: Vec::new(),
+        iterations: None,
+        retry: None,
+        assertion_sample_rate: 1.0,
+        client: None,
+        headers: Default::default(),
+        optional: false,
+        data_source: None,
+        rps: None,
     }];
     ScenarioSelector::new(scenarios);
 }
@@ -222,6 +330,18 @@ fn test_scenario_selector_zero_weight() {
         name: "Invalid".to_string(),
         weight: 0.0,
         steps: vec![],
+        network_profile: None,
+        tags: HashMap::new(),
+        max_concurrent: None,
+        depends_on: Vec::new(),
+        iterations: None,
+        retry: None,
+        assertion_sample_rate: 1.0,
+        client: None,
+        headers: Default::default(),
+        optional: false,
+        data_source: None,
+        rps: None,
     }];
     ScenarioSelector::new(scenarios);
 }
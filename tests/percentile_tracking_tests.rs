@@ -5,7 +5,7 @@
 
 use rust_loadtest::executor::{ScenarioExecutor, SessionStore};
 use rust_loadtest::percentiles::{
-    MultiLabelPercentileTracker, PercentileTracker, GLOBAL_SCENARIO_PERCENTILES,
+    MultiLabelPercentileTracker, PercentileTracker, RollingReport, GLOBAL_SCENARIO_PERCENTILES,
     GLOBAL_STEP_PERCENTILES,
 };
 use rust_loadtest::scenario::{RequestConfig, Scenario, ScenarioContext, Step};
@@ -232,11 +232,25 @@ async fn test_scenario_percentile_tracking() {
                     body: None,
                     body_size: None,
                     headers: HashMap::new(),
+                    protobuf: None,
+                    host: None,
                 },
                 extractions: vec![],
                 assertions: vec![],
                 cache: None,
                 think_time: None,
+                tags: HashMap::new(),
+                paginate: None,
+                max_concurrent: None,
+                slo: None,
+                shared: vec![],
+                rendezvous: None,
+                conditional: false,
+                expected_status: Vec::new(),
+                patterns: Default::default(),
+                templates: Default::default(),
+                idempotency_key: None,
+                tcp_check: None,
             },
             Step {
                 name: "Status Check".to_string(),
@@ -246,13 +260,39 @@ async fn test_scenario_percentile_tracking() {
                     body: None,
                     body_size: None,
                     headers: HashMap::new(),
+                    protobuf: None,
+                    host: None,
                 },
                 extractions: vec![],
                 assertions: vec![],
                 cache: None,
                 think_time: None,
+                tags: HashMap::new(),
+                paginate: None,
+                max_concurrent: None,
+                slo: None,
+                shared: vec![],
+                rendezvous: None,
+                conditional: false,
+                expected_status: Vec::new(),
+                patterns: Default::default(),
+                templates: Default::default(),
+                idempotency_key: None,
+                tcp_check: None,
             },
         ],
+        network_profile: None,
+        tags: HashMap::new(),
+        max_concurrent: None,
+        depends_on: Vec::new(),
+        iterations: None,
+        retry: None,
+        assertion_sample_rate: 1.0,
+        client: None,
+        headers: Default::default(),
+        optional: false,
+        data_source: None,
+        rps: None,
     };
 
     let client = create_test_client();
@@ -401,3 +441,47 @@ async fn test_realistic_latency_distribution() {
         stats.p99 as f64 / 1000.0
     );
 }
+
+#[test]
+fn test_rotation_keeps_memory_bounded_over_multi_day_run() {
+    // 30 days of hourly rotations, each interval touching a fresh set of
+    // step labels so the label cache churns as it would with dynamic paths.
+    const HOURS: u64 = 30 * 24;
+    const MAX_SNAPSHOTS: usize = 168;
+    const MAX_LABELS: usize = 50;
+
+    let requests = PercentileTracker::new();
+    let scenarios = MultiLabelPercentileTracker::new_with_limit(MAX_LABELS);
+    let steps = MultiLabelPercentileTracker::new_with_limit(MAX_LABELS);
+    let report = RollingReport::new(MAX_SNAPSHOTS);
+
+    for hour in 0..HOURS {
+        for i in 0..200u64 {
+            let latency = 10 + (hour + i) % 500;
+            requests.record_ms(latency);
+            scenarios.record(&format!("scenario-{}", i % 5), latency);
+            steps.record(&format!("step-{}-{}", hour, i % 20), latency);
+        }
+
+        assert!(scenarios.len() <= MAX_LABELS);
+        assert!(steps.len() <= MAX_LABELS);
+
+        report.rotate(&requests, &scenarios, &steps);
+
+        assert!(requests.stats().is_none(), "rotation clears request data");
+        assert!(report.len() <= MAX_SNAPSHOTS);
+    }
+
+    assert_eq!(report.rotations(), HOURS);
+    assert_eq!(report.len(), MAX_SNAPSHOTS);
+
+    let snapshots = report.snapshots();
+    // Oldest intervals were dropped; the most recent week is kept in order
+    assert_eq!(snapshots[0].sequence, HOURS - MAX_SNAPSHOTS as u64 + 1);
+    assert_eq!(snapshots.last().unwrap().sequence, HOURS);
+    for snapshot in &snapshots {
+        assert_eq!(snapshot.requests.as_ref().unwrap().count, 200);
+        assert!(snapshot.scenarios.len() <= MAX_LABELS);
+        assert!(snapshot.steps.len() <= MAX_LABELS);
+    }
+}
@@ -0,0 +1,72 @@
+//! MAX_TOTAL_REQUESTS together with the in-flight cap.
+//!
+//! The caps are process-wide, so these tests live in their own binary.
+
+use tokio::time::{Duration, Instant};
+use wiremock::matchers::{method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+use rust_loadtest::in_flight_limit::{self, BackpressurePolicy};
+use rust_loadtest::load_models::LoadModel;
+use rust_loadtest::rate_budget;
+use rust_loadtest::worker::{run_worker, WorkerConfig, WorkerPacing};
+
+fn worker_config(task_id: usize, url: String) -> WorkerConfig {
+    WorkerConfig {
+        task_id,
+        url,
+        request_type: "GET".to_string(),
+        send_json: false,
+        json_payload: None,
+        test_duration: Duration::from_secs(10),
+        load_model: LoadModel::Concurrent,
+        num_concurrent_tasks: 4,
+        percentile_tracking_enabled: false,
+        percentile_sampling_rate: 100,
+        region: "local".to_string(),
+        zone: "local".to_string(),
+        tenant: String::new(),
+        node_id: "test-node".to_string(),
+        run_id: "run-0".to_string(),
+        pacing: WorkerPacing::default(),
+        target_addr: None,
+        templates: None,
+        stop_rx: tokio::sync::watch::channel(false).1,
+    }
+}
+
+#[tokio::test]
+async fn shed_requests_do_not_spend_the_budget() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/slow"))
+        .respond_with(ResponseTemplate::new(200).set_delay(Duration::from_millis(50)))
+        .mount(&server)
+        .await;
+
+    // One request in flight at a time: the other workers' attempts are shed
+    // while it is outstanding.
+    rate_budget::init(6, 0.0);
+    in_flight_limit::init(1, BackpressurePolicy::Shed);
+
+    let start = Instant::now();
+    let workers: Vec<_> = (0..4)
+        .map(|task_id| {
+            let config = worker_config(task_id, format!("{}/slow", server.uri()));
+            tokio::spawn(run_worker(reqwest::Client::new(), config, start))
+        })
+        .collect();
+    for worker in workers {
+        worker.await.unwrap();
+    }
+
+    assert!(rate_budget::is_exhausted());
+    assert!(
+        start.elapsed() < Duration::from_secs(10),
+        "workers ran out the clock"
+    );
+    assert_eq!(server.received_requests().await.unwrap().len(), 6);
+
+    in_flight_limit::init(0, BackpressurePolicy::Queue);
+    rate_budget::init(0, 0.0);
+}
@@ -6,12 +6,17 @@
 //! Run with: cargo test --test scenario_integration_tests
 
 use rust_loadtest::executor::{ScenarioExecutor, SessionStore};
+use rust_loadtest::middleware::{HeaderInjector, MiddlewareChain};
+use rust_loadtest::plugin::{DataSourcePlugin, PluginError, GLOBAL_PLUGIN_REGISTRY};
 use rust_loadtest::scenario::{
-    Assertion, RequestConfig, Scenario, ScenarioContext, Step, ThinkTime,
+    Assertion, Extractor, Paginate, RequestConfig, RetryPolicy, Scenario, ScenarioContext,
+    SharedAction, Step, StepCache, ThinkTime, VariableExtraction, VariableScope,
 };
+use rust_loadtest::shared_state::StoreOp;
 use std::collections::HashMap;
+use std::sync::Arc;
 use std::time::Duration;
-use wiremock::matchers::{method, path};
+use wiremock::matchers::{header, header_exists, method, path, query_param};
 use wiremock::{Mock, MockServer, ResponseTemplate};
 
 const BASE_URL: &str = "https://httpbin.org";
@@ -37,12 +42,38 @@ async fn test_health_check_scenario() {
                 body: None,
                 body_size: None,
                 headers: HashMap::new(),
+                protobuf: None,
+                host: None,
             },
             extractions: vec![],
             assertions: vec![Assertion::StatusCode(200)],
             cache: None,
             think_time: None,
+            tags: HashMap::new(),
+            paginate: None,
+            max_concurrent: None,
+            slo: None,
+            shared: vec![],
+            rendezvous: None,
+            conditional: false,
+            expected_status: Vec::new(),
+            patterns: Default::default(),
+            templates: Default::default(),
+            idempotency_key: None,
+            tcp_check: None,
         }],
+        network_profile: None,
+        tags: HashMap::new(),
+        max_concurrent: None,
+        depends_on: Vec::new(),
+        iterations: None,
+        retry: None,
+        assertion_sample_rate: 1.0,
+        client: None,
+        headers: Default::default(),
+        optional: false,
+        data_source: None,
+        rps: None,
     };
 
     let client = create_test_client();
@@ -77,11 +108,25 @@ async fn test_product_browsing_scenario() {
                     body: None,
                     body_size: None,
                     headers: HashMap::new(),
+                    protobuf: None,
+                    host: None,
                 },
                 extractions: vec![],
                 assertions: vec![Assertion::StatusCode(200)],
                 cache: None,
                 think_time: Some(ThinkTime::Fixed(Duration::from_millis(100))),
+                tags: HashMap::new(),
+                paginate: None,
+                max_concurrent: None,
+                slo: None,
+                shared: vec![],
+                rendezvous: None,
+                conditional: false,
+                expected_status: Vec::new(),
+                patterns: Default::default(),
+                templates: Default::default(),
+                idempotency_key: None,
+                tcp_check: None,
             },
             Step {
                 name: "Get Item Details".to_string(),
@@ -91,13 +136,39 @@ async fn test_product_browsing_scenario() {
                     body: None,
                     body_size: None,
                     headers: HashMap::new(),
+                    protobuf: None,
+                    host: None,
                 },
                 extractions: vec![],
                 assertions: vec![Assertion::StatusCode(200)],
                 cache: None,
                 think_time: None,
+                tags: HashMap::new(),
+                paginate: None,
+                max_concurrent: None,
+                slo: None,
+                shared: vec![],
+                rendezvous: None,
+                conditional: false,
+                expected_status: Vec::new(),
+                patterns: Default::default(),
+                templates: Default::default(),
+                idempotency_key: None,
+                tcp_check: None,
             },
         ],
+        network_profile: None,
+        tags: HashMap::new(),
+        max_concurrent: None,
+        depends_on: Vec::new(),
+        iterations: None,
+        retry: None,
+        assertion_sample_rate: 1.0,
+        client: None,
+        headers: Default::default(),
+        optional: false,
+        data_source: None,
+        rps: None,
     };
 
     let client = create_test_client();
@@ -142,12 +213,38 @@ async fn test_variable_substitution() {
                 body: None,
                 body_size: None,
                 headers: HashMap::new(),
+                protobuf: None,
+                host: None,
             },
             extractions: vec![],
             assertions: vec![],
             cache: None,
             think_time: None,
+            tags: HashMap::new(),
+            paginate: None,
+            max_concurrent: None,
+            slo: None,
+            shared: vec![],
+            rendezvous: None,
+            conditional: false,
+            expected_status: Vec::new(),
+            patterns: Default::default(),
+            templates: Default::default(),
+            idempotency_key: None,
+            tcp_check: None,
         }],
+        network_profile: None,
+        tags: HashMap::new(),
+        max_concurrent: None,
+        depends_on: Vec::new(),
+        iterations: None,
+        retry: None,
+        assertion_sample_rate: 1.0,
+        client: None,
+        headers: Default::default(),
+        optional: false,
+        data_source: None,
+        rps: None,
     };
 
     let client = create_test_client();
@@ -197,11 +294,25 @@ async fn test_multi_step_with_delays() {
                     body: None,
                     body_size: None,
                     headers: HashMap::new(),
+                    protobuf: None,
+                    host: None,
                 },
                 extractions: vec![],
                 assertions: vec![],
                 cache: None,
                 think_time: Some(ThinkTime::Fixed(Duration::from_millis(200))),
+                tags: HashMap::new(),
+                paginate: None,
+                max_concurrent: None,
+                slo: None,
+                shared: vec![],
+                rendezvous: None,
+                conditional: false,
+                expected_status: Vec::new(),
+                patterns: Default::default(),
+                templates: Default::default(),
+                idempotency_key: None,
+                tcp_check: None,
             },
             Step {
                 name: "Step 2".to_string(),
@@ -211,11 +322,25 @@ async fn test_multi_step_with_delays() {
                     body: None,
                     body_size: None,
                     headers: HashMap::new(),
+                    protobuf: None,
+                    host: None,
                 },
                 extractions: vec![],
                 assertions: vec![],
                 cache: None,
                 think_time: Some(ThinkTime::Fixed(Duration::from_millis(200))),
+                tags: HashMap::new(),
+                paginate: None,
+                max_concurrent: None,
+                slo: None,
+                shared: vec![],
+                rendezvous: None,
+                conditional: false,
+                expected_status: Vec::new(),
+                patterns: Default::default(),
+                templates: Default::default(),
+                idempotency_key: None,
+                tcp_check: None,
             },
             Step {
                 name: "Step 3".to_string(),
@@ -225,13 +350,39 @@ async fn test_multi_step_with_delays() {
                     body: None,
                     body_size: None,
                     headers: HashMap::new(),
+                    protobuf: None,
+                    host: None,
                 },
                 extractions: vec![],
                 assertions: vec![],
                 cache: None,
                 think_time: None,
+                tags: HashMap::new(),
+                paginate: None,
+                max_concurrent: None,
+                slo: None,
+                shared: vec![],
+                rendezvous: None,
+                conditional: false,
+                expected_status: Vec::new(),
+                patterns: Default::default(),
+                templates: Default::default(),
+                idempotency_key: None,
+                tcp_check: None,
             },
         ],
+        network_profile: None,
+        tags: HashMap::new(),
+        max_concurrent: None,
+        depends_on: Vec::new(),
+        iterations: None,
+        retry: None,
+        assertion_sample_rate: 1.0,
+        client: None,
+        headers: Default::default(),
+        optional: false,
+        data_source: None,
+        rps: None,
     };
 
     let client = create_test_client();
@@ -274,11 +425,25 @@ async fn test_scenario_failure_handling() {
                     body: None,
                     body_size: None,
                     headers: HashMap::new(),
+                    protobuf: None,
+                    host: None,
                 },
                 extractions: vec![],
                 assertions: vec![Assertion::StatusCode(200)],
                 cache: None,
                 think_time: None,
+                tags: HashMap::new(),
+                paginate: None,
+                max_concurrent: None,
+                slo: None,
+                shared: vec![],
+                rendezvous: None,
+                conditional: false,
+                expected_status: Vec::new(),
+                patterns: Default::default(),
+                templates: Default::default(),
+                idempotency_key: None,
+                tcp_check: None,
             },
             Step {
                 name: "Invalid Request".to_string(),
@@ -288,11 +453,25 @@ async fn test_scenario_failure_handling() {
                     body: None,
                     body_size: None,
                     headers: HashMap::new(),
+                    protobuf: None,
+                    host: None,
                 },
                 extractions: vec![],
                 assertions: vec![Assertion::StatusCode(200)],
                 cache: None,
                 think_time: None,
+                tags: HashMap::new(),
+                paginate: None,
+                max_concurrent: None,
+                slo: None,
+                shared: vec![],
+                rendezvous: None,
+                conditional: false,
+                expected_status: Vec::new(),
+                patterns: Default::default(),
+                templates: Default::default(),
+                idempotency_key: None,
+                tcp_check: None,
             },
             Step {
                 name: "Should Not Execute".to_string(),
@@ -302,13 +481,39 @@ async fn test_scenario_failure_handling() {
                     body: None,
                     body_size: None,
                     headers: HashMap::new(),
+                    protobuf: None,
+                    host: None,
                 },
                 extractions: vec![],
                 assertions: vec![],
                 cache: None,
                 think_time: None,
+                tags: HashMap::new(),
+                paginate: None,
+                max_concurrent: None,
+                slo: None,
+                shared: vec![],
+                rendezvous: None,
+                conditional: false,
+                expected_status: Vec::new(),
+                patterns: Default::default(),
+                templates: Default::default(),
+                idempotency_key: None,
+                tcp_check: None,
             },
         ],
+        network_profile: None,
+        tags: HashMap::new(),
+        max_concurrent: None,
+        depends_on: Vec::new(),
+        iterations: None,
+        retry: None,
+        assertion_sample_rate: 1.0,
+        client: None,
+        headers: Default::default(),
+        optional: false,
+        data_source: None,
+        rps: None,
     };
 
     let client = create_test_client();
@@ -361,12 +566,38 @@ async fn test_timestamp_variable() {
                     headers.insert("X-Request-ID".to_string(), "req-${timestamp}".to_string());
                     headers
                 },
+                protobuf: None,
+                host: None,
             },
             extractions: vec![],
             assertions: vec![],
             cache: None,
             think_time: None,
+            tags: HashMap::new(),
+            paginate: None,
+            max_concurrent: None,
+            slo: None,
+            shared: vec![],
+            rendezvous: None,
+            conditional: false,
+            expected_status: Vec::new(),
+            patterns: Default::default(),
+            templates: Default::default(),
+            idempotency_key: None,
+            tcp_check: None,
         }],
+        network_profile: None,
+        tags: HashMap::new(),
+        max_concurrent: None,
+        depends_on: Vec::new(),
+        iterations: None,
+        retry: None,
+        assertion_sample_rate: 1.0,
+        client: None,
+        headers: Default::default(),
+        optional: false,
+        data_source: None,
+        rps: None,
     };
 
     let client = create_test_client();
@@ -411,12 +642,38 @@ async fn test_post_request_with_json_body() {
                     headers.insert("Content-Type".to_string(), "application/json".to_string());
                     headers
                 },
+                protobuf: None,
+                host: None,
             },
             extractions: vec![],
             assertions: vec![],
             cache: None,
             think_time: None,
+            tags: HashMap::new(),
+            paginate: None,
+            max_concurrent: None,
+            slo: None,
+            shared: vec![],
+            rendezvous: None,
+            conditional: false,
+            expected_status: Vec::new(),
+            patterns: Default::default(),
+            templates: Default::default(),
+            idempotency_key: None,
+            tcp_check: None,
         }],
+        network_profile: None,
+        tags: HashMap::new(),
+        max_concurrent: None,
+        depends_on: Vec::new(),
+        iterations: None,
+        retry: None,
+        assertion_sample_rate: 1.0,
+        client: None,
+        headers: Default::default(),
+        optional: false,
+        data_source: None,
+        rps: None,
     };
 
     let client = create_test_client();
@@ -454,12 +711,38 @@ async fn test_scenario_context_isolation() {
                 body: None,
                 body_size: None,
                 headers: HashMap::new(),
+                protobuf: None,
+                host: None,
             },
             extractions: vec![],
             assertions: vec![],
             cache: None,
             think_time: None,
+            tags: HashMap::new(),
+            paginate: None,
+            max_concurrent: None,
+            slo: None,
+            shared: vec![],
+            rendezvous: None,
+            conditional: false,
+            expected_status: Vec::new(),
+            patterns: Default::default(),
+            templates: Default::default(),
+            idempotency_key: None,
+            tcp_check: None,
         }],
+        network_profile: None,
+        tags: HashMap::new(),
+        max_concurrent: None,
+        depends_on: Vec::new(),
+        iterations: None,
+        retry: None,
+        assertion_sample_rate: 1.0,
+        client: None,
+        headers: Default::default(),
+        optional: false,
+        data_source: None,
+        rps: None,
     };
 
     let client = create_test_client();
@@ -516,12 +799,38 @@ async fn test_body_size_sends_correct_content_length() {
                 body: None,
                 body_size: Some(512),
                 headers: HashMap::new(),
+                protobuf: None,
+                host: None,
             },
             extractions: vec![],
             assertions: vec![Assertion::StatusCode(200)],
             cache: None,
             think_time: None,
+            tags: HashMap::new(),
+            paginate: None,
+            max_concurrent: None,
+            slo: None,
+            shared: vec![],
+            rendezvous: None,
+            conditional: false,
+            expected_status: Vec::new(),
+            patterns: Default::default(),
+            templates: Default::default(),
+            idempotency_key: None,
+            tcp_check: None,
         }],
+        network_profile: None,
+        tags: HashMap::new(),
+        max_concurrent: None,
+        depends_on: Vec::new(),
+        iterations: None,
+        retry: None,
+        assertion_sample_rate: 1.0,
+        client: None,
+        headers: Default::default(),
+        optional: false,
+        data_source: None,
+        rps: None,
     };
 
     let client = create_test_client();
@@ -550,3 +859,1021 @@ async fn test_body_size_sends_correct_content_length() {
         "body should be exactly 512 bytes"
     );
 }
+
+#[tokio::test]
+async fn test_paginate_follows_cursor_until_exhausted() {
+    let server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/items"))
+        .and(query_param("cursor", "c2"))
+        .respond_with(
+            ResponseTemplate::new(200).set_body_string(r#"{"items": [3, 4], "next": "c3"}"#),
+        )
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/items"))
+        .and(query_param("cursor", "c3"))
+        .respond_with(ResponseTemplate::new(200).set_body_string(r#"{"items": [5], "next": null}"#))
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/items"))
+        .respond_with(
+            ResponseTemplate::new(200).set_body_string(r#"{"items": [1, 2], "next": "c2"}"#),
+        )
+        .mount(&server)
+        .await;
+
+    let step = |max_pages| Step {
+        name: "List Items".to_string(),
+        request: RequestConfig {
+            method: "GET".to_string(),
+            path: "/items?limit=2".to_string(),
+            body: None,
+            body_size: None,
+            headers: HashMap::new(),
+            protobuf: None,
+            host: None,
+        },
+        extractions: vec![],
+        assertions: vec![Assertion::StatusCode(200)],
+        cache: None,
+        think_time: None,
+        tags: HashMap::new(),
+        paginate: Some(Paginate {
+            next_field: "$.next".to_string(),
+            max_pages,
+            cursor_param: Some("cursor".to_string()),
+        }),
+        max_concurrent: None,
+        slo: None,
+        shared: vec![],
+        rendezvous: None,
+        conditional: false,
+        expected_status: Vec::new(),
+        patterns: Default::default(),
+        templates: Default::default(),
+        idempotency_key: None,
+        tcp_check: None,
+    };
+    let executor = ScenarioExecutor::new(
+        server.uri(),
+        create_test_client(),
+        "test-node".to_string(),
+        "run-0".to_string(),
+    );
+
+    for (max_pages, expected_pages) in [(50, 3), (2, 2)] {
+        let scenario = Scenario {
+            name: "Pagination".to_string(),
+            weight: 1.0,
+            steps: vec![step(max_pages)],
+            network_profile: None,
+            tags: HashMap::new(),
+            max_concurrent: None,
+            depends_on: Vec::new(),
+            iterations: None,
+            retry: None,
+            assertion_sample_rate: 1.0,
+            client: None,
+            headers: Default::default(),
+            optional: false,
+            data_source: None,
+            rps: None,
+        };
+        let result = executor
+            .execute(
+                &scenario,
+                &mut ScenarioContext::new(),
+                &mut SessionStore::new(),
+            )
+            .await;
+
+        assert!(result.success);
+        assert_eq!(result.steps.len(), expected_pages);
+        assert!(result.steps.iter().all(|s| s.step_name == "List Items"));
+    }
+
+    let requests = server.received_requests().await.unwrap();
+    assert_eq!(requests.len(), 5);
+    assert_eq!(requests[1].url.query(), Some("limit=2&cursor=c2"));
+}
+
+#[tokio::test]
+async fn test_session_cache_keyed_by_variable() {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/login"))
+        .respond_with(ResponseTemplate::new(200).set_body_string(r#"{"token": "t"}"#))
+        .mount(&server)
+        .await;
+
+    let scenario = Scenario {
+        name: "Cached Login".to_string(),
+        weight: 1.0,
+        steps: vec![Step {
+            name: "Login".to_string(),
+            request: RequestConfig {
+                method: "POST".to_string(),
+                path: "/login".to_string(),
+                body: Some(r#"{"user": "${user}"}"#.to_string()),
+                body_size: None,
+                headers: HashMap::new(),
+                protobuf: None,
+                host: None,
+            },
+            extractions: vec![VariableExtraction {
+                name: "token".to_string(),
+                extractor: Extractor::JsonPath("$.token".to_string()),
+                transforms: Vec::new(),
+                scope: VariableScope::Iteration,
+            }],
+            assertions: vec![],
+            cache: Some(StepCache {
+                ttl: Duration::from_secs(300),
+                key: Some("${user}".to_string()),
+                shared: false,
+            }),
+            think_time: None,
+            tags: HashMap::new(),
+            paginate: None,
+            max_concurrent: None,
+            slo: None,
+            shared: vec![],
+            rendezvous: None,
+            conditional: false,
+            expected_status: Vec::new(),
+            patterns: Default::default(),
+            templates: Default::default(),
+            idempotency_key: None,
+            tcp_check: None,
+        }],
+        network_profile: None,
+        tags: HashMap::new(),
+        max_concurrent: None,
+        depends_on: Vec::new(),
+        iterations: None,
+        retry: None,
+        assertion_sample_rate: 1.0,
+        client: None,
+        headers: Default::default(),
+        optional: false,
+        data_source: None,
+        rps: None,
+    };
+    let executor = ScenarioExecutor::new(
+        server.uri(),
+        create_test_client(),
+        "test-node".to_string(),
+        "run-0".to_string(),
+    );
+
+    // One worker session: alice logs in, bob logs in, alice hits the cache
+    let mut session = SessionStore::new();
+    let mut hits = Vec::new();
+    for user in ["alice", "bob", "alice"] {
+        let mut context = ScenarioContext::new();
+        context.set_variable("user".to_string(), user.to_string());
+        let result = executor
+            .execute(&scenario, &mut context, &mut session)
+            .await;
+        assert!(result.success);
+        assert_eq!(context.get_variable("token").map(String::as_str), Some("t"));
+        hits.push(result.steps[0].cache_hit);
+    }
+
+    assert_eq!(hits, vec![false, false, true]);
+    assert_eq!(server.received_requests().await.unwrap().len(), 2);
+}
+
+#[tokio::test]
+async fn test_session_cache_isolated_per_vu_and_scenario() {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/login"))
+        .respond_with(ResponseTemplate::new(200).set_body_string(r#"{"token": "t"}"#))
+        .mount(&server)
+        .await;
+
+    let checkout = Scenario {
+        name: "Checkout".to_string(),
+        weight: 1.0,
+        steps: vec![Step {
+            name: "Login".to_string(),
+            request: RequestConfig {
+                method: "POST".to_string(),
+                path: "/login".to_string(),
+                body: None,
+                body_size: None,
+                headers: HashMap::new(),
+                protobuf: None,
+                host: None,
+            },
+            extractions: vec![VariableExtraction {
+                name: "token".to_string(),
+                extractor: Extractor::JsonPath("$.token".to_string()),
+                transforms: Vec::new(),
+                scope: VariableScope::Iteration,
+            }],
+            assertions: vec![],
+            cache: Some(StepCache {
+                ttl: Duration::from_secs(300),
+                key: None,
+                shared: false,
+            }),
+            think_time: None,
+            tags: HashMap::new(),
+            paginate: None,
+            max_concurrent: None,
+            slo: None,
+            shared: vec![],
+            rendezvous: None,
+            conditional: false,
+            expected_status: Vec::new(),
+            patterns: Default::default(),
+            templates: Default::default(),
+            idempotency_key: None,
+            tcp_check: None,
+        }],
+        network_profile: None,
+        tags: HashMap::new(),
+        max_concurrent: None,
+        depends_on: Vec::new(),
+        iterations: None,
+        retry: None,
+        assertion_sample_rate: 1.0,
+        client: None,
+        headers: Default::default(),
+        optional: false,
+        data_source: None,
+        rps: None,
+    };
+    // Same step name and cache settings in another scenario
+    let browse = Scenario {
+        name: "Browse".to_string(),
+        ..checkout.clone()
+    };
+
+    // Four virtual users run both scenarios in parallel; each logs in once
+    // per scenario and then hits its own cache entry.
+    let handles: Vec<_> = (0..4)
+        .map(|vu| {
+            let (checkout, browse) = (checkout.clone(), browse.clone());
+            let executor = ScenarioExecutor::new(
+                server.uri(),
+                create_test_client(),
+                "test-node".to_string(),
+                "run-0".to_string(),
+            );
+            tokio::spawn(async move {
+                let mut session = SessionStore::for_vu(vu);
+                let mut hits = Vec::new();
+                for scenario in [&checkout, &checkout, &browse, &browse] {
+                    let mut context = ScenarioContext::new();
+                    let result = executor.execute(scenario, &mut context, &mut session).await;
+                    assert!(result.success);
+                    hits.push(result.steps[0].cache_hit);
+                }
+                assert_eq!(hits, vec![false, true, false, true]);
+                assert_eq!(session.len(), 2);
+            })
+        })
+        .collect();
+    for handle in handles {
+        handle.await.unwrap();
+    }
+
+    assert_eq!(server.received_requests().await.unwrap().len(), 8);
+}
+
+#[tokio::test]
+async fn test_shared_session_cache_single_flight() {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/oauth/token"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .set_body_string(r#"{"access_token": "shared"}"#)
+                .set_delay(Duration::from_millis(200)),
+        )
+        .mount(&server)
+        .await;
+
+    let scenario = Scenario {
+        name: "Shared Token".to_string(),
+        weight: 1.0,
+        steps: vec![Step {
+            name: "Get Token".to_string(),
+            request: RequestConfig {
+                method: "POST".to_string(),
+                path: "/oauth/token".to_string(),
+                body: None,
+                body_size: None,
+                headers: HashMap::new(),
+                protobuf: None,
+                host: None,
+            },
+            extractions: vec![VariableExtraction {
+                name: "token".to_string(),
+                extractor: Extractor::JsonPath("$.access_token".to_string()),
+                transforms: Vec::new(),
+                scope: VariableScope::Iteration,
+            }],
+            assertions: vec![],
+            cache: Some(StepCache {
+                ttl: Duration::from_secs(300),
+                key: None,
+                shared: true,
+            }),
+            think_time: None,
+            tags: HashMap::new(),
+            paginate: None,
+            max_concurrent: None,
+            slo: None,
+            shared: vec![],
+            rendezvous: None,
+            conditional: false,
+            expected_status: Vec::new(),
+            patterns: Default::default(),
+            templates: Default::default(),
+            idempotency_key: None,
+            tcp_check: None,
+        }],
+        network_profile: None,
+        tags: HashMap::new(),
+        max_concurrent: None,
+        depends_on: Vec::new(),
+        iterations: None,
+        retry: None,
+        assertion_sample_rate: 1.0,
+        client: None,
+        headers: Default::default(),
+        optional: false,
+        data_source: None,
+        rps: None,
+    };
+
+    // Eight workers, each with its own session store, start at once
+    let handles: Vec<_> = (0..8)
+        .map(|_| {
+            let scenario = scenario.clone();
+            let executor = ScenarioExecutor::new(
+                server.uri(),
+                create_test_client(),
+                "test-node".to_string(),
+                "run-0".to_string(),
+            );
+            tokio::spawn(async move {
+                let mut context = ScenarioContext::new();
+                let result = executor
+                    .execute(&scenario, &mut context, &mut SessionStore::new())
+                    .await;
+                assert!(result.success);
+                assert_eq!(
+                    context.get_variable("token").map(String::as_str),
+                    Some("shared")
+                );
+            })
+        })
+        .collect();
+    for handle in handles {
+        handle.await.unwrap();
+    }
+
+    assert_eq!(server.received_requests().await.unwrap().len(), 1);
+}
+
+#[tokio::test]
+async fn test_middleware_transforms_requests() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/orders"))
+        .and(header("x-api-key", "k-123"))
+        .respond_with(ResponseTemplate::new(200))
+        .mount(&server)
+        .await;
+
+    let scenario = Scenario {
+        name: "Signed".to_string(),
+        weight: 1.0,
+        steps: vec![Step {
+            name: "List Orders".to_string(),
+            request: RequestConfig {
+                method: "GET".to_string(),
+                path: "/orders".to_string(),
+                body: None,
+                body_size: None,
+                headers: HashMap::new(),
+                protobuf: None,
+                host: None,
+            },
+            extractions: vec![],
+            assertions: vec![Assertion::StatusCode(200)],
+            cache: None,
+            think_time: None,
+            tags: HashMap::new(),
+            paginate: None,
+            max_concurrent: None,
+            slo: None,
+            shared: vec![],
+            rendezvous: None,
+            conditional: false,
+            expected_status: Vec::new(),
+            patterns: Default::default(),
+            templates: Default::default(),
+            idempotency_key: None,
+            tcp_check: None,
+        }],
+        network_profile: None,
+        tags: HashMap::new(),
+        max_concurrent: None,
+        depends_on: Vec::new(),
+        iterations: None,
+        retry: None,
+        assertion_sample_rate: 1.0,
+        client: None,
+        headers: Default::default(),
+        optional: false,
+        data_source: None,
+        rps: None,
+    };
+
+    let injector = HeaderInjector::new([("X-Api-Key", "k-123")]).unwrap();
+    let executor = ScenarioExecutor::new(
+        server.uri(),
+        create_test_client(),
+        "test-node".to_string(),
+        "run-0".to_string(),
+    )
+    .with_middleware(MiddlewareChain::new().with(std::sync::Arc::new(injector)));
+
+    let result = executor
+        .execute(
+            &scenario,
+            &mut ScenarioContext::new(),
+            &mut SessionStore::new(),
+        )
+        .await;
+    assert!(result.success, "{:?}", result.steps[0].error);
+}
+
+#[tokio::test]
+async fn test_step_host_header_override() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/"))
+        .and(header("host", "prod.example.com"))
+        .respond_with(ResponseTemplate::new(200))
+        .mount(&server)
+        .await;
+
+    let scenario = Scenario {
+        name: "Origin".to_string(),
+        weight: 1.0,
+        steps: vec![Step {
+            name: "Home".to_string(),
+            request: RequestConfig {
+                method: "GET".to_string(),
+                path: "/".to_string(),
+                body: None,
+                body_size: None,
+                headers: HashMap::new(),
+                protobuf: None,
+                host: Some("${env}.example.com".to_string()),
+            },
+            extractions: vec![],
+            assertions: vec![Assertion::StatusCode(200)],
+            cache: None,
+            think_time: None,
+            tags: HashMap::new(),
+            paginate: None,
+            max_concurrent: None,
+            slo: None,
+            shared: vec![],
+            rendezvous: None,
+            conditional: false,
+            expected_status: Vec::new(),
+            patterns: Default::default(),
+            templates: Default::default(),
+            idempotency_key: None,
+            tcp_check: None,
+        }],
+        network_profile: None,
+        tags: HashMap::new(),
+        max_concurrent: None,
+        depends_on: Vec::new(),
+        iterations: None,
+        retry: None,
+        assertion_sample_rate: 1.0,
+        client: None,
+        headers: Default::default(),
+        optional: false,
+        data_source: None,
+        rps: None,
+    };
+
+    let executor = ScenarioExecutor::new(
+        server.uri(),
+        create_test_client(),
+        "test-node".to_string(),
+        "run-0".to_string(),
+    );
+    let mut context = ScenarioContext::new();
+    context.set_variable("env".to_string(), "prod".to_string());
+
+    let result = executor
+        .execute(&scenario, &mut context, &mut SessionStore::new())
+        .await;
+    assert!(result.success, "{:?}", result.steps[0].error);
+}
+
+/// A token extracted by one scenario is pushed to the shared store and popped
+/// by another; popping an empty key fails the step without a request.
+#[tokio::test]
+async fn test_shared_store_hands_tokens_between_scenarios() {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/login"))
+        .respond_with(ResponseTemplate::new(200).set_body_string(r#"{"token":"tok-1"}"#))
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/orders"))
+        .and(header("authorization", "Bearer tok-1"))
+        .respond_with(ResponseTemplate::new(200))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let step = |name: &str, method: &str, path: &str| Step {
+        name: name.to_string(),
+        request: RequestConfig {
+            method: method.to_string(),
+            path: path.to_string(),
+            body: None,
+            body_size: None,
+            headers: HashMap::new(),
+            protobuf: None,
+            host: None,
+        },
+        extractions: vec![],
+        assertions: vec![Assertion::StatusCode(200)],
+        cache: None,
+        think_time: None,
+        tags: HashMap::new(),
+        paginate: None,
+        max_concurrent: None,
+        slo: None,
+        shared: vec![],
+        rendezvous: None,
+        conditional: false,
+        expected_status: Vec::new(),
+        patterns: Default::default(),
+        templates: Default::default(),
+        idempotency_key: None,
+        tcp_check: None,
+    };
+    let scenario = |name: &str, step: Step| Scenario {
+        name: name.to_string(),
+        weight: 1.0,
+        steps: vec![step],
+        network_profile: None,
+        tags: HashMap::new(),
+        max_concurrent: None,
+        depends_on: Vec::new(),
+        iterations: None,
+        retry: None,
+        assertion_sample_rate: 1.0,
+        client: None,
+        headers: Default::default(),
+        optional: false,
+        data_source: None,
+        rps: None,
+    };
+
+    let mut login = step("Login", "POST", "/login");
+    login.extractions = vec![VariableExtraction {
+        name: "token".to_string(),
+        extractor: Extractor::JsonPath("$.token".to_string()),
+        transforms: Vec::new(),
+        scope: VariableScope::Iteration,
+    }];
+    login.shared = vec![SharedAction {
+        op: StoreOp::Push {
+            key: "integration-tokens".to_string(),
+            value: "${token}".to_string(),
+        },
+        save_as: None,
+    }];
+    let mut orders = step("Orders", "GET", "/orders");
+    orders
+        .request
+        .headers
+        .insert("Authorization".to_string(), "Bearer ${token}".to_string());
+    orders.shared = vec![SharedAction {
+        op: StoreOp::Pop {
+            key: "integration-tokens".to_string(),
+        },
+        save_as: Some("token".to_string()),
+    }];
+    let producer = scenario("Producer", login);
+    let consumer = scenario("Consumer", orders);
+
+    let executor = ScenarioExecutor::new(
+        server.uri(),
+        create_test_client(),
+        "test-node".to_string(),
+        "run-0".to_string(),
+    );
+    let run = |scenario: &Scenario| {
+        let executor = &executor;
+        let scenario = scenario.clone();
+        async move {
+            executor
+                .execute(
+                    &scenario,
+                    &mut ScenarioContext::new(),
+                    &mut SessionStore::new(),
+                )
+                .await
+        }
+    };
+
+    assert!(run(&producer).await.success);
+    let consumed = run(&consumer).await;
+    assert!(consumed.success, "{:?}", consumed.steps[0].error);
+
+    let empty = run(&consumer).await;
+    assert!(!empty.success);
+    assert!(empty.steps[0].error.as_ref().unwrap().contains("no values"));
+}
+
+/// A 429 with Retry-After is retried after the requested (capped) delay
+/// rather than retryDelay, and only the last attempt is reported.
+#[tokio::test]
+async fn test_retry_honors_retry_after() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/limited"))
+        .respond_with(ResponseTemplate::new(429).insert_header("retry-after", "1"))
+        .up_to_n_times(1)
+        .with_priority(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/limited"))
+        .respond_with(ResponseTemplate::new(200))
+        .mount(&server)
+        .await;
+
+    let scenario = Scenario {
+        name: "Rate Limited".to_string(),
+        weight: 1.0,
+        steps: vec![Step {
+            name: "Limited".to_string(),
+            request: RequestConfig {
+                method: "GET".to_string(),
+                path: "/limited".to_string(),
+                body: None,
+                body_size: None,
+                headers: HashMap::new(),
+                protobuf: None,
+                host: None,
+            },
+            extractions: vec![],
+            assertions: vec![],
+            cache: None,
+            think_time: None,
+            tags: HashMap::new(),
+            paginate: None,
+            max_concurrent: None,
+            slo: None,
+            shared: vec![],
+            rendezvous: None,
+            conditional: false,
+            expected_status: Vec::new(),
+            patterns: Default::default(),
+            templates: Default::default(),
+            idempotency_key: None,
+            tcp_check: None,
+        }],
+        network_profile: None,
+        tags: HashMap::new(),
+        max_concurrent: None,
+        depends_on: Vec::new(),
+        iterations: None,
+        retry: Some(RetryPolicy {
+            count: 2,
+            delay: Duration::from_secs(30),
+            honor_retry_after: true,
+            max_retry_after: Duration::from_millis(50),
+        }),
+        assertion_sample_rate: 1.0,
+        client: None,
+        headers: Default::default(),
+        optional: false,
+        data_source: None,
+        rps: None,
+    };
+    let executor = ScenarioExecutor::new(
+        server.uri(),
+        create_test_client(),
+        "test-node".to_string(),
+        "run-0".to_string(),
+    );
+
+    let start = std::time::Instant::now();
+    let result = executor
+        .execute(
+            &scenario,
+            &mut ScenarioContext::new(),
+            &mut SessionStore::new(),
+        )
+        .await;
+    assert!(result.success);
+    assert!(start.elapsed() < Duration::from_secs(5));
+    // One result per step: the last attempt, counting the retry before it.
+    assert_eq!(result.steps.len(), 1);
+    assert_eq!(result.steps[0].status_code, Some(200));
+    assert_eq!(result.steps[0].retries, 1);
+    assert_eq!(server.received_requests().await.unwrap().len(), 2);
+}
+
+#[tokio::test]
+async fn test_idempotency_key_is_kept_across_retries() {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/orders"))
+        .and(header_exists("Idempotency-Key"))
+        .respond_with(ResponseTemplate::new(503))
+        .up_to_n_times(1)
+        .with_priority(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/orders"))
+        .and(header_exists("Idempotency-Key"))
+        .respond_with(ResponseTemplate::new(201))
+        .mount(&server)
+        .await;
+
+    let scenario = Scenario {
+        name: "Orders".to_string(),
+        weight: 1.0,
+        steps: vec![Step {
+            name: "Place Order".to_string(),
+            request: RequestConfig {
+                method: "POST".to_string(),
+                path: "/orders".to_string(),
+                body: Some(r#"{"requestId":"${idempotency_key}"}"#.to_string()),
+                body_size: None,
+                headers: HashMap::new(),
+                protobuf: None,
+                host: None,
+            },
+            extractions: vec![],
+            assertions: vec![],
+            cache: None,
+            think_time: None,
+            tags: HashMap::new(),
+            paginate: None,
+            max_concurrent: None,
+            slo: None,
+            shared: vec![],
+            rendezvous: None,
+            conditional: false,
+            expected_status: Vec::new(),
+            patterns: Default::default(),
+            templates: Default::default(),
+            idempotency_key: Some("Idempotency-Key".to_string()),
+            tcp_check: None,
+        }],
+        network_profile: None,
+        tags: HashMap::new(),
+        max_concurrent: None,
+        depends_on: Vec::new(),
+        iterations: None,
+        retry: Some(RetryPolicy {
+            count: 1,
+            delay: Duration::from_millis(10),
+            honor_retry_after: false,
+            max_retry_after: RetryPolicy::DEFAULT_MAX_RETRY_AFTER,
+        }),
+        assertion_sample_rate: 1.0,
+        client: None,
+        headers: Default::default(),
+        optional: false,
+        data_source: None,
+        rps: None,
+    };
+    let executor = ScenarioExecutor::new(
+        server.uri(),
+        create_test_client(),
+        "test-node".to_string(),
+        "run-0".to_string(),
+    );
+
+    for _ in 0..2 {
+        let result = executor
+            .execute(
+                &scenario,
+                &mut ScenarioContext::new(),
+                &mut SessionStore::new(),
+            )
+            .await;
+        assert!(result.success);
+    }
+
+    // The body echoes the key sent in the header (matched by the mocks).
+    let keys: Vec<String> = server
+        .received_requests()
+        .await
+        .unwrap()
+        .iter()
+        .map(|r| String::from_utf8(r.body.clone()).unwrap())
+        .collect();
+    assert_eq!(keys.len(), 3);
+    assert_eq!(keys[0], keys[1], "retry must resend the same key");
+    assert_ne!(keys[1], keys[2], "a new execution gets a new key");
+    assert_eq!(keys[0].len(), r#"{"requestId":""}"#.len() + 36);
+}
+
+fn status_step(name: &str, path: &str, expected_status: Vec<u16>) -> Step {
+    Step {
+        name: name.to_string(),
+        request: RequestConfig {
+            method: "GET".to_string(),
+            path: path.to_string(),
+            body: None,
+            body_size: None,
+            headers: HashMap::new(),
+            protobuf: None,
+            host: None,
+        },
+        extractions: vec![],
+        assertions: vec![],
+        cache: None,
+        think_time: None,
+        tags: HashMap::new(),
+        paginate: None,
+        max_concurrent: None,
+        slo: None,
+        shared: vec![],
+        rendezvous: None,
+        conditional: false,
+        expected_status,
+        patterns: Default::default(),
+        templates: Default::default(),
+        idempotency_key: None,
+        tcp_check: None,
+    }
+}
+
+/// A step listing `expectedStatus` succeeds on those codes only, so negative
+/// tests continue the scenario and unexpected successes fail it.
+#[tokio::test]
+async fn test_expected_status_codes() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/missing"))
+        .respond_with(ResponseTemplate::new(404))
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/ok"))
+        .respond_with(ResponseTemplate::new(200))
+        .mount(&server)
+        .await;
+
+    let scenario = Scenario {
+        name: "Negative".to_string(),
+        weight: 1.0,
+        steps: vec![
+            status_step("Missing", "/missing", vec![404]),
+            status_step("Ok", "/ok", Vec::new()),
+            status_step("Must Be Forbidden", "/ok", vec![401, 403]),
+        ],
+        network_profile: None,
+        tags: HashMap::new(),
+        max_concurrent: None,
+        depends_on: Vec::new(),
+        iterations: None,
+        retry: None,
+        assertion_sample_rate: 1.0,
+        client: None,
+        headers: Default::default(),
+        optional: false,
+        data_source: None,
+        rps: None,
+    };
+    let executor = ScenarioExecutor::new(
+        server.uri(),
+        create_test_client(),
+        "test-node".to_string(),
+        "run-0".to_string(),
+    );
+
+    let result = executor
+        .execute(
+            &scenario,
+            &mut ScenarioContext::new(),
+            &mut SessionStore::new(),
+        )
+        .await;
+    assert!(!result.success);
+    assert_eq!(result.failed_at_step, Some(2));
+    assert!(result.steps[0].success);
+    assert!(result.steps[1].success);
+    assert_eq!(
+        result.steps[2].error.as_deref(),
+        Some("HTTP 200 (expected [401, 403])")
+    );
+}
+
+/// Yields one user per row, then runs out.
+struct Users(std::sync::Mutex<Vec<&'static str>>);
+
+impl DataSourcePlugin for Users {
+    fn name(&self) -> &str {
+        "integration-users"
+    }
+
+    fn next_row(&self) -> Result<Option<HashMap<String, String>>, PluginError> {
+        let user = self.0.lock().unwrap().pop();
+        Ok(user.map(|user| HashMap::from([("user".to_string(), user.to_string())])))
+    }
+}
+
+#[tokio::test]
+async fn test_data_source_plugin_rows_become_variables() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/users/alice"))
+        .respond_with(ResponseTemplate::new(200))
+        .mount(&server)
+        .await;
+    GLOBAL_PLUGIN_REGISTRY
+        .write()
+        .unwrap()
+        .register_data_source(Arc::new(Users(std::sync::Mutex::new(vec!["alice"]))));
+
+    let scenario = Scenario {
+        name: "Data Driven".to_string(),
+        weight: 1.0,
+        steps: vec![Step {
+            name: "Profile".to_string(),
+            request: RequestConfig {
+                method: "GET".to_string(),
+                path: "/users/${user}".to_string(),
+                ..Default::default()
+            },
+            ..Default::default()
+        }],
+        data_source: Some("integration-users".to_string()),
+        ..Default::default()
+    };
+    let executor = ScenarioExecutor::new(
+        server.uri(),
+        create_test_client(),
+        "test-node".to_string(),
+        "run-0".to_string(),
+    );
+
+    let result = executor
+        .execute(
+            &scenario,
+            &mut ScenarioContext::new(),
+            &mut SessionStore::new(),
+        )
+        .await;
+    assert!(result.success, "{:?}", result.steps[0].error);
+    assert_eq!(result.steps[0].status_code, Some(200));
+
+    // An exhausted source fails the iteration before any request.
+    let result = executor
+        .execute(
+            &scenario,
+            &mut ScenarioContext::new(),
+            &mut SessionStore::new(),
+        )
+        .await;
+    assert!(!result.success);
+    assert!(result.steps.is_empty());
+    assert_eq!(result.failed_at_step, Some(0));
+
+    let unknown = Scenario {
+        data_source: Some("missing".to_string()),
+        ..scenario
+    };
+    let result = executor
+        .execute(
+            &unknown,
+            &mut ScenarioContext::new(),
+            &mut SessionStore::new(),
+        )
+        .await;
+    assert!(!result.success);
+    assert_eq!(server.received_requests().await.unwrap().len(), 1);
+}
@@ -3,9 +3,10 @@
 //! These tests validate that the scenario worker correctly executes scenarios
 //! according to load models and respects timing constraints.
 
+use rust_loadtest::ip_family::IpFamily;
 use rust_loadtest::load_models::LoadModel;
 use rust_loadtest::scenario::{RequestConfig, Scenario, Step, ThinkTime};
-use rust_loadtest::worker::{run_scenario_worker, ScenarioWorkerConfig};
+use rust_loadtest::worker::{run_scenario_worker, ScenarioWorkerConfig, WorkerPacing};
 use std::collections::HashMap;
 use std::time::Duration;
 use tokio::time::Instant;
@@ -23,12 +24,38 @@ async fn test_scenario_worker_respects_duration() {
                 body: None,
                 body_size: None,
                 headers: HashMap::new(),
+                protobuf: None,
+                host: None,
             },
             extractions: vec![],
             assertions: vec![],
             cache: None,
             think_time: None,
+            tags: HashMap::new(),
+            paginate: None,
+            max_concurrent: None,
+            slo: None,
+            shared: vec![],
+            rendezvous: None,
+            conditional: false,
+            expected_status: Vec::new(),
+            patterns: Default::default(),
+            templates: Default::default(),
+            idempotency_key: None,
+            tcp_check: None,
         }],
+        network_profile: None,
+        tags: HashMap::new(),
+        max_concurrent: None,
+        depends_on: Vec::new(),
+        iterations: None,
+        retry: None,
+        assertion_sample_rate: 1.0,
+        client: None,
+        headers: Default::default(),
+        optional: false,
+        data_source: None,
+        rps: None,
     };
 
     let config = ScenarioWorkerConfig {
@@ -41,11 +68,19 @@ async fn test_scenario_worker_respects_duration() {
         percentile_tracking_enabled: true,
         percentile_sampling_rate: 100,
         region: "local".to_string(),
+        zone: "local".to_string(),
         tenant: String::new(),
         node_id: "test-node".to_string(),
         run_id: "run-0".to_string(),
         skip_tls_verify: false,
         resolve_target_addr: None,
+        local_address: None,
+        target_addr: None,
+        ip_family: IpFamily::Auto,
+        pacing: WorkerPacing::default(),
+        tls_session_resumption: true,
+        sni_override: None,
+        iteration_budget: None,
     };
 
     let start_time = Instant::now();
@@ -76,12 +111,38 @@ async fn test_scenario_worker_constant_load() {
                 body: None,
                 body_size: None,
                 headers: HashMap::new(),
+                protobuf: None,
+                host: None,
             },
             extractions: vec![],
             assertions: vec![],
             cache: None,
             think_time: None,
+            tags: HashMap::new(),
+            paginate: None,
+            max_concurrent: None,
+            slo: None,
+            shared: vec![],
+            rendezvous: None,
+            conditional: false,
+            expected_status: Vec::new(),
+            patterns: Default::default(),
+            templates: Default::default(),
+            idempotency_key: None,
+            tcp_check: None,
         }],
+        network_profile: None,
+        tags: HashMap::new(),
+        max_concurrent: None,
+        depends_on: Vec::new(),
+        iterations: None,
+        retry: None,
+        assertion_sample_rate: 1.0,
+        client: None,
+        headers: Default::default(),
+        optional: false,
+        data_source: None,
+        rps: None,
     };
 
     // Run at 2 scenarios per second for 3 seconds
@@ -96,11 +157,19 @@ async fn test_scenario_worker_constant_load() {
         percentile_tracking_enabled: true,
         percentile_sampling_rate: 100,
         region: "local".to_string(),
+        zone: "local".to_string(),
         tenant: String::new(),
         node_id: "test-node".to_string(),
         run_id: "run-0".to_string(),
         skip_tls_verify: false,
         resolve_target_addr: None,
+        local_address: None,
+        target_addr: None,
+        ip_family: IpFamily::Auto,
+        pacing: WorkerPacing::default(),
+        tls_session_resumption: true,
+        sni_override: None,
+        iteration_budget: None,
     };
 
     let start_time = Instant::now();
@@ -125,11 +194,25 @@ async fn test_scenario_worker_with_think_time() {
                     body: None,
                     body_size: None,
                     headers: HashMap::new(),
+                    protobuf: None,
+                    host: None,
                 },
                 extractions: vec![],
                 assertions: vec![],
                 cache: None,
                 think_time: Some(ThinkTime::Fixed(Duration::from_millis(500))),
+                tags: HashMap::new(),
+                paginate: None,
+                max_concurrent: None,
+                slo: None,
+                shared: vec![],
+                rendezvous: None,
+                conditional: false,
+                expected_status: Vec::new(),
+                patterns: Default::default(),
+                templates: Default::default(),
+                idempotency_key: None,
+                tcp_check: None,
             },
             Step {
                 name: "Step 2".to_string(),
@@ -139,13 +222,39 @@ async fn test_scenario_worker_with_think_time() {
                     body: None,
                     body_size: None,
                     headers: HashMap::new(),
+                    protobuf: None,
+                    host: None,
                 },
                 extractions: vec![],
                 assertions: vec![],
                 cache: None,
                 think_time: None,
+                tags: HashMap::new(),
+                paginate: None,
+                max_concurrent: None,
+                slo: None,
+                shared: vec![],
+                rendezvous: None,
+                conditional: false,
+                expected_status: Vec::new(),
+                patterns: Default::default(),
+                templates: Default::default(),
+                idempotency_key: None,
+                tcp_check: None,
             },
         ],
+        network_profile: None,
+        tags: HashMap::new(),
+        max_concurrent: None,
+        depends_on: Vec::new(),
+        iterations: None,
+        retry: None,
+        assertion_sample_rate: 1.0,
+        client: None,
+        headers: Default::default(),
+        optional: false,
+        data_source: None,
+        rps: None,
     };
 
     let config = ScenarioWorkerConfig {
@@ -158,11 +267,19 @@ async fn test_scenario_worker_with_think_time() {
         percentile_tracking_enabled: true,
         percentile_sampling_rate: 100,
         region: "local".to_string(),
+        zone: "local".to_string(),
         tenant: String::new(),
         node_id: "test-node".to_string(),
         run_id: "run-0".to_string(),
         skip_tls_verify: false,
         resolve_target_addr: None,
+        local_address: None,
+        target_addr: None,
+        ip_family: IpFamily::Auto,
+        pacing: WorkerPacing::default(),
+        tls_session_resumption: true,
+        sni_override: None,
+        iteration_budget: None,
     };
 
     let start_time = Instant::now();
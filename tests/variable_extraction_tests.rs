@@ -6,6 +6,7 @@
 use rust_loadtest::executor::{ScenarioExecutor, SessionStore};
 use rust_loadtest::scenario::{
     Extractor, RequestConfig, Scenario, ScenarioContext, Step, ThinkTime, VariableExtraction,
+    VariableScope,
 };
 use std::collections::HashMap;
 use std::time::Duration;
@@ -33,21 +34,51 @@ async fn test_jsonpath_extraction_from_products() {
                 body: None,
                 body_size: None,
                 headers: HashMap::new(),
+                protobuf: None,
+                host: None,
             },
             extractions: vec![
                 VariableExtraction {
                     name: "author".to_string(),
                     extractor: Extractor::JsonPath("$.slideshow.author".to_string()),
+                    transforms: Vec::new(),
+                    scope: VariableScope::Iteration,
                 },
                 VariableExtraction {
                     name: "title".to_string(),
                     extractor: Extractor::JsonPath("$.slideshow.title".to_string()),
+                    transforms: Vec::new(),
+                    scope: VariableScope::Iteration,
                 },
             ],
             assertions: vec![],
             cache: None,
             think_time: None,
+            tags: HashMap::new(),
+            paginate: None,
+            max_concurrent: None,
+            slo: None,
+            shared: vec![],
+            rendezvous: None,
+            conditional: false,
+            expected_status: Vec::new(),
+            patterns: Default::default(),
+            templates: Default::default(),
+            idempotency_key: None,
+            tcp_check: None,
         }],
+        network_profile: None,
+        tags: HashMap::new(),
+        max_concurrent: None,
+        depends_on: Vec::new(),
+        iterations: None,
+        retry: None,
+        assertion_sample_rate: 1.0,
+        client: None,
+        headers: Default::default(),
+        optional: false,
+        data_source: None,
+        rps: None,
     };
 
     let client = create_test_client();
@@ -94,14 +125,30 @@ async fn test_extraction_and_reuse_in_next_step() {
                     body: None,
                     body_size: None,
                     headers: HashMap::new(),
+                    protobuf: None,
+                    host: None,
                 },
                 extractions: vec![VariableExtraction {
                     name: "origin_ip".to_string(),
                     extractor: Extractor::JsonPath("$.origin".to_string()),
+                    transforms: Vec::new(),
+                    scope: VariableScope::Iteration,
                 }],
                 assertions: vec![],
                 cache: None,
                 think_time: Some(ThinkTime::Fixed(Duration::from_millis(100))),
+                tags: HashMap::new(),
+                paginate: None,
+                max_concurrent: None,
+                slo: None,
+                shared: vec![],
+                rendezvous: None,
+                conditional: false,
+                expected_status: Vec::new(),
+                patterns: Default::default(),
+                templates: Default::default(),
+                idempotency_key: None,
+                tcp_check: None,
             },
             Step {
                 name: "Use Extracted Value".to_string(),
@@ -111,13 +158,39 @@ async fn test_extraction_and_reuse_in_next_step() {
                     body: None,
                     body_size: None,
                     headers: HashMap::new(),
+                    protobuf: None,
+                    host: None,
                 },
                 extractions: vec![],
                 assertions: vec![],
                 cache: None,
                 think_time: None,
+                tags: HashMap::new(),
+                paginate: None,
+                max_concurrent: None,
+                slo: None,
+                shared: vec![],
+                rendezvous: None,
+                conditional: false,
+                expected_status: Vec::new(),
+                patterns: Default::default(),
+                templates: Default::default(),
+                idempotency_key: None,
+                tcp_check: None,
             },
         ],
+        network_profile: None,
+        tags: HashMap::new(),
+        max_concurrent: None,
+        depends_on: Vec::new(),
+        iterations: None,
+        retry: None,
+        assertion_sample_rate: 1.0,
+        client: None,
+        headers: Default::default(),
+        optional: false,
+        data_source: None,
+        rps: None,
     };
 
     let client = create_test_client();
@@ -163,15 +236,43 @@ async fn test_header_extraction() {
                 body: None,
                 body_size: None,
                 headers: HashMap::new(),
+                protobuf: None,
+                host: None,
             },
             extractions: vec![VariableExtraction {
                 name: "content_type".to_string(),
                 extractor: Extractor::Header("content-type".to_string()),
+                transforms: Vec::new(),
+                scope: VariableScope::Iteration,
             }],
             assertions: vec![],
             cache: None,
             think_time: None,
+            tags: HashMap::new(),
+            paginate: None,
+            max_concurrent: None,
+            slo: None,
+            shared: vec![],
+            rendezvous: None,
+            conditional: false,
+            expected_status: Vec::new(),
+            patterns: Default::default(),
+            templates: Default::default(),
+            idempotency_key: None,
+            tcp_check: None,
         }],
+        network_profile: None,
+        tags: HashMap::new(),
+        max_concurrent: None,
+        depends_on: Vec::new(),
+        iterations: None,
+        retry: None,
+        assertion_sample_rate: 1.0,
+        client: None,
+        headers: Default::default(),
+        optional: false,
+        data_source: None,
+        rps: None,
     };
 
     let client = create_test_client();
@@ -216,25 +317,57 @@ async fn test_multiple_extractions_in_single_step() {
                 body: None,
                 body_size: None,
                 headers: HashMap::new(),
+                protobuf: None,
+                host: None,
             },
             extractions: vec![
                 VariableExtraction {
                     name: "author".to_string(),
                     extractor: Extractor::JsonPath("$.slideshow.author".to_string()),
+                    transforms: Vec::new(),
+                    scope: VariableScope::Iteration,
                 },
                 VariableExtraction {
                     name: "title".to_string(),
                     extractor: Extractor::JsonPath("$.slideshow.title".to_string()),
+                    transforms: Vec::new(),
+                    scope: VariableScope::Iteration,
                 },
                 VariableExtraction {
                     name: "content_type".to_string(),
                     extractor: Extractor::Header("content-type".to_string()),
+                    transforms: Vec::new(),
+                    scope: VariableScope::Iteration,
                 },
             ],
             assertions: vec![],
             cache: None,
             think_time: None,
+            tags: HashMap::new(),
+            paginate: None,
+            max_concurrent: None,
+            slo: None,
+            shared: vec![],
+            rendezvous: None,
+            conditional: false,
+            expected_status: Vec::new(),
+            patterns: Default::default(),
+            templates: Default::default(),
+            idempotency_key: None,
+            tcp_check: None,
         }],
+        network_profile: None,
+        tags: HashMap::new(),
+        max_concurrent: None,
+        depends_on: Vec::new(),
+        iterations: None,
+        retry: None,
+        assertion_sample_rate: 1.0,
+        client: None,
+        headers: Default::default(),
+        optional: false,
+        data_source: None,
+        rps: None,
     };
 
     let client = create_test_client();
@@ -287,14 +420,30 @@ async fn test_shopping_flow_with_extraction() {
                     body: None,
                     body_size: None,
                     headers: HashMap::new(),
+                    protobuf: None,
+                    host: None,
                 },
                 extractions: vec![VariableExtraction {
                     name: "author".to_string(),
                     extractor: Extractor::JsonPath("$.slideshow.author".to_string()),
+                    transforms: Vec::new(),
+                    scope: VariableScope::Iteration,
                 }],
                 assertions: vec![],
                 cache: None,
                 think_time: Some(ThinkTime::Fixed(Duration::from_millis(500))),
+                tags: HashMap::new(),
+                paginate: None,
+                max_concurrent: None,
+                slo: None,
+                shared: vec![],
+                rendezvous: None,
+                conditional: false,
+                expected_status: Vec::new(),
+                patterns: Default::default(),
+                templates: Default::default(),
+                idempotency_key: None,
+                tcp_check: None,
             },
             Step {
                 name: "Post Data with Extracted Value".to_string(),
@@ -314,14 +463,30 @@ async fn test_shopping_flow_with_extraction() {
                         headers.insert("Content-Type".to_string(), "application/json".to_string());
                         headers
                     },
+                    protobuf: None,
+                    host: None,
                 },
                 extractions: vec![VariableExtraction {
                     name: "post_url".to_string(),
                     extractor: Extractor::JsonPath("$.url".to_string()),
+                    transforms: Vec::new(),
+                    scope: VariableScope::Iteration,
                 }],
                 assertions: vec![],
                 cache: None,
                 think_time: Some(ThinkTime::Fixed(Duration::from_millis(500))),
+                tags: HashMap::new(),
+                paginate: None,
+                max_concurrent: None,
+                slo: None,
+                shared: vec![],
+                rendezvous: None,
+                conditional: false,
+                expected_status: Vec::new(),
+                patterns: Default::default(),
+                templates: Default::default(),
+                idempotency_key: None,
+                tcp_check: None,
             },
             Step {
                 name: "Final GET".to_string(),
@@ -331,16 +496,44 @@ async fn test_shopping_flow_with_extraction() {
                     body: None,
                     body_size: None,
                     headers: HashMap::new(),
+                    protobuf: None,
+                    host: None,
                 },
                 extractions: vec![VariableExtraction {
                     name: "final_origin".to_string(),
                     extractor: Extractor::JsonPath("$.origin".to_string()),
+                    transforms: Vec::new(),
+                    scope: VariableScope::Iteration,
                 }],
                 assertions: vec![],
                 cache: None,
                 think_time: None,
+                tags: HashMap::new(),
+                paginate: None,
+                max_concurrent: None,
+                slo: None,
+                shared: vec![],
+                rendezvous: None,
+                conditional: false,
+                expected_status: Vec::new(),
+                patterns: Default::default(),
+                templates: Default::default(),
+                idempotency_key: None,
+                tcp_check: None,
             },
         ],
+        network_profile: None,
+        tags: HashMap::new(),
+        max_concurrent: None,
+        depends_on: Vec::new(),
+        iterations: None,
+        retry: None,
+        assertion_sample_rate: 1.0,
+        client: None,
+        headers: Default::default(),
+        optional: false,
+        data_source: None,
+        rps: None,
     };
 
     let client = create_test_client();
@@ -386,20 +579,38 @@ async fn test_extraction_failure_doesnt_stop_scenario() {
                     body: None,
                     body_size: None,
                     headers: HashMap::new(),
+                    protobuf: None,
+                    host: None,
                 },
                 extractions: vec![
                     VariableExtraction {
                         name: "author".to_string(),
                         extractor: Extractor::JsonPath("$.slideshow.author".to_string()),
+                        transforms: Vec::new(),
+                        scope: VariableScope::Iteration,
                     },
                     VariableExtraction {
                         name: "nonexistent".to_string(),
                         extractor: Extractor::JsonPath("$.does.not.exist".to_string()),
+                        transforms: Vec::new(),
+                        scope: VariableScope::Iteration,
                     },
                 ],
                 assertions: vec![],
                 cache: None,
                 think_time: None,
+                tags: HashMap::new(),
+                paginate: None,
+                max_concurrent: None,
+                slo: None,
+                shared: vec![],
+                rendezvous: None,
+                conditional: false,
+                expected_status: Vec::new(),
+                patterns: Default::default(),
+                templates: Default::default(),
+                idempotency_key: None,
+                tcp_check: None,
             },
             Step {
                 name: "Next Step".to_string(),
@@ -409,13 +620,39 @@ async fn test_extraction_failure_doesnt_stop_scenario() {
                     body: None,
                     body_size: None,
                     headers: HashMap::new(),
+                    protobuf: None,
+                    host: None,
                 },
                 extractions: vec![],
                 assertions: vec![],
                 cache: None,
                 think_time: None,
+                tags: HashMap::new(),
+                paginate: None,
+                max_concurrent: None,
+                slo: None,
+                shared: vec![],
+                rendezvous: None,
+                conditional: false,
+                expected_status: Vec::new(),
+                patterns: Default::default(),
+                templates: Default::default(),
+                idempotency_key: None,
+                tcp_check: None,
             },
         ],
+        network_profile: None,
+        tags: HashMap::new(),
+        max_concurrent: None,
+        depends_on: Vec::new(),
+        iterations: None,
+        retry: None,
+        assertion_sample_rate: 1.0,
+        client: None,
+        headers: Default::default(),
+        optional: false,
+        data_source: None,
+        rps: None,
     };
 
     let client = create_test_client();
@@ -157,6 +157,7 @@ scenarios:
             min_rps,
             max_rps,
             ramp_duration,
+            ..
         } => {
             assert_eq!(min_rps, 10.0);
             assert_eq!(max_rps, 100.0);